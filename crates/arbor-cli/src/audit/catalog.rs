@@ -0,0 +1,832 @@
+//! Source, sink and sanitizer rules for taint-style audits.
+//!
+//! A rule matches a function in three ways:
+//!   - `calls`: a call in its body, by callee text. `execute` matches
+//!     `execute(...)`, `cursor.execute(...)` and `db::execute(...)`.
+//!     Calls that resolve to a function defined in the repository are
+//!     skipped: a local `def execute(cmd)` is not the database driver.
+//!   - `params`: a word in its signature, such as `HttpServletRequest` or
+//!     `@RequestBody`, for frameworks that hand request data to handlers.
+//!   - `names`: its own name, ignoring case (`sanitize*`). For sanitizers,
+//!     calling a function with a matching name counts too.
+//!
+//! Patterns may use `*` as a wildcard. Rules apply to the file extensions
+//! they list, or to every file when the list is empty.
+//!
+//! The built-in catalog covers common HTTP, environment, stdin and
+//! deserialization sources, SQL, shell, eval and file-path sinks, and the
+//! escaping helpers that neutralize them. Projects extend it in
+//! `.arbor/security.toml`:
+//!
+//! ```toml
+//! # builtin = false   # use only the rules below
+//!
+//! [[sources]]
+//! id = "tenant-header"
+//! category = "http"
+//! extensions = ["py"]
+//! calls = ["get_tenant_header"]
+//!
+//! [[sinks]]
+//! id = "legacy-query"
+//! category = "sql"
+//! names = ["run_legacy_query"]
+//!
+//! [[sanitizers]]
+//! id = "tenant-validator"
+//! names = ["validate_tenant*"]
+//! ```
+
+use anyhow::{Context, Result};
+use arbor_core::CodeNode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Project rules, relative to the project root.
+pub const SECURITY_CONFIG: &str = ".arbor/security.toml";
+
+/// One source, sink or sanitizer rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Rule {
+    pub id: String,
+    /// Groups rules in reports: `http`, `env`, `sql`, `command`, ...
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub extensions: Vec<String>,
+    #[serde(default)]
+    pub calls: Vec<String>,
+    #[serde(default)]
+    pub params: Vec<String>,
+    #[serde(default)]
+    pub names: Vec<String>,
+}
+
+/// Why a function matched a rule.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct RuleMatch {
+    pub rule: String,
+    pub category: String,
+    /// The call, signature word or name that matched.
+    pub matched: String,
+}
+
+impl RuleMatch {
+    /// How a source takes in untrusted input, for trace output.
+    pub fn source_action(&self) -> String {
+        if self.rule == "route" {
+            "receives HTTP requests".to_string()
+        } else {
+            format!("reads {}", self.matched)
+        }
+    }
+}
+
+/// Contents of `.arbor/security.toml`.
+#[derive(Debug, Default, Deserialize)]
+struct SecurityConfig {
+    #[serde(default = "default_true")]
+    builtin: bool,
+    #[serde(default)]
+    sources: Vec<Rule>,
+    #[serde(default)]
+    sinks: Vec<Rule>,
+    #[serde(default)]
+    sanitizers: Vec<Rule>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// The rules an audit runs with.
+#[derive(Debug, Clone, Default)]
+pub struct SecurityCatalog {
+    pub sources: Vec<Rule>,
+    pub sinks: Vec<Rule>,
+    pub sanitizers: Vec<Rule>,
+}
+
+impl SecurityCatalog {
+    /// The built-in rules.
+    pub fn builtin() -> Self {
+        Self {
+            sources: SOURCES.iter().map(BuiltinRule::to_rule).collect(),
+            sinks: SINKS.iter().map(BuiltinRule::to_rule).collect(),
+            sanitizers: SANITIZERS.iter().map(BuiltinRule::to_rule).collect(),
+        }
+    }
+
+    /// The built-in rules plus the project's `.arbor/security.toml`, if any.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(SECURITY_CONFIG);
+        if !path.exists() {
+            return Ok(Self::builtin());
+        }
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_config(&text).with_context(|| format!("Invalid {}", path.display()))
+    }
+
+    fn from_config(text: &str) -> Result<Self> {
+        let config: SecurityConfig = toml::from_str(text)?;
+        let mut catalog = if config.builtin {
+            Self::builtin()
+        } else {
+            Self::default()
+        };
+        catalog.sources.extend(config.sources);
+        catalog.sinks.extend(config.sinks);
+        catalog.sanitizers.extend(config.sanitizers);
+        Ok(catalog)
+    }
+
+    /// The source rule `node` matches. `local` holds the calls in its body
+    /// that resolve to functions in the repository.
+    pub fn source(&self, node: &CodeNode, local: &HashSet<&str>) -> Option<RuleMatch> {
+        first_match(&self.sources, node, local)
+    }
+
+    /// The sink rule `node` matches; `local` as for [`Self::source`].
+    pub fn sink(&self, node: &CodeNode, local: &HashSet<&str>) -> Option<RuleMatch> {
+        first_match(&self.sinks, node, local)
+    }
+
+    /// The sanitizer rule `node` matches, or calls a function named by;
+    /// `local` as for [`Self::source`].
+    pub fn sanitizer(&self, node: &CodeNode, local: &HashSet<&str>) -> Option<RuleMatch> {
+        first_match(&self.sanitizers, node, local).or_else(|| {
+            self.sanitizers
+                .iter()
+                .find_map(|rule| rule.calls_named(node))
+        })
+    }
+}
+
+fn first_match(rules: &[Rule], node: &CodeNode, local: &HashSet<&str>) -> Option<RuleMatch> {
+    rules.iter().find_map(|rule| rule.matches(node, local))
+}
+
+impl Rule {
+    /// How `node` matches this rule, if it does. Calls in `local` resolve
+    /// to the repository's own functions and never match `calls`.
+    pub fn matches(&self, node: &CodeNode, local: &HashSet<&str>) -> Option<RuleMatch> {
+        if !self.applies_to(&node.file) {
+            return None;
+        }
+        let found = |matched: &str| RuleMatch {
+            rule: self.id.clone(),
+            category: self.category.clone(),
+            matched: matched.to_string(),
+        };
+
+        for reference in &node.references {
+            if local.contains(reference.as_str()) {
+                continue;
+            }
+            if self.calls.iter().any(|p| call_matches(p, reference)) {
+                return Some(found(reference));
+            }
+        }
+        if let Some(signature) = &node.signature {
+            if let Some(param) = self.params.iter().find(|p| contains_word(signature, p)) {
+                return Some(found(param));
+            }
+        }
+        let name = node.name.to_lowercase();
+        if self
+            .names
+            .iter()
+            .any(|p| glob_matches(&p.to_lowercase(), &name))
+        {
+            return Some(found(&node.name));
+        }
+        None
+    }
+
+    /// A call to a function this rule matches by name.
+    fn calls_named(&self, node: &CodeNode) -> Option<RuleMatch> {
+        if self.names.is_empty() || !self.applies_to(&node.file) {
+            return None;
+        }
+        node.references.iter().find_map(|reference| {
+            let callee = reference
+                .rsplit(['.', ':', '>'])
+                .next()
+                .unwrap_or(reference)
+                .to_lowercase();
+            self.names
+                .iter()
+                .any(|p| glob_matches(&p.to_lowercase(), &callee))
+                .then(|| RuleMatch {
+                    rule: self.id.clone(),
+                    category: self.category.clone(),
+                    matched: reference.clone(),
+                })
+        })
+    }
+
+    fn applies_to(&self, file: &str) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        Path::new(file)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+}
+
+/// A call matches when the pattern covers its whole text or its last
+/// path segments: `execute` matches `self.db.execute`, not `executed`.
+fn call_matches(pattern: &str, call: &str) -> bool {
+    let call = call.trim();
+    if glob_matches(pattern, call) {
+        return true;
+    }
+    [".", "::", "->"].iter().any(|sep| {
+        call.match_indices(sep)
+            .any(|(i, _)| glob_matches(pattern, &call[i + sep.len()..]))
+    })
+}
+
+/// True if `word` appears in `text` with no identifier characters on
+/// either side.
+fn contains_word(text: &str, word: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(word).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+/// Matches `text` against a pattern where `*` stands for any run of
+/// characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// A rule compiled into the binary.
+struct BuiltinRule {
+    id: &'static str,
+    category: &'static str,
+    extensions: &'static [&'static str],
+    calls: &'static [&'static str],
+    params: &'static [&'static str],
+    names: &'static [&'static str],
+}
+
+impl BuiltinRule {
+    fn to_rule(&self) -> Rule {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Rule {
+            id: self.id.to_string(),
+            category: self.category.to_string(),
+            extensions: owned(self.extensions),
+            calls: owned(self.calls),
+            params: owned(self.params),
+            names: owned(self.names),
+        }
+    }
+}
+
+const PY: &[&str] = &["py"];
+const RS: &[&str] = &["rs"];
+const JS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx", "vue", "svelte"];
+const GO: &[&str] = &["go"];
+const JVM: &[&str] = &["java", "kt", "kts", "scala"];
+const CS: &[&str] = &["cs"];
+const PHP: &[&str] = &["php", "phtml"];
+const RB: &[&str] = &["rb"];
+const C: &[&str] = &["c", "h", "cpp", "hpp", "cc", "cxx", "hh"];
+
+const NONE: &[&str] = &[];
+
+const SOURCES: &[BuiltinRule] = &[
+    BuiltinRule {
+        id: "python-http",
+        category: "http",
+        extensions: PY,
+        calls: &[
+            "request.args.get",
+            "request.form.get",
+            "request.values.get",
+            "request.cookies.get",
+            "request.headers.get",
+            "request.get_json",
+            "request.GET.get",
+            "request.POST.get",
+        ],
+        params: &["Query", "Form", "Body", "Request", "HttpRequest"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "rust-http",
+        category: "http",
+        extensions: RS,
+        calls: NONE,
+        params: &["Query", "Form", "Json", "HttpRequest", "Multipart"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "js-http",
+        category: "http",
+        extensions: JS,
+        calls: &["req.param", "req.get", "req.header", "searchParams.get"],
+        params: &["req", "request", "Request", "FastifyRequest"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "go-http",
+        category: "http",
+        extensions: GO,
+        calls: &[
+            "r.FormValue",
+            "r.URL.Query",
+            "c.Query",
+            "c.Param",
+            "c.PostForm",
+            "c.BindJSON",
+            "c.ShouldBindJSON",
+        ],
+        params: &["http.Request", "gin.Context", "echo.Context", "fiber.Ctx"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "jvm-http",
+        category: "http",
+        extensions: JVM,
+        calls: &[
+            "getParameter",
+            "getHeader",
+            "getQueryString",
+            "getInputStream",
+        ],
+        params: &[
+            "@RequestParam",
+            "@RequestBody",
+            "@PathVariable",
+            "@RequestHeader",
+            "@QueryParam",
+            "@PathParam",
+            "HttpServletRequest",
+        ],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "dotnet-http",
+        category: "http",
+        extensions: CS,
+        calls: &["Request.Query", "Request.Form", "ReadFromJsonAsync"],
+        params: &[
+            "FromQuery",
+            "FromBody",
+            "FromForm",
+            "FromRoute",
+            "FromHeader",
+            "HttpRequest",
+        ],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "php-http",
+        category: "http",
+        extensions: PHP,
+        calls: &[
+            "filter_input",
+            "$request->input",
+            "$request->get",
+            "$request->query",
+        ],
+        params: &["Request"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "ruby-http",
+        category: "http",
+        extensions: RB,
+        calls: &[
+            "params.require",
+            "params.permit",
+            "params.fetch",
+            "request.body.read",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "env",
+        category: "env",
+        extensions: NONE,
+        calls: &[
+            "os.getenv",
+            "os.environ.get",
+            "env::var",
+            "env::var_os",
+            "os.Getenv",
+            "os.LookupEnv",
+            "System.getenv",
+            "Environment.GetEnvironmentVariable",
+            "getenv",
+            "ENV.fetch",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "stdin",
+        category: "stdin",
+        extensions: NONE,
+        calls: &[
+            "input",
+            "sys.stdin.read",
+            "sys.stdin.readline",
+            "io::stdin",
+            "stdin().read_line",
+            "fmt.Scan",
+            "fmt.Scanln",
+            "fmt.Scanf",
+            "readLine",
+            "Console.ReadLine",
+            "gets",
+            "fgets",
+            "scanf",
+            "readline.createInterface",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "c-args",
+        category: "stdin",
+        extensions: C,
+        calls: &["getline"],
+        params: &["argv"],
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "deserialization",
+        category: "deserialization",
+        extensions: NONE,
+        calls: &[
+            "pickle.loads",
+            "pickle.load",
+            "yaml.load",
+            "marshal.loads",
+            "json.loads",
+            "serde_json::from_str",
+            "serde_json::from_slice",
+            "serde_yaml::from_str",
+            "bincode::deserialize",
+            "JSON.parse",
+            "json.Unmarshal",
+            "yaml.Unmarshal",
+            "readObject",
+            "JsonSerializer.Deserialize",
+            "JsonConvert.DeserializeObject",
+            "unserialize",
+            "Marshal.load",
+            "YAML.load",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+];
+
+const SINKS: &[BuiltinRule] = &[
+    BuiltinRule {
+        id: "python-sql",
+        category: "sql",
+        extensions: PY,
+        calls: &["execute", "executemany", "executescript", "objects.raw"],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "rust-sql",
+        category: "sql",
+        extensions: RS,
+        calls: &[
+            "sqlx::query",
+            "sqlx::query_as",
+            "execute",
+            "batch_execute",
+            "sql_query",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "js-sql",
+        category: "sql",
+        extensions: JS,
+        calls: &[
+            "query",
+            "execute",
+            "raw",
+            "$queryRawUnsafe",
+            "$executeRawUnsafe",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "go-sql",
+        category: "sql",
+        extensions: GO,
+        calls: &[
+            "db.Exec",
+            "db.Query",
+            "db.QueryRow",
+            "tx.Exec",
+            "tx.Query",
+            "ExecContext",
+            "QueryContext",
+            "QueryRowContext",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "jvm-sql",
+        category: "sql",
+        extensions: JVM,
+        calls: &[
+            "executeQuery",
+            "executeUpdate",
+            "createNativeQuery",
+            "createQuery",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "dotnet-sql",
+        category: "sql",
+        extensions: CS,
+        calls: &[
+            "ExecuteSqlRaw",
+            "FromSqlRaw",
+            "ExecuteReader",
+            "ExecuteNonQuery",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "php-sql",
+        category: "sql",
+        extensions: PHP,
+        calls: &[
+            "mysqli_query",
+            "mysql_query",
+            "pg_query",
+            "$pdo->query",
+            "$pdo->exec",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "ruby-sql",
+        category: "sql",
+        extensions: RB,
+        calls: &["find_by_sql", "connection.execute", "exec_query"],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "command",
+        category: "command",
+        extensions: NONE,
+        calls: &[
+            "os.system",
+            "os.popen",
+            "subprocess.run",
+            "subprocess.call",
+            "subprocess.Popen",
+            "subprocess.check_output",
+            "Command::new",
+            "child_process.exec",
+            "child_process.spawn",
+            "execSync",
+            "spawnSync",
+            "exec.Command",
+            "exec.CommandContext",
+            "Runtime.getRuntime().exec",
+            "ProcessBuilder",
+            "Process.Start",
+            "shell_exec",
+            "passthru",
+            "proc_open",
+            "IO.popen",
+            "popen",
+            "system",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "eval",
+        category: "eval",
+        extensions: NONE,
+        calls: &[
+            "eval",
+            "vm.runInContext",
+            "vm.runInNewContext",
+            "instance_eval",
+            "class_eval",
+            "ScriptEngine.eval",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+    BuiltinRule {
+        id: "file-path",
+        category: "path",
+        extensions: NONE,
+        calls: &[
+            "open",
+            "os.remove",
+            "shutil.rmtree",
+            "send_file",
+            "File::open",
+            "File::create",
+            "fs::read_to_string",
+            "fs::write",
+            "fs::remove_file",
+            "fs.readFile",
+            "fs.readFileSync",
+            "fs.writeFile",
+            "fs.createReadStream",
+            "res.sendFile",
+            "os.Open",
+            "os.Create",
+            "os.ReadFile",
+            "os.Remove",
+            "Files.readAllBytes",
+            "Files.newInputStream",
+            "File.ReadAllText",
+            "File.Open",
+            "File.Delete",
+            "file_get_contents",
+            "fopen",
+            "unlink",
+            "File.read",
+        ],
+        params: NONE,
+        names: NONE,
+    },
+];
+
+const SANITIZERS: &[BuiltinRule] = &[
+    BuiltinRule {
+        id: "escaping",
+        category: "escape",
+        extensions: NONE,
+        calls: &[
+            "html.escape",
+            "markupsafe.escape",
+            "bleach.clean",
+            "shlex.quote",
+            "secure_filename",
+            "os.path.basename",
+            "html_escape::encode_text",
+            "shell_escape::escape",
+            "ammonia::clean",
+            "validator.escape",
+            "DOMPurify.sanitize",
+            "sqlstring.escape",
+            "encodeURIComponent",
+            "path.basename",
+            "html.EscapeString",
+            "template.HTMLEscapeString",
+            "url.QueryEscape",
+            "filepath.Base",
+            "StringEscapeUtils.escapeHtml4",
+            "Encode.forHtml",
+            "FilenameUtils.getName",
+            "WebUtility.HtmlEncode",
+            "HtmlEncoder.Default.Encode",
+            "Path.GetFileName",
+            "htmlspecialchars",
+            "escapeshellarg",
+            "escapeshellcmd",
+            "mysqli_real_escape_string",
+            "basename",
+            "ERB::Util.html_escape",
+            "Shellwords.escape",
+            "File.basename",
+        ],
+        params: NONE,
+        names: &["sanitize*", "escape*", "*_escape", "quote_*"],
+    },
+    BuiltinRule {
+        id: "validation",
+        category: "validate",
+        extensions: NONE,
+        calls: NONE,
+        params: NONE,
+        names: &["validate*", "is_valid_*", "isvalid*"],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbor_core::NodeKind;
+
+    fn function(file: &str, name: &str, calls: &[&str]) -> CodeNode {
+        CodeNode::new(name, name, NodeKind::Function, file)
+            .with_references(calls.iter().map(|c| c.to_string()).collect())
+    }
+
+    #[test]
+    fn test_call_patterns_match_trailing_segments() {
+        assert!(call_matches("execute", "cursor.execute"));
+        assert!(call_matches("execute", "self.db.execute"));
+        assert!(call_matches("Command::new", "std::process::Command::new"));
+        assert!(call_matches("$pdo->query", "$pdo->query"));
+        assert!(!call_matches("execute", "executed"));
+        assert!(!call_matches("exec", "cursor.execute"));
+        assert!(!call_matches("db.Query", "r.URL.Query"));
+        assert!(glob_matches("sanitize*", "sanitize_html"));
+        assert!(glob_matches("*_escape", "shell_escape"));
+        assert!(!glob_matches("escape*", "unescape"));
+    }
+
+    #[test]
+    fn test_builtin_rules_respect_extensions() {
+        let catalog = SecurityCatalog::builtin();
+        let none = HashSet::new();
+        let sink = catalog.sink(&function("app/db.py", "save", &["cursor.execute"]), &none);
+        assert_eq!(sink.unwrap().category, "sql");
+        // `db.Exec` is a SQL sink in Go only
+        assert!(catalog
+            .sink(&function("app/db.py", "save", &["db.Exec"]), &none)
+            .is_none());
+        assert!(catalog
+            .sink(&function("app/db.go", "save", &["db.Exec"]), &none)
+            .is_some());
+
+        let handler = CodeNode::new("show", "show", NodeKind::Method, "Users.java")
+            .with_signature("public User show(@PathVariable long id)");
+        assert_eq!(
+            catalog.source(&handler, &none).unwrap().matched,
+            "@PathVariable"
+        );
+
+        let sanitizer = function("lib/text.rs", "sanitize_name", &[]);
+        assert_eq!(
+            catalog.sanitizer(&sanitizer, &none).unwrap().rule,
+            "escaping"
+        );
+    }
+
+    #[test]
+    fn test_calls_to_local_functions_are_not_sinks() {
+        let catalog = SecurityCatalog::builtin();
+        let run = function("app/views.py", "run_cmd", &["execute"]);
+        assert!(catalog.sink(&run, &HashSet::new()).is_some());
+        // `execute` here is the app's own `def execute(cmd)`
+        assert!(catalog.sink(&run, &HashSet::from(["execute"])).is_none());
+    }
+
+    #[test]
+    fn test_project_rules_extend_or_replace_builtins() {
+        let config = r#"
+[[sinks]]
+id = "legacy-query"
+category = "sql"
+names = ["run_legacy_query"]
+"#;
+        let catalog = SecurityCatalog::from_config(config).unwrap();
+        assert_eq!(catalog.sinks.len(), SINKS.len() + 1);
+        let legacy = function("db.py", "run_legacy_query", &[]);
+        assert_eq!(
+            catalog.sink(&legacy, &HashSet::new()).unwrap().rule,
+            "legacy-query"
+        );
+
+        let only = SecurityCatalog::from_config(&format!("builtin = false\n{}", config)).unwrap();
+        assert_eq!(only.sinks.len(), 1);
+        assert!(only.sources.is_empty());
+    }
+}
@@ -1,11 +1,35 @@
 //! Security audit module for Arbor.
 //!
-//! Traces paths from public entry points to sensitive sinks,
-//! enabling blast-radius analysis for CVEs and security reviews.
+//! Taint-style audit over the call graph: functions that read untrusted
+//! input (sources), and the HTTP routes frameworks dispatch requests
+//! through, are traced to functions that run SQL, shell commands,
+//! eval or file access with it (sinks). Paths that pass through a
+//! sanitizer are dropped. What counts as each comes from the rule
+//! catalog in [`catalog`].
+//!
+//! Static calls are walked first. Paths that also need one of the graph's
+//! uncertain edges (a callback, event handler, injected provider or
+//! dynamic dispatch) are added as a second tier, and every hop that is
+//! inferred or matched by name alone lowers the path's confidence.
+//!
+//! Paths come from the bounded path engine in `arbor-graph`: each sink
+//! lists its shortest paths up to a limit and counts the rest, so a
+//! densely connected sink cannot stall the audit. Sinks whose answer was
+//! cut short are reported in [`AuditResult::truncated`].
+
+pub mod catalog;
 
 use anyhow::Result;
-use arbor_graph::{ArborGraph, NodeId, NodeInfo};
+use arbor_core::{CodeNode, NodeKind};
+use arbor_graph::{
+    ArborGraph, EdgeKind, NodeId, NodeInfo, PathOptions, PathTruncation, Provenance, UncertainEdge,
+    UncertainEdgeKind,
+};
+use catalog::{RuleMatch, SecurityCatalog};
+use petgraph::Direction;
 use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 /// Severity level of an audit finding based on path characteristics.
 #[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -55,16 +79,22 @@ impl std::fmt::Display for Severity {
     }
 }
 
-/// Complete result of a security audit against a single sink.
+/// Result of a taint audit.
 #[derive(Debug, Serialize, Clone)]
 pub struct AuditResult {
-    /// The sensitive sink being audited (e.g., "db_query", "exec")
-    pub sink: NodeInfo,
-    /// Paths from public entry points to this sink
+    /// Unsanitized paths from a source to a sink
     pub paths: Vec<AuditPath>,
-    /// Total number of reachable paths found
+    /// Total number of unsanitized paths found
     pub path_count: usize,
-    /// Confidence score of this audit (0.0 - 1.0)
+    /// Source-to-sink paths dropped because they pass through a sanitizer
+    pub sanitized_count: usize,
+    /// Unsanitized paths that exist, listed or not; an upper bound when
+    /// cycles are involved
+    pub estimated_paths: u64,
+    /// Sinks whose paths were not all listed, and why
+    pub truncated: Vec<TruncatedSink>,
+    /// Lowest path confidence (0.0 - 1.0); 1.0 when no path crosses an
+    /// uncertain edge
     pub confidence: f32,
     /// Summary statistics
     pub summary: AuditSummary,
@@ -77,35 +107,76 @@ pub struct AuditSummary {
     pub high_count: usize,
     pub medium_count: usize,
     pub low_count: usize,
-    pub unique_entry_points: usize,
+    pub sources_found: usize,
+    pub sinks_found: usize,
+    pub unique_sources: usize,
+    pub unique_sinks: usize,
     pub unique_files: usize,
 }
 
-/// A single path from entry point to sink.
+/// A single path from a source to a sink.
 #[derive(Debug, Serialize, Clone)]
 pub struct AuditPath {
-    /// The public entry point (source)
+    /// The function reading untrusted input
     pub source: NodeInfo,
+    /// The source rule it matched
+    pub source_rule: RuleMatch,
+    /// The function using it
+    pub sink: NodeInfo,
+    /// The sink rule it matched
+    pub sink_rule: RuleMatch,
     /// The sequence of nodes from source to sink
     pub trace: Vec<NodeInfo>,
     /// Severity based on path length
     pub severity: Severity,
-    /// Any uncertain edges encountered along this path
-    pub uncertainty: Vec<String>,
+    /// Hops along the path that are inferred rather than proven
+    pub uncertainty: Vec<UncertainEdge>,
+    /// Product of the uncertain edges' confidence
+    pub confidence: f32,
+}
+
+/// A sink with more paths than the audit listed.
+#[derive(Debug, Serialize, Clone)]
+pub struct TruncatedSink {
+    pub sink: NodeInfo,
+    /// Unsanitized paths into this sink, listed or not
+    pub estimated_paths: u64,
+    pub reasons: Vec<PathTruncation>,
 }
 
 /// Configuration for the audit engine.
 pub struct AuditConfig {
+    /// Longest path, counted in functions
     pub max_depth: usize,
     pub ignore_tests: bool,
+    /// Only audit sinks with this rule id, category or symbol name
+    pub sink: Option<String>,
+    /// Paths to list per sink, shortest first
+    pub max_paths: usize,
+    /// Time budget per sink for listing paths
+    pub time_budget: Duration,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 8,
+            ignore_tests: true,
+            sink: None,
+            max_paths: 20,
+            time_budget: Duration::from_millis(500),
+        }
+    }
 }
 
 impl AuditResult {
-    fn new(sink: NodeInfo) -> Self {
+    fn new() -> Self {
         Self {
-            sink,
             paths: Vec::new(),
             path_count: 0,
+            sanitized_count: 0,
+            estimated_paths: 0,
+            truncated: Vec::new(),
             confidence: 1.0,
             summary: AuditSummary::default(),
         }
@@ -113,11 +184,13 @@ impl AuditResult {
 
     /// Build the summary statistics from collected paths.
     fn compute_summary(&mut self) {
-        let mut entry_names = std::collections::HashSet::new();
-        let mut files = std::collections::HashSet::new();
+        let mut sources = HashSet::new();
+        let mut sinks = HashSet::new();
+        let mut files = HashSet::new();
 
         for path in &self.paths {
-            entry_names.insert(path.source.name.clone());
+            sources.insert(path.source.id.clone());
+            sinks.insert(path.sink.id.clone());
             for node in &path.trace {
                 files.insert(node.file.clone());
             }
@@ -129,8 +202,10 @@ impl AuditResult {
             }
         }
 
-        self.summary.unique_entry_points = entry_names.len();
+        self.summary.unique_sources = sources.len();
+        self.summary.unique_sinks = sinks.len();
         self.summary.unique_files = files.len();
+        self.confidence = self.paths.iter().map(|p| p.confidence).fold(1.0, f32::min);
     }
 }
 
@@ -148,58 +223,181 @@ fn is_test_file(file: &str) -> bool {
         || lower.ends_with(".spec.js")
 }
 
-/// Run a security audit: find all paths from entry points to the given sink.
-pub fn run_audit(graph: &ArborGraph, sink_name: &str, config: &AuditConfig) -> Result<AuditResult> {
-    // 1. Find the sink node
-    let nodes = graph.find_by_name(sink_name);
-    if nodes.is_empty() {
-        return Err(anyhow::anyhow!(
-            "Sink symbol '{}' not found in graph",
-            sink_name
-        ));
+/// How each function in the graph matched the catalog.
+#[derive(Default)]
+struct Classification {
+    sources: HashMap<NodeId, RuleMatch>,
+    sinks: HashMap<NodeId, RuleMatch>,
+    sanitizers: HashSet<NodeId>,
+}
+
+fn classify(
+    graph: &ArborGraph,
+    catalog: &SecurityCatalog,
+    config: &AuditConfig,
+) -> Result<Classification> {
+    let mut classes = Classification::default();
+    for idx in graph.node_indexes() {
+        let Some(node) = graph.get(idx) else {
+            continue;
+        };
+        // A framework route hands request data to its handler.
+        if node.kind == NodeKind::Route && !(config.ignore_tests && is_test_file(&node.file)) {
+            classes.sources.insert(
+                idx,
+                RuleMatch {
+                    rule: "route".to_string(),
+                    category: "http".to_string(),
+                    matched: node.name.clone(),
+                },
+            );
+            continue;
+        }
+        if !matches!(
+            node.kind,
+            NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+        ) || (config.ignore_tests && is_test_file(&node.file))
+        {
+            continue;
+        }
+        let local: HashSet<&str> = graph
+            .edges_of(idx, Direction::Outgoing)
+            .into_iter()
+            .filter(|(_, edge)| edge.kind == EdgeKind::Calls)
+            .filter_map(|(_, edge)| edge.reference.as_deref())
+            .collect();
+        if let Some(m) = catalog.source(node, &local) {
+            classes.sources.insert(idx, m);
+        }
+        if let Some(m) = catalog.sink(node, &local) {
+            classes.sinks.insert(idx, m);
+        }
+        if catalog.sanitizer(node, &local).is_some() {
+            classes.sanitizers.insert(idx);
+        }
+    }
+
+    if let Some(filter) = &config.sink {
+        let is_rule = catalog
+            .sinks
+            .iter()
+            .any(|r| &r.id == filter || &r.category == filter);
+        classes
+            .sinks
+            .retain(|_, m| &m.rule == filter || &m.category == filter);
+        // Not a rule: audit the named symbol itself as the sink.
+        if !is_rule {
+            let nodes = graph.find_by_name(filter);
+            if nodes.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Sink '{}' is neither a rule, a category nor a symbol in the graph",
+                    filter
+                ));
+            }
+            for node in nodes {
+                if let Some(idx) = graph.get_index(&node.id) {
+                    classes.sinks.insert(
+                        idx,
+                        RuleMatch {
+                            rule: "symbol".to_string(),
+                            category: "symbol".to_string(),
+                            matched: node.name.clone(),
+                        },
+                    );
+                }
+            }
+        }
     }
+    Ok(classes)
+}
+
+/// Run a taint audit: find source-to-sink call paths that no sanitizer
+/// interrupts.
+pub fn run_audit(
+    graph: &ArborGraph,
+    catalog: &SecurityCatalog,
+    config: &AuditConfig,
+) -> Result<AuditResult> {
+    let classes = classify(graph, catalog, config)?;
+    let mut result = AuditResult::new();
+    result.summary.sources_found = classes.sources.len();
+    result.summary.sinks_found = classes.sinks.len();
+
+    // Walk up from each sink through callers; a path ends at the first
+    // source it meets and never passes through a sanitizer or test code.
+    let sources: HashSet<NodeId> = classes.sources.keys().copied().collect();
+    let tests: HashSet<NodeId> = if config.ignore_tests {
+        graph
+            .node_indexes()
+            .filter(|idx| graph.get(*idx).is_some_and(|n| is_test_file(&n.file)))
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    let unsanitized = PathOptions {
+        max_paths: config.max_paths,
+        max_depth: config.max_depth.saturating_sub(1),
+        time_budget: config.time_budget,
+        direction: Direction::Incoming,
+        edge_kinds: Some(vec![EdgeKind::Calls]),
+        uncertain_edges: false,
+        excluded: tests.union(&classes.sanitizers).copied().collect(),
+    };
+    let any = PathOptions {
+        max_paths: 0,
+        excluded: tests,
+        ..unsanitized.clone()
+    };
+    // Paths that need a callback, event, injection or dispatch link are
+    // a second, lower-confidence tier on top of the static calls.
+    let inferred = (!graph.uncertain_edges().is_empty()).then(|| PathOptions {
+        uncertain_edges: true,
+        ..unsanitized.clone()
+    });
 
-    let sink_node = nodes[0];
-    let sink_id = graph
-        .get_index(&sink_node.id)
-        .ok_or_else(|| anyhow::anyhow!("Sink node has no graph index"))?;
-
-    let mut result = AuditResult::new(NodeInfo::from(sink_node));
-
-    // 2. Reverse-traverse the call graph to find all paths to entry points
-    let mut raw_paths = Vec::new();
-    find_paths_to_roots(
-        graph,
-        sink_id,
-        &mut Vec::new(),
-        &mut raw_paths,
-        config.max_depth,
-        config.ignore_tests,
-    );
-
-    // 3. Convert raw NodeId paths into structured AuditPaths
-    for path_ids in raw_paths {
-        if let Some(source_id) = path_ids.last() {
-            if let Some(source_node) = graph.get(*source_id) {
-                let trace: Vec<NodeInfo> = path_ids
-                    .iter()
-                    .rev()
-                    .filter_map(|id| graph.get(*id).map(NodeInfo::from))
-                    .collect();
-
-                let severity = Severity::from_depth(trace.len());
-
-                result.paths.push(AuditPath {
-                    source: NodeInfo::from(source_node),
-                    trace,
-                    severity,
-                    uncertainty: Vec::new(),
+    let mut sinks: Vec<NodeId> = classes.sinks.keys().copied().collect();
+    sinks.sort();
+    for sink_id in sinks {
+        let mut found = graph.find_paths_to_any(sink_id, &sources, &unsanitized);
+        if !classes.sanitizers.is_empty() {
+            let all = graph.find_paths_to_any(sink_id, &sources, &any);
+            result.sanitized_count +=
+                all.estimated_paths.saturating_sub(found.estimated_paths) as usize;
+        }
+        if let Some(inferred) = &inferred {
+            let wider = graph.find_paths_to_any(sink_id, &sources, inferred);
+            let extra: Vec<Vec<NodeId>> = wider
+                .paths
+                .into_iter()
+                .filter(|path| !found.paths.contains(path))
+                .collect();
+            found.estimated_paths = found.estimated_paths.max(wider.estimated_paths);
+            found.paths.extend(extra);
+            for reason in wider.truncated {
+                if !found.truncated.contains(&reason) {
+                    found.truncated.push(reason);
+                }
+            }
+        }
+        result.estimated_paths = result.estimated_paths.saturating_add(found.estimated_paths);
+
+        for path_ids in &found.paths {
+            if let Some(path) = build_path(graph, &classes, path_ids) {
+                result.paths.push(path);
+            }
+        }
+        if !found.is_complete() {
+            if let Some(sink) = graph.get(sink_id) {
+                result.truncated.push(TruncatedSink {
+                    sink: NodeInfo::from(sink),
+                    estimated_paths: found.estimated_paths,
+                    reasons: found.truncated,
                 });
             }
         }
     }
 
-    // 4. Sort by severity (critical first), then by path length
+    // Sort by severity (critical first), then by path length
     result.paths.sort_by(|a, b| {
         a.severity
             .cmp(&b.severity)
@@ -212,49 +410,269 @@ pub fn run_audit(graph: &ArborGraph, sink_name: &str, config: &AuditConfig) -> R
     Ok(result)
 }
 
-/// DFS traversal upstream (callers) to find all paths from entry points to the current node.
-fn find_paths_to_roots(
+/// Turns a sink-first list of node ids into an [`AuditPath`].
+fn build_path(
     graph: &ArborGraph,
-    current: NodeId,
-    path: &mut Vec<NodeId>,
-    results: &mut Vec<Vec<NodeId>>,
-    depth_remaining: usize,
-    ignore_tests: bool,
-) {
-    path.push(current);
-
-    if depth_remaining == 0 {
-        path.pop();
-        return;
+    classes: &Classification,
+    path_ids: &[NodeId],
+) -> Option<AuditPath> {
+    let source_id = *path_ids.last()?;
+    let sink_id = *path_ids.first()?;
+    let nodes: Vec<&CodeNode> = path_ids
+        .iter()
+        .rev()
+        .filter_map(|id| graph.get(*id))
+        .collect();
+    if nodes.len() != path_ids.len() {
+        return None;
     }
 
-    let callers = graph.get_callers(current);
+    let ids: Vec<NodeId> = path_ids.iter().rev().copied().collect();
+    let uncertainty: Vec<UncertainEdge> = ids
+        .windows(2)
+        .filter_map(|hop| hop_uncertainty(graph, hop[0], hop[1]))
+        .collect();
+    let confidence = uncertainty.iter().map(|e| e.confidence).product();
+    let trace: Vec<NodeInfo> = nodes.iter().map(|n| NodeInfo::from(*n)).collect();
 
-    if callers.is_empty() {
-        // Reached a root — this is an entry point
-        results.push(path.clone());
-    } else {
-        for caller in callers {
-            // Skip test files if configured
-            if ignore_tests && is_test_file(&caller.file) {
-                continue;
-            }
+    Some(AuditPath {
+        source: trace.first()?.clone(),
+        source_rule: classes.sources.get(&source_id)?.clone(),
+        sink: trace.last()?.clone(),
+        sink_rule: classes.sinks.get(&sink_id)?.clone(),
+        severity: Severity::from_depth(trace.len()),
+        trace,
+        uncertainty,
+        confidence,
+    })
+}
 
-            if let Some(caller_id) = graph.get_index(&caller.id) {
-                // Skip cycles
-                if !path.contains(&caller_id) {
-                    find_paths_to_roots(
-                        graph,
-                        caller_id,
-                        path,
-                        results,
-                        depth_remaining - 1,
-                        ignore_tests,
-                    );
-                }
-            }
+/// How sure the hop from `caller` to `callee` is: a call resolved by
+/// qualified name or import is certain; one matched by name alone, or a
+/// link the graph only infers (callback, event handler, injection,
+/// dispatch), is flagged with its confidence.
+fn hop_uncertainty(graph: &ArborGraph, caller: NodeId, callee: NodeId) -> Option<UncertainEdge> {
+    let strongest = |a: f32, b: f32| a.total_cmp(&b);
+    let call = graph
+        .edges_of(caller, Direction::Outgoing)
+        .into_iter()
+        .filter(|(far, edge)| *far == callee && edge.kind == EdgeKind::Calls)
+        .map(|(_, edge)| edge)
+        .max_by(|a, b| strongest(a.confidence, b.confidence));
+    let (from, to) = (graph.get(caller)?, graph.get(callee)?);
+    match call {
+        Some(edge) if edge.confidence >= Provenance::ImportMap.confidence() => None,
+        Some(edge) => Some(UncertainEdge {
+            from: from.id.clone(),
+            to: to.id.clone(),
+            kind: UncertainEdgeKind::NameMatch,
+            confidence: edge.confidence,
+            reason: format!(
+                "'{}' reaches '{}' through a {}",
+                from.name, to.name, edge.provenance
+            ),
+        }),
+        None => graph
+            .uncertain_edges_of(caller, Direction::Outgoing)
+            .into_iter()
+            .filter(|(far, _)| *far == callee)
+            .map(|(_, edge)| edge)
+            .max_by(|a, b| strongest(a.confidence, b.confidence))
+            .cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbor_graph::{Edge, EdgeKind};
+
+    fn function(graph: &mut ArborGraph, file: &str, name: &str, calls: &[&str]) -> NodeId {
+        let node = CodeNode::new(name, name, NodeKind::Function, file)
+            .with_references(calls.iter().map(|c| c.to_string()).collect());
+        graph.add_node(node)
+    }
+
+    fn calls(graph: &mut ArborGraph, from: NodeId, to: NodeId) {
+        graph.add_edge(from, to, Edge::new(EdgeKind::Calls));
+    }
+
+    fn config(sink: Option<&str>) -> AuditConfig {
+        AuditConfig {
+            sink: sink.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_reports_only_unsanitized_source_to_sink_paths() {
+        let mut graph = ArborGraph::new();
+        let search = function(
+            &mut graph,
+            "api.py",
+            "search",
+            &["request.args.get", "find"],
+        );
+        let export = function(
+            &mut graph,
+            "api.py",
+            "export",
+            &["request.args.get", "sanitize_name", "find"],
+        );
+        let find = function(&mut graph, "db.py", "find", &["cursor.execute"]);
+        let cleanup = function(&mut graph, "jobs.py", "cleanup", &["find"]);
+        calls(&mut graph, search, find);
+        calls(&mut graph, export, find);
+        calls(&mut graph, cleanup, find);
+
+        let catalog = SecurityCatalog::builtin();
+        let result = run_audit(&graph, &catalog, &config(None)).unwrap();
+
+        // `export` sanitizes; `cleanup` reads no untrusted input
+        assert_eq!(result.path_count, 1);
+        assert_eq!(result.sanitized_count, 1);
+        let path = &result.paths[0];
+        assert_eq!(path.source.name, "search");
+        assert_eq!(path.source_rule.category, "http");
+        assert_eq!(path.sink.name, "find");
+        assert_eq!(path.sink_rule.matched, "cursor.execute");
+        assert_eq!(path.severity, Severity::Critical);
+        assert!(path.uncertainty.is_empty());
+
+        assert!(run_audit(&graph, &catalog, &config(Some("command")))
+            .unwrap()
+            .paths
+            .is_empty());
+    }
+
+    #[test]
+    fn test_flags_inferred_hops_and_named_sinks() {
+        use arbor_graph::Provenance;
+
+        let mut graph = ArborGraph::new();
+        // A direct call is certain, however the callee is named
+        let save = function(&mut graph, "api.py", "save", &["request.get_json"]);
+        let on_save = function(&mut graph, "api.py", "on_save", &[]);
+        // `upload` registers `on_upload`; `on_upload` reaches the sink by name
+        let upload = function(&mut graph, "api.py", "upload", &["request.get_json"]);
+        let on_upload = function(&mut graph, "api.py", "on_upload", &[]);
+        let store = function(&mut graph, "store.py", "store_blob", &[]);
+        calls(&mut graph, save, on_save);
+        calls(&mut graph, on_save, store);
+        graph.add_edge(
+            on_upload,
+            store,
+            Edge::new(EdgeKind::Calls).with_provenance(Provenance::GlobalSuffix),
+        );
+        let id = |graph: &ArborGraph, idx| graph.get(idx).unwrap().id.clone();
+        let registration = UncertainEdge {
+            from: id(&graph, upload),
+            to: id(&graph, on_upload),
+            kind: UncertainEdgeKind::EventHandler,
+            confidence: 0.7,
+            reason: "'upload' passes 'on_upload' to 'on'".to_string(),
+        };
+        graph.add_uncertain_edges([registration]);
+
+        let catalog = SecurityCatalog::builtin();
+        let result = run_audit(&graph, &catalog, &config(Some("store_blob"))).unwrap();
+        assert_eq!(result.path_count, 2);
+        let path = |source: &str| {
+            result
+                .paths
+                .iter()
+                .find(|p| p.source.name == source)
+                .unwrap()
+        };
+
+        let direct = path("save");
+        assert_eq!(direct.sink_rule.rule, "symbol");
+        assert_eq!(direct.trace.len(), 3);
+        assert!(direct.uncertainty.is_empty());
+        assert_eq!(direct.confidence, 1.0);
+
+        let inferred = path("upload");
+        let kinds: Vec<&UncertainEdgeKind> = inferred.uncertainty.iter().map(|e| &e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &UncertainEdgeKind::EventHandler,
+                &UncertainEdgeKind::NameMatch
+            ]
+        );
+        assert!((inferred.confidence - 0.7 * 0.7).abs() < 1e-6);
+        assert!(result.confidence < 1.0);
+
+        assert!(run_audit(&graph, &catalog, &config(Some("missing"))).is_err());
+    }
+
+    #[test]
+    fn test_local_function_named_like_a_sink_is_not_one() {
+        let mut graph = ArborGraph::new();
+        let run = function(
+            &mut graph,
+            "app.py",
+            "run_cmd",
+            &["request.args.get", "execute"],
+        );
+        let execute = function(&mut graph, "app.py", "execute", &["print"]);
+        graph.add_edge(
+            run,
+            execute,
+            Edge::new(EdgeKind::Calls).with_reference("execute"),
+        );
+
+        let result = run_audit(&graph, &SecurityCatalog::builtin(), &config(None)).unwrap();
+        assert_eq!(result.summary.sinks_found, 0);
+        assert!(result.paths.is_empty());
+    }
+
+    #[test]
+    fn test_reports_sinks_with_more_paths_than_listed() {
+        let mut graph = ArborGraph::new();
+        let find = function(&mut graph, "db.py", "find", &["cursor.execute"]);
+        for name in ["search", "lookup", "browse"] {
+            let handler = function(&mut graph, "api.py", name, &["request.args.get"]);
+            calls(&mut graph, handler, find);
         }
+
+        let catalog = SecurityCatalog::builtin();
+        let limited = AuditConfig {
+            max_paths: 1,
+            ..Default::default()
+        };
+        let result = run_audit(&graph, &catalog, &limited).unwrap();
+        assert_eq!(result.path_count, 1);
+        assert_eq!(result.estimated_paths, 3);
+        assert_eq!(result.truncated.len(), 1);
+        assert_eq!(result.truncated[0].sink.name, "find");
+        assert_eq!(result.truncated[0].reasons, [PathTruncation::ResultLimit]);
+
+        let result = run_audit(&graph, &catalog, &config(None)).unwrap();
+        assert_eq!(result.path_count, 3);
+        assert!(result.truncated.is_empty());
     }
 
-    path.pop();
+    #[test]
+    fn test_routes_are_sources_for_their_handlers() {
+        let mut graph = ArborGraph::new();
+        let route = graph.add_node(CodeNode::new(
+            "POST /orders",
+            "POST /orders",
+            NodeKind::Route,
+            "api.py",
+        ));
+        // Reads its input from parameters, which no source rule matches.
+        let create = function(&mut graph, "api.py", "create_order", &["save"]);
+        let save = function(&mut graph, "db.py", "save", &["cursor.execute"]);
+        calls(&mut graph, route, create);
+        calls(&mut graph, create, save);
+
+        let result = run_audit(&graph, &SecurityCatalog::builtin(), &config(None)).unwrap();
+        assert_eq!(result.path_count, 1);
+        let path = &result.paths[0];
+        assert_eq!(path.source.name, "POST /orders");
+        assert_eq!(path.source_rule.rule, "route");
+        assert_eq!(path.trace.len(), 3);
+    }
 }
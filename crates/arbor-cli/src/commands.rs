@@ -23,6 +23,39 @@ struct DiffSummary {
     files_likely_updates: usize,
     blast_radius_nodes: usize,
     mermaid_diagram: Option<String>,
+    teams_affected: Vec<arbor_graph::OwnerImpact>,
+    authors_affected: Vec<arbor_graph::OwnerImpact>,
+}
+
+/// Limits `arbor check` enforces on a change set.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckThresholds {
+    pub max_blast_radius: usize,
+    /// Cyclomatic complexity a changed function may have.
+    pub max_complexity: Option<u32>,
+    /// Nesting depth a changed function may have.
+    pub max_nesting: Option<u32>,
+}
+
+/// How `arbor check` prints its report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckFormat {
+    Text,
+    Json,
+    Markdown,
+    Sarif,
+}
+
+/// A changed function over a complexity or nesting threshold.
+#[derive(Debug, serde::Serialize)]
+struct ComplexSymbol {
+    id: String,
+    name: String,
+    file: String,
+    line: u32,
+    cyclomatic: u32,
+    nesting: u32,
+    centrality: f64,
 }
 
 const ROOT_MARKERS: &[&str] = &[
@@ -202,7 +235,7 @@ fn save_graph_binary(path: &Path, graph: &arbor_graph::ArborGraph) -> Result<()>
     }
 
     let tmp_path = graph_path.with_extension("bin.tmp");
-    let bytes = bincode::serialize(graph)?;
+    let bytes = arbor_graph::encode_graph(graph)?;
     fs::write(&tmp_path, bytes)?;
     fs::rename(&tmp_path, &graph_path)?;
     Ok(())
@@ -233,7 +266,7 @@ fn load_graph_binary(path: &Path) -> Result<arbor_graph::ArborGraph> {
     }
 
     let bytes = fs::read(graph_path)?;
-    let mut graph: arbor_graph::ArborGraph = bincode::deserialize(&bytes)?;
+    let mut graph = arbor_graph::decode_graph(&bytes)?;
     graph.rebuild_search_index();
     Ok(graph)
 }
@@ -248,7 +281,7 @@ fn load_graph_from_store(path: &Path) -> Result<arbor_graph::ArborGraph> {
         .map_err(|e| format!("Failed to open graph store: {}", e))?;
 
     let mut graph = store
-        .load_graph()
+        .load_graph_with(load_declarations(path))
         .map_err(|e| format!("Failed to load graph from store: {}", e))?;
 
     if graph.node_count() == 0 {
@@ -259,6 +292,15 @@ fn load_graph_from_store(path: &Path) -> Result<arbor_graph::ArborGraph> {
     Ok(graph)
 }
 
+/// The project's `.arbor/edges.toml`; none if it is missing or invalid
+/// (`arbor doctor` reports why).
+fn load_declarations(path: &Path) -> arbor_graph::Declarations {
+    arbor_graph::Declarations::load(path)
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
 /// Returns the modified time of a cache file in seconds since the UNIX epoch.
 fn cache_mtime_secs(cache_path: &Path) -> Option<u64> {
     fs::metadata(cache_path)
@@ -423,35 +465,28 @@ fn git_changed_files(path: &Path) -> Result<Vec<String>> {
         return Ok(Vec::new());
     }
 
-    let range_base = std::env::var("ARBOR_DIFF_BASE").ok();
-    let range_head = std::env::var("ARBOR_DIFF_HEAD").ok();
-
-    if let (Some(base), Some(head)) = (range_base, range_head) {
-        let base = base.trim();
-        let head = head.trim();
-
-        if !base.is_empty() && !head.is_empty() {
-            let ranged = run_git(
-                path,
-                &["diff", "-w", "--name-status", "--find-renames", base, head],
-            )?;
+    if let Some((base, head)) = diff_range() {
+        let (base, head) = (base.as_str(), head.as_str());
+        let ranged = run_git(
+            path,
+            &["diff", "-w", "--name-status", "--find-renames", base, head],
+        )?;
 
-            let mut files = parse_git_name_status_output(&ranged);
+        let mut files = parse_git_name_status_output(&ranged);
 
-            let numstat = run_git(
-                path,
-                &["diff", "-w", "--numstat", "--find-renames", base, head],
-            )?;
-            let has_real_diff: std::collections::HashSet<String> =
-                parse_numstat_files(&numstat).into_iter().collect();
-            files.retain(|f| has_real_diff.contains(f));
+        let numstat = run_git(
+            path,
+            &["diff", "-w", "--numstat", "--find-renames", base, head],
+        )?;
+        let has_real_diff: std::collections::HashSet<String> =
+            parse_numstat_files(&numstat).into_iter().collect();
+        files.retain(|f| has_real_diff.contains(f));
 
-            files.retain(|path| !is_generated_or_internal_path(path));
-            files.sort();
-            files.dedup();
+        files.retain(|path| !is_generated_or_internal_path(path));
+        files.sort();
+        files.dedup();
 
-            return Ok(files);
-        }
+        return Ok(files);
     }
 
     let mut files = Vec::new();
@@ -525,6 +560,35 @@ fn node_matches_changed_file(node_file: &str, changed_file: &str, project_root:
     node_norm == abs_norm
 }
 
+/// The `ARBOR_DIFF_BASE`..`ARBOR_DIFF_HEAD` commit range, when both are set.
+fn diff_range() -> Option<(String, String)> {
+    let base = std::env::var("ARBOR_DIFF_BASE").ok()?;
+    let head = std::env::var("ARBOR_DIFF_HEAD").ok()?;
+    let (base, head) = (base.trim(), head.trim());
+    (!base.is_empty() && !head.is_empty()).then(|| (base.to_string(), head.to_string()))
+}
+
+/// The symbols the diff's changed lines touch, among the nodes of
+/// `changed_files`; every node of those files if git has no line ranges.
+fn touched_node_ids(
+    graph: &arbor_graph::ArborGraph,
+    changed_files: &[String],
+    project_root: &Path,
+) -> Vec<arbor_graph::NodeId> {
+    let changes = match diff_range() {
+        Some((base, head)) => arbor_graph::WorkingChanges::load_range(project_root, &base, &head),
+        None => arbor_graph::WorkingChanges::load(project_root),
+    };
+    match changes {
+        Some(changes) => graph.changed_symbols(&changes, |file| {
+            changed_files
+                .iter()
+                .any(|f| node_matches_changed_file(file, f, project_root))
+        }),
+        None => changed_node_ids(graph, changed_files, project_root),
+    }
+}
+
 fn changed_node_ids(
     graph: &arbor_graph::ArborGraph,
     changed_files: &[String],
@@ -542,21 +606,73 @@ fn changed_node_ids(
         .collect()
 }
 
+/// Changed functions over the complexity or nesting threshold, the most
+/// central first: those are the ones a risky edit hurts the most.
+fn complex_changed_symbols(
+    graph: &arbor_graph::ArborGraph,
+    changed_node_ids: &[arbor_graph::NodeId],
+    thresholds: &CheckThresholds,
+) -> Vec<ComplexSymbol> {
+    if thresholds.max_complexity.is_none() && thresholds.max_nesting.is_none() {
+        return Vec::new();
+    }
+    let over = |limit: Option<u32>, value: Option<u32>| {
+        limit.zip(value).is_some_and(|(limit, value)| value > limit)
+    };
+    let mut symbols: Vec<ComplexSymbol> = changed_node_ids
+        .iter()
+        .filter_map(|&idx| {
+            let node = graph.get(idx)?;
+            let cyclomatic = node.metric(arbor_core::metrics::CYCLOMATIC);
+            let nesting = node.metric(arbor_core::metrics::NESTING);
+            if !over(thresholds.max_complexity, cyclomatic)
+                && !over(thresholds.max_nesting, nesting)
+            {
+                return None;
+            }
+            Some(ComplexSymbol {
+                id: node.id.clone(),
+                name: node.qualified_name.clone(),
+                file: node.file.clone(),
+                line: node.line_start,
+                cyclomatic: cyclomatic.unwrap_or(0),
+                nesting: nesting.unwrap_or(0),
+                centrality: graph.centrality(idx),
+            })
+        })
+        .collect();
+    symbols.sort_by(|a, b| {
+        b.centrality
+            .partial_cmp(&a.centrality)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| b.cyclomatic.cmp(&a.cyclomatic))
+    });
+    symbols
+}
+
 fn compute_diff_summary(
     graph: &arbor_graph::ArborGraph,
     changed_files: Vec<String>,
     changed_node_ids: Vec<arbor_graph::NodeId>,
     max_depth: usize,
     project_root: &Path,
+    ownership: Option<&mut arbor_graph::Ownership>,
 ) -> DiffSummary {
     let mut direct_callers = std::collections::HashSet::new();
     let mut indirect_callers = std::collections::HashSet::new();
     let mut affected_nodes = std::collections::HashSet::new();
     let mut affected_files = std::collections::HashSet::new();
+    let mut affected_infos = std::collections::BTreeMap::new();
 
     for node_id in changed_node_ids.iter().copied() {
         let analysis = graph.analyze_impact(node_id, max_depth);
 
+        for node in analysis.upstream.iter().chain(&analysis.downstream) {
+            affected_infos
+                .entry(node.node_info.id.clone())
+                .or_insert_with(|| node.node_info.clone());
+        }
+
         for up in &analysis.upstream {
             affected_nodes.insert(up.node_info.id.clone());
             affected_files.insert(up.node_info.file.clone());
@@ -652,6 +768,11 @@ fn compute_diff_summary(
         None
     };
 
+    let (teams_affected, authors_affected) = match ownership {
+        Some(ownership) => ownership.summarize(affected_infos.values()),
+        None => (Vec::new(), Vec::new()),
+    };
+
     DiffSummary {
         changed_files,
         changed_symbols: changed_node_ids.len(),
@@ -661,6 +782,8 @@ fn compute_diff_summary(
         files_likely_updates,
         blast_radius_nodes: affected_nodes.len(),
         mermaid_diagram,
+        teams_affected,
+        authors_affected,
     }
 }
 
@@ -685,6 +808,65 @@ fn print_diff_summary(summary: &DiffSummary) {
     );
     println!("  • {} impacted nodes total", summary.blast_radius_nodes);
     println!("  • {} changed symbols resolved", summary.changed_symbols);
+    print_owner_list("Teams affected:", &summary.teams_affected);
+    print_owner_list("Authors affected:", &summary.authors_affected);
+}
+
+fn print_complex_symbols(complex: &[ComplexSymbol]) {
+    if complex.is_empty() {
+        return;
+    }
+    println!();
+    println!("Complex changed code:");
+    for symbol in complex {
+        println!(
+            "  • {} {}",
+            symbol.name.cyan(),
+            format!(
+                "(complexity {}, nesting {}, rank {:.4}) {}:{}",
+                symbol.cyclomatic, symbol.nesting, symbol.centrality, symbol.file, symbol.line
+            )
+            .dimmed()
+        );
+    }
+}
+
+fn print_owner_list(title: &str, owners: &[arbor_graph::OwnerImpact]) {
+    if owners.is_empty() {
+        return;
+    }
+    println!();
+    println!("{}", title);
+    for owner in owners {
+        println!(
+            "  • {} {}",
+            owner.owner.cyan(),
+            format!(
+                "({} node{})",
+                owner.nodes,
+                if owner.nodes == 1 { "" } else { "s" }
+            )
+            .dimmed()
+        );
+    }
+}
+
+fn print_owner_markdown(title: &str, owners: &[arbor_graph::OwnerImpact]) {
+    if owners.is_empty() {
+        return;
+    }
+    println!("\n### {}\n", title);
+    println!("| Owner | Nodes | Files |");
+    println!("|-------|-------|-------|");
+    for owner in owners {
+        let files: Vec<String> = owner.files.iter().map(|f| format!("`{}`", f)).collect();
+        println!(
+            "| {} | {} | {} |",
+            owner.owner,
+            owner.nodes,
+            files.join(", ")
+        );
+    }
 }
 
 fn print_diff_markdown(summary: &DiffSummary) {
@@ -738,6 +920,9 @@ fn print_diff_markdown(summary: &DiffSummary) {
     );
     println!("| Total blast radius | {} |", summary.blast_radius_nodes);
 
+    print_owner_markdown("👥 Teams Affected", &summary.teams_affected);
+    print_owner_markdown("✍️ Authors Affected", &summary.authors_affected);
+
     // Recommendations
     if summary.entrypoints_affected > 0 {
         println!(
@@ -753,7 +938,83 @@ fn print_diff_markdown(summary: &DiffSummary) {
     println!("*Powered by [Arbor](https://github.com/Anandb71/arbor) v{} — graph-native code intelligence*", env!("CARGO_PKG_VERSION"));
 }
 
-fn print_check_markdown(summary: &DiffSummary, risky: bool, max_blast_radius: usize) {
+/// One SARIF result per changed symbol that others depend on, with a code
+/// flow from the nearest entry point that reaches it. Entry points are
+/// those `arbor entry-points` lists, less test code: a test without
+/// callers is not where production traffic comes in.
+fn add_blast_radius_results(
+    sarif: &mut crate::sarif::SarifBuilder,
+    graph: &arbor_graph::ArborGraph,
+    changed_nodes: &[arbor_graph::NodeId],
+    depth: usize,
+    reason: &str,
+) {
+    for &idx in changed_nodes {
+        let Some(node) = graph.get(idx) else {
+            continue;
+        };
+        let analysis = graph.analyze_impact(idx, depth);
+        if analysis.upstream.is_empty() {
+            continue;
+        }
+        let entry_points: Vec<&arbor_graph::AffectedNode> = analysis
+            .upstream
+            .iter()
+            .filter(|up| {
+                graph.is_entry_point(up.node_id)
+                    && graph.get(up.node_id).is_some_and(|n| !is_test_node(n))
+            })
+            .collect();
+        let flow: Vec<arbor_graph::NodeInfo> = entry_points
+            .iter()
+            .min_by_key(|up| up.hop_distance)
+            .and_then(|entry| graph.find_path(entry.node_id, idx))
+            .map(|path| path.into_iter().map(arbor_graph::NodeInfo::from).collect())
+            .unwrap_or_default();
+        let steps: Vec<(&arbor_graph::NodeInfo, String)> = flow
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let text = if i == 0 {
+                    format!("entry point {}", step.name)
+                } else if i + 1 == flow.len() {
+                    format!("changed: {}", step.name)
+                } else {
+                    format!("calls {}", step.name)
+                };
+                (step, text)
+            })
+            .collect();
+
+        sarif.rule(
+            "arbor/blast-radius",
+            "Change has a large blast radius",
+            "Changed code is depended on by more symbols or entry points than the configured limit allows",
+            "error",
+        );
+        sarif.result(
+            "arbor/blast-radius",
+            "error",
+            &format!(
+                "'{}' changed: {} symbols depend on it, {} of them entry points. {}",
+                node.name,
+                analysis.upstream.len(),
+                entry_points.len(),
+                reason
+            ),
+            &arbor_graph::NodeInfo::from(node),
+            &steps,
+        );
+    }
+}
+
+fn print_check_markdown(
+    summary: &DiffSummary,
+    complex: &[ComplexSymbol],
+    risky: bool,
+    thresholds: &CheckThresholds,
+) {
+    let max_blast_radius = thresholds.max_blast_radius;
     let status = if risky {
         ("🔴", "FAIL", "High-risk change detected")
     } else {
@@ -811,8 +1072,28 @@ fn print_check_markdown(summary: &DiffSummary, risky: bool, max_blast_radius: us
         summary.files_likely_updates
     );
 
+    print_owner_markdown("👥 Teams Affected", &summary.teams_affected);
+    print_owner_markdown("✍️ Authors Affected", &summary.authors_affected);
+
+    if !complex.is_empty() {
+        println!("\n### 🧩 Complex Changed Code\n");
+        println!("| Symbol | Location | Complexity | Nesting | Centrality |");
+        println!("|--------|----------|------------|---------|------------|");
+        for symbol in complex {
+            println!(
+                "| `{}` | `{}:{}` | {} | {} | {:.4} |",
+                symbol.name,
+                symbol.file,
+                symbol.line,
+                symbol.cyclomatic,
+                symbol.nesting,
+                symbol.centrality
+            );
+        }
+    }
+
     if risky {
-        println!("\n> 🚨 **Action Required:** This PR exceeds the blast radius threshold. Review the impact carefully before merging.");
+        println!("\n> 🚨 **Action Required:** This PR exceeds the blast radius or complexity thresholds. Review the impact carefully before merging.");
     }
 
     println!("\n---");
@@ -1034,6 +1315,8 @@ fn index_changed_only(path: &Path, output: Option<&Path>, follow_symlinks: bool)
         "⚡".cyan()
     );
     let base_graph = load_or_index_graph(path)?;
+    arbor_core::languages::load_plugins(path);
+    arbor_core::topics::load_topic_patterns(path);
 
     let mut retained_nodes = Vec::new();
     for node in base_graph.nodes() {
@@ -1058,12 +1341,7 @@ fn index_changed_only(path: &Path, output: Option<&Path>, follow_symlinks: bool)
             continue;
         }
 
-        let extension = match abs.extension().and_then(|e| e.to_str()) {
-            Some(ext) => ext,
-            None => continue,
-        };
-
-        if !arbor_core::languages::is_supported(extension) {
+        if !arbor_core::languages::is_supported_file(&abs) {
             continue;
         }
 
@@ -1079,6 +1357,7 @@ fn index_changed_only(path: &Path, output: Option<&Path>, follow_symlinks: bool)
     }
 
     let mut builder = arbor_graph::GraphBuilder::new();
+    builder.add_declarations(load_declarations(path));
     builder.add_nodes(retained_nodes);
     builder.add_nodes(parsed_nodes);
     let graph = builder.build();
@@ -1124,6 +1403,14 @@ fn export_graph(graph: &arbor_graph::ArborGraph, path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Test files, and test functions next to production code (`test_*` in
+/// Python and Rust, `Test*` in Go).
+fn is_test_node(node: &arbor_core::CodeNode) -> bool {
+    is_test_file(&node.file)
+        || node.name.starts_with("test_")
+        || (node.name.starts_with("Test") && node.file.ends_with(".go"))
+}
+
 fn is_test_file(file_path: &str) -> bool {
     let lower = file_path.to_lowercase().replace('\\', "/");
     let segments: Vec<&str> = lower.split('/').collect();
@@ -1160,36 +1447,35 @@ fn is_test_file(file_path: &str) -> bool {
         || filename == "conftest.py"
 }
 
-pub fn query(query: &str, limit: usize, path: &Path, exclude_test: bool) -> Result<()> {
+pub fn query(
+    query: &str,
+    limit: usize,
+    path: &Path,
+    exclude_test: bool,
+    semantic: bool,
+) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let _ = ensure_arbor_initialized(&resolved_path)?;
     let graph = load_or_index_graph(&resolved_path)?;
 
-    let terms: Vec<&str> = query
-        .split('|')
-        .map(str::trim)
-        .filter(|s| !s.is_empty())
-        .collect();
-
-    let mut seen_ids = std::collections::HashSet::new();
-    let mut matches = Vec::new();
-
-    for term in &terms {
-        for node in graph.search(term) {
-            if exclude_test && is_test_file(&node.file) {
-                continue;
-            }
-            if seen_ids.insert(&node.id) {
-                matches.push(node);
-            }
-            if matches.len() >= limit {
-                break;
-            }
-        }
-        if matches.len() >= limit {
-            break;
-        }
-    }
+    let matches: Vec<_> = if semantic {
+        let (index, provider) = load_embedding_index(&resolved_path, &graph)?;
+        index
+            .search(provider.as_ref(), query, limit * 2)?
+            .into_iter()
+            .filter_map(|hit| graph.get_by_id(&hit.node_id))
+            .filter(|node| !(exclude_test && is_test_file(&node.file)))
+            .take(limit)
+            .collect()
+    } else {
+        // Ranked search; '|' separates words like whitespace, so "a|b" ranks either
+        graph
+            .search(query)
+            .into_iter()
+            .filter(|node| !(exclude_test && is_test_file(&node.file)))
+            .take(limit)
+            .collect()
+    };
 
     if matches.is_empty() {
         if exclude_test {
@@ -1217,7 +1503,269 @@ pub fn query(query: &str, limit: usize, path: &Path, exclude_test: bool) -> Resu
     Ok(())
 }
 
-pub fn diff(path: &Path, depth: usize, json_output: bool, markdown: bool) -> Result<()> {
+/// Builds the embedding index for a project, reusing vectors cached in
+/// `.arbor/cache` and embedding only symbols that changed.
+fn load_embedding_index(
+    root: &Path,
+    graph: &arbor_graph::ArborGraph,
+) -> Result<(
+    arbor_graph::EmbeddingIndex,
+    Box<dyn arbor_graph::EmbeddingProvider>,
+)> {
+    let Some(config) = arbor_graph::EmbeddingConfig::load(root)? else {
+        return Err(format!(
+            "Embeddings are not configured. Create {} with a provider, e.g.\n\n  provider = \"command\"\n  command = [\"python3\", \"embed.py\"]",
+            arbor_graph::EMBEDDINGS_CONFIG
+        )
+        .into());
+    };
+    let provider = config.provider(root)?;
+
+    // The cache may be held by a running bridge; embed without it then.
+    let store = arbor_graph::GraphStore::open_or_reset(graph_store_path(root)).ok();
+    let mut reader = arbor_graph::SourceReader::with_root(root);
+    let (index, stats) = arbor_graph::EmbeddingIndex::build(
+        graph,
+        provider.as_ref(),
+        store.as_ref(),
+        &mut reader,
+        &config,
+    )?;
+    if stats.embedded > 0 {
+        eprintln!(
+            "{} Embedded {} symbols ({} cached)",
+            "ℹ".blue(),
+            stats.embedded,
+            stats.reused
+        );
+    }
+    Ok((index, provider))
+}
+
+pub fn similar(symbol: &str, limit: usize, path: &Path, json_output: bool) -> Result<()> {
+    let resolved_path = resolve_project_path(path)?;
+    let _ = ensure_arbor_initialized(&resolved_path)?;
+    let graph = load_or_index_graph(&resolved_path)?;
+
+    let node = graph
+        .get_by_id(symbol)
+        .or_else(|| graph.find_by_name(symbol).into_iter().next())
+        .ok_or_else(|| format!("Symbol '{}' not found", symbol))?;
+
+    let (index, _) = load_embedding_index(&resolved_path, &graph)?;
+    let hits: Vec<_> = index
+        .similar_to(&node.id, limit)
+        .into_iter()
+        .filter_map(|hit| Some((graph.get_by_id(&hit.node_id)?, hit.similarity)))
+        .collect();
+
+    if json_output {
+        let results: Vec<_> = hits
+            .iter()
+            .map(|(similar, similarity)| {
+                serde_json::json!({
+                    "id": similar.id,
+                    "name": similar.qualified_name,
+                    "kind": similar.kind.to_string(),
+                    "file": similar.file,
+                    "line": similar.line_start,
+                    "similarity": similarity,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({ "symbol": node.qualified_name, "similar": results });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No similar symbols found for {}", node.qualified_name);
+        return Ok(());
+    }
+
+    println!("Symbols similar to {}:\n", node.qualified_name.cyan());
+    for (similar, similarity) in hits {
+        println!(
+            "  {:.3}  {} {} {}",
+            similarity,
+            similar.kind.to_string().yellow(),
+            similar.qualified_name.cyan(),
+            format!("({}:{})", similar.file, similar.line_start).dimmed()
+        );
+    }
+    Ok(())
+}
+
+pub fn duplicates(
+    path: &Path,
+    min_tokens: usize,
+    threshold: f64,
+    limit: usize,
+    json_output: bool,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err("--threshold must be between 0.0 and 1.0".into());
+    }
+    let resolved_path = resolve_project_path(path)?;
+    let _ = ensure_arbor_initialized(&resolved_path)?;
+    let graph = load_or_index_graph(&resolved_path)?;
+
+    let options = arbor_graph::DuplicateOptions {
+        min_tokens,
+        threshold,
+    };
+    let mut groups = graph.find_duplicates(
+        &options,
+        &mut arbor_graph::SourceReader::with_root(&resolved_path),
+    );
+    let total = groups.len();
+    groups.truncate(limit);
+
+    if json_output {
+        let output = serde_json::json!({ "total": total, "groups": groups });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if groups.is_empty() {
+        println!("{} No duplicated functions found.", "✓".green());
+        return Ok(());
+    }
+
+    println!(
+        "Found {} clone group{} (showing {}):\n",
+        total,
+        if total == 1 { "" } else { "s" },
+        groups.len()
+    );
+    for (rank, group) in groups.iter().enumerate() {
+        println!(
+            "  {} similarity {:.2} · {} copies · centrality {:.3} · {}",
+            format!("#{}", rank + 1).yellow(),
+            group.similarity,
+            group.instances.len(),
+            group.combined_centrality,
+            group.languages.join(", ").dimmed()
+        );
+        for instance in &group.instances {
+            let info = &instance.node_info;
+            println!(
+                "      {} {} {} {}",
+                info.kind.yellow(),
+                info.qualified_name.cyan(),
+                format!("({}:{}-{})", info.file, info.line_start, info.line_end).dimmed(),
+                format!("{} tokens", instance.tokens).dimmed()
+            );
+        }
+        println!();
+    }
+    Ok(())
+}
+
+pub fn dead_code(
+    path: &Path,
+    exported: bool,
+    tests: bool,
+    framework_hooks: bool,
+    roots: Vec<String>,
+    json_output: bool,
+) -> Result<()> {
+    let resolved_path = resolve_project_path(path)?;
+    let _ = ensure_arbor_initialized(&resolved_path)?;
+    let graph = load_or_index_graph(&resolved_path)?;
+
+    let options = arbor_graph::DeadCodeOptions {
+        exported,
+        tests,
+        framework_hooks,
+        roots,
+    };
+    let report = graph.find_dead_code(&options);
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "Checked {} symbols from {} root{}{}.\n",
+        report.analyzed,
+        report.roots,
+        if report.roots == 1 { "" } else { "s" },
+        if tests {
+            format!(
+                " and {} test{}",
+                report.test_roots,
+                if report.test_roots == 1 { "" } else { "s" }
+            )
+        } else {
+            String::new()
+        }
+    );
+
+    if report.unreachable.is_empty() {
+        println!("{} No unreachable symbols found.", "✓".green());
+    } else {
+        println!(
+            "{} {} unreachable symbol{}:",
+            "✗".red(),
+            report.unreachable_count(),
+            if report.unreachable_count() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+        print_dead_files(&report.unreachable);
+    }
+
+    if !report.test_only.is_empty() {
+        println!(
+            "\n{} {} symbol{} only reached from tests:",
+            "!".yellow(),
+            report.test_only_count(),
+            if report.test_only_count() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+        print_dead_files(&report.test_only);
+    }
+    Ok(())
+}
+
+fn print_dead_files(files: &[arbor_graph::DeadFile]) {
+    for file in files {
+        println!("\n  {}", file.file.cyan());
+        for symbol in &file.symbols {
+            let info = &symbol.node_info;
+            let confidence = match symbol.confidence {
+                arbor_graph::ConfidenceLevel::High => "high".green(),
+                arbor_graph::ConfidenceLevel::Medium => "medium".yellow(),
+                arbor_graph::ConfidenceLevel::Low => "low".red(),
+            };
+            println!(
+                "      {} {} {} {}",
+                info.kind.yellow(),
+                info.qualified_name,
+                format!("(line {})", info.line_start).dimmed(),
+                confidence
+            );
+            for reason in &symbol.reasons {
+                println!("          {}", reason.dimmed());
+            }
+        }
+    }
+}
+
+pub fn diff(
+    path: &Path,
+    depth: usize,
+    authors: bool,
+    json_output: bool,
+    markdown: bool,
+) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let _ = ensure_arbor_initialized(&resolved_path)?;
 
@@ -1232,8 +1780,16 @@ pub fn diff(path: &Path, depth: usize, json_output: bool, markdown: bool) -> Res
     }
 
     let graph = load_or_index_graph(&resolved_path)?;
-    let changed_nodes = changed_node_ids(&graph, &changed_files, &resolved_path);
-    let summary = compute_diff_summary(&graph, changed_files, changed_nodes, depth, &resolved_path);
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
+    let mut ownership = arbor_graph::Ownership::load(&resolved_path, authors);
+    let summary = compute_diff_summary(
+        &graph,
+        changed_files,
+        changed_nodes,
+        depth,
+        &resolved_path,
+        Some(&mut ownership),
+    );
 
     if markdown {
         print_diff_markdown(&summary);
@@ -1250,7 +1806,9 @@ pub fn diff(path: &Path, depth: usize, json_output: bool, markdown: bool) -> Res
                 "api_entrypoints_affected": summary.entrypoints_affected,
                 "files_likely_require_updates": summary.files_likely_updates,
                 "blast_radius_nodes": summary.blast_radius_nodes
-            }
+            },
+            "teams_affected": summary.teams_affected,
+            "authors_affected": summary.authors_affected
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
         return Ok(());
@@ -1260,13 +1818,130 @@ pub fn diff(path: &Path, depth: usize, json_output: bool, markdown: bool) -> Res
     Ok(())
 }
 
+pub fn affected_tests(
+    path: &Path,
+    depth: usize,
+    format: Option<&str>,
+    json_output: bool,
+) -> Result<()> {
+    let runner = match format {
+        Some(name) => Some(arbor_graph::TestRunner::parse(name).ok_or_else(|| {
+            format!(
+                "unknown --format '{}' (expected cargo, pytest, go or jest)",
+                name
+            )
+        })?),
+        None => None,
+    };
+    let resolved_path = resolve_project_path(path)?;
+    let _ = ensure_arbor_initialized(&resolved_path)?;
+
+    if !is_git_repo(&resolved_path) {
+        return Err("arbor affected-tests requires a git repository".into());
+    }
+
+    let changed_files = git_changed_files(&resolved_path)?;
+    let graph = load_or_index_graph(&resolved_path)?;
+    // Only the symbols the diff touches, not every symbol in a changed file.
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
+    let impact = graph.affected_tests(&changed_nodes, depth);
+
+    if let Some(runner) = runner {
+        // Bare filter, for `cargo test -- $(arbor affected-tests --format cargo)`.
+        if let Some(filter) = impact.filter(runner, &resolved_path) {
+            println!("{}", filter);
+        }
+        return Ok(());
+    }
+
+    if json_output {
+        let filters: serde_json::Map<String, serde_json::Value> = impact
+            .runners()
+            .into_iter()
+            .filter_map(|r| {
+                let filter = impact.filter(r, &resolved_path)?;
+                Some((r.to_string(), serde_json::Value::String(filter)))
+            })
+            .collect();
+        let output = serde_json::json!({
+            "changed_files": changed_files,
+            "changed_symbols": impact.changed_symbols,
+            "tests": impact.tests,
+            "uncovered": impact.uncovered,
+            "filters": filters,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if changed_files.is_empty() {
+        println!("{} No modified files detected against HEAD.", "✓".green());
+        return Ok(());
+    }
+
+    println!(
+        "{} changed symbol{} in {} file{}.\n",
+        impact.changed_symbols,
+        if impact.changed_symbols == 1 { "" } else { "s" },
+        changed_files.len(),
+        if changed_files.len() == 1 { "" } else { "s" }
+    );
+
+    if impact.tests.is_empty() {
+        println!("{} No tests reach the changed code.", "!".yellow());
+    } else {
+        println!(
+            "{} affected test{}:",
+            impact.tests.len(),
+            if impact.tests.len() == 1 { "" } else { "s" }
+        );
+        for test in &impact.tests {
+            let info = &test.node_info;
+            println!(
+                "  {} {} {}",
+                info.qualified_name.cyan(),
+                format!("({}:{})", info.file, info.line_start).dimmed(),
+                format!("{} hop{}", test.hops, if test.hops == 1 { "" } else { "s" }).dimmed()
+            );
+        }
+
+        let runners = impact.runners();
+        if !runners.is_empty() {
+            println!("\nRun them with:");
+            for runner in runners {
+                if let Some(filter) = impact.filter(runner, &resolved_path) {
+                    println!("  {}", runner.command(&filter).green());
+                }
+            }
+        }
+    }
+
+    if !impact.uncovered.is_empty() {
+        println!(
+            "\n{} {} changed symbol{} not reached by any test:",
+            "!".yellow(),
+            impact.uncovered.len(),
+            if impact.uncovered.len() == 1 { "" } else { "s" }
+        );
+        for info in &impact.uncovered {
+            println!(
+                "  {} {} {}",
+                info.kind.yellow(),
+                info.qualified_name,
+                format!("({}:{})", info.file, info.line_start).dimmed()
+            );
+        }
+    }
+    Ok(())
+}
+
 pub fn check(
     path: &Path,
     depth: usize,
-    max_blast_radius: usize,
+    thresholds: CheckThresholds,
+    authors: bool,
     no_fail: bool,
-    json_output: bool,
-    markdown: bool,
+    format: CheckFormat,
 ) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let _ = ensure_arbor_initialized(&resolved_path)?;
@@ -1277,26 +1952,77 @@ pub fn check(
 
     let changed_files = git_changed_files(&resolved_path)?;
     let graph = load_or_index_graph(&resolved_path)?;
-    let changed_nodes = changed_node_ids(&graph, &changed_files, &resolved_path);
-    let summary = compute_diff_summary(&graph, changed_files, changed_nodes, depth, &resolved_path);
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
+    let complex = complex_changed_symbols(&graph, &changed_nodes, &thresholds);
+    let mut ownership = arbor_graph::Ownership::load(&resolved_path, authors);
+    let summary = compute_diff_summary(
+        &graph,
+        changed_files,
+        changed_nodes.clone(),
+        depth,
+        &resolved_path,
+        Some(&mut ownership),
+    );
 
+    let max_blast_radius = thresholds.max_blast_radius;
     let risky = summary.blast_radius_nodes > max_blast_radius
         || summary.entrypoints_affected > 0
-        || summary.indirect_callers > max_blast_radius / 2;
+        || summary.indirect_callers > max_blast_radius / 2
+        || !complex.is_empty();
 
-    if markdown {
-        print_check_markdown(&summary, risky, max_blast_radius);
+    if format == CheckFormat::Markdown {
+        print_check_markdown(&summary, &complex, risky, &thresholds);
         if risky && !no_fail {
             return Err("risky change set detected".into());
         }
         return Ok(());
     }
 
-    if json_output {
+    if format == CheckFormat::Sarif {
+        let mut sarif = crate::sarif::SarifBuilder::new(&resolved_path);
+        if risky {
+            let reason = format!(
+                "The change set's blast radius is {} nodes (limit {}), with {} entry points affected.",
+                summary.blast_radius_nodes, max_blast_radius, summary.entrypoints_affected
+            );
+            add_blast_radius_results(&mut sarif, &graph, &changed_nodes, depth, &reason);
+        }
+        for symbol in &complex {
+            if let Some(node) = graph.get_index(&symbol.id).and_then(|idx| graph.get(idx)) {
+                sarif.rule(
+                    "arbor/complexity",
+                    "Changed function is too complex",
+                    "A changed function exceeds the configured complexity or nesting limit",
+                    "error",
+                );
+                sarif.result(
+                    "arbor/complexity",
+                    "error",
+                    &format!(
+                        "'{}' has cyclomatic complexity {} and nesting depth {} (limits: {}, {}).",
+                        symbol.name,
+                        symbol.cyclomatic,
+                        symbol.nesting,
+                        thresholds
+                            .max_complexity
+                            .map_or("none".to_string(), |n| n.to_string()),
+                        thresholds
+                            .max_nesting
+                            .map_or("none".to_string(), |n| n.to_string())
+                    ),
+                    &arbor_graph::NodeInfo::from(node),
+                    &[],
+                );
+            }
+        }
+        println!("{}", serde_json::to_string_pretty(&sarif.finish())?);
+    } else if format == CheckFormat::Json {
         let output = serde_json::json!({
             "risky": risky,
             "thresholds": {
-                "max_blast_radius": max_blast_radius
+                "max_blast_radius": max_blast_radius,
+                "max_complexity": thresholds.max_complexity,
+                "max_nesting": thresholds.max_nesting
             },
             "summary": {
                 "changed_files": summary.changed_files,
@@ -1306,13 +2032,17 @@ pub fn check(
                 "api_entrypoints_affected": summary.entrypoints_affected,
                 "files_likely_require_updates": summary.files_likely_updates,
                 "blast_radius_nodes": summary.blast_radius_nodes
-            }
+            },
+            "teams_affected": summary.teams_affected,
+            "authors_affected": summary.authors_affected,
+            "complex_symbols": complex
         });
         println!("{}", serde_json::to_string_pretty(&output)?);
     } else if risky {
         println!("{}", "High risk refactor detected.".red().bold());
         println!();
         print_diff_summary(&summary);
+        print_complex_symbols(&complex);
         println!();
         println!("Recommendation: run integration tests.");
     } else {
@@ -1409,7 +2139,7 @@ pub async fn viz(path: &Path, follow_symlinks: bool) -> Result<()> {
     // 3. Start Sync Server (WebSocket Broadcast)
     let sync_port = 8081;
     let sync_addr = format!("127.0.0.1:{}", sync_port).parse()?;
-    let sync_config = arbor_server::SyncServerConfig {
+    let mut sync_config = arbor_server::SyncServerConfig {
         addr: sync_addr,
         watch_path: resolved_path.to_path_buf(),
         debounce_ms: 1000,
@@ -1440,8 +2170,27 @@ pub async fn viz(path: &Path, follow_symlinks: bool) -> Result<()> {
             "sh".to_string(),
             "bash".to_string(),
             "zsh".to_string(),
+            "scala".to_string(),
+            "sc".to_string(),
+            "lua".to_string(),
+            "ex".to_string(),
+            "exs".to_string(),
+            "zig".to_string(),
+            "proto".to_string(),
+            "thrift".to_string(),
+            "sql".to_string(),
+            "vue".to_string(),
+            "svelte".to_string(),
+            "astro".to_string(),
+            "html".to_string(),
+            "htm".to_string(),
         ],
     };
+    arbor_core::languages::load_plugins(&resolved_path);
+    arbor_core::topics::load_topic_patterns(&resolved_path);
+    sync_config
+        .extensions
+        .extend(arbor_core::languages::plugin_extensions(&resolved_path));
     let sync_server = arbor_server::SyncServer::new_with_shared(sync_config, shared_graph.clone());
 
     // Spawn servers
@@ -1575,6 +2324,7 @@ pub fn status(path: &Path, show_files: bool) -> Result<()> {
     println!("  {} {}", "Files indexed:".dimmed(), result.files_indexed);
     println!("  {} {}", "Nodes:".dimmed(), result.nodes_extracted);
     println!("  {} {}", "Edges:".dimmed(), result.graph.edge_count());
+    print_parse_health(&result.graph, &resolved_path, show_files);
 
     if show_files {
         println!();
@@ -1622,13 +2372,78 @@ pub fn status(path: &Path, show_files: bool) -> Result<()> {
     if result.nodes_extracted == 0 && result.files_indexed > 0 {
         println!();
         println!(
-            "{} Files were scanned but no code nodes extracted.",
-            "💡".yellow()
+            "{} Files were scanned but no code nodes extracted.",
+            "💡".yellow()
+        );
+        println!("   This may happen if files contain only comments or imports.");
+    }
+
+    Ok(())
+}
+
+/// Prints the parse-health line for `status`; `detailed` lists the files with
+/// parse errors and where the errors are.
+fn print_parse_health(graph: &arbor_graph::ArborGraph, root: &Path, detailed: bool) {
+    let diagnostics = graph.all_file_diagnostics();
+    let health = arbor_core::ParseHealth::summarize(diagnostics);
+    if health.files == 0 {
+        return;
+    }
+
+    let mut parts = vec![format!(
+        "{:.1}% avg coverage",
+        health.average_coverage * 100.0
+    )];
+    if health.files_with_errors > 0 {
+        parts.push(format!(
+            "{} file(s) with parse errors",
+            health.files_with_errors
+        ));
+    }
+    if health.fallback_files > 0 {
+        parts.push(format!("{} via fallback parser", health.fallback_files));
+    }
+    if health.low_coverage_files > 0 {
+        parts.push(format!("{} low coverage", health.low_coverage_files));
+    }
+    let line = parts.join(" · ");
+    if health.files_with_errors > 0 {
+        println!("  {} {}", "Parse health:".dimmed(), line.yellow());
+    } else {
+        println!("  {} {}", "Parse health:".dimmed(), line);
+    }
+
+    if !detailed || health.worst_files.is_empty() {
+        return;
+    }
+    println!();
+    println!("  {}", "Files with parse errors:".yellow());
+    for file in health.worst_files.iter().take(20) {
+        let diag = &diagnostics[file];
+        let display = Path::new(file)
+            .strip_prefix(root)
+            .unwrap_or(Path::new(file))
+            .display();
+        let lines: Vec<String> = diag
+            .error_ranges
+            .iter()
+            .take(3)
+            .map(|r| {
+                if r.line_start == r.line_end {
+                    format!("L{}", r.line_start)
+                } else {
+                    format!("L{}-{}", r.line_start, r.line_end)
+                }
+            })
+            .collect();
+        println!(
+            "    {} — {} error(s) at {}, {:.0}% covered",
+            display,
+            diag.error_count,
+            lines.join(", "),
+            diag.coverage * 100.0
         );
-        println!("   This may happen if files contain only comments or imports.");
     }
-
-    Ok(())
 }
 
 /// Start the Agentic Bridge (MCP + Viz).
@@ -1693,7 +2508,7 @@ pub async fn bridge(
 
     let arbor_server = ArborServer::new_with_shared(shared_graph.clone(), rpc_config);
 
-    let sync_config = arbor_server::SyncServerConfig {
+    let mut sync_config = arbor_server::SyncServerConfig {
         addr: format!("127.0.0.1:{}", sync_port).parse()?,
         watch_path: resolved_path.to_path_buf(),
         debounce_ms: 1000,
@@ -1724,8 +2539,27 @@ pub async fn bridge(
             "sh".to_string(),
             "bash".to_string(),
             "zsh".to_string(),
+            "scala".to_string(),
+            "sc".to_string(),
+            "lua".to_string(),
+            "ex".to_string(),
+            "exs".to_string(),
+            "zig".to_string(),
+            "proto".to_string(),
+            "thrift".to_string(),
+            "sql".to_string(),
+            "vue".to_string(),
+            "svelte".to_string(),
+            "astro".to_string(),
+            "html".to_string(),
+            "htm".to_string(),
         ],
     };
+    arbor_core::languages::load_plugins(&resolved_path);
+    arbor_core::topics::load_topic_patterns(&resolved_path);
+    sync_config
+        .extensions
+        .extend(arbor_core::languages::plugin_extensions(&resolved_path));
 
     let sync_server = arbor_server::SyncServer::new_with_shared(sync_config, shared_graph.clone());
     let spotlight_handle = sync_server.handle();
@@ -1931,7 +2765,82 @@ pub async fn check_health(path: Option<&Path>) -> Result<()> {
             .or_else(|_| load_graph_snapshot(&workspace_root))
             .or_else(|_| load_graph_from_store(&workspace_root))
         {
-            Ok(_) => println!("{} Cache and snapshot readable", "✓".green()),
+            Ok(graph) => {
+                println!("{} Cache and snapshot readable", "✓".green());
+
+                // 7b. Parse health of the indexed files
+                let health = arbor_core::ParseHealth::summarize(graph.all_file_diagnostics());
+                if health.files == 0 {
+                    println!(
+                        "{} No parse diagnostics recorded (re-run 'arbor index')",
+                        "•".blue()
+                    );
+                } else if health.files_with_errors == 0 && health.low_coverage_files == 0 {
+                    println!(
+                        "{} All {} files parsed cleanly ({:.1}% avg coverage)",
+                        "✓".green(),
+                        health.files,
+                        health.average_coverage * 100.0
+                    );
+                } else {
+                    println!(
+                        "{} {} of {} files have parse errors, {} low coverage — impact results touching them are less reliable",
+                        "⚠".yellow(),
+                        health.files_with_errors,
+                        health.files,
+                        health.low_coverage_files
+                    );
+                    for file in health.worst_files.iter().take(5) {
+                        let display = Path::new(file)
+                            .strip_prefix(&workspace_root)
+                            .unwrap_or(Path::new(file))
+                            .display();
+                        println!("    {}", display.to_string().dimmed());
+                    }
+                    if health.worst_files.len() > 5 {
+                        println!(
+                            "    ... and {} more ('arbor status --files')",
+                            health.worst_files.len() - 5
+                        );
+                    }
+                }
+                if health.fallback_files > 0 {
+                    println!(
+                        "{} {} files parsed by the fallback parser (no grammar)",
+                        "•".blue(),
+                        health.fallback_files
+                    );
+                }
+
+                // 7c. Declared edges whose endpoints no longer resolve
+                if let Err(e) = arbor_graph::Declarations::load(&workspace_root) {
+                    println!("{} {}", "⚠".yellow(), e);
+                    all_ok = false;
+                }
+                let unresolved = graph.unresolved_declarations();
+                if !unresolved.is_empty() {
+                    println!(
+                        "{} {} declared edges or aliases no longer resolve",
+                        "⚠".yellow(),
+                        unresolved.len()
+                    );
+                    for declaration in unresolved {
+                        let display = Path::new(&declaration.source)
+                            .strip_prefix(&workspace_root)
+                            .unwrap_or(Path::new(&declaration.source))
+                            .display();
+                        println!(
+                            "    {}",
+                            format!(
+                                "{}: {} ({})",
+                                display, declaration.declaration, declaration.problem
+                            )
+                            .dimmed()
+                        );
+                    }
+                    all_ok = false;
+                }
+            }
             Err(e) => {
                 println!("{} Cache may be corrupted: {}", "⚠".yellow(), e);
                 all_ok = false;
@@ -2033,15 +2942,28 @@ pub fn refactor(
                 "name": n.node_info.name,
                 "severity": n.severity.as_str(),
                 "hop_distance": n.hop_distance,
-                "entry_edge": n.entry_edge.to_string()
+                "entry_edge": n.entry_edge.to_string(),
+                "probability": n.probability,
+                "trace": n.trace
             })).collect::<Vec<_>>(),
             "downstream": analysis.downstream.iter().map(|n| serde_json::json!({
                 "id": n.node_info.id,
                 "name": n.node_info.name,
                 "severity": n.severity.as_str(),
                 "hop_distance": n.hop_distance,
-                "entry_edge": n.entry_edge.to_string()
+                "entry_edge": n.entry_edge.to_string(),
+                "probability": n.probability,
+                "trace": n.trace
+            })).collect::<Vec<_>>(),
+            "possibly_affected": analysis.possibly_affected.iter().map(|n| serde_json::json!({
+                "id": n.node_info.id,
+                "name": n.node_info.name,
+                "direction": n.direction.to_string(),
+                "hop_distance": n.hop_distance,
+                "probability": n.probability,
+                "trace": n.trace
             })).collect::<Vec<_>>(),
+            "uncertain_edges": analysis.uncertain_edges,
             "total_affected": analysis.total_affected,
             "query_time_ms": analysis.query_time_ms
         });
@@ -2059,7 +2981,7 @@ pub fn refactor(
     println!();
 
     // Compute and display confidence
-    let confidence = arbor_graph::ConfidenceExplanation::from_analysis(&analysis);
+    let confidence = arbor_graph::ConfidenceExplanation::from_graph_analysis(&analysis, &graph);
     let role = arbor_graph::NodeRole::from_analysis(&analysis);
 
     let confidence_color = match confidence.level {
@@ -2091,18 +3013,33 @@ pub fn refactor(
 
         // 1. Why this confidence level?
         println!("{}", "📊 Why this confidence level?".cyan());
-        match confidence.level {
-            arbor_graph::ConfidenceLevel::High => {
-                println!("   • High caller count indicates well-integrated code");
-                println!("   • Clear static call graph with minimal uncertainty");
-            }
-            arbor_graph::ConfidenceLevel::Medium => {
-                println!("   • Moderate caller count or some uncertain edges");
-                println!("   • May have dynamic dispatch or callback patterns");
+        let mut unsure: Vec<_> = analysis
+            .all_affected()
+            .into_iter()
+            .chain(&analysis.possibly_affected)
+            .filter(|n| !n.trace.is_empty())
+            .collect();
+        unsure.sort_by(|a, b| a.probability.total_cmp(&b.probability));
+        match confidence.probability {
+            None => println!("   • Nothing affected was found statically"),
+            Some(_) if unsure.is_empty() => {
+                println!("   • Every path is an exact match through cleanly parsed files")
             }
-            arbor_graph::ConfidenceLevel::Low => {
-                println!("   • Few or no callers detected statically");
-                println!("   • May be called via reflection, DI, or externally");
+            Some(_) => {
+                println!("   • Least certain nodes and what lowered them:");
+                for node in unsure.iter().take(5) {
+                    println!(
+                        "     {} {:.0}%",
+                        node.node_info.name.yellow(),
+                        node.probability * 100.0
+                    );
+                    for factor in &node.trace {
+                        println!("       └─ {}", factor.to_string().dimmed());
+                    }
+                }
+                if unsure.len() > 5 {
+                    println!("     ... and {} more", unsure.len() - 5);
+                }
             }
         }
         println!();
@@ -2360,6 +3297,35 @@ pub fn refactor(
         }
     }
 
+    if !analysis.possibly_affected.is_empty() {
+        println!();
+        println!(
+            "{}",
+            "Possibly affected (dispatch, callbacks, DI):".yellow()
+        );
+        for node in analysis.possibly_affected.iter().take(5) {
+            println!(
+                "  • {} ({}, {} hop{})",
+                node.node_info.name.cyan(),
+                node.direction,
+                node.hop_distance,
+                if node.hop_distance == 1 { "" } else { "s" }
+            );
+        }
+        if analysis.possibly_affected.len() > 5 {
+            println!("  • ... and {} more", analysis.possibly_affected.len() - 5);
+        }
+        if show_why {
+            for edge in &analysis.uncertain_edges {
+                println!(
+                    "    {} {:.0}%",
+                    edge.reason.dimmed(),
+                    edge.confidence * 100.0
+                );
+            }
+        }
+    }
+
     println!();
     println!("{}", format!("File: {}", target_node.file).dimmed());
 
@@ -2635,7 +3601,8 @@ pub fn pr_summary(symbols: &str, path: &Path) -> Result<()> {
         if let Some(idx) = node_idx {
             let node = graph.get(idx).unwrap();
             let analysis = graph.analyze_impact(idx, 3);
-            let confidence = arbor_graph::ConfidenceExplanation::from_analysis(&analysis);
+            let confidence =
+                arbor_graph::ConfidenceExplanation::from_graph_analysis(&analysis, &graph);
             let role = arbor_graph::NodeRole::from_analysis(&analysis);
 
             println!("### `{}`", node.name);
@@ -2680,15 +3647,16 @@ pub fn summary(path: &Path) -> Result<()> {
     }
 
     let graph = load_or_index_graph(&resolved_path)?;
-    let changed_nodes = changed_node_ids(&graph, &changed_files, &resolved_path);
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
 
     // Reuse our depth=5 summary computation
     let summary = compute_diff_summary(
         &graph,
         changed_files.clone(),
-        changed_nodes,
+        changed_nodes.clone(),
         5,
         &resolved_path,
+        Some(&mut arbor_graph::Ownership::load(&resolved_path, false)),
     );
 
     // Classify changes
@@ -2790,6 +3758,19 @@ pub fn summary(path: &Path) -> Result<()> {
     };
     println!("**Risk Classification:** {}\n", risk_emoji);
 
+    if let Some(history) =
+        arbor_graph::ChangeHistory::load(&resolved_path, arbor_graph::HISTORY_COMMITS)
+    {
+        let report = graph.analyze_history(&history, &arbor_graph::HistoryOptions::default());
+        print_history_markdown(
+            &report,
+            &graph,
+            &changed_nodes,
+            &changed_files,
+            &resolved_path,
+        );
+    }
+
     // Suggested reviewers based on files modified
     println!("### 👥 Suggested Reviewers");
     let mut reviewers = std::collections::HashSet::new();
@@ -2836,6 +3817,63 @@ pub fn summary(path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Churn of the changed symbols, and files that usually change with the
+/// changed ones but were left out of this change.
+fn print_history_markdown(
+    report: &arbor_graph::HistoryReport,
+    graph: &arbor_graph::ArborGraph,
+    changed_nodes: &[arbor_graph::NodeId],
+    changed_files: &[String],
+    root: &Path,
+) {
+    let mut churned: Vec<&arbor_graph::SymbolChurn> = changed_nodes
+        .iter()
+        .filter_map(|&id| report.symbol(&graph.get(id)?.id))
+        .collect();
+    churned.sort_by(|a, b| b.risk.total_cmp(&a.risk));
+
+    let changed: std::collections::HashSet<&str> =
+        changed_files.iter().map(String::as_str).collect();
+    let mut partners: Vec<(&str, &str, &arbor_graph::FileCoupling)> = Vec::new();
+    for file in changed_files {
+        for (partner, coupling) in report.hidden_partners(root, file) {
+            if !changed.contains(partner) && !partners.iter().any(|(p, _, _)| *p == partner) {
+                partners.push((partner, file, coupling));
+            }
+        }
+    }
+
+    if churned.is_empty() && partners.is_empty() {
+        return;
+    }
+    println!("### 🔥 Change History");
+    println!("From the last {} commit(s).\n", report.commits_analyzed);
+    if !churned.is_empty() {
+        println!("| Symbol | Commits | Authors | Risk |");
+        println!("|--------|---------|---------|------|");
+        for symbol in churned.iter().take(10) {
+            println!(
+                "| `{}` | {} | {} | {:.2} |",
+                symbol.node_info.qualified_name, symbol.commits, symbol.authors, symbol.risk
+            );
+        }
+        println!();
+    }
+    if !partners.is_empty() {
+        println!("**Usually changed together, but not in this PR** (no static edge):");
+        for (partner, file, coupling) in partners.iter().take(10) {
+            println!(
+                "- `{}` changed with `{}` in {} commits ({:.0}%)",
+                partner,
+                file,
+                coupling.shared_commits,
+                coupling.degree * 100.0
+            );
+        }
+        println!();
+    }
+}
+
 /// Periodically persists the live graph to `graph.bin` while a bridge runs.
 ///
 /// Only one bridge per project wins the persist lock — additional bridges for
@@ -2940,7 +3978,10 @@ pub async fn watch(path: &Path) -> Result<()> {
 #[cfg(test)]
 #[allow(clippy::items_after_test_module)]
 mod tests {
-    use super::{is_generated_or_internal_path, parse_git_name_status_output};
+    use super::{
+        add_blast_radius_results, complex_changed_symbols, is_generated_or_internal_path,
+        parse_git_name_status_output, CheckThresholds,
+    };
     use std::path::PathBuf;
 
     /// Returns the platform-specific bundled visualizer path relative to exe_dir.
@@ -3085,29 +4126,125 @@ mod tests {
         let long_input = "a".repeat(300);
         assert!(!command_exists(&long_input));
     }
+
+    #[test]
+    fn test_complex_changed_symbols_respects_thresholds() {
+        use arbor_core::{CodeNode, NodeKind};
+
+        let mut graph = arbor_graph::ArborGraph::new();
+        let mut ids = Vec::new();
+        for (name, cyclomatic, nesting) in [("simple", 2, 1), ("tangled", 12, 2), ("deep", 3, 5)] {
+            let mut node = CodeNode::new(name, name, NodeKind::Function, "src/lib.rs");
+            node.metrics.insert("cyclomatic".to_string(), cyclomatic);
+            node.metrics.insert("nesting".to_string(), nesting);
+            ids.push(graph.add_node(node));
+        }
+        let thresholds = |max_complexity, max_nesting| CheckThresholds {
+            max_blast_radius: 25,
+            max_complexity,
+            max_nesting,
+        };
+        let names = |t: CheckThresholds| -> Vec<String> {
+            let mut names: Vec<String> = complex_changed_symbols(&graph, &ids, &t)
+                .into_iter()
+                .map(|s| s.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert!(names(thresholds(None, None)).is_empty());
+        assert_eq!(names(thresholds(Some(10), None)), ["tangled"]);
+        assert_eq!(names(thresholds(Some(10), Some(4))), ["deep", "tangled"]);
+    }
+
+    #[test]
+    fn test_blast_radius_sarif_flows_from_entry_point() {
+        use arbor_core::{CodeNode, NodeKind};
+        use arbor_graph::{Edge, EdgeKind};
+
+        let mut graph = arbor_graph::ArborGraph::new();
+        let mut add = |name: &str| {
+            graph.add_node(
+                CodeNode::new(
+                    name,
+                    name,
+                    NodeKind::Function,
+                    format!("/repo/src/{}.rs", name),
+                )
+                .with_lines(3, 9),
+            )
+        };
+        let (main, route, helper, unused) =
+            (add("main"), add("route"), add("helper"), add("unused"));
+        let test_helper = add("test_helper");
+        graph.add_edge(main, route, Edge::new(EdgeKind::Calls));
+        graph.add_edge(route, helper, Edge::new(EdgeKind::Calls));
+        // A test has no callers either, but it is not where traffic enters
+        graph.add_edge(test_helper, helper, Edge::new(EdgeKind::Calls));
+
+        let root = std::path::Path::new("/repo");
+        let mut sarif = crate::sarif::SarifBuilder::new(root);
+        add_blast_radius_results(&mut sarif, &graph, &[helper, unused], 5, "Over the limit.");
+        let log = sarif.finish();
+
+        // `unused` has no dependents, so only `helper` is reported
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "arbor/blast-radius");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/helper.rs"
+        );
+        let steps = results[0]["codeFlows"][0]["threadFlows"][0]["locations"]
+            .as_array()
+            .unwrap();
+        let names: Vec<&str> = steps
+            .iter()
+            .map(|s| {
+                s["location"]["logicalLocations"][0]["name"]
+                    .as_str()
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(names, ["main", "route", "helper"]);
+    }
 }
 
 /// Perform a security audit to find paths to a sensitive sink.
-pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()> {
+pub fn audit(
+    sink: Option<&str>,
+    depth: usize,
+    max_paths: usize,
+    format: &str,
+    path: &Path,
+) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
 
-    // 1. Load the graph
+    // 1. Load the graph and the source/sink/sanitizer rules
     let graph = load_or_index_graph(&resolved_path)?;
-    println!(
-        "{} Auditing security paths to sink: {}",
-        "🔍".cyan(),
-        sink.yellow().bold()
-    );
+    let catalog =
+        crate::audit::catalog::SecurityCatalog::load(&resolved_path).map_err(|e| e.to_string())?;
+    let target = sink.unwrap_or("all sinks");
+    if format == "text" {
+        println!(
+            "{} Auditing untrusted input flowing to: {}",
+            "🔍".cyan(),
+            target.yellow().bold()
+        );
+    }
 
     // 2. Configure audit
     let config = crate::audit::AuditConfig {
         max_depth: depth,
-        ignore_tests: true,
+        sink: sink.map(str::to_string),
+        max_paths,
+        ..Default::default()
     };
 
     // 3. Run audit
     let start = std::time::Instant::now();
-    let result = crate::audit::run_audit(&graph, sink, &config).map_err(|e| e.to_string())?;
+    let result = crate::audit::run_audit(&graph, &catalog, &config).map_err(|e| e.to_string())?;
     let duration = start.elapsed();
 
     // 4. Output results
@@ -3116,15 +4253,23 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
             println!("{}", serde_json::to_string_pretty(&result)?);
             return Ok(());
         }
+        "sarif" => {
+            let log = crate::sarif::audit_log(&result, &resolved_path);
+            println!("{}", serde_json::to_string_pretty(&log)?);
+            return Ok(());
+        }
         "csv" => {
-            println!("severity,entry_point,entry_file,path_length,trace");
+            println!("severity,source,source_rule,sink,sink_rule,source_file,path_length,trace");
             for audit_path in &result.paths {
                 let trace_str: Vec<&str> =
                     audit_path.trace.iter().map(|n| n.name.as_str()).collect();
                 println!(
-                    "{},{},{},{},\"{}\"",
+                    "{},{},{},{},{},{},{},\"{}\"",
                     audit_path.severity.label(),
                     audit_path.source.name,
+                    audit_path.source_rule.rule,
+                    audit_path.sink.name,
+                    audit_path.sink_rule.rule,
                     audit_path.source.file,
                     audit_path.trace.len(),
                     trace_str.join(" -> ")
@@ -3137,20 +4282,23 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
 
     // Text output
     println!(
-        "\n{} Found {} paths to sink in {:.2?}",
+        "\n{} Found {} unsanitized source → sink paths in {:.2?} ({} sanitized, {} sources, {} sinks)",
         if result.path_count > 0 {
             "⚠️".yellow()
         } else {
             "✓".green()
         },
         result.path_count,
-        duration
+        duration,
+        result.sanitized_count,
+        result.summary.sources_found,
+        result.summary.sinks_found
     );
 
     if result.path_count == 0 {
         println!(
-            "\nNo public entry points found leading to '{}'.",
-            sink.dimmed()
+            "\nNo untrusted input reaches {} without a sanitizer.",
+            target.dimmed()
         );
         return Ok(());
     }
@@ -3165,8 +4313,8 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
         result.summary.low_count
     );
     println!(
-        "│  Entry Points: {}  Files Touched: {}",
-        result.summary.unique_entry_points, result.summary.unique_files
+        "│  Sources: {}  Sinks: {}  Files Touched: {}",
+        result.summary.unique_sources, result.summary.unique_sinks, result.summary.unique_files
     );
     println!("{}", "└─────────────────────────────────────┘".dimmed());
 
@@ -3176,11 +4324,16 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
 
     for (i, audit_path) in result.paths.iter().take(15).enumerate() {
         println!(
-            "\n{} {}. {} → {}",
+            "\n{} {}. {} → {} {}",
             audit_path.severity.emoji(),
             i + 1,
             audit_path.source.name.green().bold(),
-            sink.red().bold()
+            audit_path.sink.name.red().bold(),
+            format!(
+                "({} → {})",
+                audit_path.source_rule.category, audit_path.sink_rule.category
+            )
+            .dimmed()
         );
         println!(
             "   {} {}  Depth: {}",
@@ -3194,20 +4347,37 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
             let is_last = j == audit_path.trace.len() - 1;
             let prefix = if is_last { "└─" } else { "├─" };
             let name = if j == 0 {
-                step.name.green().to_string()
-            } else if is_last {
-                step.name.red().to_string()
+                format!(
+                    "{} {}",
+                    step.name.green(),
+                    audit_path.source_rule.source_action().dimmed()
+                )
             } else {
                 step.name.white().to_string()
             };
+            let name = if is_last {
+                format!(
+                    "{} {}",
+                    if j == 0 {
+                        name
+                    } else {
+                        step.name.red().to_string()
+                    },
+                    format!("→ {}", audit_path.sink_rule.matched).red()
+                )
+            } else {
+                name
+            };
             println!("     {} {}", prefix.dimmed(), name);
         }
 
-        if !audit_path.uncertainty.is_empty() {
+        for edge in &audit_path.uncertainty {
             println!(
-                "   {} {}",
+                "   {} {} ({:.0}%): {}",
                 "⚠ Heuristic:".yellow(),
-                audit_path.uncertainty.join(", ")
+                edge.kind,
+                edge.confidence * 100.0,
+                edge.reason
             );
         }
     }
@@ -3220,17 +4390,33 @@ pub fn audit(sink: &str, depth: usize, format: &str, path: &Path) -> Result<()>
         );
     }
 
+    if !result.truncated.is_empty() {
+        println!("\n{}", "Truncated Sinks:".yellow().bold());
+        for truncated in &result.truncated {
+            let reasons: Vec<String> = truncated.reasons.iter().map(|r| r.to_string()).collect();
+            println!(
+                "  {} ~{} paths, not all listed ({})",
+                truncated.sink.name.red(),
+                truncated.estimated_paths,
+                reasons.join(", ")
+            );
+        }
+        println!(
+            "  {}",
+            "Raise --max-paths or --depth to list more, or narrow with a sink filter.".dimmed()
+        );
+    }
+
     // Remediation
     println!("\n{}", "Recommended Actions:".cyan().bold());
+    println!("  1. Validate or escape the input before it reaches the sinks above.");
     println!(
-        "  1. Review direct callers of '{}' for input validation.",
-        sink
+        "  2. Teach Arbor about project-specific sanitizers in {}.",
+        crate::audit::catalog::SECURITY_CONFIG.bold()
     );
-    println!("  2. Add sanitization at entry points marked CRITICAL/HIGH.");
     println!(
-        "  3. Export full report: {} {} --format csv",
-        "arbor audit".bold(),
-        sink
+        "  3. Upload to code scanning: {} --format sarif",
+        "arbor audit".bold()
     );
 
     Ok(())
@@ -3336,11 +4522,25 @@ pub fn callees(symbol: &str, path: &Path, json_output: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn entry_points(path: &Path, json_output: bool) -> Result<()> {
+pub fn entry_points(path: &Path, reaching: Option<&str>, json_output: bool) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let graph = load_or_index_graph(&resolved_path)?;
 
-    let eps = graph.list_entry_points();
+    let eps = match reaching {
+        Some(symbol) => graph.entry_points_reaching(resolve_symbol(&graph, symbol)?),
+        None => graph.list_entry_points(),
+    };
+    // Routes dispatch to handlers through call edges.
+    let handlers = |n: &arbor_core::CodeNode| -> Vec<String> {
+        match (&n.route, graph.get_index(&n.id)) {
+            (Some(_), Some(idx)) => graph
+                .get_callees(idx)
+                .iter()
+                .map(|h| h.qualified_name.clone())
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
 
     if json_output {
         let items: Vec<serde_json::Value> = eps
@@ -3351,27 +4551,54 @@ pub fn entry_points(path: &Path, json_output: bool) -> Result<()> {
                     "name": n.name,
                     "kind": n.kind.to_string(),
                     "file": n.file,
-                    "line": n.line_start
+                    "line": n.line_start,
+                    "route": n.route,
+                    "handlers": handlers(n)
                 })
             })
             .collect();
         println!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
+                "reaching": reaching,
                 "entry_points": items
             }))?
         );
     } else if eps.is_empty() {
-        println!("No entry points detected.");
+        match reaching {
+            Some(symbol) => println!("No entry points reach '{}'.", symbol),
+            None => println!("No entry points detected."),
+        }
     } else {
-        println!("Entry points ({}):\n", eps.len());
+        match reaching {
+            Some(symbol) => println!("Entry points reaching {} ({}):\n", symbol.cyan(), eps.len()),
+            None => println!("Entry points ({}):\n", eps.len()),
+        }
         for n in &eps {
-            println!(
-                "  {} {} {}",
-                n.kind.to_string().yellow(),
-                n.qualified_name.cyan(),
-                format!("({}:{})", n.file, n.line_start).dimmed()
-            );
+            let location = format!("({}:{})", n.file, n.line_start).dimmed();
+            match &n.route {
+                Some(route) => {
+                    let handlers = handlers(n);
+                    println!(
+                        "  {} {} {} {} {}",
+                        route.method.yellow(),
+                        route.path.cyan(),
+                        format!("[{}]", route.framework).dimmed(),
+                        if handlers.is_empty() {
+                            String::new()
+                        } else {
+                            format!("→ {}", handlers.join(", "))
+                        },
+                        location
+                    );
+                }
+                None => println!(
+                    "  {} {} {}",
+                    n.kind.to_string().yellow(),
+                    n.qualified_name.cyan(),
+                    location
+                ),
+            }
         }
     }
 
@@ -3486,7 +4713,27 @@ pub fn inspect(symbol: &str, path: &Path, json_output: bool) -> Result<()> {
         "internal"
     };
 
+    let outgoing = graph.edges_of(idx, petgraph::Direction::Outgoing);
+    let incoming = graph.edges_of(idx, petgraph::Direction::Incoming);
+
     if json_output {
+        let edges =
+            |edges: &[(arbor_graph::NodeId, &arbor_graph::Edge)]| -> Vec<serde_json::Value> {
+                edges
+                    .iter()
+                    .filter_map(|(other, edge)| {
+                        let other = graph.get(*other)?;
+                        Some(serde_json::json!({
+                            "id": other.id,
+                            "name": other.name,
+                            "kind": edge.kind,
+                            "provenance": edge.provenance,
+                            "reference": edge.reference,
+                            "confidence": edge.confidence
+                        }))
+                    })
+                    .collect()
+            };
         println!(
             "{}",
             serde_json::to_string_pretty(&serde_json::json!({
@@ -3501,7 +4748,10 @@ pub fn inspect(symbol: &str, path: &Path, json_output: bool) -> Result<()> {
                 "role": role,
                 "caller_count": callers.len(),
                 "callee_count": callees.len(),
-                "is_entry_point": is_entry
+                "is_entry_point": is_entry,
+                "metrics": node.metrics,
+                "edges_out": edges(&outgoing),
+                "edges_in": edges(&incoming)
             }))?
         );
     } else {
@@ -3521,77 +4771,200 @@ pub fn inspect(symbol: &str, path: &Path, json_output: bool) -> Result<()> {
         println!("  {}:     {:.4}", "Rank".bold(), centrality);
         println!("  {}:  {}", "Callers".bold(), callers.len());
         println!("  {}:  {}", "Callees".bold(), callees.len());
+        if !node.metrics.is_empty() {
+            let metrics: Vec<String> = node
+                .metrics
+                .iter()
+                .map(|(name, value)| format!("{} {}", name, value))
+                .collect();
+            println!("  {}:  {}", "Metrics".bold(), metrics.join(", "));
+        }
+        let describe = |edge: &arbor_graph::Edge| {
+            let mut how = format!("via {}", edge.provenance);
+            if let Some(reference) = &edge.reference {
+                how.push_str(&format!(" of '{}'", reference));
+            }
+            if edge.confidence < 1.0 {
+                how.push_str(&format!(", {:.0}%", edge.confidence * 100.0));
+            }
+            how
+        };
+        if !outgoing.is_empty() {
+            println!();
+            for (other, edge) in &outgoing {
+                if let Some(other) = graph.get(*other) {
+                    // Subscribes edges run from the topic to its subscriber.
+                    let relation = match edge.kind {
+                        arbor_graph::EdgeKind::Subscribes => "delivers to".to_string(),
+                        kind => kind.to_string(),
+                    };
+                    println!(
+                        "  {} {} {}",
+                        relation,
+                        other.qualified_name.cyan(),
+                        describe(edge).dimmed()
+                    );
+                }
+            }
+        }
+        if !incoming.is_empty() {
+            println!();
+            for (other, edge) in &incoming {
+                if let Some(other) = graph.get(*other) {
+                    if edge.kind == arbor_graph::EdgeKind::Subscribes {
+                        println!(
+                            "  {} subscribes to {} {}",
+                            "this".bold(),
+                            other.qualified_name.cyan(),
+                            describe(edge).dimmed()
+                        );
+                        continue;
+                    }
+                    println!(
+                        "  {} {} {} {}",
+                        other.qualified_name.cyan(),
+                        edge.kind,
+                        "this".bold(),
+                        describe(edge).dimmed()
+                    );
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
-pub fn find_path_cmd(start: &str, end: &str, path: &Path, json_output: bool) -> Result<()> {
+pub fn find_path_cmd(
+    start: &str,
+    end: &str,
+    path: &Path,
+    options: &arbor_graph::PathOptions,
+    json_output: bool,
+) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let graph = load_or_index_graph(&resolved_path)?;
 
     let start_idx = resolve_symbol(&graph, start)?;
     let end_idx = resolve_symbol(&graph, end)?;
 
-    let found = graph.find_path(start_idx, end_idx);
+    let found = graph.find_paths(start_idx, end_idx, options);
+    let paths: Vec<Vec<&arbor_core::CodeNode>> = found
+        .paths
+        .iter()
+        .map(|p| p.iter().filter_map(|id| graph.get(*id)).collect())
+        .collect();
+    let chokepoints: Vec<&arbor_core::CodeNode> = found
+        .chokepoints
+        .iter()
+        .filter_map(|id| graph.get(*id))
+        .collect();
 
     if json_output {
-        match &found {
-            Some(nodes) => {
-                let items: Vec<serde_json::Value> = nodes
-                    .iter()
-                    .map(|n| {
-                        serde_json::json!({
-                            "id": n.id,
-                            "name": n.name,
-                            "kind": n.kind.to_string(),
-                            "file": n.file,
-                            "line": n.line_start
-                        })
+        let items = |nodes: &[&arbor_core::CodeNode]| -> Vec<serde_json::Value> {
+            nodes
+                .iter()
+                .map(|n| {
+                    serde_json::json!({
+                        "id": n.id,
+                        "name": n.name,
+                        "kind": n.kind.to_string(),
+                        "file": n.file,
+                        "line": n.line_start
                     })
-                    .collect();
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&serde_json::json!({
-                        "start": start,
-                        "end": end,
-                        "path": items,
-                        "hops": items.len().saturating_sub(1)
-                    }))?
-                );
-            }
-            None => {
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&serde_json::json!({
-                        "start": start,
-                        "end": end,
-                        "path": null,
-                        "message": "No path found"
-                    }))?
-                );
+                })
+                .collect()
+        };
+        let all: Vec<serde_json::Value> = paths
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "hops": p.len().saturating_sub(1),
+                    "nodes": items(p)
+                })
+            })
+            .collect();
+        let mut output = serde_json::json!({
+            "start": start,
+            "end": end,
+            "path": paths.first().map(|p| items(p)),
+            "hops": paths.first().map(|p| p.len().saturating_sub(1)),
+            "paths": all,
+            "estimated_paths": found.estimated_paths,
+            "count_exact": found.count_exact,
+            "chokepoints": items(&chokepoints),
+            "truncated": found.truncated,
+            "elapsed_ms": found.elapsed_ms
+        });
+        if paths.is_empty() {
+            output["message"] = serde_json::json!("No path found");
+        }
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if paths.is_empty() {
+        println!("No path found between '{}' and '{}'", start, end);
+        if found
+            .truncated
+            .contains(&arbor_graph::PathTruncation::MaxDepth)
+        {
+            println!(
+                "{}",
+                format!(
+                    "(searched up to {} hops; try --max-depth)",
+                    options.max_depth
+                )
+                .dimmed()
+            );
+        }
+        return Ok(());
+    }
+
+    for (k, nodes) in paths.iter().enumerate() {
+        if paths.len() > 1 {
+            println!(
+                "Path {} of {} ({} hops):\n",
+                k + 1,
+                paths.len(),
+                nodes.len().saturating_sub(1)
+            );
+        } else {
+            println!("Path ({} hops):\n", nodes.len().saturating_sub(1));
+        }
+        for (i, n) in nodes.iter().enumerate() {
+            if i > 0 {
+                println!("    {}", "↓".dimmed());
             }
+            println!(
+                "  {} {} {}",
+                n.kind.to_string().yellow(),
+                n.qualified_name.cyan(),
+                format!("({}:{})", n.file, n.line_start).dimmed()
+            );
         }
+        println!();
+    }
+
+    let count = if found.count_exact {
+        found.estimated_paths.to_string()
     } else {
-        match &found {
-            Some(nodes) => {
-                println!("Path ({} hops):\n", nodes.len().saturating_sub(1));
-                for (i, n) in nodes.iter().enumerate() {
-                    if i > 0 {
-                        println!("    {}", "↓".dimmed());
-                    }
-                    println!(
-                        "  {} {} {}",
-                        n.kind.to_string().yellow(),
-                        n.qualified_name.cyan(),
-                        format!("({}:{})", n.file, n.line_start).dimmed()
-                    );
-                }
-            }
-            None => {
-                println!("No path found between '{}' and '{}'", start, end);
+        format!("~{}", found.estimated_paths)
+    };
+    println!("{} path(s) within {} hops", count, options.max_depth);
+    if !chokepoints.is_empty() {
+        let names: Vec<&str> = chokepoints.iter().map(|n| n.name.as_str()).collect();
+        println!("Every path goes through: {}", names.join(" → ").yellow());
+    }
+    for reason in &found.truncated {
+        let note = match reason {
+            arbor_graph::PathTruncation::ResultLimit => "more paths exist; raise --k to see them",
+            arbor_graph::PathTruncation::TimeBudget => "stopped at the time budget",
+            arbor_graph::PathTruncation::MaxDepth => {
+                "longer paths exist; raise --max-depth to follow them"
             }
-        }
+        };
+        println!("{}", format!("Truncated ({}): {}", reason, note).dimmed());
     }
 
     Ok(())
@@ -3909,13 +5282,14 @@ pub fn agent_review(path: &Path, json: bool) -> Result<()> {
     }
 
     let graph = load_or_index_graph(&resolved_path)?;
-    let changed_nodes = changed_node_ids(&graph, &changed_files, &resolved_path);
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
     let summary = compute_diff_summary(
         &graph,
         changed_files.clone(),
         changed_nodes.clone(),
         5,
         &resolved_path,
+        Some(&mut arbor_graph::Ownership::load(&resolved_path, false)),
     );
 
     let mut high_risk_changes = Vec::new();
@@ -4253,7 +5627,12 @@ pub fn agent_onboard(path: &Path, json: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
+pub fn agent_guard(
+    path: &Path,
+    max_blast_radius: usize,
+    depth: usize,
+    sarif_output: bool,
+) -> Result<()> {
     let resolved_path = resolve_project_path(path)?;
     let _ = ensure_arbor_initialized(&resolved_path)?;
 
@@ -4262,6 +5641,11 @@ pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
     }
 
     let changed_files = git_changed_files(&resolved_path)?;
+    if changed_files.is_empty() && sarif_output {
+        let sarif = crate::sarif::SarifBuilder::new(&resolved_path);
+        println!("{}", serde_json::to_string_pretty(&sarif.finish())?);
+        return Ok(());
+    }
     if changed_files.is_empty() {
         println!(
             "{} No changes detected. Architecture guard PASS.",
@@ -4271,19 +5655,31 @@ pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
     }
 
     let graph = load_or_index_graph(&resolved_path)?;
-    let changed_nodes = changed_node_ids(&graph, &changed_files, &resolved_path);
+    let changed_nodes = touched_node_ids(&graph, &changed_files, &resolved_path);
     let summary = compute_diff_summary(
         &graph,
         changed_files.clone(),
         changed_nodes.clone(),
-        5,
+        depth,
         &resolved_path,
+        None,
     );
 
     let mut failed = false;
     let mut checks = Vec::new();
+    let mut sarif = crate::sarif::SarifBuilder::new(&resolved_path);
 
     if summary.blast_radius_nodes > max_blast_radius {
+        add_blast_radius_results(
+            &mut sarif,
+            &graph,
+            &changed_nodes,
+            depth,
+            &format!(
+                "The change set's blast radius of {} nodes exceeds the guard limit of {}.",
+                summary.blast_radius_nodes, max_blast_radius
+            ),
+        );
         checks.push(format!(
             "❌ Blast radius of {} nodes exceeds limit of {}",
             summary.blast_radius_nodes, max_blast_radius
@@ -4301,6 +5697,19 @@ pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
         if let Some(node) = graph.get(*node_id) {
             if arbor_graph::HeuristicsMatcher::is_likely_entry_point(node) {
                 changed_entries.push(node.name.clone());
+                sarif.rule(
+                    "arbor/guard/entry-point",
+                    "Public entry point modified",
+                    "The change modifies a public entry point such as a route handler or main",
+                    "error",
+                );
+                sarif.result(
+                    "arbor/guard/entry-point",
+                    "error",
+                    &format!("Public entry point '{}' is modified.", node.name),
+                    &arbor_graph::NodeInfo::from(node),
+                    &[],
+                );
             }
         }
     }
@@ -4320,6 +5729,24 @@ pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
         if graph.centrality(*node_id) > 0.4 {
             if let Some(node) = graph.get(*node_id) {
                 changed_hubs.push(node.name.clone());
+                let mut info = arbor_graph::NodeInfo::from(node);
+                info.centrality = graph.centrality(*node_id);
+                sarif.rule(
+                    "arbor/guard/hub",
+                    "High-centrality hub modified",
+                    "The change modifies a symbol much of the codebase depends on",
+                    "error",
+                );
+                sarif.result(
+                    "arbor/guard/hub",
+                    "error",
+                    &format!(
+                        "High-centrality hub '{}' (centrality {:.2}) is modified.",
+                        node.name, info.centrality
+                    ),
+                    &info,
+                    &[],
+                );
             }
         }
     }
@@ -4334,6 +5761,14 @@ pub fn agent_guard(path: &Path, max_blast_radius: usize) -> Result<()> {
         checks.push("✅ No high-centrality hubs modified".to_string());
     }
 
+    if sarif_output {
+        println!("{}", serde_json::to_string_pretty(&sarif.finish())?);
+        if failed {
+            return Err("Architecture guard failed".into());
+        }
+        return Ok(());
+    }
+
     println!("# 🌳 Arbor Agent: Architecture Guard\n");
     if failed {
         println!("## Status: ❌ FAIL\n");
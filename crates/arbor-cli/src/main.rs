@@ -11,6 +11,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 mod audit;
 mod commands;
 mod hook;
+mod sarif;
 
 #[derive(Parser)]
 #[command(name = "arbor")]
@@ -22,6 +23,19 @@ struct Cli {
     #[arg(short, long, global = true)]
     verbose: bool,
 
+    /// Load grammar plugins from the project's .arbor/languages/. They are
+    /// native code from the checkout, so only use this on trusted repositories
+    /// (or set ARBOR_ALLOW_PLUGINS=1)
+    #[arg(long, global = true)]
+    allow_plugins: bool,
+
+    /// Run the embedding provider named in the project's
+    /// .arbor/embeddings.toml. It is a program or model from the checkout,
+    /// so only use this on trusted repositories (or set
+    /// ARBOR_ALLOW_EMBEDDINGS=1)
+    #[arg(long, global = true)]
+    allow_embeddings: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -75,7 +89,9 @@ enum Commands {
 
     /// Search the code graph
     Query {
-        /// Search query
+        /// Search query: words plus optional filters
+        /// (kind:method, file:src/billing/**, async:true, exported:true, static:true,
+        /// via:global_suffix)
         query: String,
 
         /// Path to index/search (defaults to current directory)
@@ -89,6 +105,81 @@ enum Commands {
         /// Exclude test/spec/fixture/mock files from results
         #[arg(long)]
         exclude_test: bool,
+
+        /// Rank by embedding similarity instead of keywords
+        /// (needs .arbor/embeddings.toml)
+        #[arg(long)]
+        semantic: bool,
+    },
+
+    /// Find code similar to a symbol using local embeddings
+    /// (needs .arbor/embeddings.toml)
+    Similar {
+        /// Symbol name or ID
+        symbol: String,
+
+        /// Path to analyze (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Maximum results to return
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Find duplicated and near-duplicated functions across the codebase
+    Duplicates {
+        /// Path to analyze (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Ignore functions shorter than this many normalized tokens
+        #[arg(long, default_value = "50")]
+        min_tokens: usize,
+
+        /// Minimum similarity (0.0-1.0) for two functions to count as clones
+        #[arg(long, default_value = "0.85")]
+        threshold: f64,
+
+        /// Maximum clone groups to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Find functions, methods and classes no entry point reaches
+    DeadCode {
+        /// Path to analyze (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Treat public and exported symbols as roots (for libraries)
+        #[arg(long)]
+        exported: bool,
+
+        /// Don't let tests keep code alive (report test-only code as dead)
+        #[arg(long)]
+        no_tests: bool,
+
+        /// Don't treat event handlers, widgets and DI providers as roots
+        #[arg(long)]
+        no_framework_hooks: bool,
+
+        /// Extra root: a symbol name glob (`register_*`) or a path glob
+        /// (`src/plugins/**`). Repeatable
+        #[arg(long = "root")]
+        roots: Vec<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Analyze git changes and preview impact blast radius
@@ -101,6 +192,10 @@ enum Commands {
         #[arg(short, long, default_value = "5")]
         depth: usize,
 
+        /// Also group impacted code by primary git blame author (slower)
+        #[arg(long)]
+        authors: bool,
+
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -110,6 +205,25 @@ enum Commands {
         markdown: bool,
     },
 
+    /// List the tests that exercise changed code, as runner filters
+    AffectedTests {
+        /// Path to analyze (defaults to current directory)
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Maximum caller hops from a changed symbol to a test (0 = unlimited)
+        #[arg(short, long, default_value = "0")]
+        depth: usize,
+
+        /// Print only the filter for one runner: cargo, pytest, go or jest
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// CI safety mode for changed code paths
     Check {
         /// Path to analyze (defaults to current directory)
@@ -124,6 +238,18 @@ enum Commands {
         #[arg(long, default_value = "25")]
         max_blast_radius: usize,
 
+        /// Flag changed functions whose cyclomatic complexity exceeds this
+        #[arg(long)]
+        max_complexity: Option<u32>,
+
+        /// Flag changed functions nested deeper than this
+        #[arg(long)]
+        max_nesting: Option<u32>,
+
+        /// Also group impacted code by primary git blame author (slower)
+        #[arg(long)]
+        authors: bool,
+
         /// Do not fail with non-zero exit code on risky changes
         #[arg(long)]
         no_fail: bool,
@@ -135,6 +261,10 @@ enum Commands {
         /// Output as Markdown (for PR comments)
         #[arg(long)]
         markdown: bool,
+
+        /// Output as SARIF 2.1.0 (for code-scanning dashboards)
+        #[arg(long)]
+        sarif: bool,
     },
 
     /// Start the Arbor server
@@ -305,16 +435,22 @@ enum Commands {
         path: PathBuf,
     },
 
-    /// Security audit: Trace paths to sensitive sinks
+    /// Security audit: trace untrusted input to sensitive sinks
     Audit {
-        /// The sensitive sink to analyze (e.g., "db_query", "exec")
-        sink: String,
+        /// Only audit this sink: a rule id or category (e.g. "sql", "command")
+        /// or a symbol name (e.g. "db_query"). Defaults to every sink.
+        #[arg(long)]
+        sink: Option<String>,
 
         /// Maximum depth to search (default: 8)
         #[arg(short, long, default_value = "8")]
         depth: usize,
 
-        /// Output format (default: text, options: json, csv)
+        /// Paths to list per sink, shortest first; the rest are counted
+        #[arg(long, default_value = "20")]
+        max_paths: usize,
+
+        /// Output format (default: text, options: json, csv, sarif)
         #[arg(long, default_value = "text")]
         format: String,
 
@@ -351,12 +487,16 @@ enum Commands {
         json: bool,
     },
 
-    /// List all detected entry points (HTTP handlers, main, webhooks, jobs, CLI commands)
+    /// List all detected entry points (HTTP routes, main, webhooks, jobs, CLI commands)
     EntryPoints {
         /// Path to analyze (defaults to current directory)
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Only entry points whose call tree reaches this symbol
+        #[arg(long)]
+        reaching: Option<String>,
+
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -390,7 +530,7 @@ enum Commands {
         json: bool,
     },
 
-    /// Find the shortest path between two symbols in the call graph
+    /// Find the shortest paths between two symbols in the call graph
     #[command(name = "path")]
     FindPath {
         /// Start symbol (name or ID)
@@ -403,6 +543,18 @@ enum Commands {
         #[arg(default_value = ".")]
         path: PathBuf,
 
+        /// Number of shortest paths to list
+        #[arg(long, default_value = "1")]
+        k: usize,
+
+        /// Longest path to follow, in hops
+        #[arg(long, default_value = "12")]
+        max_depth: usize,
+
+        /// Time budget for path enumeration, in milliseconds
+        #[arg(long, default_value = "500")]
+        timeout_ms: u64,
+
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -494,6 +646,14 @@ enum AgentAction {
         /// Maximum blast radius threshold (default: 25)
         #[arg(long, default_value = "25")]
         max_blast_radius: usize,
+
+        /// Maximum impact traversal depth
+        #[arg(short, long, default_value = "5")]
+        depth: usize,
+
+        /// Output violations as SARIF 2.1.0 instead of Markdown
+        #[arg(long)]
+        sarif: bool,
     },
 }
 
@@ -512,6 +672,13 @@ async fn main() {
         .with(tracing_subscriber::EnvFilter::new(filter))
         .init();
 
+    if cli.allow_plugins {
+        arbor_core::languages::allow_plugins();
+    }
+    if cli.allow_embeddings {
+        arbor_graph::allow_embeddings();
+    }
+
     let result = match cli.command {
         Commands::Setup {
             path,
@@ -538,21 +705,73 @@ async fn main() {
             path,
             limit,
             exclude_test,
-        } => commands::query(&query, limit, &path, exclude_test),
+            semantic,
+        } => commands::query(&query, limit, &path, exclude_test, semantic),
+        Commands::Similar {
+            symbol,
+            path,
+            limit,
+            json,
+        } => commands::similar(&symbol, limit, &path, json),
+        Commands::Duplicates {
+            path,
+            min_tokens,
+            threshold,
+            limit,
+            json,
+        } => commands::duplicates(&path, min_tokens, threshold, limit, json),
+        Commands::DeadCode {
+            path,
+            exported,
+            no_tests,
+            no_framework_hooks,
+            roots,
+            json,
+        } => commands::dead_code(&path, exported, !no_tests, !no_framework_hooks, roots, json),
         Commands::Diff {
             path,
             depth,
+            authors,
             json,
             markdown,
-        } => commands::diff(&path, depth, json, markdown),
+        } => commands::diff(&path, depth, authors, json, markdown),
+        Commands::AffectedTests {
+            path,
+            depth,
+            format,
+            json,
+        } => commands::affected_tests(&path, depth, format.as_deref(), json),
         Commands::Check {
             path,
             depth,
             max_blast_radius,
+            max_complexity,
+            max_nesting,
+            authors,
             no_fail,
             json,
             markdown,
-        } => commands::check(&path, depth, max_blast_radius, no_fail, json, markdown),
+            sarif,
+        } => commands::check(
+            &path,
+            depth,
+            commands::CheckThresholds {
+                max_blast_radius,
+                max_complexity,
+                max_nesting,
+            },
+            authors,
+            no_fail,
+            if sarif {
+                commands::CheckFormat::Sarif
+            } else if markdown {
+                commands::CheckFormat::Markdown
+            } else if json {
+                commands::CheckFormat::Json
+            } else {
+                commands::CheckFormat::Text
+            },
+        ),
         Commands::Serve {
             port,
             headless,
@@ -595,20 +814,36 @@ async fn main() {
         Commands::Audit {
             sink,
             depth,
+            max_paths,
             format,
             path,
-        } => commands::audit(&sink, depth, &format, &path),
+        } => commands::audit(sink.as_deref(), depth, max_paths, &format, &path),
         Commands::Callers { symbol, path, json } => commands::callers(&symbol, &path, json),
         Commands::Callees { symbol, path, json } => commands::callees(&symbol, &path, json),
-        Commands::EntryPoints { path, json } => commands::entry_points(&path, json),
+        Commands::EntryPoints {
+            path,
+            reaching,
+            json,
+        } => commands::entry_points(&path, reaching.as_deref(), json),
         Commands::FileGraph { file, path, json } => commands::file_graph(&file, &path, json),
         Commands::Inspect { symbol, path, json } => commands::inspect(&symbol, &path, json),
         Commands::FindPath {
             start,
             end,
             path,
+            k,
+            max_depth,
+            timeout_ms,
             json,
-        } => commands::find_path_cmd(&start, &end, &path, json),
+        } => {
+            let options = arbor_graph::PathOptions {
+                max_paths: k,
+                max_depth,
+                time_budget: std::time::Duration::from_millis(timeout_ms),
+                ..Default::default()
+            };
+            commands::find_path_cmd(&start, &end, &path, &options, json)
+        }
         Commands::Hook {
             harness,
             path,
@@ -637,7 +872,9 @@ async fn main() {
             AgentAction::Guard {
                 path,
                 max_blast_radius,
-            } => commands::agent_guard(&path, max_blast_radius),
+                depth,
+                sarif,
+            } => commands::agent_guard(&path, max_blast_radius, depth, sarif),
         },
     };
 
@@ -0,0 +1,266 @@
+//! SARIF 2.1.0 output for code-scanning dashboards.
+//!
+//! Findings become `results` with a rule ID, a level and the file/line of
+//! the symbol they point at. Multi-hop findings also carry a `codeFlow`
+//! with one location per hop, so viewers can step through the path.
+
+use crate::audit::{AuditResult, Severity};
+use arbor_graph::NodeInfo;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A rule referenced by results, listed once in the tool driver.
+struct SarifRule {
+    id: String,
+    name: String,
+    description: String,
+    level: &'static str,
+}
+
+/// Collects rules and results for a single SARIF run.
+pub struct SarifBuilder<'a> {
+    root: &'a Path,
+    rules: BTreeMap<String, SarifRule>,
+    results: Vec<Value>,
+}
+
+impl<'a> SarifBuilder<'a> {
+    /// File paths in results are made relative to `root`.
+    pub fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            rules: BTreeMap::new(),
+            results: Vec::new(),
+        }
+    }
+
+    /// Registers a rule; later calls with the same ID are ignored.
+    pub fn rule(&mut self, id: &str, name: &str, description: &str, level: &'static str) {
+        self.rules
+            .entry(id.to_string())
+            .or_insert_with(|| SarifRule {
+                id: id.to_string(),
+                name: name.to_string(),
+                description: description.to_string(),
+                level,
+            });
+    }
+
+    /// Adds a result at `node`. A `flow` of two or more nodes becomes a
+    /// code flow, each step labelled with its message.
+    pub fn result(
+        &mut self,
+        rule_id: &str,
+        level: &str,
+        message: &str,
+        node: &NodeInfo,
+        flow: &[(&NodeInfo, String)],
+    ) {
+        let mut result = json!({
+            "ruleId": rule_id,
+            "level": level,
+            "message": { "text": message },
+            "locations": [self.location(node, None)],
+        });
+        if flow.len() > 1 {
+            let steps: Vec<Value> = flow
+                .iter()
+                .map(|(step, text)| json!({ "location": self.location(step, Some(text)) }))
+                .collect();
+            result["codeFlows"] = json!([{ "threadFlows": [{ "locations": steps }] }]);
+        }
+        self.results.push(result);
+    }
+
+    fn location(&self, node: &NodeInfo, message: Option<&str>) -> Value {
+        let mut location = json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": self.uri(&node.file) },
+                "region": {
+                    "startLine": node.line_start.max(1),
+                    "endLine": node.line_end.max(node.line_start).max(1),
+                }
+            },
+            "logicalLocations": [{
+                "name": node.name,
+                "fullyQualifiedName": node.qualified_name,
+                "kind": node.kind.to_lowercase(),
+            }]
+        });
+        if let Some(text) = message {
+            location["message"] = json!({ "text": text });
+        }
+        location
+    }
+
+    fn uri(&self, file: &str) -> String {
+        let path = Path::new(file);
+        path.strip_prefix(self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+
+    /// The finished log, with one run.
+    pub fn finish(self) -> Value {
+        let rules: Vec<Value> = self
+            .rules
+            .values()
+            .map(|rule| {
+                json!({
+                    "id": rule.id,
+                    "name": rule.name,
+                    "shortDescription": { "text": rule.description },
+                    "defaultConfiguration": { "level": rule.level },
+                })
+            })
+            .collect();
+        json!({
+            "$schema": SCHEMA,
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "arbor",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": "https://github.com/Anandb71/arbor",
+                        "rules": rules,
+                    }
+                },
+                "results": self.results,
+            }]
+        })
+    }
+}
+
+fn severity_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Medium => "warning",
+        Severity::Low => "note",
+    }
+}
+
+/// SARIF log for a taint audit: one result per unsanitized path, located
+/// at the sink, with the path as a code flow.
+pub fn audit_log(result: &AuditResult, root: &Path) -> Value {
+    let mut sarif = SarifBuilder::new(root);
+    for path in &result.paths {
+        let rule_id = format!("arbor/taint/{}", path.sink_rule.category);
+        sarif.rule(
+            &rule_id,
+            &format!("Untrusted input reaches {} sink", path.sink_rule.category),
+            &format!(
+                "Data from a source flows to a {} sink with no sanitizer on the call path",
+                path.sink_rule.category
+            ),
+            "error",
+        );
+
+        let mut flow: Vec<(&NodeInfo, String)> = path
+            .trace
+            .iter()
+            .map(|node| (node, format!("calls into {}", node.name)))
+            .collect();
+        if let Some(first) = flow.first_mut() {
+            first.1 = format!(
+                "{} source: {}",
+                path.source_rule.category,
+                path.source_rule.source_action()
+            );
+        }
+        if let Some(last) = flow.last_mut() {
+            last.1 = format!(
+                "{} sink: {}",
+                path.sink_rule.category, path.sink_rule.matched
+            );
+        }
+
+        let mut message = format!(
+            "{} input read in '{}' ({}) reaches {} sink '{}' in '{}' over {} hop(s) with no sanitizer.",
+            path.source_rule.category,
+            path.source.name,
+            path.source_rule.matched,
+            path.sink_rule.category,
+            path.sink_rule.matched,
+            path.sink.name,
+            path.trace.len().saturating_sub(1)
+        );
+        if !path.uncertainty.is_empty() {
+            message.push_str(&format!(
+                " {} hop(s) are inferred (confidence {:.2}).",
+                path.uncertainty.len(),
+                path.confidence
+            ));
+        }
+        sarif.result(
+            &rule_id,
+            severity_level(path.severity),
+            &message,
+            &path.sink,
+            &flow,
+        );
+    }
+    sarif.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, file: &str, line: u32) -> NodeInfo {
+        NodeInfo {
+            id: name.to_string(),
+            name: name.to_string(),
+            qualified_name: name.to_string(),
+            kind: "function".to_string(),
+            file: file.to_string(),
+            line_start: line,
+            line_end: line + 3,
+            signature: None,
+            centrality: 0.0,
+            metrics: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_results_carry_relative_locations_and_code_flows() {
+        let root = Path::new("/repo");
+        let handler = node("handler", "/repo/src/api.py", 10);
+        let save = node("save", "/repo/src/db.py", 0);
+
+        let mut sarif = SarifBuilder::new(root);
+        sarif.rule("arbor/test", "Test", "A test rule", "warning");
+        sarif.rule("arbor/test", "Ignored", "Registered twice", "error");
+        sarif.result(
+            "arbor/test",
+            "warning",
+            "handler reaches save",
+            &save,
+            &[(&handler, "start".to_string()), (&save, "end".to_string())],
+        );
+        let log = sarif.finish();
+
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+        assert_eq!(run["tool"]["driver"]["rules"][0]["name"], "Test");
+
+        let result = &run["results"][0];
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/db.py");
+        // line 0 is not a valid SARIF line
+        assert_eq!(location["region"]["startLine"], 1);
+
+        let steps = &result["codeFlows"][0]["threadFlows"][0]["locations"];
+        assert_eq!(steps.as_array().unwrap().len(), 2);
+        assert_eq!(
+            steps[0]["location"]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/api.py"
+        );
+        assert_eq!(steps[1]["location"]["message"]["text"], "end");
+    }
+}
@@ -300,3 +300,46 @@ fn diff_markdown_and_summary_use_env_commit_range() {
         "expected file src/range.rs in summary, got: {summary_stdout}"
     );
 }
+
+#[test]
+fn guard_sarif_reports_only_the_symbols_the_diff_touches() {
+    let temp = init_repo();
+    let repo = temp.path();
+
+    fs::write(
+        repo.join("src").join("main.rs"),
+        "fn alpha() {}\nfn beta() { alpha(); }\nfn gamma() { beta(); }\nfn main() { gamma(); }\n",
+    )
+    .expect("write file");
+
+    run_git(repo, &["add", "."]);
+    run_git(repo, &["commit", "-m", "base"]);
+
+    fs::write(
+        repo.join("src").join("main.rs"),
+        "fn alpha() {}\nfn beta() { alpha(); alpha(); }\nfn gamma() { beta(); }\nfn main() { gamma(); }\n",
+    )
+    .expect("rewrite file");
+
+    let output = run_arbor(
+        repo,
+        &["agent", "guard", "--sarif", "--max-blast-radius", "0", "."],
+    );
+    let sarif: Value = serde_json::from_slice(&output.stdout).unwrap_or_else(|e| {
+        panic!(
+            "guard output is not SARIF ({e}):\nstdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        )
+    });
+
+    let changed: Vec<&str> = sarif["runs"][0]["results"]
+        .as_array()
+        .expect("results array")
+        .iter()
+        .filter(|r| r["ruleId"] == "arbor/blast-radius")
+        .filter_map(|r| r["message"]["text"].as_str())
+        .filter_map(|text| text.split('\'').nth(1))
+        .collect();
+    assert_eq!(changed, ["beta"], "{sarif:#}");
+}
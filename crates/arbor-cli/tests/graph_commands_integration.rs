@@ -330,6 +330,19 @@ fn path_json_output() {
     assert!(json["hops"].as_u64().unwrap() >= 1);
 }
 
+#[test]
+fn path_json_reports_count_and_chokepoints() {
+    let temp = setup_rust_project();
+    let dir = temp.path();
+
+    let stdout = run_arbor_stdout(dir, &["path", "main", "helper", ".", "--json", "--k", "3"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid json");
+    assert_eq!(json["paths"].as_array().unwrap().len(), 1);
+    assert_eq!(json["estimated_paths"], 1);
+    assert_eq!(json["chokepoints"][0]["name"], "compute");
+    assert!(json["truncated"].as_array().unwrap().is_empty());
+}
+
 #[test]
 fn path_no_route() {
     let temp = setup_rust_project();
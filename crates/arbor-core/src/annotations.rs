@@ -0,0 +1,136 @@
+//! Relationships declared in source comments.
+//!
+//! Some links are invisible to static parsing: handlers dispatched by a
+//! string, plugin registries, message buses. A comment can declare them:
+//!
+//! ```text
+//! // arbor: calls billing::charge
+//! # arbor: calls billing.charge, billing.refund
+//! -- arbor: alias charge_handler
+//! ```
+//!
+//! `alias` gives the node another name that references resolve to; any
+//! other word is an edge kind (`calls`, `references`, `uses_type`,
+//! `extends`, `implements`, `imports`) that the graph crate checks. The
+//! declaration goes on the node that starts within a few lines below a
+//! standalone comment, or else on the innermost node the comment is in.
+
+use crate::node::{CodeNode, Declaration, NodeKind};
+
+/// Comment markers across the supported languages.
+const COMMENT_MARKERS: &[&str] = &["//", "#", "--", "/*", "*", ";"];
+
+/// How far below a standalone comment the declared node may start.
+const MAX_GAP: u32 = 3;
+
+/// Records `arbor:` comments in [`CodeNode::declared`].
+pub(crate) fn attach_annotations(source: &str, nodes: &mut [CodeNode]) {
+    for (index, line) in source.lines().enumerate() {
+        let Some(at) = line.find("arbor:") else {
+            continue;
+        };
+        let before = line[..at].trim_end();
+        if !COMMENT_MARKERS.iter().any(|m| before.ends_with(m)) {
+            continue;
+        }
+        let Some(declarations) = parse(&line[at + "arbor:".len()..], index as u32 + 1) else {
+            continue;
+        };
+        let standalone = COMMENT_MARKERS.iter().any(|m| before.trim_start() == *m);
+        let Some(target) = annotated(nodes, index as u32 + 1, standalone) else {
+            continue;
+        };
+        let id = nodes[target].id.clone();
+        for node in nodes.iter_mut().filter(|n| n.id == id) {
+            node.declared.extend(declarations.iter().cloned());
+        }
+    }
+}
+
+/// `calls a::b, c` → one declaration per target.
+fn parse(text: &str, line: u32) -> Option<Vec<Declaration>> {
+    let text = text.trim().trim_end_matches("*/").trim_end_matches("-->");
+    let (relation, targets) = text.split_once(char::is_whitespace)?;
+    let declarations: Vec<Declaration> = targets
+        .split([',', ' ', '\t'])
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|target| Declaration {
+            relation: relation.to_lowercase(),
+            target: target.to_string(),
+            line,
+        })
+        .collect();
+    (!declarations.is_empty()).then_some(declarations)
+}
+
+/// The node a comment on `line` annotates.
+fn annotated(nodes: &[CodeNode], line: u32, standalone: bool) -> Option<usize> {
+    let candidates = || {
+        nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| n.kind != NodeKind::Import)
+    };
+    let rank = |n: &CodeNode| match n.kind {
+        NodeKind::Function | NodeKind::Method | NodeKind::Constructor => 0,
+        _ => 1,
+    };
+    let below = candidates()
+        .filter(|(_, n)| standalone && n.line_start > line && n.line_start <= line + MAX_GAP)
+        .min_by_key(|(_, n)| (n.line_start, rank(n)))
+        .map(|(index, _)| index);
+    below.or_else(|| {
+        candidates()
+            .filter(|(_, n)| n.line_start <= line && line <= n.line_end)
+            .min_by_key(|(_, n)| (n.line_end - n.line_start, rank(n)))
+            .map(|(index, _)| index)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(name: &str, kind: NodeKind, start: u32, end: u32) -> CodeNode {
+        CodeNode::new(name, name, kind, "src/app.rs").with_lines(start, end)
+    }
+
+    #[test]
+    fn test_comments_declare_edges_on_the_annotated_node() {
+        let source = "\
+// arbor: calls billing::charge, billing::refund
+fn checkout() {
+    dispatch(\"charge\"); // arbor: references jobs.Charge
+}
+
+/* arbor: alias charge_handler */
+fn charge() {}
+let url = \"https://example.com/arbor: calls nothing\";
+";
+        let mut nodes = vec![
+            node("checkout", NodeKind::Function, 2, 4),
+            node("charge", NodeKind::Function, 7, 7),
+        ];
+        attach_annotations(source, &mut nodes);
+
+        let declared = |n: &CodeNode| -> Vec<(String, String, u32)> {
+            n.declared
+                .iter()
+                .map(|d| (d.relation.clone(), d.target.clone(), d.line))
+                .collect()
+        };
+        assert_eq!(
+            declared(&nodes[0]),
+            [
+                ("calls".into(), "billing::charge".into(), 1),
+                ("calls".into(), "billing::refund".into(), 1),
+                ("references".into(), "jobs.Charge".into(), 3),
+            ]
+        );
+        assert_eq!(
+            declared(&nodes[1]),
+            [("alias".into(), "charge_handler".into(), 6)]
+        );
+    }
+}
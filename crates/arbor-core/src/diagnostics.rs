@@ -0,0 +1,288 @@
+//! Per-file parse diagnostics.
+//!
+//! Tree-sitter recovers from syntax errors by wrapping the unparseable
+//! region in an ERROR node and carrying on, so a file with a stray brace
+//! still "parses" — it just loses the symbols inside the broken region.
+//! These diagnostics make that visible: how many error regions there were,
+//! where, how much of the file ended up inside extracted nodes, and whether
+//! the heuristic fallback parser was used instead of a grammar.
+
+use crate::node::CodeNode;
+use serde::{Deserialize, Serialize};
+use tree_sitter::Tree;
+
+/// Error ranges kept per file; the count keeps going past this.
+const MAX_ERROR_RANGES: usize = 20;
+
+/// Files whose extracted nodes cover less than this fraction of the bytes
+/// are reported as low coverage.
+pub const LOW_COVERAGE_THRESHOLD: f32 = 0.2;
+
+/// A region Tree-sitter couldn't parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorRange {
+    pub line_start: u32,
+    pub line_end: u32,
+    pub byte_start: u32,
+    pub byte_end: u32,
+}
+
+/// How well a single file parsed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseDiagnostics {
+    /// Number of ERROR and MISSING nodes in the syntax tree.
+    pub error_count: usize,
+    /// The first error regions, in source order.
+    pub error_ranges: Vec<ErrorRange>,
+    /// Fraction of the file's bytes that fall inside extracted nodes (0.0-1.0).
+    pub coverage: f32,
+    /// True if the line-based fallback parser produced the nodes.
+    pub used_fallback: bool,
+}
+
+impl Default for ParseDiagnostics {
+    fn default() -> Self {
+        Self {
+            error_count: 0,
+            error_ranges: Vec::new(),
+            coverage: 1.0,
+            used_fallback: false,
+        }
+    }
+}
+
+impl ParseDiagnostics {
+    /// Diagnostics for nodes extracted from one or more syntax trees.
+    pub fn from_trees(trees: &[&Tree], nodes: &[CodeNode], total_bytes: usize) -> Self {
+        let mut diagnostics = Self {
+            coverage: coverage(nodes, total_bytes),
+            ..Self::default()
+        };
+        for tree in trees {
+            collect_errors(tree, &mut diagnostics);
+        }
+        diagnostics
+            .error_ranges
+            .sort_by_key(|range| range.byte_start);
+        diagnostics
+    }
+
+    /// Diagnostics for nodes produced by the fallback parser.
+    pub fn fallback(nodes: &[CodeNode], total_bytes: usize) -> Self {
+        Self {
+            coverage: coverage(nodes, total_bytes),
+            used_fallback: true,
+            ..Self::default()
+        }
+    }
+
+    /// True if the file has parse errors or was only heuristically parsed,
+    /// meaning symbols and edges from it may be missing.
+    pub fn is_degraded(&self) -> bool {
+        self.error_count > 0 || self.used_fallback
+    }
+
+    /// True if little of the file ended up inside extracted nodes.
+    pub fn is_low_coverage(&self) -> bool {
+        self.coverage < LOW_COVERAGE_THRESHOLD
+    }
+}
+
+/// Parse health across a set of files.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseHealth {
+    /// Files with recorded diagnostics.
+    pub files: usize,
+    /// Files with at least one error region.
+    pub files_with_errors: usize,
+    /// Error regions across all files.
+    pub total_errors: usize,
+    /// Files parsed by the fallback parser.
+    pub fallback_files: usize,
+    /// Files below [`LOW_COVERAGE_THRESHOLD`].
+    pub low_coverage_files: usize,
+    /// Mean coverage across files (1.0 when there are none).
+    pub average_coverage: f32,
+    /// Files with parse errors, most errors first.
+    pub worst_files: Vec<String>,
+}
+
+impl ParseHealth {
+    /// Summarises per-file diagnostics.
+    pub fn summarize<'a, I>(diagnostics: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a String, &'a ParseDiagnostics)>,
+    {
+        let mut health = Self::default();
+        let mut coverage_sum = 0.0f64;
+        let mut with_errors = Vec::new();
+
+        for (file, diag) in diagnostics {
+            health.files += 1;
+            coverage_sum += diag.coverage as f64;
+            health.total_errors += diag.error_count;
+            if diag.error_count > 0 {
+                health.files_with_errors += 1;
+                with_errors.push((diag.error_count, file.clone()));
+            }
+            if diag.used_fallback {
+                health.fallback_files += 1;
+            }
+            if diag.is_low_coverage() {
+                health.low_coverage_files += 1;
+            }
+        }
+
+        health.average_coverage = if health.files == 0 {
+            1.0
+        } else {
+            (coverage_sum / health.files as f64) as f32
+        };
+        with_errors.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        health.worst_files = with_errors.into_iter().map(|(_, file)| file).collect();
+        health
+    }
+}
+
+/// Counts ERROR/MISSING nodes, recording the outermost ones as ranges.
+fn collect_errors(tree: &Tree, diagnostics: &mut ParseDiagnostics) {
+    let root = tree.root_node();
+    if !root.has_error() {
+        return;
+    }
+
+    let mut cursor = root.walk();
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.is_error() || node.is_missing() {
+            diagnostics.error_count += 1;
+            if diagnostics.error_ranges.len() < MAX_ERROR_RANGES {
+                diagnostics.error_ranges.push(ErrorRange {
+                    line_start: node.start_position().row as u32 + 1,
+                    line_end: node.end_position().row as u32 + 1,
+                    byte_start: node.start_byte() as u32,
+                    byte_end: node.end_byte() as u32,
+                });
+            }
+        } else if node.has_error() && cursor.goto_first_child() {
+            // Only subtrees containing errors are worth walking into.
+            continue;
+        }
+
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+/// Fraction of `total_bytes` covered by the union of the nodes' byte ranges.
+fn coverage(nodes: &[CodeNode], total_bytes: usize) -> f32 {
+    if total_bytes == 0 {
+        return 1.0;
+    }
+
+    let mut ranges: Vec<(u32, u32)> = nodes
+        .iter()
+        .filter(|n| n.byte_end > n.byte_start)
+        .map(|n| (n.byte_start, n.byte_end))
+        .collect();
+    ranges.sort_unstable();
+
+    let mut covered = 0u64;
+    let mut current: Option<(u32, u32)> = None;
+    for (start, end) in ranges {
+        match current {
+            Some((cur_start, cur_end)) if start <= cur_end => {
+                current = Some((cur_start, cur_end.max(end)));
+            }
+            Some((cur_start, cur_end)) => {
+                covered += (cur_end - cur_start) as u64;
+                current = Some((start, end));
+            }
+            None => current = Some((start, end)),
+        }
+    }
+    if let Some((start, end)) = current {
+        covered += (end - start) as u64;
+    }
+
+    (covered as f32 / total_bytes as f32).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::get_parser;
+
+    fn diagnose(source: &str, ext: &str) -> ParseDiagnostics {
+        let parser = get_parser(ext).unwrap();
+        let mut ts = tree_sitter::Parser::new();
+        ts.set_language(&parser.language()).unwrap();
+        let tree = ts.parse(source, None).unwrap();
+        let nodes = parser.extract_nodes(&tree, source, "test");
+        ParseDiagnostics::from_trees(&[&tree], &nodes, source.len())
+    }
+
+    #[test]
+    fn test_clean_file_has_no_errors() {
+        let source = "fn a() {}\nfn b() { a(); }\n";
+        let diagnostics = diagnose(source, "rs");
+        assert_eq!(diagnostics.error_count, 0);
+        assert!(diagnostics.error_ranges.is_empty());
+        assert!(!diagnostics.is_degraded());
+        // Only the trailing newline is outside a function.
+        assert!(diagnostics.coverage > 0.9);
+    }
+
+    #[test]
+    fn test_error_regions_are_reported() {
+        let source = "fn ok() {}\n\nfn broken( {\n    let x = ;\n}\n\nfn also_ok() {}\n";
+        let diagnostics = diagnose(source, "rs");
+        assert!(diagnostics.error_count > 0);
+        assert!(diagnostics.is_degraded());
+        let first = diagnostics.error_ranges[0];
+        assert!(first.line_start >= 3 && first.line_end <= 5);
+    }
+
+    #[test]
+    fn test_coverage_merges_overlapping_nodes() {
+        let nodes = vec![
+            CodeNode::new("Outer", "Outer", crate::NodeKind::Class, "f").with_bytes(0, 50),
+            CodeNode::new("inner", "Outer.inner", crate::NodeKind::Method, "f").with_bytes(10, 20),
+            CodeNode::new("other", "other", crate::NodeKind::Function, "f").with_bytes(60, 80),
+        ];
+        assert!((coverage(&nodes, 100) - 0.7).abs() < f32::EPSILON);
+        assert_eq!(coverage(&[], 0), 1.0);
+        assert!(ParseDiagnostics::fallback(&[], 10).is_low_coverage());
+    }
+
+    #[test]
+    fn test_health_summary_orders_worst_files() {
+        let mut files = std::collections::HashMap::new();
+        let with_errors = |error_count| ParseDiagnostics {
+            error_count,
+            ..ParseDiagnostics::default()
+        };
+        files.insert("a.rs".to_string(), with_errors(1));
+        files.insert("b.rs".to_string(), with_errors(3));
+        files.insert("c.md".to_string(), ParseDiagnostics::fallback(&[], 10));
+
+        let health = ParseHealth::summarize(&files);
+        assert_eq!(health.files, 3);
+        assert_eq!(health.files_with_errors, 2);
+        assert_eq!(health.total_errors, 4);
+        assert_eq!(health.fallback_files, 1);
+        assert_eq!(health.low_coverage_files, 1);
+        assert_eq!(health.worst_files, vec!["b.rs", "a.rs"]);
+    }
+}
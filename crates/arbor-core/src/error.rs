@@ -34,6 +34,11 @@ pub enum ParseError {
     #[error("query error: {0}")]
     QueryError(String),
 
+    /// A runtime grammar plugin in `.arbor/languages/` couldn't be
+    /// loaded: bad manifest, missing library, or incompatible ABI.
+    #[error("language plugin '{name}': {message}")]
+    PluginError { name: String, message: String },
+
     /// The file exists but is empty. Not really an error,
     /// but we surface it so callers can handle it gracefully.
     #[error("file is empty: '{0}'")]
@@ -4,6 +4,7 @@
 //! so Arbor can provide useful symbol indexing for languages that are not yet
 //! wired to a full Tree-sitter grammar in every runtime path.
 
+use crate::injection::SQL_REFERENCE_PREFIX;
 use crate::node::{CodeNode, NodeKind};
 
 /// Extra language extensions supported via fallback parsing.
@@ -14,6 +15,8 @@ pub const FALLBACK_EXTENSIONS: &[&str] = &[
     "php", "phtml", // PHP
     "sh", "bash", "zsh", // Shell
     "md", "markdown", // Markdown for knowledge graphs (Lattice)
+    "thrift",   // Thrift IDL (no tree-sitter grammar for our runtime yet)
+    "sql",      // SQL schemas and migrations (tables referenced from query strings)
 ];
 
 pub fn is_fallback_supported_extension(ext: &str) -> bool {
@@ -24,11 +27,23 @@ pub fn is_fallback_supported_extension(ext: &str) -> bool {
 pub fn parse_fallback_source(source: &str, file_path: &str, ext: &str) -> Vec<CodeNode> {
     let ext = ext.to_ascii_lowercase();
     let mut nodes = Vec::new();
+    let mut in_code_block = false;
 
     for (idx, line) in source.lines().enumerate() {
         let line_no = idx as u32 + 1;
         let trimmed = line.trim_start();
 
+        // `#` lines inside fenced code are comments, not headings
+        if matches!(ext.as_str(), "md" | "markdown") {
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                continue;
+            }
+            if in_code_block {
+                continue;
+            }
+        }
+
         let candidate = match ext.as_str() {
             "md" | "markdown" => parse_markdown_line(trimmed),
             "kt" | "kts" => parse_kotlin_line(trimmed),
@@ -36,6 +51,8 @@ pub fn parse_fallback_source(source: &str, file_path: &str, ext: &str) -> Vec<Co
             "rb" => parse_ruby_line(trimmed),
             "php" | "phtml" => parse_php_line(trimmed),
             "sh" | "bash" | "zsh" => parse_shell_line(trimmed),
+            "thrift" => parse_thrift_line(trimmed),
+            "sql" => parse_sql_line(trimmed),
             _ => None,
         };
 
@@ -47,7 +64,14 @@ pub fn parse_fallback_source(source: &str, file_path: &str, ext: &str) -> Vec<Co
 
         if let Some((name, kind)) = candidate {
             let col = (line.len().saturating_sub(trimmed.len())) as u32;
-            let node = CodeNode::new(&name, &name, kind, file_path)
+            // SQL objects live in their own namespace so `sql:users`
+            // references from query strings resolve exactly.
+            let qualified_name = if ext == "sql" {
+                format!("{}{}", SQL_REFERENCE_PREFIX, name)
+            } else {
+                name.clone()
+            };
+            let node = CodeNode::new(&name, &qualified_name, kind, file_path)
                 .with_lines(line_no, line_no)
                 .with_column(col)
                 .with_signature(trimmed.to_string());
@@ -166,6 +190,58 @@ fn parse_shell_line(line: &str) -> Option<(String, NodeKind)> {
     None
 }
 
+fn parse_thrift_line(line: &str) -> Option<(String, NodeKind)> {
+    for keyword in ["struct ", "union ", "exception "] {
+        if let Some(rest) = line.strip_prefix(keyword) {
+            return take_ident(rest).map(|name| (name, NodeKind::Struct));
+        }
+    }
+
+    if let Some(rest) = line.strip_prefix("enum ") {
+        return take_ident(rest).map(|name| (name, NodeKind::Enum));
+    }
+
+    if let Some(rest) = line.strip_prefix("service ") {
+        return take_ident(rest).map(|name| (name, NodeKind::Interface));
+    }
+
+    if let Some(rest) = line.strip_prefix("typedef ") {
+        // typedef <type> <Name>
+        return rest
+            .split_whitespace()
+            .last()
+            .and_then(take_ident)
+            .map(|name| (name, NodeKind::TypeAlias));
+    }
+
+    None
+}
+
+fn parse_sql_line(line: &str) -> Option<(String, NodeKind)> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let upper: Vec<String> = words.iter().map(|w| w.to_ascii_uppercase()).collect();
+    if upper.first().map(String::as_str) != Some("CREATE") {
+        return None;
+    }
+
+    // CREATE [OR REPLACE] [TEMP|MATERIALIZED|...] TABLE|VIEW|FUNCTION|PROCEDURE [IF NOT EXISTS] name
+    let object = upper
+        .iter()
+        .position(|w| matches!(w.as_str(), "TABLE" | "VIEW" | "FUNCTION" | "PROCEDURE"))?;
+    let kind = match upper[object].as_str() {
+        "TABLE" | "VIEW" => NodeKind::Struct,
+        _ => NodeKind::Function,
+    };
+    let mut name_idx = object + 1;
+    if upper.get(name_idx).map(String::as_str) == Some("IF") {
+        name_idx += 3; // IF NOT EXISTS
+    }
+
+    let name = words.get(name_idx)?.rsplit('.').next()?;
+    let name = name.trim_matches(|c| c == '"' || c == '`' || c == '[' || c == ']');
+    take_ident(name).map(|name| (name.to_ascii_lowercase(), kind))
+}
+
 fn parse_markdown_line(line: &str) -> Option<(String, NodeKind)> {
     let trimmed = line.trim_start();
     if let Some(rest) = trimmed.strip_prefix("# ") {
@@ -382,4 +458,56 @@ object Singleton
         let nodes = parse_fallback_source(source, "deploy.bash", "bash");
         assert!(nodes.iter().any(|n| n.name == "deploy_staging"));
     }
+
+    #[test]
+    fn parses_sql_schema() {
+        let src = r#"
+CREATE TABLE IF NOT EXISTS public.Users (
+    id SERIAL PRIMARY KEY
+);
+create materialized view active_users as select * from users;
+CREATE OR REPLACE FUNCTION calc_total(order_id int) RETURNS int AS $$
+-- CREATE TABLE ignored_comment
+"#;
+        let nodes = parse_fallback_source(src, "db/schema.sql", "sql");
+        let summary: Vec<_> = nodes
+            .iter()
+            .map(|n| (n.qualified_name.as_str(), n.kind))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("sql:users", NodeKind::Struct),
+                ("sql:active_users", NodeKind::Struct),
+                ("sql:calc_total", NodeKind::Function),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_thrift_idl() {
+        let source = r#"namespace java com.acme.users
+typedef i64 UserId
+
+struct User {
+  1: UserId id
+}
+
+exception NotFound {}
+
+enum Role { ADMIN = 1 }
+
+service UserService {
+  User getUser(1: UserId id) throws (1: NotFound missing)
+}
+"#;
+        let nodes = parse_fallback_source(source, "users.thrift", "thrift");
+        let found =
+            |name: &str, kind: NodeKind| nodes.iter().any(|n| n.name == name && n.kind == kind);
+        assert!(found("UserId", NodeKind::TypeAlias));
+        assert!(found("User", NodeKind::Struct));
+        assert!(found("NotFound", NodeKind::Struct));
+        assert!(found("Role", NodeKind::Enum));
+        assert!(found("UserService", NodeKind::Interface));
+    }
 }
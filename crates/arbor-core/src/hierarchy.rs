@@ -0,0 +1,512 @@
+//! Type hierarchy extraction.
+//!
+//! Records what each class, struct, interface or trait extends or
+//! implements in [`CodeNode::supertypes`], so the graph crate can add
+//! `extends`/`implements` edges and work out where a call through an
+//! interface may dispatch to:
+//!
+//! | Language | Declarations |
+//! |----------|--------------|
+//! | Rust | `impl Trait for Type` (recorded on the block's methods), supertraits `trait A: B` |
+//! | JS/TS | `class A extends B implements C`, `interface A extends B` |
+//! | Python | `class A(B, C)` |
+//! | Java | `extends`, `implements`, interface `extends` |
+//! | C# | base lists `class A : B, IC` |
+//!
+//! Go interfaces are satisfied implicitly; the graph builder matches
+//! method sets instead.
+//!
+//! Interface members without a body (Rust trait methods, TypeScript
+//! interface and abstract methods, Go interface methods) are not picked up
+//! by the language parsers. They are added here as `Method` nodes so that
+//! calls through the interface have a declaration to resolve to.
+
+use crate::node::{CodeNode, NodeKind, Visibility};
+use std::ops::Range;
+use tree_sitter::{Node, Tree};
+
+/// What the walk found, before it is matched against extracted nodes.
+#[derive(Default)]
+struct Found<'t> {
+    /// Byte offset of a type's name, and the supertypes it declares.
+    heritage: Vec<(usize, Vec<String>)>,
+    /// A Rust `impl Trait for Type` block: its range and the trait.
+    impls: Vec<(Range<usize>, String)>,
+    /// Bodiless members: byte offset of the owning type's name, the
+    /// member's name and its declaration.
+    members: Vec<(usize, String, Node<'t>)>,
+}
+
+/// Adds supertypes to extracted type nodes and nodes for bodiless
+/// interface members.
+pub(crate) fn attach_hierarchy(
+    tree: &Tree,
+    source: &str,
+    file_path: &str,
+    language: &str,
+    nodes: &mut Vec<CodeNode>,
+) {
+    let visit: for<'t> fn(Node<'t>, &str, &mut Found<'t>) = match language {
+        "rs" => rust_hierarchy,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => js_hierarchy,
+        "py" => python_hierarchy,
+        "java" => java_hierarchy,
+        "cs" => csharp_hierarchy,
+        "go" => go_hierarchy,
+        _ => return,
+    };
+
+    let mut found = Found::default();
+    let mut cursor = tree.walk();
+    'outer: loop {
+        visit(cursor.node(), source, &mut found);
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    for (offset, supertypes) in found.heritage {
+        if let Some(index) = enclosing_type(nodes, offset) {
+            let node = &mut nodes[index];
+            for supertype in supertypes {
+                if supertype != node.name && !node.supertypes.contains(&supertype) {
+                    node.supertypes.push(supertype);
+                }
+            }
+        }
+    }
+
+    for (range, supertype) in found.impls {
+        for node in nodes.iter_mut().filter(|n| {
+            n.kind == NodeKind::Method
+                && range.start <= n.byte_start as usize
+                && n.byte_end as usize <= range.end
+        }) {
+            if !node.supertypes.contains(&supertype) {
+                node.supertypes.push(supertype.clone());
+            }
+        }
+    }
+
+    for (offset, name, declaration) in found.members {
+        let Some(owner) = enclosing_type(nodes, offset) else {
+            continue;
+        };
+        let qualified_name = format!("{}.{}", nodes[owner].qualified_name, name);
+        let id = CodeNode::compute_id(file_path, &qualified_name, NodeKind::Method);
+        if nodes.iter().any(|n| n.id == id) {
+            continue;
+        }
+        let signature = text(declaration, source)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_end_matches([';', ','])
+            .to_string();
+        let start = declaration.start_position();
+        nodes.push(
+            CodeNode::new(name, qualified_name, NodeKind::Method, file_path)
+                .with_lines(
+                    start.row as u32 + 1,
+                    declaration.end_position().row as u32 + 1,
+                )
+                .with_bytes(
+                    declaration.start_byte() as u32,
+                    declaration.end_byte() as u32,
+                )
+                .with_column(start.column as u32)
+                .with_signature(signature)
+                .with_visibility(Visibility::Public),
+        );
+    }
+}
+
+/// Index of the innermost class, interface, struct or enum whose range
+/// holds `offset`.
+fn enclosing_type(nodes: &[CodeNode], offset: usize) -> Option<usize> {
+    let offset = offset as u32;
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| {
+            matches!(
+                n.kind,
+                NodeKind::Class | NodeKind::Interface | NodeKind::Struct | NodeKind::Enum
+            ) && n.byte_start <= offset
+                && offset < n.byte_end
+        })
+        .min_by_key(|(_, n)| n.byte_end - n.byte_start)
+        .map(|(index, _)| index)
+}
+
+pub(crate) fn text<'s>(node: Node, source: &'s str) -> &'s str {
+    &source[node.byte_range()]
+}
+
+pub(crate) fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|n| !n.kind().contains("comment"))
+        .collect()
+}
+
+/// Bare name of a type as written in a supertype list: generics,
+/// `dyn`, constructor arguments and module paths dropped
+/// (`crate::store::Store<T>` → `Store`). `None` for anything that is not
+/// a plain type name.
+pub(crate) fn type_name(written: &str) -> Option<String> {
+    let written = written.trim();
+    let written = written.strip_prefix("dyn ").unwrap_or(written);
+    let base = written.split(['<', '[', '(']).next()?.trim();
+    let name = base.rsplit(['.', ':']).next()?.trim();
+    let plain = !name.is_empty()
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !name.starts_with(|c: char| c.is_ascii_digit());
+    (plain && name != "object").then(|| name.to_string())
+}
+
+fn type_names<'t>(nodes: impl IntoIterator<Item = Node<'t>>, source: &str) -> Vec<String> {
+    nodes
+        .into_iter()
+        .filter_map(|n| type_name(text(n, source)))
+        .collect()
+}
+
+/// Records a type's supertypes, keyed by its name node.
+fn push_heritage(found: &mut Found, declaration: Node, supertypes: Vec<String>) {
+    if supertypes.is_empty() {
+        return;
+    }
+    if let Some(name) = declaration.child_by_field_name("name") {
+        found.heritage.push((name.start_byte(), supertypes));
+    }
+}
+
+/// Records the bodiless members of `kind` in `body`, owned by `declaration`.
+fn push_members<'t>(
+    found: &mut Found<'t>,
+    declaration: Node<'t>,
+    body: Option<Node<'t>>,
+    kinds: &[&str],
+    source: &str,
+) {
+    let (Some(owner), Some(body)) = (declaration.child_by_field_name("name"), body) else {
+        return;
+    };
+    for member in named_children(body) {
+        if !kinds.contains(&member.kind()) {
+            continue;
+        }
+        if let Some(name) = member.child_by_field_name("name") {
+            found
+                .members
+                .push((owner.start_byte(), text(name, source).to_string(), member));
+        }
+    }
+}
+
+// ============================================================================
+// Rust
+// ============================================================================
+
+fn rust_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    match node.kind() {
+        "trait_item" => {
+            let supertraits = node
+                .child_by_field_name("bounds")
+                .map(|bounds| {
+                    type_names(
+                        named_children(bounds)
+                            .into_iter()
+                            .filter(|b| b.kind() != "lifetime"),
+                        source,
+                    )
+                })
+                .unwrap_or_default();
+            push_heritage(found, node, supertraits);
+            push_members(
+                found,
+                node,
+                node.child_by_field_name("body"),
+                &["function_signature_item"],
+                source,
+            );
+        }
+        "impl_item" => {
+            if let Some(name) = node
+                .child_by_field_name("trait")
+                .and_then(|t| type_name(text(t, source)))
+            {
+                found.impls.push((node.byte_range(), name));
+            }
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// JavaScript / TypeScript
+// ============================================================================
+
+fn js_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    match node.kind() {
+        "class_declaration" | "abstract_class_declaration" | "class" => {
+            let mut supertypes = Vec::new();
+            for heritage in named_children(node)
+                .into_iter()
+                .filter(|c| c.kind() == "class_heritage")
+            {
+                for clause in named_children(heritage) {
+                    match clause.kind() {
+                        "extends_clause" => {
+                            let mut cursor = clause.walk();
+                            supertypes.extend(type_names(
+                                clause.children_by_field_name("value", &mut cursor),
+                                source,
+                            ));
+                        }
+                        "implements_clause" => {
+                            supertypes.extend(type_names(named_children(clause), source))
+                        }
+                        // JavaScript: `class A extends B` has no clause node.
+                        _ => supertypes.extend(type_name(text(clause, source))),
+                    }
+                }
+            }
+            push_heritage(found, node, supertypes);
+            push_members(
+                found,
+                node,
+                node.child_by_field_name("body"),
+                &["abstract_method_signature"],
+                source,
+            );
+        }
+        "interface_declaration" => {
+            let mut supertypes = Vec::new();
+            for clause in named_children(node)
+                .into_iter()
+                .filter(|c| c.kind() == "extends_type_clause")
+            {
+                let mut cursor = clause.walk();
+                supertypes.extend(type_names(
+                    clause.children_by_field_name("type", &mut cursor),
+                    source,
+                ));
+            }
+            push_heritage(found, node, supertypes);
+            push_members(
+                found,
+                node,
+                node.child_by_field_name("body"),
+                &["method_signature"],
+                source,
+            );
+        }
+        _ => {}
+    }
+}
+
+// ============================================================================
+// Python
+// ============================================================================
+
+fn python_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    if node.kind() != "class_definition" {
+        return;
+    }
+    let bases = node
+        .child_by_field_name("superclasses")
+        .map(|list| {
+            type_names(
+                named_children(list)
+                    .into_iter()
+                    .filter(|b| b.kind() != "keyword_argument"),
+                source,
+            )
+        })
+        .unwrap_or_default();
+    push_heritage(found, node, bases);
+}
+
+// ============================================================================
+// Java
+// ============================================================================
+
+fn java_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    if !matches!(
+        node.kind(),
+        "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration"
+    ) {
+        return;
+    }
+    let mut supertypes = Vec::new();
+    for clause in named_children(node) {
+        match clause.kind() {
+            "superclass" => supertypes.extend(type_names(named_children(clause), source)),
+            "super_interfaces" | "extends_interfaces" => {
+                for list in named_children(clause) {
+                    supertypes.extend(type_names(named_children(list), source));
+                }
+            }
+            _ => {}
+        }
+    }
+    push_heritage(found, node, supertypes);
+}
+
+// ============================================================================
+// C#
+// ============================================================================
+
+fn csharp_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    if !matches!(
+        node.kind(),
+        "class_declaration" | "struct_declaration" | "interface_declaration" | "record_declaration"
+    ) {
+        return;
+    }
+    let bases = named_children(node)
+        .into_iter()
+        .filter(|c| c.kind() == "base_list")
+        .flat_map(|list| type_names(named_children(list), source))
+        .collect();
+    push_heritage(found, node, bases);
+}
+
+// ============================================================================
+// Go
+// ============================================================================
+
+fn go_hierarchy<'t>(node: Node<'t>, source: &str, found: &mut Found<'t>) {
+    if node.kind() != "type_spec" {
+        return;
+    }
+    let interface = node
+        .child_by_field_name("type")
+        .filter(|t| t.kind() == "interface_type");
+    push_members(
+        found,
+        node,
+        interface,
+        &["method_elem", "method_spec"],
+        source,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::languages::get_parser;
+    use crate::node::{CodeNode, NodeKind};
+    use crate::parser::parse_source;
+    use std::path::Path;
+
+    fn parse(file: &str, source: &str) -> Vec<CodeNode> {
+        let extension = Path::new(file).extension().unwrap().to_str().unwrap();
+        let parser = get_parser(extension).unwrap();
+        parse_source(source, file, parser.as_ref()).unwrap()
+    }
+
+    fn supertypes<'a>(nodes: &'a [CodeNode], qualified_name: &str) -> Vec<&'a str> {
+        nodes
+            .iter()
+            .find(|n| n.qualified_name == qualified_name)
+            .unwrap_or_else(|| panic!("no node {}", qualified_name))
+            .supertypes
+            .iter()
+            .map(String::as_str)
+            .collect()
+    }
+
+    #[test]
+    fn test_rust_traits_and_impls() {
+        let nodes = parse(
+            "store.rs",
+            r#"
+trait Store: Send + crate::base::Named<u8> {
+    fn save(&self);
+    fn load(&self) {}
+}
+struct Pg;
+impl Store for Pg {
+    fn save(&self) {}
+}
+impl Pg {
+    fn connect() {}
+}
+"#,
+        );
+        assert_eq!(supertypes(&nodes, "Store"), ["Send", "Named"]);
+        assert_eq!(supertypes(&nodes, "Pg.save"), ["Store"]);
+        assert!(supertypes(&nodes, "Pg.connect").is_empty());
+
+        let save = nodes.iter().find(|n| n.qualified_name == "Store.save");
+        assert_eq!(save.map(|n| n.kind), Some(NodeKind::Method));
+        assert_eq!(
+            save.and_then(|n| n.signature.as_deref()),
+            Some("fn save(&self)")
+        );
+    }
+
+    #[test]
+    fn test_class_heritage_across_languages() {
+        let ts = parse(
+            "store.ts",
+            r#"
+interface Store extends Base, Other<T> { save(): void; }
+abstract class Repo<T> extends mod.Model<T> implements Store {
+    abstract flush(): void;
+    save() {}
+}
+"#,
+        );
+        assert_eq!(supertypes(&ts, "Store"), ["Base", "Other"]);
+        assert_eq!(supertypes(&ts, "Repo"), ["Model", "Store"]);
+        assert!(ts.iter().any(|n| n.qualified_name == "Store.save"));
+        assert!(ts.iter().any(|n| n.qualified_name == "Repo.flush"));
+
+        let py = parse(
+            "store.py",
+            "class Repo(Base, mod.Mixin, Generic[T], metaclass=ABCMeta):\n    pass\n",
+        );
+        assert_eq!(supertypes(&py, "Repo"), ["Base", "Mixin", "Generic"]);
+
+        let java = parse(
+            "Repo.java",
+            "interface Store extends Base {}\nclass Repo<T> extends Model<T> implements Store, Closeable {}\n",
+        );
+        assert_eq!(supertypes(&java, "Store"), ["Base"]);
+        assert_eq!(supertypes(&java, "Repo"), ["Model", "Store", "Closeable"]);
+
+        let cs = parse(
+            "Repo.cs",
+            "interface IStore : IBase {}\nclass Repo : Model, IStore, IList<int> {}\n",
+        );
+        assert_eq!(supertypes(&cs, "IStore"), ["IBase"]);
+        assert_eq!(supertypes(&cs, "Repo"), ["Model", "IStore", "IList"]);
+    }
+
+    #[test]
+    fn test_go_interface_methods_become_nodes() {
+        let nodes = parse(
+            "store.go",
+            "package store\n\ntype Store interface {\n\tSave() error\n\tLoad(id int) string\n}\n",
+        );
+        let mut members: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Method)
+            .map(|n| n.qualified_name.as_str())
+            .collect();
+        members.sort();
+        assert_eq!(members, ["Store.Load", "Store.Save"]);
+    }
+}
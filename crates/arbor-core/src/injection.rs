@@ -0,0 +1,691 @@
+//! Embedded-language extraction.
+//!
+//! Some files are containers for code in another language: `<script>` blocks
+//! in Vue, Svelte, Astro and HTML, Astro frontmatter, and fenced code blocks
+//! in Markdown. None of these hosts has a grammar of its own here, so we find
+//! the embedded regions and parse each with the `LanguageParser` for its
+//! language.
+//!
+//! Regions are parsed in place with Tree-sitter included ranges: the parser
+//! sees the whole host file but only the region's bytes, so every line,
+//! column and byte offset in the resulting nodes already points into the
+//! host file.
+//!
+//! Vue, Svelte and Astro files also get a component node named after the
+//! file. It carries the calls made anywhere in its scripts plus the
+//! components used in its template, so `UserCard.vue` depends on the TS
+//! modules it calls and on the components it renders.
+//!
+//! SQL is handled separately: string literals that look like queries add
+//! `sql:<table>` references to the function they sit in, which resolve to
+//! `CREATE TABLE` definitions indexed from `.sql` files.
+
+use crate::error::{ParseError, Result};
+use crate::fallback_parser;
+use crate::languages::{get_parser, typescript};
+use crate::node::{CodeNode, NodeKind, Visibility};
+use crate::parser;
+use std::path::Path;
+use tree_sitter::{Point, Range, Tree};
+
+/// Host file extensions whose content is extracted from embedded regions.
+pub const HOST_EXTENSIONS: &[&str] = &["vue", "svelte", "astro", "html", "htm", "md", "markdown"];
+
+/// Prefix for table references found in SQL strings.
+pub const SQL_REFERENCE_PREFIX: &str = "sql:";
+
+/// Returns true if `ext` names a host format handled by this module.
+pub fn is_host_extension(ext: &str) -> bool {
+    let ext = ext.to_ascii_lowercase();
+    HOST_EXTENSIONS.iter().any(|e| *e == ext)
+}
+
+/// A span of a host file written in another language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedRegion {
+    /// Extension of the embedded language (`ts`, `js`, `rs`, ...).
+    pub language: String,
+    /// Byte offset of the first byte of the region in the host file.
+    pub start_byte: usize,
+    /// Byte offset just past the region.
+    pub end_byte: usize,
+}
+
+/// Finds the embedded regions of a host file.
+pub fn find_regions(source: &str, host_ext: &str) -> Vec<EmbeddedRegion> {
+    let host_ext = host_ext.to_ascii_lowercase();
+    let mut regions = match host_ext.as_str() {
+        "md" | "markdown" => markdown_code_blocks(source),
+        "astro" => {
+            let mut regions: Vec<_> = astro_frontmatter(source).into_iter().collect();
+            regions.extend(script_blocks(source));
+            regions
+        }
+        "vue" | "svelte" | "html" | "htm" => script_blocks(source),
+        _ => Vec::new(),
+    };
+    regions.retain(|region| !source[region.start_byte..region.end_byte].trim().is_empty());
+    regions
+}
+
+/// Parses a host file by extracting and parsing its embedded regions.
+pub fn parse_host_source(source: &str, file_path: &str, host_ext: &str) -> Result<Vec<CodeNode>> {
+    parse_host_source_with_trees(source, file_path, host_ext).map(|(nodes, _)| nodes)
+}
+
+/// Like [`parse_host_source`], also returning each region's syntax tree.
+pub(crate) fn parse_host_source_with_trees(
+    source: &str,
+    file_path: &str,
+    host_ext: &str,
+) -> Result<(Vec<CodeNode>, Vec<Tree>)> {
+    let host_ext = host_ext.to_ascii_lowercase();
+    let mut nodes = Vec::new();
+
+    // Markdown keeps its heading sections alongside the code in its blocks.
+    if matches!(host_ext.as_str(), "md" | "markdown") {
+        nodes.extend(fallback_parser::parse_fallback_source(
+            source, file_path, &host_ext,
+        ));
+    }
+
+    let mut trees = Vec::new();
+    let mut script_calls = Vec::new();
+    for region in find_regions(source, &host_ext) {
+        let Some(lang_parser) = get_parser(&region.language) else {
+            continue;
+        };
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&lang_parser.language())
+            .map_err(|e| ParseError::ParserError(format!("Failed to set language: {}", e)))?;
+        parser
+            .set_included_ranges(&[region_range(source, &region)])
+            .map_err(|e| ParseError::ParserError(format!("Invalid embedded range: {:?}", e)))?;
+        let tree = parser
+            .parse(source, None)
+            .ok_or_else(|| ParseError::ParserError("Tree-sitter returned no tree".into()))?;
+
+        let mut region_nodes = lang_parser.extract_nodes(&tree, source, file_path);
+        parser::attach_tree_facts(
+            &tree,
+            source,
+            file_path,
+            &region.language,
+            lang_parser.metrics_rules(),
+            &mut region_nodes,
+        );
+        nodes.extend(region_nodes);
+
+        if is_script_language(&region.language) {
+            script_calls.extend(typescript::extract_call_references(
+                &tree.root_node(),
+                source,
+            ));
+        }
+        trees.push(tree);
+    }
+
+    if matches!(host_ext.as_str(), "vue" | "svelte" | "astro") {
+        if let Some(component) = component_node(source, file_path, script_calls) {
+            nodes.push(component);
+        }
+    }
+
+    Ok((nodes, trees))
+}
+
+fn is_script_language(ext: &str) -> bool {
+    matches!(ext, "ts" | "tsx" | "js" | "jsx")
+}
+
+/// Converts a region to a Tree-sitter range in host coordinates.
+fn region_range(source: &str, region: &EmbeddedRegion) -> Range {
+    Range {
+        start_byte: region.start_byte,
+        end_byte: region.end_byte,
+        start_point: point_at(source, region.start_byte),
+        end_point: point_at(source, region.end_byte),
+    }
+}
+
+fn point_at(source: &str, byte: usize) -> Point {
+    let before = &source.as_bytes()[..byte];
+    let row = before.iter().filter(|&&b| b == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    Point::new(row, byte - line_start)
+}
+
+// ============================================================================
+// Region discovery
+// ============================================================================
+
+/// Finds `<script>` blocks and the language they're written in.
+fn script_blocks(source: &str) -> Vec<EmbeddedRegion> {
+    // ASCII lowercasing keeps byte offsets identical to the source.
+    let lower = source.to_ascii_lowercase();
+    let mut regions = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find("<script") {
+        let tag_start = pos + found;
+        let after_name = tag_start + "<script".len();
+        pos = after_name;
+
+        // Reject `<scripts>` and friends.
+        match lower[after_name..].chars().next() {
+            Some(c) if c.is_whitespace() || c == '>' => {}
+            _ => continue,
+        }
+        let Some(gt) = lower[after_name..].find('>') else {
+            break;
+        };
+        let open_end = after_name + gt;
+        let attributes = &lower[after_name..open_end];
+        if attributes.trim_end().ends_with('/') {
+            pos = open_end + 1;
+            continue; // <script src="..." />
+        }
+
+        let body_start = open_end + 1;
+        let Some(close) = lower[body_start..].find("</script") else {
+            break;
+        };
+        let body_end = body_start + close;
+        pos = body_end;
+
+        if let Some(language) = script_language(attributes) {
+            regions.push(EmbeddedRegion {
+                language: language.to_string(),
+                start_byte: body_start,
+                end_byte: body_end,
+            });
+        }
+    }
+
+    regions
+}
+
+/// Maps `lang`/`type` attributes to an extension; None for non-code scripts
+/// such as JSON data blocks or client-side templates.
+fn script_language(attributes: &str) -> Option<&'static str> {
+    if let Some(lang) = attribute_value(attributes, "lang") {
+        return match lang.as_str() {
+            "ts" | "typescript" => Some("ts"),
+            "tsx" => Some("tsx"),
+            "jsx" => Some("jsx"),
+            "js" | "javascript" => Some("js"),
+            _ => None,
+        };
+    }
+    match attribute_value(attributes, "type").as_deref() {
+        None | Some("module") | Some("text/javascript") | Some("application/javascript") => {
+            Some("js")
+        }
+        Some("text/typescript") | Some("application/typescript") => Some("ts"),
+        _ => None,
+    }
+}
+
+/// Reads `name="value"` (or single-quoted/unquoted) from a tag's attributes.
+fn attribute_value(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while let Some(idx) = rest.find(name) {
+        let preceded_by_space = rest[..idx]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace());
+        let after = rest[idx + name.len()..].trim_start();
+        rest = &rest[idx + name.len()..];
+        if !preceded_by_space {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+            _ => value.split_whitespace().next().unwrap_or_default(),
+        };
+        return Some(value.trim().to_string());
+    }
+    None
+}
+
+/// Astro's `---` fenced TypeScript frontmatter.
+fn astro_frontmatter(source: &str) -> Option<EmbeddedRegion> {
+    let start = source.len() - source.trim_start().len();
+    let rest = source[start..].strip_prefix("---")?;
+    let body_start = start + 3 + rest.find('\n')? + 1;
+
+    let mut offset = body_start;
+    for line in source[body_start..].split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some(EmbeddedRegion {
+                language: "ts".to_string(),
+                start_byte: body_start,
+                end_byte: offset,
+            });
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Fenced code blocks whose info string names a language we can parse.
+fn markdown_code_blocks(source: &str) -> Vec<EmbeddedRegion> {
+    let mut regions = Vec::new();
+    // (fence char, fence length, language, body start)
+    let mut open: Option<(char, usize, Option<String>, usize)> = None;
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~');
+        let fence_len = fence_char
+            .map(|c| trimmed.chars().take_while(|x| *x == c).count())
+            .unwrap_or(0);
+
+        match &open {
+            None => {
+                if fence_len >= 3 && line.len() - trimmed.len() <= 3 {
+                    let info = trimmed[fence_len..].trim();
+                    open = Some((
+                        fence_char.unwrap(),
+                        fence_len,
+                        code_block_language(info),
+                        offset,
+                    ));
+                }
+            }
+            Some((c, len, language, body_start)) => {
+                let closes = fence_char == Some(*c)
+                    && fence_len >= *len
+                    && trimmed[fence_len..].trim().is_empty();
+                if closes {
+                    if let Some(language) = language {
+                        regions.push(EmbeddedRegion {
+                            language: language.clone(),
+                            start_byte: *body_start,
+                            end_byte: line_start,
+                        });
+                    }
+                    open = None;
+                }
+            }
+        }
+    }
+
+    regions
+}
+
+/// Maps a code block info string (` ```rust,ignore `) to a parser extension.
+fn code_block_language(info: &str) -> Option<String> {
+    let word = info
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '{')
+        .next()?
+        .trim_start_matches('.')
+        .to_ascii_lowercase();
+    let ext = match word.as_str() {
+        "rust" => "rs",
+        "python" | "python3" => "py",
+        "typescript" => "ts",
+        "javascript" | "node" => "js",
+        "golang" => "go",
+        "kotlin" => "kt",
+        "ruby" => "rb",
+        "csharp" | "c#" => "cs",
+        "c++" => "cpp",
+        "shell" | "console" => "sh",
+        "elixir" => "ex",
+        "protobuf" => "proto",
+        other => other,
+    };
+    (!ext.is_empty() && get_parser(ext).is_some()).then(|| ext.to_string())
+}
+
+// ============================================================================
+// Components
+// ============================================================================
+
+/// Builds the node for a Vue/Svelte/Astro single-file component.
+fn component_node(source: &str, file_path: &str, mut references: Vec<String>) -> Option<CodeNode> {
+    let name = Path::new(file_path).file_stem()?.to_str()?.to_string();
+    if name.is_empty() {
+        return None;
+    }
+
+    references.extend(template_components(source));
+    references.retain(|r| *r != name);
+    references.sort();
+    references.dedup();
+
+    let line_end = source.lines().count().max(1) as u32;
+    Some(
+        CodeNode::new(&name, &name, NodeKind::Class, file_path)
+            .with_lines(1, line_end)
+            .with_bytes(0, source.len() as u32)
+            .with_visibility(Visibility::Public)
+            .with_references(references),
+    )
+}
+
+/// Collects PascalCase tags (`<UserCard />`) used outside script blocks.
+fn template_components(source: &str) -> Vec<String> {
+    let scripts = script_blocks(source);
+    let mut components = Vec::new();
+
+    for (idx, _) in source.match_indices('<') {
+        if scripts
+            .iter()
+            .any(|s| idx >= s.start_byte && idx < s.end_byte)
+        {
+            continue;
+        }
+        let tag: String = source[idx + 1..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect();
+        if tag.starts_with(|c: char| c.is_ascii_uppercase()) {
+            components.push(tag);
+        }
+    }
+
+    components
+}
+
+// ============================================================================
+// SQL in string literals
+// ============================================================================
+
+/// Adds `sql:<table>` references for queries embedded in string literals.
+///
+/// Each query is attributed to the innermost extracted node whose byte
+/// range contains the string, and to every node sharing its id.
+pub(crate) fn attach_sql_references(tree: &Tree, source: &str, nodes: &mut [CodeNode]) {
+    let references = sql_references(tree, source, nodes);
+    if references.is_empty() {
+        return;
+    }
+    for (idx, reference) in references {
+        // Overloads share an id; keep every copy in step.
+        let id = nodes[idx].id.clone();
+        for node in nodes.iter_mut().filter(|n| n.id == id) {
+            node.references.push(reference.clone());
+        }
+    }
+    for node in nodes.iter_mut() {
+        node.references.sort();
+        node.references.dedup();
+    }
+}
+
+/// Finds (node index, `sql:<table>`) pairs for queries in string literals.
+pub(crate) fn sql_references(
+    tree: &Tree,
+    source: &str,
+    nodes: &[CodeNode],
+) -> Vec<(usize, String)> {
+    let mut references = Vec::new();
+    let mut cursor = tree.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        let is_outer_string = node.is_named()
+            && node.kind().contains("string")
+            && !node
+                .parent()
+                .is_some_and(|parent| parent.kind().contains("string"));
+
+        if is_outer_string {
+            let tables = sql_tables(&source[node.byte_range()]);
+            if !tables.is_empty() {
+                if let Some(owner) = innermost_node(nodes, node.start_byte()) {
+                    for table in tables {
+                        references.push((owner, format!("{}{}", SQL_REFERENCE_PREFIX, table)));
+                    }
+                }
+            }
+        } else if cursor.goto_first_child() {
+            continue;
+        }
+
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    references
+}
+
+fn innermost_node(nodes: &[CodeNode], byte: usize) -> Option<usize> {
+    let byte = byte as u32;
+    nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, n)| {
+            n.kind != NodeKind::Import
+                && n.byte_end > n.byte_start
+                && n.byte_start <= byte
+                && byte < n.byte_end
+        })
+        .min_by_key(|(_, n)| n.byte_end - n.byte_start)
+        .map(|(i, _)| i)
+}
+
+/// Statement keywords that start a query worth recording.
+const SQL_STATEMENTS: &[&str] = &[
+    "SELECT", "INSERT", "UPDATE", "DELETE", "WITH", "MERGE", "REPLACE",
+];
+
+/// Keywords after which the next word names a table.
+const SQL_TABLE_KEYWORDS: &[&str] = &["FROM", "JOIN", "INTO", "UPDATE", "TABLE"];
+
+/// Words that follow a table keyword without being a table.
+const SQL_NOT_TABLES: &[&str] = &[
+    "SELECT", "SET", "LATERAL", "ONLY", "WHERE", "VALUES", "ON", "AS", "OF", "IF", "NOT", "EXISTS",
+    "NOWAIT", "SKIP", "UNNEST",
+];
+
+/// Returns the lowercase table names a SQL string literal reads or writes.
+///
+/// Returns nothing unless the literal starts with a query keyword, so prose
+/// strings mentioning "select from" aren't picked up.
+fn sql_tables(literal: &str) -> Vec<String> {
+    let body = match literal.find(['"', '\'', '`']) {
+        Some(quote) if quote <= 3 => literal[quote..].trim_start_matches(['"', '\'', '`']),
+        _ => literal,
+    };
+
+    let words: Vec<&str> = body
+        .split(|c: char| {
+            !(c.is_alphanumeric() || matches!(c, '_' | '.' | '"' | '`' | '[' | ']' | '$'))
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let Some(first) = words.first() else {
+        return Vec::new();
+    };
+    if !SQL_STATEMENTS.contains(&first.to_ascii_uppercase().as_str()) {
+        return Vec::new();
+    }
+
+    let mut tables = Vec::new();
+    for pair in words.windows(2) {
+        if !SQL_TABLE_KEYWORDS.contains(&pair[0].to_ascii_uppercase().as_str()) {
+            continue;
+        }
+        let name = pair[1]
+            .rsplit('.')
+            .next()
+            .unwrap_or_default()
+            .trim_matches(['"', '`', '[', ']']);
+        let is_identifier = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if is_identifier && !SQL_NOT_TABLES.contains(&name.to_ascii_uppercase().as_str()) {
+            tables.push(name.to_ascii_lowercase());
+        }
+    }
+
+    tables.sort();
+    tables.dedup();
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vue_script_setup_component() {
+        let source = r#"<template>
+  <div @click="refresh"><UserAvatar :user="user" /></div>
+</template>
+
+<script setup lang="ts">
+import { fetchUser } from '@/api/users'
+
+const user = fetchUser(props.id)
+
+function refresh() {
+  reload()
+}
+</script>
+
+<style scoped>
+div { color: red; }
+</style>
+"#;
+        let nodes = parse_host_source(source, "src/components/UserCard.vue", "vue").unwrap();
+
+        let refresh = nodes.iter().find(|n| n.name == "refresh").unwrap();
+        assert_eq!(refresh.kind, NodeKind::Function);
+        assert_eq!(refresh.line_start, 10);
+        assert_eq!(refresh.line_end, 12);
+        assert_eq!(
+            &source[refresh.byte_start as usize..refresh.byte_end as usize],
+            "function refresh() {\n  reload()\n}"
+        );
+        assert_eq!(refresh.references, vec!["reload".to_string()]);
+
+        let import = nodes.iter().find(|n| n.kind == NodeKind::Import).unwrap();
+        assert_eq!(import.name, "@/api/users");
+        assert_eq!(import.references, vec!["fetchUser".to_string()]);
+
+        let component = nodes.iter().find(|n| n.name == "UserCard").unwrap();
+        assert_eq!(component.kind, NodeKind::Class);
+        assert_eq!(component.line_start, 1);
+        assert_eq!(
+            component.references,
+            vec![
+                "UserAvatar".to_string(),
+                "fetchUser".to_string(),
+                "reload".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_svelte_astro_and_html_regions() {
+        let svelte = "<script>\n  export let name;\n</script>\n<h1>{name}</h1>\n";
+        let regions = find_regions(svelte, "svelte");
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].language, "js");
+        assert_eq!(
+            &svelte[regions[0].start_byte..regions[0].end_byte],
+            "\n  export let name;\n"
+        );
+
+        let astro = "---\nimport Card from '../components/Card.astro';\nconst posts = await getPosts();\n---\n<Card />\n";
+        let regions = find_regions(astro, "astro");
+        assert_eq!(regions.len(), 1);
+        assert_eq!(regions[0].language, "ts");
+
+        let html = r#"<script type="application/ld+json">{"a": 1}</script>
+<script src="/app.js"></script>
+<SCRIPT type="module">function boot() {}</SCRIPT>"#;
+        let regions = find_regions(html, "html");
+        assert_eq!(regions.len(), 1);
+        assert_eq!(
+            &html[regions[0].start_byte..regions[0].end_byte],
+            "function boot() {}"
+        );
+    }
+
+    #[test]
+    fn test_markdown_code_blocks() {
+        let source = "# Guide\n\n```rust\nfn setup() {\n    connect();\n}\n```\n\n```text\nfn not_code() {}\n```\n\n```bash\n# not a heading\n```\n";
+        let nodes = parse_host_source(source, "docs/guide.md", "md").unwrap();
+
+        let setup = nodes.iter().find(|n| n.name == "setup").unwrap();
+        assert_eq!(setup.kind, NodeKind::Function);
+        assert_eq!(setup.line_start, 4);
+        assert_eq!(setup.references, vec!["connect".to_string()]);
+
+        assert!(nodes.iter().all(|n| n.name != "not_code"));
+        let sections: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Section)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(sections, vec!["Guide"]);
+    }
+
+    #[test]
+    fn test_sql_references_reach_every_overload() {
+        let source = r#"
+class Repo {
+    void find(int id) { jdbc.query("SELECT * FROM users WHERE id = ?", id); }
+    void find(String name) { jdbc.query("SELECT * FROM accounts WHERE name = ?", name); }
+}
+"#;
+        let parser = get_parser("java").unwrap();
+        let nodes = crate::parser::parse_source(source, "Repo.java", parser.as_ref()).unwrap();
+        let overloads: Vec<_> = nodes.iter().filter(|n| n.name == "find").collect();
+        assert_eq!(overloads.len(), 2);
+        for node in overloads {
+            let tables: Vec<_> = node
+                .references
+                .iter()
+                .filter(|r| r.starts_with(SQL_REFERENCE_PREFIX))
+                .collect();
+            assert_eq!(tables, ["sql:accounts", "sql:users"]);
+        }
+    }
+
+    #[test]
+    fn test_sql_tables_in_strings() {
+        assert_eq!(
+            sql_tables("\"SELECT u.id FROM public.users u JOIN orders o ON o.user_id = u.id\""),
+            vec!["orders".to_string(), "users".to_string()]
+        );
+        assert_eq!(
+            sql_tables("r#\"INSERT INTO audit_log (event) VALUES ($1)\"#"),
+            vec!["audit_log".to_string()]
+        );
+        assert_eq!(
+            sql_tables("'update Accounts set balance = 0'"),
+            vec!["accounts".to_string()]
+        );
+        assert!(sql_tables("\"please select from the list\"").is_empty());
+        assert!(sql_tables("\"SELECT 1\"").is_empty());
+    }
+}
@@ -4,11 +4,33 @@
 //! typedefs, and global variables.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct CParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "case_statement",
+        "conditional_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+    ],
+    parameter_lists: &["parameter_list"],
+    parameters: &["parameter_declaration", "variadic_parameter"],
+};
+
 impl LanguageParser for CParser {
     fn language(&self) -> Language {
         tree_sitter_c::language()
@@ -18,6 +40,10 @@ impl LanguageParser for CParser {
         &["c", "h"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
@@ -41,11 +67,9 @@ fn extract_from_node(node: &Node, source: &str, file_path: &str, nodes: &mut Vec
             }
 
             // Function declarations (prototypes)
-            "declaration" => {
-                if has_function_declarator(node) {
-                    if let Some(code_node) = extract_function_declaration(node, source, file_path) {
-                        nodes.push(code_node);
-                    }
+            "declaration" if has_function_declarator(node) => {
+                if let Some(code_node) = extract_function_declaration(node, source, file_path) {
+                    nodes.push(code_node);
                 }
             }
 
@@ -4,11 +4,41 @@
 //! namespaces, methods, functions, and structs.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct CppParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "for_range_loop",
+        "while_statement",
+        "do_statement",
+        "case_statement",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "for_range_loop",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "try_statement",
+    ],
+    parameter_lists: &["parameter_list"],
+    parameters: &[
+        "parameter_declaration",
+        "optional_parameter_declaration",
+        "variadic_parameter_declaration",
+    ],
+};
+
 impl LanguageParser for CppParser {
     fn language(&self) -> Language {
         tree_sitter_cpp::language()
@@ -18,6 +48,10 @@ impl LanguageParser for CppParser {
         &["cpp", "hpp", "cc", "hh", "cxx", "hxx"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
@@ -137,10 +171,8 @@ fn extract_from_node(
             }
 
             // Field declarations in class
-            "field_declaration" => {
-                if context.is_some() {
-                    extract_fields(node, source, file_path, nodes, context);
-                }
+            "field_declaration" if context.is_some() => {
+                extract_fields(node, source, file_path, nodes, context);
             }
 
             // Template declarations
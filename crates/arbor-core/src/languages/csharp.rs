@@ -4,11 +4,38 @@
 //! constructors, properties, and enums.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct CSharpParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "switch_section",
+        "switch_expression_arm",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "try_statement",
+    ],
+    parameter_lists: &["parameter_list"],
+    parameters: &["parameter"],
+};
+
 impl LanguageParser for CSharpParser {
     fn language(&self) -> Language {
         tree_sitter_c_sharp::language()
@@ -18,6 +45,10 @@ impl LanguageParser for CSharpParser {
         &["cs"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
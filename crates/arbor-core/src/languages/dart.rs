@@ -4,11 +4,35 @@
 //! functions, methods, and imports.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct DartParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_label",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "try_statement",
+    ],
+    parameter_lists: &["formal_parameter_list"],
+    parameters: &[],
+};
+
 impl LanguageParser for DartParser {
     fn language(&self) -> Language {
         tree_sitter_dart::language()
@@ -18,6 +42,10 @@ impl LanguageParser for DartParser {
         &["dart"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
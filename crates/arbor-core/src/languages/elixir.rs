@@ -0,0 +1,451 @@
+//! Elixir language parser implementation.
+//!
+//! Handles .ex and .exs files. Elixir has no declaration syntax of its own:
+//! `defmodule`, `def`, `defp`, `alias` and friends are all macro calls, so
+//! this parser matches `call` nodes by the name of their target.
+
+use crate::languages::LanguageParser;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use std::collections::HashMap;
+use tree_sitter::{Language, Node, Tree};
+
+pub struct ElixirParser;
+
+impl LanguageParser for ElixirParser {
+    fn language(&self) -> Language {
+        tree_sitter_elixir::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ex", "exs"]
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        let aliases = HashMap::new();
+        extract_from_node(&root, source, file_path, &mut nodes, None, &aliases);
+        nodes
+    }
+}
+
+/// Macros that import another module into the current one.
+const IMPORT_MACROS: &[&str] = &["alias", "import", "require", "use"];
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+    aliases: &HashMap<String, String>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        if node.kind() == "call" {
+            match call_target(node, source).as_deref() {
+                Some("defmodule") | Some("defprotocol") => {
+                    extract_module(node, source, file_path, nodes, context);
+                    return;
+                }
+                Some("def") | Some("defp") | Some("defmacro") | Some("defmacrop") => {
+                    if let Some(code_node) =
+                        extract_function(node, source, file_path, context, aliases)
+                    {
+                        nodes.push(code_node);
+                    }
+                    return;
+                }
+                Some(target) if IMPORT_MACROS.contains(&target) => {
+                    if let Some(code_node) = extract_import(node, source, file_path) {
+                        nodes.push(code_node);
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context, aliases);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts `defmodule Name do ... end` and its contents.
+///
+/// Nested modules are qualified by their parent, mirroring how Elixir
+/// names them (`defmodule Outer do defmodule Inner` is `Outer.Inner`).
+fn extract_module(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    let Some(args) = find_child_by_kind(node, "arguments") else {
+        return;
+    };
+    let Some(name_node) = find_child_by_kind(&args, "alias") else {
+        return;
+    };
+    let short_name = get_text(&name_node, source);
+    let module_name = match context {
+        Some(parent) => format!("{}.{}", parent, short_name),
+        None => short_name,
+    };
+
+    let kind = if call_target(node, source).as_deref() == Some("defprotocol") {
+        NodeKind::Interface
+    } else {
+        NodeKind::Module
+    };
+
+    nodes.push(
+        CodeNode::new(&module_name, &module_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(Visibility::Public),
+    );
+
+    let Some(body) = find_child_by_kind(node, "do_block") else {
+        return;
+    };
+    let aliases = collect_aliases(&body, source);
+    for i in 0..body.child_count() {
+        if let Some(child) = body.child(i) {
+            extract_from_node(
+                &child,
+                source,
+                file_path,
+                nodes,
+                Some(&module_name),
+                &aliases,
+            );
+        }
+    }
+}
+
+/// Extracts `def`, `defp`, `defmacro`, and `defmacrop`.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+    aliases: &HashMap<String, String>,
+) -> Option<CodeNode> {
+    let macro_name = call_target(node, source)?;
+    let args = find_child_by_kind(node, "arguments")?;
+    let head = args.named_child(0)?;
+
+    // `def name(args) when guard` wraps the head in a `when` operator.
+    let (head, guard) = if head.kind() == "binary_operator" {
+        (
+            head.child_by_field_name("left")?,
+            head.child_by_field_name("right"),
+        )
+    } else {
+        (head, None)
+    };
+    let name_node = match head.kind() {
+        "call" => head.child_by_field_name("target")?,
+        "identifier" => head,
+        _ => return None,
+    };
+    let name = get_text(&name_node, source);
+
+    let qualified_name = match context {
+        Some(module) => format!("{}.{}", module, name),
+        None => name.clone(),
+    };
+
+    let visibility = if macro_name.ends_with('p') {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    };
+
+    // Body is either a `do ... end` block or a `do:` keyword argument.
+    let mut references = guard
+        .map(|guard| extract_call_references(&guard, source, aliases))
+        .unwrap_or_default();
+    if let Some(block) = find_child_by_kind(node, "do_block") {
+        references.extend(extract_call_references(&block, source, aliases));
+    }
+    if let Some(keywords) = find_child_by_kind(&args, "keywords") {
+        references.extend(extract_call_references(&keywords, source, aliases));
+    }
+    references.sort();
+    references.dedup();
+
+    let signature = format!("{} {}", macro_name, get_text(&head, source));
+
+    Some(
+        CodeNode::new(&name, &qualified_name, NodeKind::Function, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_signature(signature)
+            .with_visibility(visibility)
+            .with_references(references),
+    )
+}
+
+/// Extracts `alias`/`import`/`require`/`use` as an Import node.
+///
+/// Remote calls are written module-qualified (and expanded through aliases
+/// below), while functions pulled in by `import` are called by bare name and
+/// resolve like local calls, so no bound names are recorded.
+fn extract_import(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let args = find_child_by_kind(node, "arguments")?;
+    let module_node = find_child_by_kind(&args, "alias")?;
+    let module = get_text(&module_node, source);
+
+    Some(
+        CodeNode::new(&module, &module, NodeKind::Import, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+    )
+}
+
+/// Collects `alias A.B.C` (→ `C`) and `alias A.B.C, as: D` (→ `D`) bindings
+/// declared directly in a module body.
+fn collect_aliases(body: &Node, source: &str) -> HashMap<String, String> {
+    let mut aliases = HashMap::new();
+    for i in 0..body.child_count() {
+        let Some(child) = body.child(i) else { continue };
+        if child.kind() != "call" || call_target(&child, source).as_deref() != Some("alias") {
+            continue;
+        }
+        let Some(args) = find_child_by_kind(&child, "arguments") else {
+            continue;
+        };
+        let Some(module_node) = find_child_by_kind(&args, "alias") else {
+            continue;
+        };
+        let module = get_text(&module_node, source);
+        let bound = find_child_by_kind(&args, "keywords")
+            .and_then(|keywords| keyword_alias(&keywords, source))
+            .unwrap_or_else(|| module.rsplit('.').next().unwrap_or(&module).to_string());
+        aliases.insert(bound, module);
+    }
+    aliases
+}
+
+/// Returns `D` for the `as: D` keyword.
+fn keyword_alias(keywords: &Node, source: &str) -> Option<String> {
+    for i in 0..keywords.child_count() {
+        let pair = keywords.child(i)?;
+        let key = pair.child_by_field_name("key")?;
+        if get_text(&key, source).trim().trim_end_matches(':') == "as" {
+            let value = pair.child_by_field_name("value")?;
+            return Some(get_text(&value, source));
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the identifier a call is made to (`def`, `alias`, `helper`, ...).
+fn call_target(node: &Node, source: &str) -> Option<String> {
+    let target = node.child_by_field_name("target")?;
+    (target.kind() == "identifier").then(|| get_text(&target, source))
+}
+
+/// Extracts function call references.
+///
+/// Local calls (`deliver(x)`, `|> log()`) are recorded by name; remote calls
+/// are recorded module-qualified, with aliases expanded so
+/// `Templates.render` under `alias Notify.Templates` becomes
+/// `Notify.Templates.render`.
+fn extract_call_references(
+    root: &Node,
+    source: &str,
+    aliases: &HashMap<String, String>,
+) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "call" {
+            if let Some(target) = node.child_by_field_name("target") {
+                match target.kind() {
+                    "identifier" => {
+                        let name = get_text(&target, source);
+                        if !IMPORT_MACROS.contains(&name.as_str()) {
+                            refs.push(name);
+                        }
+                    }
+                    "dot" => {
+                        let left = target.child_by_field_name("left");
+                        let right = target.child_by_field_name("right");
+                        if let (Some(left), Some(right)) = (left, right) {
+                            if left.kind() == "alias" {
+                                let module = get_text(&left, source);
+                                let (head, rest) = match module.split_once('.') {
+                                    Some((head, rest)) => (head, Some(rest)),
+                                    None => (module.as_str(), None),
+                                };
+                                let expanded = match (aliases.get(head), rest) {
+                                    (Some(full), Some(rest)) => format!("{}.{}", full, rest),
+                                    (Some(full), None) => full.clone(),
+                                    (None, _) => module.clone(),
+                                };
+                                refs.push(format!("{}.{}", expanded, get_text(&right, source)));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = ElixirParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "lib/notify/mailer.ex")
+    }
+
+    #[test]
+    fn test_module_functions_and_calls() {
+        let source = r#"defmodule Notify.Mailer do
+  alias Notify.Templates
+  use GenServer
+
+  def send(user, opts \\ []) do
+    body = Templates.render(user)
+    deliver(body)
+    |> log()
+  end
+
+  defp deliver(body), do: body
+
+  defp log(x) when is_binary(x), do: x
+end
+"#;
+        let nodes = parse(source);
+
+        let module = nodes.iter().find(|n| n.name == "Notify.Mailer").unwrap();
+        assert_eq!(module.kind, NodeKind::Module);
+        assert_eq!(module.line_start, 1);
+        assert_eq!(module.line_end, 14);
+
+        let send = nodes.iter().find(|n| n.name == "send").unwrap();
+        assert_eq!(send.qualified_name, "Notify.Mailer.send");
+        assert_eq!(send.line_start, 5);
+        assert_eq!(send.line_end, 9);
+        assert_eq!(send.visibility, Visibility::Public);
+        assert_eq!(
+            send.references,
+            vec![
+                "Notify.Templates.render".to_string(),
+                "deliver".to_string(),
+                "log".to_string()
+            ]
+        );
+
+        let deliver = nodes.iter().find(|n| n.name == "deliver").unwrap();
+        assert_eq!(deliver.visibility, Visibility::Private);
+
+        let log = nodes.iter().find(|n| n.name == "log").unwrap();
+        assert_eq!(log.qualified_name, "Notify.Mailer.log");
+        assert_eq!(log.references, vec!["is_binary".to_string()]);
+
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(imports, vec!["Notify.Templates", "GenServer"]);
+    }
+
+    #[test]
+    fn test_nested_modules_and_protocols() {
+        let source = r#"defmodule Outer do
+  defmodule Inner do
+    def run, do: :ok
+  end
+end
+
+defprotocol Notify.Channel do
+  def push(data)
+end
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Outer.Inner" && n.kind == NodeKind::Module));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Outer.Inner.run" && n.kind == NodeKind::Function));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Notify.Channel" && n.kind == NodeKind::Interface));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Notify.Channel.push"));
+    }
+}
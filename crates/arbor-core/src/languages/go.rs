@@ -4,11 +4,32 @@
 //! and type definitions.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct GoParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "expression_case",
+        "type_case",
+        "communication_case",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "expression_switch_statement",
+        "type_switch_statement",
+        "select_statement",
+    ],
+    parameter_lists: &["parameter_list"],
+    parameters: &[],
+};
+
 impl LanguageParser for GoParser {
     fn language(&self) -> Language {
         tree_sitter_go::language()
@@ -18,6 +39,10 @@ impl LanguageParser for GoParser {
         &["go"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
@@ -4,11 +4,38 @@
 //! constructors, and fields.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct JavaParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_label",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "enhanced_for_statement",
+        "while_statement",
+        "do_statement",
+        "switch_expression",
+        "try_statement",
+        "try_with_resources_statement",
+    ],
+    parameter_lists: &["formal_parameters"],
+    parameters: &["formal_parameter", "spread_parameter"],
+};
+
 impl LanguageParser for JavaParser {
     fn language(&self) -> Language {
         tree_sitter_java::language()
@@ -18,6 +45,10 @@ impl LanguageParser for JavaParser {
         &["java"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
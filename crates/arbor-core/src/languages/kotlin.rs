@@ -0,0 +1,512 @@
+//! Kotlin language parser implementation.
+//!
+//! Handles .kt and .kts files. Extracts classes, interfaces, objects,
+//! functions, methods, properties, and imports. Kotlin's grammar has
+//! almost no named fields, so most lookups go by child kind.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct KotlinParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_expression",
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "when_entry",
+        "catch_block",
+    ],
+    nesting: &[
+        "if_expression",
+        "for_statement",
+        "while_statement",
+        "do_while_statement",
+        "when_expression",
+        "try_expression",
+    ],
+    parameter_lists: &["function_value_parameters"],
+    parameters: &["parameter"],
+};
+
+impl LanguageParser for KotlinParser {
+    fn language(&self) -> Language {
+        tree_sitter_kotlin::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["kt", "kts"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes, None);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        let kind = node.kind();
+
+        match kind {
+            // Classes, interfaces, enum classes, and objects
+            "class_declaration" | "object_declaration" => {
+                if let Some(code_node) = extract_class(node, source, file_path) {
+                    let class_name = code_node.name.clone();
+                    nodes.push(code_node);
+                    if let Some(body) = find_child_by_kind(node, "class_body")
+                        .or_else(|| find_child_by_kind(node, "enum_class_body"))
+                    {
+                        for i in 0..body.child_count() {
+                            if let Some(child) = body.child(i) {
+                                extract_from_node(
+                                    &child,
+                                    source,
+                                    file_path,
+                                    nodes,
+                                    Some(&class_name),
+                                );
+                            }
+                        }
+                    }
+                    return;
+                }
+            }
+
+            // Functions and methods
+            "function_declaration" => {
+                if let Some(code_node) = extract_function(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                // Local functions are not interesting on their own; skip the body.
+                return;
+            }
+
+            // `constructor(...)` inside a class body
+            "secondary_constructor" => {
+                if let Some(code_node) = extract_constructor(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            // Properties (class fields and top-level vals)
+            "property_declaration" => {
+                if let Some(code_node) = extract_property(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            // Package header
+            "package_header" => {
+                if let Some(ident) = find_child_by_kind(node, "identifier") {
+                    let name = get_text(&ident, source);
+                    nodes.push(
+                        CodeNode::new(&name, &name, NodeKind::Module, file_path)
+                            .with_lines(
+                                node.start_position().row as u32 + 1,
+                                node.end_position().row as u32 + 1,
+                            )
+                            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                    );
+                }
+                return;
+            }
+
+            // Imports
+            "import_header" => {
+                if let Some(code_node) = extract_import(node, source, file_path) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children (companion objects keep the enclosing class context)
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a class, interface, enum class, or object declaration.
+fn extract_class(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = find_child_by_kind(node, "type_identifier")?;
+    let name = get_text(&name_node, source);
+
+    let kind = if has_token(node, "interface") {
+        NodeKind::Interface
+    } else if modifiers_text(node, source).contains("enum") {
+        NodeKind::Enum
+    } else {
+        NodeKind::Class
+    };
+
+    Some(
+        CodeNode::new(&name, &name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(detect_visibility(node, source)),
+    )
+}
+
+/// Extracts a top-level function or a method inside a class body.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let name_node = find_child_by_kind(node, "simple_identifier")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Method, format!("{}.{}", ctx, name)),
+        None => (NodeKind::Function, name.clone()),
+    };
+
+    let modifiers = modifiers_text(node, source);
+    let is_async = modifiers.contains("suspend");
+    let signature = build_signature(node, source);
+    let references = extract_call_references(node, source);
+
+    let mut code_node = CodeNode::new(&name, &qualified_name, kind, file_path)
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(detect_visibility(node, source))
+        .with_references(references);
+    if is_async {
+        code_node = code_node.as_async();
+    }
+    Some(code_node)
+}
+
+/// Extracts a secondary constructor.
+fn extract_constructor(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let class_name = context?;
+    let qualified_name = format!("{}.constructor", class_name);
+    let signature = build_signature(node, source);
+    let references = extract_call_references(node, source);
+
+    Some(
+        CodeNode::new(
+            class_name,
+            &qualified_name,
+            NodeKind::Constructor,
+            file_path,
+        )
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(detect_visibility(node, source))
+        .with_references(references),
+    )
+}
+
+/// Extracts a property as a field (in a class) or a variable/constant (top level).
+fn extract_property(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let decl = find_child_by_kind(node, "variable_declaration")?;
+    let name_node = find_child_by_kind(&decl, "simple_identifier")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Field, format!("{}.{}", ctx, name)),
+        None if modifiers_text(node, source).contains("const") => {
+            (NodeKind::Constant, name.clone())
+        }
+        None => (NodeKind::Variable, name.clone()),
+    };
+
+    Some(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(detect_visibility(node, source))
+            .with_references(extract_call_references(node, source)),
+    )
+}
+
+/// Extracts an import header and records the locally bound name.
+///
+/// Examples:
+///   `import com.acme.tax.TaxRules` → references: ["TaxRules"]
+///   `import com.acme.tax.TaxRules as Rules` → references: ["Rules"]
+///   `import com.acme.util.*` → references: ["*"]
+fn extract_import(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let ident = find_child_by_kind(node, "identifier")?;
+    let module = get_text(&ident, source);
+
+    let bound = if find_child_by_kind(node, "wildcard_import").is_some() {
+        "*".to_string()
+    } else if let Some(alias) = find_child_by_kind(node, "import_alias") {
+        let alias_text = get_text(&alias, source);
+        alias_text.trim_start_matches("as").trim().to_string()
+    } else {
+        module.rsplit('.').next().unwrap_or(&module).to_string()
+    };
+
+    Some(
+        CodeNode::new(&module, &module, NodeKind::Import, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_references(vec![bound]),
+    )
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the node has a direct anonymous child token of this kind.
+fn has_token(node: &Node, token: &str) -> bool {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if !child.is_named() && child.kind() == token {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn modifiers_text(node: &Node, source: &str) -> String {
+    find_child_by_kind(node, "modifiers")
+        .map(|m| get_text(&m, source))
+        .unwrap_or_default()
+}
+
+/// Kotlin declarations are public unless a visibility modifier says otherwise.
+fn detect_visibility(node: &Node, source: &str) -> Visibility {
+    let modifiers = modifiers_text(node, source);
+    if modifiers.contains("private") {
+        Visibility::Private
+    } else if modifiers.contains("protected") {
+        Visibility::Protected
+    } else if modifiers.contains("internal") {
+        Visibility::Internal
+    } else {
+        Visibility::Public
+    }
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = find_child_by_kind(node, "function_body")
+        .or_else(|| find_child_by_kind(node, "statements"))
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let header = &source[node.start_byte()..end];
+    header.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts function call references.
+///
+/// `helper(x)` is recorded as `helper`, `this.helper(x)` as `helper`, and
+/// `TaxRules.apply(x)` as `TaxRules.apply` so type-qualified calls resolve
+/// to the right class. Instance calls (`rules.apply`) simply fail to resolve.
+fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "call_expression" {
+            if let Some(callee) = node.child(0) {
+                match callee.kind() {
+                    "simple_identifier" => refs.push(get_text(&callee, source)),
+                    "navigation_expression" => {
+                        if let Some(reference) = navigation_reference(&callee, source) {
+                            refs.push(reference);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+fn navigation_reference(node: &Node, source: &str) -> Option<String> {
+    let receiver = node.child(0)?;
+    let suffix = find_child_by_kind(node, "navigation_suffix")?;
+    let method = find_child_by_kind(&suffix, "simple_identifier")?;
+    let method = get_text(&method, source);
+
+    match receiver.kind() {
+        "this_expression" | "super_expression" => Some(method),
+        "simple_identifier" => Some(format!("{}.{}", get_text(&receiver, source), method)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = KotlinParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "billing.kt")
+    }
+
+    #[test]
+    fn test_class_methods_are_contained() {
+        let source = r#"
+package com.acme.billing
+
+class TaxService(private val rules: TaxRules) {
+    fun calculateTax(amount: Double): Double {
+        return TaxRules.apply(amount) + helper(amount)
+    }
+
+    private suspend fun load() {}
+}
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "com.acme.billing" && n.kind == NodeKind::Module));
+
+        let class = nodes.iter().find(|n| n.name == "TaxService").unwrap();
+        assert_eq!(class.kind, NodeKind::Class);
+        assert_eq!(class.line_start, 4);
+        assert_eq!(class.line_end, 10);
+
+        let method = nodes.iter().find(|n| n.name == "calculateTax").unwrap();
+        assert_eq!(method.kind, NodeKind::Method);
+        assert_eq!(method.qualified_name, "TaxService.calculateTax");
+        assert_eq!(method.line_start, 5);
+        assert_eq!(method.line_end, 7);
+        assert!(method.references.contains(&"helper".to_string()));
+        assert!(method.references.contains(&"TaxRules.apply".to_string()));
+
+        let load = nodes.iter().find(|n| n.name == "load").unwrap();
+        assert!(load.is_async);
+        assert_eq!(load.visibility, Visibility::Private);
+    }
+
+    #[test]
+    fn test_imports_record_bound_names() {
+        let source = r#"
+import com.acme.tax.TaxRules
+import com.acme.util.*
+
+interface Api
+
+fun topLevel(x: Int) = x
+"#;
+        let nodes = parse(source);
+
+        let import = nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Import && n.name == "com.acme.tax.TaxRules")
+            .unwrap();
+        assert_eq!(import.references, vec!["TaxRules".to_string()]);
+
+        let wildcard = nodes
+            .iter()
+            .find(|n| n.kind == NodeKind::Import && n.name == "com.acme.util")
+            .unwrap();
+        assert_eq!(wildcard.references, vec!["*".to_string()]);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Api" && n.kind == NodeKind::Interface));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "topLevel" && n.kind == NodeKind::Function));
+    }
+}
@@ -0,0 +1,443 @@
+//! Lua language parser implementation.
+//!
+//! Handles .lua files. Extracts global and local functions, table
+//! functions (`function M.f()` / `function M:f()` / `M.f = function()`),
+//! and `require` calls.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use std::collections::HashSet;
+use tree_sitter::{Language, Node, Tree};
+
+pub struct LuaParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "elseif_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_statement",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_statement",
+    ],
+    parameter_lists: &["parameters"],
+    parameters: &[],
+};
+
+impl LanguageParser for LuaParser {
+    fn language(&self) -> Language {
+        tree_sitter_lua::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lua"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+
+        // Module aliases bound by `local x = require("...")`. Calls through
+        // them (`x.render()`) are recorded by bare name, since the target
+        // module names its own table independently.
+        let mut modules = HashSet::new();
+        collect_requires(&root, source, file_path, &mut nodes, &mut modules);
+
+        extract_from_node(&root, source, file_path, &mut nodes, &modules);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    modules: &HashSet<String>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        match node.kind() {
+            // function f() / local function f() / function M.f() / function M:f()
+            "function_declaration" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    if let Some(code_node) =
+                        extract_function(node, &name_node, source, file_path, modules)
+                    {
+                        nodes.push(code_node);
+                    }
+                }
+                return;
+            }
+
+            // M.f = function() ... end
+            "assignment_statement" => {
+                if let Some(code_node) = extract_assigned_function(node, source, file_path, modules)
+                {
+                    nodes.push(code_node);
+                    return;
+                }
+            }
+
+            _ => {}
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, modules);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a function from its declaration node and name expression.
+fn extract_function(
+    node: &Node,
+    name_node: &Node,
+    source: &str,
+    file_path: &str,
+    modules: &HashSet<String>,
+) -> Option<CodeNode> {
+    let (name, qualified_name, kind) = match name_node.kind() {
+        "identifier" => {
+            let name = get_text(name_node, source);
+            (name.clone(), name, NodeKind::Function)
+        }
+        "dot_index_expression" | "method_index_expression" => {
+            let table = name_node.child_by_field_name("table")?;
+            let field = name_node
+                .child_by_field_name("field")
+                .or_else(|| name_node.child_by_field_name("method"))?;
+            let name = get_text(&field, source);
+            let qualified = format!("{}.{}", get_text(&table, source), name);
+            (name, qualified, NodeKind::Method)
+        }
+        _ => return None,
+    };
+
+    let visibility = if is_local(node) {
+        Visibility::Private
+    } else {
+        Visibility::Public
+    };
+
+    let references = node
+        .child_by_field_name("body")
+        .map(|body| extract_call_references(&body, source, modules))
+        .unwrap_or_default();
+
+    Some(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_signature(build_signature(node, source))
+            .with_visibility(visibility)
+            .with_references(references),
+    )
+}
+
+/// Extracts `M.f = function(...) ... end` (and `local f = function ...`).
+fn extract_assigned_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    modules: &HashSet<String>,
+) -> Option<CodeNode> {
+    let targets = find_child_by_kind(node, "variable_list")?;
+    let values = find_child_by_kind(node, "expression_list")?;
+    let target = targets.child_by_field_name("name")?;
+    let value = values.child_by_field_name("value")?;
+    if value.kind() != "function_definition" {
+        return None;
+    }
+
+    let mut code_node = extract_function(&value, &target, source, file_path, modules)?;
+    // Use the whole assignment as the span so the name is inside it.
+    code_node.line_start = node.start_position().row as u32 + 1;
+    code_node.byte_start = node.start_byte() as u32;
+    if node.parent().map(|p| p.kind()) == Some("variable_declaration") {
+        code_node.visibility = Visibility::Private;
+    }
+    code_node.signature = Some(build_signature(node, source));
+    Some(code_node)
+}
+
+/// Records `require("mod")` calls bound to a local as Import nodes.
+///
+/// Lua modules return a table rather than binding names, so imports carry
+/// no references; the alias is only used to strip module prefixes off calls.
+fn collect_requires(
+    root: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    modules: &mut HashSet<String>,
+) {
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "function_call" {
+            if let Some(module) = require_target(&node, source) {
+                if let Some(alias) = require_alias(&node, source) {
+                    modules.insert(alias);
+                }
+                nodes.push(
+                    CodeNode::new(&module, &module, NodeKind::Import, file_path)
+                        .with_lines(
+                            node.start_position().row as u32 + 1,
+                            node.end_position().row as u32 + 1,
+                        )
+                        .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                );
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+/// Returns the module name of a `require "x"` / `require("x")` call.
+fn require_target(call: &Node, source: &str) -> Option<String> {
+    let callee = call.child_by_field_name("name")?;
+    if callee.kind() != "identifier" || get_text(&callee, source) != "require" {
+        return None;
+    }
+    let args = call.child_by_field_name("arguments")?;
+    let string = find_child_by_kind(&args, "string")?;
+    let content = string.child_by_field_name("content")?;
+    Some(get_text(&content, source))
+}
+
+/// Returns `x` for `local x = require(...)`.
+fn require_alias(call: &Node, source: &str) -> Option<String> {
+    let expressions = call.parent()?;
+    let assignment = expressions.parent()?;
+    if assignment.kind() != "assignment_statement" {
+        return None;
+    }
+    let targets = find_child_by_kind(&assignment, "variable_list")?;
+    let name = targets.child_by_field_name("name")?;
+    (name.kind() == "identifier").then(|| get_text(&name, source))
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true for `local function f()`.
+fn is_local(node: &Node) -> bool {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if !child.is_named() && child.kind() == "local" {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = find_child_by_kind(node, "parameters")
+        .map(|params| params.end_byte())
+        .or_else(|| {
+            find_child_by_kind(node, "expression_list")
+                .and_then(|list| list.child_by_field_name("value"))
+                .and_then(|value| find_child_by_kind(&value, "parameters").map(|p| p.end_byte()))
+        })
+        .unwrap_or_else(|| node.end_byte());
+    let header = &source[node.start_byte()..end];
+    header.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts function call references.
+///
+/// `helper()` is recorded as `helper`, `self:save()` as `save`, calls through
+/// a required module alias (`util.render()`) as `render`, and calls on any
+/// other table (`M.handle()`) as `M.handle`.
+fn extract_call_references(root: &Node, source: &str, modules: &HashSet<String>) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "function_call" {
+            if let Some(callee) = node.child_by_field_name("name") {
+                match callee.kind() {
+                    "identifier" => {
+                        let name = get_text(&callee, source);
+                        if name != "require" {
+                            refs.push(name);
+                        }
+                    }
+                    "dot_index_expression" | "method_index_expression" => {
+                        let table = callee.child_by_field_name("table");
+                        let field = callee
+                            .child_by_field_name("field")
+                            .or_else(|| callee.child_by_field_name("method"));
+                        if let (Some(table), Some(field)) = (table, field) {
+                            if table.kind() == "identifier" {
+                                let table = get_text(&table, source);
+                                let method = get_text(&field, source);
+                                if table == "self" || modules.contains(&table) {
+                                    refs.push(method);
+                                } else {
+                                    refs.push(format!("{}.{}", table, method));
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = LuaParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "handler.lua")
+    }
+
+    #[test]
+    fn test_table_functions_and_calls() {
+        let source = r#"local util = require("app.util")
+
+local M = {}
+
+function M.handle(req)
+  local body = util.render(req.body)
+  return M.finish(body) .. helper(1)
+end
+
+function M:close()
+  self:flush()
+end
+
+local function helper(n)
+  return n
+end
+
+M.finish = function(body)
+  return body
+end
+
+return M
+"#;
+        let nodes = parse(source);
+
+        let handle = nodes.iter().find(|n| n.name == "handle").unwrap();
+        assert_eq!(handle.kind, NodeKind::Method);
+        assert_eq!(handle.qualified_name, "M.handle");
+        assert_eq!(handle.line_start, 5);
+        assert_eq!(handle.line_end, 8);
+        assert_eq!(
+            handle.references,
+            vec![
+                "M.finish".to_string(),
+                "helper".to_string(),
+                "render".to_string()
+            ]
+        );
+
+        let close = nodes.iter().find(|n| n.name == "close").unwrap();
+        assert_eq!(close.qualified_name, "M.close");
+        assert_eq!(close.references, vec!["flush".to_string()]);
+
+        let helper = nodes.iter().find(|n| n.name == "helper").unwrap();
+        assert_eq!(helper.kind, NodeKind::Function);
+        assert_eq!(helper.visibility, Visibility::Private);
+
+        let finish = nodes.iter().find(|n| n.name == "finish").unwrap();
+        assert_eq!(finish.qualified_name, "M.finish");
+        assert_eq!(finish.line_start, 18);
+        assert_eq!(finish.line_end, 20);
+    }
+
+    #[test]
+    fn test_requires_become_imports() {
+        let source = "local json = require \"cjson\"\nrequire(\"app.boot\")\n";
+        let nodes = parse(source);
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(imports, vec!["cjson", "app.boot"]);
+    }
+}
@@ -8,14 +8,33 @@ mod c;
 mod cpp;
 mod csharp;
 mod dart;
+mod elixir;
 mod go;
 mod java;
+mod kotlin;
+mod lua;
+mod php;
+mod plugin;
+mod protobuf;
 mod python;
+mod ruby;
 mod rust;
-mod typescript;
+mod scala;
+mod shell;
+mod swift;
+pub(crate) mod typescript;
+mod zig;
 
 use crate::fallback_parser::is_fallback_supported_extension;
+use crate::injection::is_host_extension;
+use crate::metrics::MetricsRules;
 use crate::node::CodeNode;
+use std::path::Path;
+
+pub use plugin::{
+    allow_plugins, load_plugins, plugin_extensions, plugins_allowed, PluginLanguage,
+    ALLOW_PLUGINS_ENV,
+};
 
 /// Trait for language-specific parsing logic.
 ///
@@ -29,6 +48,11 @@ pub trait LanguageParser: Send + Sync {
     /// File extensions this parser handles.
     fn extensions(&self) -> &[&str];
 
+    /// Node kinds that code metrics count for this language.
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &MetricsRules::GENERIC
+    }
+
     /// Extracts CodeNodes from a parsed Tree-sitter tree.
     ///
     /// This is where the magic happens. Each language traverses
@@ -41,9 +65,25 @@ pub trait LanguageParser: Send + Sync {
     ) -> Vec<CodeNode>;
 }
 
-/// Gets a parser for the given file extension.
+/// Converts a grammar exported through `tree-sitter-language` into a
+/// `tree_sitter::Language`.
+///
+/// Newer grammar crates (Zig, Protobuf) no longer depend on `tree-sitter`
+/// directly; they export a bare function returning the C `TSLanguage`
+/// pointer, which is exactly what `Language` wraps.
+pub(crate) fn language_from_fn(
+    language: tree_sitter_language::LanguageFn,
+) -> tree_sitter::Language {
+    // SAFETY: `Language` is a `#[repr(transparent)]` wrapper around
+    // `*const TSLanguage`, and the grammar function returns a pointer to a
+    // static, immutable language definition.
+    unsafe { std::mem::transmute::<*const (), tree_sitter::Language>((language.into_raw())()) }
+}
+
+/// Gets a built-in parser for the given file extension.
 ///
-/// Returns None if we don't support this extension.
+/// Returns None if we don't support this extension. Grammar plugins
+/// belong to a project; [`get_parser_for`] finds those too.
 pub fn get_parser(extension: &str) -> Option<Box<dyn LanguageParser>> {
     match extension.to_lowercase().as_str() {
         // TypeScript and JavaScript
@@ -74,10 +114,47 @@ pub fn get_parser(extension: &str) -> Option<Box<dyn LanguageParser>> {
         // Dart
         "dart" => Some(Box::new(dart::DartParser)),
 
+        // Kotlin
+        "kt" | "kts" => Some(Box::new(kotlin::KotlinParser)),
+
+        // Swift
+        "swift" => Some(Box::new(swift::SwiftParser)),
+
+        // Ruby
+        "rb" => Some(Box::new(ruby::RubyParser)),
+
+        // PHP
+        "php" | "phtml" => Some(Box::new(php::PhpParser)),
+
+        // Shell
+        "sh" | "bash" | "zsh" => Some(Box::new(shell::ShellParser)),
+
+        // Scala
+        "scala" | "sc" => Some(Box::new(scala::ScalaParser)),
+
+        // Lua
+        "lua" => Some(Box::new(lua::LuaParser)),
+
+        // Elixir
+        "ex" | "exs" => Some(Box::new(elixir::ElixirParser)),
+
+        // Zig
+        "zig" => Some(Box::new(zig::ZigParser)),
+
+        // Protocol Buffers
+        "proto" => Some(Box::new(protobuf::ProtobufParser)),
+
         _ => None,
     }
 }
 
+/// Gets a parser for a file: a built-in grammar for its extension, else a
+/// plugin loaded with [`load_plugins`] for the project containing it.
+pub fn get_parser_for(path: &Path) -> Option<Box<dyn LanguageParser>> {
+    let extension = path.extension()?.to_str()?;
+    get_parser(extension).or_else(|| plugin::get_plugin_parser(path, extension))
+}
+
 /// Lists all supported file extensions.
 pub fn supported_extensions() -> &'static [&'static str] {
     &[
@@ -91,11 +168,16 @@ pub fn supported_extensions() -> &'static [&'static str] {
         "cpp", "hpp", "cc", "hh", "cxx", "hxx",  // C++
         "cs",   // C#
         "dart", // Dart
-        "kt", "kts",   // Kotlin (fallback parser)
-        "swift", // Swift (fallback parser)
-        "rb",    // Ruby (fallback parser)
-        "php", "phtml", // PHP (fallback parser)
-        "sh", "bash", "zsh", // Shell (fallback parser)
+        "kt", "kts",   // Kotlin
+        "swift", // Swift
+        "rb",    // Ruby
+        "php", "phtml", // PHP
+        "sh", "bash", "zsh", // Shell
+        "scala", "sc",  // Scala
+        "lua", // Lua
+        "ex", "exs",   // Elixir
+        "zig",   // Zig
+        "proto", // Protocol Buffers
     ]
 }
 
@@ -117,10 +199,31 @@ pub fn supported_language_names() -> &'static [&'static str] {
         "ruby",
         "php",
         "shell",
+        "scala",
+        "lua",
+        "elixir",
+        "zig",
+        "protobuf",
+        "thrift",
+        "vue",
+        "svelte",
+        "astro",
+        "html",
+        "sql",
     ]
 }
 
-/// Checks if a file extension is supported.
+/// Checks if a file extension is supported without grammar plugins.
 pub fn is_supported(extension: &str) -> bool {
-    get_parser(extension).is_some() || is_fallback_supported_extension(extension)
+    get_parser(extension).is_some()
+        || is_host_extension(extension)
+        || is_fallback_supported_extension(extension)
+}
+
+/// Checks if a file is supported, including by the plugins of its project.
+pub fn is_supported_file(path: &Path) -> bool {
+    let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    is_supported(extension) || plugin::get_plugin_parser(path, extension).is_some()
 }
@@ -0,0 +1,530 @@
+//! PHP language parser implementation.
+//!
+//! Handles .php and .phtml files (including inline HTML around `<?php`
+//! blocks). Extracts namespaces, classes, interfaces, traits, enums,
+//! functions, methods, properties, and `use` imports.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct PhpParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "else_if_clause",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "case_statement",
+        "match_conditional_expression",
+        "catch_clause",
+        "conditional_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "foreach_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "match_expression",
+        "try_statement",
+    ],
+    parameter_lists: &["formal_parameters"],
+    parameters: &[],
+};
+
+impl LanguageParser for PhpParser {
+    fn language(&self) -> Language {
+        tree_sitter_php::language_php()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["php", "phtml"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes, None);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        let kind = node.kind();
+
+        match kind {
+            "class_declaration"
+            | "interface_declaration"
+            | "trait_declaration"
+            | "enum_declaration" => {
+                if let Some(code_node) = extract_type(node, source, file_path) {
+                    let type_name = code_node.name.clone();
+                    nodes.push(code_node);
+                    if let Some(body) = node.child_by_field_name("body") {
+                        for i in 0..body.child_count() {
+                            if let Some(child) = body.child(i) {
+                                extract_from_node(
+                                    &child,
+                                    source,
+                                    file_path,
+                                    nodes,
+                                    Some(&type_name),
+                                );
+                            }
+                        }
+                    }
+                    return;
+                }
+            }
+
+            "function_definition" | "method_declaration" => {
+                if let Some(code_node) = extract_function(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "property_declaration" => {
+                extract_properties(node, source, file_path, nodes, context);
+                return;
+            }
+
+            "namespace_definition" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    nodes.push(
+                        CodeNode::new(&name, &name, NodeKind::Module, file_path)
+                            .with_lines(
+                                node.start_position().row as u32 + 1,
+                                node.end_position().row as u32 + 1,
+                            )
+                            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                    );
+                }
+            }
+
+            "namespace_use_declaration" => {
+                extract_use(node, source, file_path, nodes);
+                return;
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a class, interface, trait, or enum declaration.
+fn extract_type(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let kind = match node.kind() {
+        "interface_declaration" | "trait_declaration" => NodeKind::Interface,
+        "enum_declaration" => NodeKind::Enum,
+        _ => NodeKind::Class,
+    };
+
+    Some(
+        CodeNode::new(&name, &name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(Visibility::Public),
+    )
+}
+
+/// Extracts a free function or a method.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) if name == "__construct" => (NodeKind::Constructor, format!("{}.{}", ctx, name)),
+        Some(ctx) => (NodeKind::Method, format!("{}.{}", ctx, name)),
+        None => (NodeKind::Function, name.clone()),
+    };
+
+    let is_static = find_child_by_kind(node, "static_modifier").is_some();
+    let signature = build_signature(node, source);
+    let references = extract_call_references(node, source);
+
+    let mut code_node = CodeNode::new(&name, &qualified_name, kind, file_path)
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(detect_visibility(node, source))
+        .with_references(references);
+    if is_static {
+        code_node = code_node.as_static();
+    }
+    Some(code_node)
+}
+
+/// Extracts class properties (`private TaxRules $rules;`).
+fn extract_properties(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    let visibility = detect_visibility(node, source);
+    for i in 0..node.child_count() {
+        let Some(element) = node.child(i) else {
+            continue;
+        };
+        if element.kind() != "property_element" {
+            continue;
+        }
+        let Some(var) = find_child_by_kind(&element, "variable_name") else {
+            continue;
+        };
+        let name = get_text(&var, source).trim_start_matches('$').to_string();
+        let qualified_name = match context {
+            Some(ctx) => format!("{}.{}", ctx, name),
+            None => name.clone(),
+        };
+        nodes.push(
+            CodeNode::new(&name, &qualified_name, NodeKind::Field, file_path)
+                .with_lines(
+                    node.start_position().row as u32 + 1,
+                    node.end_position().row as u32 + 1,
+                )
+                .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+                .with_column(var.start_position().column as u32)
+                .with_visibility(visibility),
+        );
+    }
+}
+
+/// Extracts `use` declarations, one Import node per imported name.
+///
+/// Examples:
+///   `use App\Tax\TaxRules;` → "App\Tax\TaxRules", references: ["TaxRules"]
+///   `use App\Tax\TaxRules as Rules;` → references: ["Rules"]
+///   `use App\Util\{Helper, Other as O};` → "App\Util\Helper" ["Helper"], "App\Util\Other" ["O"]
+fn extract_use(node: &Node, source: &str, file_path: &str, nodes: &mut Vec<CodeNode>) {
+    let group_prefix = find_child_by_kind(node, "namespace_name").map(|n| get_text(&n, source));
+
+    let mut push_import = |clause: &Node, path: String| {
+        let bound = find_child_by_kind(clause, "namespace_aliasing_clause")
+            .and_then(|alias| find_child_by_kind(&alias, "name").map(|n| get_text(&n, source)))
+            .unwrap_or_else(|| path.rsplit('\\').next().unwrap_or(&path).to_string());
+        nodes.push(
+            CodeNode::new(&path, &path, NodeKind::Import, file_path)
+                .with_lines(
+                    clause.start_position().row as u32 + 1,
+                    clause.end_position().row as u32 + 1,
+                )
+                .with_bytes(clause.start_byte() as u32, clause.end_byte() as u32)
+                .with_references(vec![bound]),
+        );
+    };
+
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else {
+            continue;
+        };
+        match child.kind() {
+            "namespace_use_clause" => {
+                let Some(target) = find_child_by_kind(&child, "qualified_name")
+                    .or_else(|| find_child_by_kind(&child, "name"))
+                else {
+                    continue;
+                };
+                let path = get_text(&target, source)
+                    .trim_start_matches('\\')
+                    .to_string();
+                push_import(&child, path);
+            }
+            "namespace_use_group" => {
+                for j in 0..child.child_count() {
+                    let Some(clause) = child.child(j) else {
+                        continue;
+                    };
+                    if clause.kind() != "namespace_use_group_clause" {
+                        continue;
+                    }
+                    let Some(target) = find_child_by_kind(&clause, "namespace_name") else {
+                        continue;
+                    };
+                    let leaf = get_text(&target, source);
+                    let path = match &group_prefix {
+                        Some(prefix) => format!("{}\\{}", prefix, leaf),
+                        None => leaf,
+                    };
+                    push_import(&clause, path);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// PHP members are public unless a visibility modifier says otherwise.
+fn detect_visibility(node: &Node, source: &str) -> Visibility {
+    match find_child_by_kind(node, "visibility_modifier")
+        .map(|m| get_text(&m, source))
+        .as_deref()
+    {
+        Some("private") => Visibility::Private,
+        Some("protected") => Visibility::Protected,
+        _ => Visibility::Public,
+    }
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let header = &source[node.start_byte()..end];
+    header
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(';')
+        .to_string()
+}
+
+/// Strips a namespace qualifier: `\App\Tax\TaxRules` → `TaxRules`.
+fn short_name(name: &str) -> String {
+    name.rsplit('\\').next().unwrap_or(name).to_string()
+}
+
+/// Extracts call references.
+///
+/// `helper()` → `helper`, `$this->helper()` / `self::helper()` → `helper`,
+/// `TaxRules::apply()` → `TaxRules.apply`, and `new TaxRules()` → `TaxRules`.
+/// Calls on other objects (`$rules->apply()`) can't be typed and are skipped.
+fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        match node.kind() {
+            "function_call_expression" => {
+                if let Some(function) = node.child_by_field_name("function") {
+                    if matches!(function.kind(), "name" | "qualified_name") {
+                        refs.push(short_name(&get_text(&function, source)));
+                    }
+                }
+            }
+            "member_call_expression" | "nullsafe_member_call_expression" => {
+                let object = node.child_by_field_name("object");
+                let name = node.child_by_field_name("name");
+                if let (Some(object), Some(name)) = (object, name) {
+                    if get_text(&object, source) == "$this" {
+                        refs.push(get_text(&name, source));
+                    }
+                }
+            }
+            "scoped_call_expression" => {
+                let scope = node.child_by_field_name("scope");
+                let name = node.child_by_field_name("name");
+                if let (Some(scope), Some(name)) = (scope, name) {
+                    let method = get_text(&name, source);
+                    match scope.kind() {
+                        "relative_scope" => refs.push(method),
+                        "name" | "qualified_name" => refs.push(format!(
+                            "{}.{}",
+                            short_name(&get_text(&scope, source)),
+                            method
+                        )),
+                        _ => {}
+                    }
+                }
+            }
+            "object_creation_expression" => {
+                let class = find_child_by_kind(&node, "name")
+                    .or_else(|| find_child_by_kind(&node, "qualified_name"));
+                if let Some(class) = class {
+                    refs.push(short_name(&get_text(&class, source)));
+                }
+            }
+            _ => {}
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = PhpParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "TaxService.php")
+    }
+
+    #[test]
+    fn test_class_members_are_contained() {
+        let source = r#"<?php
+namespace App\Billing;
+
+class TaxService extends Base implements Api {
+    private TaxRules $rules;
+
+    public function __construct(TaxRules $rules) {}
+
+    public function calculateTax(float $amount): float {
+        return $this->helper($amount) + TaxRules::apply($amount) + strlen('x') + new Invoice();
+    }
+
+    private static function helper($x) { return $x; }
+}
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "App\\Billing" && n.kind == NodeKind::Module));
+
+        let class = nodes.iter().find(|n| n.name == "TaxService").unwrap();
+        assert_eq!(class.kind, NodeKind::Class);
+        assert_eq!(class.line_start, 4);
+        assert_eq!(class.line_end, 14);
+
+        let method = nodes.iter().find(|n| n.name == "calculateTax").unwrap();
+        assert_eq!(method.qualified_name, "TaxService.calculateTax");
+        assert_eq!(method.line_start, 9);
+        assert_eq!(method.line_end, 11);
+        assert_eq!(
+            method.signature.as_deref(),
+            Some("public function calculateTax(float $amount): float")
+        );
+        for expected in ["helper", "TaxRules.apply", "strlen", "Invoice"] {
+            assert!(
+                method.references.contains(&expected.to_string()),
+                "missing {expected}"
+            );
+        }
+
+        let helper = nodes.iter().find(|n| n.name == "helper").unwrap();
+        assert!(helper.is_static);
+        assert_eq!(helper.visibility, Visibility::Private);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "__construct" && n.kind == NodeKind::Constructor));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "TaxService.rules" && n.kind == NodeKind::Field));
+    }
+
+    #[test]
+    fn test_use_declarations_record_bound_names() {
+        let source = r#"<?php
+use App\Tax\TaxRules;
+use App\Util\{Helper, Other as O};
+
+interface Api { public function go(); }
+function top_level($x) { return $x; }
+"#;
+        let nodes = parse(source);
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| (n.name.as_str(), n.references.clone()))
+            .collect();
+
+        assert_eq!(
+            imports,
+            vec![
+                ("App\\Tax\\TaxRules", vec!["TaxRules".to_string()]),
+                ("App\\Util\\Helper", vec!["Helper".to_string()]),
+                ("App\\Util\\Other", vec!["O".to_string()]),
+            ]
+        );
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Api.go" && n.kind == NodeKind::Method));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "top_level" && n.kind == NodeKind::Function));
+    }
+}
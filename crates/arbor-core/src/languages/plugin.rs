@@ -0,0 +1,683 @@
+//! Runtime-loadable grammar plugins.
+//!
+//! Teams with internal DSLs can index them without forking Arbor by dropping
+//! a compiled tree-sitter grammar and a tags query into the project:
+//!
+//! ```text
+//! .arbor/languages/flow/
+//!   language.toml     # extensions = ["flow"]
+//!   libtree-sitter-flow.so
+//!   tags.scm
+//! ```
+//!
+//! `language.toml` fields:
+//!   - `extensions` (required): file extensions handled by the plugin.
+//!   - `library`: grammar shared library, relative to the plugin directory.
+//!     Defaults to the only `.so`/`.dylib`/`.dll` in the directory.
+//!   - `symbol`: exported language function. Defaults to `tree_sitter_<name>`.
+//!   - `query`: tags query file. Defaults to `tags.scm`.
+//!
+//! The query uses the tree-sitter tags conventions: `@definition.function`,
+//! `@definition.method`, `@definition.class`, ... around the whole
+//! definition, `@name` on its identifier, and `@reference.call` (or any
+//! other `@reference.*`) with `@name` on the callee. Definitions nested in a
+//! class-like definition become methods qualified by it (`Flow.run`), and
+//! references are attached to the innermost definition that contains them,
+//! so plugin languages produce the same `CodeNode`s as the built-in parsers.
+//!
+//! Built-in tree-sitter parsers keep their extensions; a plugin can take
+//! over an extension that only the fallback parser handled.
+//!
+//! Loading a plugin runs native code from the checkout, so it is off unless
+//! the user opts in: `arbor --allow-plugins`, or `ARBOR_ALLOW_PLUGINS=1` in
+//! the environment. Nothing in the repository can turn it on. Plugins are
+//! registered under the project root they were loaded from and only parse
+//! files inside it.
+
+use crate::error::{ParseError, Result};
+use crate::languages::LanguageParser;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use crate::project::ByProject;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+use tree_sitter::{Language, Node, Query, QueryCursor, Tree};
+
+/// Name of the manifest inside each plugin directory.
+const MANIFEST_FILE: &str = "language.toml";
+
+/// Default tags query file.
+const DEFAULT_QUERY_FILE: &str = "tags.scm";
+
+/// Shared library extensions we look for when `library` is omitted.
+const LIBRARY_EXTENSIONS: &[&str] = &["so", "dylib", "dll"];
+
+/// Environment variable that allows loading plugins when set to `1`/`true`.
+pub const ALLOW_PLUGINS_ENV: &str = "ARBOR_ALLOW_PLUGINS";
+
+/// Set by [`allow_plugins`] for the rest of the process.
+static PLUGINS_ALLOWED: AtomicBool = AtomicBool::new(false);
+
+/// Contents of `language.toml`.
+#[derive(Debug, Deserialize)]
+struct PluginManifest {
+    extensions: Vec<String>,
+    library: Option<PathBuf>,
+    symbol: Option<String>,
+    query: Option<PathBuf>,
+}
+
+/// A grammar loaded at runtime together with its compiled tags query.
+pub struct PluginLanguage {
+    name: String,
+    language: Language,
+    query: Query,
+    /// Leaked once per loaded plugin so `LanguageParser::extensions` can
+    /// hand out `&str`s; plugins live for the rest of the process anyway.
+    extensions: Vec<&'static str>,
+    /// Keeps the grammar's code mapped while `language` is in use.
+    _library: Option<libloading::Library>,
+}
+
+impl PluginLanguage {
+    /// Builds a plugin from an already-loaded grammar and tags query source.
+    pub(crate) fn from_parts(
+        name: &str,
+        language: Language,
+        extensions: &[String],
+        query_source: &str,
+    ) -> Result<Self> {
+        let query = Query::new(&language, query_source).map_err(|e| ParseError::PluginError {
+            name: name.to_string(),
+            message: format!("invalid tags query: {}", e),
+        })?;
+
+        Ok(Self {
+            name: name.to_string(),
+            language,
+            query,
+            extensions: extensions
+                .iter()
+                .map(|ext| &*Box::leak(ext.to_lowercase().into_boxed_str()))
+                .collect(),
+            _library: None,
+        })
+    }
+
+    /// Loads the plugin described by `<dir>/language.toml`.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let name = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let plugin_error = |message: String| ParseError::PluginError {
+            name: name.clone(),
+            message,
+        };
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let manifest = std::fs::read_to_string(&manifest_path)
+            .map_err(|e| ParseError::io(&manifest_path, e))?;
+        let manifest: PluginManifest = toml::from_str(&manifest)
+            .map_err(|e| plugin_error(format!("invalid {}: {}", MANIFEST_FILE, e)))?;
+        if manifest.extensions.is_empty() {
+            return Err(plugin_error("no extensions declared".into()));
+        }
+
+        let library_path = match &manifest.library {
+            Some(library) => dir.join(library),
+            None => find_library(dir).ok_or_else(|| {
+                plugin_error(format!(
+                    "no grammar library found (expected a .{} file)",
+                    LIBRARY_EXTENSIONS.join("/.")
+                ))
+            })?,
+        };
+        let symbol = manifest
+            .symbol
+            .clone()
+            .unwrap_or_else(|| format!("tree_sitter_{}", name.replace('-', "_")));
+
+        let query_path = dir.join(
+            manifest
+                .query
+                .as_deref()
+                .unwrap_or(Path::new(DEFAULT_QUERY_FILE)),
+        );
+        let query_source =
+            std::fs::read_to_string(&query_path).map_err(|e| ParseError::io(&query_path, e))?;
+
+        info!(
+            "Loading language plugin library {} (symbol {})",
+            library_path.display(),
+            symbol
+        );
+        // SAFETY: loading a grammar runs its initializers and calling the
+        // exported function must return a `TSLanguage` pointer. That is the
+        // contract of every tree-sitter grammar library; the user opted in
+        // to running it (see `plugins_allowed`). A null pointer is rejected
+        // before it becomes a `Language`.
+        let (library, language) = unsafe {
+            let library = libloading::Library::new(&library_path).map_err(|e| {
+                plugin_error(format!("failed to load {}: {}", library_path.display(), e))
+            })?;
+            let constructor: libloading::Symbol<unsafe extern "C" fn() -> *const ()> = library
+                .get(symbol.as_bytes())
+                .map_err(|e| plugin_error(format!("missing symbol '{}': {}", symbol, e)))?;
+            let pointer = constructor();
+            if pointer.is_null() {
+                return Err(plugin_error(format!(
+                    "'{}' returned a null language",
+                    symbol
+                )));
+            }
+            let language = std::mem::transmute::<*const (), Language>(pointer);
+            (library, language)
+        };
+
+        let version = language.version();
+        if !(tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION)
+            .contains(&version)
+        {
+            return Err(plugin_error(format!(
+                "grammar ABI version {} is not supported (expected {}..={})",
+                version,
+                tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION,
+                tree_sitter::LANGUAGE_VERSION
+            )));
+        }
+
+        let mut plugin = Self::from_parts(&name, language, &manifest.extensions, &query_source)?;
+        plugin._library = Some(library);
+        Ok(plugin)
+    }
+
+    /// The plugin's name (its directory name).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl LanguageParser for PluginLanguage {
+    fn language(&self) -> Language {
+        self.language.clone()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let (definitions, references) = run_tags_query(&self.query, tree, source);
+        build_nodes(definitions, references, source, file_path)
+    }
+}
+
+/// Boxed handle handed out by `get_parser`.
+struct PluginParser(Arc<PluginLanguage>);
+
+impl LanguageParser for PluginParser {
+    fn language(&self) -> Language {
+        self.0.language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.0.extensions()
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        self.0.extract_nodes(tree, source, file_path)
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// The plugins of one project.
+#[derive(Default)]
+struct Registry {
+    /// Extension (lowercase) -> plugin.
+    by_extension: HashMap<String, Arc<PluginLanguage>>,
+    /// Plugin directories loaded, so re-indexing doesn't reload.
+    loaded_dirs: HashMap<PathBuf, Arc<PluginLanguage>>,
+}
+
+impl Registry {
+    /// Makes a plugin available for its extensions.
+    fn register(&mut self, plugin: Arc<PluginLanguage>) {
+        for ext in &plugin.extensions {
+            self.by_extension
+                .insert(ext.to_string(), Arc::clone(&plugin));
+        }
+    }
+}
+
+static REGISTRY: ByProject<Registry> = ByProject::new();
+
+/// Allows [`load_plugins`] to load grammar libraries for the rest of the
+/// process, as `--allow-plugins` does.
+pub fn allow_plugins() {
+    PLUGINS_ALLOWED.store(true, Ordering::Relaxed);
+}
+
+/// True if the user allowed plugins, with [`allow_plugins`] or
+/// [`ALLOW_PLUGINS_ENV`].
+pub fn plugins_allowed() -> bool {
+    PLUGINS_ALLOWED.load(Ordering::Relaxed)
+        || std::env::var(ALLOW_PLUGINS_ENV)
+            .is_ok_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// Loads every plugin under `<project_root>/.arbor/languages/`.
+///
+/// Does nothing unless [`plugins_allowed`]; a project that ships plugins
+/// gets a warning saying how to opt in. Plugins that fail to load are
+/// logged and skipped so one broken grammar doesn't stop indexing. The
+/// plugins replace any loaded earlier for this project and parse only
+/// files under `project_root`. Returns the names of the plugins now
+/// registered for this project.
+pub fn load_plugins(project_root: &Path) -> Vec<String> {
+    load_plugins_if(project_root, plugins_allowed())
+}
+
+fn load_plugins_if(project_root: &Path, allowed: bool) -> Vec<String> {
+    let plugins_dir = project_root.join(".arbor").join("languages");
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(&plugins_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .collect();
+    dirs.sort();
+
+    if dirs.is_empty() {
+        REGISTRY.remove(project_root);
+        return Vec::new();
+    }
+    if !allowed {
+        warn!(
+            "Not loading {} language plugin(s) from {}: plugins run native code; \
+             pass --allow-plugins or set {}=1 to load them",
+            dirs.len(),
+            plugins_dir.display(),
+            ALLOW_PLUGINS_ENV
+        );
+        REGISTRY.remove(project_root);
+        return Vec::new();
+    }
+
+    let previous = REGISTRY.for_root(project_root);
+    let mut registry = Registry::default();
+    let mut loaded = Vec::new();
+    for dir in dirs {
+        let key = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+        let existing = previous
+            .as_ref()
+            .and_then(|previous| previous.loaded_dirs.get(&key).cloned());
+        let plugin = match existing {
+            Some(plugin) => plugin,
+            None => match PluginLanguage::load(&dir) {
+                Ok(plugin) => {
+                    info!(
+                        "Loaded language plugin '{}' for .{}",
+                        plugin.name(),
+                        plugin.extensions.join(", .")
+                    );
+                    Arc::new(plugin)
+                }
+                Err(e) => {
+                    warn!("Skipping language plugin {}: {}", dir.display(), e);
+                    continue;
+                }
+            },
+        };
+        loaded.push(plugin.name().to_string());
+        registry.loaded_dirs.insert(key, Arc::clone(&plugin));
+        registry.register(plugin);
+    }
+    REGISTRY.set(project_root, registry);
+    loaded
+}
+
+/// Returns a parser for `path` if a plugin of its project registered
+/// `extension`.
+pub(crate) fn get_plugin_parser(path: &Path, extension: &str) -> Option<Box<dyn LanguageParser>> {
+    let registry = REGISTRY.for_path(path)?;
+    let plugin = registry.by_extension.get(&extension.to_lowercase())?;
+    Some(Box::new(PluginParser(Arc::clone(plugin))))
+}
+
+/// Lists the extensions registered by the plugins of `project_root`.
+pub fn plugin_extensions(project_root: &Path) -> Vec<String> {
+    let mut extensions: Vec<String> = REGISTRY
+        .for_root(project_root)
+        .map(|registry| registry.by_extension.keys().cloned().collect())
+        .unwrap_or_default();
+    extensions.sort();
+    extensions
+}
+
+fn find_library(dir: &Path) -> Option<PathBuf> {
+    let mut libraries: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| LIBRARY_EXTENSIONS.contains(&ext))
+        })
+        .collect();
+    libraries.sort();
+    (libraries.len() == 1).then(|| libraries.remove(0))
+}
+
+// ============================================================================
+// Tags query evaluation
+// ============================================================================
+
+struct Definition {
+    kind: NodeKind,
+    name: String,
+    start_byte: usize,
+    end_byte: usize,
+    start_line: u32,
+    end_line: u32,
+    name_column: u32,
+}
+
+struct Reference {
+    name: String,
+    byte: usize,
+}
+
+/// Maps a `@definition.<kind>` suffix to a node kind.
+fn definition_kind(suffix: &str) -> Option<NodeKind> {
+    Some(match suffix {
+        "function" | "macro" => NodeKind::Function,
+        "method" => NodeKind::Method,
+        "constructor" => NodeKind::Constructor,
+        "class" => NodeKind::Class,
+        "interface" | "trait" | "protocol" => NodeKind::Interface,
+        "struct" => NodeKind::Struct,
+        "enum" => NodeKind::Enum,
+        "type" => NodeKind::TypeAlias,
+        "module" | "namespace" => NodeKind::Module,
+        "constant" => NodeKind::Constant,
+        "variable" => NodeKind::Variable,
+        "field" | "property" => NodeKind::Field,
+        "import" => NodeKind::Import,
+        _ => return None,
+    })
+}
+
+/// Returns true for definitions whose nested functions are methods.
+fn is_type_like(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Class | NodeKind::Interface | NodeKind::Struct | NodeKind::Enum
+    )
+}
+
+fn run_tags_query(query: &Query, tree: &Tree, source: &str) -> (Vec<Definition>, Vec<Reference>) {
+    let capture_names = query.capture_names();
+    let mut definitions = Vec::new();
+    let mut references = Vec::new();
+
+    let mut cursor = QueryCursor::new();
+    for match_ in cursor.matches(query, tree.root_node(), source.as_bytes()) {
+        let mut name_node: Option<Node> = None;
+        let mut definition: Option<(NodeKind, Node)> = None;
+        let mut reference: Option<Node> = None;
+
+        for capture in match_.captures {
+            let capture_name = capture_names[capture.index as usize];
+            if capture_name == "name" {
+                name_node = Some(capture.node);
+            } else if let Some(suffix) = capture_name.strip_prefix("definition.") {
+                if let Some(kind) = definition_kind(suffix) {
+                    definition = Some((kind, capture.node));
+                }
+            } else if capture_name.starts_with("reference.") {
+                reference = Some(capture.node);
+            }
+        }
+
+        let Some(name_node) = name_node else { continue };
+        let name = source[name_node.byte_range()].to_string();
+        if name.is_empty() {
+            continue;
+        }
+
+        if let Some((kind, node)) = definition {
+            definitions.push(Definition {
+                kind,
+                name,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start_line: node.start_position().row as u32 + 1,
+                end_line: node.end_position().row as u32 + 1,
+                name_column: name_node.start_position().column as u32,
+            });
+        } else if let Some(node) = reference {
+            references.push(Reference {
+                name,
+                byte: node.start_byte(),
+            });
+        }
+    }
+
+    // Outer definitions first; several patterns may tag the same node.
+    definitions.sort_by(|a, b| {
+        a.start_byte
+            .cmp(&b.start_byte)
+            .then(b.end_byte.cmp(&a.end_byte))
+    });
+    definitions.dedup_by(|a, b| a.start_byte == b.start_byte && a.end_byte == b.end_byte);
+
+    (definitions, references)
+}
+
+fn build_nodes(
+    definitions: Vec<Definition>,
+    references: Vec<Reference>,
+    source: &str,
+    file_path: &str,
+) -> Vec<CodeNode> {
+    // Resolve nesting with a stack of enclosing definitions.
+    let mut parents: Vec<Option<usize>> = Vec::with_capacity(definitions.len());
+    let mut stack: Vec<usize> = Vec::new();
+    for (i, def) in definitions.iter().enumerate() {
+        while let Some(&top) = stack.last() {
+            if definitions[top].end_byte >= def.end_byte
+                && definitions[top].start_byte <= def.start_byte
+            {
+                break;
+            }
+            stack.pop();
+        }
+        parents.push(stack.last().copied());
+        stack.push(i);
+    }
+
+    // Attach each reference to the innermost definition containing it.
+    let mut refs_by_def: Vec<Vec<String>> = vec![Vec::new(); definitions.len()];
+    for reference in references {
+        let innermost = definitions
+            .iter()
+            .enumerate()
+            .filter(|(_, def)| def.start_byte <= reference.byte && reference.byte < def.end_byte)
+            .max_by_key(|(i, _)| *i)
+            .map(|(i, _)| i);
+        if let Some(i) = innermost {
+            refs_by_def[i].push(reference.name);
+        }
+    }
+
+    let mut qualified_names: Vec<String> = Vec::with_capacity(definitions.len());
+    let mut nodes = Vec::with_capacity(definitions.len());
+    for (i, def) in definitions.iter().enumerate() {
+        let parent = parents[i];
+        let qualified_name = match parent {
+            Some(p) if definitions[p].kind != NodeKind::Import => {
+                format!("{}.{}", qualified_names[p], def.name)
+            }
+            _ => def.name.clone(),
+        };
+        qualified_names.push(qualified_name.clone());
+
+        let kind = match (def.kind, parent) {
+            (NodeKind::Function, Some(p)) if is_type_like(definitions[p].kind) => NodeKind::Method,
+            (kind, _) => kind,
+        };
+
+        let mut references = std::mem::take(&mut refs_by_def[i]);
+        references.sort();
+        references.dedup();
+
+        let mut code_node = CodeNode::new(&def.name, &qualified_name, kind, file_path)
+            .with_lines(def.start_line, def.end_line)
+            .with_bytes(def.start_byte as u32, def.end_byte as u32)
+            .with_column(def.name_column)
+            .with_visibility(Visibility::Public)
+            .with_references(references);
+        if let Some(signature) = signature(&source[def.start_byte..def.end_byte]) {
+            code_node = code_node.with_signature(signature);
+        }
+        nodes.push(code_node);
+    }
+
+    nodes
+}
+
+/// First line of a definition, without a trailing block opener.
+fn signature(text: &str) -> Option<String> {
+    let line = text.lines().next()?.trim();
+    let line = line.trim_end_matches(['{', ':']).trim_end();
+    (!line.is_empty()).then(|| line.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Python stands in for a plugin grammar; the tags query is what a
+    /// plugin author would ship.
+    const TAGS: &str = r#"
+(class_definition name: (identifier) @name) @definition.class
+(function_definition name: (identifier) @name) @definition.function
+(call function: [
+    (identifier) @name
+    (attribute attribute: (identifier) @name)
+  ]) @reference.call
+"#;
+
+    fn plugin() -> PluginLanguage {
+        PluginLanguage::from_parts(
+            "pyflow",
+            tree_sitter_python::language(),
+            &["pyflow".to_string()],
+            TAGS,
+        )
+        .unwrap()
+    }
+
+    fn parse(plugin: &PluginLanguage, source: &str) -> Vec<CodeNode> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&plugin.language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+        plugin.extract_nodes(&tree, source, "flows/billing.pyflow")
+    }
+
+    #[test]
+    fn test_tags_query_produces_code_nodes() {
+        let source = r#"class Invoice:
+    def total(self):
+        return self.sum_lines() + tax()
+
+def tax():
+    return rate()
+"#;
+        let nodes = parse(&plugin(), source);
+
+        let invoice = nodes.iter().find(|n| n.name == "Invoice").unwrap();
+        assert_eq!(invoice.kind, NodeKind::Class);
+        assert_eq!(invoice.line_start, 1);
+        assert_eq!(invoice.line_end, 3);
+        assert_eq!(invoice.signature.as_deref(), Some("class Invoice"));
+        // Calls belong to the method, not the enclosing class.
+        assert!(invoice.references.is_empty());
+
+        let total = nodes.iter().find(|n| n.name == "total").unwrap();
+        assert_eq!(total.kind, NodeKind::Method);
+        assert_eq!(total.qualified_name, "Invoice.total");
+        assert_eq!(
+            total.references,
+            vec!["sum_lines".to_string(), "tax".to_string()]
+        );
+
+        let tax = nodes.iter().find(|n| n.name == "tax").unwrap();
+        assert_eq!(tax.kind, NodeKind::Function);
+        assert_eq!(tax.qualified_name, "tax");
+        assert_eq!(tax.references, vec!["rate".to_string()]);
+        assert_eq!(tax.column, 4);
+    }
+
+    #[test]
+    fn test_registered_plugin_is_used_by_parse_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = Registry::default();
+        registry.register(Arc::new(plugin()));
+        REGISTRY.set(dir.path(), registry);
+        assert_eq!(plugin_extensions(dir.path()), vec!["pyflow".to_string()]);
+
+        let path = dir.path().join("rules.pyflow");
+        std::fs::write(&path, "def check():\n    return validate()\n").unwrap();
+        assert!(crate::languages::is_supported_file(&path));
+
+        let nodes = crate::parse_file(&path).unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].name, "check");
+        assert_eq!(nodes[0].references, vec!["validate".to_string()]);
+
+        // Another project doesn't get this project's plugins.
+        let other = tempfile::tempdir().unwrap();
+        let path = other.path().join("rules.pyflow");
+        std::fs::write(&path, "def check():\n    return validate()\n").unwrap();
+        assert!(!crate::languages::is_supported_file(&path));
+        assert!(plugin_extensions(other.path()).is_empty());
+    }
+
+    #[test]
+    fn test_plugins_need_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugin_dir = dir.path().join(".arbor").join("languages").join("flow");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(plugin_dir.join(MANIFEST_FILE), "extensions = [\"flow\"]\n").unwrap();
+        std::fs::write(plugin_dir.join("libtree-sitter-flow.so"), "not a library").unwrap();
+
+        // Not even an attempt to open the library without opt-in.
+        assert!(load_plugins_if(dir.path(), false).is_empty());
+        assert!(REGISTRY.for_root(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_broken_plugins_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugin_dir = dir.path().join(".arbor").join("languages").join("flow");
+        std::fs::create_dir_all(&plugin_dir).unwrap();
+        std::fs::write(plugin_dir.join(MANIFEST_FILE), "extensions = [\"flow\"]\n").unwrap();
+        std::fs::write(plugin_dir.join(DEFAULT_QUERY_FILE), TAGS).unwrap();
+
+        let err = PluginLanguage::load(&plugin_dir).err().unwrap();
+        assert!(err.to_string().contains("no grammar library found"));
+        assert!(load_plugins_if(dir.path(), true).is_empty());
+        assert!(!crate::languages::is_supported_file(
+            &dir.path().join("a.flow")
+        ));
+    }
+}
@@ -0,0 +1,533 @@
+//! Protocol Buffers parser implementation.
+//!
+//! Handles .proto files. Messages, enums, services and rpcs become nodes so
+//! the graph builder can link generated Go/TS/Java/Python code back to the
+//! contract it was generated from. Rpcs and messages record the message
+//! types they use as type references, so changing a message shows the rpcs
+//! built on it.
+//!
+//! Declarations are qualified by the file's package (`acme.users.v1.User`),
+//! as protoc does, so they don't collide with the generated types of the
+//! same name. Type names are resolved with proto scoping rules: the
+//! innermost enclosing message first, then outwards to the package.
+
+use crate::languages::{language_from_fn, LanguageParser};
+use crate::node::{CodeNode, NodeKind, Visibility};
+use std::collections::HashSet;
+use tree_sitter::{Language, Node, Tree};
+
+pub struct ProtobufParser;
+
+impl LanguageParser for ProtobufParser {
+    fn language(&self) -> Language {
+        language_from_fn(tree_sitter_proto::LANGUAGE)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["proto"]
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        let package = find_child_by_kind(&root, "package").and_then(|package| {
+            find_child_by_kind(&package, "full_ident").map(|ident| get_text(&ident, source))
+        });
+        extract_from_node(&root, source, file_path, &mut nodes, package.as_deref());
+        resolve_type_references(&mut nodes, package.as_deref());
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        match node.kind() {
+            "message" => {
+                if let Some(code_node) = extract_message(node, source, file_path, context) {
+                    let message_name = code_node.qualified_name.clone();
+                    nodes.push(code_node);
+                    if let Some(body) = find_child_by_kind(node, "message_body") {
+                        for i in 0..body.child_count() {
+                            if let Some(child) = body.child(i) {
+                                extract_from_node(
+                                    &child,
+                                    source,
+                                    file_path,
+                                    nodes,
+                                    Some(&message_name),
+                                );
+                            }
+                        }
+                    }
+                }
+                return;
+            }
+
+            "enum" => {
+                if let Some(code_node) = extract_named(
+                    node,
+                    "enum_name",
+                    NodeKind::Enum,
+                    source,
+                    file_path,
+                    context,
+                ) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "service" => {
+                if let Some(code_node) = extract_named(
+                    node,
+                    "service_name",
+                    NodeKind::Interface,
+                    source,
+                    file_path,
+                    context,
+                ) {
+                    let service_name = code_node.qualified_name.clone();
+                    nodes.push(code_node);
+                    for i in 0..node.child_count() {
+                        if let Some(child) = node.child(i) {
+                            if child.kind() == "rpc" {
+                                if let Some(rpc) =
+                                    extract_rpc(&child, source, file_path, &service_name)
+                                {
+                                    nodes.push(rpc);
+                                }
+                            }
+                        }
+                    }
+                }
+                return;
+            }
+
+            "field" | "map_field" => {
+                if let Some(code_node) = extract_field(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "package" => {
+                if let Some(ident) = find_child_by_kind(node, "full_ident") {
+                    let name = get_text(&ident, source);
+                    nodes.push(
+                        CodeNode::new(&name, &name, NodeKind::Module, file_path)
+                            .with_lines(
+                                node.start_position().row as u32 + 1,
+                                node.end_position().row as u32 + 1,
+                            )
+                            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                    );
+                }
+                return;
+            }
+
+            "import" => {
+                if let Some(path) = node.child_by_field_name("path") {
+                    let path = get_text(&path, source);
+                    let path = path.trim_matches(|c| c == '"' || c == '\'');
+                    nodes.push(
+                        CodeNode::new(path, path, NodeKind::Import, file_path)
+                            .with_lines(
+                                node.start_position().row as u32 + 1,
+                                node.end_position().row as u32 + 1,
+                            )
+                            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                    );
+                }
+                return;
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children (oneof bodies keep the message context)
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a message; nested messages are qualified by their parent.
+fn extract_message(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let code_node = extract_named(
+        node,
+        "message_name",
+        NodeKind::Struct,
+        source,
+        file_path,
+        context,
+    )?;
+    let references = find_child_by_kind(node, "message_body")
+        .map(|body| extract_type_references(&body, source))
+        .unwrap_or_default();
+    Some(code_node.with_type_references(references))
+}
+
+/// Extracts a declaration whose name lives in a `*_name` child node.
+fn extract_named(
+    node: &Node,
+    name_kind: &str,
+    kind: NodeKind,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let name_node = find_child_by_kind(node, name_kind)?;
+    let name = get_text(&name_node, source);
+    let qualified_name = match context {
+        Some(ctx) => format!("{}.{}", ctx, name),
+        None => name.clone(),
+    };
+
+    Some(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(Visibility::Public),
+    )
+}
+
+/// Extracts an rpc as a method of its service.
+///
+/// The request and response message types are recorded as type references.
+fn extract_rpc(node: &Node, source: &str, file_path: &str, service: &str) -> Option<CodeNode> {
+    let name_node = find_child_by_kind(node, "rpc_name")?;
+    let name = get_text(&name_node, source);
+    let qualified_name = format!("{}.{}", service, name);
+
+    let mut references = Vec::new();
+    let mut streams = Vec::new();
+    let mut pending_stream = false;
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        match child.kind() {
+            "stream" => pending_stream = true,
+            "message_or_enum_type" => {
+                references.push(get_text(&child, source));
+                streams.push(pending_stream);
+                pending_stream = false;
+            }
+            _ => {}
+        }
+    }
+
+    let signature = match (references.first(), references.get(1)) {
+        (Some(request), Some(response)) => format!(
+            "rpc {}({}{}) returns ({}{})",
+            name,
+            if streams[0] { "stream " } else { "" },
+            request,
+            if streams[1] { "stream " } else { "" },
+            response
+        ),
+        _ => format!("rpc {}", name),
+    };
+
+    references.sort();
+    references.dedup();
+
+    Some(
+        CodeNode::new(&name, &qualified_name, NodeKind::Method, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_signature(signature)
+            .with_visibility(Visibility::Public)
+            .with_type_references(references),
+    )
+}
+
+/// Extracts a message field.
+fn extract_field(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let message = context?;
+    let name_node = find_child_by_kind(node, "identifier")?;
+    let name = get_text(&name_node, source);
+    let signature = get_text(node, source)
+        .trim_end_matches(';')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(
+        CodeNode::new(
+            &name,
+            format!("{}.{}", message, name),
+            NodeKind::Field,
+            file_path,
+        )
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(Visibility::Public),
+    )
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Collects the message/enum types used by the fields of a message body.
+///
+/// Nested message bodies are skipped; they record their own references.
+fn extract_type_references(body: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = body.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "message_or_enum_type" {
+            refs.push(get_text(&node, source));
+        }
+
+        let descend = node.kind() != "message" || node.id() == body.id();
+        if descend && cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+/// Qualifies the type references of messages and rpcs.
+///
+/// A name is looked up in the scope it is used in and each enclosing
+/// scope in turn, as protoc does. A name this file doesn't declare is
+/// assumed to come from another file of the same package, unless it is
+/// already qualified; a leading `.` marks a fully qualified name.
+fn resolve_type_references(nodes: &mut [CodeNode], package: Option<&str>) {
+    let declared: HashSet<String> = nodes
+        .iter()
+        .filter(|n| matches!(n.kind, NodeKind::Struct | NodeKind::Enum))
+        .map(|n| n.qualified_name.clone())
+        .collect();
+
+    for node in nodes.iter_mut() {
+        let scope = match node.kind {
+            NodeKind::Struct => node.qualified_name.as_str(),
+            _ => package.unwrap_or_default(),
+        };
+        let resolved = node
+            .type_references
+            .iter()
+            .map(|reference| resolve_type(reference, scope, package, &declared))
+            .collect();
+        node.type_references = resolved;
+        node.type_references.sort();
+        node.type_references.dedup();
+    }
+}
+
+/// Resolves one type name used in `scope`.
+fn resolve_type(
+    reference: &str,
+    scope: &str,
+    package: Option<&str>,
+    declared: &HashSet<String>,
+) -> String {
+    if let Some(absolute) = reference.strip_prefix('.') {
+        return absolute.to_string();
+    }
+
+    let mut scope = scope;
+    while !scope.is_empty() {
+        let candidate = format!("{}.{}", scope, reference);
+        if declared.contains(&candidate) {
+            return candidate;
+        }
+        scope = scope.rsplit_once('.').map_or("", |(parent, _)| parent);
+    }
+
+    match package {
+        Some(package) if !declared.contains(reference) && !reference.contains('.') => {
+            format!("{}.{}", package, reference)
+        }
+        _ => reference.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = ProtobufParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "proto/users.proto")
+    }
+
+    #[test]
+    fn test_services_rpcs_and_messages() {
+        let source = r#"syntax = "proto3";
+package acme.users.v1;
+import "google/protobuf/timestamp.proto";
+
+service UserService {
+  rpc GetUser(GetUserRequest) returns (User);
+  rpc WatchUsers(WatchRequest) returns (stream User) {}
+}
+
+message User {
+  string id = 1;
+  Address address = 2;
+  message Address { string city = 1; }
+}
+
+enum Role { ROLE_UNSPECIFIED = 0; }
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "acme.users.v1" && n.kind == NodeKind::Module));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "google/protobuf/timestamp.proto" && n.kind == NodeKind::Import));
+
+        let service = nodes.iter().find(|n| n.name == "UserService").unwrap();
+        assert_eq!(service.kind, NodeKind::Interface);
+        assert_eq!(service.line_start, 5);
+        assert_eq!(service.line_end, 8);
+
+        let get_user = nodes.iter().find(|n| n.name == "GetUser").unwrap();
+        assert_eq!(get_user.kind, NodeKind::Method);
+        assert_eq!(get_user.qualified_name, "acme.users.v1.UserService.GetUser");
+        assert!(get_user.references.is_empty());
+        assert_eq!(
+            get_user.type_references,
+            vec![
+                "acme.users.v1.GetUserRequest".to_string(),
+                "acme.users.v1.User".to_string()
+            ]
+        );
+
+        let watch = nodes.iter().find(|n| n.name == "WatchUsers").unwrap();
+        assert_eq!(
+            watch.signature.as_deref(),
+            Some("rpc WatchUsers(WatchRequest) returns (stream User)")
+        );
+
+        let user = nodes
+            .iter()
+            .find(|n| n.qualified_name == "acme.users.v1.User")
+            .unwrap();
+        assert_eq!(user.kind, NodeKind::Struct);
+        assert_eq!(user.line_start, 10);
+        assert_eq!(user.line_end, 14);
+        assert_eq!(
+            user.type_references,
+            vec!["acme.users.v1.User.Address".to_string()]
+        );
+
+        assert!(nodes.iter().any(
+            |n| n.qualified_name == "acme.users.v1.User.Address" && n.kind == NodeKind::Struct
+        ));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "acme.users.v1.User.Address.city"
+                && n.kind == NodeKind::Field));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Role" && n.kind == NodeKind::Enum));
+    }
+
+    #[test]
+    fn test_type_references_follow_proto_scoping() {
+        let source = r#"syntax = "proto3";
+package orders.v1;
+
+message Order {
+  message Line { string sku = 1; }
+  repeated Line lines = 1;
+  Status status = 2;
+  .google.protobuf.Timestamp created = 3;
+  common.v1.Money total = 4;
+}
+
+message Line { int32 qty = 1; }
+enum Status { STATUS_UNSPECIFIED = 0; }
+"#;
+        let nodes = parse(source);
+        let order = nodes
+            .iter()
+            .find(|n| n.qualified_name == "orders.v1.Order")
+            .unwrap();
+        // The nested Line shadows the top-level one.
+        assert_eq!(
+            order.type_references,
+            vec![
+                "common.v1.Money".to_string(),
+                "google.protobuf.Timestamp".to_string(),
+                "orders.v1.Order.Line".to_string(),
+                "orders.v1.Status".to_string(),
+            ]
+        );
+    }
+}
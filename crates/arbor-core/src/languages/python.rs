@@ -4,11 +4,37 @@
 //! straightforward with clear function and class boundaries.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct PythonParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "while_statement",
+        "except_clause",
+        "except_group_clause",
+        "case_clause",
+        "conditional_expression",
+        "if_clause",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "while_statement",
+        "try_statement",
+        "with_statement",
+        "match_statement",
+    ],
+    parameter_lists: &["parameters"],
+    parameters: &[],
+};
+
 impl LanguageParser for PythonParser {
     fn language(&self) -> Language {
         tree_sitter_python::language()
@@ -18,6 +44,10 @@ impl LanguageParser for PythonParser {
         &["py", "pyi"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
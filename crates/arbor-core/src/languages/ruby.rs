@@ -0,0 +1,478 @@
+//! Ruby language parser implementation.
+//!
+//! Handles .rb files. Extracts modules, classes, instance and singleton
+//! methods, constants, and `require` / `require_relative` / `load` imports.
+//! Tracks bare `private` / `protected` / `public` sections in class bodies.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct RubyParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if",
+        "elsif",
+        "unless",
+        "if_modifier",
+        "unless_modifier",
+        "while",
+        "until",
+        "while_modifier",
+        "until_modifier",
+        "for",
+        "when",
+        "rescue",
+        "conditional",
+    ],
+    nesting: &["if", "unless", "while", "until", "for", "case", "begin"],
+    parameter_lists: &["method_parameters"],
+    parameters: &[],
+};
+
+impl LanguageParser for RubyParser {
+    fn language(&self) -> Language {
+        tree_sitter_ruby::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["rb"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes, None);
+        nodes
+    }
+}
+
+/// Method calls that load another file.
+const IMPORT_METHODS: &[&str] = &["require", "require_relative", "load"];
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        let kind = node.kind();
+
+        match kind {
+            "class" | "module" => {
+                if let Some(code_node) = extract_container(node, source, file_path) {
+                    let name = code_node.name.clone();
+                    nodes.push(code_node);
+                    if let Some(body) = node.child_by_field_name("body") {
+                        extract_body(&body, source, file_path, nodes, &name);
+                    }
+                    return;
+                }
+            }
+
+            "method" | "singleton_method" => {
+                if let Some(code_node) =
+                    extract_method(node, source, file_path, context, Visibility::Public)
+                {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "call" => {
+                if let Some(code_node) = extract_require(node, source, file_path) {
+                    nodes.push(code_node);
+                    return;
+                }
+            }
+
+            "assignment" if context.is_none() => {
+                if let Some(code_node) = extract_constant(node, source, file_path, None) {
+                    nodes.push(code_node);
+                }
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Walks a class/module body in order so visibility sections apply to the
+/// methods that follow them.
+fn extract_body(
+    body: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    container: &str,
+) {
+    let mut visibility = Visibility::Public;
+
+    for i in 0..body.child_count() {
+        let Some(child) = body.child(i) else {
+            continue;
+        };
+
+        match child.kind() {
+            "identifier" => match get_text(&child, source).as_str() {
+                "private" => visibility = Visibility::Private,
+                "protected" => visibility = Visibility::Protected,
+                "public" => visibility = Visibility::Public,
+                _ => {}
+            },
+            "method" => {
+                if let Some(code_node) =
+                    extract_method(&child, source, file_path, Some(container), visibility)
+                {
+                    nodes.push(code_node);
+                }
+            }
+            "singleton_method" => {
+                // `private` sections don't apply to `def self.x`
+                if let Some(code_node) = extract_method(
+                    &child,
+                    source,
+                    file_path,
+                    Some(container),
+                    Visibility::Public,
+                ) {
+                    nodes.push(code_node);
+                }
+            }
+            "assignment" => {
+                if let Some(code_node) =
+                    extract_constant(&child, source, file_path, Some(container))
+                {
+                    nodes.push(code_node);
+                }
+            }
+            _ => extract_from_node(&child, source, file_path, nodes, Some(container)),
+        }
+    }
+}
+
+/// Extracts a class or module definition.
+fn extract_container(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let full_name = get_text(&name_node, source);
+    // `class Billing::TaxService` → "TaxService"
+    let name = full_name
+        .rsplit("::")
+        .next()
+        .unwrap_or(&full_name)
+        .to_string();
+
+    let kind = if node.kind() == "module" {
+        NodeKind::Module
+    } else {
+        NodeKind::Class
+    };
+
+    Some(
+        CodeNode::new(&name, &name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(Visibility::Public),
+    )
+}
+
+/// Extracts an instance method (`def x`) or singleton method (`def self.x`).
+fn extract_method(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+    visibility: Visibility,
+) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+    let is_static = node.kind() == "singleton_method";
+
+    let qualified_name = match context {
+        Some(ctx) => format!("{}.{}", ctx, name),
+        None => name.clone(),
+    };
+    let kind = match context {
+        Some(_) if name == "initialize" => NodeKind::Constructor,
+        Some(_) => NodeKind::Method,
+        None => NodeKind::Function,
+    };
+
+    let params = node
+        .child_by_field_name("parameters")
+        .map(|p| get_text(&p, source))
+        .unwrap_or_default();
+    let receiver = if is_static { "self." } else { "" };
+    let signature = format!("def {}{}{}", receiver, name, params);
+    let references = extract_call_references(node, source);
+
+    let mut code_node = CodeNode::new(&name, &qualified_name, kind, file_path)
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(visibility)
+        .with_references(references);
+    if is_static {
+        code_node = code_node.as_static();
+    }
+    Some(code_node)
+}
+
+/// Extracts a constant assignment (`RATE = 0.2`).
+fn extract_constant(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let left = node.child_by_field_name("left")?;
+    if left.kind() != "constant" {
+        return None;
+    }
+    let name = get_text(&left, source);
+    let qualified_name = match context {
+        Some(ctx) => format!("{}.{}", ctx, name),
+        None => name.clone(),
+    };
+
+    Some(
+        CodeNode::new(&name, &qualified_name, NodeKind::Constant, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(left.start_position().column as u32)
+            .with_visibility(Visibility::Public),
+    )
+}
+
+/// Extracts `require 'x'`, `require_relative 'x'`, and `load 'x'` as imports.
+///
+/// Ruby requires don't bind names, so no references are recorded.
+fn extract_require(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    if node.child_by_field_name("receiver").is_some() {
+        return None;
+    }
+    let method = node.child_by_field_name("method")?;
+    if !IMPORT_METHODS.contains(&get_text(&method, source).as_str()) {
+        return None;
+    }
+
+    let args = node.child_by_field_name("arguments")?;
+    let string = find_child_by_kind(&args, "string")?;
+    let content = find_child_by_kind(&string, "string_content")?;
+    let path = get_text(&content, source);
+
+    Some(
+        CodeNode::new(&path, &path, NodeKind::Import, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+    )
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Extracts method call references.
+///
+/// `helper(x)` and `self.helper` are recorded as `helper`, `TaxRules.apply`
+/// as `TaxRules.apply`, and `TaxRules.new` as `TaxRules` so constructions
+/// link to the class. Calls on local receivers (`rules.apply`) are skipped.
+fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "call" {
+            if let Some(method_node) = node.child_by_field_name("method") {
+                let method = get_text(&method_node, source);
+                match node.child_by_field_name("receiver") {
+                    None => {
+                        if !IMPORT_METHODS.contains(&method.as_str()) {
+                            refs.push(method);
+                        }
+                    }
+                    Some(receiver) => match receiver.kind() {
+                        "self" => refs.push(method),
+                        "constant" | "scope_resolution" => {
+                            let receiver_text = get_text(&receiver, source);
+                            let class = receiver_text
+                                .rsplit("::")
+                                .next()
+                                .unwrap_or(&receiver_text)
+                                .to_string();
+                            if method == "new" {
+                                refs.push(class);
+                            } else {
+                                refs.push(format!("{}.{}", class, method));
+                            }
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = RubyParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "billing.rb")
+    }
+
+    #[test]
+    fn test_class_methods_and_visibility() {
+        let source = r#"
+require 'json'
+require_relative "billing/tax"
+
+module Billing
+  class TaxService < Base
+    RATE = 0.2
+
+    def initialize(rules)
+      @rules = rules
+    end
+
+    def calculate_tax(amount)
+      TaxRules.apply(amount) + helper(amount)
+    end
+
+    def self.create
+      new(TaxRules.new)
+    end
+
+    private
+
+    def helper(x)
+      x
+    end
+  end
+end
+"#;
+        let nodes = parse(source);
+
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(imports, vec!["json", "billing/tax"]);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Billing" && n.kind == NodeKind::Module));
+
+        let class = nodes.iter().find(|n| n.name == "TaxService").unwrap();
+        assert_eq!(class.kind, NodeKind::Class);
+        assert_eq!(class.line_start, 6);
+        assert_eq!(class.line_end, 26);
+
+        let method = nodes.iter().find(|n| n.name == "calculate_tax").unwrap();
+        assert_eq!(method.qualified_name, "TaxService.calculate_tax");
+        assert_eq!(method.line_start, 13);
+        assert_eq!(method.line_end, 15);
+        assert_eq!(method.visibility, Visibility::Public);
+        assert!(method.references.contains(&"helper".to_string()));
+        assert!(method.references.contains(&"TaxRules.apply".to_string()));
+
+        let create = nodes.iter().find(|n| n.name == "create").unwrap();
+        assert!(create.is_static);
+        assert!(create.references.contains(&"TaxRules".to_string()));
+
+        let helper = nodes.iter().find(|n| n.name == "helper").unwrap();
+        assert_eq!(helper.visibility, Visibility::Private);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "initialize" && n.kind == NodeKind::Constructor));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "TaxService.RATE" && n.kind == NodeKind::Constant));
+    }
+
+    #[test]
+    fn test_top_level_method_is_function() {
+        let nodes = parse("def top_level\n  puts 'hi'\nend\n");
+        let func = nodes.iter().find(|n| n.name == "top_level").unwrap();
+        assert_eq!(func.kind, NodeKind::Function);
+        assert_eq!(func.line_end, 3);
+        assert_eq!(func.references, vec!["puts".to_string()]);
+    }
+}
@@ -4,11 +4,31 @@
 //! and impl blocks.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct RustParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "match_arm",
+    ],
+    nesting: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "loop_expression",
+        "match_expression",
+    ],
+    parameter_lists: &["parameters"],
+    parameters: &[],
+};
+
 impl LanguageParser for RustParser {
     fn language(&self) -> Language {
         tree_sitter_rust::language()
@@ -18,6 +38,10 @@ impl LanguageParser for RustParser {
         &["rs"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
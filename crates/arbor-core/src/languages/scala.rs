@@ -0,0 +1,531 @@
+//! Scala language parser implementation.
+//!
+//! Handles .scala and .sc files. Extracts classes, case classes, traits,
+//! objects, enums, methods, vals/vars, packages, and imports.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct ScalaParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "do_while_expression",
+        "case_clause",
+        "catch_clause",
+    ],
+    nesting: &[
+        "if_expression",
+        "for_expression",
+        "while_expression",
+        "do_while_expression",
+        "match_expression",
+        "try_expression",
+    ],
+    parameter_lists: &["parameters"],
+    parameters: &["parameter"],
+};
+
+impl LanguageParser for ScalaParser {
+    fn language(&self) -> Language {
+        tree_sitter_scala::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scala", "sc"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes, None);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        match node.kind() {
+            // Classes, traits, objects, and Scala 3 enums
+            "class_definition" | "trait_definition" | "object_definition" | "enum_definition" => {
+                if let Some(code_node) = extract_type(node, source, file_path) {
+                    let type_name = code_node.name.clone();
+                    nodes.push(code_node);
+                    if let Some(body) = node.child_by_field_name("body") {
+                        for i in 0..body.child_count() {
+                            if let Some(child) = body.child(i) {
+                                extract_from_node(
+                                    &child,
+                                    source,
+                                    file_path,
+                                    nodes,
+                                    Some(&type_name),
+                                );
+                            }
+                        }
+                    }
+                    return;
+                }
+            }
+
+            // Methods with a body and abstract declarations in traits
+            "function_definition" | "function_declaration" => {
+                if let Some(code_node) = extract_function(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                // Local defs are not interesting on their own; skip the body.
+                return;
+            }
+
+            // vals and vars
+            "val_definition" | "var_definition" => {
+                if let Some(code_node) = extract_value(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            // package com.acme.billing
+            "package_clause" => {
+                if let Some(name_node) = node.child_by_field_name("name") {
+                    let name = get_text(&name_node, source);
+                    nodes.push(
+                        CodeNode::new(&name, &name, NodeKind::Module, file_path)
+                            .with_lines(
+                                node.start_position().row as u32 + 1,
+                                node.end_position().row as u32 + 1,
+                            )
+                            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+                    );
+                }
+                // Packages with a body (`package x { ... }`) still contain declarations.
+            }
+
+            "import_declaration" => {
+                nodes.extend(extract_imports(node, source, file_path));
+                return;
+            }
+
+            _ => {}
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a class, case class, trait, object, or enum.
+fn extract_type(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let kind = match node.kind() {
+        "trait_definition" => NodeKind::Interface,
+        "enum_definition" => NodeKind::Enum,
+        _ => NodeKind::Class,
+    };
+
+    let references = node
+        .child_by_field_name("class_parameters")
+        .map(|params| extract_call_references(&params, source))
+        .unwrap_or_default();
+
+    Some(
+        CodeNode::new(&name, &name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(detect_visibility(node, source))
+            .with_references(references),
+    )
+}
+
+/// Extracts a `def` as a top-level function or a method.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Method, format!("{}.{}", ctx, name)),
+        None => (NodeKind::Function, name.clone()),
+    };
+
+    let references = node
+        .child_by_field_name("body")
+        .map(|body| extract_call_references(&body, source))
+        .unwrap_or_default();
+
+    Some(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_signature(build_signature(node, source))
+            .with_visibility(detect_visibility(node, source))
+            .with_references(references),
+    )
+}
+
+/// Extracts a `val` (constant or field) or `var` (variable or field).
+fn extract_value(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let pattern = node.child_by_field_name("pattern")?;
+    if pattern.kind() != "identifier" {
+        // Destructuring patterns (`val (a, b) = ...`) bind several names; skip them.
+        return None;
+    }
+    let name = get_text(&pattern, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Field, format!("{}.{}", ctx, name)),
+        None if node.kind() == "val_definition" => (NodeKind::Constant, name.clone()),
+        None => (NodeKind::Variable, name.clone()),
+    };
+
+    let references = node
+        .child_by_field_name("value")
+        .map(|value| extract_call_references(&value, source))
+        .unwrap_or_default();
+
+    Some(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(pattern.start_position().column as u32)
+            .with_visibility(detect_visibility(node, source))
+            .with_references(references),
+    )
+}
+
+/// Extracts an import declaration, one Import node per imported selector.
+///
+/// Examples:
+///   `import scala.util.Try` → `scala.util.Try`, references: ["Try"]
+///   `import com.acme.{A, B => C}` → `com.acme.A` ["A"], `com.acme.B` ["C"]
+///   `import com.acme._` → `com.acme`, references: ["*"]
+fn extract_imports(node: &Node, source: &str, file_path: &str) -> Vec<CodeNode> {
+    let mut path = Vec::new();
+    let mut selectors: Vec<(String, String)> = Vec::new();
+
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        match child.kind() {
+            "identifier" => path.push(get_text(&child, source)),
+            "namespace_wildcard" => selectors.push((String::new(), "*".to_string())),
+            "namespace_selectors" => {
+                for j in 0..child.child_count() {
+                    let Some(selector) = child.child(j) else {
+                        continue;
+                    };
+                    match selector.kind() {
+                        "identifier" => {
+                            let name = get_text(&selector, source);
+                            selectors.push((name.clone(), name));
+                        }
+                        "arrow_renamed_identifier" | "as_renamed_identifier" => {
+                            let name = selector
+                                .child_by_field_name("name")
+                                .map(|n| get_text(&n, source));
+                            let alias = selector
+                                .child_by_field_name("alias")
+                                .map(|n| get_text(&n, source));
+                            if let (Some(name), Some(alias)) = (name, alias) {
+                                if alias != "_" {
+                                    selectors.push((name, alias));
+                                }
+                            }
+                        }
+                        "namespace_wildcard" => selectors.push((String::new(), "*".to_string())),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let prefix = path.join(".");
+    // `{given, _}` yields two wildcards for the same package.
+    selectors.dedup();
+    if selectors.is_empty() {
+        let bound = path.last().cloned().unwrap_or_default();
+        selectors.push((String::new(), bound));
+    }
+
+    selectors
+        .into_iter()
+        .map(|(name, bound)| {
+            let module = if name.is_empty() {
+                prefix.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+            CodeNode::new(&module, &module, NodeKind::Import, file_path)
+                .with_lines(
+                    node.start_position().row as u32 + 1,
+                    node.end_position().row as u32 + 1,
+                )
+                .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+                .with_references(vec![bound])
+        })
+        .collect()
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Scala members are public unless an access modifier says otherwise.
+fn detect_visibility(node: &Node, source: &str) -> Visibility {
+    let modifiers = find_child_by_kind(node, "modifiers")
+        .map(|m| get_text(&m, source))
+        .unwrap_or_default();
+    if modifiers.contains("private") {
+        Visibility::Private
+    } else if modifiers.contains("protected") {
+        Visibility::Protected
+    } else {
+        Visibility::Public
+    }
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let header = source[node.start_byte()..end].trim_end();
+    let header = header.strip_suffix('=').unwrap_or(header);
+    header.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts function call references.
+///
+/// `helper(x)` is recorded as `helper`, `this.helper(x)` as `helper`, and
+/// `TaxCalc.apply(x)` as `TaxCalc.apply` so calls on objects resolve to the
+/// right definition. `new Invoice(...)` is recorded as `Invoice`.
+fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        match node.kind() {
+            "call_expression" => {
+                if let Some(callee) = node.child_by_field_name("function") {
+                    match callee.kind() {
+                        "identifier" => refs.push(get_text(&callee, source)),
+                        "field_expression" => {
+                            if let Some(reference) = field_reference(&callee, source) {
+                                refs.push(reference);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            "instance_expression" => {
+                if let Some(type_node) = find_child_by_kind(&node, "type_identifier") {
+                    refs.push(get_text(&type_node, source));
+                }
+            }
+            _ => {}
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+fn field_reference(node: &Node, source: &str) -> Option<String> {
+    let receiver = node.child_by_field_name("value")?;
+    let field = node.child_by_field_name("field")?;
+    let method = get_text(&field, source);
+
+    match receiver.kind() {
+        "identifier" => {
+            let receiver = get_text(&receiver, source);
+            if receiver == "this" || receiver == "super" {
+                Some(method)
+            } else {
+                Some(format!("{}.{}", receiver, method))
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = ScalaParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "InvoiceService.scala")
+    }
+
+    #[test]
+    fn test_object_methods_and_calls() {
+        let source = r#"package com.acme.billing
+
+trait Billing {
+  def total(x: Int): Int
+}
+
+object InvoiceService extends Billing {
+  val Rate = 3
+  private def helper(a: Int): Int = a * Rate
+  def total(x: Int): Int = {
+    val t = TaxCalc.apply(x)
+    helper(t) + this.other(x)
+  }
+  protected def other(x: Int) = x
+}
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "com.acme.billing" && n.kind == NodeKind::Module));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Billing.total" && n.kind == NodeKind::Method));
+
+        let service = nodes.iter().find(|n| n.name == "InvoiceService").unwrap();
+        assert_eq!(service.kind, NodeKind::Class);
+        assert_eq!(service.line_start, 7);
+        assert_eq!(service.line_end, 15);
+
+        let total = nodes
+            .iter()
+            .find(|n| n.qualified_name == "InvoiceService.total")
+            .unwrap();
+        assert_eq!(total.line_start, 10);
+        assert_eq!(total.line_end, 13);
+        assert_eq!(total.signature.as_deref(), Some("def total(x: Int): Int"));
+        assert_eq!(
+            total.references,
+            vec![
+                "TaxCalc.apply".to_string(),
+                "helper".to_string(),
+                "other".to_string()
+            ]
+        );
+
+        let helper = nodes.iter().find(|n| n.name == "helper").unwrap();
+        assert_eq!(helper.visibility, Visibility::Private);
+
+        let rate = nodes.iter().find(|n| n.name == "Rate").unwrap();
+        assert_eq!(rate.kind, NodeKind::Field);
+    }
+
+    #[test]
+    fn test_imports_record_bound_names() {
+        let source = r#"
+import com.acme.tax.{TaxCalc, Rates => R}
+import scala.util.Try
+
+case class Invoice(id: String, amount: Int)
+"#;
+        let nodes = parse(source);
+
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| (n.name.as_str(), n.references[0].as_str()))
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                ("com.acme.tax.TaxCalc", "TaxCalc"),
+                ("com.acme.tax.Rates", "R"),
+                ("scala.util.Try", "Try"),
+            ]
+        );
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Invoice" && n.kind == NodeKind::Class));
+    }
+}
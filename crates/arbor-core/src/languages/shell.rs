@@ -0,0 +1,236 @@
+//! Shell language parser implementation.
+//!
+//! Handles .sh, .bash, and .zsh files using the Bash grammar. Extracts
+//! function definitions (both `name() {}` and `function name {}` forms),
+//! the commands each function runs, and `source` / `.` includes.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct ShellParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "elif_clause",
+        "for_statement",
+        "c_style_for_statement",
+        "while_statement",
+        "case_item",
+        "ternary_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "c_style_for_statement",
+        "while_statement",
+        "case_statement",
+    ],
+    parameter_lists: &[],
+    parameters: &[],
+};
+
+impl LanguageParser for ShellParser {
+    fn language(&self) -> Language {
+        tree_sitter_bash::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sh", "bash", "zsh"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes);
+        nodes
+    }
+}
+
+fn extract_from_node(node: &Node, source: &str, file_path: &str, nodes: &mut Vec<CodeNode>) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        match node.kind() {
+            "function_definition" => {
+                if let Some(code_node) = extract_function(node, source, file_path) {
+                    nodes.push(code_node);
+                }
+            }
+
+            "command" => {
+                if let Some(code_node) = extract_source(node, source, file_path) {
+                    nodes.push(code_node);
+                }
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children (nested functions and sourced files inside functions)
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a function definition.
+fn extract_function(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+    let references = extract_command_references(node, source);
+
+    Some(
+        CodeNode::new(&name, &name, NodeKind::Function, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_signature(format!("{}()", name))
+            .with_visibility(Visibility::Public)
+            .with_references(references),
+    )
+}
+
+/// Extracts `source path` and `. path` as imports.
+///
+/// Sourced files define functions into the caller's scope without binding
+/// names up front, so no references are recorded.
+fn extract_source(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let command = get_text(&name_node, source);
+    if command != "source" && command != "." {
+        return None;
+    }
+
+    let argument = node.child_by_field_name("argument")?;
+    let path = get_text(&argument, source);
+    let path = path.trim_matches(|c| c == '"' || c == '\'');
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(
+        CodeNode::new(path, path, NodeKind::Import, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+    )
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+/// Extracts the names of commands run inside a function body.
+///
+/// Every command is recorded; ones that aren't functions defined in the
+/// repository (`rm`, `echo`, ...) simply fail to resolve in the builder.
+fn extract_command_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "command" {
+            if let Some(name_node) = node.child_by_field_name("name") {
+                let name = get_text(&name_node, source);
+                if name != "source" && name != "." && !name.starts_with('$') {
+                    refs.push(name);
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = ShellParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "deploy.sh")
+    }
+
+    #[test]
+    fn test_functions_have_real_ranges_and_calls() {
+        let source = r#"#!/bin/bash
+deploy_staging() {
+  build_app --fast
+  notify "done"
+}
+
+function cleanup {
+  rm -rf tmp
+}
+"#;
+        let nodes = parse(source);
+
+        let deploy = nodes.iter().find(|n| n.name == "deploy_staging").unwrap();
+        assert_eq!(deploy.kind, NodeKind::Function);
+        assert_eq!(deploy.line_start, 2);
+        assert_eq!(deploy.line_end, 5);
+        assert_eq!(
+            deploy.references,
+            vec!["build_app".to_string(), "notify".to_string()]
+        );
+
+        let cleanup = nodes.iter().find(|n| n.name == "cleanup").unwrap();
+        assert_eq!(cleanup.line_start, 7);
+        assert_eq!(cleanup.line_end, 9);
+    }
+
+    #[test]
+    fn test_source_commands_become_imports() {
+        let source = "source ./lib/common.sh\n. \"$HOME/.env\"\n";
+        let nodes = parse(source);
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(imports, vec!["./lib/common.sh", "$HOME/.env"]);
+    }
+}
@@ -0,0 +1,504 @@
+//! Swift language parser implementation.
+//!
+//! Handles .swift files. Extracts classes, structs, enums, actors,
+//! protocols, functions, methods, initializers, and imports. Members of
+//! an `extension` are attributed to the extended type.
+
+use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use tree_sitter::{Language, Node, Tree};
+
+pub struct SwiftParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "guard_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_while_statement",
+        "switch_entry",
+        "catch_block",
+        "ternary_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "guard_statement",
+        "for_statement",
+        "while_statement",
+        "repeat_while_statement",
+        "switch_statement",
+        "do_statement",
+    ],
+    parameter_lists: &["function_declaration", "init_declaration"],
+    parameters: &["parameter"],
+};
+
+impl LanguageParser for SwiftParser {
+    fn language(&self) -> Language {
+        tree_sitter_swift::language()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["swift"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+        extract_from_node(&root, source, file_path, &mut nodes, None);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        let kind = node.kind();
+
+        match kind {
+            // class / struct / enum / actor / extension share one node kind
+            "class_declaration" | "protocol_declaration" => {
+                let type_name = match extract_type(node, source, file_path) {
+                    Some(code_node) => {
+                        let name = code_node.name.clone();
+                        nodes.push(code_node);
+                        Some(name)
+                    }
+                    // Extensions produce no node, but their members belong to the type.
+                    None => node
+                        .child_by_field_name("name")
+                        .map(|n| get_text(&n, source)),
+                };
+
+                if let (Some(type_name), Some(body)) = (type_name, node.child_by_field_name("body"))
+                {
+                    for i in 0..body.child_count() {
+                        if let Some(child) = body.child(i) {
+                            extract_from_node(&child, source, file_path, nodes, Some(&type_name));
+                        }
+                    }
+                    return;
+                }
+            }
+
+            // Functions, methods, and protocol requirements
+            "function_declaration" | "protocol_function_declaration" => {
+                if let Some(code_node) = extract_function(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            // Initializers
+            "init_declaration" => {
+                if let Some(code_node) = extract_init(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            // Stored and computed properties inside a type
+            "property_declaration" if context.is_some() => {
+                extract_property(node, source, file_path, nodes, context);
+                return;
+            }
+
+            // Imports
+            "import_declaration" => {
+                if let Some(code_node) = extract_import(node, source, file_path) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            _ => {}
+        }
+
+        // Recurse into children
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a type declaration. Returns None for extensions.
+fn extract_type(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let kind = if node.kind() == "protocol_declaration" {
+        NodeKind::Interface
+    } else {
+        match node
+            .child_by_field_name("declaration_kind")
+            .map(|n| n.kind())
+        {
+            Some("struct") => NodeKind::Struct,
+            Some("enum") => NodeKind::Enum,
+            Some("extension") => return None,
+            _ => NodeKind::Class,
+        }
+    };
+
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    Some(
+        CodeNode::new(&name, &name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(detect_visibility(node, source)),
+    )
+}
+
+/// Extracts a free function or a method inside a type.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Method, format!("{}.{}", ctx, name)),
+        None => (NodeKind::Function, name.clone()),
+    };
+
+    let modifiers = modifiers_text(node, source);
+    let is_static = context.is_some()
+        && (modifiers.contains("static") || modifiers.split_whitespace().any(|m| m == "class"));
+    let is_async = has_token(node, "async");
+    let signature = build_signature(node, source);
+    let references = extract_call_references(node, source);
+
+    let mut code_node = CodeNode::new(&name, &qualified_name, kind, file_path)
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(signature)
+        .with_visibility(detect_visibility(node, source))
+        .with_references(references);
+    if is_async {
+        code_node = code_node.as_async();
+    }
+    if is_static {
+        code_node = code_node.as_static();
+    }
+    Some(code_node)
+}
+
+/// Extracts an initializer (`init(...)`).
+fn extract_init(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let type_name = context?;
+    let qualified_name = format!("{}.init", type_name);
+
+    Some(
+        CodeNode::new("init", &qualified_name, NodeKind::Constructor, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(node.start_position().column as u32)
+            .with_signature(build_signature(node, source))
+            .with_visibility(detect_visibility(node, source))
+            .with_references(extract_call_references(node, source)),
+    )
+}
+
+/// Extracts the properties bound by a `var`/`let` declaration inside a type.
+fn extract_property(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+) {
+    let visibility = detect_visibility(node, source);
+    let mut cursor = node.walk();
+    for pattern in node.children_by_field_name("name", &mut cursor) {
+        let name_node = pattern
+            .child_by_field_name("bound_identifier")
+            .unwrap_or(pattern);
+        let name = get_text(&name_node, source);
+        let qualified_name = match context {
+            Some(ctx) => format!("{}.{}", ctx, name),
+            None => name.clone(),
+        };
+        nodes.push(
+            CodeNode::new(&name, &qualified_name, NodeKind::Field, file_path)
+                .with_lines(
+                    node.start_position().row as u32 + 1,
+                    node.end_position().row as u32 + 1,
+                )
+                .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+                .with_column(name_node.start_position().column as u32)
+                .with_visibility(visibility),
+        );
+    }
+}
+
+/// Extracts an import declaration.
+///
+/// Swift imports whole modules, and files of the same module see each other
+/// without imports, so no bound names are recorded — doing so would make the
+/// graph builder reject legitimate same-module references.
+fn extract_import(node: &Node, source: &str, file_path: &str) -> Option<CodeNode> {
+    let ident = find_child_by_kind(node, "identifier")?;
+    let module = get_text(&ident, source);
+
+    Some(
+        CodeNode::new(&module, &module, NodeKind::Import, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+    )
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the node has a direct anonymous child token of this kind.
+fn has_token(node: &Node, token: &str) -> bool {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if !child.is_named() && child.kind() == token {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn modifiers_text(node: &Node, source: &str) -> String {
+    find_child_by_kind(node, "modifiers")
+        .map(|m| get_text(&m, source))
+        .unwrap_or_default()
+}
+
+/// Swift declarations are `internal` unless a modifier says otherwise.
+fn detect_visibility(node: &Node, source: &str) -> Visibility {
+    let modifiers = modifiers_text(node, source);
+    if modifiers.contains("public") || modifiers.contains("open") {
+        Visibility::Public
+    } else if modifiers.contains("private") {
+        Visibility::Private
+    } else {
+        Visibility::Internal
+    }
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let header = &source[node.start_byte()..end];
+    header.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts function call references.
+///
+/// `helper(x)` and `self.helper(x)` are recorded as `helper`, and
+/// `TaxRules.apply(x)` as `TaxRules.apply`. Instance calls (`rules.apply`)
+/// are captured the same way and simply fail to resolve in the builder.
+fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "call_expression" {
+            if let Some(callee) = node.child(0).map(rightmost_operand) {
+                match callee.kind() {
+                    "simple_identifier" => refs.push(get_text(&callee, source)),
+                    "navigation_expression" => {
+                        if let Some(reference) = navigation_reference(&callee, source) {
+                            refs.push(reference);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+/// The grammar binds `a + b.c(x)` as `(a + b).c(x)`, so the callee (or its
+/// receiver) can be a binary expression whose right operand is the real one.
+fn rightmost_operand(node: Node) -> Node {
+    let mut current = node;
+    while let Some(rhs) = current.child_by_field_name("rhs") {
+        current = rhs;
+    }
+    current
+}
+
+fn navigation_reference(node: &Node, source: &str) -> Option<String> {
+    let target = rightmost_operand(node.child_by_field_name("target")?);
+    let suffix = node.child_by_field_name("suffix")?;
+    let method = suffix.child_by_field_name("suffix")?;
+    let method = get_text(&method, source);
+
+    match target.kind() {
+        "self_expression" | "super_expression" => Some(method),
+        "simple_identifier" => Some(format!("{}.{}", get_text(&target, source), method)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = SwiftParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "TaxService.swift")
+    }
+
+    #[test]
+    fn test_class_members_are_contained() {
+        let source = r#"
+import Foundation
+
+public class TaxService {
+    init(rules: Rules) { self.rules = rules }
+
+    public func calculateTax(amount: Double) async -> Double {
+        return Rules.apply(amount) + self.helper(amount)
+    }
+
+    static func make() -> TaxService { TaxService(rules: Rules()) }
+}
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Foundation" && n.kind == NodeKind::Import));
+
+        let class = nodes.iter().find(|n| n.name == "TaxService").unwrap();
+        assert_eq!(class.kind, NodeKind::Class);
+        assert_eq!(class.visibility, Visibility::Public);
+        assert_eq!(class.line_start, 4);
+        assert_eq!(class.line_end, 12);
+
+        let method = nodes.iter().find(|n| n.name == "calculateTax").unwrap();
+        assert_eq!(method.qualified_name, "TaxService.calculateTax");
+        assert_eq!(method.line_start, 7);
+        assert_eq!(method.line_end, 9);
+        assert!(method.is_async);
+        assert!(method.references.contains(&"helper".to_string()));
+        assert!(method.references.contains(&"Rules.apply".to_string()));
+
+        let make = nodes.iter().find(|n| n.name == "make").unwrap();
+        assert!(make.is_static);
+        assert!(make.references.contains(&"TaxService".to_string()));
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "TaxService.init" && n.kind == NodeKind::Constructor));
+    }
+
+    #[test]
+    fn test_structs_protocols_and_extensions() {
+        let source = r#"
+struct Point { var x: Int }
+enum Mode { case a, b }
+protocol Api { func go() }
+extension Point { func norm() -> Int { x } }
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Point" && n.kind == NodeKind::Struct));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Mode" && n.kind == NodeKind::Enum));
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Api" && n.kind == NodeKind::Interface));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Api.go" && n.kind == NodeKind::Method));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Point.norm" && n.kind == NodeKind::Method));
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Point.x" && n.kind == NodeKind::Field));
+        assert_eq!(nodes.iter().filter(|n| n.name == "Point").count(), 1);
+    }
+}
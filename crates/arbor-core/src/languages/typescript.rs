@@ -4,11 +4,37 @@
 //! grammar is comprehensive enough to handle most JS patterns too.
 
 use crate::languages::LanguageParser;
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind, Visibility};
 use tree_sitter::{Language, Node, Tree};
 
 pub struct TypeScriptParser;
 
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "switch_case",
+        "catch_clause",
+        "ternary_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "for_statement",
+        "for_in_statement",
+        "while_statement",
+        "do_statement",
+        "switch_statement",
+        "try_statement",
+    ],
+    parameter_lists: &["formal_parameters"],
+    parameters: &[],
+};
+
 impl LanguageParser for TypeScriptParser {
     fn language(&self) -> Language {
         tree_sitter_typescript::language_typescript()
@@ -18,6 +44,10 @@ impl LanguageParser for TypeScriptParser {
         &["ts", "tsx", "js", "jsx", "mts", "cts", "mjs", "cjs"]
     }
 
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
     fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
         let mut nodes = Vec::new();
         let root = tree.root_node();
@@ -52,7 +82,7 @@ fn extract_from_node(
                 }
             }
 
-            "class_declaration" | "class" => {
+            "class_declaration" | "abstract_class_declaration" | "class" => {
                 if let Some(code_node) = extract_class(node, source, file_path) {
                     let class_name = code_node.name.clone();
                     nodes.push(code_node);
@@ -97,20 +127,6 @@ fn extract_from_node(
                 }
             }
 
-            "export_statement" => {
-                for i in 0..node.child_count() {
-                    if let Some(child) = node.child(i) {
-                        let child_kind = child.kind();
-                        if matches!(
-                            child_kind,
-                            "function_declaration" | "class_declaration" | "lexical_declaration"
-                        ) {
-                            extract_from_node(&child, source, file_path, nodes, parent_name);
-                        }
-                    }
-                }
-            }
-
             _ => {}
         }
 
@@ -457,7 +473,7 @@ fn build_arrow_signature(node: &Node, source: &str, name: &str) -> String {
 ///   - Other dotted  `arr.push()`    → DROPPED        (method on unknown object type;
 ///     can't resolve without type inference,
 ///     and would cause false name collisions)
-fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
+pub(crate) fn extract_call_references(root: &Node, source: &str) -> Vec<String> {
     let mut refs = Vec::new();
     let mut cursor = root.walk();
 
@@ -0,0 +1,593 @@
+//! Zig language parser implementation.
+//!
+//! Handles .zig files. In Zig, types and imports are ordinary `const`
+//! bindings (`const Point = struct { ... }`, `const std = @import("std")`),
+//! so most declarations are recognised by the value a binding is given.
+
+use crate::languages::{language_from_fn, LanguageParser};
+use crate::metrics::MetricsRules;
+use crate::node::{CodeNode, NodeKind, Visibility};
+use std::collections::HashSet;
+use tree_sitter::{Language, Node, Tree};
+
+pub struct ZigParser;
+
+/// Node kinds counted by code metrics.
+const METRICS: MetricsRules = MetricsRules {
+    decisions: &[
+        "if_statement",
+        "if_expression",
+        "for_statement",
+        "for_expression",
+        "while_statement",
+        "while_expression",
+        "switch_case",
+        "catch_expression",
+    ],
+    nesting: &[
+        "if_statement",
+        "if_expression",
+        "for_statement",
+        "for_expression",
+        "while_statement",
+        "while_expression",
+        "switch_expression",
+    ],
+    parameter_lists: &["parameters"],
+    parameters: &["parameter"],
+};
+
+impl LanguageParser for ZigParser {
+    fn language(&self) -> Language {
+        language_from_fn(tree_sitter_zig::LANGUAGE)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["zig"]
+    }
+
+    fn metrics_rules(&self) -> &'static MetricsRules {
+        &METRICS
+    }
+
+    fn extract_nodes(&self, tree: &Tree, source: &str, file_path: &str) -> Vec<CodeNode> {
+        let mut nodes = Vec::new();
+        let root = tree.root_node();
+
+        // Names bound to `@import(...)`. Calls through them (`util.abs()`)
+        // are recorded by bare name, since a file's declarations are not
+        // qualified by the name the importer gives it.
+        let modules = collect_import_aliases(&root, source);
+
+        extract_from_node(&root, source, file_path, &mut nodes, None, &modules);
+        nodes
+    }
+}
+
+fn extract_from_node(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+    modules: &HashSet<String>,
+) {
+    stacker::maybe_grow(64 * 1024, 4 * 1024 * 1024, || {
+        match node.kind() {
+            "variable_declaration" => {
+                extract_variable(node, source, file_path, nodes, context, modules);
+                return;
+            }
+
+            "function_declaration" => {
+                if let Some(code_node) = extract_function(node, source, file_path, context, modules)
+                {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "container_field" => {
+                if let Some(code_node) = extract_field(node, source, file_path, context) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            "test_declaration" => {
+                if let Some(code_node) = extract_test(node, source, file_path, modules) {
+                    nodes.push(code_node);
+                }
+                return;
+            }
+
+            _ => {}
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                extract_from_node(&child, source, file_path, nodes, context, modules);
+            }
+        }
+    }); // stacker::maybe_grow
+}
+
+/// Extracts a `const`/`var` binding: a container type, an import, or a value.
+fn extract_variable(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    nodes: &mut Vec<CodeNode>,
+    context: Option<&str>,
+    modules: &HashSet<String>,
+) {
+    let Some(name_node) = find_child_by_kind(node, "identifier") else {
+        return;
+    };
+    let name = get_text(&name_node, source);
+    let value = find_value(node);
+
+    if let Some(value) = value {
+        if let Some(module) = import_path(&value, source) {
+            nodes.push(
+                CodeNode::new(&module, &module, NodeKind::Import, file_path)
+                    .with_lines(
+                        node.start_position().row as u32 + 1,
+                        node.end_position().row as u32 + 1,
+                    )
+                    .with_bytes(node.start_byte() as u32, node.end_byte() as u32),
+            );
+            return;
+        }
+
+        let type_kind = match value.kind() {
+            "struct_declaration" | "union_declaration" | "opaque_declaration" => {
+                Some(NodeKind::Struct)
+            }
+            "enum_declaration" => Some(NodeKind::Enum),
+            _ => None,
+        };
+
+        if let Some(kind) = type_kind {
+            let qualified_name = qualify(context, &name);
+            nodes.push(
+                CodeNode::new(&name, &qualified_name, kind, file_path)
+                    .with_lines(
+                        node.start_position().row as u32 + 1,
+                        node.end_position().row as u32 + 1,
+                    )
+                    .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+                    .with_column(name_node.start_position().column as u32)
+                    .with_visibility(detect_visibility(node)),
+            );
+
+            // Enum members are values, not fields worth indexing.
+            if kind == NodeKind::Struct {
+                for i in 0..value.child_count() {
+                    if let Some(child) = value.child(i) {
+                        extract_from_node(
+                            &child,
+                            source,
+                            file_path,
+                            nodes,
+                            Some(&qualified_name),
+                            modules,
+                        );
+                    }
+                }
+            } else {
+                for i in 0..value.child_count() {
+                    if let Some(child) = value.child(i) {
+                        if child.kind() == "function_declaration" {
+                            extract_from_node(
+                                &child,
+                                source,
+                                file_path,
+                                nodes,
+                                Some(&qualified_name),
+                                modules,
+                            );
+                        }
+                    }
+                }
+            }
+            return;
+        }
+    }
+
+    let is_const = has_token(node, "const");
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Constant, format!("{}.{}", ctx, name)),
+        None if is_const => (NodeKind::Constant, name.clone()),
+        None => (NodeKind::Variable, name.clone()),
+    };
+    let references = value
+        .map(|value| extract_call_references(&value, source, modules))
+        .unwrap_or_default();
+
+    nodes.push(
+        CodeNode::new(&name, &qualified_name, kind, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(name_node.start_position().column as u32)
+            .with_visibility(detect_visibility(node))
+            .with_references(references),
+    );
+}
+
+/// Extracts a function, or a method when declared inside a container.
+fn extract_function(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+    modules: &HashSet<String>,
+) -> Option<CodeNode> {
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    let (kind, qualified_name) = match context {
+        Some(ctx) => (NodeKind::Method, format!("{}.{}", ctx, name)),
+        None => (NodeKind::Function, name.clone()),
+    };
+
+    // Methods that don't take `self` are namespaced functions (`Point.init`).
+    let is_static = context.is_some() && !takes_self(node, source);
+
+    let references = node
+        .child_by_field_name("body")
+        .map(|body| extract_call_references(&body, source, modules))
+        .unwrap_or_default();
+
+    let mut code_node = CodeNode::new(&name, &qualified_name, kind, file_path)
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_signature(build_signature(node, source))
+        .with_visibility(detect_visibility(node))
+        .with_references(references);
+    if is_static {
+        code_node = code_node.as_static();
+    }
+    Some(code_node)
+}
+
+/// Extracts a struct/union field.
+fn extract_field(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    context: Option<&str>,
+) -> Option<CodeNode> {
+    let ctx = context?;
+    let name_node = node.child_by_field_name("name")?;
+    let name = get_text(&name_node, source);
+
+    Some(
+        CodeNode::new(
+            &name,
+            format!("{}.{}", ctx, name),
+            NodeKind::Field,
+            file_path,
+        )
+        .with_lines(
+            node.start_position().row as u32 + 1,
+            node.end_position().row as u32 + 1,
+        )
+        .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+        .with_column(name_node.start_position().column as u32)
+        .with_visibility(Visibility::Public),
+    )
+}
+
+/// Extracts a `test "name" { ... }` block so the functions it exercises
+/// have a test caller.
+fn extract_test(
+    node: &Node,
+    source: &str,
+    file_path: &str,
+    modules: &HashSet<String>,
+) -> Option<CodeNode> {
+    let name = find_child_by_kind(node, "string")
+        .and_then(|s| find_child_by_kind(&s, "string_content").map(|c| get_text(&c, source)))
+        .or_else(|| find_child_by_kind(node, "identifier").map(|id| get_text(&id, source)))?;
+    let qualified_name = format!("test \"{}\"", name);
+    let references = find_child_by_kind(node, "block")
+        .map(|block| extract_call_references(&block, source, modules))
+        .unwrap_or_default();
+
+    Some(
+        CodeNode::new(&name, &qualified_name, NodeKind::Function, file_path)
+            .with_lines(
+                node.start_position().row as u32 + 1,
+                node.end_position().row as u32 + 1,
+            )
+            .with_bytes(node.start_byte() as u32, node.end_byte() as u32)
+            .with_column(node.start_position().column as u32)
+            .with_signature(qualified_name)
+            .with_visibility(Visibility::Private)
+            .with_references(references),
+    )
+}
+
+/// Collects the names of top-level `const x = @import("...")` bindings.
+fn collect_import_aliases(root: &Node, source: &str) -> HashSet<String> {
+    let mut modules = HashSet::new();
+    for i in 0..root.child_count() {
+        let Some(child) = root.child(i) else { continue };
+        if child.kind() != "variable_declaration" {
+            continue;
+        }
+        let is_import = find_value(&child)
+            .and_then(|value| import_path(&value, source))
+            .is_some();
+        if is_import {
+            if let Some(name) = find_child_by_kind(&child, "identifier") {
+                modules.insert(get_text(&name, source));
+            }
+        }
+    }
+    modules
+}
+
+/// Returns the path of an `@import("path")` expression.
+fn import_path(value: &Node, source: &str) -> Option<String> {
+    if value.kind() != "builtin_function" {
+        return None;
+    }
+    let builtin = find_child_by_kind(value, "builtin_identifier")?;
+    if get_text(&builtin, source) != "@import" {
+        return None;
+    }
+    let args = find_child_by_kind(value, "arguments")?;
+    let string = find_child_by_kind(&args, "string")?;
+    let content = find_child_by_kind(&string, "string_content")?;
+    Some(get_text(&content, source))
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Gets text content of a node.
+fn get_text(node: &Node, source: &str) -> String {
+    source[node.byte_range()].to_string()
+}
+
+fn find_child_by_kind<'a>(node: &'a Node, kind: &str) -> Option<Node<'a>> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == kind {
+                return Some(child);
+            }
+        }
+    }
+    None
+}
+
+/// Returns true if the node has a direct anonymous child token of this kind.
+fn has_token(node: &Node, token: &str) -> bool {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if !child.is_named() && child.kind() == token {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Returns the expression after `=` in a variable declaration.
+fn find_value<'a>(node: &'a Node) -> Option<Node<'a>> {
+    let mut seen_eq = false;
+    for i in 0..node.child_count() {
+        let child = node.child(i)?;
+        if seen_eq && child.is_named() {
+            return Some(child);
+        }
+        if child.kind() == "=" {
+            seen_eq = true;
+        }
+    }
+    None
+}
+
+fn qualify(context: Option<&str>, name: &str) -> String {
+    match context {
+        Some(ctx) => format!("{}.{}", ctx, name),
+        None => name.to_string(),
+    }
+}
+
+/// Zig declarations are file-private unless marked `pub`.
+fn detect_visibility(node: &Node) -> Visibility {
+    if has_token(node, "pub") {
+        Visibility::Public
+    } else {
+        Visibility::Private
+    }
+}
+
+fn takes_self(node: &Node, source: &str) -> bool {
+    find_child_by_kind(node, "parameters")
+        .and_then(|params| params.named_child(0))
+        .and_then(|param| param.child_by_field_name("name"))
+        .map(|name| get_text(&name, source) == "self")
+        .unwrap_or(false)
+}
+
+/// Builds a signature from the declaration header (everything before the body).
+fn build_signature(node: &Node, source: &str) -> String {
+    let end = node
+        .child_by_field_name("body")
+        .map(|body| body.start_byte())
+        .unwrap_or_else(|| node.end_byte());
+    let header = &source[node.start_byte()..end];
+    header.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Extracts function call references.
+///
+/// `helper(x)` is recorded as `helper`, `self.norm()` as `norm`, calls
+/// through an imported file (`util.abs()`) as `abs`, and calls on a type
+/// (`Point.init()`) as `Point.init`. Calls on other values are dropped.
+fn extract_call_references(root: &Node, source: &str, modules: &HashSet<String>) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut cursor = root.walk();
+
+    'outer: loop {
+        let node = cursor.node();
+
+        if node.kind() == "call_expression" {
+            if let Some(callee) = node.child_by_field_name("function") {
+                match callee.kind() {
+                    "identifier" => refs.push(get_text(&callee, source)),
+                    "field_expression" => {
+                        let object = callee.child_by_field_name("object");
+                        let member = callee.child_by_field_name("member");
+                        if let (Some(object), Some(member)) = (object, member) {
+                            if object.kind() == "identifier" {
+                                let object = get_text(&object, source);
+                                let member = get_text(&member, source);
+                                if object == "self" || modules.contains(&object) {
+                                    refs.push(member);
+                                } else if object.starts_with(|c: char| c.is_ascii_uppercase()) {
+                                    refs.push(format!("{}.{}", object, member));
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.depth() == 0 {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    refs.sort();
+    refs.dedup();
+    refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Vec<CodeNode> {
+        let parser = ZigParser;
+        let mut ts_parser = tree_sitter::Parser::new();
+        ts_parser.set_language(&parser.language()).unwrap();
+        let tree = ts_parser.parse(source, None).unwrap();
+        parser.extract_nodes(&tree, source, "src/point.zig")
+    }
+
+    #[test]
+    fn test_struct_methods_and_calls() {
+        let source = r#"const std = @import("std");
+const util = @import("util.zig");
+
+pub const Point = struct {
+    x: i32,
+
+    pub fn init(x: i32) Point {
+        return Point{ .x = x };
+    }
+
+    fn norm(self: Point) i32 {
+        return helper(self.x) + util.abs(self.x) + self.scale();
+    }
+};
+
+fn helper(v: i32) i32 {
+    return v;
+}
+
+test "norm of origin" {
+    const p = Point.init(0);
+    try std.testing.expect(p.norm() == 0);
+}
+"#;
+        let nodes = parse(source);
+
+        let imports: Vec<_> = nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Import)
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(imports, vec!["std", "util.zig"]);
+
+        let point = nodes.iter().find(|n| n.name == "Point").unwrap();
+        assert_eq!(point.kind, NodeKind::Struct);
+        assert_eq!(point.visibility, Visibility::Public);
+        assert_eq!(point.line_start, 4);
+        assert_eq!(point.line_end, 14);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.qualified_name == "Point.x" && n.kind == NodeKind::Field));
+
+        let init = nodes.iter().find(|n| n.name == "init").unwrap();
+        assert_eq!(init.qualified_name, "Point.init");
+        assert!(init.is_static);
+        assert_eq!(init.signature.as_deref(), Some("pub fn init(x: i32) Point"));
+
+        let norm = nodes.iter().find(|n| n.name == "norm").unwrap();
+        assert_eq!(norm.kind, NodeKind::Method);
+        assert_eq!(norm.visibility, Visibility::Private);
+        assert!(!norm.is_static);
+        assert_eq!(
+            norm.references,
+            vec!["abs".to_string(), "helper".to_string(), "scale".to_string()]
+        );
+
+        let test = nodes
+            .iter()
+            .find(|n| n.qualified_name == "test \"norm of origin\"")
+            .unwrap();
+        assert_eq!(test.references, vec!["Point.init".to_string()]);
+    }
+
+    #[test]
+    fn test_enums_and_top_level_values() {
+        let source = r#"pub const Color = enum { red, green };
+var counter: u32 = 0;
+const limit = compute(4);
+"#;
+        let nodes = parse(source);
+
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Color" && n.kind == NodeKind::Enum));
+        assert!(!nodes.iter().any(|n| n.name == "red"));
+
+        let counter = nodes.iter().find(|n| n.name == "counter").unwrap();
+        assert_eq!(counter.kind, NodeKind::Variable);
+
+        let limit = nodes.iter().find(|n| n.name == "limit").unwrap();
+        assert_eq!(limit.kind, NodeKind::Constant);
+        assert_eq!(limit.references, vec!["compute".to_string()]);
+    }
+}
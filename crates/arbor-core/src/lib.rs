@@ -16,15 +16,30 @@
 //! }
 //! ```
 
+pub mod annotations;
+pub mod diagnostics;
 pub mod error;
 pub mod fallback_parser;
+pub mod hierarchy;
+pub mod injection;
 pub mod languages;
+pub mod metrics;
 pub mod node;
 pub mod parser;
 pub mod parser_v2;
+mod project;
+pub mod routes;
+pub mod tokens;
+pub mod topics;
+pub mod wiring;
 
+pub use diagnostics::{ErrorRange, ParseDiagnostics, ParseHealth};
 pub use error::{ParseError, Result};
 pub use languages::LanguageParser;
-pub use node::{CodeNode, NodeKind, Visibility};
-pub use parser::{detect_language, parse_file, parse_source};
+pub use metrics::{attach_metrics, MetricsRules};
+pub use node::{
+    content_hash, stamp_content_hashes, CodeNode, Declaration, NodeKind, Route, Visibility, Wiring,
+};
+pub use parser::{detect_language, parse_file, parse_file_with_diagnostics, parse_source};
 pub use parser_v2::{ArborParser, ParseResult, RelationType, SymbolRelation};
+pub use tokens::{normalized_tokens, NormalizedToken};
@@ -0,0 +1,401 @@
+//! Code metrics for functions and methods.
+//!
+//! Computed from the Tree-sitter tree right after extraction, using node
+//! kinds each language declares in its [`MetricsRules`]:
+//!
+//! - **cyclomatic**: 1, plus one per branch point (`if`, loop, `case`
+//!   arm, `catch`, ternary) and per short-circuit `&&`/`||`/`and`/`or`.
+//! - **nesting**: deepest stack of nested control structures; an
+//!   `else if` continues its chain instead of nesting.
+//! - **params**: declared parameters, not counting `self`/`cls` receivers.
+//! - **loc**: lines holding at least one token other than a comment.
+//!
+//! Nested functions that were extracted as nodes of their own are left out
+//! of the enclosing function's numbers. Closures and lambdas are not, as
+//! their logic runs as part of the function that defines them.
+
+use crate::node::{CodeNode, NodeKind};
+use std::collections::{BTreeSet, HashSet};
+use tree_sitter::{Node, Tree};
+
+/// Cyclomatic complexity.
+pub const CYCLOMATIC: &str = "cyclomatic";
+/// Maximum nesting depth of control structures.
+pub const NESTING: &str = "nesting";
+/// Parameter count.
+pub const PARAMS: &str = "params";
+/// Logical lines of code.
+pub const LOC: &str = "loc";
+
+/// Short-circuit operator tokens.
+const LOGICAL_OPERATORS: &[&str] = &["&&", "||", "and", "or", "??", "orelse"];
+
+/// Receivers that are not counted as parameters.
+const RECEIVERS: &[&str] = &["self", "&self", "&mut self", "mut self", "cls"];
+
+/// The Tree-sitter node kinds a language uses for what metrics count.
+#[derive(Debug, Clone, Copy)]
+pub struct MetricsRules {
+    /// Branch points, one cyclomatic path each. A `case` that starts with
+    /// `default` (or Kotlin's `else`) is not counted.
+    pub decisions: &'static [&'static str],
+    /// Control structures that open a nesting level.
+    pub nesting: &'static [&'static str],
+    /// Nodes holding a function's parameters. The function node itself
+    /// can be listed for grammars without a parameter list node.
+    pub parameter_lists: &'static [&'static str],
+    /// Children of a parameter list that are parameters. Empty means
+    /// every named child other than a comment.
+    pub parameters: &'static [&'static str],
+}
+
+impl MetricsRules {
+    /// Kinds shared by most C-family and scripting grammars. Used by
+    /// languages without rules of their own, such as runtime plugins.
+    pub const GENERIC: MetricsRules = MetricsRules {
+        decisions: &[
+            "if_statement",
+            "if_expression",
+            "elif_clause",
+            "else_if_clause",
+            "for_statement",
+            "for_expression",
+            "for_in_statement",
+            "while_statement",
+            "while_expression",
+            "do_statement",
+            "case_clause",
+            "case_statement",
+            "switch_case",
+            "catch_clause",
+            "except_clause",
+            "conditional_expression",
+            "ternary_expression",
+        ],
+        nesting: &[
+            "if_statement",
+            "if_expression",
+            "for_statement",
+            "for_expression",
+            "for_in_statement",
+            "while_statement",
+            "while_expression",
+            "do_statement",
+            "switch_statement",
+            "match_expression",
+            "try_statement",
+        ],
+        parameter_lists: &["parameters", "formal_parameters", "parameter_list"],
+        parameters: &[],
+    };
+}
+
+/// Fills in [`CodeNode::metrics`] for the functions, methods and
+/// constructors in `nodes`, using the tree they were extracted from.
+pub fn attach_metrics(tree: &Tree, source: &str, rules: &MetricsRules, nodes: &mut [CodeNode]) {
+    let callable = |node: &CodeNode| {
+        matches!(
+            node.kind,
+            NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+        ) && node.byte_start < node.byte_end
+    };
+    let ranges: HashSet<(usize, usize)> = nodes
+        .iter()
+        .filter(|n| callable(n))
+        .map(|n| (n.byte_start as usize, n.byte_end as usize))
+        .collect();
+
+    let root = tree.root_node();
+    for node in nodes.iter_mut().filter(|n| callable(n)) {
+        let range = (node.byte_start as usize, node.byte_end as usize);
+        let Some(function) = root.descendant_for_byte_range(range.0, range.1) else {
+            continue;
+        };
+        let metrics = FunctionMetrics::compute(function, source, rules, &ranges);
+        node.metrics
+            .insert(CYCLOMATIC.to_string(), metrics.cyclomatic);
+        node.metrics.insert(NESTING.to_string(), metrics.nesting);
+        node.metrics.insert(PARAMS.to_string(), metrics.params);
+        node.metrics.insert(LOC.to_string(), metrics.loc);
+    }
+}
+
+#[derive(Debug, Default)]
+struct FunctionMetrics {
+    cyclomatic: u32,
+    nesting: u32,
+    params: u32,
+    loc: u32,
+}
+
+impl FunctionMetrics {
+    fn compute(
+        function: Node,
+        source: &str,
+        rules: &MetricsRules,
+        nested_functions: &HashSet<(usize, usize)>,
+    ) -> Self {
+        let mut metrics = FunctionMetrics {
+            cyclomatic: 1,
+            params: count_parameters(function, source, rules),
+            ..Default::default()
+        };
+        let mut lines = BTreeSet::new();
+
+        // (node, nesting depth of its parent)
+        let mut stack = vec![(function, 0u32)];
+        // Dart keeps a function's body next to its signature, not inside it.
+        if let Some(body) = function
+            .next_named_sibling()
+            .filter(|sibling| sibling.kind() == "function_body")
+        {
+            stack.push((body, 0));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            if node.id() != function.id()
+                && nested_functions.contains(&(node.start_byte(), node.end_byte()))
+            {
+                continue;
+            }
+            let kind = node.kind();
+            if kind.contains("comment") {
+                continue;
+            }
+            if node.child_count() == 0 {
+                lines.insert(node.start_position().row);
+                let text = &source[node.byte_range()];
+                if LOGICAL_OPERATORS.contains(&text) && is_logical_parent(node) {
+                    metrics.cyclomatic += 1;
+                }
+                continue;
+            }
+
+            if node.is_named() && rules.decisions.contains(&kind) && !is_default_case(node) {
+                metrics.cyclomatic += 1;
+            }
+            let depth = if node.is_named() && rules.nesting.contains(&kind) && !continues_else(node)
+            {
+                metrics.nesting = metrics.nesting.max(depth + 1);
+                depth + 1
+            } else {
+                depth
+            };
+
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                stack.push((child, depth));
+            }
+        }
+
+        metrics.loc = lines.len() as u32;
+        metrics
+    }
+}
+
+/// Short-circuit tokens only count inside a binary, boolean or infix
+/// expression: Rust's `||` also opens a closure with no parameters.
+fn is_logical_parent(token: Node) -> bool {
+    token.parent().is_some_and(|parent| {
+        let kind = parent.kind();
+        [
+            "binary",
+            "boolean",
+            "logical",
+            "conjunction",
+            "disjunction",
+            "infix",
+        ]
+        .iter()
+        .any(|k| kind.contains(k))
+    })
+}
+
+/// `default:` labels and Kotlin's `else ->` entry add no path.
+fn is_default_case(node: Node) -> bool {
+    node.child(0)
+        .is_some_and(|first| matches!(first.kind(), "default" | "default_keyword" | "else"))
+}
+
+/// An `if` inside an `else` clause, or right after an `else` token,
+/// continues the chain.
+fn continues_else(node: Node) -> bool {
+    node.parent()
+        .is_some_and(|parent| parent.kind().contains("else"))
+        || node
+            .prev_sibling()
+            .is_some_and(|prev| prev.kind() == "else")
+}
+
+fn count_parameters(function: Node, source: &str, rules: &MetricsRules) -> u32 {
+    let Some(list) = parameter_list(function, rules) else {
+        return 0;
+    };
+    let mut cursor = list.walk();
+    list.named_children(&mut cursor)
+        .filter(|child| {
+            if rules.parameters.is_empty() {
+                !child.kind().contains("comment")
+            } else {
+                rules.parameters.contains(&child.kind())
+            }
+        })
+        .filter(|child| !RECEIVERS.contains(&source[child.byte_range()].trim()))
+        .count() as u32
+}
+
+/// The function's own parameter list: its `parameters` field if it has
+/// one, else the first listed node found before any body.
+fn parameter_list<'a>(function: Node<'a>, rules: &MetricsRules) -> Option<Node<'a>> {
+    if rules.parameter_lists.contains(&function.kind()) {
+        return Some(function);
+    }
+    if let Some(list) = function.child_by_field_name("parameters") {
+        return Some(list);
+    }
+    let mut queue = std::collections::VecDeque::from([function]);
+    while let Some(node) = queue.pop_front() {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if rules.parameter_lists.contains(&child.kind()) {
+                return Some(child);
+            }
+            // Parameter lists sit in the signature, never in the body.
+            let kind = child.kind();
+            if !(kind.contains("body") || kind.contains("block")) {
+                queue.push_back(child);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::get_parser;
+    use crate::parser::parse_source;
+
+    fn metrics(source: &str, extension: &str, name: &str) -> [u32; 4] {
+        let parser = get_parser(extension).unwrap();
+        let nodes = parse_source(source, &format!("test.{}", extension), parser.as_ref()).unwrap();
+        let node = nodes.iter().find(|n| n.name == name).unwrap();
+        [CYCLOMATIC, NESTING, PARAMS, LOC].map(|key| node.metric(key).unwrap())
+    }
+
+    #[test]
+    fn test_rust_metrics() {
+        let source = r#"
+fn classify(&self, n: i32, strict: bool) -> &str {
+    // comment lines do not count
+    if n < 0 && strict {
+        return "negative";
+    } else if n == 0 {
+        return "zero";
+    }
+    for i in 0..n {
+        match i {
+            1 => {}
+            _ => {}
+        }
+    }
+    let f = || n > 0;
+    "positive"
+}
+"#;
+        // 1 + if + && + else-if + for + 2 arms; the closure's `||` is no branch
+        assert_eq!(metrics(source, "rs", "classify"), [7, 2, 2, 15]);
+
+        let nested = r#"
+fn walk(items: &[i32]) -> i32 {
+    let mut total = 0;
+    while let Some(x) = items.first() {
+        if *x > 0 {
+            if *x > 10 {
+                total += 1;
+            } else if *x > 5 {
+                total += 2;
+            }
+        }
+        break;
+    }
+    items.iter().filter(|x| **x > 0 || **x < -5).count() as i32 + total
+}
+"#;
+        // while > if > if; the else-if continues the inner chain. The `||`
+        // inside the closure runs as part of `walk`, so it counts.
+        assert_eq!(metrics(nested, "rs", "walk"), [6, 3, 1, 14]);
+    }
+
+    #[test]
+    fn test_python_metrics() {
+        let source = r#"
+def load(path, retries=3):
+    try:
+        with open(path) as f:
+            return f.read() if retries else None
+    except OSError:
+        pass
+    while retries > 0 and path:
+        retries -= 1
+
+    def inner(x):
+        if x:
+            return 1
+"#;
+        // 1 + ternary + except + while + and; `inner` is its own node
+        assert_eq!(metrics(source, "py", "load"), [5, 2, 2, 8]);
+        assert_eq!(metrics(source, "py", "inner"), [2, 1, 1, 3]);
+
+        let nested = r#"
+def grade(score, curve):
+    if score > 90:
+        return "A"
+    elif score > 80:
+        for bonus in curve:
+            if bonus:
+                score += bonus
+    elif score > 70 or curve:
+        return "C"
+    return "F"
+"#;
+        // 1 + if + 2 elifs + for + if + or; elifs do not nest, the loop
+        // under one does
+        assert_eq!(metrics(nested, "py", "grade"), [7, 3, 2, 10]);
+    }
+
+    #[test]
+    fn test_typescript_metrics() {
+        let source = r#"
+function route(req: Request, res: Response) {
+    switch (req.method) {
+        case "GET":
+            return res.ok ?? false;
+        case "POST":
+            return true;
+        default:
+            return false;
+    }
+}
+"#;
+        // 1 + 2 cases + ??
+        assert_eq!(metrics(source, "ts", "route"), [4, 1, 2, 10]);
+
+        let nested = r#"
+function load(id: string) {
+    try {
+        if (!id) {
+            return null;
+        } else if (id.length > 10) {
+            return cache?.get(id);
+        }
+        return items.map((x) => (x ? x : id));
+    } catch (e) {
+        return undefined;
+    }
+}
+"#;
+        // 1 + if + else-if + ternary in the arrow function + catch; `?.`
+        // is no branch and the else-if stays at the depth of its chain
+        assert_eq!(metrics(nested, "ts", "load"), [5, 2, 1, 12]);
+    }
+}
@@ -5,6 +5,7 @@
 //! and enough metadata to be useful for graph construction.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 
 /// The kind of code entity this node represents.
@@ -45,6 +46,11 @@ pub enum NodeKind {
     Field,
     /// A document section or heading (for Markdown knowledge graphs in Lattice).
     Section,
+    /// An HTTP endpoint declared through a web framework; see [`Route`].
+    Route,
+    /// A message-bus topic or event name that code publishes to or
+    /// subscribes to; the graph crate creates one per name.
+    Topic,
 }
 
 impl std::fmt::Display for NodeKind {
@@ -65,6 +71,8 @@ impl std::fmt::Display for NodeKind {
             Self::Constructor => "constructor",
             Self::Field => "field",
             Self::Section => "section",
+            Self::Route => "route",
+            Self::Topic => "topic",
         };
         write!(f, "{}", s)
     }
@@ -82,6 +90,57 @@ pub enum Visibility {
     Internal,
 }
 
+/// An HTTP endpoint declared by a route decorator, attribute or
+/// registration call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Route {
+    /// Upper-case HTTP method, or `ANY` when every method is accepted.
+    pub method: String,
+    /// Path pattern as written, joined with any controller-level prefix.
+    pub path: String,
+    /// Framework that declared it (`axum`, `flask`, `spring`, ...).
+    pub framework: String,
+}
+
+/// Runtime wiring seen in the source that is not a direct call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Wiring {
+    /// `function` is passed by name to `call`, which may invoke it later
+    /// (`register(handler)`, `.then(cb)`).
+    Callback { call: String, function: String },
+    /// The node gets a `type_name` from a dependency-injection container.
+    Injects {
+        type_name: String,
+        container: String,
+    },
+    /// The node registers `type_name` with a container, built by
+    /// `implementation` when that is another type.
+    Provides {
+        type_name: String,
+        implementation: Option<String>,
+        container: String,
+    },
+    /// The node publishes to `topic` through `call`
+    /// (`producer.send("orders.created")`, `emitter.emit("saved")`).
+    Publishes { topic: String, call: String },
+    /// The node receives messages from `topic`, registered through `call`
+    /// (`bus.subscribe("orders.created")`, `@KafkaListener`).
+    Subscribes { topic: String, call: String },
+}
+
+/// A relationship declared in a source comment
+/// (`// arbor: calls billing::charge`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Declaration {
+    /// `alias`, or the edge kind: `calls`, `references`, `uses_type`, ...
+    pub relation: String,
+    /// The symbol it names, as written.
+    pub target: String,
+    /// Line of the comment.
+    pub line: u32,
+}
+
 /// A code entity extracted from source.
 ///
 /// This is the core data type that flows through Arbor. It's designed
@@ -134,9 +193,42 @@ pub struct CodeNode {
     pub byte_start: u32,
     pub byte_end: u32,
 
+    /// Hash of the source bytes in `byte_start..byte_end`, used to detect
+    /// that a file changed since it was indexed. Zero when unknown.
+    #[serde(default)]
+    pub content_hash: u64,
+
     /// Entities this node references (call targets, type refs, etc).
     /// These are names, not IDs - resolution happens in the graph crate.
     pub references: Vec<String>,
+
+    /// Types this node uses without calling them, such as the request and
+    /// response messages of a protobuf rpc. Resolved like `references`,
+    /// but linked as type uses rather than calls.
+    #[serde(default)]
+    pub type_references: Vec<String>,
+
+    /// Code metrics by name (see [`crate::metrics`]), filled in for
+    /// functions, methods and constructors parsed with a grammar.
+    #[serde(default)]
+    pub metrics: BTreeMap<String, u32>,
+
+    /// Method, path and framework of a [`NodeKind::Route`] node.
+    #[serde(default)]
+    pub route: Option<Route>,
+
+    /// Names of the types this type extends or implements, as written.
+    /// On a method of a Rust `impl Trait for Type` block, the trait.
+    #[serde(default)]
+    pub supertypes: Vec<String>,
+
+    /// Callbacks it registers and dependencies it injects or provides.
+    #[serde(default)]
+    pub wiring: Vec<Wiring>,
+
+    /// Edges and aliases declared in `arbor:` comments.
+    #[serde(default)]
+    pub declared: Vec<Declaration>,
 }
 
 impl CodeNode {
@@ -184,7 +276,14 @@ impl CodeNode {
             docstring: None,
             byte_start: 0,
             byte_end: 0,
+            content_hash: 0,
             references: Vec::new(),
+            type_references: Vec::new(),
+            metrics: BTreeMap::new(),
+            route: None,
+            supertypes: Vec::new(),
+            wiring: Vec::new(),
+            declared: Vec::new(),
         }
     }
 
@@ -238,11 +337,54 @@ impl CodeNode {
         self
     }
 
+    /// True if `bytes` are the same source this node was extracted from.
+    ///
+    /// Nodes without a recorded hash accept any bytes.
+    pub fn matches_source(&self, bytes: &[u8]) -> bool {
+        self.content_hash == 0 || self.content_hash == content_hash(bytes)
+    }
+
+    /// A metric such as [`metrics::CYCLOMATIC`](crate::metrics::CYCLOMATIC).
+    pub fn metric(&self, name: &str) -> Option<u32> {
+        self.metrics.get(name).copied()
+    }
+
     /// Builder pattern: add references.
     pub fn with_references(mut self, refs: Vec<String>) -> Self {
         self.references = refs;
         self
     }
+
+    /// Builder pattern: add type references.
+    pub fn with_type_references(mut self, refs: Vec<String>) -> Self {
+        self.type_references = refs;
+        self
+    }
+}
+
+/// FNV-1a hash of a node's source bytes.
+///
+/// Stable across runs and platforms, unlike `DefaultHasher`, so hashes
+/// stored in the cache stay comparable.
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    // Zero means "no hash recorded".
+    hash.max(1)
+}
+
+/// Records the content hash of each node's byte range in `source`.
+pub fn stamp_content_hashes(nodes: &mut [CodeNode], source: &str) {
+    let bytes = source.as_bytes();
+    for node in nodes {
+        let (start, end) = (node.byte_start as usize, node.byte_end as usize);
+        if start < end && end <= bytes.len() {
+            node.content_hash = content_hash(&bytes[start..end]);
+        }
+    }
 }
 
 impl PartialEq for CodeNode {
@@ -346,6 +488,19 @@ mod tests {
         assert_eq!(set.len(), 1);
     }
 
+    #[test]
+    fn test_content_hash_detects_changed_source() {
+        let source = "fn a() {}\nfn b() {}\n";
+        let mut nodes = vec![CodeNode::new("a", "a", NodeKind::Function, "x.rs").with_bytes(0, 9)];
+        stamp_content_hashes(&mut nodes, source);
+
+        assert_ne!(nodes[0].content_hash, 0);
+        assert!(nodes[0].matches_source(b"fn a() {}"));
+        assert!(!nodes[0].matches_source(b"fn a() { panic!() }"));
+        // Unhashed nodes accept anything.
+        assert!(CodeNode::new("b", "b", NodeKind::Function, "x.rs").matches_source(b"?"));
+    }
+
     #[test]
     fn test_compute_id_deterministic() {
         // Same inputs must always produce the same ID
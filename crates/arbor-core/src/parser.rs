@@ -4,12 +4,21 @@
 //! source files into CodeNodes. Language detection is automatic based
 //! on file extension.
 
+use crate::annotations::attach_annotations;
+use crate::diagnostics::ParseDiagnostics;
 use crate::error::{ParseError, Result};
 use crate::fallback_parser;
-use crate::languages::{get_parser, LanguageParser};
-use crate::node::CodeNode;
+use crate::hierarchy;
+use crate::injection;
+use crate::languages::{get_parser_for, LanguageParser};
+use crate::metrics::{attach_metrics, MetricsRules};
+use crate::node::{stamp_content_hashes, CodeNode};
+use crate::routes;
+use crate::topics;
+use crate::wiring;
 use std::fs;
 use std::path::Path;
+use tree_sitter::Tree;
 
 /// Parses a source file and extracts all code nodes.
 ///
@@ -29,6 +38,15 @@ use std::path::Path;
 /// println!("Found {} nodes", nodes.len());
 /// ```
 pub fn parse_file(path: &Path) -> Result<Vec<CodeNode>> {
+    parse_file_with_diagnostics(path).map(|(nodes, _)| nodes)
+}
+
+/// Parses a source file and reports how well it parsed.
+///
+/// Same as [`parse_file`], plus the file's [`ParseDiagnostics`]: syntax
+/// error regions Tree-sitter recovered from, how much of the file is
+/// covered by extracted nodes, and whether the fallback parser was used.
+pub fn parse_file_with_diagnostics(path: &Path) -> Result<(Vec<CodeNode>, ParseDiagnostics)> {
     // Read the source file
     let source = fs::read_to_string(path).map_err(|e| ParseError::io(path, e))?;
 
@@ -39,7 +57,8 @@ pub fn parse_file(path: &Path) -> Result<Vec<CodeNode>> {
             .map(|n| n == "__init__.py")
             .unwrap_or(false)
         {
-            return Ok(vec![]); // Return empty nodes, not an error
+            // Return empty nodes, not an error
+            return Ok((vec![], ParseDiagnostics::default()));
         }
         return Err(ParseError::EmptyFile(path.to_path_buf()));
     }
@@ -49,25 +68,32 @@ pub fn parse_file(path: &Path) -> Result<Vec<CodeNode>> {
         .and_then(|e| e.to_str())
         .unwrap_or_default();
 
-    // Get the appropriate parser for this file type (tree-sitter path first)
-    let parser = detect_language(path);
+    // Use the file path as a string for node IDs
+    let file_path = path.to_string_lossy().to_string();
 
-    if parser.is_none() {
+    // Get the appropriate parser for this file type (tree-sitter path first)
+    let Some(parser) = detect_language(path) else {
+        // Vue/Svelte/HTML/Markdown: parse the code embedded in them
+        if injection::is_host_extension(extension) {
+            let (mut nodes, trees) =
+                injection::parse_host_source_with_trees(&source, &file_path, extension)?;
+            finish_nodes(&source, &mut nodes);
+            let trees: Vec<&tree_sitter::Tree> = trees.iter().collect();
+            let diagnostics = ParseDiagnostics::from_trees(&trees, &nodes, source.len());
+            return Ok((nodes, diagnostics));
+        }
         if fallback_parser::is_fallback_supported_extension(extension) {
-            let file_path = path.to_string_lossy().to_string();
-            return Ok(fallback_parser::parse_fallback_source(
-                &source, &file_path, extension,
-            ));
+            let mut nodes = fallback_parser::parse_fallback_source(&source, &file_path, extension);
+            finish_nodes(&source, &mut nodes);
+            let diagnostics = ParseDiagnostics::fallback(&nodes, source.len());
+            return Ok((nodes, diagnostics));
         }
         return Err(ParseError::UnsupportedLanguage(path.to_path_buf()));
-    }
+    };
 
-    let parser = parser.unwrap();
-
-    // Use the file path as a string for node IDs
-    let file_path = path.to_string_lossy().to_string();
-
-    parse_source(&source, &file_path, parser.as_ref())
+    let (nodes, tree) = parse_tree(&source, &file_path, parser.as_ref())?;
+    let diagnostics = ParseDiagnostics::from_trees(&[&tree], &nodes, source.len());
+    Ok((nodes, diagnostics))
 }
 
 /// Parses source code directly (useful for testing or in-memory content).
@@ -79,6 +105,15 @@ pub fn parse_source(
     file_path: &str,
     lang_parser: &dyn LanguageParser,
 ) -> Result<Vec<CodeNode>> {
+    parse_tree(source, file_path, lang_parser).map(|(nodes, _)| nodes)
+}
+
+/// Parses source and extracts nodes, keeping the tree for diagnostics.
+fn parse_tree(
+    source: &str,
+    file_path: &str,
+    lang_parser: &dyn LanguageParser,
+) -> Result<(Vec<CodeNode>, Tree)> {
     // Create and configure Tree-sitter parser
     let mut parser = tree_sitter::Parser::new();
     parser
@@ -91,22 +126,66 @@ pub fn parse_source(
         .ok_or_else(|| ParseError::ParserError("Tree-sitter returned no tree".into()))?;
 
     // Extract nodes using the language-specific extractor
-    let nodes = lang_parser.extract_nodes(&tree, source, file_path);
+    let mut nodes = lang_parser.extract_nodes(&tree, source, file_path);
+    let language = Path::new(file_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    attach_tree_facts(
+        &tree,
+        source,
+        file_path,
+        language,
+        lang_parser.metrics_rules(),
+        &mut nodes,
+    );
+    finish_nodes(source, &mut nodes);
 
-    Ok(nodes)
+    Ok((nodes, tree))
+}
+
+/// Adds what the syntax tree says beyond the extracted nodes themselves:
+/// SQL tables, routes, supertypes, wiring, topics and metrics.
+///
+/// Shared by every parse path (this module, [`crate::ArborParser`] and
+/// embedded regions) so they all produce the same nodes for the same file.
+/// `language` is the extension of the tree's grammar, which for an
+/// embedded region is not the host file's.
+pub(crate) fn attach_tree_facts(
+    tree: &Tree,
+    source: &str,
+    file_path: &str,
+    language: &str,
+    rules: &MetricsRules,
+    nodes: &mut Vec<CodeNode>,
+) {
+    let language = language.to_ascii_lowercase();
+    injection::attach_sql_references(tree, source, nodes);
+    routes::attach_routes(tree, source, file_path, &language, nodes);
+    hierarchy::attach_hierarchy(tree, source, file_path, &language, nodes);
+    wiring::attach_wiring(tree, source, &language, nodes);
+    topics::attach_topics(tree, source, file_path, nodes);
+    attach_metrics(tree, source, rules, nodes);
+}
+
+/// Records `arbor:` annotations and content hashes, once per file.
+pub(crate) fn finish_nodes(source: &str, nodes: &mut [CodeNode]) {
+    attach_annotations(source, nodes);
+    stamp_content_hashes(nodes, source);
 }
 
 /// Detects the programming language from a file path.
 ///
-/// Returns None if we don't support the file's extension.
+/// Returns None if neither a built-in grammar nor a plugin of the file's
+/// project supports its extension.
 pub fn detect_language(path: &Path) -> Option<Box<dyn LanguageParser>> {
-    let extension = path.extension()?.to_str()?;
-    get_parser(extension)
+    get_parser_for(path)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::languages::get_parser;
     use crate::node::NodeKind;
 
     #[test]
@@ -118,7 +197,7 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_fallback_language_source() {
+    fn test_parse_kotlin_file() {
         let source = r#"
             fun calculateTax(price: Double): Double = price * 0.18
             class TaxService
@@ -133,6 +212,18 @@ mod tests {
         assert!(nodes.iter().any(|n| n.name == "TaxService"));
     }
 
+    #[test]
+    fn test_parse_fallback_language_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("README.md");
+        std::fs::write(&path, "# Billing\n\n## Tax rules\n").unwrap();
+
+        let nodes = parse_file(&path).unwrap();
+        assert!(nodes
+            .iter()
+            .any(|n| n.name == "Billing" && n.kind == NodeKind::Section));
+    }
+
     #[test]
     fn test_parse_rust_source() {
         let source = r#"
@@ -167,6 +258,8 @@ mod tests {
             export class UserService {
                 validate() {}
             }
+
+            export const load = async () => db.query("SELECT * FROM users");
         "#;
 
         let parser = get_parser("ts").unwrap();
@@ -178,5 +271,14 @@ mod tests {
         assert!(nodes
             .iter()
             .any(|n| n.name == "UserService" && n.kind == NodeKind::Class));
+
+        // Exported declarations are extracted once.
+        for name in ["greet", "UserService", "validate", "load"] {
+            let copies: Vec<_> = nodes.iter().filter(|n| n.name == name).collect();
+            assert_eq!(copies.len(), 1, "{}", name);
+        }
+        let load = nodes.iter().find(|n| n.name == "load").unwrap();
+        assert!(load.is_exported);
+        assert_eq!(load.references, ["sql:users"]);
     }
 }
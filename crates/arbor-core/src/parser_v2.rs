@@ -7,10 +7,15 @@
 //! The parser is designed for incremental updates - calling it on the same file
 //! will update existing nodes rather than creating duplicates.
 
+use crate::diagnostics::ParseDiagnostics;
 use crate::error::{ParseError, Result};
 use crate::fallback_parser;
+use crate::injection;
+use crate::languages::{get_parser, get_parser_for, LanguageParser};
+use crate::metrics::MetricsRules;
 use crate::node::{CodeNode, NodeKind};
-use std::collections::HashMap;
+use crate::parser;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use tree_sitter::{Language, Parser, Query, QueryCursor, Tree};
@@ -54,6 +59,8 @@ pub struct ParseResult {
     pub relations: Vec<SymbolRelation>,
     /// File path that was parsed.
     pub file_path: String,
+    /// Parse errors, coverage and fallback use for the file.
+    pub diagnostics: ParseDiagnostics,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -129,7 +136,8 @@ impl ArborParser {
             queries.insert(ext.to_string(), Self::compile_cpp_queries()?);
         }
 
-        // Dart remains on legacy path (queries incompatible with tree-sitter-dart v0.0.4). Registry helper enables trivial future addition.
+        // Dart, Kotlin, Swift, Ruby, PHP, and shell have no query sets; they go
+        // through their `LanguageParser` in `parse_with_language_parser`.
         // Compile C# queries
         let csharp_queries = Self::compile_csharp_queries()?;
         queries.insert("cs".to_string(), csharp_queries);
@@ -167,15 +175,36 @@ impl ArborParser {
         let compiled = match self.queries.get(&ext) {
             Some(compiled) => compiled,
             None => {
+                if let Some(lang_parser) = get_parser_for(path) {
+                    return self.parse_with_language_parser(
+                        &source,
+                        &path.to_string_lossy(),
+                        &ext,
+                        lang_parser.as_ref(),
+                    );
+                }
+                if injection::is_host_extension(&ext) {
+                    let file_path = path.to_string_lossy();
+                    let (mut nodes, trees) =
+                        injection::parse_host_source_with_trees(&source, &file_path, &ext)?;
+                    parser::finish_nodes(&source, &mut nodes);
+                    let trees: Vec<&Tree> = trees.iter().collect();
+                    let diagnostics = ParseDiagnostics::from_trees(&trees, &nodes, source.len());
+                    return Ok(result_from_nodes(nodes, &file_path, diagnostics));
+                }
                 if fallback_parser::is_fallback_supported_extension(&ext) {
+                    let mut symbols = fallback_parser::parse_fallback_source(
+                        &source,
+                        &path.to_string_lossy(),
+                        &ext,
+                    );
+                    parser::finish_nodes(&source, &mut symbols);
+                    let diagnostics = ParseDiagnostics::fallback(&symbols, source.len());
                     return Ok(ParseResult {
-                        symbols: fallback_parser::parse_fallback_source(
-                            &source,
-                            &path.to_string_lossy(),
-                            &ext,
-                        ),
+                        symbols,
                         relations: Vec::new(),
                         file_path: path.to_string_lossy().to_string(),
+                        diagnostics,
                     });
                 }
                 return Err(ParseError::UnsupportedLanguage(path.to_path_buf()));
@@ -200,15 +229,26 @@ impl ArborParser {
             .unwrap_or("unknown");
 
         // Extract symbols
-        let symbols = self.extract_symbols(&tree, &source, &file_path, file_name, compiled);
+        let mut symbols = self.extract_symbols(&tree, &source, &file_path, file_name, compiled);
 
         // Extract relationships
-        let relations = self.extract_relations(&tree, &source, &file_path, &symbols, compiled);
+        let mut relations = self.extract_relations(&tree, &source, &file_path, &symbols, compiled);
+        attach_tree_relations(
+            &tree,
+            &source,
+            &file_path,
+            &ext,
+            &mut symbols,
+            &mut relations,
+        );
+        parser::finish_nodes(&source, &mut symbols);
+        let diagnostics = ParseDiagnostics::from_trees(&[&tree], &symbols, source.len());
 
         Ok(ParseResult {
             symbols,
             relations,
             file_path,
+            diagnostics,
         })
     }
 
@@ -228,11 +268,32 @@ impl ArborParser {
         let compiled = self.queries.get(&language);
 
         if compiled.is_none() {
+            if let Some(lang_parser) = get_parser(&language) {
+                return self.parse_with_language_parser(
+                    source,
+                    file_path,
+                    &language,
+                    lang_parser.as_ref(),
+                );
+            }
+            if injection::is_host_extension(&language) {
+                let (mut nodes, trees) =
+                    injection::parse_host_source_with_trees(source, file_path, &language)?;
+                parser::finish_nodes(source, &mut nodes);
+                let trees: Vec<&Tree> = trees.iter().collect();
+                let diagnostics = ParseDiagnostics::from_trees(&trees, &nodes, source.len());
+                return Ok(result_from_nodes(nodes, file_path, diagnostics));
+            }
             if fallback_parser::is_fallback_supported_extension(&language) {
+                let mut symbols =
+                    fallback_parser::parse_fallback_source(source, file_path, &language);
+                parser::finish_nodes(source, &mut symbols);
+                let diagnostics = ParseDiagnostics::fallback(&symbols, source.len());
                 return Ok(ParseResult {
-                    symbols: fallback_parser::parse_fallback_source(source, file_path, &language),
+                    symbols,
                     relations: Vec::new(),
                     file_path: file_path.to_string(),
+                    diagnostics,
                 });
             }
             return Err(ParseError::UnsupportedLanguage(file_path.into()));
@@ -254,16 +315,61 @@ impl ArborParser {
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
 
-        let symbols = self.extract_symbols(&tree, source, file_path, file_name, compiled);
-        let relations = self.extract_relations(&tree, source, file_path, &symbols, compiled);
+        let mut symbols = self.extract_symbols(&tree, source, file_path, file_name, compiled);
+        let mut relations = self.extract_relations(&tree, source, file_path, &symbols, compiled);
+        attach_tree_relations(
+            &tree,
+            source,
+            file_path,
+            &language,
+            &mut symbols,
+            &mut relations,
+        );
+        parser::finish_nodes(source, &mut symbols);
+        let diagnostics = ParseDiagnostics::from_trees(&[&tree], &symbols, source.len());
 
         Ok(ParseResult {
             symbols,
             relations,
             file_path: file_path.to_string(),
+            diagnostics,
         })
     }
 
+    /// Parses with a `LanguageParser` for languages that have no query set.
+    ///
+    /// Relations are derived from the extracted nodes: each reference becomes
+    /// a call and each Import node a file-level import.
+    fn parse_with_language_parser(
+        &mut self,
+        source: &str,
+        file_path: &str,
+        language: &str,
+        lang_parser: &dyn LanguageParser,
+    ) -> Result<ParseResult> {
+        self.parser
+            .set_language(&lang_parser.language())
+            .map_err(|e| ParseError::ParserError(format!("Failed to set language: {}", e)))?;
+
+        let tree = self
+            .parser
+            .parse(source, None)
+            .ok_or_else(|| ParseError::ParserError("Tree-sitter returned no tree".into()))?;
+
+        let mut nodes = lang_parser.extract_nodes(&tree, source, file_path);
+        parser::attach_tree_facts(
+            &tree,
+            source,
+            file_path,
+            language,
+            lang_parser.metrics_rules(),
+            &mut nodes,
+        );
+        parser::finish_nodes(source, &mut nodes);
+        let diagnostics = ParseDiagnostics::from_trees(&[&tree], &nodes, source.len());
+        Ok(result_from_nodes(nodes, file_path, diagnostics))
+    }
+
     // ─────────────────────────────────────────────────────────────────────────
     // Symbol Extraction
     // ─────────────────────────────────────────────────────────────────────────
@@ -699,6 +805,86 @@ impl ArborParser {
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Builds a ParseResult from `LanguageParser` nodes.
+///
+/// Each reference becomes a call and each Import node a file-level import.
+fn result_from_nodes(
+    nodes: Vec<CodeNode>,
+    file_path: &str,
+    diagnostics: ParseDiagnostics,
+) -> ParseResult {
+    let (imports, symbols): (Vec<CodeNode>, Vec<CodeNode>) = nodes
+        .into_iter()
+        .partition(|node| node.kind == NodeKind::Import);
+
+    let file_id = format!("{}:__file__", file_path);
+    let mut relations: Vec<SymbolRelation> = imports
+        .iter()
+        .map(|import| SymbolRelation {
+            from_id: file_id.clone(),
+            to_name: import.name.clone(),
+            kind: RelationType::Imports,
+            line: import.line_start,
+        })
+        .collect();
+
+    for symbol in &symbols {
+        for reference in &symbol.references {
+            relations.push(SymbolRelation {
+                from_id: symbol.id.clone(),
+                to_name: reference.clone(),
+                kind: RelationType::Calls,
+                line: symbol.line_start,
+            });
+        }
+    }
+
+    ParseResult {
+        symbols,
+        relations,
+        file_path: file_path.to_string(),
+        diagnostics,
+    }
+}
+
+/// Runs the shared post-extraction steps on query-extracted symbols.
+///
+/// The steps add nodes (routes, bodiless members) and references (SQL
+/// tables, route handlers, wiring); every reference they add becomes a
+/// call, the same way [`result_from_nodes`] treats references.
+fn attach_tree_relations(
+    tree: &Tree,
+    source: &str,
+    file_path: &str,
+    language: &str,
+    symbols: &mut Vec<CodeNode>,
+    relations: &mut Vec<SymbolRelation>,
+) {
+    let known: Vec<HashSet<String>> = symbols
+        .iter()
+        .map(|symbol| symbol.references.iter().cloned().collect())
+        .collect();
+    let rules = get_parser(language).map_or(&MetricsRules::GENERIC, |p| p.metrics_rules());
+    parser::attach_tree_facts(tree, source, file_path, language, rules, symbols);
+
+    for (index, symbol) in symbols.iter().enumerate() {
+        for reference in &symbol.references {
+            if known
+                .get(index)
+                .is_some_and(|known| known.contains(reference))
+            {
+                continue;
+            }
+            relations.push(SymbolRelation {
+                from_id: symbol.id.clone(),
+                to_name: reference.clone(),
+                kind: RelationType::Calls,
+                line: symbol.line_start,
+            });
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -848,22 +1034,30 @@ class UserService:
     }
 
     #[test]
-    fn test_parse_fallback_kotlin_symbols() {
+    fn test_parse_kotlin_symbols() {
         let mut parser = ArborParser::new().unwrap();
 
         let source = r#"
+            import com.acme.tax.applyTax
             class BillingService
-            fun computeInvoiceTotal(amount: Double): Double = amount
+            fun computeInvoiceTotal(amount: Double): Double = applyTax(amount)
         "#;
 
         let result = parser.parse_source(source, "billing.kt", "kt").unwrap();
 
         assert!(result.symbols.iter().any(|s| s.name == "BillingService"));
-        assert!(result
+        let total = result
             .symbols
             .iter()
-            .any(|s| s.name == "computeInvoiceTotal"));
-        assert!(result.relations.is_empty());
+            .find(|s| s.name == "computeInvoiceTotal")
+            .unwrap();
+        assert!(result.relations.iter().any(|r| r.from_id == total.id
+            && r.to_name == "applyTax"
+            && r.kind == RelationType::Calls));
+        assert!(result
+            .relations
+            .iter()
+            .any(|r| r.to_name == "com.acme.tax.applyTax" && r.kind == RelationType::Imports));
     }
 
     #[test]
@@ -1052,4 +1246,65 @@ def read_file(path):
             .iter()
             .any(|r| r.kind == RelationType::Imports));
     }
+
+    #[test]
+    fn test_query_path_matches_language_parser_facts() {
+        let source = r#"
+import express from "express";
+const app = express();
+
+// arbor: calls billing.charge
+export function createOrder(req, res) {
+    if (req.body && req.body.items) {
+        db.query("SELECT * FROM orders");
+    }
+    emitter.emit("orders.created", req.body);
+    res.send("ok");
+}
+
+app.post("/orders", createOrder);
+"#;
+        let facts = |nodes: &[CodeNode]| {
+            let mut facts: Vec<_> = nodes
+                .iter()
+                .filter(|n| n.route.is_some() || n.name == "createOrder")
+                .map(|n| {
+                    let mut references = n.references.clone();
+                    references.sort();
+                    references.dedup();
+                    (
+                        n.name.clone(),
+                        n.route.clone(),
+                        references,
+                        n.wiring.clone(),
+                        n.declared.clone(),
+                        n.metrics.clone(),
+                        n.content_hash,
+                    )
+                })
+                .collect();
+            facts.sort_by(|a, b| a.0.cmp(&b.0));
+            facts
+        };
+
+        let ts = get_parser("ts").unwrap();
+        let expected = crate::parser::parse_source(source, "src/orders.ts", ts.as_ref()).unwrap();
+        let mut parser = ArborParser::new().unwrap();
+        let result = parser.parse_source(source, "src/orders.ts", "ts").unwrap();
+
+        let expected = facts(&expected);
+        assert!(expected.iter().any(|f| f.1.is_some()), "{:?}", expected);
+        assert!(expected.iter().any(|f| !f.3.is_empty() && !f.4.is_empty()));
+        assert_eq!(facts(&result.symbols), expected);
+
+        // What the shared steps add becomes relations too.
+        let route = result.symbols.iter().find(|n| n.route.is_some()).unwrap();
+        assert!(result.relations.iter().any(|r| r.from_id == route.id
+            && r.to_name == "createOrder"
+            && r.kind == RelationType::Calls));
+        assert!(result
+            .relations
+            .iter()
+            .any(|r| r.to_name == "sql:orders" && r.kind == RelationType::Calls));
+    }
 }
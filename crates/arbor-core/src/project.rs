@@ -0,0 +1,95 @@
+//! Settings that belong to one project, looked up by file path.
+//!
+//! A long-lived process (the server, the GUI, an MCP session) can index
+//! several projects. Anything loaded from a project's `.arbor/` directory
+//! is registered under that project's root, and a file only sees the
+//! settings of the innermost project that contains it.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Values keyed by canonical project root.
+pub(crate) struct ByProject<T> {
+    roots: RwLock<Vec<(PathBuf, Arc<T>)>>,
+}
+
+impl<T> ByProject<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            roots: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Replaces the value registered for `root`.
+    pub(crate) fn set(&self, root: &Path, value: T) {
+        let root = canonical(root);
+        let mut roots = self.roots.write().unwrap();
+        roots.retain(|(existing, _)| *existing != root);
+        roots.push((root, Arc::new(value)));
+    }
+
+    /// Forgets the value registered for `root`.
+    pub(crate) fn remove(&self, root: &Path) {
+        let root = canonical(root);
+        self.roots
+            .write()
+            .unwrap()
+            .retain(|(existing, _)| *existing != root);
+    }
+
+    /// The value registered for exactly `root`.
+    pub(crate) fn for_root(&self, root: &Path) -> Option<Arc<T>> {
+        let root = canonical(root);
+        let roots = self.roots.read().unwrap();
+        roots
+            .iter()
+            .find(|(existing, _)| *existing == root)
+            .map(|(_, value)| Arc::clone(value))
+    }
+
+    /// The value of the innermost project containing `path`.
+    pub(crate) fn for_path(&self, path: &Path) -> Option<Arc<T>> {
+        let roots = self.roots.read().unwrap();
+        if roots.is_empty() {
+            return None;
+        }
+        let path = canonical(path);
+        roots
+            .iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map(|(_, value)| Arc::clone(value))
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize()
+        .or_else(|_| std::path::absolute(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_innermost_project_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let outer = dir.path().join("outer");
+        let inner = outer.join("vendor").join("inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        std::fs::write(inner.join("lib.rs"), "").unwrap();
+        std::fs::write(outer.join("main.rs"), "").unwrap();
+
+        let projects = ByProject::new();
+        projects.set(&outer, "outer");
+        projects.set(&inner, "inner");
+
+        assert_eq!(*projects.for_path(&outer.join("main.rs")).unwrap(), "outer");
+        assert_eq!(*projects.for_path(&inner.join("lib.rs")).unwrap(), "inner");
+        assert!(projects.for_path(&dir.path().join("other.rs")).is_none());
+
+        projects.remove(&inner);
+        assert_eq!(*projects.for_path(&inner.join("lib.rs")).unwrap(), "outer");
+    }
+}
@@ -0,0 +1,1131 @@
+//! HTTP route extraction for web frameworks.
+//!
+//! Handlers are rarely called from inside the repository; the framework
+//! calls them when a request arrives. Name heuristics (`handle_*`, files
+//! under `routes/`) only guess at that, so here we read the declarations
+//! the frameworks themselves use:
+//!
+//! | Language | Declarations |
+//! |----------|--------------|
+//! | Rust | actix/rocket `#[get("/p")]`, axum `.route("/p", get(h).post(h2))`, actix `.route("/p", web::get().to(h))` |
+//! | JS/TS | Express, Fastify, Koa `app.get("/p", ..., h)` |
+//! | Python | FastAPI `@app.get("/p")`, Flask `@app.route("/p", methods=[...])`, with an `APIRouter`/`Blueprint` prefix |
+//! | Java | Spring `@GetMapping`, `@RequestMapping` with a class-level prefix |
+//! | C# | ASP.NET `[HttpGet]`/`[Route]` with a controller prefix, minimal API `app.MapGet` |
+//! | Go | `http.HandleFunc` (with Go 1.22 `"GET /p"` patterns), gin/echo `r.GET`, chi `r.Get` |
+//!
+//! Each declaration becomes a [`NodeKind::Route`] node named `METHOD /path`
+//! that references its handler, so the graph gets a `route → handler`
+//! call edge. Inline handlers (closures, lambdas) have no node of their
+//! own; the route references whatever they call instead.
+
+use crate::node::{CodeNode, NodeKind, Route, Visibility};
+use tree_sitter::{Node, Tree};
+
+const RUST_VERBS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "trace", "any",
+];
+const JS_VERBS: &[&str] = &[
+    "get", "post", "put", "delete", "patch", "head", "options", "all",
+];
+const PYTHON_VERBS: &[&str] = &["get", "post", "put", "delete", "patch", "head", "options"];
+const GO_VERBS: &[&str] = &[
+    "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "Any", "Get", "Post", "Put",
+    "Delete", "Patch", "Head", "Options",
+];
+const SPRING_MAPPINGS: &[(&str, &str)] = &[
+    ("GetMapping", "GET"),
+    ("PostMapping", "POST"),
+    ("PutMapping", "PUT"),
+    ("DeleteMapping", "DELETE"),
+    ("PatchMapping", "PATCH"),
+    ("RequestMapping", "ANY"),
+];
+const ASPNET_ATTRIBUTES: &[(&str, &str)] = &[
+    ("HttpGet", "GET"),
+    ("HttpPost", "POST"),
+    ("HttpPut", "PUT"),
+    ("HttpDelete", "DELETE"),
+    ("HttpPatch", "PATCH"),
+    ("HttpHead", "HEAD"),
+    ("HttpOptions", "OPTIONS"),
+];
+const ASPNET_MAP_CALLS: &[(&str, &str)] = &[
+    ("MapGet", "GET"),
+    ("MapPost", "POST"),
+    ("MapPut", "PUT"),
+    ("MapDelete", "DELETE"),
+    ("MapPatch", "PATCH"),
+];
+
+/// Call nodes across the supported grammars.
+const CALL_KINDS: &[&str] = &[
+    "call_expression",
+    "call",
+    "method_invocation",
+    "invocation_expression",
+];
+
+/// What a route dispatches to.
+enum Handler<'t> {
+    /// A handler passed by name: `get(list_users)`.
+    Named(String),
+    /// A function declared right under the route: the byte offset of its
+    /// name, which lies inside the extracted node for it.
+    Declared(usize),
+    /// A closure or lambda; the route takes over its calls.
+    Inline(Node<'t>),
+}
+
+struct Declaration<'t> {
+    method: String,
+    path: String,
+    framework: &'static str,
+    at: Node<'t>,
+    handler: Handler<'t>,
+}
+
+/// Adds a route node for each framework route declared in the file.
+pub(crate) fn attach_routes(
+    tree: &Tree,
+    source: &str,
+    file_path: &str,
+    language: &str,
+    nodes: &mut Vec<CodeNode>,
+) {
+    let detect: for<'t> fn(Node<'t>, &str) -> Vec<Declaration<'t>> = match language {
+        "rs" => rust_routes,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => js_routes,
+        "py" => python_routes,
+        "java" => java_routes,
+        "cs" => csharp_routes,
+        "go" => go_routes,
+        _ => return,
+    };
+
+    let mut declarations = Vec::new();
+    let mut cursor = tree.walk();
+    'outer: loop {
+        declarations.extend(detect(cursor.node(), source));
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    for declaration in declarations {
+        let references = match declaration.handler {
+            Handler::Named(name) => vec![name],
+            Handler::Declared(offset) => declared_handler(nodes, offset).into_iter().collect(),
+            Handler::Inline(body) => calls_in(body, source),
+        };
+        let name = format!("{} {}", declaration.method, declaration.path);
+        let id = CodeNode::compute_id(file_path, &name, NodeKind::Route);
+
+        // The same route declared twice in a file (e.g. `route("/p", get(a))`
+        // and `route("/p", get(b))`) is one endpoint with both handlers.
+        if let Some(existing) = nodes.iter_mut().find(|n| n.id == id) {
+            existing.references.extend(references);
+            existing.references.sort();
+            existing.references.dedup();
+            continue;
+        }
+
+        let at = declaration.at;
+        let mut node = CodeNode::new(name.clone(), name.clone(), NodeKind::Route, file_path)
+            .with_lines(
+                at.start_position().row as u32 + 1,
+                at.end_position().row as u32 + 1,
+            )
+            .with_bytes(at.start_byte() as u32, at.end_byte() as u32)
+            .with_column(at.start_position().column as u32)
+            .with_signature(name)
+            .with_visibility(Visibility::Public)
+            .with_references(references);
+        node.route = Some(Route {
+            method: declaration.method,
+            path: declaration.path,
+            framework: declaration.framework.to_string(),
+        });
+        nodes.push(node);
+    }
+}
+
+/// Qualified name of the innermost function whose range holds `offset`.
+fn declared_handler(nodes: &[CodeNode], offset: usize) -> Option<String> {
+    let offset = offset as u32;
+    nodes
+        .iter()
+        .filter(|n| {
+            matches!(
+                n.kind,
+                NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+            ) && n.byte_start <= offset
+                && offset < n.byte_end
+        })
+        .min_by_key(|n| n.byte_end - n.byte_start)
+        .map(|n| n.qualified_name.clone())
+}
+
+/// Callee texts of every call under `root`, as the language parsers
+/// record them (`this.` and `self.` receivers dropped).
+fn calls_in(root: Node, source: &str) -> Vec<String> {
+    let mut calls = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if CALL_KINDS.contains(&node.kind()) {
+            let callee = match (
+                node.child_by_field_name("function"),
+                node.child_by_field_name("name"),
+            ) {
+                (Some(function), _) => Some(text(function, source).to_string()),
+                (None, Some(name)) => Some(match node.child_by_field_name("object") {
+                    Some(object) => format!("{}.{}", text(object, source), text(name, source)),
+                    None => text(name, source).to_string(),
+                }),
+                _ => None,
+            };
+            if let Some(callee) = callee {
+                let callee = ["this.", "self.", "base.", "super."]
+                    .iter()
+                    .find_map(|prefix| callee.strip_prefix(prefix))
+                    .unwrap_or(&callee);
+                calls.push(callee.to_string());
+            }
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    calls.sort();
+    calls.dedup();
+    calls
+}
+
+fn text<'s>(node: Node, source: &'s str) -> &'s str {
+    &source[node.byte_range()]
+}
+
+/// Contents of a string literal in any of the grammars: prefixes (`r`,
+/// `f`, `@`, ...), quotes and raw-string hashes stripped.
+fn string_value(node: Node, source: &str) -> Option<String> {
+    if !node.kind().contains("string") {
+        return None;
+    }
+    let literal = text(node, source);
+    let open = literal.find(['"', '\'', '`'])?;
+    let quote = literal[open..].chars().next()?;
+    let close = literal.rfind(quote)?;
+    (close > open).then(|| literal[open + 1..close].to_string())
+}
+
+/// Route paths start with `/`; anything else is a different API that
+/// happens to share a method name (`cache.get("key")`).
+fn route_path(node: Node, source: &str) -> Option<String> {
+    string_value(node, source).filter(|p| p.starts_with('/'))
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|n| !n.kind().contains("comment"))
+        .collect()
+}
+
+/// Joins a controller prefix and a route path with exactly one `/`.
+fn join_path(prefix: &str, path: &str) -> String {
+    let joined = match (prefix.trim_end_matches('/'), path.trim_start_matches('/')) {
+        ("", path) => format!("/{}", path),
+        (prefix, "") => prefix.to_string(),
+        (prefix, path) => format!("{}/{}", prefix, path),
+    };
+    if joined.starts_with('/') {
+        joined
+    } else {
+        format!("/{}", joined)
+    }
+}
+
+/// Last segment of a path such as `web::get` or `actix_web::get`.
+fn last_segment(path: &str) -> &str {
+    path.rsplit(['.', ':']).next().unwrap_or(path)
+}
+
+// ============================================================================
+// Rust
+// ============================================================================
+
+fn rust_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    match node.kind() {
+        "attribute_item" => rust_attribute_route(node, source).into_iter().collect(),
+        "call_expression" => rust_router_routes(node, source),
+        _ => Vec::new(),
+    }
+}
+
+/// `#[get("/users")] async fn list()` (actix, rocket).
+fn rust_attribute_route<'t>(item: Node<'t>, source: &str) -> Option<Declaration<'t>> {
+    let attribute = named_children(item)
+        .into_iter()
+        .find(|n| n.kind() == "attribute")?;
+    let parts = named_children(attribute);
+    let macro_name = last_segment(text(*parts.first()?, source));
+    let arguments = parts.iter().find(|n| n.kind() == "token_tree")?;
+    let tokens = named_children(*arguments);
+    let path = route_path(*tokens.first()?, source)?;
+
+    let method = if macro_name == "route" {
+        // #[route("/p", method = "GET")]
+        tokens
+            .windows(2)
+            .find(|w| text(w[0], source) == "method")
+            .and_then(|w| string_value(w[1], source))
+            .map(|m| m.to_ascii_uppercase())
+            .unwrap_or_else(|| "ANY".to_string())
+    } else if RUST_VERBS.contains(&macro_name) {
+        macro_name.to_ascii_uppercase()
+    } else {
+        return None;
+    };
+
+    let mut next = item.next_named_sibling();
+    while let Some(sibling) = next {
+        if sibling.kind() != "attribute_item" && !sibling.kind().contains("comment") {
+            break;
+        }
+        next = sibling.next_named_sibling();
+    }
+    let function = next.filter(|n| n.kind() == "function_item")?;
+    let name = function.child_by_field_name("name")?;
+    let framework = if source.contains("rocket") {
+        "rocket"
+    } else {
+        "actix"
+    };
+    Some(Declaration {
+        method,
+        path,
+        framework,
+        at: item,
+        handler: Handler::Declared(name.start_byte()),
+    })
+}
+
+/// `.route("/p", get(a).post(b))` (axum) and `.route("/p", web::get().to(a))`
+/// (actix).
+fn rust_router_routes<'t>(call: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    let is_route = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "field_expression")
+        .and_then(|f| f.child_by_field_name("field"))
+        .is_some_and(|field| text(field, source) == "route");
+    let arguments = call
+        .child_by_field_name("arguments")
+        .map(named_children)
+        .unwrap_or_default();
+    let (true, [path, methods, ..]) = (is_route, arguments.as_slice()) else {
+        return Vec::new();
+    };
+    let Some(path) = route_path(*path, source) else {
+        return Vec::new();
+    };
+
+    let mut handlers = Vec::new();
+    let mut actix = false;
+    rust_method_chain(*methods, source, &mut handlers, &mut actix);
+    let framework = if actix { "actix" } else { "axum" };
+    handlers
+        .into_iter()
+        .map(|(method, handler)| Declaration {
+            method,
+            path: path.clone(),
+            framework,
+            at: call,
+            handler,
+        })
+        .collect()
+}
+
+/// Collects (method, handler) pairs from `get(a).post(b)` or
+/// `web::get().to(a)`.
+fn rust_method_chain<'t>(
+    expr: Node<'t>,
+    source: &str,
+    out: &mut Vec<(String, Handler<'t>)>,
+    actix: &mut bool,
+) {
+    if expr.kind() != "call_expression" {
+        return;
+    }
+    let Some(function) = expr.child_by_field_name("function") else {
+        return;
+    };
+    let first_arg = expr
+        .child_by_field_name("arguments")
+        .and_then(|a| named_children(a).into_iter().next());
+
+    match function.kind() {
+        "identifier" | "scoped_identifier" => {
+            let verb = last_segment(text(function, source));
+            if let (true, Some(arg)) = (RUST_VERBS.contains(&verb), first_arg) {
+                out.push((verb.to_ascii_uppercase(), rust_handler(arg, source)));
+            }
+        }
+        "field_expression" => {
+            let (Some(value), Some(field)) = (
+                function.child_by_field_name("value"),
+                function.child_by_field_name("field"),
+            ) else {
+                return;
+            };
+            let field = text(field, source);
+            if field == "to" {
+                // web::get().to(handler)
+                let verb = value
+                    .child_by_field_name("function")
+                    .map(|f| last_segment(text(f, source)))
+                    .filter(|v| RUST_VERBS.contains(v));
+                if let (Some(verb), Some(arg)) = (verb, first_arg) {
+                    *actix = true;
+                    out.push((verb.to_ascii_uppercase(), rust_handler(arg, source)));
+                }
+                return;
+            }
+            rust_method_chain(value, source, out, actix);
+            if let (true, Some(arg)) = (RUST_VERBS.contains(&field), first_arg) {
+                out.push((field.to_ascii_uppercase(), rust_handler(arg, source)));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rust_handler<'t>(arg: Node<'t>, source: &str) -> Handler<'t> {
+    match arg.kind() {
+        "closure_expression" => Handler::Inline(arg),
+        _ => Handler::Named(text(arg, source).to_string()),
+    }
+}
+
+// ============================================================================
+// JavaScript / TypeScript
+// ============================================================================
+
+/// `app.get("/p", middleware, handler)` and `router.post(...)`.
+fn js_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    if node.kind() != "call_expression" {
+        return Vec::new();
+    }
+    let verb = node
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "member_expression")
+        .and_then(|f| f.child_by_field_name("property"))
+        .map(|p| text(p, source))
+        .filter(|v| JS_VERBS.contains(v));
+    let arguments = node
+        .child_by_field_name("arguments")
+        .map(named_children)
+        .unwrap_or_default();
+    let (Some(verb), [path, handlers @ ..]) = (verb, arguments.as_slice()) else {
+        return Vec::new();
+    };
+    let Some(path) = route_path(*path, source) else {
+        return Vec::new();
+    };
+    let framework = if source.contains("fastify") {
+        "fastify"
+    } else if source.contains("koa") {
+        "koa"
+    } else {
+        "express"
+    };
+    let method = match verb {
+        "all" => "ANY".to_string(),
+        verb => verb.to_ascii_uppercase(),
+    };
+
+    // Middleware runs before the handler, so every argument is reached.
+    handlers
+        .iter()
+        .filter_map(|arg| {
+            let handler = match arg.kind() {
+                "identifier" | "member_expression" => {
+                    let name = text(*arg, source);
+                    Handler::Named(name.strip_prefix("this.").unwrap_or(name).to_string())
+                }
+                "arrow_function" | "function_expression" | "function" => Handler::Inline(*arg),
+                _ => return None,
+            };
+            Some(Declaration {
+                method: method.clone(),
+                path: path.clone(),
+                framework,
+                at: node,
+                handler,
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Python
+// ============================================================================
+
+/// `@app.get("/p")` (FastAPI) and `@app.route("/p", methods=["POST"])`
+/// (Flask), under the prefix of the `APIRouter`/`Blueprint` they are
+/// declared on.
+fn python_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    if node.kind() != "decorator" {
+        return Vec::new();
+    }
+    let Some(call) = named_children(node)
+        .into_iter()
+        .find(|n| n.kind() == "call")
+    else {
+        return Vec::new();
+    };
+    let Some(function) = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "attribute")
+    else {
+        return Vec::new();
+    };
+    let Some(decorator) = function
+        .child_by_field_name("attribute")
+        .map(|a| text(a, source))
+    else {
+        return Vec::new();
+    };
+    let arguments = call
+        .child_by_field_name("arguments")
+        .map(named_children)
+        .unwrap_or_default();
+    let Some(path) = arguments.first().and_then(|a| route_path(*a, source)) else {
+        return Vec::new();
+    };
+
+    let (methods, framework) = match decorator {
+        "route" | "api_route" => {
+            let listed: Vec<String> = arguments
+                .iter()
+                .filter(|a| a.kind() == "keyword_argument")
+                .filter(|a| {
+                    a.child_by_field_name("name")
+                        .is_some_and(|n| text(n, source) == "methods")
+                })
+                .filter_map(|a| a.child_by_field_name("value"))
+                .flat_map(named_children)
+                .filter_map(|m| string_value(m, source))
+                .map(|m| m.to_ascii_uppercase())
+                .collect();
+            let methods = if listed.is_empty() {
+                vec!["GET".to_string()]
+            } else {
+                listed
+            };
+            let framework = if decorator == "api_route" {
+                "fastapi"
+            } else {
+                "flask"
+            };
+            (methods, framework)
+        }
+        verb if PYTHON_VERBS.contains(&verb) => {
+            let framework = if source.contains("fastapi") {
+                "fastapi"
+            } else {
+                "flask"
+            };
+            (vec![verb.to_ascii_uppercase()], framework)
+        }
+        _ => return Vec::new(),
+    };
+    let prefix = function
+        .child_by_field_name("object")
+        .filter(|o| o.kind() == "identifier")
+        .and_then(|o| python_router_prefix(node, text(o, source), source))
+        .unwrap_or_default();
+
+    let Some(name) = node
+        .parent()
+        .filter(|p| p.kind() == "decorated_definition")
+        .and_then(|p| p.child_by_field_name("definition"))
+        .filter(|d| d.kind() == "function_definition")
+        .and_then(|d| d.child_by_field_name("name"))
+    else {
+        return Vec::new();
+    };
+    methods
+        .into_iter()
+        .map(|method| Declaration {
+            method,
+            path: join_path(&prefix, &path),
+            framework,
+            at: node,
+            handler: Handler::Declared(name.start_byte()),
+        })
+        .collect()
+}
+
+/// The prefix of a module-level `router = APIRouter(prefix="/p")`
+/// (FastAPI) or `bp = Blueprint("name", __name__, url_prefix="/p")` (Flask).
+fn python_router_prefix(node: Node, router: &str, source: &str) -> Option<String> {
+    let mut module = node;
+    while let Some(parent) = module.parent() {
+        module = parent;
+    }
+    named_children(module)
+        .into_iter()
+        .filter(|s| s.kind() == "expression_statement")
+        .flat_map(named_children)
+        .filter(|a| a.kind() == "assignment")
+        .filter(|a| {
+            a.child_by_field_name("left")
+                .is_some_and(|l| text(l, source) == router)
+        })
+        .filter_map(|a| a.child_by_field_name("right"))
+        .filter(|r| r.kind() == "call")
+        .filter(|r| {
+            r.child_by_field_name("function")
+                .is_some_and(|f| matches!(last_segment(text(f, source)), "APIRouter" | "Blueprint"))
+        })
+        .filter_map(|r| r.child_by_field_name("arguments"))
+        .flat_map(named_children)
+        .filter(|a| a.kind() == "keyword_argument")
+        .filter(|a| {
+            a.child_by_field_name("name")
+                .is_some_and(|n| matches!(text(n, source), "prefix" | "url_prefix"))
+        })
+        .filter_map(|a| a.child_by_field_name("value"))
+        .find_map(|v| string_value(v, source))
+}
+
+// ============================================================================
+// Java (Spring)
+// ============================================================================
+
+/// `@GetMapping("/p")` on a method, under a class-level
+/// `@RequestMapping("/prefix")`.
+fn java_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    if node.kind() != "method_declaration" {
+        return Vec::new();
+    }
+    let Some(name) = node.child_by_field_name("name") else {
+        return Vec::new();
+    };
+    let Some((annotation, method, path)) = spring_mapping(node, source) else {
+        return Vec::new();
+    };
+    let mut class = node.parent();
+    while let Some(c) = class.filter(|c| c.kind() != "class_declaration") {
+        class = c.parent();
+    }
+    let prefix = class
+        .and_then(|c| spring_mapping(c, source))
+        .map(|(_, _, prefix)| prefix)
+        .unwrap_or_default();
+    vec![Declaration {
+        method,
+        path: join_path(&prefix, &path),
+        framework: "spring",
+        at: annotation,
+        handler: Handler::Declared(name.start_byte()),
+    }]
+}
+
+/// The mapping annotation on a declaration: (annotation, method, path).
+fn spring_mapping<'t>(declaration: Node<'t>, source: &str) -> Option<(Node<'t>, String, String)> {
+    let modifiers = named_children(declaration)
+        .into_iter()
+        .find(|n| n.kind() == "modifiers")?;
+    named_children(modifiers)
+        .into_iter()
+        .find_map(|annotation| {
+            if !matches!(annotation.kind(), "annotation" | "marker_annotation") {
+                return None;
+            }
+            let name = last_segment(text(annotation.child_by_field_name("name")?, source));
+            let (_, mut method) = SPRING_MAPPINGS.iter().find(|(n, _)| *n == name)?;
+            let mut path = String::new();
+            let arguments = annotation
+                .child_by_field_name("arguments")
+                .map(named_children)
+                .unwrap_or_default();
+            for argument in arguments {
+                match argument.kind() {
+                    "element_value_pair" => {
+                        let key = argument
+                            .child_by_field_name("key")
+                            .map(|k| text(k, source))
+                            .unwrap_or_default();
+                        let Some(value) = argument.child_by_field_name("value") else {
+                            continue;
+                        };
+                        match key {
+                            "value" | "path" => {
+                                path = spring_path(value, source).unwrap_or_default()
+                            }
+                            // method = RequestMethod.POST
+                            "method" => {
+                                let verb = last_segment(text(value, source));
+                                if let Some((_, m)) = SPRING_MAPPINGS
+                                    .iter()
+                                    .find(|(_, m)| m.eq_ignore_ascii_case(verb))
+                                {
+                                    method = m;
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {
+                        if let Some(value) = spring_path(argument, source) {
+                            path = value;
+                        }
+                    }
+                }
+            }
+            Some((annotation, method.to_string(), path))
+        })
+}
+
+/// A path value: `"/p"` or the first of `{"/p", "/q"}`.
+fn spring_path(value: Node, source: &str) -> Option<String> {
+    string_value(value, source).or_else(|| {
+        named_children(value)
+            .into_iter()
+            .find_map(|v| string_value(v, source))
+    })
+}
+
+// ============================================================================
+// C# (ASP.NET)
+// ============================================================================
+
+fn csharp_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    match node.kind() {
+        "method_declaration" => csharp_action(node, source).into_iter().collect(),
+        "invocation_expression" => csharp_minimal_api(node, source).into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// `[HttpGet("{id}")]` on a controller action, under the controller's
+/// `[Route("api/[controller]")]`.
+fn csharp_action<'t>(method: Node<'t>, source: &str) -> Option<Declaration<'t>> {
+    let name = method.child_by_field_name("name")?;
+    let attributes = csharp_attributes(method, source);
+    let (verb_attribute, verb, verb_path) = attributes
+        .iter()
+        .find_map(|(node, name, path)| {
+            ASPNET_ATTRIBUTES
+                .iter()
+                .find(|(a, _)| a == name)
+                .map(|(_, verb)| (*node, verb.to_string(), path.clone()))
+        })
+        .or_else(|| {
+            attributes
+                .iter()
+                .find(|(_, name, _)| name == "Route")
+                .map(|(node, _, path)| (*node, "ANY".to_string(), path.clone()))
+        })?;
+    let template = verb_path
+        .or_else(|| {
+            attributes
+                .iter()
+                .find(|(_, name, _)| name == "Route")
+                .and_then(|(_, _, path)| path.clone())
+        })
+        .unwrap_or_default();
+
+    let mut class = method.parent();
+    while let Some(c) = class.filter(|c| c.kind() != "class_declaration") {
+        class = c.parent();
+    }
+    let controller = class
+        .and_then(|c| c.child_by_field_name("name"))
+        .map(|n| text(n, source))
+        .unwrap_or_default();
+    let prefix = class
+        .map(|c| csharp_attributes(c, source))
+        .and_then(|attrs| attrs.into_iter().find(|(_, name, _)| name == "Route"))
+        .and_then(|(_, _, path)| path)
+        .unwrap_or_default();
+
+    // A template starting with `/` or `~/` ignores the controller route.
+    let path = if template.starts_with('/') || template.starts_with("~/") {
+        join_path("", template.trim_start_matches('~'))
+    } else {
+        join_path(&prefix, &template)
+    };
+    let path = path
+        .replace(
+            "[controller]",
+            controller.strip_suffix("Controller").unwrap_or(controller),
+        )
+        .replace("[action]", text(name, source));
+    Some(Declaration {
+        method: verb,
+        path,
+        framework: "aspnet",
+        at: verb_attribute,
+        handler: Handler::Declared(name.start_byte()),
+    })
+}
+
+/// Attributes on a declaration: (node, name without `Attribute`, first
+/// string argument).
+fn csharp_attributes<'t>(
+    declaration: Node<'t>,
+    source: &str,
+) -> Vec<(Node<'t>, String, Option<String>)> {
+    named_children(declaration)
+        .into_iter()
+        .filter(|n| n.kind() == "attribute_list")
+        .flat_map(named_children)
+        .filter(|a| a.kind() == "attribute")
+        .filter_map(|attribute| {
+            let name = last_segment(text(attribute.child_by_field_name("name")?, source));
+            let name = name.strip_suffix("Attribute").unwrap_or(name).to_string();
+            let path = named_children(attribute)
+                .into_iter()
+                .filter(|n| n.kind() == "attribute_argument_list")
+                .flat_map(named_children)
+                .flat_map(named_children)
+                .find_map(|v| string_value(v, source));
+            Some((attribute, name, path))
+        })
+        .collect()
+}
+
+/// `app.MapGet("/p", handler)`.
+fn csharp_minimal_api<'t>(call: Node<'t>, source: &str) -> Option<Declaration<'t>> {
+    let name = call
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "member_access_expression")
+        .and_then(|f| f.child_by_field_name("name"))?;
+    let (_, method) = ASPNET_MAP_CALLS
+        .iter()
+        .find(|(n, _)| *n == text(name, source))?;
+    let arguments: Vec<Node> = call
+        .child_by_field_name("arguments")
+        .map(named_children)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|a| named_children(a).into_iter().next())
+        .collect();
+    let [path, handler, ..] = arguments.as_slice() else {
+        return None;
+    };
+    let path = route_path(*path, source)?;
+    let handler = match handler.kind() {
+        "lambda_expression" | "anonymous_method_expression" => Handler::Inline(*handler),
+        _ => Handler::Named(text(*handler, source).to_string()),
+    };
+    Some(Declaration {
+        method: method.to_string(),
+        path,
+        framework: "aspnet",
+        at: call,
+        handler,
+    })
+}
+
+// ============================================================================
+// Go
+// ============================================================================
+
+/// `http.HandleFunc("GET /p", h)`, `r.GET("/p", h)` (gin, echo) and
+/// `r.Get("/p", h)` (chi).
+fn go_routes<'t>(node: Node<'t>, source: &str) -> Vec<Declaration<'t>> {
+    if node.kind() != "call_expression" {
+        return Vec::new();
+    }
+    let Some(function) = node
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "selector_expression")
+        .and_then(|f| f.child_by_field_name("field"))
+        .map(|f| text(f, source))
+    else {
+        return Vec::new();
+    };
+    let arguments = node
+        .child_by_field_name("arguments")
+        .map(named_children)
+        .unwrap_or_default();
+    let (Some(pattern), Some(handler)) = (
+        arguments.first().and_then(|a| string_value(*a, source)),
+        arguments.get(1..).and_then(|rest| rest.last()),
+    ) else {
+        return Vec::new();
+    };
+
+    let (method, path) = match function {
+        "HandleFunc" | "Handle" => match pattern.split_once(' ') {
+            // Go 1.22 method patterns: "GET /users/{id}"
+            Some((method, path)) => (method.to_string(), path.trim().to_string()),
+            None => ("ANY".to_string(), pattern),
+        },
+        verb if GO_VERBS.contains(&verb) => (verb.to_ascii_uppercase(), pattern),
+        _ => return Vec::new(),
+    };
+    if !path.starts_with('/') {
+        return Vec::new();
+    }
+    let framework = if source.contains("gin-gonic") {
+        "gin"
+    } else if source.contains("labstack/echo") {
+        "echo"
+    } else if source.contains("go-chi") {
+        "chi"
+    } else if source.contains("gorilla/mux") {
+        "gorilla"
+    } else if source.contains("gofiber") {
+        "fiber"
+    } else if matches!(function, "HandleFunc" | "Handle") {
+        "net/http"
+    } else if function.chars().all(|c| c.is_ascii_uppercase()) {
+        "gin"
+    } else {
+        "chi"
+    };
+
+    let handler = match handler.kind() {
+        "func_literal" => Handler::Inline(*handler),
+        // http.HandlerFunc(h)
+        "call_expression" => match handler
+            .child_by_field_name("arguments")
+            .and_then(|a| named_children(a).into_iter().next())
+        {
+            Some(inner) if inner.kind() != "func_literal" => {
+                Handler::Named(text(inner, source).to_string())
+            }
+            Some(inner) => Handler::Inline(inner),
+            None => return Vec::new(),
+        },
+        _ => Handler::Named(text(*handler, source).to_string()),
+    };
+    vec![Declaration {
+        method: if method == "ANY" {
+            method
+        } else {
+            method.to_ascii_uppercase()
+        },
+        path,
+        framework,
+        at: node,
+        handler,
+    }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::get_parser;
+    use crate::parser::parse_source;
+    use std::path::Path;
+
+    fn routes(file: &str, source: &str) -> Vec<(String, String, Vec<String>)> {
+        let extension = Path::new(file).extension().unwrap().to_str().unwrap();
+        let parser = get_parser(extension).unwrap();
+        let mut found: Vec<_> = parse_source(source, file, parser.as_ref())
+            .unwrap()
+            .into_iter()
+            .filter(|n| n.kind == NodeKind::Route)
+            .map(|n| {
+                let route = n.route.unwrap();
+                (n.name, route.framework, n.references)
+            })
+            .collect();
+        found.sort();
+        found
+    }
+
+    fn route(name: &str, framework: &str, references: &[&str]) -> (String, String, Vec<String>) {
+        (
+            name.to_string(),
+            framework.to_string(),
+            references.iter().map(|r| r.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_rust_attribute_and_router_routes() {
+        let source = r#"
+#[get("/users/{id}")]
+async fn show(id: u32) -> String { load(id) }
+
+fn app() -> Router {
+    Router::new()
+        .route("/users", get(list).post(handlers::create))
+        .route("/health", get(|| async { ping() }))
+}
+"#;
+        assert_eq!(
+            routes("src/api.rs", source),
+            [
+                route("GET /health", "axum", &["ping"]),
+                route("GET /users", "axum", &["list"]),
+                route("GET /users/{id}", "actix", &["show"]),
+                route("POST /users", "axum", &["handlers::create"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_express_and_python_routes() {
+        let js = r#"
+app.get('/users/:id', auth, getUser);
+router.post(`/orders`, async (req, res) => { await saveOrder(req.body); });
+cache.get('/not-a-route');
+"#;
+        assert_eq!(
+            routes("server.js", js),
+            [
+                route("GET /users/:id", "express", &["auth", "getUser"]),
+                route("POST /orders", "express", &["saveOrder"]),
+            ]
+        );
+
+        let py = r#"
+from fastapi import FastAPI
+
+class Users:
+    @router.get("/users/{id}")
+    async def show(self, id: int):
+        return load(id)
+
+@app.api_route("/items", methods=["GET", "PUT"])
+def items():
+    pass
+
+@pytest.mark.parametrize("/x")
+def test_x():
+    pass
+"#;
+        assert_eq!(
+            routes("api.py", py),
+            [
+                route("GET /items", "fastapi", &["items"]),
+                route("GET /users/{id}", "fastapi", &["Users.show"]),
+                route("PUT /items", "fastapi", &["items"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_python_routes_join_router_prefixes() {
+        let fastapi = r#"
+from fastapi import APIRouter
+
+router = APIRouter(prefix="/items", tags=["items"])
+
+@router.get("/{id}")
+async def show(id: int):
+    return load(id)
+"#;
+        assert_eq!(
+            routes("items.py", fastapi),
+            [route("GET /items/{id}", "fastapi", &["show"])]
+        );
+
+        let flask = r#"
+from flask import Blueprint
+
+bp = Blueprint("users", __name__, url_prefix="/users/")
+other = Blueprint("other", __name__)
+
+@bp.route("/", methods=["POST"])
+def create():
+    pass
+
+@other.route("/health")
+def health():
+    pass
+"#;
+        assert_eq!(
+            routes("users.py", flask),
+            [
+                route("GET /health", "flask", &["health"]),
+                route("POST /users", "flask", &["create"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spring_and_aspnet_routes_join_controller_prefixes() {
+        let java = r#"
+@RestController
+@RequestMapping("/api/users")
+class UserController {
+    @GetMapping("/{id}")
+    public User show(long id) { return repo.find(id); }
+
+    @RequestMapping(value = "/search", method = RequestMethod.POST)
+    public List<User> search() { return null; }
+
+    @DeleteMapping
+    public void clear() {}
+}
+"#;
+        let found: Vec<String> = routes("UserController.java", java)
+            .into_iter()
+            .map(|(name, framework, _)| format!("{} ({})", name, framework))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                "DELETE /api/users (spring)",
+                "GET /api/users/{id} (spring)",
+                "POST /api/users/search (spring)",
+            ]
+        );
+
+        let cs = r#"
+[Route("api/[controller]")]
+public class OrdersController : ControllerBase {
+    [HttpGet("{id}")]
+    public Order Get(int id) { return null; }
+}
+class Program {
+    static void Main() { app.MapPost("/orders", (Order o) => Save(o)); }
+}
+"#;
+        assert_eq!(
+            routes("Orders.cs", cs),
+            [
+                route("GET /api/Orders/{id}", "aspnet", &["OrdersController.Get"]),
+                route("POST /orders", "aspnet", &["Save"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_go_routes() {
+        let source = r#"
+package main
+
+import "github.com/gin-gonic/gin"
+
+func main() {
+    http.HandleFunc("GET /users/{id}", showUser)
+    r.POST("/orders", h.CreateOrder)
+    r.GET("/health", func(c *gin.Context) { ping(c) })
+}
+"#;
+        assert_eq!(
+            routes("main.go", source),
+            [
+                route("GET /health", "gin", &["ping"]),
+                route("GET /users/{id}", "gin", &["showUser"]),
+                route("POST /orders", "gin", &["h.CreateOrder"]),
+            ]
+        );
+    }
+}
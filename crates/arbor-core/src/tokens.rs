@@ -0,0 +1,227 @@
+//! Normalized token streams for clone detection.
+//!
+//! Two functions that differ only in names, literals, comments and layout
+//! should produce the same stream. Identifiers, strings and numbers collapse
+//! to placeholders, comments disappear, and a few keywords that mean the
+//! same thing across languages (`fn`/`def`/`function`, `self`/`this`,
+//! `None`/`null`/`nil`, ...) share one token so clones can match across
+//! languages. Block delimiters, statement terminators and declaration
+//! keywords (`{`, `;`, `let`, `var`, ...) are dropped for the same reason:
+//! they encode layout that differs between languages, not logic. Everything
+//! else keeps its text.
+//!
+//! Files with a Tree-sitter grammar use its leaves; other files go through
+//! a small lexical scanner that applies the same rules.
+
+use crate::languages::get_parser;
+use crate::node::content_hash;
+
+/// One token of a normalized stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizedToken {
+    /// Byte offset of the token in the source.
+    pub start: usize,
+    /// Hash of the normalized token text.
+    pub hash: u64,
+}
+
+/// Tokenizes `source` for a file with the given extension.
+pub fn normalized_tokens(source: &str, extension: &str) -> Vec<NormalizedToken> {
+    let Some(lang) = get_parser(extension) else {
+        return lexical_tokens(source);
+    };
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&lang.language()).is_err() {
+        return lexical_tokens(source);
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return lexical_tokens(source);
+    };
+
+    let mut tokens = Vec::new();
+    let mut cursor = tree.walk();
+    loop {
+        let node = cursor.node();
+        let kind = node.kind();
+        let atomic = is_string_kind(kind) && node.is_named();
+        if kind.contains("comment") {
+            // Skipped whole: some grammars give comments child tokens.
+        } else if atomic || node.child_count() == 0 {
+            let text = &source[node.byte_range()];
+            if let Some(token) = normalize_leaf(kind, text, node.is_named(), atomic) {
+                tokens.push(NormalizedToken {
+                    start: node.start_byte(),
+                    hash: content_hash(token.as_bytes()),
+                });
+            }
+        } else if cursor.goto_first_child() {
+            continue;
+        }
+
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return tokens;
+            }
+        }
+    }
+}
+
+fn is_string_kind(kind: &str) -> bool {
+    kind.contains("string") || kind.contains("char_literal") || kind == "character_literal"
+}
+
+fn normalize_leaf<'a>(kind: &str, text: &'a str, named: bool, atomic: bool) -> Option<&'a str> {
+    if text.trim().is_empty() || is_layout(text) {
+        return None;
+    }
+    if atomic {
+        return Some("$str");
+    }
+    if kind.contains("number")
+        || kind.contains("integer")
+        || kind.contains("float")
+        || kind.contains("int_literal")
+        || kind.contains("decimal")
+    {
+        return Some("$num");
+    }
+    if let Some(shared) = shared_keyword(text) {
+        return Some(shared);
+    }
+    if named && (kind.ends_with("identifier") || kind.contains("type") || kind == "name") {
+        return Some("$id");
+    }
+    Some(text)
+}
+
+/// Cross-language spellings of the same construct.
+fn shared_keyword(text: &str) -> Option<&'static str> {
+    Some(match text {
+        "fn" | "def" | "func" | "function" | "fun" => "$fn",
+        "self" | "this" | "Self" => "$self",
+        "None" | "null" | "nil" | "undefined" | "nullptr" | "NULL" => "$null",
+        "True" | "true" => "true",
+        "False" | "false" => "false",
+        "elif" | "elsif" => "elif",
+        "&&" | "and" => "&&",
+        "||" | "or" => "||",
+        "of" => "in",
+        "===" => "==",
+        "!==" => "!=",
+        _ => return None,
+    })
+}
+
+/// Tokens that only carry layout or declaration syntax.
+fn is_layout(text: &str) -> bool {
+    matches!(
+        text,
+        "{" | "}" | ";" | ":" | "let" | "const" | "var" | "val" | "mut" | "auto"
+    )
+}
+
+/// Keywords kept verbatim by the lexical scanner; other words are names.
+const KEYWORDS: &[&str] = &[
+    "if", "else", "for", "while", "do", "return", "break", "continue", "switch", "case", "when",
+    "match", "try", "catch", "rescue", "finally", "throw", "raise", "new", "in", "not", "end",
+    "class", "import", "yield", "await", "async", "then", "fi",
+];
+
+/// Scanner for files without a grammar: words, numbers, quoted strings,
+/// `//` and `#` comments, and single-character punctuation.
+fn lexical_tokens(source: &str) -> Vec<NormalizedToken> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let c = bytes[i];
+        let token: &str = if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if c == b'#' || (c == b'/' && bytes.get(i + 1) == Some(&b'/')) {
+            while i < bytes.len() && bytes[i] != b'\n' {
+                i += 1;
+            }
+            continue;
+        } else if matches!(c, b'"' | b'\'' | b'`') {
+            i += 1;
+            while i < bytes.len() && bytes[i] != c {
+                i += if bytes[i] == b'\\' { 2 } else { 1 };
+            }
+            i += 1;
+            "$str"
+        } else if c.is_ascii_digit() {
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                i += 1;
+            }
+            "$num"
+        } else if c.is_ascii_alphabetic() || c == b'_' || c == b'$' {
+            while i < bytes.len()
+                && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b'$')
+            {
+                i += 1;
+            }
+            let word = &source[start..i];
+            if is_layout(word) {
+                continue;
+            }
+            match shared_keyword(word) {
+                Some(shared) => shared,
+                None if KEYWORDS.contains(&word) => word,
+                None => "$id",
+            }
+        } else {
+            // Skip the whole UTF-8 sequence so slices stay on char boundaries.
+            i += source[start..].chars().next().map_or(1, char::len_utf8);
+            if is_layout(&source[start..i]) {
+                continue;
+            }
+            &source[start..i]
+        };
+        tokens.push(NormalizedToken {
+            start,
+            hash: content_hash(token.as_bytes()),
+        });
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(source: &str, ext: &str) -> Vec<u64> {
+        normalized_tokens(source, ext)
+            .into_iter()
+            .map(|t| t.hash)
+            .collect()
+    }
+
+    #[test]
+    fn test_renames_and_comments_normalize_away() {
+        let a = "fn total(items: &[u32]) -> u32 {\n    // sum\n    items.iter().sum()\n}\n";
+        let b = "fn count(values: &[u64]) -> u64 { values.iter().sum() }";
+        assert_eq!(hashes(a, "rs"), hashes(b, "rs"));
+
+        let c = "fn total(items: &[u32]) -> u32 { items.len() + 1 }";
+        assert_ne!(hashes(a, "rs"), hashes(c, "rs"));
+
+        let strings = normalized_tokens("x = \"hello\" + 'a' + 42", "py");
+        assert_eq!(strings.len(), 7);
+        assert_eq!(strings[2].hash, content_hash(b"$str"));
+        assert_eq!(strings[6].hash, content_hash(b"$num"));
+    }
+
+    #[test]
+    fn test_lexical_fallback() {
+        let a = normalized_tokens("def greet(name) # hi\n  puts \"hi #{name}\"\nend", "txt");
+        let b = normalized_tokens("def wave(who)\n  puts 'yo'\nend", "txt");
+        assert_eq!(
+            a.iter().map(|t| t.hash).collect::<Vec<_>>(),
+            b.iter().map(|t| t.hash).collect::<Vec<_>>()
+        );
+        assert_eq!(a[0].start, 0);
+        assert_eq!(a[1].start, 4);
+    }
+}
@@ -0,0 +1,691 @@
+//! Message-bus topics: publish and subscribe calls.
+//!
+//! Services that talk over Kafka, NATS, Redis or an in-process event
+//! emitter never call each other; each side only names a topic. This pass
+//! records the topics a node publishes to or subscribes to in
+//! [`CodeNode::wiring`], and the graph crate links every publisher and
+//! subscriber of a name through one [`NodeKind::Topic`] node.
+//!
+//! A subscription belongs to its handler: the function or method passed
+//! to the subscribe call when it is defined in the same file, else the
+//! function making the call (whose body holds an inline closure).
+//!
+//! Only literal topic names count: the first argument
+//! (`producer.send("orders.created")`, `bus.subscribe(["a", "b"])`), a
+//! `topic`/`subject`/`channel` keyword or object key
+//! (`producer.send({ topic: "orders" })`), or an annotation element
+//! (`@KafkaListener(topics = "orders")`). Built-in patterns:
+//!
+//! | Role | Calls | Annotations |
+//! |------|-------|-------------|
+//! | Publish | `publish`, `emit`, `produce`, `convertAndSend`, `xadd`, `publisher`, `send` on a producer, template, bus or queue | `@Outgoing`, `@SendTo` |
+//! | Subscribe | `subscribe`, `psubscribe`, `queueSubscribe`, `on`, `once`, `addListener`, `consume`, `subscriber`, NestJS `@EventPattern`/`@MessagePattern`/`@OnEvent` | `@KafkaListener`, `@RabbitListener`, `@JmsListener`, `@SqsListener`, `@Incoming` |
+//!
+//! A project adds its own in `.arbor/topics.toml`, read by
+//! [`load_topic_patterns`]:
+//!
+//! ```toml
+//! builtin = true          # keep the patterns above (default)
+//!
+//! [[publish]]
+//! call = "dispatch"       # method name, any case
+//! receiver = "events"     # optional: text the receiver must contain
+//!
+//! [[subscribe]]
+//! annotation = "StreamListener"
+//! key = "value"           # optional: element holding the topic
+//! ```
+
+use crate::hierarchy::{named_children, text};
+use crate::node::{CodeNode, NodeKind, Wiring};
+use crate::project::ByProject;
+use crate::wiring::{
+    attach_found, function_reference, last_segment, name_offset, unwrap_argument, CALL_KINDS,
+};
+use serde::Deserialize;
+use std::path::Path;
+use tracing::warn;
+use tree_sitter::{Node, Tree};
+
+/// Config file, relative to the project root.
+pub const TOPICS_CONFIG: &str = ".arbor/topics.toml";
+
+/// `(call, receiver)`: calls that publish.
+const PUBLISH_CALLS: &[(&str, Option<&str>)] = &[
+    ("publish", None),
+    ("emit", None),
+    ("produce", None),
+    ("convertAndSend", None),
+    ("xadd", None),
+    ("publisher", None),
+    ("send", Some("producer")),
+    ("send", Some("template")),
+    ("send", Some("kafka")),
+    ("send", Some("bus")),
+    ("send", Some("queue")),
+    ("send", Some("publisher")),
+];
+
+/// `(call, receiver)`: calls that subscribe.
+const SUBSCRIBE_CALLS: &[(&str, Option<&str>)] = &[
+    ("subscribe", None),
+    ("psubscribe", None),
+    ("queueSubscribe", None),
+    ("on", None),
+    ("once", None),
+    ("addListener", None),
+    ("consume", None),
+    ("subscriber", None),
+    ("EventPattern", None),
+    ("MessagePattern", None),
+    ("OnEvent", None),
+];
+
+/// `(annotation, element)`: annotations that publish.
+const PUBLISH_ANNOTATIONS: &[(&str, &str)] = &[("Outgoing", "value"), ("SendTo", "value")];
+
+/// `(annotation, element)`: annotations that subscribe.
+const SUBSCRIBE_ANNOTATIONS: &[(&str, &str)] = &[
+    ("KafkaListener", "topics"),
+    ("RabbitListener", "queues"),
+    ("JmsListener", "destination"),
+    ("SqsListener", "value"),
+    ("Incoming", "value"),
+];
+
+/// Keyword arguments and object keys that name a topic.
+const TOPIC_KEYS: &[&str] = &[
+    "topic",
+    "topics",
+    "subject",
+    "channel",
+    "queue",
+    "destination",
+];
+
+/// One publish or subscribe pattern from `.arbor/topics.toml`: a `call`,
+/// optionally on a matching `receiver`, or an `annotation`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicPattern {
+    /// Method or function name, matched in any case.
+    #[serde(default)]
+    pub call: Option<String>,
+    /// Text the receiver must contain, in any case (`producer` for
+    /// `orderProducer.send`).
+    #[serde(default)]
+    pub receiver: Option<String>,
+    /// Annotation name, e.g. `StreamListener`.
+    #[serde(default)]
+    pub annotation: Option<String>,
+    /// Annotation element holding the topic; defaults to `value` or the
+    /// first argument.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+/// Contents of `.arbor/topics.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicPatterns {
+    /// Whether the built-in patterns apply too.
+    #[serde(default = "yes")]
+    pub builtin: bool,
+    #[serde(default)]
+    pub publish: Vec<TopicPattern>,
+    #[serde(default)]
+    pub subscribe: Vec<TopicPattern>,
+}
+
+fn yes() -> bool {
+    true
+}
+
+impl Default for TopicPatterns {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            publish: Vec::new(),
+            subscribe: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Publish,
+    Subscribe,
+}
+
+impl TopicPatterns {
+    /// Parses the contents of a topics file.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let patterns: Self = toml::from_str(text).map_err(|e| e.to_string())?;
+        for pattern in patterns.publish.iter().chain(&patterns.subscribe) {
+            if pattern.call.is_some() == pattern.annotation.is_some() {
+                return Err(format!(
+                    "each pattern needs either `call` or `annotation`: {:?}",
+                    pattern
+                ));
+            }
+        }
+        Ok(patterns)
+    }
+
+    /// Role of a call to `method` on `receiver` (empty for a bare call).
+    fn call_role(&self, method: &str, receiver: &str) -> Option<Role> {
+        let receiver = receiver.to_lowercase();
+        let matches = |call: &str, wanted: Option<&str>| {
+            call.eq_ignore_ascii_case(method)
+                && wanted.is_none_or(|w| receiver.contains(&w.to_lowercase()))
+        };
+        let configured = |patterns: &[TopicPattern]| {
+            patterns.iter().any(|p| {
+                p.call
+                    .as_deref()
+                    .is_some_and(|call| matches(call, p.receiver.as_deref()))
+            })
+        };
+        let builtin = |calls: &[(&str, Option<&str>)]| {
+            self.builtin && calls.iter().any(|&(call, wanted)| matches(call, wanted))
+        };
+        if configured(&self.publish) || builtin(PUBLISH_CALLS) {
+            Some(Role::Publish)
+        } else if configured(&self.subscribe) || builtin(SUBSCRIBE_CALLS) {
+            Some(Role::Subscribe)
+        } else {
+            None
+        }
+    }
+
+    /// Role of an annotation and the element that holds its topic.
+    fn annotation_role(&self, name: &str) -> Option<(Role, Option<&str>)> {
+        let roles = [
+            (Role::Publish, &self.publish, PUBLISH_ANNOTATIONS),
+            (Role::Subscribe, &self.subscribe, SUBSCRIBE_ANNOTATIONS),
+        ];
+        roles.into_iter().find_map(|(role, configured, builtin)| {
+            let key = match configured
+                .iter()
+                .find(|p| p.annotation.as_deref() == Some(name))
+            {
+                Some(pattern) => pattern.key.as_deref(),
+                None => builtin
+                    .iter()
+                    .find(|(annotation, _)| self.builtin && *annotation == name)
+                    .map(|&(_, key)| Some(key))?,
+            };
+            Some((role, key))
+        })
+    }
+}
+
+/// Patterns loaded for each project root.
+static PATTERNS: ByProject<TopicPatterns> = ByProject::new();
+
+/// Uses `<project_root>/.arbor/topics.toml` for later parses of files in
+/// that project, or the built-in patterns when there is none. An invalid
+/// file is logged and the built-in patterns are used. Other projects keep
+/// their own patterns.
+pub fn load_topic_patterns(project_root: &Path) {
+    let path = project_root.join(TOPICS_CONFIG);
+    let patterns = match std::fs::read_to_string(&path) {
+        Ok(text) => TopicPatterns::parse(&text).unwrap_or_else(|e| {
+            warn!("Ignoring {}: {}", path.display(), e);
+            TopicPatterns::default()
+        }),
+        Err(_) => TopicPatterns::default(),
+    };
+    PATTERNS.set(project_root, patterns);
+}
+
+/// Records the topics each node publishes to or subscribes to, using the
+/// patterns of the project containing `file_path`.
+pub(crate) fn attach_topics(tree: &Tree, source: &str, file_path: &str, nodes: &mut [CodeNode]) {
+    match PATTERNS.for_path(Path::new(file_path)) {
+        Some(patterns) => attach_with(tree, source, nodes, &patterns),
+        None => attach_with(tree, source, nodes, &TopicPatterns::default()),
+    }
+}
+
+fn attach_with(tree: &Tree, source: &str, nodes: &mut [CodeNode], patterns: &TopicPatterns) {
+    let mut found = Vec::new();
+    let mut cursor = tree.walk();
+    'outer: loop {
+        visit(cursor.node(), source, patterns, nodes, &mut found);
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    attach_found(nodes, found);
+}
+
+fn visit(
+    node: Node,
+    source: &str,
+    patterns: &TopicPatterns,
+    nodes: &[CodeNode],
+    found: &mut Vec<(usize, Wiring)>,
+) {
+    let mut handler = None;
+    let (role, call, topics) = if CALL_KINDS.contains(&node.kind()) {
+        let Some(callee) = callee(node, source) else {
+            return;
+        };
+        let (receiver, method) = match callee.rfind(['.', ':']) {
+            Some(at) => (callee[..at].trim_end_matches(':'), &callee[at + 1..]),
+            None => ("", callee.as_str()),
+        };
+        let Some(role) = patterns.call_role(method, receiver) else {
+            return;
+        };
+        let Some(arguments) = node.child_by_field_name("arguments").or_else(|| {
+            named_children(node)
+                .into_iter()
+                .find(|n| n.kind() == "arguments")
+        }) else {
+            return;
+        };
+        if role == Role::Subscribe {
+            handler = handler_offset(node, arguments, source, nodes);
+        }
+        (
+            role,
+            callee.clone(),
+            topics_in(arguments, source, TOPIC_KEYS),
+        )
+    } else if node.kind() == "annotation" {
+        let Some(name) = node.child_by_field_name("name") else {
+            return;
+        };
+        let name = last_segment(text(name, source));
+        let Some((role, key)) = patterns.annotation_role(name) else {
+            return;
+        };
+        let Some(arguments) = node.child_by_field_name("arguments") else {
+            return;
+        };
+        let keys = key.map_or(vec!["value"], |k| vec![k]);
+        (
+            role,
+            format!("@{}", name),
+            topics_in(arguments, source, &keys),
+        )
+    } else {
+        return;
+    };
+
+    let offset = handler.unwrap_or_else(|| decorated(node).map_or(node.start_byte(), name_offset));
+    for topic in topics {
+        let wiring = match role {
+            Role::Publish => Wiring::Publishes {
+                topic,
+                call: call.clone(),
+            },
+            Role::Subscribe => Wiring::Subscribes {
+                topic,
+                call: call.clone(),
+            },
+        };
+        found.push((offset, wiring));
+    }
+}
+
+/// Where the handler a subscribe call registers is defined in this file:
+/// a function passed by name (`emitter.on("saved", onSaved)`) or a method
+/// of the class around the call (`this.handle`, `self.handle`). `None`
+/// for an inline closure, whose body belongs to the node around the call,
+/// or a handler defined elsewhere.
+fn handler_offset(call: Node, arguments: Node, source: &str, nodes: &[CodeNode]) -> Option<usize> {
+    let function = named_children(arguments)
+        .into_iter()
+        .find_map(|argument| function_reference(unwrap_argument(argument), source))?;
+    let name = last_segment(&function);
+    let at = call.start_byte() as u32;
+    let class = nodes
+        .iter()
+        .filter(|n| n.kind == NodeKind::Class && n.byte_start <= at && at < n.byte_end)
+        .min_by_key(|n| n.byte_end - n.byte_start);
+    let in_class = |n: &CodeNode| {
+        class.is_some_and(|c| c.byte_start <= n.byte_start && n.byte_end <= c.byte_end)
+    };
+    nodes
+        .iter()
+        .filter(|n| matches!(n.kind, NodeKind::Function | NodeKind::Method) && n.name == name)
+        .filter(|n| {
+            !function.contains('.')
+                || n.qualified_name == function
+                || n.qualified_name.ends_with(&format!(".{}", function))
+        })
+        .min_by_key(|n| !in_class(n))
+        .map(|n| n.byte_start as usize)
+}
+
+/// Callee as written: `producer.send`, `nc.Publish`, `FutureRecord::to`.
+fn callee(call: Node, source: &str) -> Option<String> {
+    if let Some(function) = call.child_by_field_name("function") {
+        return Some(text(function, source).to_string());
+    }
+    let name = text(call.child_by_field_name("name")?, source);
+    Some(match call.child_by_field_name("object") {
+        Some(object) => format!("{}.{}", text(object, source), name),
+        None => name.to_string(),
+    })
+}
+
+/// The declaration a decorator or annotation holding `node` applies to.
+fn decorated(node: Node) -> Option<Node> {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        if matches!(n.kind(), "decorator" | "annotation") {
+            let declaration = n.parent()?;
+            return match declaration.kind() {
+                "modifiers" => declaration.parent(),
+                "decorated_definition" => declaration.child_by_field_name("definition"),
+                _ => Some(declaration),
+            };
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// Topic names in an argument list: the values under `keys`, or else the
+/// literals of the first argument, or of its `keys` when it is an object.
+fn topics_in(arguments: Node, source: &str, keys: &[&str]) -> Vec<String> {
+    let arguments = named_children(arguments);
+    let named: Vec<String> = arguments
+        .iter()
+        .filter_map(|&a| keyed(a, source, keys))
+        .flat_map(|value| literals(value, source))
+        .collect();
+    if !named.is_empty() {
+        return named;
+    }
+    let Some(&first) = arguments.first() else {
+        return Vec::new();
+    };
+    let first = unwrap_argument(first);
+    if first.kind() == "object" {
+        return named_children(first)
+            .into_iter()
+            .filter_map(|pair| keyed(pair, source, TOPIC_KEYS))
+            .flat_map(|value| literals(value, source))
+            .collect();
+    }
+    literals(first, source)
+}
+
+/// The value of a `key = value` argument or `key: value` pair whose key
+/// is one of `keys`.
+fn keyed<'t>(node: Node<'t>, source: &str, keys: &[&str]) -> Option<Node<'t>> {
+    let (key, value) = match node.kind() {
+        "keyword_argument" => ("name", "value"),
+        "element_value_pair" | "pair" => ("key", "value"),
+        _ => return None,
+    };
+    let key = text(node.child_by_field_name(key)?, source).trim_matches(['"', '\'']);
+    keys.iter()
+        .any(|k| k.eq_ignore_ascii_case(key))
+        .then(|| node.child_by_field_name(value))
+        .flatten()
+}
+
+/// The string literals an expression spells out: a literal, or the
+/// literals in an array or list of them.
+fn literals(node: Node, source: &str) -> Vec<String> {
+    match node.kind() {
+        "string"
+        | "string_literal"
+        | "interpreted_string_literal"
+        | "raw_string_literal"
+        | "template_string" => {
+            let interpolated = named_children(node).iter().any(|c| {
+                matches!(
+                    c.kind(),
+                    "interpolation" | "template_substitution" | "string_interpolation"
+                )
+            });
+            let value = text(node, source)
+                .trim_start_matches(|c: char| "rRbBuUfF@$".contains(c))
+                .trim_matches(['"', '\'', '`', '#']);
+            if interpolated || value.is_empty() {
+                Vec::new()
+            } else {
+                vec![value.to_string()]
+            }
+        }
+        "array"
+        | "list"
+        | "tuple"
+        | "array_expression"
+        | "reference_expression"
+        | "element_value_array_initializer"
+        | "array_initializer"
+        | "composite_literal"
+        | "literal_value"
+        | "literal_element" => named_children(node)
+            .into_iter()
+            .flat_map(|child| literals(child, source))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::get_parser;
+    use crate::parser::parse_source;
+
+    fn parse(file: &str, source: &str) -> Vec<CodeNode> {
+        let extension = Path::new(file).extension().unwrap().to_str().unwrap();
+        let parser = get_parser(extension).unwrap();
+        parse_source(source, file, parser.as_ref()).unwrap()
+    }
+
+    fn topics(nodes: &[CodeNode], name: &str) -> Vec<String> {
+        let node = nodes
+            .iter()
+            .find(|n| n.name == name)
+            .unwrap_or_else(|| panic!("no node {}", name));
+        node.wiring
+            .iter()
+            .filter_map(|w| match w {
+                Wiring::Publishes { topic, .. } => Some(format!("pub {}", topic)),
+                Wiring::Subscribes { topic, .. } => Some(format!("sub {}", topic)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_publish_and_subscribe_calls() {
+        let js = parse(
+            "src/orders.ts",
+            r#"
+async function placeOrder(order) {
+    await this.producer.send({ topic: "orders.created", messages: [order] });
+    emitter.emit(`order:${order.id}`);
+    client.send("not-a-topic");
+}
+function listen() {
+    emitter.on('orders.created', handle);
+    bus.subscribe(["orders.paid", "orders.shipped"]);
+}
+"#,
+        );
+        assert_eq!(topics(&js, "placeOrder"), ["pub orders.created"]);
+        assert_eq!(
+            topics(&js, "listen"),
+            [
+                "sub orders.created",
+                "sub orders.paid",
+                "sub orders.shipped"
+            ]
+        );
+
+        let java = parse(
+            "src/Billing.java",
+            r#"
+class Billing {
+    void charge(Order order) {
+        kafkaTemplate.send("payments.requested", order);
+    }
+
+    @KafkaListener(id = "billing", topics = {"orders.created", "orders.updated"})
+    void onOrder(Order order) {}
+}
+"#,
+        );
+        assert_eq!(topics(&java, "charge"), ["pub payments.requested"]);
+        assert_eq!(
+            topics(&java, "onOrder"),
+            ["sub orders.created", "sub orders.updated"]
+        );
+
+        let py = parse(
+            "app/worker.py",
+            r#"
+@broker.subscriber("payments.requested")
+async def pay(msg):
+    redis.publish(channel="payments.done", message=msg)
+"#,
+        );
+        assert_eq!(
+            topics(&py, "pay"),
+            ["sub payments.requested", "pub payments.done"]
+        );
+
+        let go = parse(
+            "svc/notify.go",
+            r#"package svc
+
+func Start(nc *nats.Conn) {
+    nc.Subscribe("payments.done", notify)
+    nc.Publish(`mail.queued`, nil)
+}
+"#,
+        );
+        assert_eq!(
+            topics(&go, "Start"),
+            ["sub payments.done", "pub mail.queued"]
+        );
+    }
+
+    #[test]
+    fn test_configured_patterns() {
+        let patterns = TopicPatterns::parse(
+            r#"
+builtin = false
+
+[[publish]]
+call = "dispatch"
+receiver = "events"
+
+[[subscribe]]
+annotation = "StreamListener"
+"#,
+        )
+        .unwrap();
+        assert!(TopicPatterns::parse("[[publish]]\nreceiver = \"bus\"").is_err());
+
+        let source = r#"
+class Shipping {
+    void ship() {
+        domainEvents.dispatch("shipped");
+        producer.publish("ignored");
+    }
+
+    @StreamListener("orders.paid")
+    void onPaid() {}
+}
+"#;
+        let parser = get_parser("java").unwrap();
+        let mut tree_parser = tree_sitter::Parser::new();
+        tree_parser.set_language(&parser.language()).unwrap();
+        let tree = tree_parser.parse(source, None).unwrap();
+        let mut nodes = parser.extract_nodes(&tree, source, "Shipping.java");
+        attach_with(&tree, source, &mut nodes, &patterns);
+
+        assert_eq!(topics(&nodes, "ship"), ["pub shipped"]);
+        assert_eq!(topics(&nodes, "onPaid"), ["sub orders.paid"]);
+    }
+
+    #[test]
+    fn test_subscriptions_link_to_their_handler() {
+        // Registered at module level, with no function around the call
+        let js = parse(
+            "src/listeners.js",
+            r#"
+emitter.on('saved', onSaved);
+function onSaved(event) { audit(event); }
+"#,
+        );
+        assert_eq!(topics(&js, "onSaved"), ["sub saved"]);
+
+        // Registered inside `setup`, handled elsewhere
+        let py = parse(
+            "app/consumer.py",
+            r#"
+def handle_order(message):
+    ship(message)
+
+def setup():
+    consumer.subscribe("orders", handle_order)
+"#,
+        );
+        assert_eq!(topics(&py, "handle_order"), ["sub orders"]);
+        assert!(topics(&py, "setup").is_empty());
+
+        let ts = parse(
+            "src/worker.ts",
+            r#"
+class Worker {
+    start() {
+        this.bus.subscribe("jobs", this.run);
+        this.bus.subscribe("pings", (m) => this.pong(m));
+    }
+    run(job) {}
+    pong(m) {}
+}
+"#,
+        );
+        assert_eq!(topics(&ts, "run"), ["sub jobs"]);
+        // An inline closure is part of the function that registers it
+        assert_eq!(topics(&ts, "start"), ["sub pings"]);
+    }
+
+    #[test]
+    fn test_each_project_keeps_its_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "function ship() {\n  domainEvents.dispatch(\"shipped\");\n}\n";
+        let mut files = Vec::new();
+        for project in ["custom", "plain"] {
+            let root = dir.path().join(project);
+            std::fs::create_dir_all(root.join(".arbor")).unwrap();
+            std::fs::write(root.join("ship.js"), source).unwrap();
+            files.push(root.join("ship.js").to_string_lossy().to_string());
+        }
+        std::fs::write(
+            dir.path().join("custom").join(TOPICS_CONFIG),
+            "[[publish]]\ncall = \"dispatch\"\n",
+        )
+        .unwrap();
+
+        // Loading the second project must not replace the first one's
+        load_topic_patterns(&dir.path().join("custom"));
+        load_topic_patterns(&dir.path().join("plain"));
+
+        assert_eq!(topics(&parse(&files[0], source), "ship"), ["pub shipped"]);
+        assert!(topics(&parse(&files[1], source), "ship").is_empty());
+    }
+}
@@ -0,0 +1,798 @@
+//! Runtime wiring extraction: callbacks and dependency injection.
+//!
+//! Some calls never appear as calls in the source. A function handed to
+//! `register(handler)` runs whenever the registry decides, and a service
+//! pulled from a DI container is picked by type at runtime. This pass
+//! records both in [`CodeNode::wiring`] so the graph crate can turn them
+//! into uncertain edges:
+//!
+//! | Wiring | Declarations |
+//! |--------|--------------|
+//! | Callbacks | a function passed by name: `register(handler)`, `.then(cb)`, `addEventListener("x", this.onX)`, `Worker::run` |
+//! | Angular/NestJS | `@Injectable`, constructor parameters of decorated classes, `@Inject(TOKEN)`, `{ provide: A, useClass: B }` |
+//! | Spring | `@Component`/`@Service`/... classes, `@Autowired` fields, constructor injection, `@Bean` methods |
+//! | Dart `get_it` | `registerSingleton<A>(B())`, `getIt<A>()`, `GetIt.I.get<A>()` |
+//!
+//! Wiring is recorded on the innermost function or method it appears in,
+//! or on the class for class-level declarations.
+
+use crate::hierarchy::{named_children, text, type_name};
+use crate::node::{CodeNode, NodeKind, Wiring};
+use tree_sitter::{Node, Tree};
+
+/// Call nodes across the supported grammars.
+pub(crate) const CALL_KINDS: &[&str] = &[
+    "call_expression",
+    "call",
+    "method_invocation",
+    "invocation_expression",
+];
+
+/// Decorators that put a TypeScript class under Angular or NestJS DI.
+const TS_DI_DECORATORS: &[&str] = &[
+    "Injectable",
+    "Component",
+    "Directive",
+    "Pipe",
+    "NgModule",
+    "Controller",
+    "Module",
+    "Resolver",
+    "Gateway",
+];
+
+/// Spring stereotypes: the class is a bean of its own type.
+const SPRING_COMPONENTS: &[&str] = &[
+    "Component",
+    "Service",
+    "Repository",
+    "Controller",
+    "RestController",
+    "Configuration",
+];
+
+const SPRING_INJECT: &[&str] = &["Autowired", "Inject", "Resource"];
+
+/// `get_it` registration calls.
+const GET_IT_REGISTRATIONS: &[&str] = &[
+    "registerSingleton",
+    "registerLazySingleton",
+    "registerFactory",
+    "registerSingletonAsync",
+    "registerFactoryAsync",
+];
+
+/// Names a `get_it` locator is conventionally bound to.
+const GET_IT_LOCATORS: &[&str] = &["getIt", "locator", "sl", "serviceLocator", "injector"];
+
+/// Records callbacks and DI declarations on the nodes they appear in.
+pub(crate) fn attach_wiring(tree: &Tree, source: &str, language: &str, nodes: &mut [CodeNode]) {
+    let visit: fn(Node, &str, &mut Vec<(usize, Wiring)>) = match language {
+        "rs" | "py" | "go" | "cs" => callbacks,
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => js_wiring,
+        "java" => java_wiring,
+        "dart" => dart_wiring,
+        _ => return,
+    };
+
+    let mut found = Vec::new();
+    let mut cursor = tree.walk();
+    'outer: loop {
+        visit(cursor.node(), source, &mut found);
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                break 'outer;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+
+    attach_found(nodes, found);
+}
+
+/// Records each wiring on the node enclosing its offset.
+pub(crate) fn attach_found(nodes: &mut [CodeNode], found: Vec<(usize, Wiring)>) {
+    for (offset, wiring) in found {
+        let Some(index) = enclosing(nodes, offset) else {
+            continue;
+        };
+        // Overloads share an id; keep every copy in step.
+        let id = nodes[index].id.clone();
+        for node in nodes.iter_mut().filter(|n| n.id == id) {
+            if !node.wiring.contains(&wiring) {
+                node.wiring.push(wiring.clone());
+            }
+        }
+    }
+}
+
+/// Index of the innermost function holding `offset`, or failing that the
+/// innermost declaration.
+fn enclosing(nodes: &[CodeNode], offset: usize) -> Option<usize> {
+    let offset = offset as u32;
+    let innermost = |kinds: &[NodeKind]| {
+        nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| {
+                kinds.contains(&n.kind) && n.byte_start <= offset && offset < n.byte_end
+            })
+            .min_by_key(|(_, n)| n.byte_end - n.byte_start)
+            .map(|(index, _)| index)
+    };
+    innermost(&[NodeKind::Function, NodeKind::Method, NodeKind::Constructor]).or_else(|| {
+        innermost(&[
+            NodeKind::Class,
+            NodeKind::Interface,
+            NodeKind::Struct,
+            NodeKind::Enum,
+            NodeKind::Variable,
+            NodeKind::Constant,
+        ])
+    })
+}
+
+pub(crate) fn last_segment(path: &str) -> &str {
+    path.rsplit(['.', ':']).next().unwrap_or(path)
+}
+
+/// Byte offset of a declaration's name, which lies inside its node but
+/// outside any of its members.
+pub(crate) fn name_offset(declaration: Node) -> usize {
+    declaration
+        .child_by_field_name("name")
+        .unwrap_or(declaration)
+        .start_byte()
+}
+
+// ============================================================================
+// Callbacks
+// ============================================================================
+
+/// Records each function passed by name to a call.
+fn callbacks(node: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    if !CALL_KINDS.contains(&node.kind()) {
+        return;
+    }
+    let Some(arguments) = node.child_by_field_name("arguments").or_else(|| {
+        named_children(node)
+            .into_iter()
+            .find(|n| n.kind() == "arguments")
+    }) else {
+        return;
+    };
+    let callee = node
+        .child_by_field_name("name")
+        .or_else(|| node.child_by_field_name("function"))
+        .map(|f| last_segment(text(f, source)))
+        .unwrap_or_default();
+    if callee.is_empty() {
+        return;
+    }
+    push_callbacks(node.start_byte(), callee, arguments, source, found);
+}
+
+fn push_callbacks(
+    offset: usize,
+    callee: &str,
+    arguments: Node,
+    source: &str,
+    found: &mut Vec<(usize, Wiring)>,
+) {
+    for argument in named_children(arguments) {
+        if let Some(function) = function_reference(unwrap_argument(argument), source) {
+            found.push((
+                offset,
+                Wiring::Callback {
+                    call: callee.to_string(),
+                    function,
+                },
+            ));
+        }
+    }
+}
+
+/// The expression inside an argument wrapper (C#, Dart, keyword arguments).
+pub(crate) fn unwrap_argument(argument: Node) -> Node {
+    match argument.kind() {
+        "keyword_argument" => argument.child_by_field_name("value").unwrap_or(argument),
+        "argument" | "named_argument" => named_children(argument)
+            .into_iter()
+            .last()
+            .unwrap_or(argument),
+        _ => argument,
+    }
+}
+
+/// Name of the function an argument refers to, when it is one: `handler`,
+/// `this.onClick`, `self.done`, `Self::run`, `Worker::run` (as
+/// `Worker.run`).
+pub(crate) fn function_reference(argument: Node, source: &str) -> Option<String> {
+    match argument.kind() {
+        "identifier" => Some(text(argument, source).to_string()),
+        "member_expression"
+        | "attribute"
+        | "field_expression"
+        | "member_access_expression"
+        | "selector_expression" => {
+            let parts = named_children(argument);
+            let (receiver, member) = (parts.first()?, parts.last()?);
+            matches!(text(*receiver, source), "this" | "self")
+                .then(|| text(*member, source).to_string())
+        }
+        "method_reference" | "scoped_identifier" => {
+            let parts = named_children(argument);
+            let (owner, member) = (parts.first()?, parts.last()?);
+            let member = text(*member, source);
+            if parts.len() < 2 || member == "new" {
+                return None;
+            }
+            match text(*owner, source) {
+                "this" | "self" | "Self" | "super" => Some(member.to_string()),
+                owner => Some(format!("{}.{}", last_segment(owner), member)),
+            }
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// JavaScript / TypeScript: Angular and NestJS
+// ============================================================================
+
+fn js_wiring(node: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    callbacks(node, source, found);
+    match node.kind() {
+        "class_declaration" | "abstract_class_declaration" | "class" => {
+            ts_class_injection(node, source, found)
+        }
+        "pair" => ts_provider(node, source, found),
+        _ => {}
+    }
+}
+
+/// Decorator names on a class, including those an `export` statement
+/// carries for it.
+fn ts_decorators(class: Node, source: &str) -> Vec<String> {
+    let mut holders = vec![class];
+    holders.extend(class.parent().filter(|p| p.kind() == "export_statement"));
+    holders
+        .into_iter()
+        .flat_map(named_children)
+        .filter(|n| n.kind() == "decorator")
+        .filter_map(|decorator| {
+            let expression = named_children(decorator).into_iter().next()?;
+            let name = match expression.kind() {
+                "call_expression" => text(expression.child_by_field_name("function")?, source),
+                _ => text(expression, source),
+            };
+            Some(last_segment(name).to_string())
+        })
+        .collect()
+}
+
+fn ts_container(source: &str) -> &'static str {
+    if source.contains("@nestjs/") {
+        "nestjs"
+    } else {
+        "angular"
+    }
+}
+
+/// `@Injectable()` classes provide themselves; decorated classes get their
+/// constructor parameters from the injector.
+fn ts_class_injection(class: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    let decorators = ts_decorators(class, source);
+    if !decorators
+        .iter()
+        .any(|d| TS_DI_DECORATORS.contains(&d.as_str()))
+    {
+        return;
+    }
+    let container = ts_container(source).to_string();
+    let offset = name_offset(class);
+    let own_name = class
+        .child_by_field_name("name")
+        .map(|n| text(n, source).to_string());
+    if let Some(own_name) = own_name.filter(|_| decorators.iter().any(|d| d == "Injectable")) {
+        found.push((
+            offset,
+            Wiring::Provides {
+                type_name: own_name,
+                implementation: None,
+                container: container.clone(),
+            },
+        ));
+    }
+
+    let Some(body) = class.child_by_field_name("body") else {
+        return;
+    };
+    let constructor = named_children(body).into_iter().find(|m| {
+        m.kind() == "method_definition"
+            && m.child_by_field_name("name")
+                .is_some_and(|n| text(n, source) == "constructor")
+    });
+    let Some(parameters) = constructor.and_then(|c| c.child_by_field_name("parameters")) else {
+        return;
+    };
+    for parameter in named_children(parameters) {
+        if let Some(type_name) = ts_injected_type(parameter, source) {
+            found.push((
+                offset,
+                Wiring::Injects {
+                    type_name,
+                    container: container.clone(),
+                },
+            ));
+        }
+    }
+}
+
+/// The token of `@Inject(TOKEN) p: T`, else the parameter's type.
+fn ts_injected_type(parameter: Node, source: &str) -> Option<String> {
+    let token = named_children(parameter)
+        .into_iter()
+        .filter(|n| n.kind() == "decorator")
+        .find_map(|decorator| {
+            let call = named_children(decorator).into_iter().next()?;
+            let function = call.child_by_field_name("function")?;
+            if text(function, source) != "Inject" {
+                return None;
+            }
+            let arguments = call.child_by_field_name("arguments")?;
+            let token = named_children(arguments).into_iter().next()?;
+            type_name(text(token, source))
+        });
+    token.or_else(|| {
+        let annotation = parameter.child_by_field_name("type")?;
+        let written = named_children(annotation).into_iter().next()?;
+        type_name(text(written, source))
+    })
+}
+
+/// `{ provide: Store, useClass: PgStore }` in a providers list.
+fn ts_provider(pair: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    let key = |p: Node| p.child_by_field_name("key").map(|k| text(k, source));
+    if key(pair) != Some("provide") {
+        return;
+    }
+    let Some(type_name) = pair
+        .child_by_field_name("value")
+        .and_then(|v| type_name(text(v, source)))
+    else {
+        return;
+    };
+    let implementation = pair.parent().and_then(|object| {
+        named_children(object)
+            .into_iter()
+            .filter(|p| matches!(key(*p), Some("useClass" | "useExisting" | "useFactory")))
+            .find_map(|p| type_name_of(p.child_by_field_name("value")?, source))
+    });
+
+    // Providers in a decorator belong to the decorated class.
+    let mut offset = pair.start_byte();
+    let mut ancestor = pair.parent();
+    while let Some(node) = ancestor {
+        if node.kind() == "decorator" {
+            let class = node.parent().and_then(|p| match p.kind() {
+                "export_statement" => p.child_by_field_name("declaration"),
+                _ => Some(p),
+            });
+            if let Some(class) = class {
+                offset = name_offset(class);
+            }
+            break;
+        }
+        ancestor = node.parent();
+    }
+
+    found.push((
+        offset,
+        Wiring::Provides {
+            implementation: implementation.filter(|i| *i != type_name),
+            type_name,
+            container: ts_container(source).to_string(),
+        },
+    ));
+}
+
+fn type_name_of(node: Node, source: &str) -> Option<String> {
+    type_name(text(node, source))
+}
+
+// ============================================================================
+// Java: Spring
+// ============================================================================
+
+fn java_wiring(node: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    callbacks(node, source, found);
+    match node.kind() {
+        "class_declaration" => spring_class(node, source, found),
+        "method_declaration" if java_annotations(node, source).iter().any(|a| a == "Bean") => {
+            if let Some(type_name) = node
+                .child_by_field_name("type")
+                .and_then(|t| type_name_of(t, source))
+            {
+                found.push((
+                    name_offset(node),
+                    Wiring::Provides {
+                        type_name,
+                        implementation: None,
+                        container: "spring".to_string(),
+                    },
+                ));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn java_annotations(declaration: Node, source: &str) -> Vec<String> {
+    named_children(declaration)
+        .into_iter()
+        .filter(|n| n.kind() == "modifiers")
+        .flat_map(named_children)
+        .filter(|a| matches!(a.kind(), "annotation" | "marker_annotation"))
+        .filter_map(|a| {
+            Some(last_segment(text(a.child_by_field_name("name")?, source)).to_string())
+        })
+        .collect()
+}
+
+/// Stereotyped classes are beans; `@Autowired` fields and the constructor
+/// parameters of beans are injected.
+fn spring_class(class: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    let offset = name_offset(class);
+    let component = java_annotations(class, source)
+        .iter()
+        .any(|a| SPRING_COMPONENTS.contains(&a.as_str()));
+    let mut injects = |type_node: Option<Node>| {
+        if let Some(type_name) = type_node.and_then(|t| type_name_of(t, source)) {
+            found.push((
+                offset,
+                Wiring::Injects {
+                    type_name,
+                    container: "spring".to_string(),
+                },
+            ));
+        }
+    };
+    let Some(body) = class.child_by_field_name("body") else {
+        return;
+    };
+    for member in named_children(body) {
+        let annotated = java_annotations(member, source)
+            .iter()
+            .any(|a| SPRING_INJECT.contains(&a.as_str()));
+        match member.kind() {
+            "field_declaration" if annotated => injects(member.child_by_field_name("type")),
+            "constructor_declaration" if component || annotated => {
+                for parameter in member
+                    .child_by_field_name("parameters")
+                    .map(named_children)
+                    .unwrap_or_default()
+                {
+                    injects(parameter.child_by_field_name("type"));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(name) = class.child_by_field_name("name").filter(|_| component) {
+        found.push((
+            offset,
+            Wiring::Provides {
+                type_name: text(name, source).to_string(),
+                implementation: None,
+                container: "spring".to_string(),
+            },
+        ));
+    }
+}
+
+// ============================================================================
+// Dart: get_it
+// ============================================================================
+
+fn dart_wiring(node: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    match node.kind() {
+        // `arguments` hangs off a selector that follows the callee.
+        "arguments" => {
+            let callee = node
+                .parent()
+                .filter(|p| p.kind() == "argument_part")
+                .and_then(|p| p.parent())
+                .and_then(|selector| selector.prev_named_sibling())
+                .map(|callee| last_segment(text(callee, source)).to_string());
+            if let Some(callee) = callee.filter(|c| !c.is_empty()) {
+                push_callbacks(dart_offset(node), &callee, node, source, found);
+            }
+        }
+        "identifier" => get_it(node, source, found),
+        _ => {}
+    }
+}
+
+/// Where to record wiring found at `node`. Extracted Dart functions span
+/// only their signature, so wiring inside a body is placed at the name
+/// in the signature before it.
+fn dart_offset(node: Node) -> usize {
+    let mut ancestor = node.parent();
+    while let Some(current) = ancestor {
+        if current.kind() == "function_body" {
+            let signature = current
+                .prev_named_sibling()
+                .or_else(|| current.parent()?.child_by_field_name("parameters"));
+            if let Some(signature) = signature {
+                return name_offset(signature);
+            }
+        }
+        ancestor = current.parent();
+    }
+    node.start_byte()
+}
+
+/// `get_it` calls, read from the text: the Dart grammar does not always
+/// parse generic calls such as `getIt.registerSingleton<Store>(PgStore())`.
+fn get_it(identifier: Node, source: &str, found: &mut Vec<(usize, Wiring)>) {
+    let name = text(identifier, source);
+    let Some((type_name, rest)) = generic_argument(&source[identifier.end_byte()..]) else {
+        return;
+    };
+    let Some(arguments) = rest.trim_start().strip_prefix('(') else {
+        return;
+    };
+    let receiver = receiver(source, identifier.start_byte());
+    let offset = dart_offset(identifier);
+    if GET_IT_REGISTRATIONS.contains(&name) {
+        let implementation = constructed_type(arguments).filter(|i| *i != type_name);
+        found.push((
+            offset,
+            Wiring::Provides {
+                type_name,
+                implementation,
+                container: "get_it".to_string(),
+            },
+        ));
+        return;
+    }
+    let lookup = match name {
+        "get" | "call" => {
+            GET_IT_LOCATORS.contains(&receiver) || matches!(receiver, "I" | "instance")
+        }
+        "I" | "instance" => receiver == "GetIt",
+        _ => receiver.is_empty() && GET_IT_LOCATORS.contains(&name),
+    };
+    if lookup {
+        found.push((
+            offset,
+            Wiring::Injects {
+                type_name,
+                container: "get_it".to_string(),
+            },
+        ));
+    }
+}
+
+/// `<T>` at the start of `text`: the type's name and what follows it.
+fn generic_argument(text: &str) -> Option<(String, &str)> {
+    let inner = text.strip_prefix('<')?;
+    let mut depth = 1;
+    let end = inner.char_indices().find_map(|(i, c)| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            _ => {}
+        }
+        (depth == 0).then_some(i)
+    })?;
+    Some((type_name(&inner[..end])?, &inner[end + 1..]))
+}
+
+/// The identifier before `.` preceding `offset`, or `""` when the name
+/// has no receiver.
+fn receiver(source: &str, offset: usize) -> &str {
+    let Some(before) = source[..offset].trim_end().strip_suffix('.') else {
+        return "";
+    };
+    let before = before.trim_end();
+    let start = before
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+    &before[start..]
+}
+
+/// The type constructed by a registration argument: `PgStore()` or
+/// `() => PgStore()`.
+fn constructed_type(arguments: &str) -> Option<String> {
+    let arguments = arguments.trim_start();
+    let arguments = arguments
+        .strip_prefix("()")
+        .and_then(|a| a.trim_start().strip_prefix("=>"))
+        .unwrap_or(arguments)
+        .trim_start();
+    let end = arguments.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    arguments[end..]
+        .starts_with('(')
+        .then(|| type_name(&arguments[..end]))
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::languages::get_parser;
+    use crate::node::{CodeNode, Wiring};
+    use crate::parser::parse_source;
+    use std::path::Path;
+
+    fn parse(file: &str, source: &str) -> Vec<CodeNode> {
+        let extension = Path::new(file).extension().unwrap().to_str().unwrap();
+        let parser = get_parser(extension).unwrap();
+        parse_source(source, file, parser.as_ref()).unwrap()
+    }
+
+    fn wiring<'a>(nodes: &'a [CodeNode], qualified_name: &str) -> &'a [Wiring] {
+        &nodes
+            .iter()
+            .find(|n| n.qualified_name == qualified_name)
+            .unwrap_or_else(|| panic!("no node {}", qualified_name))
+            .wiring
+    }
+
+    fn callback(call: &str, function: &str) -> Wiring {
+        Wiring::Callback {
+            call: call.to_string(),
+            function: function.to_string(),
+        }
+    }
+
+    fn injects(type_name: &str, container: &str) -> Wiring {
+        Wiring::Injects {
+            type_name: type_name.to_string(),
+            container: container.to_string(),
+        }
+    }
+
+    fn provides(type_name: &str, implementation: Option<&str>, container: &str) -> Wiring {
+        Wiring::Provides {
+            type_name: type_name.to_string(),
+            implementation: implementation.map(str::to_string),
+            container: container.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_functions_passed_as_arguments() {
+        let nodes = parse(
+            "app.js",
+            r#"
+function onClick(event) {}
+function setup(button, bus) {
+    button.addEventListener("click", onClick);
+    bus.register(this.onMessage, { retries: 3 });
+    fetch("/x").then(render);
+}
+"#,
+        );
+        assert_eq!(
+            wiring(&nodes, "setup"),
+            [
+                callback("addEventListener", "onClick"),
+                callback("register", "onMessage"),
+                callback("then", "render"),
+            ]
+        );
+
+        let nodes = parse(
+            "Jobs.java",
+            r#"
+class Jobs {
+    void start(Executor executor) {
+        executor.submit(this::handle);
+        executor.submit(Worker::run);
+    }
+}
+"#,
+        );
+        assert_eq!(
+            wiring(&nodes, "Jobs.start"),
+            [
+                callback("submit", "handle"),
+                callback("submit", "Worker.run")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_angular_and_spring_injection() {
+        let nodes = parse(
+            "checkout.ts",
+            r#"
+@Injectable()
+export class Checkout {
+    constructor(private store: Store, @Inject(CLOCK) clock: Clock) {}
+}
+
+@NgModule({
+    providers: [{ provide: Store, useClass: PgStore }],
+})
+export class AppModule {}
+"#,
+        );
+        assert_eq!(
+            wiring(&nodes, "Checkout"),
+            [
+                provides("Checkout", None, "angular"),
+                injects("Store", "angular"),
+                injects("CLOCK", "angular"),
+            ]
+        );
+        assert_eq!(
+            wiring(&nodes, "AppModule"),
+            [provides("Store", Some("PgStore"), "angular")]
+        );
+
+        let nodes = parse(
+            "Checkout.java",
+            r#"
+@Service
+class Checkout {
+    @Autowired
+    private Store store;
+
+    Checkout(Clock clock) {}
+}
+
+@Configuration
+class Beans {
+    @Bean
+    Store store() { return new PgStore(); }
+}
+"#,
+        );
+        assert_eq!(
+            wiring(&nodes, "Checkout"),
+            [
+                injects("Store", "spring"),
+                injects("Clock", "spring"),
+                provides("Checkout", None, "spring"),
+            ]
+        );
+        assert!(wiring(&nodes, "Beans.store").contains(&provides("Store", None, "spring")));
+    }
+
+    #[test]
+    fn test_get_it_registrations_and_lookups() {
+        let nodes = parse(
+            "locator.dart",
+            r#"
+void setup() {
+  getIt.registerSingleton<Store>(PgStore());
+  getIt.registerFactory<Clock>(() => SystemClock());
+}
+
+void checkout() {
+  final store = getIt<Store>();
+  final clock = GetIt.I.get<Clock>();
+}
+"#,
+        );
+        assert_eq!(
+            wiring(&nodes, "setup"),
+            [
+                provides("Store", Some("PgStore"), "get_it"),
+                provides("Clock", Some("SystemClock"), "get_it"),
+            ]
+        );
+        assert_eq!(
+            wiring(&nodes, "checkout"),
+            [injects("Store", "get_it"), injects("Clock", "get_it")]
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Approximate nearest-neighbor search over embedding vectors.
+//!
+//! A small HNSW (hierarchical navigable small world) graph: each vector is
+//! linked to its closest neighbors on a random number of layers, sparse at
+//! the top and dense at layer 0. A query greedily descends from the top
+//! layer's entry point, then runs a best-first search with `ef` candidates
+//! on layer 0. Vectors must be L2-normalized; similarity is the dot product.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Links per node on upper layers (layer 0 allows twice as many).
+const M: usize = 16;
+/// Candidate list size while inserting.
+const EF_CONSTRUCTION: usize = 100;
+/// Below this many vectors a linear scan is exact and just as fast.
+const EXACT_SCAN_LIMIT: usize = 512;
+
+/// Cosine similarity of two L2-normalized vectors.
+pub(crate) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Scales `vector` to unit length (zero vectors stay zero).
+pub(crate) fn normalize(vector: &mut [f32]) {
+    let norm = dot(vector, vector).sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// A node and its similarity to the query; ordered by similarity.
+#[derive(Clone, Copy, PartialEq)]
+struct Scored(f32, u32);
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .total_cmp(&other.0)
+            .then_with(|| other.1.cmp(&self.1))
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Hnsw {
+    vectors: Vec<Vec<f32>>,
+    /// `links[node][layer]` are the node's neighbors on that layer.
+    links: Vec<Vec<Vec<u32>>>,
+    entry: Option<u32>,
+    /// xorshift state for level assignment; fixed seed keeps builds reproducible.
+    rng: u64,
+}
+
+impl Hnsw {
+    pub(crate) fn new() -> Self {
+        Self {
+            rng: 0x9e37_79b9_7f4a_7c15,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn vector(&self, index: usize) -> &[f32] {
+        &self.vectors[index]
+    }
+
+    /// Adds a normalized vector; its index is the insertion order.
+    pub(crate) fn insert(&mut self, vector: Vec<f32>) {
+        let node = self.vectors.len() as u32;
+        let level = self.random_level();
+        self.vectors.push(vector);
+        self.links.push(vec![Vec::new(); level + 1]);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            return;
+        };
+
+        let top = self.links[entry as usize].len() - 1;
+        let query = self.vectors[node as usize].clone();
+        let mut nearest = Scored(dot(&query, &self.vectors[entry as usize]), entry);
+        for layer in (level + 1..=top).rev() {
+            nearest = self.greedy(&query, nearest, layer);
+        }
+
+        let mut entry_points = vec![nearest];
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(&query, &entry_points, EF_CONSTRUCTION, layer);
+            let max_links = if layer == 0 { M * 2 } else { M };
+            let neighbors: Vec<u32> = candidates.iter().take(M).map(|s| s.1).collect();
+            for &neighbor in &neighbors {
+                self.link(neighbor, node, layer, max_links);
+            }
+            self.links[node as usize][layer] = neighbors;
+            entry_points = candidates;
+        }
+
+        if level > top {
+            self.entry = Some(node);
+        }
+    }
+
+    /// The `k` most similar vectors to a normalized `query`, best first.
+    pub(crate) fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(usize, f32)> {
+        if self.vectors.len() <= EXACT_SCAN_LIMIT {
+            let mut all: Vec<Scored> = self
+                .vectors
+                .iter()
+                .enumerate()
+                .map(|(i, v)| Scored(dot(query, v), i as u32))
+                .collect();
+            all.sort_unstable_by(|a, b| b.cmp(a));
+            return all
+                .into_iter()
+                .take(k)
+                .map(|s| (s.1 as usize, s.0))
+                .collect();
+        }
+
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        let mut nearest = Scored(dot(query, &self.vectors[entry as usize]), entry);
+        for layer in (1..self.links[entry as usize].len()).rev() {
+            nearest = self.greedy(query, nearest, layer);
+        }
+        self.search_layer(query, &[nearest], ef.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|s| (s.1 as usize, s.0))
+            .collect()
+    }
+
+    /// Walks to the most similar neighbor until none improves.
+    fn greedy(&self, query: &[f32], mut current: Scored, layer: usize) -> Scored {
+        loop {
+            let mut improved = false;
+            for &neighbor in &self.links[current.1 as usize][layer] {
+                let score = dot(query, &self.vectors[neighbor as usize]);
+                if score > current.0 {
+                    current = Scored(score, neighbor);
+                    improved = true;
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search on one layer, returning up to `ef` nodes best first.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[Scored],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|s| s.1).collect();
+        let mut candidates: BinaryHeap<Scored> = entry_points.iter().copied().collect();
+        // Min-heap of the best `ef` found so far.
+        let mut results: BinaryHeap<std::cmp::Reverse<Scored>> =
+            entry_points.iter().map(|&s| std::cmp::Reverse(s)).collect();
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(candidate) = candidates.pop() {
+            let worst = results.peek().map(|r| r.0 .0).unwrap_or(f32::MIN);
+            if candidate.0 < worst && results.len() >= ef {
+                break;
+            }
+            let Some(neighbors) = self.links[candidate.1 as usize].get(layer) else {
+                continue;
+            };
+            for &neighbor in neighbors {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let score = dot(query, &self.vectors[neighbor as usize]);
+                let worst = results.peek().map(|r| r.0 .0).unwrap_or(f32::MIN);
+                if results.len() < ef || score > worst {
+                    candidates.push(Scored(score, neighbor));
+                    results.push(std::cmp::Reverse(Scored(score, neighbor)));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        let mut found: Vec<Scored> = results.into_iter().map(|r| r.0).collect();
+        found.sort_unstable_by(|a, b| b.cmp(a));
+        found
+    }
+
+    /// Adds `to` to `from`'s links, keeping only the closest `max_links`.
+    fn link(&mut self, from: u32, to: u32, layer: usize, max_links: usize) {
+        let links = &mut self.links[from as usize][layer];
+        links.push(to);
+        if links.len() <= max_links {
+            return;
+        }
+        let base = &self.vectors[from as usize];
+        let mut scored: Vec<Scored> = links
+            .iter()
+            .map(|&n| Scored(dot(base, &self.vectors[n as usize]), n))
+            .collect();
+        scored.sort_unstable_by(|a, b| b.cmp(a));
+        scored.truncate(max_links);
+        *links = scored.into_iter().map(|s| s.1).collect();
+    }
+
+    /// Draws a layer with probability decaying by 1/M per level.
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let uniform = (self.rng >> 11) as f64 / (1u64 << 53) as f64;
+        let level = -(uniform.max(f64::MIN_POSITIVE)).ln() / (M as f64).ln();
+        (level as usize).min(16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_vectors(count: usize, dims: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                let mut v: Vec<f32> = (0..dims)
+                    .map(|_| {
+                        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        ((state >> 33) as f32 / u32::MAX as f32) - 0.25
+                    })
+                    .collect();
+                normalize(&mut v);
+                v
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_index_is_exact() {
+        let mut index = Hnsw::new();
+        for v in random_vectors(50, 8) {
+            index.insert(v);
+        }
+        let query = index.vector(7).to_vec();
+        let hits = index.search(&query, 3, 10);
+        assert_eq!(hits[0].0, 7);
+        assert!((hits[0].1 - 1.0).abs() < 1e-5);
+        assert!(hits[1].1 <= hits[0].1 && hits[2].1 <= hits[1].1);
+    }
+
+    #[test]
+    fn test_hnsw_recall() {
+        let vectors = random_vectors(3000, 16);
+        let mut index = Hnsw::new();
+        for v in &vectors {
+            index.insert(v.clone());
+        }
+
+        let mut found = 0;
+        let queries = random_vectors(3020, 16).split_off(3000);
+        for query in &queries {
+            let mut exact: Vec<(usize, f32)> = vectors
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i, dot(query, v)))
+                .collect();
+            exact.sort_by(|a, b| b.1.total_cmp(&a.1));
+            let expected: HashSet<usize> = exact.iter().take(10).map(|e| e.0).collect();
+            found += index
+                .search(query, 10, 64)
+                .iter()
+                .filter(|hit| expected.contains(&hit.0))
+                .count();
+        }
+        let recall = found as f32 / (queries.len() * 10) as f32;
+        assert!(recall > 0.9, "recall@10 was {}", recall);
+    }
+}
@@ -3,15 +3,38 @@
 //! Two-pass construction:
 //!   1. Add all nodes — populates symbol table and import map
 //!   2. Resolve edges — uses import context to create accurate edges
+//!
+//! References may also resolve through user-declared aliases, and edges
+//! declared in `.arbor/edges.toml` or `arbor:` comments are added with
+//! [`Provenance::UserDeclared`] (see [`crate::declared`]).
+//!
+//! After resolution, generated code (`*.pb.go`, `*_pb2.py`, ...) is linked
+//! back to the `.proto`/`.thrift` definitions it came from, types to their
+//! supertypes, and calls through interfaces to the implementations they
+//! may dispatch to. Callbacks and dependency injections become uncertain
+//! edges last, since injected types resolve through the hierarchy.
 
-use crate::edge::{Edge, EdgeKind};
+use crate::codegen::generated_links;
+use crate::declared::{
+    edge_kind, resolve_symbol, Declarations, UnresolvedDeclaration, EDGES_CONFIG,
+};
+use crate::edge::{Edge, EdgeKind, Provenance};
 use crate::graph::{ArborGraph, NodeId};
+use crate::hierarchy::{dispatch_edges, supertype_links};
 use crate::symbol_table::SymbolTable;
+use crate::topics::topic_links;
+use crate::wiring::wiring_edges;
 use arbor_core::{CodeNode, NodeKind};
+use petgraph::visit::EdgeRef;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::warn;
 
+/// Confidence in a generated-code link found by naming convention.
+const GENERATED_CODE: f32 = 0.9;
+/// Confidence in a publish or subscribe call recognized by its name.
+const MESSAGE_TOPIC: f32 = 0.9;
+
 /// Builds an ArborGraph from parsed code nodes.
 pub struct GraphBuilder {
     graph: ArborGraph,
@@ -39,6 +62,9 @@ pub struct GraphBuilder {
     /// those calls at parse time, this is reserved for future use when we add
     /// a richer call-site representation.
     namespace_imports: HashMap<String, HashMap<String, String>>,
+
+    /// Edges and aliases from `.arbor/edges.toml`.
+    declarations: Declarations,
 }
 
 impl Default for GraphBuilder {
@@ -55,6 +81,7 @@ impl GraphBuilder {
             name_to_id: HashMap::new(),
             import_map: HashMap::new(),
             namespace_imports: HashMap::new(),
+            declarations: Declarations::default(),
         }
     }
 
@@ -94,6 +121,11 @@ impl GraphBuilder {
                 // was attributed to the import node itself.
                 continue;
             }
+            // Topic nodes are rebuilt from their publishers and subscribers;
+            // one kept from an earlier graph would be a duplicate.
+            if node.kind == NodeKind::Topic {
+                continue;
+            }
 
             let id_str = node.id.clone();
             let name = node.name.clone();
@@ -112,34 +144,81 @@ impl GraphBuilder {
         }
     }
 
+    /// Adds edges and aliases declared in `.arbor/edges.toml`.
+    pub fn add_declarations(&mut self, declarations: Declarations) {
+        self.declarations.edges.extend(declarations.edges);
+        self.declarations.aliases.extend(declarations.aliases);
+    }
+
     /// Resolves references into actual graph edges.
     ///
     /// Resolution order for each reference `R` from file `F`:
-    ///   1. Exact FQN match in symbol table
+    ///   0. A user-declared alias named `R`
+    ///   1. Exact FQN match in symbol table, preferring a definition in `F`,
+    ///      then the only one in `F`'s directory
     ///   2. Context-aware suffix match (prefers same directory, avoids ambiguity)
     ///   3. Import-validated match — if R is in F's import map AND a match was found
     ///      in step 2 for a different file, we skip it to avoid wrong-module edges
     ///
+    /// Each edge records its [`Provenance`]: an exact FQN match, or for a
+    /// match on the name alone, whether the file imports it, the target
+    /// is in the same directory, or it is the only candidate in the repo.
+    /// When several symbols could be meant and neither the file nor its
+    /// directory settles it, the pick is a [`Provenance::Heuristic`] whose
+    /// confidence shrinks with the number of candidates.
+    ///
+    /// Type references resolve the same way but become
+    /// [`EdgeKind::UsesType`] edges instead of calls.
+    ///
     /// References that cannot be resolved are silently dropped (they are external/stdlib
     /// symbols with no definition in this repository).
     pub fn resolve_edges(&mut self) {
-        let mut edges_to_add: Vec<(NodeId, NodeId, String)> = Vec::new();
+        let mut edges_to_add: Vec<(NodeId, NodeId, Edge)> = Vec::new();
 
         let node_indices: Vec<NodeId> = self.graph.node_indexes().collect();
+        let aliases = self.aliases();
 
         for from_idx in node_indices {
             let (references, from_file) = {
                 let node = self.graph.get(from_idx).unwrap();
-                (node.references.clone(), PathBuf::from(&node.file))
+                let calls = node.references.iter().map(|r| (r, EdgeKind::Calls));
+                let types = node.type_references.iter().map(|r| (r, EdgeKind::UsesType));
+                let references: Vec<(String, EdgeKind)> =
+                    calls.chain(types).map(|(r, k)| (r.clone(), k)).collect();
+                (references, PathBuf::from(&node.file))
             };
 
             let from_file_str = from_file.to_string_lossy().to_string();
 
-            for reference in references {
-                // 1. Exact FQN match
-                if let Some(to_idx) = self.symbol_table.resolve(&reference) {
+            for (reference, kind) in references {
+                // 0. User-declared alias
+                if let Some(&to_idx) = aliases.get(&reference) {
                     if from_idx != to_idx {
-                        edges_to_add.push((from_idx, to_idx, reference.clone()));
+                        edges_to_add.push((
+                            from_idx,
+                            to_idx,
+                            Edge::new(kind)
+                                .with_provenance(Provenance::UserDeclared)
+                                .with_reference(&reference),
+                        ));
+                    }
+                    continue;
+                }
+
+                // 1. Exact FQN match, in this file or directory first
+                let exact = self.symbol_table.resolve_from(&reference, &from_file);
+                if let Some(to_idx) = exact {
+                    if from_idx != to_idx {
+                        let (provenance, confidence) =
+                            self.match_provenance(&reference, &from_file, to_idx);
+                        edges_to_add.push((
+                            from_idx,
+                            to_idx,
+                            Edge::new(kind)
+                                .with_provenance(provenance)
+                                .with_confidence(confidence)
+                                .with_reference(&reference),
+                        ));
                     }
                     continue;
                 }
@@ -164,17 +243,16 @@ impl GraphBuilder {
                     // matched node is in a completely different part of the tree.
                     // This prevents `validate()` in file X from linking to `validate` in
                     // an unrelated module when `validate` is not imported.
-                    if let Some(file_imports) = self.import_map.get(&from_file_str) {
+                    let file_imports = self.import_map.get(&from_file_str);
+                    let to_file = PathBuf::from(&self.graph.get(to_idx).unwrap().file);
+                    let same_dir = to_file.parent() == from_file.parent();
+                    if let Some(file_imports) = file_imports {
                         if !file_imports.is_empty()
                             && !reference.contains('.')
                             && !file_imports.contains_key(&reference)
                         {
                             // Not imported explicitly — only allow if in same file or same dir
-                            let to_node = self.graph.get(to_idx).unwrap();
-                            let to_file = PathBuf::from(&to_node.file);
-                            let same_file = to_file == from_file;
-                            let same_dir = to_file.parent() == from_file.parent();
-                            if !same_file && !same_dir {
+                            if to_file != from_file && !same_dir {
                                 warn!(
                                     "Skipping unimported cross-module reference '{}' in {} → {}",
                                     reference,
@@ -186,7 +264,16 @@ impl GraphBuilder {
                         }
                     }
 
-                    edges_to_add.push((from_idx, to_idx, reference.clone()));
+                    let (provenance, confidence) =
+                        self.match_provenance(&reference, &from_file, to_idx);
+                    edges_to_add.push((
+                        from_idx,
+                        to_idx,
+                        Edge::new(kind)
+                            .with_provenance(provenance)
+                            .with_confidence(confidence)
+                            .with_reference(&reference),
+                    ));
                     continue;
                 }
 
@@ -200,9 +287,243 @@ impl GraphBuilder {
             }
         }
 
-        for (from_id, to_id, _) in edges_to_add {
-            self.graph
-                .add_edge(from_id, to_id, Edge::new(EdgeKind::Calls));
+        for (from_id, to_id, edge) in edges_to_add {
+            self.graph.add_edge(from_id, to_id, edge);
+        }
+
+        self.link_declared();
+        self.link_generated_code();
+        self.link_hierarchy();
+        self.link_wiring();
+        self.link_topics();
+    }
+
+    /// Provenance and confidence of `reference` from `from_file` resolving
+    /// to `to_idx`, judged by every symbol the reference could mean.
+    fn match_provenance(
+        &self,
+        reference: &str,
+        from_file: &Path,
+        to_idx: NodeId,
+    ) -> (Provenance, f32) {
+        let to_file = Path::new(&self.graph.get(to_idx).unwrap().file);
+        if to_file == from_file {
+            return (Provenance::ExactFqn, 1.0);
+        }
+
+        let from_dir = from_file.parent();
+        // By node id: copies of one symbol (overloads) are one candidate.
+        let mut candidates: Vec<(&str, bool)> = self
+            .symbol_table
+            .matches(reference)
+            .filter_map(|(id, file)| {
+                let node = self.graph.get(id)?;
+                Some((node.id.as_str(), file.parent() == from_dir))
+            })
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        let same_dir = to_file.parent() == from_dir;
+        let unique_in_dir = same_dir && candidates.iter().filter(|(_, same)| *same).count() == 1;
+
+        let provenance = if candidates.len() <= 1 {
+            if reference.contains(['.', ':']) {
+                Provenance::ExactFqn
+            } else {
+                self.name_match(reference, &from_file.to_string_lossy(), same_dir)
+            }
+        } else if unique_in_dir {
+            self.name_match(reference, &from_file.to_string_lossy(), true)
+        } else {
+            // Several symbols share the name and nothing tells them apart
+            let confidence = Provenance::Heuristic.confidence() / candidates.len() as f32;
+            return (Provenance::Heuristic, confidence);
+        };
+        (provenance, provenance.confidence())
+    }
+
+    /// Provenance of a reference matched by name rather than qualified
+    /// name: `helper` imported by name, or `utils.helper` through an
+    /// imported or namespace-imported `utils`, else where the target is.
+    /// Only called once the target is known to be the one candidate.
+    fn name_match(&self, reference: &str, file: &str, same_dir: bool) -> Provenance {
+        let head = reference.split(['.', ':']).next().unwrap_or(reference);
+        let imported = self
+            .import_map
+            .get(file)
+            .is_some_and(|i| i.contains_key(reference) || i.contains_key(head))
+            || self
+                .namespace_imports
+                .get(file)
+                .is_some_and(|i| i.contains_key(head));
+        if imported {
+            Provenance::ImportMap
+        } else if same_dir {
+            Provenance::SameDirSuffix
+        } else {
+            Provenance::GlobalSuffix
+        }
+    }
+
+    /// Alias name → node, from `[[alias]]` entries and `arbor: alias`
+    /// comments. Aliases that do not resolve are recorded on the graph.
+    fn aliases(&mut self) -> HashMap<String, NodeId> {
+        let mut aliases = HashMap::new();
+        let mut unresolved = Vec::new();
+        for alias in &self.declarations.aliases {
+            match resolve_symbol(&self.graph, &alias.symbol, None) {
+                Ok(to) => {
+                    aliases.insert(alias.name.clone(), to);
+                }
+                Err(problem) => unresolved.push(UnresolvedDeclaration {
+                    source: EDGES_CONFIG.to_string(),
+                    declaration: format!("alias {} = {}", alias.name, alias.symbol),
+                    problem,
+                }),
+            }
+        }
+        for index in self.graph.node_indexes() {
+            for declaration in &self.graph.graph[index].declared {
+                if declaration.relation == "alias" {
+                    aliases.insert(declaration.target.clone(), index);
+                }
+            }
+        }
+        self.graph.add_unresolved_declarations(unresolved);
+        aliases
+    }
+
+    /// Adds the edges declared in `arbor:` comments and `[[edge]]`
+    /// entries. A declared edge the code already has becomes
+    /// user-declared, with full confidence.
+    fn link_declared(&mut self) {
+        let mut edges = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for index in self.graph.node_indexes() {
+            let node = &self.graph.graph[index];
+            for declaration in &node.declared {
+                if declaration.relation == "alias" {
+                    continue;
+                }
+                let source = format!("{}:{}", node.file, declaration.line);
+                let written = format!("{} {}", declaration.relation, declaration.target);
+                let Some(kind) = edge_kind(&declaration.relation) else {
+                    unresolved.push(UnresolvedDeclaration {
+                        source,
+                        declaration: written,
+                        problem: format!("unknown relation '{}'", declaration.relation),
+                    });
+                    continue;
+                };
+                match resolve_symbol(&self.graph, &declaration.target, Some(&node.file)) {
+                    Ok(to) => edges.push((
+                        index,
+                        to,
+                        Edge::with_location(kind, &node.file, declaration.line)
+                            .with_provenance(Provenance::UserDeclared)
+                            .with_reference(&declaration.target),
+                    )),
+                    Err(problem) => unresolved.push(UnresolvedDeclaration {
+                        source,
+                        declaration: written,
+                        problem,
+                    }),
+                }
+            }
+        }
+
+        for declared in &self.declarations.edges {
+            let written = format!("{} {} {}", declared.from, declared.kind, declared.to);
+            let from = resolve_symbol(&self.graph, &declared.from, None);
+            let to = resolve_symbol(&self.graph, &declared.to, None);
+            match (from, to) {
+                (Ok(from), Ok(to)) => {
+                    let mut edge = Edge::new(declared.kind)
+                        .with_provenance(Provenance::UserDeclared)
+                        .with_reference(&declared.to);
+                    edge.file = Some(EDGES_CONFIG.to_string());
+                    edges.push((from, to, edge));
+                }
+                (Err(problem), _) | (_, Err(problem)) => unresolved.push(UnresolvedDeclaration {
+                    source: EDGES_CONFIG.to_string(),
+                    declaration: written,
+                    problem,
+                }),
+            }
+        }
+
+        for (from, to, edge) in edges {
+            let existing = self
+                .graph
+                .graph
+                .edges_connecting(from, to)
+                .find(|e| e.weight().kind == edge.kind)
+                .map(|e| e.id());
+            match existing {
+                Some(id) => {
+                    let weight = &mut self.graph.graph[id];
+                    weight.provenance = Provenance::UserDeclared;
+                    weight.confidence = Provenance::UserDeclared.confidence();
+                }
+                None if from != to => self.graph.add_edge(from, to, edge),
+                None => {}
+            }
+        }
+        self.graph.add_unresolved_declarations(unresolved);
+    }
+
+    /// Adds `GeneratedFrom` edges from generated symbols to their IDL
+    /// definitions so impact analysis can cross RPC boundaries.
+    fn link_generated_code(&mut self) {
+        for (generated, definition) in generated_links(&self.graph) {
+            // Linked by naming convention; protoc and thrift are consistent.
+            let edge = Edge::new(EdgeKind::GeneratedFrom)
+                .with_provenance(Provenance::Heuristic)
+                .with_confidence(GENERATED_CODE);
+            self.graph.add_edge(generated, definition, edge);
+        }
+    }
+
+    /// Adds `Extends`/`Implements` edges, then records which overrides each
+    /// call through an interface or base class may dispatch to.
+    fn link_hierarchy(&mut self) {
+        for (from, to, edge) in supertype_links(&self.graph) {
+            self.graph.add_edge(from, to, edge);
+        }
+        let dispatch = dispatch_edges(&self.graph);
+        self.graph.add_uncertain_edges(dispatch);
+    }
+
+    /// Records callbacks and dependency injections as uncertain edges
+    /// between the registering or consuming node and its target.
+    fn link_wiring(&mut self) {
+        let wiring = wiring_edges(&self.graph);
+        self.graph.add_uncertain_edges(wiring);
+    }
+
+    /// Adds a node per message topic, with `Publishes` edges from the
+    /// nodes that publish to it and `Subscribes` edges to the nodes that
+    /// subscribe to it.
+    fn link_topics(&mut self) {
+        for topic in topic_links(&self.graph) {
+            let edge = |kind: EdgeKind, call: &str| {
+                Edge::new(kind)
+                    .with_provenance(Provenance::Heuristic)
+                    .with_confidence(MESSAGE_TOPIC)
+                    .with_reference(call)
+            };
+            let publishers = topic.publishers;
+            let subscribers = topic.subscribers;
+            let id = self.graph.add_node(topic.node);
+            for (publisher, call) in publishers {
+                self.graph
+                    .add_edge(publisher, id, edge(EdgeKind::Publishes, &call));
+            }
+            for (subscriber, call) in subscribers {
+                self.graph
+                    .add_edge(id, subscriber, edge(EdgeKind::Subscribes, &call));
+            }
         }
     }
 
@@ -329,6 +650,201 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_edges_record_provenance() {
+        let import = CodeNode::new(
+            "../billing",
+            "../billing",
+            NodeKind::Import,
+            "src/app/main.ts",
+        )
+        .with_references(vec!["charge".to_string()]);
+        let main = CodeNode::new("main", "main", NodeKind::Function, "src/app/main.ts")
+            .with_references(vec![
+                "Cart.total".to_string(),
+                "charge".to_string(),
+                "helper".to_string(),
+            ]);
+        let cli = CodeNode::new("cli", "cli", NodeKind::Function, "bin/cli.ts")
+            .with_references(vec!["format".to_string()]);
+        let total = CodeNode::new("total", "Cart.total", NodeKind::Method, "src/cart.ts");
+        let charge = CodeNode::new(
+            "charge",
+            "billing.charge",
+            NodeKind::Function,
+            "src/billing/index.ts",
+        );
+        let helper = CodeNode::new(
+            "helper",
+            "util.helper",
+            NodeKind::Function,
+            "src/app/util.ts",
+        );
+        let format = CodeNode::new("format", "fmt.format", NodeKind::Function, "lib/fmt.ts");
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![import, main, cli, total, charge, helper, format]);
+        let graph = builder.build();
+
+        let mut edges: Vec<(String, Provenance, f32)> = graph
+            .edges()
+            .map(|e| (e.reference.clone().unwrap(), e.provenance, e.confidence))
+            .collect();
+        edges.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            edges,
+            [
+                ("Cart.total".to_string(), Provenance::ExactFqn, 1.0),
+                ("charge".to_string(), Provenance::ImportMap, 0.95),
+                ("format".to_string(), Provenance::GlobalSuffix, 0.7),
+                ("helper".to_string(), Provenance::SameDirSuffix, 0.85),
+            ]
+        );
+
+        let names = |query: &str| -> Vec<String> {
+            graph.search(query).iter().map(|n| n.name.clone()).collect()
+        };
+        assert_eq!(names("via:global_suffix"), ["cli"]);
+        assert_eq!(names("via:import-map,exact_fqn"), ["main"]);
+        assert!(names("via:heuristic").is_empty());
+    }
+
+    #[test]
+    fn test_copies_of_one_symbol_are_one_candidate() {
+        let import = CodeNode::new("./store", "./store", NodeKind::Import, "src/app/main.ts")
+            .with_references(vec!["save".to_string()]);
+        let main = CodeNode::new("main", "main", NodeKind::Function, "src/app/main.ts")
+            .with_references(vec!["save".to_string()]);
+        let save = CodeNode::new("save", "save", NodeKind::Function, "src/store.ts");
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![import, main, save.clone(), save]);
+        let graph = builder.build();
+
+        let edges: Vec<(String, Provenance, f32)> = graph
+            .edges()
+            .map(|e| (e.reference.clone().unwrap(), e.provenance, e.confidence))
+            .collect();
+        assert!(!edges.is_empty());
+        for edge in edges {
+            assert_eq!(edge, ("save".to_string(), Provenance::ImportMap, 0.95));
+        }
+    }
+
+    #[test]
+    fn test_same_named_symbols_are_not_confident() {
+        let function = |name: &str, file: &str| CodeNode::new(name, name, NodeKind::Function, file);
+        let build = function("main", "tools/build.zig")
+            .with_references(vec!["helper".to_string(), "orders.Order".to_string()]);
+        let page = function("render", "web/page.php").with_references(vec!["helper".to_string()]);
+        let php_helper = function("helper", "web/helper.php");
+        let go_helper = function("helper", "lib/helper.go");
+        let message = CodeNode::new(
+            "Order",
+            "orders.Order",
+            NodeKind::Struct,
+            "api/orders.proto",
+        );
+        let generated = CodeNode::new(
+            "Order",
+            "orders.Order",
+            NodeKind::Struct,
+            "gen/orders.pb.go",
+        );
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![build, page, php_helper, go_helper, message, generated]);
+        let graph = builder.build();
+
+        let mut edges: Vec<(String, Provenance, f32)> = graph
+            .edges()
+            .filter(|e| e.kind == EdgeKind::Calls)
+            .map(|e| (e.reference.clone().unwrap(), e.provenance, e.confidence))
+            .collect();
+        edges.sort_by(|a, b| a.0.cmp(&b.0).then(a.2.total_cmp(&b.2)));
+        assert_eq!(
+            edges,
+            [
+                // From `main`: two definitions, neither near the caller
+                ("helper".to_string(), Provenance::Heuristic, 0.3),
+                // From `render`: the directory settles it
+                ("helper".to_string(), Provenance::SameDirSuffix, 0.85),
+                ("orders.Order".to_string(), Provenance::Heuristic, 0.3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declared_edges_and_aliases() {
+        use arbor_core::Declaration;
+
+        let declare = |relation: &str, target: &str| Declaration {
+            relation: relation.to_string(),
+            target: target.to_string(),
+            line: 3,
+        };
+        let mut checkout =
+            CodeNode::new("checkout", "checkout", NodeKind::Function, "src/orders.rs")
+                .with_references(vec!["charge_handler".to_string()]);
+        checkout.declared = vec![
+            declare("calls", "billing::refund"),
+            declare("calls", "gone"),
+        ];
+        let mut charge = CodeNode::new("charge", "charge", NodeKind::Function, "src/billing.rs");
+        charge.declared = vec![declare("alias", "charge_handler")];
+        let refund = CodeNode::new("refund", "refund", NodeKind::Function, "src/billing.rs");
+        let worker = CodeNode::new("run", "Worker.run", NodeKind::Method, "src/jobs/worker.rs");
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![checkout, charge, refund, worker]);
+        builder.add_declarations(
+            Declarations::parse(
+                r#"
+[[edge]]
+from = "jobs::Worker::run"
+to = "orders::checkout"
+
+[[alias]]
+name = "pay"
+symbol = "billing::missing"
+"#,
+            )
+            .unwrap(),
+        );
+        let graph = builder.build();
+
+        let mut edges: Vec<(String, String, Provenance)> = graph
+            .export_edges()
+            .into_iter()
+            .map(|e| {
+                let name = |id: &str| graph.get_by_id(id).unwrap().name.clone();
+                (name(&e.source), name(&e.target), e.provenance)
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.0, &a.1).cmp(&(&b.0, &b.1)));
+        assert_eq!(
+            edges,
+            [
+                ("checkout".into(), "charge".into(), Provenance::UserDeclared),
+                ("checkout".into(), "refund".into(), Provenance::UserDeclared),
+                ("run".into(), "checkout".into(), Provenance::UserDeclared),
+            ]
+        );
+
+        let unresolved: Vec<String> = graph
+            .unresolved_declarations()
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        assert_eq!(
+            unresolved,
+            [
+                ".arbor/edges.toml: alias pay = billing::missing (no symbol matches 'billing::missing')",
+                "src/orders.rs:3: calls gone (no symbol matches 'gone')",
+            ]
+        );
+    }
+
     #[test]
     fn test_build_empty_graph() {
         let builder = GraphBuilder::new();
@@ -367,4 +883,146 @@ mod tests {
             "static call must resolve to the qualified class, not a same-named sibling"
         );
     }
+
+    #[test]
+    fn test_generated_code_links_back_to_proto() {
+        let mut b = GraphBuilder::new();
+        let message = CodeNode::new("User", "User", NodeKind::Struct, "proto/users.proto");
+        let service = CodeNode::new(
+            "UserService",
+            "UserService",
+            NodeKind::Interface,
+            "proto/users.proto",
+        );
+        let rpc = CodeNode::new(
+            "GetUser",
+            "UserService.GetUser",
+            NodeKind::Method,
+            "proto/users.proto",
+        );
+        let generated_struct = CodeNode::new("User", "User", NodeKind::Struct, "gen/users.pb.go");
+        let generated_client = CodeNode::new(
+            "UserServiceClient",
+            "UserServiceClient",
+            NodeKind::Interface,
+            "gen/users_grpc.pb.go",
+        );
+        let generated_method = CodeNode::new(
+            "GetUser",
+            "userServiceClient.GetUser",
+            NodeKind::Method,
+            "gen/users_grpc.pb.go",
+        );
+        // Hand-written code with a same-named type must not be linked.
+        let handwritten = CodeNode::new("User", "User", NodeKind::Struct, "internal/user.go");
+        let caller = CodeNode::new(
+            "loadProfile",
+            "loadProfile",
+            NodeKind::Function,
+            "gen/app.go",
+        )
+        .with_references(vec!["userServiceClient.GetUser".to_string()]);
+
+        b.add_nodes(vec![message, service, rpc]);
+        b.add_nodes(vec![generated_struct, generated_client, generated_method]);
+        b.add_nodes(vec![handwritten, caller]);
+        let graph = b.build();
+
+        let generated_from: Vec<(String, String)> = graph
+            .export_edges()
+            .into_iter()
+            .filter(|e| e.kind == EdgeKind::GeneratedFrom)
+            .map(|e| {
+                (
+                    graph.get_by_id(&e.source).unwrap().file.clone(),
+                    graph.get_by_id(&e.target).unwrap().qualified_name.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(generated_from.len(), 3);
+        assert!(generated_from.contains(&("gen/users.pb.go".to_string(), "User".to_string())));
+        assert!(generated_from.contains(&(
+            "gen/users_grpc.pb.go".to_string(),
+            "UserService".to_string()
+        )));
+        assert!(generated_from.contains(&(
+            "gen/users_grpc.pb.go".to_string(),
+            "UserService.GetUser".to_string()
+        )));
+
+        // Impact on the rpc crosses into code that calls the generated client.
+        let rpc_idx = graph
+            .node_indexes()
+            .find(|&i| graph.get(i).unwrap().qualified_name == "UserService.GetUser")
+            .unwrap();
+        let impact = graph.analyze_impact(rpc_idx, 3);
+        assert!(impact
+            .upstream
+            .iter()
+            .any(|n| n.node_info.name == "loadProfile"));
+    }
+
+    #[test]
+    fn test_proto_type_uses_stay_in_the_proto_file() {
+        let proto = r#"syntax = "proto3";
+package orders.v1;
+
+service OrderService {
+  rpc CreateOrder(CreateOrderRequest) returns (Order);
+}
+
+message CreateOrderRequest { Order order = 1; }
+message Order { string id = 1; }
+"#;
+        let generated = r#"package ordersv1
+
+type CreateOrderRequest struct {
+	Order *Order
+}
+
+type Order struct {
+	Id string
+}
+"#;
+        let parse = |source: &str, file: &str, extension: &str| {
+            let parser = arbor_core::languages::get_parser(extension).unwrap();
+            arbor_core::parse_source(source, file, parser.as_ref()).unwrap()
+        };
+
+        let mut b = GraphBuilder::new();
+        b.add_nodes(parse(proto, "api/orders.proto", "proto"));
+        b.add_nodes(parse(generated, "api/orders.pb.go", "go"));
+        let graph = b.build();
+
+        let rpc = graph.find_by_name("CreateOrder")[0];
+        let uses: Vec<(String, String)> = graph
+            .export_edges()
+            .into_iter()
+            .filter(|e| e.source == rpc.id)
+            .map(|e| {
+                let target = graph.get_by_id(&e.target).unwrap();
+                assert_eq!(e.kind, EdgeKind::UsesType);
+                (target.qualified_name.clone(), target.file.clone())
+            })
+            .collect();
+        assert_eq!(uses.len(), 2);
+        assert!(uses.contains(&(
+            "orders.v1.CreateOrderRequest".to_string(),
+            "api/orders.proto".to_string()
+        )));
+        assert!(uses.contains(&(
+            "orders.v1.Order".to_string(),
+            "api/orders.proto".to_string()
+        )));
+
+        // The generated structs still link back to their messages.
+        let generated_from: Vec<String> = graph
+            .export_edges()
+            .into_iter()
+            .filter(|e| e.kind == EdgeKind::GeneratedFrom)
+            .map(|e| graph.get_by_id(&e.target).unwrap().qualified_name.clone())
+            .collect();
+        assert!(generated_from.contains(&"orders.v1.Order".to_string()));
+        assert!(generated_from.contains(&"orders.v1.CreateOrderRequest".to_string()));
+    }
 }
@@ -0,0 +1,204 @@
+//! Links generated code back to the IDL definitions it was generated from.
+//!
+//! `protoc` and the Thrift compiler emit Go/TS/Java/Python symbols that
+//! mirror the messages and services in `.proto`/`.thrift` files, but nothing
+//! in the generated source points back at the contract. Without a link,
+//! impact analysis stops at the RPC boundary: changing `message User` shows
+//! nothing in the services that serialize it.
+//!
+//! Matching is by convention, not by reading generator output:
+//!   - A generated file is recognised by its name (`*.pb.go`, `*_pb2.py`,
+//!     `*_pb.ts`, `*_grpc.pb.go`, ...) or by living under a `generated`,
+//!     `generated-sources` or Thrift `gen-*` directory.
+//!   - A message/enum links to generated types with the same name, less
+//!     the proto package (`User`, or `User_Address` / `User.Address` for
+//!     nested messages).
+//!   - A service links to generated types whose name contains it
+//!     (`UserServiceClient`, `UserServiceGrpc`, `UserServiceServicer`, ...).
+//!   - An rpc links to generated methods with the same name (ignoring case,
+//!     so Java's `getUser` matches `GetUser`) on a type named after its
+//!     service.
+
+use crate::graph::{ArborGraph, NodeId};
+use arbor_core::NodeKind;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// File suffixes produced by protobuf/gRPC code generators.
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".pb.go",
+    ".twirp.go",
+    "_pb2.py",
+    "_pb2.pyi",
+    "_pb2_grpc.py",
+    "_pb.ts",
+    "_pb.js",
+    "_pb.d.ts",
+    "_grpc_pb.ts",
+    "_grpc_pb.js",
+    "_grpc_pb.d.ts",
+    ".pb.ts",
+    "_connect.ts",
+    "Grpc.java",
+    "OuterClass.java",
+    "Grpc.cs",
+];
+
+/// Directory names that hold generated sources.
+const GENERATED_DIRS: &[&str] = &["generated", "generated-sources"];
+
+/// Returns true for interface definition files (`.proto`, `.thrift`).
+pub(crate) fn is_idl_file(file: &str) -> bool {
+    matches!(
+        Path::new(file).extension().and_then(|e| e.to_str()),
+        Some("proto") | Some("thrift")
+    )
+}
+
+/// Returns true if the file looks like generator output.
+pub(crate) fn is_generated_file(file: &str) -> bool {
+    let normalized = file.replace('\\', "/");
+    if GENERATED_SUFFIXES
+        .iter()
+        .any(|suffix| normalized.ends_with(suffix))
+    {
+        return true;
+    }
+
+    let mut segments: Vec<&str> = normalized.split('/').collect();
+    segments.pop(); // file name
+    segments
+        .iter()
+        .any(|segment| GENERATED_DIRS.contains(segment) || segment.starts_with("gen-"))
+}
+
+/// Finds (generated node, IDL node) pairs that should be linked.
+pub(crate) fn generated_links(graph: &ArborGraph) -> Vec<(NodeId, NodeId)> {
+    let mut idl_nodes = Vec::new();
+    let mut packages: HashMap<&str, &str> = HashMap::new();
+    let mut generated_types: Vec<NodeId> = Vec::new();
+    let mut generated_types_by_name: HashMap<String, Vec<NodeId>> = HashMap::new();
+    let mut generated_methods_by_name: HashMap<String, Vec<NodeId>> = HashMap::new();
+
+    for idx in graph.node_indexes() {
+        let Some(node) = graph.get(idx) else { continue };
+        if is_idl_file(&node.file) {
+            if node.kind == NodeKind::Module {
+                packages.insert(&node.file, &node.qualified_name);
+            }
+            idl_nodes.push(idx);
+        } else if is_generated_file(&node.file) {
+            match node.kind {
+                NodeKind::Struct
+                | NodeKind::Class
+                | NodeKind::Interface
+                | NodeKind::Enum
+                | NodeKind::TypeAlias => {
+                    generated_types.push(idx);
+                    generated_types_by_name
+                        .entry(node.name.clone())
+                        .or_default()
+                        .push(idx);
+                }
+                NodeKind::Method | NodeKind::Function => {
+                    generated_methods_by_name
+                        .entry(node.name.to_lowercase())
+                        .or_default()
+                        .push(idx);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if idl_nodes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut links = Vec::new();
+
+    for idl_idx in idl_nodes {
+        let idl = graph.get(idl_idx).unwrap();
+        // Generated names leave out the package.
+        let local = packages
+            .get(idl.file.as_str())
+            .and_then(|package| idl.qualified_name.strip_prefix(package))
+            .and_then(|rest| rest.strip_prefix('.'))
+            .unwrap_or(&idl.qualified_name)
+            .to_string();
+        match idl.kind {
+            // Messages, structs, exceptions, enums, typedefs
+            NodeKind::Struct | NodeKind::Enum | NodeKind::TypeAlias => {
+                let flattened = local.replace('.', "_");
+                for name in [&local, &flattened] {
+                    for &generated in generated_types_by_name.get(name).into_iter().flatten() {
+                        links.push((generated, idl_idx));
+                    }
+                }
+                // Nested messages keep their parent in the qualified name (Java, TS).
+                if local != idl.name {
+                    for &generated in generated_types_by_name.get(&idl.name).into_iter().flatten() {
+                        let qualified = &graph.get(generated).unwrap().qualified_name;
+                        if qualified.ends_with(&local) {
+                            links.push((generated, idl_idx));
+                        }
+                    }
+                }
+            }
+
+            // Services: clients, servers, stubs, servicers
+            NodeKind::Interface => {
+                for &generated in &generated_types {
+                    if graph.get(generated).unwrap().name.contains(&idl.name) {
+                        links.push((generated, idl_idx));
+                    }
+                }
+            }
+
+            // Rpcs: the matching method on a client/server type for this service
+            NodeKind::Method => {
+                let Some((service, _)) = local.rsplit_once('.') else {
+                    continue;
+                };
+                let service = service.to_lowercase();
+                let candidates = generated_methods_by_name.get(&idl.name.to_lowercase());
+                for &generated in candidates.into_iter().flatten() {
+                    let qualified = graph.get(generated).unwrap().qualified_name.to_lowercase();
+                    let owner = qualified.rsplit_once('.').map(|(owner, _)| owner);
+                    if owner.is_some_and(|owner| owner.contains(&service)) {
+                        links.push((generated, idl_idx));
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    links.sort();
+    links.dedup();
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_file_detection() {
+        assert!(is_generated_file("gen/users/v1/users.pb.go"));
+        assert!(is_generated_file("api/users_grpc.pb.go"));
+        assert!(is_generated_file("py/users_pb2_grpc.py"));
+        assert!(is_generated_file("web/src/gen/users_pb.ts"));
+        assert!(is_generated_file(
+            "build/generated/source/proto/main/java/com/acme/User.java"
+        ));
+        assert!(is_generated_file("gen-py/users/UserService.py"));
+        assert!(!is_generated_file("src/users/service.go"));
+        assert!(!is_generated_file("src/generated.rs"));
+
+        assert!(is_idl_file("proto/users.proto"));
+        assert!(is_idl_file("idl/users.thrift"));
+        assert!(!is_idl_file("src/users.go"));
+    }
+}
@@ -1,11 +1,129 @@
 //! Confidence scoring for impact analysis
 //!
-//! Provides explainable risk levels (Low/Medium/High) based on graph structure.
+//! Each affected node carries the probability that a change to the target
+//! really reaches it. Every hop on its path multiplies in a factor for
+//! whatever makes that hop less than certain:
+//!
+//! | Factor | Weight |
+//! |--------|--------|
+//! | The reference was not matched on its qualified name | the edge's confidence |
+//! | The calling file has parse errors | 0.8 |
+//! | The calling file is mostly not covered by extracted symbols | 0.9 |
+//! | The calling file was read by the fallback parser | 0.6 |
+//! | The hop is an uncertain edge (dispatch, callback, DI) | its own confidence |
+//!
+//! File factors count once per file on a path. The factors applied form
+//! the node's explanation trace, and the analysis-level
+//! [`ConfidenceLevel`] follows from the probabilities of the affected
+//! nodes rather than from how many there are.
+
+use crate::{
+    detect_analysis_limitations, AnalysisWarning, ArborGraph, Edge, ImpactAnalysis, UncertainEdge,
+    UncertainEdgeKind,
+};
+use arbor_core::ParseDiagnostics;
+use serde::{Deserialize, Serialize};
+
+/// The file holding the reference has syntax errors.
+const PARSE_ERRORS: f32 = 0.8;
+/// Most of the file holding the reference is not covered by symbols.
+const LOW_COVERAGE: f32 = 0.9;
+/// The file holding the reference was read by the fallback parser.
+const FALLBACK_PARSER: f32 = 0.6;
+
+/// Mean probability at or above which a result is High confidence.
+const HIGH_PROBABILITY: f32 = 0.8;
+/// Mean probability at or above which a result is Medium confidence.
+const MEDIUM_PROBABILITY: f32 = 0.5;
+
+/// What lowered an affected node's probability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfidenceFactorKind {
+    /// An edge resolved other than by exact qualified name.
+    Resolution,
+    /// A reference in a file with parse errors.
+    ParseErrors,
+    /// A reference in a file with low symbol coverage.
+    LowCoverage,
+    /// A reference in a file the fallback parser read.
+    FallbackParser,
+    /// A hop over an uncertain edge.
+    UncertainEdge,
+}
+
+impl std::fmt::Display for ConfidenceFactorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Resolution => write!(f, "resolution"),
+            Self::ParseErrors => write!(f, "parse errors"),
+            Self::LowCoverage => write!(f, "low parse coverage"),
+            Self::FallbackParser => write!(f, "fallback parser"),
+            Self::UncertainEdge => write!(f, "uncertain edge"),
+        }
+    }
+}
+
+/// One step of an affected node's explanation trace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceFactor {
+    pub kind: ConfidenceFactorKind,
+    /// Multiplier applied to the probability.
+    pub weight: f32,
+    /// What it applied to, e.g. `'checkout' → 'save'` or a file path.
+    pub detail: String,
+}
+
+impl std::fmt::Display for ConfidenceFactor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ×{:.2} ({})", self.kind, self.weight, self.detail)
+    }
+}
 
-use crate::ImpactAnalysis;
+impl ConfidenceFactor {
+    /// The factor for how an edge was resolved, if it is not certain.
+    pub(crate) fn resolution(edge: &Edge, from: &str, to: &str) -> Option<Self> {
+        (edge.confidence < 1.0).then(|| Self {
+            kind: ConfidenceFactorKind::Resolution,
+            weight: edge.confidence,
+            detail: format!("'{}' → '{}' via {}", from, to, edge.provenance),
+        })
+    }
+
+    /// Factors for references read from a poorly parsed file.
+    pub(crate) fn parse(file: &str, diagnostics: &ParseDiagnostics) -> Vec<Self> {
+        let mut factors = Vec::new();
+        let mut push = |kind, weight| {
+            factors.push(Self {
+                kind,
+                weight,
+                detail: file.to_string(),
+            })
+        };
+        if diagnostics.used_fallback {
+            push(ConfidenceFactorKind::FallbackParser, FALLBACK_PARSER);
+        }
+        if diagnostics.error_count > 0 {
+            push(ConfidenceFactorKind::ParseErrors, PARSE_ERRORS);
+        }
+        if diagnostics.is_low_coverage() {
+            push(ConfidenceFactorKind::LowCoverage, LOW_COVERAGE);
+        }
+        factors
+    }
+
+    /// The factor for a hop over an uncertain edge.
+    pub(crate) fn uncertain(edge: &UncertainEdge) -> Self {
+        Self {
+            kind: ConfidenceFactorKind::UncertainEdge,
+            weight: edge.confidence,
+            detail: format!("{}: {}", edge.kind, edge.reason),
+        }
+    }
+}
 
 /// Confidence level for an analysis result
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum ConfidenceLevel {
     /// High confidence - well-connected node with clear edges
     High,
@@ -25,10 +143,26 @@ impl std::fmt::Display for ConfidenceLevel {
     }
 }
 
+impl ConfidenceLevel {
+    /// The level for a mean probability across affected nodes.
+    pub fn from_probability(probability: f32) -> Self {
+        if probability >= HIGH_PROBABILITY {
+            ConfidenceLevel::High
+        } else if probability >= MEDIUM_PROBABILITY {
+            ConfidenceLevel::Medium
+        } else {
+            ConfidenceLevel::Low
+        }
+    }
+}
+
 /// Reasons explaining the confidence level
 #[derive(Debug, Clone)]
 pub struct ConfidenceExplanation {
     pub level: ConfidenceLevel,
+    /// Mean probability of the upstream and downstream nodes; `None`
+    /// when nothing is affected.
+    pub probability: Option<f32>,
     pub reasons: Vec<String>,
     pub suggestions: Vec<String>,
 }
@@ -43,50 +177,76 @@ impl ConfidenceExplanation {
         let downstream_count = analysis.downstream.len();
         let total = analysis.total_affected;
 
-        // Determine base confidence from connectivity
-        let level = if upstream_count == 0 && downstream_count == 0 {
-            // Isolated node
+        let affected: Vec<_> = analysis
+            .upstream
+            .iter()
+            .chain(&analysis.downstream)
+            .collect();
+        let probability = (!affected.is_empty())
+            .then(|| affected.iter().map(|n| n.probability).sum::<f32>() / affected.len() as f32);
+
+        // Describe the node's place in the graph
+        if upstream_count == 0 && downstream_count == 0 {
             reasons.push("Node appears isolated (no detected connections)".to_string());
             suggestions
                 .push("Verify if this is called dynamically or from external code".to_string());
-            ConfidenceLevel::Low
         } else if upstream_count == 0 {
-            // Entry point
             reasons.push("Node is an entry point (no internal callers)".to_string());
             reasons.push(format!("Has {} downstream dependencies", downstream_count));
             if downstream_count > 5 {
                 suggestions.push("Consider impact on downstream dependencies".to_string());
-                ConfidenceLevel::Medium
-            } else {
-                ConfidenceLevel::High
             }
         } else if downstream_count == 0 {
-            // Leaf/utility node
             reasons.push("Node is a utility (no outgoing dependencies)".to_string());
             reasons.push(format!("Called by {} upstream nodes", upstream_count));
-            ConfidenceLevel::High
         } else {
-            // Connected node
             reasons.push(format!(
                 "{} callers, {} dependencies",
                 upstream_count, downstream_count
             ));
-
             if total > 50 {
                 reasons.push("Very large blast radius".to_string());
                 suggestions
                     .push("This change affects a significant portion of the codebase".to_string());
-                ConfidenceLevel::Low
             } else if total > 20 {
                 reasons.push("Large blast radius detected".to_string());
                 suggestions
                     .push("Consider breaking this change into smaller refactors".to_string());
-                ConfidenceLevel::Medium
             } else {
                 reasons.push("Well-connected with manageable impact".to_string());
-                ConfidenceLevel::High
             }
+        }
+
+        // Confidence follows how sure each affected node is, not how many
+        // there are. An isolated node may still have callers the graph
+        // cannot see.
+        let mut level = match probability {
+            Some(p) => {
+                reasons.push(format!(
+                    "Mean probability {:.0}% across {} affected node(s)",
+                    p * 100.0,
+                    affected.len()
+                ));
+                ConfidenceLevel::from_probability(p)
+            }
+            None => ConfidenceLevel::Low,
         };
+        let mut lowered_by: Vec<(ConfidenceFactorKind, usize)> = Vec::new();
+        for node in &affected {
+            let mut kinds: Vec<_> = node.trace.iter().map(|f| f.kind).collect();
+            kinds.sort();
+            kinds.dedup();
+            for kind in kinds {
+                match lowered_by.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, count)) => *count += 1,
+                    None => lowered_by.push((kind, 1)),
+                }
+            }
+        }
+        lowered_by.sort();
+        for (kind, count) in lowered_by {
+            reasons.push(format!("{} node(s) reached through {}", count, kind));
+        }
 
         // Add structural insights
         if total > 0 {
@@ -100,15 +260,84 @@ impl ConfidenceExplanation {
             }
         }
 
+        // Co-change partners the graph has no edge to
+        let co_changes = analysis
+            .uncertain_edges
+            .iter()
+            .filter(|e| e.kind == UncertainEdgeKind::CoChange)
+            .count();
+        if co_changes > 0 {
+            reasons.push(format!(
+                "{} co-change partner(s) in git history with no static edge",
+                co_changes
+            ));
+            suggestions.push("Check the co-change partners for hidden dependencies".to_string());
+            if level == ConfidenceLevel::High {
+                level = ConfidenceLevel::Medium;
+            }
+        }
+
+        // Reached only through dispatch, callbacks or injection
+        if !analysis.possibly_affected.is_empty() {
+            reasons.push(format!(
+                "{} more node(s) possibly affected through dynamic dispatch, callbacks or dependency injection",
+                analysis.possibly_affected.len()
+            ));
+            suggestions.push(
+                "Check callers of the interface, callback registrations and injection sites"
+                    .to_string(),
+            );
+            if level == ConfidenceLevel::High {
+                level = ConfidenceLevel::Medium;
+            }
+        }
+
         // Standard disclaimer
         suggestions.push("Tests still recommended for behavioral verification".to_string());
 
         Self {
             level,
+            probability,
             reasons,
             suggestions,
         }
     }
+
+    /// Compute confidence from an impact analysis, lowered when the result
+    /// touches code the graph knows it parsed badly.
+    pub fn from_graph_analysis(analysis: &ImpactAnalysis, graph: &ArborGraph) -> Self {
+        let nodes: Vec<_> = std::iter::once(graph.get_by_id(&analysis.target.id))
+            .chain(analysis.all_affected().iter().map(|n| graph.get(n.node_id)))
+            .flatten()
+            .collect();
+        let warnings = detect_analysis_limitations(&nodes, graph.all_file_diagnostics());
+        Self::from_analysis(analysis).with_warnings(&warnings)
+    }
+
+    /// Folds analysis warnings into the explanation. Each warning that
+    /// lowers confidence drops the level by one step, at most to Low.
+    pub fn with_warnings(mut self, warnings: &[AnalysisWarning]) -> Self {
+        for warning in warnings {
+            self.reasons.push(warning.message.clone());
+            // Keep the standard disclaimer last.
+            let at = self.suggestions.len().saturating_sub(1);
+            self.suggestions.insert(at, warning.suggestion.clone());
+            if warning.lowers_confidence {
+                self.level = self.level.lowered();
+            }
+        }
+        self
+    }
+}
+
+impl ConfidenceLevel {
+    /// One step less confident.
+    fn lowered(self) -> Self {
+        match self {
+            ConfidenceLevel::High => ConfidenceLevel::Medium,
+            ConfidenceLevel::Medium | ConfidenceLevel::Low => ConfidenceLevel::Low,
+        }
+    }
 }
 
 /// Node role classification
@@ -182,6 +411,7 @@ mod tests {
             line_end: 1,
             signature: None,
             centrality: 0.0,
+            metrics: Default::default(),
         }
     }
 
@@ -193,6 +423,10 @@ mod tests {
             hop_distance,
             entry_edge: EdgeKind::Calls,
             direction,
+            owners: Vec::new(),
+            primary_author: None,
+            probability: 1.0,
+            trace: Vec::new(),
         }
     }
 
@@ -215,6 +449,8 @@ mod tests {
             target: node_info("target"),
             upstream: upstream_nodes,
             downstream: downstream_nodes,
+            possibly_affected: Vec::new(),
+            uncertain_edges: Vec::new(),
             total_affected,
             max_depth: 3,
             query_time_ms: 1,
@@ -238,15 +474,17 @@ mod tests {
     }
 
     #[test]
-    fn test_confidence_connected_thresholds_regression() {
+    fn test_blast_radius_is_a_reason_not_a_confidence_level() {
         let medium_case = analysis(10, 20, 30);
         let low_case = analysis(20, 40, 60);
 
         let medium = ConfidenceExplanation::from_analysis(&medium_case);
         let low = ConfidenceExplanation::from_analysis(&low_case);
 
-        assert_eq!(medium.level, ConfidenceLevel::Medium);
-        assert_eq!(low.level, ConfidenceLevel::Low);
+        // Every path is exact, so the graph is sure of all of them.
+        assert_eq!(medium.level, ConfidenceLevel::High);
+        assert_eq!(low.level, ConfidenceLevel::High);
+        assert_eq!(low.probability, Some(1.0));
         assert!(medium
             .reasons
             .iter()
@@ -257,21 +495,43 @@ mod tests {
             .any(|r| r.contains("Very large blast radius")));
     }
 
+    #[test]
+    fn test_co_change_partners_cap_confidence_at_medium() {
+        let mut a = analysis(2, 0, 2);
+        a.uncertain_edges.push(crate::UncertainEdge {
+            from: "target".to_string(),
+            to: "web/client.ts".to_string(),
+            kind: UncertainEdgeKind::CoChange,
+            confidence: 0.8,
+            reason: String::new(),
+        });
+        let explanation = ConfidenceExplanation::from_analysis(&a);
+        assert_eq!(explanation.level, ConfidenceLevel::Medium);
+        assert!(explanation
+            .reasons
+            .iter()
+            .any(|r| r.contains("1 co-change partner")));
+    }
+
     #[test]
     fn test_confidence_entry_point_matrix_120_cases() {
         let mut cases = 0;
         for downstream in 1..=120 {
             let a = analysis(0, downstream, downstream);
             let explanation = ConfidenceExplanation::from_analysis(&a);
-            let expected = if downstream > 5 {
-                ConfidenceLevel::Medium
-            } else {
-                ConfidenceLevel::High
-            };
             assert_eq!(
-                explanation.level, expected,
+                explanation.level,
+                ConfidenceLevel::High,
                 "entry-point mismatch for downstream={downstream}"
             );
+            assert_eq!(
+                explanation
+                    .suggestions
+                    .iter()
+                    .any(|s| s.contains("downstream dependencies")),
+                downstream > 5,
+                "entry-point suggestion mismatch for downstream={downstream}"
+            );
             cases += 1;
         }
         assert_eq!(cases, 120);
@@ -298,27 +558,22 @@ mod tests {
         let mut cases = 0;
         for upstream in 1..=11 {
             for downstream in 1..=11 {
-                // Ensure we exercise all connected-tier branches deterministically:
-                // <=20 (High), 21..=50 (Medium), >50 (Low)
-                let total = match (upstream + downstream) % 3 {
-                    0 => 15,
-                    1 => 35,
-                    _ => 70,
+                // Ensure we exercise every level deterministically:
+                // >=0.8 (High), 0.5..0.8 (Medium), <0.5 (Low)
+                let (probability, expected) = match (upstream + downstream) % 3 {
+                    0 => (0.9, ConfidenceLevel::High),
+                    1 => (0.6, ConfidenceLevel::Medium),
+                    _ => (0.3, ConfidenceLevel::Low),
                 };
 
-                let expected = if total > 50 {
-                    ConfidenceLevel::Low
-                } else if total > 20 {
-                    ConfidenceLevel::Medium
-                } else {
-                    ConfidenceLevel::High
-                };
-
-                let a = analysis(upstream, downstream, total);
+                let mut a = analysis(upstream, downstream, upstream + downstream);
+                for node in a.upstream.iter_mut().chain(a.downstream.iter_mut()) {
+                    node.probability = probability;
+                }
                 let explanation = ConfidenceExplanation::from_analysis(&a);
                 assert_eq!(
                     explanation.level, expected,
-                    "connected mismatch for upstream={upstream}, downstream={downstream}, total={total}"
+                    "connected mismatch for upstream={upstream}, downstream={downstream}, probability={probability}"
                 );
                 cases += 1;
             }
@@ -377,4 +632,44 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_errors_lower_graph_confidence() {
+        use crate::Edge;
+        use arbor_core::{CodeNode, NodeKind, ParseDiagnostics};
+
+        let mut graph = ArborGraph::new();
+        let caller = graph.add_node(CodeNode::new(
+            "caller",
+            "caller",
+            NodeKind::Function,
+            "a.rs",
+        ));
+        let target = graph.add_node(CodeNode::new(
+            "target",
+            "target",
+            NodeKind::Function,
+            "b.rs",
+        ));
+        graph.add_edge(caller, target, Edge::new(EdgeKind::Calls));
+
+        let analysis = graph.analyze_impact(target, 3);
+        let clean = ConfidenceExplanation::from_graph_analysis(&analysis, &graph);
+        assert_eq!(clean.level, ConfidenceLevel::High);
+
+        graph.set_file_diagnostics(
+            "a.rs",
+            ParseDiagnostics {
+                error_count: 1,
+                ..ParseDiagnostics::default()
+            },
+        );
+        let degraded = ConfidenceExplanation::from_graph_analysis(&analysis, &graph);
+        assert_eq!(degraded.level, ConfidenceLevel::Medium);
+        assert!(degraded.reasons.iter().any(|r| r.contains("parse errors")));
+        assert!(degraded
+            .suggestions
+            .last()
+            .is_some_and(|s| s.contains("Tests still recommended")));
+    }
 }
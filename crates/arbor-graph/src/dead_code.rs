@@ -0,0 +1,702 @@
+//! Dead code detection.
+//!
+//! Symbols are alive if they can be reached from a root by following graph
+//! edges. Roots are the detected entry points plus whatever the caller opts
+//! into: exported API, framework hooks and named patterns. Test code forms a
+//! second root set, so code that only tests keep alive is reported apart
+//! from code nothing reaches at all.
+//!
+//! Static reachability misses runtime wiring, so every unreachable symbol
+//! carries a confidence level lowered by the [`UncertainEdgeKind`]s that
+//! could still connect it (callbacks, dynamic dispatch, reflection, DI).
+
+use crate::confidence::ConfidenceLevel;
+use crate::graph::{ArborGraph, NodeId};
+use crate::heuristics::{HeuristicsMatcher, UncertainEdgeKind};
+use crate::hierarchy::member_owners;
+use crate::query::NodeInfo;
+use crate::ranking::is_test_file;
+use crate::search_index::{glob_match, path_matches};
+use arbor_core::{CodeNode, NodeKind, Visibility};
+use petgraph::Direction;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// Calls whose target is chosen by name at runtime, by the file extensions
+/// of the languages where they do that. Matched against the last segment of
+/// a reference, so `send` only counts in Ruby: a Rust channel or a Kafka
+/// producer has a `send` too.
+const REFLECTIVE_CALLS: &[(&[&str], &[&str])] = &[
+    (
+        &["py", "pyi"],
+        &[
+            "getattr",
+            "setattr",
+            "__getattr__",
+            "import_module",
+            "__import__",
+            "eval",
+        ],
+    ),
+    (
+        &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"],
+        &["eval"],
+    ),
+    (
+        &["java", "kt", "kts", "scala"],
+        &["forName", "getMethod", "getDeclaredMethod"],
+    ),
+    (&["go"], &["MethodByName"]),
+    (
+        &["rb"],
+        &["send", "public_send", "__send__", "method_missing"],
+    ),
+    (
+        &["php", "phtml"],
+        &["call_user_func", "call_user_func_array"],
+    ),
+];
+
+/// The reflective calls of the language `file` is written in.
+fn reflective_calls(file: &str) -> &'static [&'static str] {
+    let extension = std::path::Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    REFLECTIVE_CALLS
+        .iter()
+        .find(|(extensions, _)| extensions.contains(&extension))
+        .map_or(&[], |&(_, calls)| calls)
+}
+
+/// Which symbols besides detected entry points count as roots.
+#[derive(Debug, Clone)]
+pub struct DeadCodeOptions {
+    /// Public and exported symbols are roots (set for libraries).
+    pub exported: bool,
+    /// Test code keeps what it reaches out of the unreachable list; those
+    /// symbols are reported as test-only instead.
+    pub tests: bool,
+    /// Event handlers, widgets, React components and DI providers are roots.
+    pub framework_hooks: bool,
+    /// Extra roots: a glob over names and qualified names (`register_*`),
+    /// or over file paths when the pattern contains `/` (`src/plugins/**`).
+    pub roots: Vec<String>,
+}
+
+impl Default for DeadCodeOptions {
+    fn default() -> Self {
+        Self {
+            exported: false,
+            tests: true,
+            framework_hooks: true,
+            roots: Vec::new(),
+        }
+    }
+}
+
+/// A symbol no root reaches.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadSymbol {
+    #[serde(flatten)]
+    pub node_info: NodeInfo,
+    /// How sure we are that nothing uses it at runtime.
+    pub confidence: ConfidenceLevel,
+    /// Runtime wiring that could still reach it.
+    pub uncertain: Vec<UncertainEdgeKind>,
+    pub reasons: Vec<String>,
+}
+
+/// Dead symbols in one file, by line.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadFile {
+    pub file: String,
+    pub symbols: Vec<DeadSymbol>,
+}
+
+/// Result of [`ArborGraph::find_dead_code`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DeadCodeReport {
+    /// Production roots the search started from.
+    pub roots: usize,
+    /// Test symbols the test-only search started from.
+    pub test_roots: usize,
+    /// Functions, methods and classes outside test code that were checked.
+    pub analyzed: usize,
+    /// Symbols nothing reaches.
+    pub unreachable: Vec<DeadFile>,
+    /// Symbols only reached from tests.
+    pub test_only: Vec<DeadFile>,
+}
+
+impl DeadCodeReport {
+    /// Number of symbols nothing reaches.
+    pub fn unreachable_count(&self) -> usize {
+        self.unreachable.iter().map(|f| f.symbols.len()).sum()
+    }
+
+    /// Number of symbols only tests reach.
+    pub fn test_only_count(&self) -> usize {
+        self.test_only.iter().map(|f| f.symbols.len()).sum()
+    }
+}
+
+/// Links that keep a symbol alive besides graph edges: a live method keeps
+/// its class, and a live class keeps its constructors.
+struct Members {
+    owner: HashMap<NodeId, NodeId>,
+    constructors: HashMap<NodeId, Vec<NodeId>>,
+}
+
+impl ArborGraph {
+    /// Finds functions, methods and classes that no root reaches, grouped
+    /// by file.
+    pub fn find_dead_code(&self, options: &DeadCodeOptions) -> DeadCodeReport {
+        let members = self.members();
+        let test_code = self.test_code();
+        let is_test = |index: NodeId| test_code.contains(&index);
+
+        let mut roots = Vec::new();
+        let mut test_roots = Vec::new();
+        for index in self.node_indexes() {
+            if is_test(index) {
+                if is_code(self.graph[index].kind) {
+                    test_roots.push(index);
+                }
+            } else if self.is_root(&self.graph[index], options) {
+                roots.push(index);
+            }
+        }
+
+        let live = self.reach(&roots, &members);
+        let test_live = if options.tests {
+            self.reach(&test_roots, &members)
+        } else {
+            HashSet::new()
+        };
+
+        let reflective = self.reflective_files();
+        let dispatched = self.interface_methods(&members);
+
+        let mut unreachable: BTreeMap<&str, Vec<DeadSymbol>> = BTreeMap::new();
+        let mut test_only: BTreeMap<&str, Vec<DeadSymbol>> = BTreeMap::new();
+        let mut analyzed = 0;
+        for index in self.node_indexes() {
+            let node = &self.graph[index];
+            if !is_reported(node.kind) || is_test(index) {
+                continue;
+            }
+            analyzed += 1;
+            if live.contains(&index) {
+                continue;
+            }
+            let symbol = self.dead_symbol(index, options, &reflective, &dispatched);
+            let group = if test_live.contains(&index) {
+                &mut test_only
+            } else {
+                &mut unreachable
+            };
+            group.entry(node.file.as_str()).or_default().push(symbol);
+        }
+
+        DeadCodeReport {
+            roots: roots.len(),
+            test_roots: if options.tests { test_roots.len() } else { 0 },
+            analyzed,
+            unreachable: dead_files(unreachable),
+            test_only: dead_files(test_only),
+        }
+    }
+
+    fn is_root(&self, node: &CodeNode, options: &DeadCodeOptions) -> bool {
+        if HeuristicsMatcher::is_likely_entry_point(node) {
+            return true;
+        }
+        if options.exported && (node.is_exported || node.visibility == Visibility::Public) {
+            return true;
+        }
+        if options.framework_hooks
+            && (HeuristicsMatcher::is_event_handler(node)
+                || HeuristicsMatcher::is_flutter_widget(node)
+                || HeuristicsMatcher::is_react_component(node)
+                || HeuristicsMatcher::is_dependency_injection(node))
+        {
+            return true;
+        }
+        options
+            .roots
+            .iter()
+            .any(|pattern| root_matches(pattern, node))
+    }
+
+    /// Everything reachable from `roots` along edges and member links.
+    fn reach(&self, roots: &[NodeId], members: &Members) -> HashSet<NodeId> {
+        let mut seen: HashSet<NodeId> = roots.iter().copied().collect();
+        let mut queue: VecDeque<NodeId> = roots.iter().copied().collect();
+        while let Some(current) = queue.pop_front() {
+            let linked = members
+                .owner
+                .get(&current)
+                .into_iter()
+                .chain(members.constructors.get(&current).into_iter().flatten())
+                .copied();
+            for next in self.graph.neighbors(current).chain(linked) {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Owning types of members, and the constructors of each type.
+    fn members(&self) -> Members {
+        let owner = member_owners(self);
+        let mut constructors: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (&member, &ty) in &owner {
+            if self.graph[member].kind == NodeKind::Constructor {
+                constructors.entry(ty).or_default().push(member);
+            }
+        }
+        for list in constructors.values_mut() {
+            list.sort();
+        }
+        Members {
+            owner,
+            constructors,
+        }
+    }
+
+    /// Nodes in test files, test functions, and everything inside
+    /// `tests`/`test` modules (e.g. Rust's `#[cfg(test)] mod tests`).
+    pub(crate) fn test_code(&self) -> HashSet<NodeId> {
+        let mut modules: HashMap<&str, Vec<(u32, u32)>> = HashMap::new();
+        for node in self.nodes() {
+            if node.kind == NodeKind::Module
+                && matches!(node.name.as_str(), "tests" | "test")
+                && node.line_end > 0
+            {
+                modules
+                    .entry(node.file.as_str())
+                    .or_default()
+                    .push((node.line_start, node.line_end));
+            }
+        }
+        self.node_indexes()
+            .filter(|&index| {
+                let node = &self.graph[index];
+                is_test_code(node)
+                    || modules.get(node.file.as_str()).is_some_and(|ranges| {
+                        ranges
+                            .iter()
+                            .any(|&(start, end)| start <= node.line_start && node.line_end <= end)
+                    })
+            })
+            .collect()
+    }
+
+    /// Files that look up code by name at runtime.
+    fn reflective_files(&self) -> HashSet<&str> {
+        self.nodes()
+            .filter(|node| {
+                let calls = reflective_calls(&node.file);
+                node.references.iter().any(|r| {
+                    let last = r.rsplit(['.', ':']).next().unwrap_or(r);
+                    calls.contains(&last)
+                })
+            })
+            .map(|node| node.file.as_str())
+            .collect()
+    }
+
+    /// Names of methods declared on interfaces and traits; implementations
+    /// of them are called through the interface.
+    fn interface_methods(&self, members: &Members) -> HashSet<&str> {
+        members
+            .owner
+            .iter()
+            .filter(|(_, &owner)| self.graph[owner].kind == NodeKind::Interface)
+            .map(|(&method, _)| self.graph[method].name.as_str())
+            .collect()
+    }
+
+    fn dead_symbol(
+        &self,
+        index: NodeId,
+        options: &DeadCodeOptions,
+        reflective: &HashSet<&str>,
+        dispatched: &HashSet<&str>,
+    ) -> DeadSymbol {
+        let node = &self.graph[index];
+        let mut uncertain: Vec<(UncertainEdgeKind, f32, String)> = Vec::new();
+
+        // Wiring the graph found (a callback registration, an injection,
+        // a call through an interface) outranks a guess from the name.
+        for (_, edge) in self.uncertain_edges_of(index, Direction::Incoming) {
+            match uncertain.iter_mut().find(|(kind, _, _)| *kind == edge.kind) {
+                Some(entry) if entry.1 < edge.confidence => {
+                    *entry = (edge.kind.clone(), edge.confidence, edge.reason.clone())
+                }
+                Some(_) => {}
+                None => uncertain.push((edge.kind.clone(), edge.confidence, edge.reason.clone())),
+            }
+        }
+        let found: Vec<UncertainEdgeKind> = uncertain.iter().map(|(k, _, _)| k.clone()).collect();
+        let mut guess = |kind: UncertainEdgeKind, confidence: f32, reason: String| {
+            if !found.contains(&kind) {
+                uncertain.push((kind, confidence, reason));
+            }
+        };
+
+        if HeuristicsMatcher::is_event_handler(node) {
+            guess(
+                UncertainEdgeKind::EventHandler,
+                0.7,
+                format!("'{}' looks like an event handler", node.name),
+            );
+        }
+        if HeuristicsMatcher::is_callback_style(node) {
+            guess(
+                UncertainEdgeKind::Callback,
+                0.6,
+                format!("'{}' is likely passed as a callback", node.name),
+            );
+        }
+        if HeuristicsMatcher::is_flutter_widget(node) || HeuristicsMatcher::is_react_component(node)
+        {
+            guess(
+                UncertainEdgeKind::WidgetTree,
+                0.8,
+                format!("'{}' is a UI component mounted at runtime", node.name),
+            );
+        }
+        if HeuristicsMatcher::is_dependency_injection(node) {
+            guess(
+                UncertainEdgeKind::DependencyInjection,
+                0.7,
+                format!("'{}' looks like a DI factory or provider", node.name),
+            );
+        }
+        if node.kind == NodeKind::Method && dispatched.contains(node.name.as_str()) {
+            guess(
+                UncertainEdgeKind::DynamicDispatch,
+                0.8,
+                format!(
+                    "'{}' implements an interface method and may be called through it",
+                    node.name
+                ),
+            );
+        }
+        if reflective.contains(node.file.as_str()) {
+            guess(
+                UncertainEdgeKind::Reflection,
+                0.5,
+                "its file looks up code by name at runtime".to_string(),
+            );
+        }
+
+        let strongest = uncertain.iter().map(|(_, c, _)| *c).fold(0.0f32, f32::max);
+        let mut level = if strongest >= 0.7 {
+            ConfidenceLevel::Low
+        } else if strongest > 0.0 {
+            ConfidenceLevel::Medium
+        } else {
+            ConfidenceLevel::High
+        };
+
+        let mut reasons: Vec<String> = uncertain.iter().map(|(_, _, r)| r.clone()).collect();
+        if !options.exported && (node.is_exported || node.visibility == Visibility::Public) {
+            reasons.push("public API may be used outside this project".to_string());
+            level = lower(level);
+        }
+        if let Some(diag) = self.file_diagnostics(&node.file) {
+            if diag.is_degraded() || diag.is_low_coverage() {
+                reasons.push("its file parsed poorly, so callers may be missing".to_string());
+                level = lower(level);
+            }
+        }
+
+        let mut node_info = NodeInfo::from(node);
+        node_info.centrality = self.centrality(index);
+        DeadSymbol {
+            node_info,
+            confidence: level,
+            uncertain: uncertain.into_iter().map(|(kind, _, _)| kind).collect(),
+            reasons,
+        }
+    }
+}
+
+fn lower(level: ConfidenceLevel) -> ConfidenceLevel {
+    match level {
+        ConfidenceLevel::High => ConfidenceLevel::Medium,
+        _ => ConfidenceLevel::Low,
+    }
+}
+
+fn dead_files(groups: BTreeMap<&str, Vec<DeadSymbol>>) -> Vec<DeadFile> {
+    groups
+        .into_iter()
+        .map(|(file, mut symbols)| {
+            symbols.sort_by_key(|s| (s.node_info.line_start, s.node_info.name.clone()));
+            DeadFile {
+                file: file.to_string(),
+                symbols,
+            }
+        })
+        .collect()
+}
+
+fn root_matches(pattern: &str, node: &CodeNode) -> bool {
+    if pattern.contains(['/', '\\']) {
+        return path_matches(pattern, &node.file);
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    [&node.name, &node.qualified_name]
+        .iter()
+        .any(|name| glob_match(&pattern, &name.chars().collect::<Vec<_>>()))
+}
+
+/// Test files, and test functions living next to production code
+/// (`test_*` in Python and Rust, `Test*` in Go).
+fn is_test_code(node: &CodeNode) -> bool {
+    is_test_file(&node.file)
+        || (matches!(node.kind, NodeKind::Function | NodeKind::Method)
+            && (node.name.starts_with("test_")
+                || (node.name.starts_with("Test") && node.file.ends_with(".go"))))
+}
+
+fn is_code(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function | NodeKind::Method | NodeKind::Constructor | NodeKind::Class
+    )
+}
+
+fn is_reported(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function | NodeKind::Method | NodeKind::Class | NodeKind::Struct
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{Edge, EdgeKind};
+
+    fn add(graph: &mut ArborGraph, name: &str, kind: NodeKind, file: &str) -> NodeId {
+        graph.add_node(CodeNode::new(name, name, kind, file).with_lines(1, 5))
+    }
+
+    fn calls(graph: &mut ArborGraph, from: NodeId, to: NodeId) {
+        graph.add_edge(from, to, Edge::new(EdgeKind::Calls));
+    }
+
+    fn names(files: &[DeadFile]) -> Vec<&str> {
+        let mut names: Vec<&str> = files
+            .iter()
+            .flat_map(|f| f.symbols.iter().map(|s| s.node_info.name.as_str()))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_unreachable_and_test_only_are_separate() {
+        let mut graph = ArborGraph::new();
+        let main = add(&mut graph, "main", NodeKind::Function, "src/main.rs");
+        let used = add(&mut graph, "load", NodeKind::Function, "src/lib.rs");
+        let tested = add(&mut graph, "legacy_parse", NodeKind::Function, "src/lib.rs");
+        add(&mut graph, "orphan", NodeKind::Function, "src/lib.rs");
+        let test = add(
+            &mut graph,
+            "parses",
+            NodeKind::Function,
+            "tests/parse_test.rs",
+        );
+        calls(&mut graph, main, used);
+        calls(&mut graph, test, tested);
+        calls(&mut graph, test, used);
+
+        let report = graph.find_dead_code(&DeadCodeOptions::default());
+        assert_eq!(report.roots, 1);
+        assert_eq!(report.test_roots, 1);
+        assert_eq!(report.analyzed, 4);
+        assert_eq!(names(&report.unreachable), ["orphan"]);
+        assert_eq!(names(&report.test_only), ["legacy_parse"]);
+        assert_eq!(report.unreachable[0].file, "src/lib.rs");
+        assert_eq!(
+            report.unreachable[0].symbols[0].confidence,
+            ConfidenceLevel::High
+        );
+
+        // Without test roots, test-only code is simply unreachable.
+        let options = DeadCodeOptions {
+            tests: false,
+            ..Default::default()
+        };
+        let report = graph.find_dead_code(&options);
+        assert_eq!(names(&report.unreachable), ["legacy_parse", "orphan"]);
+        assert!(report.test_only.is_empty());
+    }
+
+    #[test]
+    fn test_methods_keep_their_class_alive() {
+        let mut graph = ArborGraph::new();
+        let main = add(&mut graph, "main", NodeKind::Function, "main.py");
+        graph.add_node(CodeNode::new("Store", "Store", NodeKind::Class, "store.py"));
+        let save = graph.add_node(CodeNode::new(
+            "save",
+            "Store.save",
+            NodeKind::Method,
+            "store.py",
+        ));
+        graph.add_node(CodeNode::new(
+            "drop",
+            "Store.drop",
+            NodeKind::Method,
+            "store.py",
+        ));
+        calls(&mut graph, main, save);
+
+        let report = graph.find_dead_code(&DeadCodeOptions::default());
+        assert_eq!(names(&report.unreachable), ["drop"]);
+    }
+
+    #[test]
+    fn test_extra_roots_and_exported_api() {
+        let mut graph = ArborGraph::new();
+        add(
+            &mut graph,
+            "register_hooks",
+            NodeKind::Function,
+            "src/setup.py",
+        );
+        add(
+            &mut graph,
+            "plugin_init",
+            NodeKind::Function,
+            "src/plugins/a.py",
+        );
+        let mut public = CodeNode::new("parse", "parse", NodeKind::Function, "src/api.ts");
+        public.is_exported = true;
+        graph.add_node(public);
+
+        let report = graph.find_dead_code(&DeadCodeOptions::default());
+        assert_eq!(report.unreachable_count(), 3);
+        let exported = &report.unreachable[0].symbols[0];
+        assert_eq!(exported.node_info.name, "parse");
+        assert_eq!(exported.confidence, ConfidenceLevel::Medium);
+
+        let options = DeadCodeOptions {
+            exported: true,
+            roots: vec!["register_*".to_string(), "src/plugins/**".to_string()],
+            ..Default::default()
+        };
+        let report = graph.find_dead_code(&options);
+        assert_eq!(report.roots, 3);
+        assert_eq!(report.unreachable_count(), 0);
+    }
+
+    #[test]
+    fn test_uncertain_edges_lower_confidence() {
+        let mut graph = ArborGraph::new();
+        add(&mut graph, "sort_fn", NodeKind::Function, "util.ts");
+        add(&mut graph, "onClick", NodeKind::Function, "button.ts");
+        graph.add_node(CodeNode::new(
+            "Shape",
+            "Shape",
+            NodeKind::Interface,
+            "shape.rs",
+        ));
+        graph.add_node(CodeNode::new(
+            "area",
+            "Shape.area",
+            NodeKind::Method,
+            "shape.rs",
+        ));
+        graph.add_node(CodeNode::new(
+            "area",
+            "Circle.area",
+            NodeKind::Method,
+            "circle.rs",
+        ));
+        let mut lookup = CodeNode::new("dispatch", "dispatch", NodeKind::Function, "cmd.py");
+        lookup.references = vec!["getattr".to_string()];
+        graph.add_node(lookup);
+
+        let report = graph.find_dead_code(&DeadCodeOptions::default());
+        let symbol = |name: &str, file: &str| {
+            report
+                .unreachable
+                .iter()
+                .filter(|f| f.file == file)
+                .flat_map(|f| &f.symbols)
+                .find(|s| s.node_info.name == name)
+                .cloned()
+        };
+
+        // Framework hooks are roots by default.
+        assert!(symbol("onClick", "button.ts").is_none());
+
+        let callback = symbol("sort_fn", "util.ts").unwrap();
+        assert_eq!(callback.uncertain, [UncertainEdgeKind::Callback]);
+        assert_eq!(callback.confidence, ConfidenceLevel::Medium);
+
+        let implementation = symbol("area", "circle.rs").unwrap();
+        assert_eq!(
+            implementation.uncertain,
+            [UncertainEdgeKind::DynamicDispatch]
+        );
+        assert_eq!(implementation.confidence, ConfidenceLevel::Low);
+
+        let reflective = symbol("dispatch", "cmd.py").unwrap();
+        assert_eq!(reflective.uncertain, [UncertainEdgeKind::Reflection]);
+    }
+
+    #[test]
+    fn test_wiring_edges_explain_unreached_functions() {
+        let mut run = CodeNode::new("run", "run", NodeKind::Function, "jobs.ts");
+        run.wiring = vec![arbor_core::Wiring::Callback {
+            call: "sort".to_string(),
+            function: "byDate".to_string(),
+        }];
+        let mut builder = crate::GraphBuilder::new();
+        builder.add_nodes(vec![
+            run,
+            CodeNode::new("byDate", "byDate", NodeKind::Function, "jobs.ts"),
+        ]);
+        let graph = builder.build();
+
+        let report = graph.find_dead_code(&DeadCodeOptions::default());
+        let by_date = report
+            .unreachable
+            .iter()
+            .flat_map(|f| &f.symbols)
+            .find(|s| s.node_info.name == "byDate")
+            .unwrap();
+        assert_eq!(by_date.uncertain, [UncertainEdgeKind::Callback]);
+        assert_eq!(by_date.reasons, ["'run' passes 'byDate' to 'sort'"]);
+        assert_eq!(by_date.confidence, ConfidenceLevel::Low);
+    }
+
+    #[test]
+    fn test_reflective_calls_depend_on_the_language() {
+        let mut graph = ArborGraph::new();
+        let mut worker = CodeNode::new("worker", "worker", NodeKind::Function, "src/jobs.rs");
+        worker.references = vec!["tx.send".to_string()];
+        graph.add_node(worker);
+        let mut publish = CodeNode::new("publish", "publish", NodeKind::Function, "events.js");
+        publish.references = vec!["producer.send".to_string()];
+        graph.add_node(publish);
+        let mut route = CodeNode::new("route", "route", NodeKind::Method, "router.rb");
+        route.references = vec!["public_send".to_string()];
+        graph.add_node(route);
+
+        let reflective = graph.reflective_files();
+        assert!(!reflective.contains("src/jobs.rs"));
+        assert!(!reflective.contains("events.js"));
+        assert!(reflective.contains("router.rb"));
+    }
+}
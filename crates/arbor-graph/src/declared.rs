@@ -0,0 +1,267 @@
+//! User-declared edges and symbol aliases.
+//!
+//! Relationships the parsers cannot see — string-dispatched handlers,
+//! plugin registries, message buses — can be declared in
+//! `.arbor/edges.toml` or in `arbor:` source comments
+//! ([`CodeNode::declared`]). [`GraphBuilder`](crate::GraphBuilder) adds
+//! them as edges with [`Provenance::UserDeclared`].
+//!
+//! ```toml
+//! # .arbor/edges.toml
+//! [[edge]]
+//! from = "orders::place_order"
+//! to = "billing::charge"
+//! kind = "calls"          # optional, defaults to calls
+//!
+//! [[alias]]
+//! name = "charge_handler"  # references to this name...
+//! symbol = "billing::charge" # ...resolve to this symbol
+//! ```
+//!
+//! Symbols are written as qualified names, optionally prefixed with
+//! module or directory names (`billing::charge`, `billing.Invoice.total`).
+//! Declarations whose endpoints no longer resolve are kept on the graph
+//! for `arbor doctor` to report.
+
+use crate::edge::EdgeKind;
+use crate::graph::{ArborGraph, NodeId};
+use arbor_core::{CodeNode, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Config file, relative to the project root.
+pub const EDGES_CONFIG: &str = ".arbor/edges.toml";
+
+#[derive(Error, Debug)]
+pub enum DeclarationError {
+    #[error("Invalid edge declarations: {0}")]
+    Config(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Contents of `.arbor/edges.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Declarations {
+    #[serde(default, rename = "edge")]
+    pub edges: Vec<DeclaredEdge>,
+    #[serde(default, rename = "alias")]
+    pub aliases: Vec<SymbolAlias>,
+}
+
+/// An edge the code does not show.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeclaredEdge {
+    pub from: String,
+    pub to: String,
+    #[serde(default = "calls")]
+    pub kind: EdgeKind,
+}
+
+/// Another name that references to `symbol` use.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SymbolAlias {
+    pub name: String,
+    pub symbol: String,
+}
+
+fn calls() -> EdgeKind {
+    EdgeKind::Calls
+}
+
+impl Declarations {
+    /// Reads the project's declarations, or `None` if there are none.
+    pub fn load(root: &Path) -> Result<Option<Self>, DeclarationError> {
+        let path = root.join(EDGES_CONFIG);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&path)?;
+        Self::parse(&text)
+            .map(Some)
+            .map_err(|e| DeclarationError::Config(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Parses the contents of an edges file.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+}
+
+/// A declaration the builder could not turn into an edge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnresolvedDeclaration {
+    /// Where it was declared: `.arbor/edges.toml` or `file:line`.
+    pub source: String,
+    /// The declaration as written, e.g. `calls billing::charge`.
+    pub declaration: String,
+    /// Why it did not resolve.
+    pub problem: String,
+}
+
+impl std::fmt::Display for UnresolvedDeclaration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} ({})",
+            self.source, self.declaration, self.problem
+        )
+    }
+}
+
+/// Parses an edge kind as written in a declaration.
+pub(crate) fn edge_kind(relation: &str) -> Option<EdgeKind> {
+    let kind = match relation {
+        "calls" | "call" => EdgeKind::Calls,
+        "imports" => EdgeKind::Imports,
+        "extends" => EdgeKind::Extends,
+        "implements" => EdgeKind::Implements,
+        "uses_type" | "uses" => EdgeKind::UsesType,
+        "references" => EdgeKind::References,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// The node a declared symbol names.
+///
+/// `a::b::c` and `a.b.c` are the same. The qualified name may match
+/// outright; otherwise the last segments must end the node's qualified
+/// name and the leading ones name modules or directories on its path.
+/// Among several matches, one in `context`'s directory wins.
+pub(crate) fn resolve_symbol(
+    graph: &ArborGraph,
+    symbol: &str,
+    context: Option<&str>,
+) -> Result<NodeId, String> {
+    let normalized = symbol.replace("::", ".").replace('/', ".");
+    let segments: Vec<&str> = normalized.split('.').filter(|s| !s.is_empty()).collect();
+    let Some(name) = segments.last() else {
+        return Err("empty symbol".to_string());
+    };
+
+    let candidates: Vec<&CodeNode> = graph
+        .find_by_name(name)
+        .into_iter()
+        .filter(|n| n.kind != NodeKind::Import)
+        .filter(|n| n.qualified_name == normalized || names(n, &segments))
+        .collect();
+    let mut ids: Vec<NodeId> = candidates
+        .iter()
+        .filter_map(|n| graph.get_index(&n.id))
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    match ids.len() {
+        0 => Err(format!("no symbol matches '{}'", symbol)),
+        1 => Ok(ids[0]),
+        n => {
+            let dir = context.map(|c| Path::new(c).parent());
+            let near: Vec<NodeId> = ids
+                .iter()
+                .copied()
+                .filter(|&id| dir.is_some_and(|d| Path::new(&graph.graph[id].file).parent() == d))
+                .collect();
+            if near.len() == 1 {
+                Ok(near[0])
+            } else {
+                Err(format!("'{}' matches {} symbols", symbol, n))
+            }
+        }
+    }
+}
+
+/// Whether `segments` name `node`: some tail of them ends its qualified
+/// name and the rest appear, in order, among its path components.
+fn names(node: &CodeNode, segments: &[&str]) -> bool {
+    let qualified: Vec<&str> = node.qualified_name.split('.').collect();
+    let path: Vec<String> = Path::new(&node.file)
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    (1..=segments.len().min(qualified.len())).any(|tail| {
+        let (prefix, suffix) = segments.split_at(segments.len() - tail);
+        qualified.ends_with(suffix) && in_order(prefix, &path)
+    })
+}
+
+fn in_order(wanted: &[&str], path: &[String]) -> bool {
+    let mut rest = path.iter();
+    wanted.iter().all(|w| rest.any(|p| p == w))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_edges_file() {
+        let declarations = Declarations::parse(
+            r#"
+[[edge]]
+from = "orders::place_order"
+to = "billing::charge"
+
+[[edge]]
+from = "Plugin"
+to = "Registry"
+kind = "references"
+
+[[alias]]
+name = "charge_handler"
+symbol = "billing::charge"
+"#,
+        )
+        .unwrap();
+        assert_eq!(declarations.edges.len(), 2);
+        assert_eq!(declarations.edges[0].kind, EdgeKind::Calls);
+        assert_eq!(declarations.edges[1].kind, EdgeKind::References);
+        assert_eq!(declarations.aliases[0].symbol, "billing::charge");
+        assert!(Declarations::parse("[[edge]]\nfrom = \"a\"\ntoo = \"b\"").is_err());
+    }
+
+    #[test]
+    fn test_symbols_resolve_through_module_paths() {
+        let mut graph = ArborGraph::new();
+        let charge = graph.add_node(CodeNode::new(
+            "charge",
+            "charge",
+            NodeKind::Function,
+            "src/billing.rs",
+        ));
+        let total = graph.add_node(CodeNode::new(
+            "total",
+            "Invoice.total",
+            NodeKind::Method,
+            "src/billing/invoice.rs",
+        ));
+        graph.add_node(CodeNode::new(
+            "charge",
+            "charge",
+            NodeKind::Function,
+            "src/legacy/pay.rs",
+        ));
+
+        assert_eq!(resolve_symbol(&graph, "billing::charge", None), Ok(charge));
+        assert_eq!(
+            resolve_symbol(&graph, "billing.Invoice.total", None),
+            Ok(total)
+        );
+        assert_eq!(resolve_symbol(&graph, "Invoice::total", None), Ok(total));
+        assert_eq!(
+            resolve_symbol(&graph, "charge", Some("src/main.rs")),
+            Ok(charge)
+        );
+        assert_eq!(
+            resolve_symbol(&graph, "charge", None),
+            Err("'charge' matches 2 symbols".to_string())
+        );
+        assert!(resolve_symbol(&graph, "billing::refund", None).is_err());
+    }
+}
@@ -1,8 +1,8 @@
 //! Git-diff blast radius computation shared by CLI and MCP.
 
-use crate::{ArborGraph, NodeId};
+use crate::{ArborGraph, NodeId, NodeInfo, OwnerImpact, Ownership};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 /// Summary of blast radius for changed files (matches CLI `arbor diff` output).
@@ -17,6 +17,12 @@ pub struct BlastRadiusSummary {
     pub blast_radius_nodes: usize,
     pub mermaid_diagram: Option<String>,
     pub risk_level: String,
+    /// `CODEOWNERS` owners of the impacted nodes, most affected first.
+    #[serde(default)]
+    pub teams_affected: Vec<OwnerImpact>,
+    /// Primary authors of the impacted nodes, when blame was requested.
+    #[serde(default)]
+    pub authors_affected: Vec<OwnerImpact>,
 }
 
 fn normalize_slashes(input: &str) -> String {
@@ -68,21 +74,32 @@ fn risk_level_for(blast_radius_nodes: usize) -> String {
 }
 
 /// Compute blast radius summary from an indexed graph and changed file list.
+///
+/// With `ownership`, the impacted nodes are also grouped by owning team and
+/// primary author.
 pub fn compute_blast_radius(
     graph: &ArborGraph,
     changed_files: Vec<String>,
     changed_node_ids: Vec<NodeId>,
     max_depth: usize,
     project_root: &Path,
+    ownership: Option<&mut Ownership>,
 ) -> BlastRadiusSummary {
     let mut direct_callers = HashSet::new();
     let mut indirect_callers = HashSet::new();
     let mut affected_nodes = HashSet::new();
     let mut affected_files = HashSet::new();
+    let mut affected_infos: HashMap<String, NodeInfo> = HashMap::new();
 
     for node_id in changed_node_ids.iter().copied() {
         let analysis = graph.analyze_impact(node_id, max_depth);
 
+        for node in analysis.upstream.iter().chain(&analysis.downstream) {
+            affected_infos
+                .entry(node.node_info.id.clone())
+                .or_insert_with(|| node.node_info.clone());
+        }
+
         for up in &analysis.upstream {
             affected_nodes.insert(up.node_info.id.clone());
             affected_files.insert(up.node_info.file.clone());
@@ -179,6 +196,15 @@ pub fn compute_blast_radius(
 
     let blast_radius_nodes = affected_nodes.len();
 
+    let (teams_affected, authors_affected) = match ownership {
+        Some(ownership) => {
+            let mut infos: Vec<&NodeInfo> = affected_infos.values().collect();
+            infos.sort_by(|a, b| a.id.cmp(&b.id));
+            ownership.summarize(infos)
+        }
+        None => (Vec::new(), Vec::new()),
+    };
+
     BlastRadiusSummary {
         changed_files,
         changed_symbols: changed_node_ids.len(),
@@ -189,6 +215,8 @@ pub fn compute_blast_radius(
         blast_radius_nodes,
         mermaid_diagram,
         risk_level: risk_level_for(blast_radius_nodes),
+        teams_affected,
+        authors_affected,
     }
 }
 
@@ -201,11 +229,51 @@ mod tests {
     #[test]
     fn compute_blast_radius_empty_changes() {
         let graph = ArborGraph::new();
-        let summary = compute_blast_radius(&graph, vec![], vec![], 5, Path::new("."));
+        let summary = compute_blast_radius(&graph, vec![], vec![], 5, Path::new("."), None);
         assert_eq!(summary.blast_radius_nodes, 0);
         assert_eq!(summary.risk_level, "low");
     }
 
+    #[test]
+    fn compute_blast_radius_groups_impact_by_team() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("CODEOWNERS"),
+            "* @org/core\n/web/ @org/frontend\n",
+        )
+        .unwrap();
+
+        let mut graph = ArborGraph::new();
+        let file = |name: &str| dir.path().join(name).to_string_lossy().to_string();
+        let changed = graph.add_node(CodeNode::new(
+            "total",
+            "total",
+            NodeKind::Function,
+            file("src/cart.rs"),
+        ));
+        let caller = graph.add_node(CodeNode::new(
+            "render",
+            "render",
+            NodeKind::Function,
+            file("web/cart.ts"),
+        ));
+        graph.add_edge(caller, changed, crate::Edge::new(crate::EdgeKind::Calls));
+
+        let mut ownership = Ownership::load(dir.path(), false);
+        let summary = compute_blast_radius(
+            &graph,
+            vec!["src/cart.rs".to_string()],
+            vec![changed],
+            5,
+            dir.path(),
+            Some(&mut ownership),
+        );
+        assert_eq!(summary.teams_affected.len(), 1);
+        assert_eq!(summary.teams_affected[0].owner, "@org/frontend");
+        assert_eq!(summary.teams_affected[0].files, ["web/cart.ts"]);
+        assert!(summary.authors_affected.is_empty());
+    }
+
     #[test]
     fn changed_node_ids_finds_nodes_in_file() {
         let mut graph = ArborGraph::new();
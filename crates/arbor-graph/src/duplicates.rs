@@ -0,0 +1,560 @@
+//! Duplicate and near-duplicate function detection.
+//!
+//! Each function body becomes a normalized token stream (see
+//! [`arbor_core::normalized_tokens`]), so renamed variables, changed
+//! literals, comments and formatting don't hide a copy. The stream is hashed
+//! into overlapping k-grams and winnowed to a fingerprint set. MinHash
+//! signatures bucketed by LSH bands propose candidate pairs; each pair is
+//! then checked with the exact Jaccard similarity of the fingerprint sets.
+//! Pairs above the threshold are unioned into clone groups.
+
+use crate::graph::{ArborGraph, NodeId};
+use crate::query::NodeInfo;
+use crate::source::SourceReader;
+use arbor_core::{normalized_tokens, NodeKind, NormalizedToken};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// Tokens per k-gram.
+const K: usize = 5;
+/// Winnowing window, in k-grams.
+const WINDOW: usize = 4;
+/// MinHash signature length, split into `BANDS` bands of `ROWS` rows.
+const BANDS: usize = 16;
+const ROWS: usize = 4;
+/// Above this size an LSH bucket only pairs members with its first entries,
+/// so boilerplate shared by hundreds of functions stays linear.
+const MAX_BUCKET_PAIRS: usize = 64;
+
+/// Clone detection settings.
+#[derive(Debug, Clone)]
+pub struct DuplicateOptions {
+    /// Functions with fewer normalized tokens are ignored.
+    pub min_tokens: usize,
+    /// Minimum Jaccard similarity (0.0–1.0) of two fingerprints.
+    pub threshold: f64,
+}
+
+impl Default for DuplicateOptions {
+    fn default() -> Self {
+        Self {
+            min_tokens: 50,
+            threshold: 0.85,
+        }
+    }
+}
+
+/// One copy in a clone group.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneInstance {
+    #[serde(flatten)]
+    pub node_info: NodeInfo,
+    /// Length of the normalized token stream.
+    pub tokens: usize,
+}
+
+/// Functions that are copies of each other.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneGroup {
+    pub instances: Vec<CloneInstance>,
+    /// Mean Jaccard similarity of the matched pairs (1.0 = identical
+    /// after normalization).
+    pub similarity: f64,
+    /// Sum of the copies' centrality: how much of the codebase depends on
+    /// the duplicated logic.
+    pub combined_centrality: f64,
+    /// File extensions the copies live in.
+    pub languages: Vec<String>,
+}
+
+struct Candidate {
+    index: NodeId,
+    tokens: usize,
+    fingerprint: Vec<u64>,
+}
+
+impl ArborGraph {
+    /// Finds groups of duplicated functions, most central first.
+    ///
+    /// Bodies are read through `reader`; functions whose file changed since
+    /// indexing are skipped.
+    pub fn find_duplicates(
+        &self,
+        options: &DuplicateOptions,
+        reader: &mut SourceReader,
+    ) -> Vec<CloneGroup> {
+        let candidates = self.clone_candidates(options, reader);
+
+        let mut union = UnionFind::new(candidates.len());
+        let mut matched: Vec<(usize, usize, f64)> = Vec::new();
+        for (a, b) in candidate_pairs(&candidates) {
+            if self.nested(candidates[a].index, candidates[b].index) {
+                continue;
+            }
+            let similarity = jaccard(&candidates[a].fingerprint, &candidates[b].fingerprint);
+            if similarity >= options.threshold {
+                union.union(a, b);
+                matched.push((a, b, similarity));
+            }
+        }
+
+        let mut members: HashMap<usize, Vec<usize>> = HashMap::new();
+        for i in 0..candidates.len() {
+            members.entry(union.find(i)).or_default().push(i);
+        }
+        let mut similarities: HashMap<usize, Vec<f64>> = HashMap::new();
+        for (a, _, similarity) in matched {
+            similarities
+                .entry(union.find(a))
+                .or_default()
+                .push(similarity);
+        }
+
+        let mut groups: Vec<(Vec<NodeId>, CloneGroup)> = members
+            .into_iter()
+            .filter(|(_, m)| m.len() > 1)
+            .map(|(root, m)| {
+                let pairs = &similarities[&root];
+                let indexes: Vec<NodeId> = m.iter().map(|&i| candidates[i].index).collect();
+                let group = self.clone_group(&m, &candidates, pairs);
+                (indexes, group)
+            })
+            .collect();
+
+        // A clone nested inside copies of a larger clone adds nothing.
+        let covered: Vec<bool> = groups
+            .iter()
+            .map(|(inner, _)| {
+                groups.iter().any(|(outer, _)| {
+                    outer != inner
+                        && inner
+                            .iter()
+                            .all(|&i| outer.iter().any(|&o| self.nested(o, i)))
+                })
+            })
+            .collect();
+        let mut covered = covered.into_iter();
+        groups.retain(|_| !covered.next().unwrap_or(false));
+
+        let mut groups: Vec<CloneGroup> = groups.into_iter().map(|(_, g)| g).collect();
+        groups.sort_by(|a, b| {
+            b.combined_centrality
+                .total_cmp(&a.combined_centrality)
+                .then_with(|| b.instances.len().cmp(&a.instances.len()))
+                .then_with(|| b.instances[0].tokens.cmp(&a.instances[0].tokens))
+                .then_with(|| {
+                    a.instances[0]
+                        .node_info
+                        .id
+                        .cmp(&b.instances[0].node_info.id)
+                })
+        });
+        groups
+    }
+
+    /// Fingerprints every function-like node with a fresh, long enough body.
+    fn clone_candidates(
+        &self,
+        options: &DuplicateOptions,
+        reader: &mut SourceReader,
+    ) -> Vec<Candidate> {
+        let mut by_file: HashMap<&str, Vec<NodeId>> = HashMap::new();
+        for index in self.node_indexes() {
+            let node = &self.graph[index];
+            if matches!(
+                node.kind,
+                NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+            ) && node.byte_end > node.byte_start
+            {
+                by_file.entry(node.file.as_str()).or_default().push(index);
+            }
+        }
+
+        let mut files: Vec<_> = by_file.into_iter().collect();
+        files.sort_by_key(|(file, _)| *file);
+
+        let mut candidates = Vec::new();
+        for (file, indexes) in files {
+            let Some(bytes) = reader.file(file) else {
+                continue;
+            };
+            let Ok(source) = std::str::from_utf8(bytes) else {
+                continue;
+            };
+            let tokens = normalized_tokens(source, &extension(file));
+
+            for index in indexes {
+                let node = &self.graph[index];
+                let (start, end) = (node.byte_start as usize, node.byte_end as usize);
+                if !source
+                    .as_bytes()
+                    .get(start..end)
+                    .is_some_and(|range| node.matches_source(range))
+                {
+                    continue;
+                }
+                let body = body_tokens(&tokens, start, end);
+                if body.len() < options.min_tokens.max(K) {
+                    continue;
+                }
+                candidates.push(Candidate {
+                    index,
+                    tokens: body.len(),
+                    fingerprint: winnow(body),
+                });
+            }
+        }
+        candidates
+    }
+
+    fn clone_group(
+        &self,
+        members: &[usize],
+        candidates: &[Candidate],
+        pairs: &[f64],
+    ) -> CloneGroup {
+        let mut instances: Vec<CloneInstance> = members
+            .iter()
+            .map(|&i| {
+                let candidate = &candidates[i];
+                let mut node_info = NodeInfo::from(&self.graph[candidate.index]);
+                node_info.centrality = self.centrality(candidate.index);
+                CloneInstance {
+                    node_info,
+                    tokens: candidate.tokens,
+                }
+            })
+            .collect();
+        instances.sort_by(|a, b| {
+            b.node_info
+                .centrality
+                .total_cmp(&a.node_info.centrality)
+                .then_with(|| a.node_info.file.cmp(&b.node_info.file))
+                .then_with(|| a.node_info.line_start.cmp(&b.node_info.line_start))
+        });
+
+        let mut languages: Vec<String> = instances
+            .iter()
+            .map(|i| extension(&i.node_info.file))
+            .collect();
+        languages.sort();
+        languages.dedup();
+
+        CloneGroup {
+            combined_centrality: instances.iter().map(|i| i.node_info.centrality).sum(),
+            similarity: pairs.iter().sum::<f64>() / pairs.len().max(1) as f64,
+            instances,
+            languages,
+        }
+    }
+
+    /// Whether one node's byte range contains the other's in the same file.
+    fn nested(&self, outer: NodeId, inner: NodeId) -> bool {
+        let (a, b) = (&self.graph[outer], &self.graph[inner]);
+        a.file == b.file
+            && ((a.byte_start <= b.byte_start && b.byte_end <= a.byte_end)
+                || (b.byte_start <= a.byte_start && a.byte_end <= b.byte_end))
+    }
+}
+
+fn extension(file: &str) -> String {
+    Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+/// The tokens starting inside `start..end`, as hashes.
+fn body_tokens(tokens: &[NormalizedToken], start: usize, end: usize) -> Vec<u64> {
+    let first = tokens.partition_point(|t| t.start < start);
+    tokens[first..]
+        .iter()
+        .take_while(|t| t.start < end)
+        .map(|t| t.hash)
+        .collect()
+}
+
+/// SplitMix64 finalizer: a cheap, well-mixed 64-bit hash.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// Winnowed k-gram fingerprint of a token stream, sorted and deduplicated.
+fn winnow(tokens: Vec<u64>) -> Vec<u64> {
+    let grams: Vec<u64> = tokens
+        .windows(K)
+        .map(|gram| gram.iter().fold(0u64, |h, &t| mix(h ^ t)))
+        .collect();
+
+    let mut fingerprint: Vec<u64> = if grams.len() <= WINDOW {
+        grams
+    } else {
+        let mut selected = Vec::new();
+        let mut last = usize::MAX;
+        for (offset, window) in grams.windows(WINDOW).enumerate() {
+            // Rightmost minimum, recorded once per position.
+            let (pos, &hash) = window
+                .iter()
+                .enumerate()
+                .rev()
+                .min_by_key(|(_, &h)| h)
+                .expect("window is non-empty");
+            if offset + pos != last {
+                last = offset + pos;
+                selected.push(hash);
+            }
+        }
+        selected
+    };
+    fingerprint.sort_unstable();
+    fingerprint.dedup();
+    fingerprint
+}
+
+fn minhash(fingerprint: &[u64]) -> [u64; BANDS * ROWS] {
+    let mut signature = [u64::MAX; BANDS * ROWS];
+    for &hash in fingerprint {
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            *slot = (*slot).min(mix(hash ^ mix(seed as u64 + 1)));
+        }
+    }
+    signature
+}
+
+/// Pairs that share at least one LSH band, each listed once with `a < b`.
+fn candidate_pairs(candidates: &[Candidate]) -> Vec<(usize, usize)> {
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (i, candidate) in candidates.iter().enumerate() {
+        let signature = minhash(&candidate.fingerprint);
+        for (band, rows) in signature.chunks(ROWS).enumerate() {
+            let key = rows.iter().fold(0u64, |h, &r| mix(h ^ r));
+            buckets.entry((band, key)).or_default().push(i);
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for bucket in buckets.values().filter(|b| b.len() > 1) {
+        for (pos, &b) in bucket.iter().enumerate() {
+            for &a in &bucket[..pos.min(MAX_BUCKET_PAIRS)] {
+                pairs.insert((a.min(b), a.max(b)));
+            }
+        }
+    }
+    let mut pairs: Vec<_> = pairs.into_iter().collect();
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Jaccard similarity of two sorted, deduplicated sets.
+fn jaccard(a: &[u64], b: &[u64]) -> f64 {
+    let (mut i, mut j, mut shared) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                shared += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f64 / total as f64
+}
+
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbor_core::{stamp_content_hashes, CodeNode};
+
+    const RUST_COPY: &str = r#"
+fn checksum(data: &[u8]) -> u32 {
+    let mut total: u32 = 0;
+    for (i, byte) in data.iter().enumerate() {
+        if i % 2 == 0 {
+            total = total.wrapping_add(*byte as u32 * 31);
+        } else {
+            total ^= (*byte as u32) << 3;
+        }
+    }
+    total.rotate_left(5)
+}
+
+fn digest(input: &[u8]) -> u32 {
+    // Same algorithm, renamed.
+    let mut acc: u32 = 7;
+    for (idx, b) in input.iter().enumerate() {
+        if idx % 2 == 0 {
+            acc = acc.wrapping_add(*b as u32 * 17);
+        } else {
+            acc ^= (*b as u32) << 3;
+        }
+    }
+    acc.rotate_left(5)
+}
+
+fn unrelated(names: &[String]) -> String {
+    let mut out = String::new();
+    for name in names {
+        out.push_str(name);
+        out.push(',');
+    }
+    out.trim_end_matches(',').to_string()
+}
+"#;
+
+    fn index(graph: &mut ArborGraph, file: &str, source: &str, names: &[&str]) {
+        let mut nodes: Vec<CodeNode> = names
+            .iter()
+            .map(|name| {
+                let start = source.find(&format!("fn {}", name)).unwrap();
+                let end = source[start..].find("\n}\n").unwrap() + start + 2;
+                CodeNode::new(*name, *name, NodeKind::Function, file)
+                    .with_bytes(start as u32, end as u32)
+            })
+            .collect();
+        stamp_content_hashes(&mut nodes, source);
+        for node in nodes {
+            graph.add_node(node);
+        }
+    }
+
+    #[test]
+    fn test_renamed_copies_are_grouped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), RUST_COPY).unwrap();
+        let mut graph = ArborGraph::new();
+        index(
+            &mut graph,
+            "a.rs",
+            RUST_COPY,
+            &["checksum", "digest", "unrelated"],
+        );
+
+        let options = DuplicateOptions {
+            min_tokens: 20,
+            ..Default::default()
+        };
+        let groups = graph.find_duplicates(&options, &mut SourceReader::with_root(dir.path()));
+        assert_eq!(groups.len(), 1);
+        let mut names: Vec<_> = groups[0]
+            .instances
+            .iter()
+            .map(|i| i.node_info.name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["checksum", "digest"]);
+        assert!((groups[0].similarity - 1.0).abs() < 1e-9);
+        assert_eq!(groups[0].languages, ["rs"]);
+
+        // Too short to count.
+        let strict = DuplicateOptions {
+            min_tokens: 500,
+            ..Default::default()
+        };
+        assert!(graph
+            .find_duplicates(&strict, &mut SourceReader::with_root(dir.path()))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_clones_across_languages_and_stale_files() {
+        let ts = r#"
+function checksum(data) {
+    let total = 0;
+    for (const byte of data) {
+        if (byte % 2 == 0) {
+            total = total + byte * 31 - (byte >> 1) + (byte << 2);
+        } else {
+            total = total - byte * 17 + (byte >> 3) - (byte << 1);
+        }
+    }
+    return total;
+}
+"#;
+        let py = r#"
+def checksum(data):
+    total = 0
+    for byte in data:
+        if (byte % 2 == 0):
+            total = total + byte * 31 - (byte >> 1) + (byte << 2)
+        else:
+            total = total - byte * 17 + (byte >> 3) - (byte << 1)
+    return total
+"#;
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ts"), ts).unwrap();
+        std::fs::write(dir.path().join("b.py"), py).unwrap();
+
+        let mut graph = ArborGraph::new();
+        let mut ts_node = CodeNode::new("checksum", "checksum", NodeKind::Function, "a.ts")
+            .with_bytes(1, ts.len() as u32 - 1);
+        stamp_content_hashes(std::slice::from_mut(&mut ts_node), ts);
+        let mut py_node = CodeNode::new("checksum", "checksum", NodeKind::Function, "b.py")
+            .with_bytes(1, py.len() as u32 - 1);
+        stamp_content_hashes(std::slice::from_mut(&mut py_node), py);
+        graph.add_node(ts_node);
+        graph.add_node(py_node);
+
+        let options = DuplicateOptions {
+            min_tokens: 20,
+            threshold: 0.6,
+        };
+        let groups = graph.find_duplicates(&options, &mut SourceReader::with_root(dir.path()));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].languages, ["py", "ts"]);
+        assert!(groups[0].similarity < 1.0);
+
+        // An edited file no longer matches its indexed hash and is skipped.
+        std::fs::write(dir.path().join("b.py"), py.replace("31", "99")).unwrap();
+        assert!(graph
+            .find_duplicates(&options, &mut SourceReader::with_root(dir.path()))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_winnow_and_jaccard() {
+        let tokens: Vec<u64> = (0..40).map(|i| mix(i % 13)).collect();
+        let fingerprint = winnow(tokens.clone());
+        assert!(!fingerprint.is_empty());
+        assert!(fingerprint.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(jaccard(&fingerprint, &winnow(tokens)), 1.0);
+        assert_eq!(jaccard(&[1, 2, 3, 4], &[3, 4, 5, 6]), 2.0 / 6.0);
+        assert_eq!(jaccard(&[], &[]), 0.0);
+    }
+}
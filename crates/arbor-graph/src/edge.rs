@@ -38,6 +38,17 @@ pub enum EdgeKind {
     /// Data dependency: variable use depends on definition.
     /// Used for DFA (Data Flow Analysis) edges.
     DataDependency,
+
+    /// Generated code points at the IDL definition it was generated from
+    /// (e.g. a `*.pb.go` struct → its `.proto` message).
+    GeneratedFrom,
+
+    /// Function A publishes to message topic B.
+    Publishes,
+
+    /// Message topic A delivers to subscriber B. Points from the topic so
+    /// that publisher → topic → subscriber reads like a call chain.
+    Subscribes,
 }
 
 impl std::fmt::Display for EdgeKind {
@@ -52,11 +63,107 @@ impl std::fmt::Display for EdgeKind {
             Self::Contains => "contains",
             Self::FlowsTo => "flows_to",
             Self::DataDependency => "data_dependency",
+            Self::GeneratedFrom => "generated_from",
+            Self::Publishes => "publishes",
+            Self::Subscribes => "subscribes",
         };
         write!(f, "{}", s)
     }
 }
 
+/// How the graph builder matched the reference behind an edge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Provenance {
+    /// The reference named the target's qualified name exactly, or the
+    /// parser declared the link outright.
+    #[default]
+    ExactFqn,
+    /// The name matched on its end and the file imports it.
+    ImportMap,
+    /// The name matched on its end, in the referencing file's directory.
+    SameDirSuffix,
+    /// The name matched on its end, on the only candidate in the repo.
+    GlobalSuffix,
+    /// Inferred from structure rather than a reference, e.g. Go method
+    /// sets or generated code naming.
+    Heuristic,
+    /// Declared by the user rather than found in the code.
+    UserDeclared,
+}
+
+impl Provenance {
+    /// Every provenance, most certain first.
+    pub const ALL: [Provenance; 6] = [
+        Self::ExactFqn,
+        Self::UserDeclared,
+        Self::ImportMap,
+        Self::SameDirSuffix,
+        Self::GlobalSuffix,
+        Self::Heuristic,
+    ];
+
+    /// How likely an edge resolved this way points at the right target.
+    pub fn confidence(self) -> f32 {
+        match self {
+            Self::ExactFqn | Self::UserDeclared => 1.0,
+            Self::ImportMap => 0.95,
+            Self::SameDirSuffix => 0.85,
+            Self::GlobalSuffix => 0.7,
+            Self::Heuristic => 0.6,
+        }
+    }
+
+    /// The snake_case name used in JSON and on the command line.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ExactFqn => "exact_fqn",
+            Self::ImportMap => "import_map",
+            Self::SameDirSuffix => "same_dir_suffix",
+            Self::GlobalSuffix => "global_suffix",
+            Self::Heuristic => "heuristic",
+            Self::UserDeclared => "user_declared",
+        }
+    }
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::ExactFqn => "exact FQN match",
+            Self::ImportMap => "import-map match",
+            Self::SameDirSuffix => "same-dir suffix match",
+            Self::GlobalSuffix => "global suffix match",
+            Self::Heuristic => "heuristic",
+            Self::UserDeclared => "user declaration",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for Provenance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str() == normalized)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|p| p.as_str()).collect();
+                format!(
+                    "unknown provenance '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+fn certain() -> f32 {
+    1.0
+}
+
 /// An edge in the code graph with location info.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Edge {
@@ -68,6 +175,19 @@ pub struct Edge {
 
     /// Line number where this edge originates.
     pub line: Option<u32>,
+
+    /// How the reference was matched to its target.
+    #[serde(default)]
+    pub provenance: Provenance,
+
+    /// The reference text as the parser read it, e.g. `helper` or
+    /// `utils.helper`.
+    #[serde(default)]
+    pub reference: Option<String>,
+
+    /// How likely the edge points at the right target, 0.0–1.0.
+    #[serde(default = "certain")]
+    pub confidence: f32,
 }
 
 impl Edge {
@@ -77,17 +197,40 @@ impl Edge {
             kind,
             file: None,
             line: None,
+            provenance: Provenance::ExactFqn,
+            reference: None,
+            confidence: 1.0,
         }
     }
 
     /// Creates an edge with location info.
     pub fn with_location(kind: EdgeKind, file: impl Into<String>, line: u32) -> Self {
         Self {
-            kind,
             file: Some(file.into()),
             line: Some(line),
+            ..Self::new(kind)
         }
     }
+
+    /// Records how the edge's reference was matched, with that match's
+    /// default confidence.
+    pub fn with_provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = provenance;
+        self.confidence = provenance.confidence();
+        self
+    }
+
+    /// Overrides the confidence implied by the provenance.
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.confidence = confidence.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Records the reference text the edge was resolved from.
+    pub fn with_reference(mut self, reference: impl Into<String>) -> Self {
+        self.reference = Some(reference.into());
+        self
+    }
 }
 
 /// A simplified edge for graph export/visualization.
@@ -96,4 +239,8 @@ pub struct GraphEdge {
     pub source: String,
     pub target: String,
     pub kind: EdgeKind,
+    #[serde(default)]
+    pub provenance: Provenance,
+    #[serde(default = "certain")]
+    pub confidence: f32,
 }
@@ -0,0 +1,816 @@
+//! Local semantic embeddings for symbol similarity search.
+//!
+//! Optional: nothing here runs unless `.arbor/embeddings.toml` names a
+//! provider and the user allowed providers to run. Each embeddable node is turned into text (kind, name,
+//! docstring, signature and body), embedded by the provider, stored in the
+//! `GraphStore` keyed by a hash of that text, and loaded into an HNSW index
+//! for "find code like this" and natural-language symbol queries.
+//!
+//! Two providers are supported, neither of which touches the network:
+//!
+//! - `command`: an external program that reads one JSON string per line on
+//!   stdin and writes one JSON array of numbers per line on stdout.
+//! - `onnx`: a sentence-embedding model file plus its `tokenizer.json`,
+//!   run in-process (requires building with the `onnx` feature).
+//!
+//! ```toml
+//! # .arbor/embeddings.toml
+//! provider = "command"
+//! command = ["python3", "scripts/embed.py"]
+//! ```
+//!
+//! Both providers run code named by the repository, so a checked-in config
+//! is never enough: the user opts in with `--allow-embeddings` or
+//! [`ALLOW_EMBEDDINGS_ENV`], like grammar plugins.
+
+use crate::ann::{normalize, Hnsw};
+use crate::graph::ArborGraph;
+use crate::source::{NodeSource, SourceReader};
+use crate::store::{GraphStore, StoreError};
+use arbor_core::{content_hash, CodeNode, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use thiserror::Error;
+
+/// Config file, relative to the project root.
+pub const EMBEDDINGS_CONFIG: &str = ".arbor/embeddings.toml";
+
+/// Environment variable that allows embedding providers when set to `1`/`true`.
+pub const ALLOW_EMBEDDINGS_ENV: &str = "ARBOR_ALLOW_EMBEDDINGS";
+
+/// Set by [`allow_embeddings`] for the rest of the process.
+static EMBEDDINGS_ALLOWED: AtomicBool = AtomicBool::new(false);
+
+/// Allows [`EmbeddingConfig::provider`] to run the configured provider for
+/// the rest of the process, as `--allow-embeddings` does.
+pub fn allow_embeddings() {
+    EMBEDDINGS_ALLOWED.store(true, Ordering::Relaxed);
+}
+
+/// True if the user allowed embedding providers, with [`allow_embeddings`]
+/// or [`ALLOW_EMBEDDINGS_ENV`].
+pub fn embeddings_allowed() -> bool {
+    EMBEDDINGS_ALLOWED.load(Ordering::Relaxed)
+        || std::env::var(ALLOW_EMBEDDINGS_ENV)
+            .is_ok_and(|value| matches!(value.to_lowercase().as_str(), "1" | "true" | "yes"))
+}
+
+/// Candidate list size for ANN queries.
+const EF_SEARCH: usize = 64;
+
+#[derive(Error, Debug)]
+pub enum EmbeddingError {
+    #[error("Invalid embedding config: {0}")]
+    Config(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Embedding provider failed: {0}")]
+    Provider(String),
+    #[error("Embedding store error: {0}")]
+    Store(#[from] StoreError),
+    #[error(
+        "Embedding providers run programs named by the repository; pass --allow-embeddings or set {ALLOW_EMBEDDINGS_ENV}=1 to use them"
+    )]
+    NotAllowed,
+}
+
+/// Turns text into vectors.
+pub trait EmbeddingProvider: Send + Sync {
+    /// Identifies the model; stored vectors from a different one are discarded.
+    fn fingerprint(&self) -> String;
+
+    /// Embeds each text; the result has one vector per input, in order.
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    Command,
+    Onnx,
+}
+
+/// Contents of `.arbor/embeddings.toml`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingConfig {
+    pub provider: ProviderKind,
+    /// Program and arguments for the `command` provider.
+    #[serde(default)]
+    pub command: Vec<String>,
+    /// Model file for the `onnx` provider, relative to the project root.
+    pub model: Option<PathBuf>,
+    /// `tokenizer.json` for the `onnx` provider, relative to the project root.
+    pub tokenizer: Option<PathBuf>,
+    /// Texts per provider call.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+    /// Body text beyond this many characters is cut before embedding.
+    #[serde(default = "default_max_chars")]
+    pub max_chars: usize,
+}
+
+fn default_batch_size() -> usize {
+    32
+}
+
+fn default_max_chars() -> usize {
+    2000
+}
+
+impl EmbeddingConfig {
+    /// Reads the project's config, or `None` if embeddings aren't configured.
+    pub fn load(root: &Path) -> Result<Option<Self>, EmbeddingError> {
+        let path = root.join(EMBEDDINGS_CONFIG);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&text)
+            .map_err(|e| EmbeddingError::Config(format!("{}: {}", path.display(), e)))?;
+        Ok(Some(config))
+    }
+
+    /// Creates the configured provider; relative paths resolve against `root`.
+    ///
+    /// Fails with [`EmbeddingError::NotAllowed`] unless [`embeddings_allowed`].
+    pub fn provider(&self, root: &Path) -> Result<Box<dyn EmbeddingProvider>, EmbeddingError> {
+        self.provider_if(root, embeddings_allowed())
+    }
+
+    fn provider_if(
+        &self,
+        root: &Path,
+        allowed: bool,
+    ) -> Result<Box<dyn EmbeddingProvider>, EmbeddingError> {
+        if !allowed {
+            return Err(EmbeddingError::NotAllowed);
+        }
+        match self.provider {
+            ProviderKind::Command => {
+                let Some((program, args)) = self.command.split_first() else {
+                    return Err(EmbeddingError::Config(
+                        "provider = \"command\" needs a `command` list".into(),
+                    ));
+                };
+                Ok(Box::new(CommandProvider {
+                    program: program.clone(),
+                    args: args.to_vec(),
+                    cwd: root.to_path_buf(),
+                }))
+            }
+            ProviderKind::Onnx => {
+                let (Some(model), Some(tokenizer)) = (&self.model, &self.tokenizer) else {
+                    return Err(EmbeddingError::Config(
+                        "provider = \"onnx\" needs `model` and `tokenizer` paths".into(),
+                    ));
+                };
+                onnx_provider(&root.join(model), &root.join(tokenizer))
+            }
+        }
+    }
+}
+
+/// Runs an external program per batch: texts in as JSON lines, vectors out.
+#[derive(Debug, Clone)]
+pub struct CommandProvider {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+}
+
+impl EmbeddingProvider for CommandProvider {
+    fn fingerprint(&self) -> String {
+        format!("command:{} {}", self.program, self.args.join(" "))
+    }
+
+    fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .current_dir(&self.cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| EmbeddingError::Provider(format!("{}: {}", self.program, e)))?;
+
+        // Write from a thread so a provider streaming output can't deadlock us.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input: String = texts
+            .iter()
+            .map(|text| serde_json::to_string(text).unwrap_or_default() + "\n")
+            .collect();
+        let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut vectors = Vec::with_capacity(texts.len());
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let vector: Vec<f32> = serde_json::from_str(&line).map_err(|e| {
+                EmbeddingError::Provider(format!("expected a JSON array of numbers: {}", e))
+            })?;
+            vectors.push(vector);
+        }
+
+        let _ = writer.join();
+        let status = child.wait()?;
+        if !status.success() {
+            return Err(EmbeddingError::Provider(format!(
+                "{} exited with {}",
+                self.program, status
+            )));
+        }
+        if vectors.len() != texts.len() {
+            return Err(EmbeddingError::Provider(format!(
+                "expected {} vectors, got {}",
+                texts.len(),
+                vectors.len()
+            )));
+        }
+        Ok(vectors)
+    }
+}
+
+#[cfg(not(feature = "onnx"))]
+fn onnx_provider(
+    _model: &Path,
+    _tokenizer: &Path,
+) -> Result<Box<dyn EmbeddingProvider>, EmbeddingError> {
+    Err(EmbeddingError::Config(
+        "ONNX models need Arbor built with the `onnx` feature; use provider = \"command\" instead"
+            .into(),
+    ))
+}
+
+#[cfg(feature = "onnx")]
+fn onnx_provider(
+    model: &Path,
+    tokenizer: &Path,
+) -> Result<Box<dyn EmbeddingProvider>, EmbeddingError> {
+    Ok(Box::new(onnx::OnnxProvider::load(model, tokenizer)?))
+}
+
+#[cfg(feature = "onnx")]
+mod onnx {
+    //! In-process ONNX sentence embeddings: tokenize, run the model, mean-pool.
+    //!
+    //! ONNX Runtime is loaded at runtime (`ORT_DYLIB_PATH`, or the system
+    //! library), so the build needs no download.
+
+    use super::{EmbeddingError, EmbeddingProvider};
+    use ort::session::{Session, SessionInputValue};
+    use ort::value::Tensor;
+    use std::borrow::Cow;
+    use std::path::Path;
+    use std::sync::Mutex;
+    use tokenizers::{Tokenizer, TruncationParams};
+
+    /// Tokens per text; sentence-transformer models are trained on short inputs.
+    const MAX_SEQUENCE: usize = 256;
+
+    pub struct OnnxProvider {
+        session: Mutex<Session>,
+        tokenizer: Tokenizer,
+        fingerprint: String,
+    }
+
+    fn provider_error(error: impl std::fmt::Display) -> EmbeddingError {
+        EmbeddingError::Provider(error.to_string())
+    }
+
+    impl OnnxProvider {
+        pub fn load(model: &Path, tokenizer: &Path) -> Result<Self, EmbeddingError> {
+            let session = Session::builder()
+                .and_then(|builder| builder.commit_from_file(model))
+                .map_err(provider_error)?;
+            let mut tokenizer = Tokenizer::from_file(tokenizer).map_err(provider_error)?;
+            tokenizer
+                .with_truncation(Some(TruncationParams {
+                    max_length: MAX_SEQUENCE,
+                    ..TruncationParams::default()
+                }))
+                .map_err(provider_error)?;
+
+            let size = std::fs::metadata(model)?.len();
+            Ok(Self {
+                session: Mutex::new(session),
+                tokenizer,
+                fingerprint: format!("onnx:{}:{}", model.display(), size),
+            })
+        }
+    }
+
+    impl EmbeddingProvider for OnnxProvider {
+        fn fingerprint(&self) -> String {
+            self.fingerprint.clone()
+        }
+
+        fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+            if texts.is_empty() {
+                return Ok(Vec::new());
+            }
+            let encodings = self
+                .tokenizer
+                .encode_batch(texts.to_vec(), true)
+                .map_err(provider_error)?;
+            let batch = encodings.len();
+            let width = encodings.iter().map(|e| e.len()).max().unwrap_or(0).max(1);
+
+            // Right-pad every sequence to the longest one.
+            let mut ids = vec![0i64; batch * width];
+            let mut mask = vec![0i64; batch * width];
+            let mut types = vec![0i64; batch * width];
+            for (row, encoding) in encodings.iter().enumerate() {
+                for (col, ((&id, &m), &t)) in encoding
+                    .get_ids()
+                    .iter()
+                    .zip(encoding.get_attention_mask())
+                    .zip(encoding.get_type_ids())
+                    .enumerate()
+                {
+                    ids[row * width + col] = id as i64;
+                    mask[row * width + col] = m as i64;
+                    types[row * width + col] = t as i64;
+                }
+            }
+
+            let shape = [batch, width];
+            let mut session = self.session.lock().map_err(provider_error)?;
+            let wants_types = session.inputs.iter().any(|i| i.name == "token_type_ids");
+            let mut inputs: Vec<(Cow<str>, SessionInputValue)> = vec![
+                (
+                    "input_ids".into(),
+                    Tensor::from_array((shape, ids))
+                        .map_err(provider_error)?
+                        .into(),
+                ),
+                (
+                    "attention_mask".into(),
+                    Tensor::from_array((shape, mask.clone()))
+                        .map_err(provider_error)?
+                        .into(),
+                ),
+            ];
+            if wants_types {
+                inputs.push((
+                    "token_type_ids".into(),
+                    Tensor::from_array((shape, types))
+                        .map_err(provider_error)?
+                        .into(),
+                ));
+            }
+
+            let outputs = session.run(inputs).map_err(provider_error)?;
+            let (dims, data) = outputs[0]
+                .try_extract_tensor::<f32>()
+                .map_err(provider_error)?;
+
+            match dims.len() {
+                // Already pooled: [batch, hidden].
+                2 => {
+                    let hidden = dims[1] as usize;
+                    Ok(data.chunks(hidden).map(<[f32]>::to_vec).collect())
+                }
+                // Token states: [batch, seq, hidden]; mean over unmasked tokens.
+                3 => {
+                    let (seq, hidden) = (dims[1] as usize, dims[2] as usize);
+                    Ok((0..batch)
+                        .map(|row| {
+                            let mut pooled = vec![0f32; hidden];
+                            let mut count = 0f32;
+                            for col in 0..seq.min(width) {
+                                if mask[row * width + col] == 0 {
+                                    continue;
+                                }
+                                let offset = (row * seq + col) * hidden;
+                                for (p, x) in pooled.iter_mut().zip(&data[offset..offset + hidden])
+                                {
+                                    *p += x;
+                                }
+                                count += 1.0;
+                            }
+                            pooled.iter_mut().for_each(|p| *p /= count.max(1.0));
+                            pooled
+                        })
+                        .collect())
+                }
+                _ => Err(EmbeddingError::Provider(format!(
+                    "unexpected model output shape {:?}",
+                    &dims[..]
+                ))),
+            }
+        }
+    }
+}
+
+/// Kinds worth embedding: things with a body or a meaningful declaration.
+fn is_embeddable(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function
+            | NodeKind::Method
+            | NodeKind::Constructor
+            | NodeKind::Class
+            | NodeKind::Struct
+            | NodeKind::Interface
+            | NodeKind::Enum
+            | NodeKind::TypeAlias
+            | NodeKind::Module
+    )
+}
+
+/// The text embedded for a node: kind and name, docstring, then the body
+/// (cut to `max_chars`) or the signature if the body isn't available.
+pub fn embedding_text(node: &CodeNode, body: Option<&str>, max_chars: usize) -> String {
+    let mut text = format!("{} {}", node.kind, node.qualified_name);
+    if let Some(doc) = &node.docstring {
+        text.push('\n');
+        text.push_str(doc.trim());
+    }
+    match (body, &node.signature) {
+        (Some(body), _) => {
+            text.push('\n');
+            let end = body
+                .char_indices()
+                .nth(max_chars)
+                .map_or(body.len(), |(i, _)| i);
+            text.push_str(&body[..end]);
+        }
+        (None, Some(signature)) => {
+            text.push('\n');
+            text.push_str(signature);
+        }
+        (None, None) => {}
+    }
+    text
+}
+
+/// A node similar to a query.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarityHit {
+    pub node_id: String,
+    /// Cosine similarity (-1.0 to 1.0).
+    pub similarity: f32,
+}
+
+/// How an index build went.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct EmbeddingStats {
+    /// Vectors computed by the provider.
+    pub embedded: usize,
+    /// Vectors reused from the store.
+    pub reused: usize,
+}
+
+/// Nearest-neighbor index over node embeddings.
+#[derive(Debug)]
+pub struct EmbeddingIndex {
+    ids: Vec<String>,
+    positions: HashMap<String, usize>,
+    ann: Hnsw,
+    dimensions: usize,
+}
+
+impl EmbeddingIndex {
+    /// Embeds every embeddable node in the graph.
+    ///
+    /// With a store, vectors whose text hasn't changed are reused and new
+    /// ones are saved, so only edited symbols hit the provider.
+    pub fn build(
+        graph: &ArborGraph,
+        provider: &dyn EmbeddingProvider,
+        store: Option<&GraphStore>,
+        reader: &mut SourceReader,
+        config: &EmbeddingConfig,
+    ) -> Result<(Self, EmbeddingStats), EmbeddingError> {
+        Self::build_from(graph.nodes(), provider, store, reader, config)
+    }
+
+    /// Like [`EmbeddingIndex::build`], over nodes taken out of a graph, so
+    /// a build can run on another thread without borrowing the graph.
+    pub fn build_from<'a>(
+        nodes: impl IntoIterator<Item = &'a CodeNode>,
+        provider: &dyn EmbeddingProvider,
+        store: Option<&GraphStore>,
+        reader: &mut SourceReader,
+        config: &EmbeddingConfig,
+    ) -> Result<(Self, EmbeddingStats), EmbeddingError> {
+        if let Some(store) = store {
+            store.set_embedding_model(&provider.fingerprint())?;
+        }
+
+        let mut nodes: Vec<&CodeNode> = nodes
+            .into_iter()
+            .filter(|n| is_embeddable(n.kind))
+            .collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut vectors: Vec<Option<Vec<f32>>> = vec![None; nodes.len()];
+        let mut pending: Vec<(usize, u64, String)> = Vec::new();
+        let mut stats = EmbeddingStats::default();
+        for (position, node) in nodes.iter().enumerate() {
+            let body = match reader.read(node) {
+                NodeSource::Fresh(text) => Some(text),
+                NodeSource::Stale | NodeSource::Unavailable => None,
+            };
+            let text = embedding_text(node, body.as_deref(), config.max_chars);
+            let hash = content_hash(text.as_bytes());
+            let stored = match store {
+                Some(store) => store.get_embedding(&node.id)?,
+                None => None,
+            };
+            match stored {
+                Some((stored_hash, vector)) if stored_hash == hash => {
+                    vectors[position] = Some(vector);
+                    stats.reused += 1;
+                }
+                _ => pending.push((position, hash, text)),
+            }
+        }
+
+        for chunk in pending.chunks(config.batch_size.max(1)) {
+            let texts: Vec<String> = chunk.iter().map(|(_, _, text)| text.clone()).collect();
+            let embedded = provider.embed(&texts)?;
+            if embedded.len() != chunk.len() {
+                return Err(EmbeddingError::Provider(format!(
+                    "expected {} vectors, got {}",
+                    chunk.len(),
+                    embedded.len()
+                )));
+            }
+            if let Some(store) = store {
+                let entries: Vec<(String, u64, Vec<f32>)> = chunk
+                    .iter()
+                    .zip(&embedded)
+                    .map(|((position, hash, _), vector)| {
+                        (nodes[*position].id.clone(), *hash, vector.clone())
+                    })
+                    .collect();
+                store.set_embeddings(&entries)?;
+            }
+            for ((position, _, _), vector) in chunk.iter().zip(embedded) {
+                vectors[*position] = Some(vector);
+            }
+            stats.embedded += chunk.len();
+        }
+
+        let mut index = Self {
+            ids: Vec::with_capacity(nodes.len()),
+            positions: HashMap::with_capacity(nodes.len()),
+            ann: Hnsw::new(),
+            dimensions: 0,
+        };
+        for (node, vector) in nodes.iter().zip(vectors) {
+            if let Some(vector) = vector {
+                index.insert(&node.id, vector)?;
+            }
+        }
+        Ok((index, stats))
+    }
+
+    /// Adds a vector for `node_id`; all vectors must have the same length.
+    fn insert(&mut self, node_id: &str, mut vector: Vec<f32>) -> Result<(), EmbeddingError> {
+        if self.dimensions == 0 {
+            self.dimensions = vector.len();
+        }
+        if vector.is_empty() || vector.len() != self.dimensions {
+            return Err(EmbeddingError::Provider(format!(
+                "vector for {} has {} dimensions, expected {}",
+                node_id,
+                vector.len(),
+                self.dimensions
+            )));
+        }
+        normalize(&mut vector);
+        self.positions.insert(node_id.to_string(), self.ids.len());
+        self.ids.push(node_id.to_string());
+        self.ann.insert(vector);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Length of the stored vectors (0 when empty).
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// The `k` nodes closest to `vector`, most similar first.
+    pub fn nearest(&self, vector: &[f32], k: usize) -> Vec<SimilarityHit> {
+        if vector.len() != self.dimensions {
+            return Vec::new();
+        }
+        let mut query = vector.to_vec();
+        normalize(&mut query);
+        self.ann
+            .search(&query, k, EF_SEARCH.max(k * 2))
+            .into_iter()
+            .map(|(position, similarity)| SimilarityHit {
+                node_id: self.ids[position].clone(),
+                similarity,
+            })
+            .collect()
+    }
+
+    /// The `k` nodes most similar to an indexed node, excluding itself.
+    pub fn similar_to(&self, node_id: &str, k: usize) -> Vec<SimilarityHit> {
+        let Some(&position) = self.positions.get(node_id) else {
+            return Vec::new();
+        };
+        let vector = self.ann.vector(position).to_vec();
+        self.nearest(&vector, k + 1)
+            .into_iter()
+            .filter(|hit| hit.node_id != node_id)
+            .take(k)
+            .collect()
+    }
+
+    /// The `k` nodes closest to a natural-language or code query.
+    pub fn search(
+        &self,
+        provider: &dyn EmbeddingProvider,
+        query: &str,
+        k: usize,
+    ) -> Result<Vec<SimilarityHit>, EmbeddingError> {
+        let vector = provider
+            .embed(&[query.to_string()])?
+            .pop()
+            .ok_or_else(|| EmbeddingError::Provider("no vector returned".into()))?;
+        Ok(self.nearest(&vector, k))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{Edge, EdgeKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Bag-of-words over a fixed vocabulary, counting calls.
+    struct WordProvider {
+        calls: AtomicUsize,
+    }
+
+    const VOCABULARY: [&str; 6] = ["parse", "token", "http", "request", "retry", "json"];
+
+    impl EmbeddingProvider for WordProvider {
+        fn fingerprint(&self) -> String {
+            "test-words".into()
+        }
+
+        fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, EmbeddingError> {
+            self.calls.fetch_add(texts.len(), Ordering::SeqCst);
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let lower = text.to_lowercase();
+                    VOCABULARY
+                        .iter()
+                        .map(|word| lower.matches(word).count() as f32 + 0.01)
+                        .collect()
+                })
+                .collect())
+        }
+    }
+
+    fn config() -> EmbeddingConfig {
+        toml::from_str("provider = \"command\"\ncommand = [\"unused\"]").unwrap()
+    }
+
+    fn graph() -> ArborGraph {
+        let mut graph = ArborGraph::new();
+        let node = |name: &str, doc: &str| {
+            let mut node = CodeNode::new(name, name, NodeKind::Function, "lib.rs");
+            node.docstring = Some(doc.to_string());
+            node
+        };
+        let a = graph.add_node(node("tokenize", "Parse the input into token values"));
+        let b = graph.add_node(node("lex", "Split source text into token parse units"));
+        let c = graph.add_node(node("fetch", "Send an http request with retry"));
+        graph.add_node(CodeNode::new("std", "std", NodeKind::Import, "lib.rs"));
+        graph.add_edge(a, b, Edge::new(EdgeKind::Calls));
+        graph.add_edge(c, a, Edge::new(EdgeKind::Calls));
+        graph
+    }
+
+    #[test]
+    fn test_similar_nodes_rank_first() {
+        let graph = graph();
+        let provider = WordProvider {
+            calls: AtomicUsize::new(0),
+        };
+        let (index, stats) =
+            EmbeddingIndex::build(&graph, &provider, None, &mut SourceReader::new(), &config())
+                .unwrap();
+        // Imports aren't embedded.
+        assert_eq!(index.len(), 3);
+        assert_eq!(stats.embedded, 3);
+        assert_eq!(index.dimensions(), VOCABULARY.len());
+
+        let tokenize = &graph.find_by_name("tokenize")[0].id;
+        let lex = &graph.find_by_name("lex")[0].id;
+        let similar = index.similar_to(tokenize, 1);
+        assert_eq!(&similar[0].node_id, lex);
+
+        let hits = index.search(&provider, "http retry", 1).unwrap();
+        assert_eq!(hits[0].node_id, graph.find_by_name("fetch")[0].id);
+    }
+
+    #[test]
+    fn test_stored_vectors_are_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = GraphStore::open(dir.path()).unwrap();
+        let graph = graph();
+        let provider = WordProvider {
+            calls: AtomicUsize::new(0),
+        };
+
+        let build = || {
+            EmbeddingIndex::build(
+                &graph,
+                &provider,
+                Some(&store),
+                &mut SourceReader::new(),
+                &config(),
+            )
+            .unwrap()
+            .1
+        };
+        assert_eq!(
+            build(),
+            EmbeddingStats {
+                embedded: 3,
+                reused: 0
+            }
+        );
+        assert_eq!(
+            build(),
+            EmbeddingStats {
+                embedded: 0,
+                reused: 3
+            }
+        );
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_providers_need_opt_in() {
+        let dir = tempfile::tempdir().unwrap();
+        let config: EmbeddingConfig =
+            toml::from_str("provider = \"command\"\ncommand = [\"sh\", \"-c\", \"touch ran\"]")
+                .unwrap();
+
+        assert!(matches!(
+            config.provider_if(dir.path(), false),
+            Err(EmbeddingError::NotAllowed)
+        ));
+        assert!(!dir.path().join("ran").exists());
+        assert!(config.provider_if(dir.path(), true).is_ok());
+    }
+
+    #[test]
+    fn test_command_provider_protocol() {
+        let provider = CommandProvider {
+            program: "sh".into(),
+            args: vec![
+                "-c".into(),
+                "while read -r line; do echo \"[${#line}, 1]\"; done".into(),
+            ],
+            cwd: std::env::temp_dir(),
+        };
+        let vectors = provider
+            .embed(&["ab".to_string(), "abcd".to_string()])
+            .unwrap();
+        // Each line is the JSON-quoted text, so two extra characters.
+        assert_eq!(vectors, vec![vec![4.0, 1.0], vec![6.0, 1.0]]);
+
+        let failing = CommandProvider {
+            program: "sh".into(),
+            args: vec!["-c".into(), "cat > /dev/null; echo '[1]'".into()],
+            cwd: std::env::temp_dir(),
+        };
+        assert!(failing.embed(&["a".to_string(), "b".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_embedding_text_prefers_body() {
+        let node = CodeNode::new("f", "m.f", NodeKind::Function, "m.rs").with_signature("fn f()");
+        assert_eq!(embedding_text(&node, None, 100), "function m.f\nfn f()");
+        assert_eq!(
+            embedding_text(&node, Some("fn f() { body() }"), 8),
+            "function m.f\nfn f() {"
+        );
+    }
+}
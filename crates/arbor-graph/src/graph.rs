@@ -3,13 +3,16 @@
 //! The ArborGraph wraps petgraph and adds indexes for fast lookups.
 //! It's the central data structure that everything else works with.
 
+use crate::declared::UnresolvedDeclaration;
 use crate::edge::{Edge, EdgeKind, GraphEdge};
-use crate::search_index::SearchIndex;
-use arbor_core::CodeNode;
+use crate::heuristics::UncertainEdge;
+use crate::search_index::{SearchIndex, SearchQuery};
+use arbor_core::{CodeNode, NodeKind, ParseDiagnostics};
 use petgraph::stable_graph::{NodeIndex, StableDiGraph};
 use petgraph::visit::{EdgeRef, IntoEdgeReferences}; // For edge_references
+use petgraph::Direction;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Unique identifier for a node in the graph.
 pub type NodeId = NodeIndex;
@@ -38,6 +41,19 @@ pub struct ArborGraph {
     /// Search index for fast substring queries.
     #[serde(skip)]
     search_index: SearchIndex,
+
+    /// Parse diagnostics per file path.
+    #[serde(default)]
+    file_diagnostics: HashMap<String, ParseDiagnostics>,
+
+    /// Links the call graph cannot prove, between node ids (e.g. dynamic
+    /// dispatch candidates).
+    #[serde(default)]
+    uncertain_edges: Vec<UncertainEdge>,
+
+    /// User declarations whose endpoints did not resolve.
+    #[serde(default)]
+    unresolved_declarations: Vec<UnresolvedDeclaration>,
 }
 
 impl Default for ArborGraph {
@@ -56,6 +72,9 @@ impl ArborGraph {
             file_index: HashMap::new(),
             centrality: HashMap::new(),
             search_index: SearchIndex::new(),
+            file_diagnostics: HashMap::new(),
+            uncertain_edges: Vec::new(),
+            unresolved_declarations: Vec::new(),
         }
     }
 
@@ -65,7 +84,7 @@ impl ArborGraph {
         self.search_index = SearchIndex::new();
         for index in self.graph.node_indices() {
             if let Some(node) = self.graph.node_weight(index) {
-                self.search_index.insert(&node.name, index);
+                self.search_index.insert(node, index);
             }
         }
     }
@@ -82,9 +101,9 @@ impl ArborGraph {
 
         // Update indexes
         self.id_index.insert(id, index);
-        self.name_index.entry(name.clone()).or_default().push(index);
+        self.name_index.entry(name).or_default().push(index);
         self.file_index.entry(file).or_default().push(index);
-        self.search_index.insert(&name, index);
+        self.search_index.insert(&self.graph[index], index);
 
         index
     }
@@ -94,6 +113,71 @@ impl ArborGraph {
         self.graph.add_edge(from, to, edge);
     }
 
+    /// Records links that may exist at runtime but are not graph edges.
+    pub fn add_uncertain_edges(&mut self, edges: impl IntoIterator<Item = UncertainEdge>) {
+        self.uncertain_edges.extend(edges);
+    }
+
+    /// Records declarations from `.arbor/edges.toml` or `arbor:` comments
+    /// that did not become edges.
+    pub fn add_unresolved_declarations(
+        &mut self,
+        declarations: impl IntoIterator<Item = UnresolvedDeclaration>,
+    ) {
+        self.unresolved_declarations.extend(declarations);
+    }
+
+    /// Declared edges and aliases whose endpoints no longer resolve.
+    pub fn unresolved_declarations(&self) -> &[UnresolvedDeclaration] {
+        &self.unresolved_declarations
+    }
+
+    /// Links that may exist at runtime, such as calls through an interface
+    /// to each of its implementations.
+    pub fn uncertain_edges(&self) -> &[UncertainEdge] {
+        &self.uncertain_edges
+    }
+
+    /// Edges into (`Incoming`) or out of (`Outgoing`) a node, with the
+    /// node at the other end.
+    pub fn edges_of(&self, index: NodeId, direction: Direction) -> Vec<(NodeId, &Edge)> {
+        self.graph
+            .edges_directed(index, direction)
+            .map(|e| {
+                let far = match direction {
+                    Direction::Incoming => e.source(),
+                    Direction::Outgoing => e.target(),
+                };
+                (far, e.weight())
+            })
+            .collect()
+    }
+
+    /// Uncertain edges into (`Incoming`) or out of (`Outgoing`) a node,
+    /// with the node at the other end.
+    pub fn uncertain_edges_of(
+        &self,
+        index: NodeId,
+        direction: Direction,
+    ) -> Vec<(NodeId, &UncertainEdge)> {
+        let Some(id) = self.graph.node_weight(index).map(|n| n.id.as_str()) else {
+            return Vec::new();
+        };
+        self.uncertain_edges
+            .iter()
+            .filter_map(|edge| {
+                let (near, far) = match direction {
+                    Direction::Incoming => (&edge.to, &edge.from),
+                    Direction::Outgoing => (&edge.from, &edge.to),
+                };
+                if near != id {
+                    return None;
+                }
+                Some((self.get_index(far)?, edge))
+            })
+            .collect()
+    }
+
     /// Gets a node by its string ID.
     pub fn get_by_id(&self, id: &str) -> Option<&CodeNode> {
         let index = self.id_index.get(id)?;
@@ -131,15 +215,34 @@ impl ArborGraph {
             .unwrap_or_default()
     }
 
-    /// Searches for nodes whose name contains the query.
+    /// Searches for nodes matching the query, most relevant first.
     ///
-    /// Uses the search index for fast O(k) lookups where k is the number of matches,
-    /// instead of O(n) linear scan over all nodes.
+    /// Supports subword, prefix and typo-tolerant matching over names,
+    /// qualified names, signatures, docstrings and paths, plus filters like
+    /// `kind:method file:src/billing/** async:true via:global_suffix`
+    /// (see [`SearchQuery`]).
+    /// Central nodes rank higher.
     pub fn search(&self, query: &str) -> Vec<&CodeNode> {
+        self.search_scored(query)
+            .into_iter()
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Like [`ArborGraph::search`], with each node's relevance score.
+    pub fn search_scored(&self, query: &str) -> Vec<(&CodeNode, f64)> {
+        let query = SearchQuery::parse(query);
         self.search_index
-            .search(query)
-            .iter()
-            .filter_map(|id| self.graph.node_weight(*id))
+            .rank(&query, |id| self.centrality(id))
+            .into_iter()
+            .filter(|hit| {
+                query.via.is_empty()
+                    || self
+                        .graph
+                        .edges_directed(hit.id, Direction::Outgoing)
+                        .any(|e| query.via.contains(&e.weight().provenance))
+            })
+            .filter_map(|hit| Some((self.graph.node_weight(hit.id)?, hit.score)))
             .collect()
     }
 
@@ -209,6 +312,13 @@ impl ArborGraph {
     /// Removes all nodes from a file. Used for incremental updates.
     pub fn remove_file(&mut self, file: &str) {
         if let Some(indexes) = self.file_index.remove(file) {
+            let removed: HashSet<&str> = indexes
+                .iter()
+                .filter_map(|&index| self.graph.node_weight(index))
+                .map(|node| node.id.as_str())
+                .collect();
+            self.uncertain_edges
+                .retain(|e| !removed.contains(e.from.as_str()) && !removed.contains(e.to.as_str()));
             for index in indexes {
                 if let Some(node) = self.graph.node_weight(index) {
                     // Remove from name index
@@ -219,11 +329,27 @@ impl ArborGraph {
                     // Remove from id index
                     self.id_index.remove(&node.id);
                     // Remove from search index
-                    self.search_index.remove(&name, index);
+                    self.search_index.remove(index);
                 }
                 self.graph.remove_node(index);
             }
         }
+        self.file_diagnostics.remove(file);
+    }
+
+    /// Records how well a file parsed.
+    pub fn set_file_diagnostics(&mut self, file: &str, diagnostics: ParseDiagnostics) {
+        self.file_diagnostics.insert(file.to_string(), diagnostics);
+    }
+
+    /// Gets the parse diagnostics for a file, if it was indexed with them.
+    pub fn file_diagnostics(&self, file: &str) -> Option<&ParseDiagnostics> {
+        self.file_diagnostics.get(file)
+    }
+
+    /// Returns parse diagnostics for every file.
+    pub fn all_file_diagnostics(&self) -> &HashMap<String, ParseDiagnostics> {
+        &self.file_diagnostics
     }
 
     /// Gets the centrality score for a node.
@@ -273,6 +399,8 @@ impl ArborGraph {
                     source,
                     target,
                     kind: weight.kind,
+                    provenance: weight.provenance,
+                    confidence: weight.confidence,
                 })
             })
             .collect()
@@ -308,12 +436,63 @@ impl ArborGraph {
     }
 
     /// Returns all nodes detected as production entry points.
+    ///
+    /// Framework routes come first, ordered by file and line. Functions the
+    /// name heuristics flag follow, unless a route already dispatches to
+    /// them: the route is the entry point, not its handler.
     pub fn list_entry_points(&self) -> Vec<&CodeNode> {
-        use crate::heuristics::HeuristicsMatcher;
-        self.graph
+        let mut routes: Vec<&CodeNode> = self
+            .graph
             .node_weights()
-            .filter(|n| HeuristicsMatcher::is_likely_entry_point(n))
-            .collect()
+            .filter(|n| n.kind == NodeKind::Route)
+            .collect();
+        routes.sort_by(|a, b| (&a.file, a.line_start).cmp(&(&b.file, b.line_start)));
+        let guessed = self.graph.node_indices().filter_map(|idx| {
+            let node = self.graph.node_weight(idx)?;
+            (node.kind != NodeKind::Route && self.is_entry_point(idx)).then_some(node)
+        });
+        routes.into_iter().chain(guessed).collect()
+    }
+
+    /// A route, or a function the name heuristics flag that no route
+    /// dispatches to.
+    pub fn is_entry_point(&self, index: NodeId) -> bool {
+        use crate::heuristics::HeuristicsMatcher;
+        let Some(node) = self.graph.node_weight(index) else {
+            return false;
+        };
+        node.kind == NodeKind::Route
+            || (HeuristicsMatcher::is_likely_entry_point(node)
+                && !self
+                    .get_callers(index)
+                    .iter()
+                    .any(|caller| caller.kind == NodeKind::Route))
+    }
+
+    /// Entry points whose call tree reaches `index`, nearest first:
+    /// "which endpoints reach this function". `index` itself is included
+    /// when it is an entry point.
+    pub fn entry_points_reaching(&self, index: NodeId) -> Vec<&CodeNode> {
+        let mut seen = std::collections::HashSet::from([index]);
+        let mut queue = std::collections::VecDeque::from([index]);
+        let mut found = Vec::new();
+        while let Some(idx) = queue.pop_front() {
+            let Some(node) = self.graph.node_weight(idx) else {
+                continue;
+            };
+            if self.is_entry_point(idx) {
+                found.push(node);
+            }
+            for edge in self
+                .graph
+                .edges_directed(idx, petgraph::Direction::Incoming)
+            {
+                if edge.weight().kind == EdgeKind::Calls && seen.insert(edge.source()) {
+                    queue.push_back(edge.source());
+                }
+            }
+        }
+        found
     }
 
     /// Returns all nodes in a file and the call edges between them.
@@ -591,7 +770,7 @@ mod tests {
 #[cfg(test)]
 mod new_query_tests {
     use super::*;
-    use crate::edge::{Edge, EdgeKind};
+    use crate::edge::{Edge, EdgeKind, Provenance};
     use arbor_core::{CodeNode, NodeKind};
 
     fn make_node(name: &str, kind: NodeKind, file: &str) -> CodeNode {
@@ -614,6 +793,41 @@ mod new_query_tests {
         );
     }
 
+    #[test]
+    fn test_routes_stand_in_for_their_handlers() {
+        let mut g = ArborGraph::new();
+        let route = g.add_node(make_node("GET /orders", NodeKind::Route, "src/api.rs"));
+        let handler = g.add_node(make_node("handle_orders", NodeKind::Function, "src/api.rs"));
+        let query = g.add_node(make_node("load_orders", NodeKind::Function, "src/db.rs"));
+        let cron = g.add_node(make_node(
+            "handle_cleanup",
+            NodeKind::Function,
+            "src/jobs.rs",
+        ));
+        g.add_edge(route, handler, Edge::new(EdgeKind::Calls));
+        g.add_edge(handler, query, Edge::new(EdgeKind::Calls));
+        g.add_edge(cron, query, Edge::new(EdgeKind::Calls));
+
+        let eps: Vec<&str> = g
+            .list_entry_points()
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect();
+        assert_eq!(eps, ["GET /orders", "handle_cleanup"]);
+
+        let mut reaching: Vec<&str> = g
+            .entry_points_reaching(query)
+            .iter()
+            .map(|n| n.name.as_str())
+            .collect();
+        reaching.sort();
+        assert_eq!(reaching, ["GET /orders", "handle_cleanup"]);
+        assert!(g
+            .entry_points_reaching(route)
+            .iter()
+            .any(|n| n.name == "GET /orders"));
+    }
+
     #[test]
     fn test_nodes_in_file_with_edges_returns_edges() {
         let mut g = ArborGraph::new();
@@ -627,6 +841,9 @@ mod new_query_tests {
                 kind: EdgeKind::Calls,
                 file: None,
                 line: None,
+                provenance: Provenance::ExactFqn,
+                reference: None,
+                confidence: 1.0,
             },
         );
         let (nodes, edges) = g.nodes_in_file_with_edges("src/a.rs");
@@ -638,7 +855,7 @@ mod new_query_tests {
 
     #[test]
     fn test_nodes_in_file_with_edges_excludes_cross_file_edges() {
-        use crate::edge::{Edge, EdgeKind};
+        use crate::edge::{Edge, EdgeKind, Provenance};
         let mut g = ArborGraph::new();
         let a = g.add_node(make_node("foo", NodeKind::Function, "src/a.rs"));
         let c = g.add_node(make_node("baz", NodeKind::Function, "src/b.rs"));
@@ -650,6 +867,9 @@ mod new_query_tests {
                 kind: EdgeKind::Calls,
                 file: None,
                 line: None,
+                provenance: Provenance::ExactFqn,
+                reference: None,
+                confidence: 1.0,
             },
         );
         let (nodes, edges) = g.nodes_in_file_with_edges("src/a.rs");
@@ -5,10 +5,13 @@
 //! - Framework-specific patterns (Flutter widgets, etc.)
 //! - Possible runtime dependencies
 
-use arbor_core::{CodeNode, NodeKind};
+use arbor_core::{CodeNode, NodeKind, ParseDiagnostics};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 
 /// Types of uncertain edges
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum UncertainEdgeKind {
     /// Callback or closure passed as argument
     Callback,
@@ -22,6 +25,10 @@ pub enum UncertainEdgeKind {
     DependencyInjection,
     /// Reflection or runtime lookup
     Reflection,
+    /// Changed together in git history with no static edge
+    CoChange,
+    /// A call edge resolved by name rather than qualified name
+    NameMatch,
 }
 
 impl std::fmt::Display for UncertainEdgeKind {
@@ -33,12 +40,14 @@ impl std::fmt::Display for UncertainEdgeKind {
             UncertainEdgeKind::EventHandler => write!(f, "event handler"),
             UncertainEdgeKind::DependencyInjection => write!(f, "dependency injection"),
             UncertainEdgeKind::Reflection => write!(f, "reflection"),
+            UncertainEdgeKind::CoChange => write!(f, "co-change"),
+            UncertainEdgeKind::NameMatch => write!(f, "name match"),
         }
     }
 }
 
 /// An edge that might exist at runtime but cannot be proven statically
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UncertainEdge {
     pub from: String,
     pub to: String,
@@ -105,7 +114,15 @@ impl HeuristicsMatcher {
     /// HTTP handlers, CLI commands, cron jobs, webhook receivers, main functions.
     /// They're the roots of execution trees; if a changed function reaches one,
     /// it means the change can affect real production traffic.
+    ///
+    /// Route nodes extracted from framework declarations always count, as
+    /// do message topics, which other services may publish to; the name and
+    /// path rules below are the fallback for everything else.
     pub fn is_likely_entry_point(node: &CodeNode) -> bool {
+        // Declared through a web framework or a message bus: not a guess.
+        if matches!(node.kind, NodeKind::Route | NodeKind::Topic) {
+            return true;
+        }
         if !matches!(node.kind, NodeKind::Function | NodeKind::Method) {
             return false;
         }
@@ -180,6 +197,14 @@ impl HeuristicsMatcher {
     }
 
     /// Infer uncertain edges from node patterns
+    ///
+    /// The edges have placeholder ends (`event_source`, `caller`,
+    /// `parent_widget`); the graph now infers callback and injection edges
+    /// between real nodes from the wiring the parser records.
+    #[deprecated(
+        since = "2.5.0",
+        note = "use `ArborGraph::uncertain_edges`, which links real nodes"
+    )]
     pub fn infer_uncertain_edges(nodes: &[&CodeNode]) -> Vec<UncertainEdge> {
         let mut edges = Vec::new();
 
@@ -227,6 +252,8 @@ impl HeuristicsMatcher {
 pub struct AnalysisWarning {
     pub message: String,
     pub suggestion: String,
+    /// True if the result is known to be incomplete, not just possibly so.
+    pub lowers_confidence: bool,
 }
 
 impl AnalysisWarning {
@@ -234,14 +261,77 @@ impl AnalysisWarning {
         Self {
             message: message.into(),
             suggestion: suggestion.into(),
+            lowers_confidence: false,
+        }
+    }
+
+    /// A warning that should drop the confidence level of the result.
+    pub fn degrading(message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            lowers_confidence: true,
+            ..Self::new(message, suggestion)
         }
     }
 }
 
 /// Check for common patterns that limit static analysis accuracy
-pub fn detect_analysis_limitations(nodes: &[&CodeNode]) -> Vec<AnalysisWarning> {
+///
+/// `diagnostics` maps file paths to how well they parsed (see
+/// [`ArborGraph::all_file_diagnostics`](crate::ArborGraph::all_file_diagnostics));
+/// nodes in files with parse errors, fallback parsing or low coverage
+/// produce confidence-lowering warnings, since their edges may be missing.
+pub fn detect_analysis_limitations(
+    nodes: &[&CodeNode],
+    diagnostics: &HashMap<String, ParseDiagnostics>,
+) -> Vec<AnalysisWarning> {
     let mut warnings = Vec::new();
 
+    let mut with_errors = BTreeSet::new();
+    let mut fallback = BTreeSet::new();
+    let mut low_coverage = BTreeSet::new();
+    for node in nodes {
+        let Some(diag) = diagnostics.get(&node.file) else {
+            continue;
+        };
+        if diag.error_count > 0 {
+            with_errors.insert(node.file.as_str());
+        } else if diag.used_fallback {
+            fallback.insert(node.file.as_str());
+        } else if diag.is_low_coverage() {
+            low_coverage.insert(node.file.as_str());
+        }
+    }
+    if !with_errors.is_empty() {
+        warnings.push(AnalysisWarning::degrading(
+            format!(
+                "{} affected file(s) have parse errors: {}",
+                with_errors.len(),
+                file_list(&with_errors)
+            ),
+            "Symbols inside the broken regions are missing. Fix the syntax errors and re-index.",
+        ));
+    }
+    if !fallback.is_empty() {
+        warnings.push(AnalysisWarning::degrading(
+            format!(
+                "{} affected file(s) were parsed heuristically: {}",
+                fallback.len(),
+                file_list(&fallback)
+            ),
+            "No grammar is available for these files, so call edges are not extracted.",
+        ));
+    }
+    if !low_coverage.is_empty() {
+        warnings.push(AnalysisWarning::degrading(
+            format!(
+                "{} affected file(s) have low parse coverage: {}",
+                low_coverage.len(),
+                file_list(&low_coverage)
+            ),
+            "Most of these files fell outside extracted symbols. Check 'arbor status --files'.",
+        ));
+    }
+
     let callback_count = nodes
         .iter()
         .filter(|n| HeuristicsMatcher::is_callback_style(n))
@@ -278,6 +368,20 @@ pub fn detect_analysis_limitations(nodes: &[&CodeNode]) -> Vec<AnalysisWarning>
     warnings
 }
 
+/// Up to three file names, then a count of the rest.
+fn file_list(files: &BTreeSet<&str>) -> String {
+    let names: Vec<&str> = files
+        .iter()
+        .take(3)
+        .map(|f| f.rsplit(['/', '\\']).next().unwrap_or(f))
+        .collect();
+    if files.len() > 3 {
+        format!("{} and {} more", names.join(", "), files.len() - 3)
+    } else {
+        names.join(", ")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,6 +477,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_infer_uncertain_edges_from_patterns() {
         let handler = CodeNode::new("onClick", "onClick", NodeKind::Function, "button.ts");
         let widget = CodeNode::new("HomeWidget", "HomeWidget", NodeKind::Class, "home.dart");
@@ -407,7 +512,7 @@ mod tests {
             .collect();
         let node_refs: Vec<&CodeNode> = nodes.iter().collect();
 
-        let warnings = detect_analysis_limitations(&node_refs);
+        let warnings = detect_analysis_limitations(&node_refs, &HashMap::new());
         assert!(!warnings.is_empty());
         assert!(warnings.iter().any(|w| w.message.contains("callback")));
     }
@@ -422,7 +527,7 @@ mod tests {
         )];
         let node_refs: Vec<&CodeNode> = widgets.iter().collect();
 
-        let warnings = detect_analysis_limitations(&node_refs);
+        let warnings = detect_analysis_limitations(&node_refs, &HashMap::new());
         assert!(warnings.iter().any(|w| w.message.contains("Flutter")));
     }
 
@@ -440,6 +545,7 @@ mod tests {
             "dependency injection"
         );
         assert_eq!(UncertainEdgeKind::Reflection.to_string(), "reflection");
+        assert_eq!(UncertainEdgeKind::CoChange.to_string(), "co-change");
     }
 
     #[test]
@@ -450,7 +556,31 @@ mod tests {
         ];
         let node_refs: Vec<&CodeNode> = nodes.iter().collect();
 
-        let warnings = detect_analysis_limitations(&node_refs);
+        let warnings = detect_analysis_limitations(&node_refs, &HashMap::new());
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn test_poorly_parsed_files_lower_confidence() {
+        let nodes: Vec<CodeNode> = vec![
+            CodeNode::new("main", "main", NodeKind::Function, "src/main.rs"),
+            CodeNode::new("helper", "helper", NodeKind::Function, "src/utils.rs"),
+        ];
+        let node_refs: Vec<&CodeNode> = nodes.iter().collect();
+        let mut diagnostics = HashMap::new();
+        diagnostics.insert("src/main.rs".to_string(), ParseDiagnostics::default());
+        diagnostics.insert(
+            "src/utils.rs".to_string(),
+            ParseDiagnostics {
+                error_count: 2,
+                ..ParseDiagnostics::default()
+            },
+        );
+
+        let warnings = detect_analysis_limitations(&node_refs, &diagnostics);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].lowers_confidence);
+        assert!(warnings[0].message.contains("utils.rs"));
+        assert!(!warnings[0].message.contains("main.rs"));
+    }
 }
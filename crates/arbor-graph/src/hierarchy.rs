@@ -0,0 +1,502 @@
+//! Class hierarchy and dynamic dispatch.
+//!
+//! The parsers record what each type extends or implements
+//! ([`CodeNode::supertypes`]); [`supertype_links`] resolves those names
+//! to `extends`/`implements` edges. Go types satisfy interfaces
+//! implicitly, so there a struct implements every interface whose methods
+//! it all has.
+//!
+//! A call through an interface or base class resolves to the declaration
+//! at best, never to the code that runs. Class hierarchy analysis
+//! ([`dispatch_edges`]) adds an uncertain "may dispatch to" edge from
+//! each such call site to every override in a subtype, so a change to one
+//! implementation reaches the callers of the interface.
+
+use crate::edge::{Edge, EdgeKind, Provenance};
+use crate::graph::{ArborGraph, NodeId};
+use crate::heuristics::{UncertainEdge, UncertainEdgeKind};
+use arbor_core::{CodeNode, NodeKind};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// The call names a method declared on an interface.
+const INTERFACE_CALL: f32 = 0.9;
+/// The call names a concrete method that subclasses override.
+const BASE_CLASS_CALL: f32 = 0.7;
+/// An unresolved call whose caller's signature names the declaring type.
+const TYPED_RECEIVER: f32 = 0.8;
+/// An unresolved call whose method name only one type hierarchy declares.
+const UNIQUE_NAME: f32 = 0.5;
+
+fn is_type(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Class | NodeKind::Struct | NodeKind::Interface | NodeKind::Enum
+    )
+}
+
+/// Resolves each method, constructor and field to its owning type from
+/// its qualified name (`Owner.method`, generics dropped), preferring a
+/// type in the same file.
+pub(crate) fn member_owners(graph: &ArborGraph) -> HashMap<NodeId, NodeId> {
+    let mut types: HashMap<&str, Vec<NodeId>> = HashMap::new();
+    for index in graph.node_indexes() {
+        let node = &graph.graph[index];
+        if is_type(node.kind) {
+            types
+                .entry(node.qualified_name.as_str())
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let mut owners = HashMap::new();
+    for index in graph.node_indexes() {
+        let node = &graph.graph[index];
+        if !matches!(
+            node.kind,
+            NodeKind::Method | NodeKind::Constructor | NodeKind::Field
+        ) {
+            continue;
+        }
+        let Some((prefix, _)) = node
+            .qualified_name
+            .rsplit_once("::")
+            .or_else(|| node.qualified_name.rsplit_once('.'))
+        else {
+            continue;
+        };
+        let prefix = prefix.split('<').next().unwrap_or(prefix).trim();
+        let Some(candidates) = types.get(prefix) else {
+            continue;
+        };
+        let owner = candidates
+            .iter()
+            .copied()
+            .find(|&t| graph.graph[t].file == node.file)
+            .unwrap_or(candidates[0]);
+        owners.insert(index, owner);
+    }
+    owners
+}
+
+/// `extends`/`implements` edges for the supertypes the parsers recorded,
+/// plus implicit Go interface implementations, which are heuristic.
+pub(crate) fn supertype_links(graph: &ArborGraph) -> Vec<(NodeId, NodeId, Edge)> {
+    let owners = member_owners(graph);
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    let mut link = |from: NodeId, to: NodeId, provenance: Provenance, reference: Option<&str>| {
+        if from == to || !seen.insert((from, to)) {
+            return;
+        }
+        let kind = if graph.graph[to].kind == NodeKind::Interface
+            && graph.graph[from].kind != NodeKind::Interface
+        {
+            EdgeKind::Implements
+        } else {
+            EdgeKind::Extends
+        };
+        let mut edge = Edge::new(kind).with_provenance(provenance);
+        edge.reference = reference.map(str::to_string);
+        links.push((from, to, edge));
+    };
+
+    for index in graph.node_indexes() {
+        let node = &graph.graph[index];
+        // Methods of a Rust `impl Trait for Type` block carry the trait;
+        // the type is what implements it.
+        let Some(from) = (if is_type(node.kind) {
+            Some(index)
+        } else {
+            owners.get(&index).copied()
+        }) else {
+            continue;
+        };
+        for name in &node.supertypes {
+            if let Some(to) = resolve_type(graph, name, &node.file) {
+                let provenance = if graph.graph[to].qualified_name == *name {
+                    Provenance::ExactFqn
+                } else if Path::new(&graph.graph[to].file).parent()
+                    == Path::new(&node.file).parent()
+                {
+                    Provenance::SameDirSuffix
+                } else {
+                    Provenance::GlobalSuffix
+                };
+                link(from, to, provenance, Some(name));
+            }
+        }
+    }
+
+    let mut method_names: HashMap<NodeId, HashSet<&str>> = HashMap::new();
+    for (&method, &owner) in &owners {
+        let node = &graph.graph[method];
+        if node.kind == NodeKind::Method && node.file.ends_with(".go") {
+            method_names
+                .entry(owner)
+                .or_default()
+                .insert(node.name.as_str());
+        }
+    }
+    for (&interface, required) in &method_names {
+        if graph.graph[interface].kind != NodeKind::Interface {
+            continue;
+        }
+        for (&candidate, provided) in &method_names {
+            if graph.graph[candidate].kind == NodeKind::Struct && required.is_subset(provided) {
+                link(candidate, interface, Provenance::Heuristic, None);
+            }
+        }
+    }
+    links
+}
+
+/// The type a supertype name refers to: one in the same file, else the
+/// same directory, else the only one with that name.
+pub(crate) fn resolve_type(graph: &ArborGraph, name: &str, file: &str) -> Option<NodeId> {
+    let candidates: Vec<&CodeNode> = graph
+        .find_by_name(name)
+        .into_iter()
+        .filter(|n| is_type(n.kind))
+        .collect();
+    let dir = Path::new(file).parent();
+    let same_dir: Vec<&CodeNode> = candidates
+        .iter()
+        .copied()
+        .filter(|n| Path::new(&n.file).parent() == dir)
+        .collect();
+    let chosen = candidates
+        .iter()
+        .copied()
+        .find(|n| n.file == file)
+        .or_else(|| (same_dir.len() == 1).then(|| same_dir[0]))
+        .or_else(|| (candidates.len() == 1).then(|| candidates[0]))?;
+    graph.get_index(&chosen.id)
+}
+
+/// A method declared on a type that subtypes override.
+struct Dispatch {
+    declared: NodeId,
+    owner: NodeId,
+    overrides: Vec<NodeId>,
+}
+
+/// Candidate "may dispatch to" edges from call sites of interface and
+/// base class methods to the overrides in every subtype.
+///
+/// Calls the builder resolved to the declaration link to each override.
+/// Calls it could not resolve (`store.save()`) link to the declaration
+/// and its overrides when the caller's signature names the declaring
+/// type, or when only one hierarchy declares a method of that name.
+pub(crate) fn dispatch_edges(graph: &ArborGraph) -> Vec<UncertainEdge> {
+    let owners = member_owners(graph);
+    let mut methods: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+    for (&member, &owner) in &owners {
+        if graph.graph[member].kind == NodeKind::Method {
+            methods.entry(owner).or_default().push(member);
+        }
+    }
+
+    let mut dispatches = Vec::new();
+    for (&owner, declared) in &methods {
+        let subtypes = subtypes(graph, owner);
+        if subtypes.is_empty() {
+            continue;
+        }
+        for &method in declared {
+            let name = &graph.graph[method].name;
+            let mut overrides: Vec<NodeId> = subtypes
+                .iter()
+                .filter_map(|s| methods.get(s))
+                .flatten()
+                .copied()
+                .filter(|&m| graph.graph[m].name == *name)
+                .collect();
+            if overrides.is_empty() {
+                continue;
+            }
+            overrides.sort();
+            dispatches.push(Dispatch {
+                declared: method,
+                owner,
+                overrides,
+            });
+        }
+    }
+    dispatches.sort_by_key(|d| d.declared);
+
+    // (caller, callee) → strongest candidate edge
+    let mut edges: BTreeMap<(NodeId, NodeId), (f32, String)> = BTreeMap::new();
+    let mut add = |caller: NodeId, callee: NodeId, confidence: f32, reason: String| {
+        if caller == callee {
+            return;
+        }
+        let entry = edges
+            .entry((caller, callee))
+            .or_insert((confidence, reason.clone()));
+        if confidence > entry.0 {
+            *entry = (confidence, reason);
+        }
+    };
+
+    let mut by_name: HashMap<&str, Vec<&Dispatch>> = HashMap::new();
+    for dispatch in &dispatches {
+        let declared = &graph.graph[dispatch.declared];
+        by_name
+            .entry(declared.name.as_str())
+            .or_default()
+            .push(dispatch);
+
+        let confidence = if graph.graph[dispatch.owner].kind == NodeKind::Interface {
+            INTERFACE_CALL
+        } else {
+            BASE_CLASS_CALL
+        };
+        for edge in graph
+            .graph
+            .edges_directed(dispatch.declared, Direction::Incoming)
+            .filter(|e| e.weight().kind == EdgeKind::Calls)
+        {
+            let caller = edge.source();
+            for &target in &dispatch.overrides {
+                add(
+                    caller,
+                    target,
+                    confidence,
+                    format!(
+                        "'{}' calls '{}', which '{}' overrides",
+                        graph.graph[caller].name,
+                        declared.qualified_name,
+                        graph.graph[target].qualified_name
+                    ),
+                );
+            }
+        }
+    }
+
+    for caller in graph.node_indexes() {
+        let node = &graph.graph[caller];
+        for reference in &node.references {
+            let Some((receiver, method)) = reference.rsplit_once(['.', ':']) else {
+                continue;
+            };
+            let receiver = receiver.trim_end_matches(':');
+            let Some(candidates) = by_name.get(method) else {
+                continue;
+            };
+            let resolved = graph
+                .graph
+                .edges_directed(caller, Direction::Outgoing)
+                .any(|e| {
+                    e.weight().kind == EdgeKind::Calls && graph.graph[e.target()].name == method
+                });
+            if resolved {
+                continue;
+            }
+
+            let signature = node.signature.as_deref().unwrap_or_default();
+            let typed: Vec<&&Dispatch> = candidates
+                .iter()
+                .filter(|d| mentions(signature, &graph.graph[d.owner].name))
+                .collect();
+            let (chosen, confidence) = if !typed.is_empty() {
+                (typed, TYPED_RECEIVER)
+            } else if candidates.len() == 1 {
+                (candidates.iter().collect(), UNIQUE_NAME)
+            } else {
+                continue;
+            };
+            for dispatch in chosen {
+                let owner = &graph.graph[dispatch.owner].name;
+                let reason = if confidence == TYPED_RECEIVER {
+                    format!(
+                        "'{}' calls '{}' and takes a '{}'",
+                        node.name, reference, owner
+                    )
+                } else {
+                    format!(
+                        "'{}' calls '{}' and only '{}' declares '{}' ({})",
+                        node.name, reference, owner, method, receiver
+                    )
+                };
+                for &target in std::iter::once(&dispatch.declared).chain(&dispatch.overrides) {
+                    add(caller, target, confidence, reason.clone());
+                }
+            }
+        }
+    }
+
+    edges
+        .into_iter()
+        .map(|((from, to), (confidence, reason))| UncertainEdge {
+            from: graph.graph[from].id.clone(),
+            to: graph.graph[to].id.clone(),
+            kind: UncertainEdgeKind::DynamicDispatch,
+            confidence,
+            reason,
+        })
+        .collect()
+}
+
+/// Every type that extends or implements `root`, directly or not.
+pub(crate) fn subtypes(graph: &ArborGraph, root: NodeId) -> Vec<NodeId> {
+    let mut seen = HashSet::from([root]);
+    let mut queue = VecDeque::from([root]);
+    let mut found = Vec::new();
+    while let Some(current) = queue.pop_front() {
+        for edge in graph.graph.edges_directed(current, Direction::Incoming) {
+            if matches!(edge.weight().kind, EdgeKind::Extends | EdgeKind::Implements)
+                && seen.insert(edge.source())
+            {
+                found.push(edge.source());
+                queue.push_back(edge.source());
+            }
+        }
+    }
+    found
+}
+
+/// Whether `name` appears in `text` as a whole word.
+fn mentions(text: &str, name: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(at, _)| {
+        !text[..at].ends_with(is_word) && !text[at + name.len()..].starts_with(is_word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EdgeKind, GraphBuilder, UncertainEdgeKind};
+    use arbor_core::{CodeNode, NodeKind};
+
+    fn node(name: &str, qualified: &str, kind: NodeKind, file: &str) -> CodeNode {
+        let mut node = CodeNode::new(name, qualified, kind, file);
+        node.signature = Some(format!("fn {}()", name));
+        node
+    }
+
+    #[test]
+    fn test_supertypes_become_edges() {
+        let mut store = node("Store", "Store", NodeKind::Interface, "store.rs");
+        store.supertypes = vec!["Named".into()];
+        let named = node("Named", "Named", NodeKind::Interface, "store.rs");
+        let pg = node("Pg", "Pg", NodeKind::Struct, "pg.rs");
+        let mut save = node("save", "Pg.save", NodeKind::Method, "pg.rs");
+        save.supertypes = vec!["Store".into()];
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![store, named, pg, save]);
+        let graph = builder.build();
+
+        let mut kinds: Vec<_> = graph
+            .export_edges()
+            .into_iter()
+            .map(|e| {
+                let from = graph.get_by_id(&e.source).unwrap().name.clone();
+                let to = graph.get_by_id(&e.target).unwrap().name.clone();
+                (from, to, e.kind)
+            })
+            .collect();
+        kinds.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            kinds,
+            [
+                ("Pg".into(), "Store".into(), EdgeKind::Implements),
+                ("Store".into(), "Named".into(), EdgeKind::Extends),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_calls_dispatch_to_every_implementation() {
+        let store = node("Store", "Store", NodeKind::Interface, "store.java");
+        let save = node("save", "Store.save", NodeKind::Method, "store.java");
+        let mut pg = node("Pg", "Pg", NodeKind::Class, "pg.java");
+        pg.supertypes = vec!["Store".into()];
+        let pg_save = node("save", "Pg.save", NodeKind::Method, "pg.java");
+        let mut mem = node("Mem", "Mem", NodeKind::Class, "mem.java");
+        mem.supertypes = vec!["Store".into()];
+        let mem_save = node("save", "Mem.save", NodeKind::Method, "mem.java");
+        let checkout = node("checkout", "checkout", NodeKind::Function, "store.java")
+            .with_references(vec!["Store.save".into()]);
+        let mut typed = node("persist", "persist", NodeKind::Function, "app.java")
+            .with_references(vec!["store.save".into()]);
+        typed.signature = Some("void persist(Store store)".into());
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![
+            store, save, pg, pg_save, mem, mem_save, checkout, typed,
+        ]);
+        let graph = builder.build();
+
+        let mut edges: Vec<_> = graph
+            .uncertain_edges()
+            .iter()
+            .map(|e| {
+                assert_eq!(e.kind, UncertainEdgeKind::DynamicDispatch);
+                let from = graph.get_by_id(&e.from).unwrap();
+                let to = graph.get_by_id(&e.to).unwrap();
+                (
+                    from.name.clone(),
+                    to.qualified_name.clone(),
+                    (e.confidence * 10.0).round() as u32,
+                )
+            })
+            .collect();
+        edges.sort();
+        assert_eq!(
+            edges,
+            [
+                ("checkout".into(), "Mem.save".into(), 9),
+                ("checkout".into(), "Pg.save".into(), 9),
+                ("persist".into(), "Mem.save".into(), 8),
+                ("persist".into(), "Pg.save".into(), 8),
+                ("persist".into(), "Store.save".into(), 8),
+            ]
+        );
+
+        // A change to one implementation reaches the interface's callers,
+        // in a tier of its own.
+        let pg_save = graph
+            .node_indexes()
+            .find(|&i| graph.get(i).unwrap().qualified_name == "Pg.save")
+            .unwrap();
+        let analysis = graph.analyze_impact(pg_save, 3);
+        assert!(analysis.upstream.is_empty());
+        let mut possible: Vec<_> = analysis
+            .possibly_affected
+            .iter()
+            .map(|n| n.node_info.name.as_str())
+            .collect();
+        possible.sort();
+        assert_eq!(possible, ["checkout", "persist"]);
+        assert_eq!(analysis.uncertain_edges.len(), 2);
+    }
+
+    #[test]
+    fn test_go_structs_implement_interfaces_by_method_set() {
+        let store = node("Store", "Store", NodeKind::Interface, "store.go");
+        let save = node("Save", "Store.Save", NodeKind::Method, "store.go");
+        let load = node("Load", "Store.Load", NodeKind::Method, "store.go");
+        let pg = node("Pg", "Pg", NodeKind::Struct, "pg.go");
+        let pg_save = node("Save", "Pg.Save", NodeKind::Method, "pg.go");
+        let pg_load = node("Load", "Pg.Load", NodeKind::Method, "pg.go");
+        let half = node("Half", "Half", NodeKind::Struct, "half.go");
+        let half_save = node("Save", "Half.Save", NodeKind::Method, "half.go");
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![
+            store, save, load, pg, pg_save, pg_load, half, half_save,
+        ]);
+        let graph = builder.build();
+
+        let implements: Vec<_> = graph
+            .export_edges()
+            .into_iter()
+            .filter(|e| e.kind == EdgeKind::Implements)
+            .map(|e| graph.get_by_id(&e.source).unwrap().name.clone())
+            .collect();
+        assert_eq!(implements, ["Pg"]);
+    }
+}
@@ -0,0 +1,923 @@
+//! Change history mined from `git log`.
+//!
+//! Each commit's `--numstat` counts give per-file churn, and its zero-context
+//! hunks are mapped onto the current line ranges of the graph's symbols.
+//! Ranges in old commits drift as code moves, so symbol churn is a best-effort
+//! estimate. Files (and cross-file symbols) that keep changing in the same
+//! commits are reported as temporal coupling; coupling with no static edge
+//! behind it is a dependency the graph cannot see.
+
+use crate::graph::{ArborGraph, NodeId};
+use crate::heuristics::{UncertainEdge, UncertainEdgeKind};
+use crate::impact::ImpactAnalysis;
+use crate::query::NodeInfo;
+use arbor_core::{CodeNode, NodeKind};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Commits read by default: enough for stable coupling numbers while
+/// keeping `git log -p` fast on large repositories.
+pub const HISTORY_COMMITS: usize = 500;
+
+/// Separates commits in the `git log` output.
+const COMMIT_MARKER: char = '\u{1e}';
+/// Separates the fields of a commit header.
+const FIELD_MARKER: char = '\u{1f}';
+
+/// Options for [`ArborGraph::analyze_history`].
+#[derive(Debug, Clone)]
+pub struct HistoryOptions {
+    /// Commits a pair must share before it counts as coupled.
+    pub min_shared_commits: usize,
+    /// Minimum coupling degree: shared commits over the pair's average
+    /// commit count.
+    pub min_coupling: f64,
+    /// Commits touching more indexed files than this (mass renames,
+    /// formatting sweeps) count towards churn but not towards coupling.
+    pub max_files_per_commit: usize,
+    /// Same cap for the callable symbols a commit touches.
+    pub max_symbols_per_commit: usize,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            min_shared_commits: 3,
+            min_coupling: 0.5,
+            max_files_per_commit: 30,
+            max_symbols_per_commit: 40,
+        }
+    }
+}
+
+/// Commits read from `git log`, newest first.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeHistory {
+    root: PathBuf,
+    commits: Vec<Commit>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Commit {
+    author: String,
+    files: Vec<FileChange>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct FileChange {
+    path: String,
+    added: usize,
+    removed: usize,
+    /// Changed line ranges on the new side, 1-based and inclusive.
+    hunks: Vec<(u32, u32)>,
+}
+
+impl ChangeHistory {
+    /// Reads the last `max_commits` non-merge commits touching `root`.
+    /// Returns `None` outside a git repository.
+    pub fn load(root: &Path, max_commits: usize) -> Option<Self> {
+        let output = Command::new("git")
+            .args([
+                "log",
+                "--no-merges",
+                "--no-renames",
+                "--relative",
+                "--no-color",
+                "--no-ext-diff",
+                "--numstat",
+                "-p",
+                "-U0",
+                "--format=%x1e%H%x1f%an%x1f%at",
+            ])
+            .arg(format!("-n{}", max_commits))
+            .current_dir(root)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        Some(Self::parse(root, &text))
+    }
+
+    /// The commit `HEAD` points to, for cache invalidation.
+    pub fn head(root: &Path) -> Option<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(root)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// Parses the output of the `git log` invocation in [`Self::load`].
+    pub fn parse(root: &Path, text: &str) -> Self {
+        let mut commits = Vec::new();
+        for chunk in text.split(COMMIT_MARKER).skip(1) {
+            let mut lines = chunk.lines();
+            let header = lines.next().unwrap_or_default();
+            let author = header.split(FIELD_MARKER).nth(1).unwrap_or_default();
+            let mut commit = Commit {
+                author: author.to_string(),
+                files: Vec::new(),
+            };
+            let mut in_patch = false;
+            let mut current: Option<usize> = None;
+            for line in lines {
+                if line.starts_with("diff --git ") {
+                    in_patch = true;
+                    current = None;
+                } else if !in_patch {
+                    if let Some(change) = parse_numstat(line) {
+                        commit.files.push(change);
+                    }
+                } else if let Some(path) = line.strip_prefix("+++ b/") {
+                    let path = unquote(path);
+                    current = commit.files.iter().position(|f| f.path == path);
+                } else if let (Some(index), Some(range)) = (current, parse_hunk_header(line)) {
+                    commit.files[index].hunks.push(range);
+                }
+            }
+            commits.push(commit);
+        }
+        Self {
+            root: root.to_path_buf(),
+            commits,
+        }
+    }
+
+    /// Number of commits read.
+    pub fn commit_count(&self) -> usize {
+        self.commits.len()
+    }
+
+    fn relative(&self, file: &str) -> String {
+        let path = Path::new(file);
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+/// Lines changed in the working tree against `HEAD` (or between two
+/// commits), from `git diff -U0`.
+#[derive(Debug, Clone, Default)]
+pub struct WorkingChanges {
+    root: PathBuf,
+    /// Changed line ranges on the new side, by path relative to the root.
+    hunks: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl WorkingChanges {
+    /// Diffs the working tree and index against `HEAD`. Returns `None`
+    /// outside a git repository or before the first commit.
+    pub fn load(root: &Path) -> Option<Self> {
+        Self::diff(root, &["HEAD"])
+    }
+
+    /// Lines changed from commit `base` to commit `head`, as a pull
+    /// request sees them. Returns `None` if git cannot diff the range.
+    pub fn load_range(root: &Path, base: &str, head: &str) -> Option<Self> {
+        Self::diff(root, &[base, head])
+    }
+
+    fn diff(root: &Path, revisions: &[&str]) -> Option<Self> {
+        let output = Command::new("git")
+            .arg("diff")
+            .args(revisions)
+            .args(["--relative", "--no-color", "--no-ext-diff", "-U0"])
+            .current_dir(root)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        Some(Self::parse(root, &text))
+    }
+
+    /// Parses the output of the `git diff` invocation in [`Self::load`].
+    pub fn parse(root: &Path, text: &str) -> Self {
+        let mut hunks: HashMap<String, Vec<(u32, u32)>> = HashMap::new();
+        let mut current: Option<String> = None;
+        for line in text.lines() {
+            if line.starts_with("diff --git ") {
+                current = None;
+            } else if let Some(path) = line.strip_prefix("+++ b/") {
+                let path = unquote(path);
+                hunks.entry(path.clone()).or_default();
+                current = Some(path);
+            } else if let (Some(path), Some(range)) = (&current, parse_hunk_header(line)) {
+                hunks.entry(path.clone()).or_default().push(range);
+            }
+        }
+        Self {
+            root: root.to_path_buf(),
+            hunks,
+        }
+    }
+
+    /// Changed line ranges of `file`, or `None` if git has no line-level
+    /// diff for it (an untracked file, or one not changed at all).
+    fn hunks(&self, file: &str) -> Option<&[(u32, u32)]> {
+        let path = Path::new(file);
+        let relative = path
+            .strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        self.hunks.get(&relative).map(Vec::as_slice)
+    }
+}
+
+/// `added<TAB>removed<TAB>path`; binary files show `-` counts.
+fn parse_numstat(line: &str) -> Option<FileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let added = parts.next()?;
+    let removed = parts.next()?;
+    let path = parts.next()?;
+    let count = |s: &str| {
+        if s == "-" {
+            Some(0)
+        } else {
+            s.parse().ok()
+        }
+    };
+    Some(FileChange {
+        path: unquote(path),
+        added: count(added)?,
+        removed: count(removed)?,
+        hunks: Vec::new(),
+    })
+}
+
+/// The new-side range of `@@ -a,b +c,d @@`. A pure deletion (`d == 0`)
+/// marks the line it happened after.
+fn parse_hunk_header(line: &str) -> Option<(u32, u32)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let new_side = rest.split_whitespace().nth(1)?.strip_prefix('+')?;
+    let (start, count) = match new_side.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (new_side.parse::<u32>().ok()?, 1),
+    };
+    let start = start.max(1);
+    Some((start, start + count.saturating_sub(1)))
+}
+
+fn unquote(path: &str) -> String {
+    path.strip_prefix('"')
+        .and_then(|p| p.strip_suffix('"'))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Churn of one indexed file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChurn {
+    /// Path relative to the project root.
+    pub file: String,
+    pub commits: usize,
+    pub lines_added: usize,
+    pub lines_removed: usize,
+    pub authors: usize,
+}
+
+/// Churn of one symbol, and how risky it is to change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolChurn {
+    #[serde(flatten)]
+    pub node_info: NodeInfo,
+    /// Commits whose hunks overlap the symbol's current lines.
+    pub commits: usize,
+    pub authors: usize,
+    /// Log-scaled churn weighted by centrality, in `0.0..=1.0`.
+    pub risk: f64,
+}
+
+/// Two files that change in the same commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileCoupling {
+    pub a: String,
+    pub b: String,
+    pub shared_commits: usize,
+    /// Shared commits over the average commit count of the two files.
+    pub degree: f64,
+    /// Whether any symbol in one file has an edge to the other.
+    pub static_edge: bool,
+}
+
+/// Two symbols in different files that change in the same commits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolCoupling {
+    pub a: NodeInfo,
+    pub b: NodeInfo,
+    pub shared_commits: usize,
+    pub degree: f64,
+    pub static_edge: bool,
+}
+
+/// Churn, coupling and risk for a graph over a change history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryReport {
+    pub commits_analyzed: usize,
+    /// Indexed files by commit count, most changed first.
+    pub files: Vec<FileChurn>,
+    /// Changed symbols by risk, riskiest first.
+    pub symbols: Vec<SymbolChurn>,
+    /// Strongest first.
+    pub file_coupling: Vec<FileCoupling>,
+    /// Strongest first.
+    pub symbol_coupling: Vec<SymbolCoupling>,
+}
+
+impl HistoryReport {
+    /// The `n` riskiest symbols.
+    pub fn hotspots(&self, n: usize) -> &[SymbolChurn] {
+        &self.symbols[..n.min(self.symbols.len())]
+    }
+
+    /// Churn of a file, absolute or relative to the project root.
+    pub fn file(&self, root: &Path, file: &str) -> Option<&FileChurn> {
+        let file = relative_to(root, file);
+        self.files.iter().find(|f| f.file == file)
+    }
+
+    /// Churn of a symbol by node ID.
+    pub fn symbol(&self, id: &str) -> Option<&SymbolChurn> {
+        self.symbols.iter().find(|s| s.node_info.id == id)
+    }
+
+    /// File pairs that change together with no static edge between them.
+    pub fn hidden_file_coupling(&self) -> impl Iterator<Item = &FileCoupling> {
+        self.file_coupling.iter().filter(|c| !c.static_edge)
+    }
+
+    /// Files that usually change together with `file` but have no static
+    /// edge to it, with their coupling.
+    pub fn hidden_partners(&self, root: &Path, file: &str) -> Vec<(&str, &FileCoupling)> {
+        let file = relative_to(root, file);
+        self.hidden_file_coupling()
+            .filter_map(|c| {
+                if c.a == file {
+                    Some((c.b.as_str(), c))
+                } else if c.b == file {
+                    Some((c.a.as_str(), c))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Adds hidden co-change partners of the analysis target as uncertain
+    /// edges. Symbols come first; files are added when no symbol in them
+    /// was. Partners already in the impact set are skipped.
+    pub fn annotate(&self, root: &Path, analysis: &mut ImpactAnalysis) {
+        let target = analysis.target.clone();
+        let mut covered: HashSet<String> = analysis
+            .all_affected()
+            .iter()
+            .map(|n| n.node_info.id.clone())
+            .collect();
+        let mut covered_files: HashSet<String> = analysis
+            .all_affected()
+            .iter()
+            .map(|n| relative_to(root, &n.node_info.file))
+            .collect();
+
+        for coupling in self.symbol_coupling.iter().filter(|c| !c.static_edge) {
+            let partner = if coupling.a.id == target.id {
+                &coupling.b
+            } else if coupling.b.id == target.id {
+                &coupling.a
+            } else {
+                continue;
+            };
+            if !covered.insert(partner.id.clone()) {
+                continue;
+            }
+            covered_files.insert(relative_to(root, &partner.file));
+            analysis.uncertain_edges.push(UncertainEdge {
+                from: target.id.clone(),
+                to: partner.id.clone(),
+                kind: UncertainEdgeKind::CoChange,
+                confidence: coupling.degree as f32,
+                reason: co_change_reason(&partner.qualified_name, coupling.shared_commits),
+            });
+        }
+
+        for (partner, coupling) in self.hidden_partners(root, &target.file) {
+            if !covered_files.insert(partner.to_string()) {
+                continue;
+            }
+            analysis.uncertain_edges.push(UncertainEdge {
+                from: target.id.clone(),
+                to: partner.to_string(),
+                kind: UncertainEdgeKind::CoChange,
+                confidence: coupling.degree as f32,
+                reason: co_change_reason(partner, coupling.shared_commits),
+            });
+        }
+    }
+}
+
+fn co_change_reason(partner: &str, shared: usize) -> String {
+    format!(
+        "'{}' changed in the same commit {} times with no static edge",
+        partner, shared
+    )
+}
+
+fn relative_to(root: &Path, file: &str) -> String {
+    let path = Path::new(file);
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Symbols that get churn numbers.
+fn is_tracked(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function
+            | NodeKind::Method
+            | NodeKind::Constructor
+            | NodeKind::Class
+            | NodeKind::Struct
+            | NodeKind::Interface
+            | NodeKind::Enum
+    )
+}
+
+/// Symbols that take part in symbol-level coupling. Types are left out:
+/// they change whenever any of their methods do.
+fn is_callable(kind: NodeKind) -> bool {
+    matches!(
+        kind,
+        NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+    )
+}
+
+/// Counts how often each unordered pair appears in the same commit.
+#[derive(Default)]
+struct PairCounter<T> {
+    counts: HashMap<T, usize>,
+    pairs: HashMap<(T, T), usize>,
+}
+
+impl<T: Clone + Ord + std::hash::Hash> PairCounter<T> {
+    fn add(&mut self, items: &BTreeSet<T>, max: usize) {
+        for item in items {
+            *self.counts.entry(item.clone()).or_default() += 1;
+        }
+        if items.len() > max {
+            return;
+        }
+        let items: Vec<&T> = items.iter().collect();
+        for (i, a) in items.iter().enumerate() {
+            for b in &items[i + 1..] {
+                *self.pairs.entry(((*a).clone(), (*b).clone())).or_default() += 1;
+            }
+        }
+    }
+
+    /// Pairs above the thresholds, with their shared count and degree.
+    fn coupled(&self, options: &HistoryOptions) -> Vec<(&T, &T, usize, f64)> {
+        self.pairs
+            .iter()
+            .filter(|(_, &shared)| shared >= options.min_shared_commits)
+            .filter_map(|((a, b), &shared)| {
+                let average = (self.counts[a] + self.counts[b]) as f64 / 2.0;
+                let degree = (shared as f64 / average).min(1.0);
+                (degree >= options.min_coupling).then_some((a, b, shared, degree))
+            })
+            .collect()
+    }
+}
+
+impl ArborGraph {
+    /// The symbols a working-tree change touches, among the nodes of
+    /// `changed_files`: for each changed line range, the innermost symbols
+    /// overlapping it, so editing one method does not also count its class
+    /// or its siblings. Files git has no line-level diff for, like new
+    /// untracked files, count in full.
+    pub fn changed_symbols(
+        &self,
+        changes: &WorkingChanges,
+        changed_files: impl Fn(&str) -> bool,
+    ) -> Vec<NodeId> {
+        let mut changed = Vec::new();
+        let mut by_file: HashMap<&str, Vec<NodeId>> = HashMap::new();
+        for index in self.node_indexes() {
+            let Some(node) = self.get(index) else {
+                continue;
+            };
+            if changed_files(&node.file) {
+                by_file.entry(node.file.as_str()).or_default().push(index);
+            }
+        }
+
+        for (file, nodes) in by_file {
+            let Some(hunks) = changes.hunks(file) else {
+                changed.extend(nodes);
+                continue;
+            };
+            for &(start, end) in hunks {
+                let overlapping: Vec<(NodeId, &CodeNode)> = nodes
+                    .iter()
+                    .filter_map(|&index| Some((index, self.get(index)?)))
+                    .filter(|(_, node)| start <= node.line_end && end >= node.line_start)
+                    .collect();
+                for &(index, node) in &overlapping {
+                    let encloses_another = overlapping.iter().any(|(_, other)| {
+                        (other.line_start, other.line_end) != (node.line_start, node.line_end)
+                            && other.line_start >= node.line_start
+                            && other.line_end <= node.line_end
+                    });
+                    if !encloses_another {
+                        changed.push(index);
+                    }
+                }
+            }
+        }
+
+        changed.sort();
+        changed.dedup();
+        changed
+    }
+
+    /// Maps a change history onto the graph: churn per file and symbol,
+    /// risk (churn weighted by centrality) and temporal coupling.
+    pub fn analyze_history(
+        &self,
+        history: &ChangeHistory,
+        options: &HistoryOptions,
+    ) -> HistoryReport {
+        // Indexed files, keyed by path relative to the root.
+        let mut by_file: HashMap<String, Vec<NodeId>> = HashMap::new();
+        for index in self.node_indexes() {
+            if let Some(node) = self.get(index) {
+                by_file
+                    .entry(history.relative(&node.file))
+                    .or_default()
+                    .push(index);
+            }
+        }
+
+        #[derive(Default)]
+        struct Tally {
+            commits: usize,
+            added: usize,
+            removed: usize,
+            authors: HashSet<String>,
+        }
+        let mut files: HashMap<&str, Tally> = HashMap::new();
+        let mut symbols: HashMap<NodeId, Tally> = HashMap::new();
+        let mut file_pairs: PairCounter<String> = PairCounter::default();
+        let mut symbol_pairs: PairCounter<NodeId> = PairCounter::default();
+
+        for commit in &history.commits {
+            let mut touched_files = BTreeSet::new();
+            let mut touched_symbols = BTreeSet::new();
+            for change in &commit.files {
+                let Some(nodes) = by_file.get(&change.path) else {
+                    continue;
+                };
+                touched_files.insert(change.path.clone());
+                let tally = files.entry(change.path.as_str()).or_default();
+                tally.commits += 1;
+                tally.added += change.added;
+                tally.removed += change.removed;
+                tally.authors.insert(commit.author.clone());
+
+                for &index in nodes {
+                    let Some(node) = self.get(index) else {
+                        continue;
+                    };
+                    if !is_tracked(node.kind) {
+                        continue;
+                    }
+                    let overlaps = change
+                        .hunks
+                        .iter()
+                        .any(|&(start, end)| start <= node.line_end && end >= node.line_start);
+                    if !overlaps {
+                        continue;
+                    }
+                    let tally = symbols.entry(index).or_default();
+                    tally.commits += 1;
+                    tally.authors.insert(commit.author.clone());
+                    if is_callable(node.kind) {
+                        touched_symbols.insert(index);
+                    }
+                }
+            }
+            file_pairs.add(&touched_files, options.max_files_per_commit);
+            symbol_pairs.add(&touched_symbols, options.max_symbols_per_commit);
+        }
+
+        let mut file_churn: Vec<FileChurn> = files
+            .into_iter()
+            .map(|(file, tally)| FileChurn {
+                file: file.to_string(),
+                commits: tally.commits,
+                lines_added: tally.added,
+                lines_removed: tally.removed,
+                authors: tally.authors.len(),
+            })
+            .collect();
+        file_churn.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.file.cmp(&b.file)));
+
+        // Log scale keeps one very busy symbol from flattening the rest;
+        // a symbol nobody calls still scores, at a fifth of the weight.
+        let max_commits = symbols.values().map(|t| t.commits).max().unwrap_or(0);
+        let scale = ((1 + max_commits) as f64).ln();
+        let mut symbol_churn: Vec<SymbolChurn> = symbols
+            .into_iter()
+            .filter_map(|(index, tally)| {
+                let mut node_info = NodeInfo::from(self.get(index)?);
+                node_info.centrality = self.centrality(index);
+                let churn = ((1 + tally.commits) as f64).ln() / scale;
+                Some(SymbolChurn {
+                    risk: churn * (0.2 + 0.8 * node_info.centrality),
+                    node_info,
+                    commits: tally.commits,
+                    authors: tally.authors.len(),
+                })
+            })
+            .collect();
+        symbol_churn.sort_by(|a, b| {
+            b.risk
+                .total_cmp(&a.risk)
+                .then_with(|| b.commits.cmp(&a.commits))
+                .then_with(|| a.node_info.id.cmp(&b.node_info.id))
+        });
+
+        let linked_files = self.linked_file_pairs(history);
+        let mut file_coupling: Vec<FileCoupling> = file_pairs
+            .coupled(options)
+            .into_iter()
+            .map(|(a, b, shared_commits, degree)| FileCoupling {
+                static_edge: linked_files.contains(&(a.clone(), b.clone())),
+                a: a.clone(),
+                b: b.clone(),
+                shared_commits,
+                degree,
+            })
+            .collect();
+        file_coupling.sort_by(|x, y| {
+            y.degree
+                .total_cmp(&x.degree)
+                .then_with(|| y.shared_commits.cmp(&x.shared_commits))
+                .then_with(|| (&x.a, &x.b).cmp(&(&y.a, &y.b)))
+        });
+
+        let mut symbol_coupling: Vec<SymbolCoupling> = symbol_pairs
+            .coupled(options)
+            .into_iter()
+            .filter_map(|(&a, &b, shared_commits, degree)| {
+                let (node_a, node_b) = (self.get(a)?, self.get(b)?);
+                if node_a.file == node_b.file {
+                    return None;
+                }
+                Some(SymbolCoupling {
+                    a: NodeInfo::from(node_a),
+                    b: NodeInfo::from(node_b),
+                    shared_commits,
+                    degree,
+                    static_edge: self.graph.contains_edge(a, b) || self.graph.contains_edge(b, a),
+                })
+            })
+            .collect();
+        symbol_coupling.sort_by(|x, y| {
+            y.degree
+                .total_cmp(&x.degree)
+                .then_with(|| y.shared_commits.cmp(&x.shared_commits))
+                .then_with(|| (&x.a.id, &x.b.id).cmp(&(&y.a.id, &y.b.id)))
+        });
+
+        HistoryReport {
+            commits_analyzed: history.commit_count(),
+            files: file_churn,
+            symbols: symbol_churn,
+            file_coupling,
+            symbol_coupling,
+        }
+    }
+
+    /// Ordered pairs of distinct relative file paths joined by an edge.
+    fn linked_file_pairs(&self, history: &ChangeHistory) -> HashSet<(String, String)> {
+        let mut pairs = HashSet::new();
+        for edge in self.graph.edge_indices() {
+            let Some((from, to)) = self.graph.edge_endpoints(edge) else {
+                continue;
+            };
+            let (Some(from), Some(to)) = (self.get(from), self.get(to)) else {
+                continue;
+            };
+            if from.file == to.file {
+                continue;
+            }
+            let (a, b) = (history.relative(&from.file), history.relative(&to.file));
+            pairs.insert((a.clone(), b.clone()));
+            pairs.insert((b, a));
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{Edge, EdgeKind};
+    use crate::impact::ImpactAnalysis;
+    use arbor_core::CodeNode;
+
+    fn node(name: &str, file: &str, lines: (u32, u32)) -> CodeNode {
+        CodeNode::new(name, name, NodeKind::Function, file).with_lines(lines.0, lines.1)
+    }
+
+    /// One commit in `git log` output form: `(path, added, removed, hunks)`.
+    fn commit(author: &str, files: &[(&str, usize, usize, &[&str])]) -> String {
+        let mut out = format!("\u{1e}abc\u{1f}{}\u{1f}0\n\n", author);
+        for (path, added, removed, _) in files {
+            out.push_str(&format!("{}\t{}\t{}\n", added, removed, path));
+        }
+        out.push('\n');
+        for (path, _, _, hunks) in files {
+            out.push_str(&format!(
+                "diff --git a/{p} b/{p}\n--- a/{p}\n+++ b/{p}\n",
+                p = path
+            ));
+            for hunk in *hunks {
+                out.push_str(hunk);
+                out.push_str("\n+changed\n");
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_parses_numstat_and_hunks() {
+        let text = commit(
+            "Alice",
+            &[(
+                "src/a.rs",
+                4,
+                1,
+                &["@@ -2 +2,4 @@ fn a()", "@@ -9,2 +12,0 @@"],
+            )],
+        );
+        let history = ChangeHistory::parse(Path::new("/repo"), &text);
+        assert_eq!(history.commit_count(), 1);
+        let change = &history.commits[0].files[0];
+        assert_eq!(history.commits[0].author, "Alice");
+        assert_eq!((change.added, change.removed), (4, 1));
+        assert_eq!(change.hunks, [(2, 5), (12, 12)]);
+    }
+
+    #[test]
+    fn test_maps_hunks_onto_symbols_and_ranks_by_risk() {
+        let mut graph = ArborGraph::new();
+        let busy = graph.add_node(node("busy", "src/a.rs", (1, 10)));
+        let quiet = graph.add_node(node("quiet", "src/a.rs", (20, 30)));
+        let caller = graph.add_node(node("caller", "src/b.rs", (1, 5)));
+        graph.add_edge(caller, busy, Edge::new(EdgeKind::Calls));
+        graph.set_centrality(HashMap::from([(busy, 1.0), (quiet, 0.0), (caller, 0.0)]));
+
+        let mut text = String::new();
+        for author in ["Alice", "Bob", "Alice"] {
+            text.push_str(&commit(author, &[("src/a.rs", 1, 1, &["@@ -3 +3 @@"])]));
+        }
+        text.push_str(&commit(
+            "Carol",
+            &[("src/a.rs", 2, 0, &["@@ -21 +21,2 @@"])],
+        ));
+        let history = ChangeHistory::parse(Path::new("/repo"), &text);
+        let report = graph.analyze_history(&history, &HistoryOptions::default());
+
+        assert_eq!(report.commits_analyzed, 4);
+        assert_eq!(report.files[0].file, "src/a.rs");
+        assert_eq!(report.files[0].commits, 4);
+        assert_eq!(report.files[0].authors, 3);
+
+        let names: Vec<&str> = report
+            .symbols
+            .iter()
+            .map(|s| s.node_info.name.as_str())
+            .collect();
+        assert_eq!(names, ["busy", "quiet"]);
+        assert_eq!(report.symbols[0].commits, 3);
+        assert_eq!(report.symbols[0].authors, 2);
+        assert!((report.symbols[0].risk - 1.0).abs() < 1e-9);
+        assert!(report.symbols[1].risk < 0.2);
+    }
+
+    #[test]
+    fn test_reports_hidden_coupling_as_co_change_edges() {
+        let mut graph = ArborGraph::new();
+        let schema = graph.add_node(node("schema", "api/schema.py", (1, 10)));
+        let client = graph.add_node(node("client", "web/client.ts", (1, 10)));
+        let caller = graph.add_node(node("caller", "api/views.py", (1, 10)));
+        graph.add_edge(caller, schema, Edge::new(EdgeKind::Calls));
+
+        let both: &[(&str, usize, usize, &[&str])] = &[
+            ("api/schema.py", 1, 1, &["@@ -2 +2 @@"]),
+            ("web/client.ts", 1, 1, &["@@ -2 +2 @@"]),
+            ("api/views.py", 1, 1, &["@@ -2 +2 @@"]),
+        ];
+        let text: String = (0..3).map(|_| commit("Alice", both)).collect();
+        let history = ChangeHistory::parse(Path::new("/repo"), &text);
+        let report = graph.analyze_history(&history, &HistoryOptions::default());
+
+        let hidden: Vec<(&str, &str)> = report
+            .hidden_file_coupling()
+            .map(|c| (c.a.as_str(), c.b.as_str()))
+            .collect();
+        assert_eq!(
+            hidden,
+            [
+                ("api/schema.py", "web/client.ts"),
+                ("api/views.py", "web/client.ts")
+            ]
+        );
+        assert!(report
+            .file_coupling
+            .iter()
+            .any(|c| c.static_edge && c.a == "api/schema.py" && c.b == "api/views.py"));
+
+        let mut analysis: ImpactAnalysis = graph.analyze_impact(schema, 5);
+        report.annotate(Path::new("/repo"), &mut analysis);
+        let partners: Vec<&str> = analysis
+            .uncertain_edges
+            .iter()
+            .map(|e| e.to.as_str())
+            .collect();
+        assert_eq!(partners, [graph.get(client).unwrap().id.as_str()]);
+        assert_eq!(
+            analysis.uncertain_edges[0].kind,
+            UncertainEdgeKind::CoChange
+        );
+        assert!((analysis.uncertain_edges[0].confidence - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_wide_commits_do_not_couple() {
+        let mut graph = ArborGraph::new();
+        graph.add_node(node("a", "a.py", (1, 5)));
+        graph.add_node(node("b", "b.py", (1, 5)));
+        let both: &[(&str, usize, usize, &[&str])] = &[
+            ("a.py", 1, 0, &["@@ -1 +1 @@"]),
+            ("b.py", 1, 0, &["@@ -1 +1 @@"]),
+        ];
+        let text: String = (0..3).map(|_| commit("Alice", both)).collect();
+        let history = ChangeHistory::parse(Path::new("/repo"), &text);
+        let options = HistoryOptions {
+            max_files_per_commit: 1,
+            max_symbols_per_commit: 1,
+            ..HistoryOptions::default()
+        };
+        let report = graph.analyze_history(&history, &options);
+        assert!(report.file_coupling.is_empty());
+        assert!(report.symbol_coupling.is_empty());
+        assert_eq!(report.files.len(), 2);
+    }
+
+    #[test]
+    fn test_working_changes_select_the_edited_symbol_only() {
+        let mut graph = ArborGraph::new();
+        let class = graph.add_node(
+            CodeNode::new("Cart", "Cart", NodeKind::Class, "/repo/src/cart.py").with_lines(1, 20),
+        );
+        let total = graph.add_node(node("total", "/repo/src/cart.py", (2, 8)));
+        let sibling = graph.add_node(node("clear", "/repo/src/cart.py", (10, 14)));
+        let fresh = graph.add_node(node("helper", "/repo/src/new.py", (1, 3)));
+        let test = graph.add_node(node("test_clear", "/repo/tests/test_cart.py", (1, 4)));
+        graph.add_edge(test, sibling, Edge::new(EdgeKind::Calls));
+
+        let diff = "diff --git a/src/cart.py b/src/cart.py\n\
+                    --- a/src/cart.py\n\
+                    +++ b/src/cart.py\n\
+                    @@ -4 +4 @@ def total(self):\n\
+                    -        return 0\n\
+                    +        return sum(self.items)\n";
+        let changes = WorkingChanges::parse(Path::new("/repo"), diff);
+        let changed_files = ["src/cart.py", "src/new.py"];
+        let changed = graph.changed_symbols(&changes, |file| {
+            changed_files.iter().any(|f| file.ends_with(f))
+        });
+
+        // The edited method, not its class or its sibling; the untracked
+        // file in full.
+        assert_eq!(changed, [total, fresh]);
+        assert!(!changed.contains(&class));
+        assert!(graph.affected_tests(&changed, 0).tests.is_empty());
+    }
+}
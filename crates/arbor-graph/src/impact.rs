@@ -4,13 +4,15 @@
 //! affected by a change to a target node. It answers the question:
 //! "What breaks if I change this?"
 
-use crate::edge::EdgeKind;
+use crate::confidence::ConfidenceFactor;
+use crate::edge::{Edge, EdgeKind};
 use crate::graph::{ArborGraph, NodeId};
+use crate::heuristics::UncertainEdge;
 use crate::query::NodeInfo;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::time::Instant;
 
 /// Severity of impact based on hop distance from target.
@@ -91,6 +93,55 @@ pub struct AffectedNode {
     pub entry_edge: EdgeKind,
     /// Whether this node is upstream or downstream of target.
     pub direction: ImpactDirection,
+    /// `CODEOWNERS` owners, once filled in by [`Ownership::annotate`](crate::Ownership::annotate).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+    /// Author of most of the node's lines per `git blame`, if requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_author: Option<String>,
+    /// Probability that a change to the target reaches this node: the
+    /// product of the weights in `trace`. Severity says how far away the
+    /// node is; this says how sure the graph is about the path.
+    #[serde(default = "certain")]
+    pub probability: f32,
+    /// The factors that lowered `probability`, in path order from the
+    /// target.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trace: Vec<ConfidenceFactor>,
+}
+
+fn certain() -> f32 {
+    1.0
+}
+
+/// How sure a traversal is that it reached a node, and why not more.
+#[derive(Clone)]
+struct Reach<'g> {
+    probability: f32,
+    trace: Vec<ConfidenceFactor>,
+    /// Files whose parse quality already counts on this path.
+    files: Vec<&'g str>,
+}
+
+impl Reach<'_> {
+    fn certain() -> Self {
+        Self {
+            probability: 1.0,
+            trace: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, factor: ConfidenceFactor) {
+        self.probability *= factor.weight;
+        self.trace.push(factor);
+    }
+}
+
+/// What a traversal hop crosses.
+enum Link<'a> {
+    Edge(&'a Edge),
+    Uncertain(&'a UncertainEdge),
 }
 
 /// Complete impact analysis result.
@@ -102,6 +153,17 @@ pub struct ImpactAnalysis {
     pub upstream: Vec<AffectedNode>,
     /// Nodes the target depends on (callees, imports, etc.)
     pub downstream: Vec<AffectedNode>,
+    /// Nodes only reached through an uncertain edge, such as callers of an
+    /// interface whose implementation is the target, code that registers
+    /// it as a callback, or consumers it is injected into. Not counted in
+    /// `total_affected`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub possibly_affected: Vec<AffectedNode>,
+    /// Links the graph cannot prove: the dispatch, callback and injection
+    /// candidates that led to `possibly_affected`, and hidden co-change coupling added by
+    /// [`HistoryReport::annotate`](crate::HistoryReport::annotate).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uncertain_edges: Vec<UncertainEdge>,
     /// Total count of affected nodes.
     pub total_affected: usize,
     /// Maximum depth searched.
@@ -190,9 +252,12 @@ impl ArborGraph {
                         line_end: 0,
                         signature: None,
                         centrality: 0.0,
+                        metrics: Default::default(),
                     },
                     upstream: Vec::new(),
                     downstream: Vec::new(),
+                    possibly_affected: Vec::new(),
+                    uncertain_edges: Vec::new(),
                     total_affected: 0,
                     max_depth,
                     query_time_ms: 0,
@@ -209,6 +274,21 @@ impl ArborGraph {
         let upstream = self.bfs_impact(target, Direction::Incoming, effective_depth);
         let downstream = self.bfs_impact(target, Direction::Outgoing, effective_depth);
 
+        let mut certain: HashSet<NodeId> = upstream
+            .iter()
+            .chain(&downstream)
+            .map(|n| n.node_id)
+            .collect();
+        certain.insert(target);
+        let mut possibly_affected = Vec::new();
+        let mut uncertain_edges = Vec::new();
+        for direction in [Direction::Incoming, Direction::Outgoing] {
+            let (nodes, edges) = self.bfs_uncertain(target, direction, effective_depth, &certain);
+            certain.extend(nodes.iter().map(|n| n.node_id));
+            possibly_affected.extend(nodes);
+            uncertain_edges.extend(edges);
+        }
+
         let total = upstream.len() + downstream.len();
         let elapsed = start.elapsed().as_millis() as u64;
 
@@ -216,6 +296,8 @@ impl ArborGraph {
             target: target_node,
             upstream,
             downstream,
+            possibly_affected,
+            uncertain_edges,
             total_affected: total,
             max_depth,
             query_time_ms: elapsed,
@@ -223,6 +305,9 @@ impl ArborGraph {
     }
 
     /// BFS traversal in one direction from target.
+    ///
+    /// Runs level by level so that each node keeps the most probable of
+    /// its shortest paths.
     fn bfs_impact(
         &self,
         target: NodeId,
@@ -230,73 +315,56 @@ impl ArborGraph {
         max_depth: usize,
     ) -> Vec<AffectedNode> {
         let mut result = Vec::new();
-        let mut visited: HashSet<NodeId> = HashSet::new();
-        let mut queue: VecDeque<(NodeId, usize, EdgeKind)> = VecDeque::new();
-
-        // Track entry edges for each node (first edge that reaches it)
-        let mut entry_edges: HashMap<NodeId, EdgeKind> = HashMap::new();
-
-        visited.insert(target);
-
-        // Seed queue with immediate neighbors
-        for edge_ref in self.graph.edges_directed(target, direction) {
-            let neighbor = match direction {
-                Direction::Incoming => edge_ref.source(),
-                Direction::Outgoing => edge_ref.target(),
-            };
-
-            if !visited.contains(&neighbor) {
-                let edge_kind = edge_ref.weight().kind;
-                queue.push_back((neighbor, 1, edge_kind));
-                entry_edges.insert(neighbor, edge_kind);
-            }
-        }
-
-        while let Some((current, depth, entry_edge)) = queue.pop_front() {
-            if depth > max_depth || visited.contains(&current) {
-                continue;
-            }
-
-            visited.insert(current);
-
-            if let Some(node) = self.get(current) {
-                let mut node_info = NodeInfo::from(node);
-                node_info.centrality = self.centrality(current);
-
-                let impact_direction = match direction {
-                    Direction::Incoming => ImpactDirection::Upstream,
-                    Direction::Outgoing => ImpactDirection::Downstream,
+        let mut visited: HashSet<NodeId> = HashSet::from([target]);
+        // Entry edge (kind of the first hop from the target) and path
+        // confidence of each node reached so far
+        let mut reached: HashMap<NodeId, (EdgeKind, Reach)> = HashMap::new();
+        let root = Reach::certain();
+        let mut frontier = vec![target];
+        let mut depth = 0;
+
+        while !frontier.is_empty() && depth < max_depth {
+            depth += 1;
+            let mut next: BTreeMap<NodeId, (EdgeKind, Reach)> = BTreeMap::new();
+            for &current in &frontier {
+                let (entry_edge, reach) = match reached.get(&current) {
+                    Some((kind, reach)) => (Some(*kind), reach),
+                    None => (None, &root),
                 };
-
-                result.push(AffectedNode {
-                    node_id: current,
-                    node_info,
-                    severity: ImpactSeverity::from_hops(depth),
-                    hop_distance: depth,
-                    entry_edge,
-                    direction: impact_direction,
-                });
-            }
-
-            // Continue BFS if not at max depth
-            if depth < max_depth {
                 for edge_ref in self.graph.edges_directed(current, direction) {
                     let neighbor = match direction {
                         Direction::Incoming => edge_ref.source(),
                         Direction::Outgoing => edge_ref.target(),
                     };
-
-                    if !visited.contains(&neighbor) {
-                        let next_entry = *entry_edges.get(&neighbor).unwrap_or(&entry_edge);
-                        queue.push_back((neighbor, depth + 1, next_entry));
-
-                        // Store entry edge for first arrival
-                        entry_edges
-                            .entry(neighbor)
-                            .or_insert(edge_ref.weight().kind);
+                    if visited.contains(&neighbor) {
+                        continue;
+                    }
+                    let candidate = self.step(
+                        reach,
+                        edge_ref.source(),
+                        edge_ref.target(),
+                        Link::Edge(edge_ref.weight()),
+                    );
+                    let better = next
+                        .get(&neighbor)
+                        .is_none_or(|(_, best)| candidate.probability > best.probability);
+                    if better {
+                        let entry_edge = entry_edge.unwrap_or(edge_ref.weight().kind);
+                        next.insert(neighbor, (entry_edge, candidate));
                     }
                 }
             }
+
+            frontier = next.keys().copied().collect();
+            for (node_id, (entry_edge, reach)) in next {
+                visited.insert(node_id);
+                if let Some(node) =
+                    self.affected_node(node_id, depth, entry_edge, direction, &reach)
+                {
+                    result.push(node);
+                }
+                reached.insert(node_id, (entry_edge, reach));
+            }
         }
 
         // Sort by severity → hop_distance → id for stable ordering
@@ -309,6 +377,143 @@ impl ArborGraph {
 
         result
     }
+
+    /// `reach` extended over one hop from `source` to `target`, in graph
+    /// direction. The reference behind the hop was read from the source's
+    /// file, so that file's parse quality counts too.
+    fn step<'g>(
+        &'g self,
+        reach: &Reach<'g>,
+        source: NodeId,
+        target: NodeId,
+        link: Link,
+    ) -> Reach<'g> {
+        let mut next = reach.clone();
+        let source_node = &self.graph[source];
+        match link {
+            Link::Edge(edge) => {
+                if let Some(factor) =
+                    ConfidenceFactor::resolution(edge, &source_node.name, &self.graph[target].name)
+                {
+                    next.apply(factor);
+                }
+            }
+            Link::Uncertain(edge) => next.apply(ConfidenceFactor::uncertain(edge)),
+        }
+        let file = source_node.file.as_str();
+        if !next.files.contains(&file) {
+            next.files.push(file);
+            if let Some(diagnostics) = self.file_diagnostics(file) {
+                for factor in ConfidenceFactor::parse(file, diagnostics) {
+                    next.apply(factor);
+                }
+            }
+        }
+        next
+    }
+
+    fn affected_node(
+        &self,
+        node_id: NodeId,
+        hops: usize,
+        entry_edge: EdgeKind,
+        direction: Direction,
+        reach: &Reach,
+    ) -> Option<AffectedNode> {
+        let node = self.get(node_id)?;
+        let mut node_info = NodeInfo::from(node);
+        node_info.centrality = self.centrality(node_id);
+        Some(AffectedNode {
+            node_id,
+            node_info,
+            severity: ImpactSeverity::from_hops(hops),
+            hop_distance: hops,
+            entry_edge,
+            direction: match direction {
+                Direction::Incoming => ImpactDirection::Upstream,
+                Direction::Outgoing => ImpactDirection::Downstream,
+            },
+            owners: Vec::new(),
+            primary_author: None,
+            probability: reach.probability,
+            trace: reach.trace.clone(),
+        })
+    }
+
+    /// BFS over graph edges and uncertain edges together. Returns the
+    /// nodes outside `certain` it reaches, which only an uncertain edge
+    /// connects to the target, and the uncertain edges it crossed.
+    fn bfs_uncertain(
+        &self,
+        target: NodeId,
+        direction: Direction,
+        max_depth: usize,
+        certain: &HashSet<NodeId>,
+    ) -> (Vec<AffectedNode>, Vec<UncertainEdge>) {
+        let mut links: HashMap<NodeId, Vec<(NodeId, &UncertainEdge)>> = HashMap::new();
+        for edge in self.uncertain_edges() {
+            let (Some(from), Some(to)) = (self.get_index(&edge.from), self.get_index(&edge.to))
+            else {
+                continue;
+            };
+            match direction {
+                Direction::Incoming => links.entry(to).or_default().push((from, edge)),
+                Direction::Outgoing => links.entry(from).or_default().push((to, edge)),
+            }
+        }
+        if links.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let mut result = Vec::new();
+        let mut crossed = Vec::new();
+        let mut reaches: HashMap<NodeId, Reach> = HashMap::from([(target, Reach::certain())]);
+        let mut queue: VecDeque<(NodeId, usize)> = VecDeque::from([(target, 0)]);
+        while let Some((current, depth)) = queue.pop_front() {
+            if depth >= max_depth {
+                continue;
+            }
+            let graph_edges = self.graph.edges_directed(current, direction).map(|e| {
+                let next = match direction {
+                    Direction::Incoming => e.source(),
+                    Direction::Outgoing => e.target(),
+                };
+                (next, e.weight().kind, Link::Edge(e.weight()))
+            });
+            let uncertain = links
+                .get(&current)
+                .into_iter()
+                .flatten()
+                .map(|&(next, edge)| (next, EdgeKind::Calls, Link::Uncertain(edge)));
+            for (next, kind, link) in graph_edges.chain(uncertain).collect::<Vec<_>>() {
+                if reaches.contains_key(&next) {
+                    continue;
+                }
+                let crossing = match link {
+                    Link::Uncertain(edge) => Some(edge),
+                    Link::Edge(_) => None,
+                };
+                let (source, edge_target) = match direction {
+                    Direction::Incoming => (next, current),
+                    Direction::Outgoing => (current, next),
+                };
+                let reach = self.step(&reaches[&current], source, edge_target, link);
+                queue.push_back((next, depth + 1));
+                if !certain.contains(&next) {
+                    crossed.extend(crossing.cloned());
+                    result.extend(self.affected_node(next, depth + 1, kind, direction, &reach));
+                }
+                reaches.insert(next, reach);
+            }
+        }
+
+        result.sort_by(|a, b| {
+            a.hop_distance
+                .cmp(&b.hop_distance)
+                .then_with(|| a.node_info.id.cmp(&b.node_info.id))
+        });
+        (result, crossed)
+    }
 }
 
 #[cfg(test)]
@@ -506,4 +711,75 @@ mod tests {
         assert!(names.contains(&"m_caller"));
         assert!(names.contains(&"z_caller"));
     }
+
+    #[test]
+    fn test_probability_follows_the_most_certain_path() {
+        use crate::{ConfidenceFactorKind, Provenance};
+        use arbor_core::ParseDiagnostics;
+
+        let mut graph = ArborGraph::new();
+        let target = graph.add_node(CodeNode::new(
+            "target",
+            "target",
+            NodeKind::Function,
+            "t.rs",
+        ));
+        let guessed = graph.add_node(CodeNode::new(
+            "guessed",
+            "guessed",
+            NodeKind::Function,
+            "a.rs",
+        ));
+        let broken = graph.add_node(CodeNode::new(
+            "broken",
+            "broken",
+            NodeKind::Function,
+            "b.rs",
+        ));
+        let top = graph.add_node(CodeNode::new("top", "top", NodeKind::Function, "c.rs"));
+        graph.add_edge(
+            guessed,
+            target,
+            Edge::new(EdgeKind::Calls).with_provenance(Provenance::SameDirSuffix),
+        );
+        graph.add_edge(broken, target, Edge::new(EdgeKind::Calls));
+        graph.add_edge(top, guessed, Edge::new(EdgeKind::Calls));
+        graph.add_edge(top, broken, Edge::new(EdgeKind::Calls));
+        graph.set_file_diagnostics(
+            "b.rs",
+            ParseDiagnostics {
+                used_fallback: true,
+                coverage: 1.0,
+                ..ParseDiagnostics::default()
+            },
+        );
+
+        let result = graph.analyze_impact(target, 3);
+        let node = |name: &str| {
+            result
+                .upstream
+                .iter()
+                .find(|n| n.node_info.name == name)
+                .unwrap()
+        };
+
+        let guessed = node("guessed");
+        assert!((guessed.probability - 0.85).abs() < 1e-6);
+        assert_eq!(guessed.trace[0].kind, ConfidenceFactorKind::Resolution);
+        assert_eq!(
+            guessed.trace[0].detail,
+            "'guessed' → 'target' via same-dir suffix match"
+        );
+
+        let broken = node("broken");
+        assert!((broken.probability - 0.6).abs() < 1e-6);
+        assert_eq!(broken.trace[0].kind, ConfidenceFactorKind::FallbackParser);
+        assert_eq!(broken.trace[0].detail, "b.rs");
+
+        // Two hops either way; the suffix match is the likelier path.
+        let top = node("top");
+        assert_eq!(top.hop_distance, 2);
+        assert!((top.probability - 0.85).abs() < 1e-6);
+        assert_eq!(top.trace.len(), 1);
+    }
 }
@@ -27,37 +27,75 @@
 //! let matches = graph.find_by_name("validate");
 //! ```
 
+mod ann;
 mod builder;
+mod codegen;
 mod confidence;
+mod dead_code;
+mod declared;
 mod diff;
+mod duplicates;
 mod edge;
+mod embedding;
 mod graph;
 mod heuristics;
+mod hierarchy;
+mod history;
 mod impact;
+mod ownership;
+mod paths;
 mod query;
 mod ranking;
+mod retrieval;
 mod search_index;
 mod slice;
+mod source;
+mod test_impact;
+mod topics;
+mod wiring;
 
 pub mod store;
 pub mod symbol_table;
 
-pub use search_index::SearchIndex;
+pub use search_index::{SearchHit, SearchIndex, SearchQuery};
 
 pub use builder::GraphBuilder;
-pub use confidence::{ConfidenceExplanation, ConfidenceLevel, NodeRole};
+pub use confidence::{
+    ConfidenceExplanation, ConfidenceFactor, ConfidenceFactorKind, ConfidenceLevel, NodeRole,
+};
+pub use dead_code::{DeadCodeOptions, DeadCodeReport, DeadFile, DeadSymbol};
+pub use declared::{
+    DeclarationError, Declarations, DeclaredEdge, SymbolAlias, UnresolvedDeclaration, EDGES_CONFIG,
+};
 pub use diff::{
     changed_node_ids, compute_blast_radius, node_matches_changed_file, BlastRadiusSummary,
 };
-pub use edge::{Edge, EdgeKind, GraphEdge};
+pub use duplicates::{CloneGroup, CloneInstance, DuplicateOptions};
+pub use edge::{Edge, EdgeKind, GraphEdge, Provenance};
+pub use embedding::{
+    allow_embeddings, embedding_text, embeddings_allowed, CommandProvider, EmbeddingConfig,
+    EmbeddingError, EmbeddingIndex, EmbeddingProvider, EmbeddingStats, ProviderKind, SimilarityHit,
+    ALLOW_EMBEDDINGS_ENV, EMBEDDINGS_CONFIG,
+};
 pub use graph::{ArborGraph, NodeId};
 pub use heuristics::{
     detect_analysis_limitations, AnalysisWarning, HeuristicsMatcher, UncertainEdge,
     UncertainEdgeKind,
 };
+pub use history::{
+    ChangeHistory, FileChurn, FileCoupling, HistoryOptions, HistoryReport, SymbolChurn,
+    SymbolCoupling, WorkingChanges, HISTORY_COMMITS,
+};
 pub use impact::{AffectedNode, ImpactAnalysis, ImpactDirection, ImpactSeverity};
+pub use ownership::{CodeOwners, OwnerImpact, Ownership};
+pub use paths::{PathOptions, PathResult, PathTruncation};
 pub use query::{DependentInfo, ImpactResult, NodeInfo, QueryResult};
 pub use ranking::{compute_centrality, compute_centrality_warm, CentralityScores};
-pub use slice::{ContextNode, ContextSlice, TruncationReason};
-pub use store::{GraphStore, StoreError};
+pub use retrieval::{extract_keywords, TaskContext, TaskContextNode, TaskContextOptions};
+pub use slice::{count_tokens, ContextNode, ContextSlice, TruncationReason};
+pub use source::{
+    elide, read_node_source, signature_text, NodeSource, SourceDetail, SourcePolicy, SourceReader,
+};
+pub use store::{decode_graph, encode_graph, GraphStore, StoreError};
 pub use symbol_table::SymbolTable;
+pub use test_impact::{AffectedTest, TestImpact, TestRunner};
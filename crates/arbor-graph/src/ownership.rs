@@ -0,0 +1,402 @@
+//! Code ownership from `CODEOWNERS` and `git blame`.
+//!
+//! Owners come from the first `CODEOWNERS` file found in the usual GitHub
+//! and GitLab locations. GitLab sections are supported: each section picks
+//! its own last matching rule, and the owners of all sections combine.
+//! Primary authors are optional, since they need one `git blame` per file.
+
+use crate::impact::ImpactAnalysis;
+use crate::query::NodeInfo;
+use crate::search_index::glob_match;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where `CODEOWNERS` is looked up, in order.
+const CODEOWNERS_PATHS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// Files listed per owner in a summary.
+const MAX_OWNER_FILES: usize = 5;
+
+/// Parsed `CODEOWNERS` rules.
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Section {
+    default_owners: Vec<String>,
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    pattern: String,
+    owners: Vec<String>,
+}
+
+impl CodeOwners {
+    /// Parses GitHub or GitLab `CODEOWNERS` syntax.
+    pub fn parse(text: &str) -> Self {
+        let mut sections = vec![Section::default()];
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // GitLab: `[Section]`, `^[Optional]`, `[Section][2] @default`.
+            let header = line.strip_prefix('^').unwrap_or(line);
+            if let Some(rest) = header.strip_prefix('[') {
+                let Some(end) = rest.find(']') else {
+                    continue;
+                };
+                let mut rest = &rest[end + 1..];
+                if rest.starts_with('[') {
+                    rest = rest.find(']').map_or("", |i| &rest[i + 1..]);
+                }
+                sections.push(Section {
+                    default_owners: owners(rest),
+                    rules: Vec::new(),
+                });
+                continue;
+            }
+
+            let mut parts = line.splitn(2, char::is_whitespace);
+            let pattern = parts.next().unwrap_or_default().to_string();
+            let owners = owners(parts.next().unwrap_or_default());
+            if let Some(section) = sections.last_mut() {
+                section.rules.push(Rule { pattern, owners });
+            }
+        }
+        Self { sections }
+    }
+
+    /// Loads the first `CODEOWNERS` file under `root`.
+    pub fn load(root: &Path) -> Option<Self> {
+        CODEOWNERS_PATHS
+            .iter()
+            .find_map(|path| std::fs::read_to_string(root.join(path)).ok())
+            .map(|text| Self::parse(&text))
+    }
+
+    /// Owners of a path relative to the repository root.
+    pub fn owners(&self, path: &str) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        for section in &self.sections {
+            // The last matching rule wins.
+            let Some(rule) = section
+                .rules
+                .iter()
+                .rev()
+                .find(|rule| rule_matches(&rule.pattern, path))
+            else {
+                continue;
+            };
+            let owners = if rule.owners.is_empty() {
+                &section.default_owners
+            } else {
+                &rule.owners
+            };
+            for owner in owners {
+                if !result.contains(owner) {
+                    result.push(owner.clone());
+                }
+            }
+        }
+        result
+    }
+}
+
+fn owners(text: &str) -> Vec<String> {
+    text.split('#')
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// gitignore-style matching: patterns without an inner `/` match at any
+/// depth, and a matched directory owns everything beneath it, except that
+/// `dir/*` covers only the directory's direct children.
+fn rule_matches(pattern: &str, path: &str) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    if trimmed.is_empty() {
+        return false;
+    }
+    let anchored = trimmed.contains('/');
+    let body = trimmed.trim_start_matches('/');
+    let full = if anchored {
+        body.to_string()
+    } else {
+        format!("**/{}", body)
+    };
+
+    let path: Vec<char> = path.chars().collect();
+    if !dir_only && glob_match(&full.chars().collect::<Vec<_>>(), &path) {
+        return true;
+    }
+    !body.ends_with('*') && glob_match(&format!("{}/**", full).chars().collect::<Vec<_>>(), &path)
+}
+
+/// Impacted nodes attributed to one owner.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnerImpact {
+    /// Team or person (`@org/team`, `@user`, an email, or an author name).
+    pub owner: String,
+    pub nodes: usize,
+    /// Up to five of the owner's affected files, relative to the root.
+    pub files: Vec<String>,
+}
+
+/// Owner lookups for one project.
+#[derive(Debug)]
+pub struct Ownership {
+    root: PathBuf,
+    codeowners: Option<CodeOwners>,
+    /// Per-line authors by relative path; `None` when blame failed.
+    blame: Option<HashMap<String, Option<Vec<String>>>>,
+}
+
+impl Ownership {
+    /// Loads `CODEOWNERS` from `root`. With `blame`, primary authors are
+    /// computed from `git blame` as files are asked about.
+    pub fn load(root: &Path, blame: bool) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            codeowners: CodeOwners::load(root),
+            blame: blame.then(HashMap::new),
+        }
+    }
+
+    /// Whether there is any ownership data to report.
+    pub fn is_available(&self) -> bool {
+        self.codeowners.is_some() || self.blame.is_some()
+    }
+
+    /// `CODEOWNERS` owners of a file (absolute or relative to the root).
+    pub fn owners(&self, file: &str) -> Vec<String> {
+        match &self.codeowners {
+            Some(codeowners) => codeowners.owners(&self.relative(file)),
+            None => Vec::new(),
+        }
+    }
+
+    /// The author of most lines in `line_start..=line_end`, if blame is on.
+    pub fn primary_author(&mut self, file: &str, line_start: u32, line_end: u32) -> Option<String> {
+        let relative = self.relative(file);
+        let root = self.root.clone();
+        let lines = self
+            .blame
+            .as_mut()?
+            .entry(relative)
+            .or_insert_with_key(|path| blame_authors(&root, path))
+            .as_ref()?;
+
+        let start = line_start.max(1) as usize - 1;
+        let end = (line_end.max(line_start) as usize).min(lines.len());
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for author in lines.get(start..end)?.iter().filter(|a| !a.is_empty()) {
+            *counts.entry(author.as_str()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(author, _)| author.to_string())
+    }
+
+    /// Fills in owners and primary authors of every affected node.
+    pub fn annotate(&mut self, analysis: &mut ImpactAnalysis) {
+        for node in analysis
+            .upstream
+            .iter_mut()
+            .chain(analysis.downstream.iter_mut())
+        {
+            let info = &node.node_info;
+            node.owners = self.owners(&info.file);
+            node.primary_author = self.primary_author(&info.file, info.line_start, info.line_end);
+        }
+    }
+
+    /// Groups nodes by `CODEOWNERS` owner and by primary author, most
+    /// affected first.
+    pub fn summarize<'a>(
+        &mut self,
+        nodes: impl IntoIterator<Item = &'a NodeInfo>,
+    ) -> (Vec<OwnerImpact>, Vec<OwnerImpact>) {
+        let mut teams: HashMap<String, (usize, BTreeSet<String>)> = HashMap::new();
+        let mut authors: HashMap<String, (usize, BTreeSet<String>)> = HashMap::new();
+        for info in nodes {
+            let file = self.relative(&info.file);
+            for owner in self.owners(&info.file) {
+                let entry = teams.entry(owner).or_default();
+                entry.0 += 1;
+                entry.1.insert(file.clone());
+            }
+            if let Some(author) = self.primary_author(&info.file, info.line_start, info.line_end) {
+                let entry = authors.entry(author).or_default();
+                entry.0 += 1;
+                entry.1.insert(file.clone());
+            }
+        }
+        (owner_impacts(teams), owner_impacts(authors))
+    }
+
+    fn relative(&self, file: &str) -> String {
+        let path = Path::new(file);
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn owner_impacts(groups: HashMap<String, (usize, BTreeSet<String>)>) -> Vec<OwnerImpact> {
+    let mut impacts: Vec<OwnerImpact> = groups
+        .into_iter()
+        .map(|(owner, (nodes, files))| OwnerImpact {
+            owner,
+            nodes,
+            files: files.into_iter().take(MAX_OWNER_FILES).collect(),
+        })
+        .collect();
+    impacts.sort_by(|a, b| b.nodes.cmp(&a.nodes).then_with(|| a.owner.cmp(&b.owner)));
+    impacts
+}
+
+/// The author of each line of a file, from `git blame --line-porcelain`.
+/// Uncommitted lines are left blank.
+fn blame_authors(root: &Path, path: &str) -> Option<Vec<String>> {
+    let output = Command::new("git")
+        .args(["blame", "--line-porcelain", "--", path])
+        .current_dir(root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(parse_line_porcelain(&text))
+}
+
+fn parse_line_porcelain(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| line.strip_prefix("author "))
+        .map(|author| {
+            if author == "Not Committed Yet" {
+                String::new()
+            } else {
+                author.to_string()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODEOWNERS: &str = r#"
+# Default owners
+*                   @org/core
+*.js                @org/frontend
+/docs/*             @org/docs
+apps/               @org/apps
+/build/logs/        @ops
+api                 @org/api # inline comment
+
+[Database][2] @org/dba
+db/
+migrations/*.sql    @org/dba @alice
+"#;
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.owners("src/main.rs"), ["@org/core"]);
+        assert_eq!(owners.owners("web/app.js"), ["@org/frontend"]);
+        assert_eq!(owners.owners("docs/intro.md"), ["@org/docs"]);
+        // `docs/*` doesn't reach nested directories.
+        assert_eq!(owners.owners("docs/guide/setup.md"), ["@org/core"]);
+        assert_eq!(owners.owners("apps/web/index.ts"), ["@org/apps"]);
+        assert_eq!(owners.owners("build/logs/today.txt"), ["@ops"]);
+        assert_eq!(owners.owners("services/api/handlers.go"), ["@org/api"]);
+    }
+
+    #[test]
+    fn test_gitlab_sections_combine() {
+        let owners = CodeOwners::parse(CODEOWNERS);
+        assert_eq!(owners.owners("db/schema.rs"), ["@org/core", "@org/dba"]);
+        assert_eq!(
+            owners.owners("migrations/001_init.sql"),
+            ["@org/core", "@org/dba", "@alice"]
+        );
+    }
+
+    #[test]
+    fn test_parse_line_porcelain() {
+        let porcelain = "\
+1f2e3d 1 1 2
+author Ada
+author-mail <ada@example.com>
+filename src/lib.rs
+\tfn main() {
+1f2e3d 2 2
+author Ada
+filename src/lib.rs
+\t    run();
+000000 3 3 1
+author Not Committed Yet
+filename src/lib.rs
+\t}
+";
+        assert_eq!(parse_line_porcelain(porcelain), ["Ada", "Ada", ""]);
+    }
+
+    #[test]
+    fn test_summarize_groups_by_owner() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".github")).unwrap();
+        std::fs::write(
+            dir.path().join(".github/CODEOWNERS"),
+            "src/ @org/core\nsrc/billing/ @org/billing\n",
+        )
+        .unwrap();
+
+        let node = |file: &str| NodeInfo {
+            id: file.to_string(),
+            name: "f".to_string(),
+            qualified_name: "f".to_string(),
+            kind: "function".to_string(),
+            file: dir.path().join(file).to_string_lossy().to_string(),
+            line_start: 1,
+            line_end: 1,
+            signature: None,
+            centrality: 0.0,
+            metrics: Default::default(),
+        };
+        let nodes = [
+            node("src/billing/invoice.rs"),
+            node("src/billing/tax.rs"),
+            node("src/main.rs"),
+            node("README.md"),
+        ];
+
+        let mut ownership = Ownership::load(dir.path(), false);
+        assert!(ownership.is_available());
+        let (teams, authors) = ownership.summarize(&nodes);
+        assert_eq!(teams.len(), 2);
+        assert_eq!(teams[0].owner, "@org/billing");
+        assert_eq!(teams[0].nodes, 2);
+        assert_eq!(teams[1].owner, "@org/core");
+        assert!(authors.is_empty());
+    }
+}
@@ -0,0 +1,651 @@
+//! Bounded path queries: k-shortest paths, path counts and chokepoints.
+//!
+//! Enumerating every simple path between two symbols explodes on dense
+//! call graphs. Instead, queries here return:
+//!   - up to `max_paths` loopless paths, shortest first (Yen's algorithm),
+//!   - an estimate of how many paths exist, from a walk count over the
+//!     subgraph that connects the endpoints, without listing them,
+//!   - chokepoints: nodes that every path goes through (dominators),
+//!   - which budget, if any, cut the answer short.
+//!
+//! Queries run over a filtered view of the graph: a direction, an optional
+//! set of edge kinds, optionally the uncertain edges, and a set of excluded
+//! nodes. A path ends at the first
+//! target it reaches, so with several targets (every source in an audit)
+//! no path runs through one target to another.
+
+use crate::edge::EdgeKind;
+use crate::graph::{ArborGraph, NodeId};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Limits and filters for a path query.
+#[derive(Debug, Clone)]
+pub struct PathOptions {
+    /// Paths to return, shortest first.
+    pub max_paths: usize,
+    /// Longest path, in hops.
+    pub max_depth: usize,
+    /// Wall-clock budget for enumerating paths.
+    pub time_budget: Duration,
+    /// `Outgoing` follows edges (caller to callee); `Incoming` walks back.
+    pub direction: Direction,
+    /// Edge kinds to follow; `None` follows all of them.
+    pub edge_kinds: Option<Vec<EdgeKind>>,
+    /// Also follow the graph's uncertain edges (callbacks, event handlers,
+    /// injection, dynamic dispatch), whatever `edge_kinds` says.
+    pub uncertain_edges: bool,
+    /// Nodes no path may pass through.
+    pub excluded: HashSet<NodeId>,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        Self {
+            max_paths: 5,
+            max_depth: 12,
+            time_budget: Duration::from_millis(500),
+            direction: Direction::Outgoing,
+            edge_kinds: None,
+            uncertain_edges: false,
+            excluded: HashSet::new(),
+        }
+    }
+}
+
+/// Why a path query returned less than the full answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathTruncation {
+    /// More paths exist than `max_paths`.
+    ResultLimit,
+    /// The time budget ran out while enumerating.
+    TimeBudget,
+    /// Some routes are longer than `max_depth` and were not followed.
+    MaxDepth,
+}
+
+impl std::fmt::Display for PathTruncation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathTruncation::ResultLimit => write!(f, "result_limit"),
+            PathTruncation::TimeBudget => write!(f, "time_budget"),
+            PathTruncation::MaxDepth => write!(f, "max_depth"),
+        }
+    }
+}
+
+/// Answer to a path query.
+#[derive(Debug, Clone, Default)]
+pub struct PathResult {
+    /// Loopless paths from the start to a target, shortest first.
+    pub paths: Vec<Vec<NodeId>>,
+    /// Number of paths within `max_depth`, saturating at `u64::MAX`.
+    pub estimated_paths: u64,
+    /// True when `estimated_paths` is an exact count. With cycles in the
+    /// way it counts walks, an upper bound on paths.
+    pub count_exact: bool,
+    /// Nodes every path passes through, nearest the start first. The
+    /// start and the targets are not listed.
+    pub chokepoints: Vec<NodeId>,
+    /// Budgets that cut the answer short; empty when complete.
+    pub truncated: Vec<PathTruncation>,
+    pub elapsed_ms: u64,
+}
+
+impl PathResult {
+    pub fn is_complete(&self) -> bool {
+        self.truncated.is_empty()
+    }
+}
+
+impl ArborGraph {
+    /// Up to `options.max_paths` shortest paths from `from` to `to`, with a
+    /// path count and the chokepoints between them.
+    pub fn find_paths(&self, from: NodeId, to: NodeId, options: &PathOptions) -> PathResult {
+        self.find_paths_to_any(from, &HashSet::from([to]), options)
+    }
+
+    /// Like [`find_paths`](Self::find_paths), ending at whichever of
+    /// `targets` each path reaches first.
+    pub fn find_paths_to_any(
+        &self,
+        from: NodeId,
+        targets: &HashSet<NodeId>,
+        options: &PathOptions,
+    ) -> PathResult {
+        let start = Instant::now();
+        let view = View::build(self, from, targets, options);
+        let mut result = PathResult::default();
+
+        if !view.connected() {
+            result.count_exact = true;
+            result.elapsed_ms = start.elapsed().as_millis() as u64;
+            return result;
+        }
+
+        let (count, exact, cut_by_depth) = view.count_paths(options.max_depth);
+        result.estimated_paths = count;
+        result.count_exact = exact;
+        result.chokepoints = view
+            .chokepoints()
+            .into_iter()
+            .map(|i| view.nodes[i])
+            .collect();
+
+        let deadline = start + options.time_budget;
+        let (paths, timed_out, more) =
+            view.k_shortest(options.max_paths, options.max_depth, deadline);
+        result.paths = paths
+            .into_iter()
+            .map(|p| p.into_iter().map(|i| view.nodes[i]).collect())
+            .collect();
+
+        if more || (exact && count > result.paths.len() as u64) {
+            result.truncated.push(PathTruncation::ResultLimit);
+        }
+        if timed_out {
+            result.truncated.push(PathTruncation::TimeBudget);
+        }
+        if cut_by_depth {
+            result.truncated.push(PathTruncation::MaxDepth);
+        }
+        result.elapsed_ms = start.elapsed().as_millis() as u64;
+        result
+    }
+}
+
+/// The part of the graph between the start and the targets, renumbered
+/// `0..n` with the start at 0. Only nodes that lie on some route from the
+/// start to a target are kept.
+struct View {
+    nodes: Vec<NodeId>,
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+    is_target: Vec<bool>,
+}
+
+impl View {
+    fn build(
+        graph: &ArborGraph,
+        from: NodeId,
+        targets: &HashSet<NodeId>,
+        options: &PathOptions,
+    ) -> Self {
+        let follows = |kind: EdgeKind| {
+            options
+                .edge_kinds
+                .as_ref()
+                .is_none_or(|kinds| kinds.contains(&kind))
+        };
+        let neighbors = |node: NodeId| -> BTreeSet<NodeId> {
+            let uncertain = if options.uncertain_edges {
+                graph.uncertain_edges_of(node, options.direction)
+            } else {
+                Vec::new()
+            };
+            graph
+                .graph
+                .edges_directed(node, options.direction)
+                .filter(|e| follows(e.weight().kind))
+                .map(|e| match options.direction {
+                    Direction::Outgoing => e.target(),
+                    Direction::Incoming => e.source(),
+                })
+                .chain(uncertain.into_iter().map(|(far, _)| far))
+                .filter(|n| *n != node && !options.excluded.contains(n))
+                .collect()
+        };
+
+        // Forward reach from the start; targets are not expanded.
+        let mut index: HashMap<NodeId, usize> = HashMap::from([(from, 0)]);
+        let mut nodes = vec![from];
+        let mut succ: Vec<Vec<usize>> = vec![Vec::new()];
+        let mut queue = VecDeque::from([0usize]);
+        while let Some(i) = queue.pop_front() {
+            if targets.contains(&nodes[i]) {
+                continue;
+            }
+            for next in neighbors(nodes[i]) {
+                let j = *index.entry(next).or_insert_with(|| {
+                    nodes.push(next);
+                    succ.push(Vec::new());
+                    queue.push_back(nodes.len() - 1);
+                    nodes.len() - 1
+                });
+                succ[i].push(j);
+            }
+        }
+
+        // Keep only nodes that can still reach a target.
+        let n = nodes.len();
+        let mut pred = vec![Vec::new(); n];
+        for (i, out) in succ.iter().enumerate() {
+            for &j in out {
+                pred[j].push(i);
+            }
+        }
+        let is_target: Vec<bool> = nodes.iter().map(|n| targets.contains(n)).collect();
+        let mut relevant = is_target.clone();
+        let mut queue: VecDeque<usize> = (0..n).filter(|&i| is_target[i]).collect();
+        while let Some(j) = queue.pop_front() {
+            for &i in &pred[j] {
+                if !relevant[i] {
+                    relevant[i] = true;
+                    queue.push_back(i);
+                }
+            }
+        }
+
+        let mut view = View {
+            nodes: Vec::new(),
+            succ: Vec::new(),
+            pred: Vec::new(),
+            is_target: Vec::new(),
+        };
+        if !relevant[0] {
+            return view;
+        }
+        let mut renumber = vec![usize::MAX; n];
+        for i in (0..n).filter(|&i| relevant[i]) {
+            renumber[i] = view.nodes.len();
+            view.nodes.push(nodes[i]);
+            view.is_target.push(is_target[i]);
+        }
+        view.succ = vec![Vec::new(); view.nodes.len()];
+        view.pred = vec![Vec::new(); view.nodes.len()];
+        for i in (0..n).filter(|&i| relevant[i]) {
+            for &j in succ[i].iter().filter(|&&j| relevant[j]) {
+                view.succ[renumber[i]].push(renumber[j]);
+                view.pred[renumber[j]].push(renumber[i]);
+            }
+        }
+        view
+    }
+
+    fn connected(&self) -> bool {
+        !self.nodes.is_empty()
+    }
+
+    /// Counts start-to-target walks of at most `max_depth` hops. Returns
+    /// the count, whether it is exact (no cycles, so walks are paths) and
+    /// whether longer routes were cut off.
+    fn count_paths(&self, max_depth: usize) -> (u64, bool, bool) {
+        let n = self.nodes.len();
+        let mut layer = vec![0u64; n];
+        layer[0] = 1;
+        let mut total = if self.is_target[0] { 1u64 } else { 0 };
+        for _ in 0..max_depth {
+            let mut next = vec![0u64; n];
+            for i in (0..n).filter(|&i| layer[i] > 0 && !self.is_target[i]) {
+                for &j in &self.succ[i] {
+                    next[j] = next[j].saturating_add(layer[i]);
+                }
+            }
+            layer = next;
+            for i in (0..n).filter(|&i| self.is_target[i]) {
+                total = total.saturating_add(layer[i]);
+            }
+            if layer.iter().all(|&c| c == 0) {
+                break;
+            }
+        }
+        let cut_by_depth = (0..n).any(|i| layer[i] > 0 && !self.is_target[i]);
+        (total, !self.has_cycle(), cut_by_depth)
+    }
+
+    fn has_cycle(&self) -> bool {
+        // Kahn's algorithm: a cycle leaves nodes with unresolved in-edges.
+        let mut indegree: Vec<usize> = self.pred.iter().map(Vec::len).collect();
+        let mut queue: VecDeque<usize> = (0..self.nodes.len())
+            .filter(|&i| indegree[i] == 0)
+            .collect();
+        let mut seen = 0;
+        while let Some(i) = queue.pop_front() {
+            seen += 1;
+            for &j in &self.succ[i] {
+                indegree[j] -= 1;
+                if indegree[j] == 0 {
+                    queue.push_back(j);
+                }
+            }
+        }
+        seen < self.nodes.len()
+    }
+
+    /// Nodes that dominate every target: with all targets joined into one
+    /// virtual exit, its dominators other than the start.
+    fn chokepoints(&self) -> Vec<usize> {
+        let n = self.nodes.len();
+        let exit = n;
+        let preds = |i: usize| -> Vec<usize> {
+            if i == exit {
+                (0..n).filter(|&t| self.is_target[t]).collect()
+            } else {
+                self.pred[i].clone()
+            }
+        };
+        let succs = |i: usize| -> Vec<usize> {
+            if i == exit {
+                return Vec::new();
+            }
+            let mut out = self.succ[i].clone();
+            if self.is_target[i] {
+                out.push(exit);
+            }
+            out
+        };
+
+        // Reverse postorder from the start.
+        let mut order = Vec::with_capacity(n + 1);
+        let mut visited = vec![false; n + 1];
+        let mut stack = vec![(0usize, 0usize)];
+        visited[0] = true;
+        while let Some((node, child)) = stack.pop() {
+            let next = succs(node);
+            if child < next.len() {
+                stack.push((node, child + 1));
+                let c = next[child];
+                if !visited[c] {
+                    visited[c] = true;
+                    stack.push((c, 0));
+                }
+            } else {
+                order.push(node);
+            }
+        }
+        order.reverse();
+        let mut rank = vec![usize::MAX; n + 1];
+        for (r, &node) in order.iter().enumerate() {
+            rank[node] = r;
+        }
+
+        // Cooper, Harvey & Kennedy, "A Simple, Fast Dominance Algorithm".
+        let mut idom = vec![usize::MAX; n + 1];
+        idom[0] = 0;
+        let intersect = |idom: &[usize], mut a: usize, mut b: usize| {
+            while a != b {
+                while rank[a] > rank[b] {
+                    a = idom[a];
+                }
+                while rank[b] > rank[a] {
+                    b = idom[b];
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &node in order.iter().skip(1) {
+                let mut new_idom = usize::MAX;
+                for p in preds(node) {
+                    if idom[p] == usize::MAX {
+                        continue;
+                    }
+                    new_idom = if new_idom == usize::MAX {
+                        p
+                    } else {
+                        intersect(&idom, p, new_idom)
+                    };
+                }
+                if new_idom != usize::MAX && idom[node] != new_idom {
+                    idom[node] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        let mut chain = Vec::new();
+        let mut node = idom[exit];
+        while node != 0 && node != usize::MAX {
+            if !self.is_target[node] {
+                chain.push(node);
+            }
+            node = idom[node];
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Yen's k-shortest loopless paths. Returns the paths, whether the
+    /// deadline hit, and whether more paths were left as candidates.
+    fn k_shortest(
+        &self,
+        k: usize,
+        max_depth: usize,
+        deadline: Instant,
+    ) -> (Vec<Vec<usize>>, bool, bool) {
+        let Some(first) = self.shortest(0, &[], &HashSet::new(), max_depth) else {
+            return (Vec::new(), false, false);
+        };
+        if k == 0 {
+            return (Vec::new(), false, true);
+        }
+        let mut found = vec![first];
+        let mut candidates: BTreeSet<(usize, Vec<usize>)> = BTreeSet::new();
+
+        while found.len() < k {
+            let last = found.last().expect("at least one path").clone();
+            for i in 0..last.len().saturating_sub(1) {
+                if Instant::now() > deadline {
+                    return (found, true, true);
+                }
+                let root = &last[..=i];
+                let blocked_edges: HashSet<(usize, usize)> = found
+                    .iter()
+                    .filter(|p| p.len() > i + 1 && p[..=i] == *root)
+                    .map(|p| (p[i], p[i + 1]))
+                    .collect();
+                let Some(spur) = self.shortest(
+                    last[i],
+                    &root[..i],
+                    &blocked_edges,
+                    max_depth.saturating_sub(i),
+                ) else {
+                    continue;
+                };
+                let mut path = root[..i].to_vec();
+                path.extend(spur);
+                if !found.contains(&path) {
+                    candidates.insert((path.len(), path));
+                }
+            }
+            match candidates.pop_first() {
+                Some((_, path)) => found.push(path),
+                None => return (found, false, false),
+            }
+        }
+        (found, false, !candidates.is_empty())
+    }
+
+    /// BFS from `start` to the nearest target within `max_depth` hops,
+    /// avoiding `blocked` nodes and edges.
+    fn shortest(
+        &self,
+        start: usize,
+        blocked: &[usize],
+        blocked_edges: &HashSet<(usize, usize)>,
+        max_depth: usize,
+    ) -> Option<Vec<usize>> {
+        let mut parent = vec![usize::MAX; self.nodes.len()];
+        let mut depth = vec![0usize; self.nodes.len()];
+        let mut seen = vec![false; self.nodes.len()];
+        for &b in blocked {
+            seen[b] = true;
+        }
+        seen[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(i) = queue.pop_front() {
+            if self.is_target[i] {
+                let mut path = vec![i];
+                let mut node = i;
+                while node != start {
+                    node = parent[node];
+                    path.push(node);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if depth[i] == max_depth {
+                continue;
+            }
+            for &j in &self.succ[i] {
+                if !seen[j] && !blocked_edges.contains(&(i, j)) {
+                    seen[j] = true;
+                    parent[j] = i;
+                    depth[j] = depth[i] + 1;
+                    queue.push_back(j);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::Edge;
+    use arbor_core::{CodeNode, NodeKind};
+
+    /// Builds a graph from `a -> b` call pairs over single-letter nodes.
+    fn graph(edges: &[(&str, &str)]) -> (ArborGraph, HashMap<String, NodeId>) {
+        let mut graph = ArborGraph::new();
+        let mut ids = HashMap::new();
+        for (a, b) in edges {
+            for name in [a, b] {
+                if !ids.contains_key(*name) {
+                    let node = CodeNode::new(*name, *name, NodeKind::Function, "lib.rs");
+                    ids.insert(name.to_string(), graph.add_node(node));
+                }
+            }
+            graph.add_edge(ids[*a], ids[*b], Edge::new(EdgeKind::Calls));
+        }
+        (graph, ids)
+    }
+
+    fn names(graph: &ArborGraph, path: &[NodeId]) -> String {
+        path.iter()
+            .map(|id| graph.get(*id).unwrap().name.as_str())
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    #[test]
+    fn test_k_shortest_paths_in_length_order() {
+        // a -> b -> d, a -> c -> d, a -> b -> c -> d, a -> d
+        let (g, ids) = graph(&[
+            ("a", "b"),
+            ("b", "d"),
+            ("a", "c"),
+            ("c", "d"),
+            ("b", "c"),
+            ("a", "d"),
+        ]);
+        let options = PathOptions {
+            max_paths: 3,
+            ..Default::default()
+        };
+        let result = g.find_paths(ids["a"], ids["d"], &options);
+
+        let found: Vec<String> = result.paths.iter().map(|p| names(&g, p)).collect();
+        assert_eq!(found, ["ad", "abd", "acd"]);
+        assert_eq!(result.estimated_paths, 4);
+        assert!(result.count_exact);
+        assert_eq!(result.truncated, [PathTruncation::ResultLimit]);
+        assert!(result.chokepoints.is_empty());
+    }
+
+    #[test]
+    fn test_chokepoints_and_direction() {
+        // a -> b -> c -> {d, e} -> f
+        let (g, ids) = graph(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("c", "e"),
+            ("d", "f"),
+            ("e", "f"),
+        ]);
+        let result = g.find_paths(ids["a"], ids["f"], &PathOptions::default());
+        assert_eq!(result.paths.len(), 2);
+        assert!(result.is_complete());
+        assert_eq!(names(&g, &result.chokepoints), "bc");
+
+        let backward = PathOptions {
+            direction: Direction::Incoming,
+            ..Default::default()
+        };
+        let result = g.find_paths(ids["f"], ids["a"], &backward);
+        assert_eq!(result.paths.len(), 2);
+        assert_eq!(names(&g, &result.chokepoints), "cb");
+    }
+
+    #[test]
+    fn test_budgets_exclusions_and_multiple_targets() {
+        // a -> b -> c -> d, with a cycle c -> b
+        let (g, ids) = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("c", "b")]);
+        let short = PathOptions {
+            max_depth: 2,
+            ..Default::default()
+        };
+        let result = g.find_paths(ids["a"], ids["d"], &short);
+        assert!(result.paths.is_empty());
+        assert_eq!(result.truncated, [PathTruncation::MaxDepth]);
+
+        let result = g.find_paths(ids["a"], ids["d"], &PathOptions::default());
+        assert_eq!(result.paths.len(), 1);
+        // walks around the cycle are counted, so this is an upper bound
+        assert!(!result.count_exact);
+        assert!(result.estimated_paths > 1);
+
+        let blocked = PathOptions {
+            excluded: HashSet::from([ids["c"]]),
+            ..Default::default()
+        };
+        assert!(g.find_paths(ids["a"], ids["d"], &blocked).paths.is_empty());
+
+        // Paths stop at the first target: nothing runs through b to d.
+        let targets = HashSet::from([ids["b"], ids["d"]]);
+        let result = g.find_paths_to_any(ids["a"], &targets, &PathOptions::default());
+        let found: Vec<String> = result.paths.iter().map(|p| names(&g, p)).collect();
+        assert_eq!(found, ["ab"]);
+    }
+
+    #[test]
+    fn test_uncertain_edges_are_opt_in() {
+        use crate::heuristics::{UncertainEdge, UncertainEdgeKind};
+
+        // a -> b, and b registers c as a callback
+        let (mut g, mut ids) = graph(&[("a", "b")]);
+        ids.insert(
+            "c".to_string(),
+            g.add_node(CodeNode::new("c", "c", NodeKind::Function, "lib.rs")),
+        );
+        let id = |name: &str| g.get(ids[name]).unwrap().id.clone();
+        let callback = UncertainEdge {
+            from: id("b"),
+            to: id("c"),
+            kind: UncertainEdgeKind::Callback,
+            confidence: 0.6,
+            reason: "b passes c".to_string(),
+        };
+        g.add_uncertain_edges([callback]);
+
+        assert!(g
+            .find_paths(ids["a"], ids["c"], &PathOptions::default())
+            .paths
+            .is_empty());
+        let uncertain = PathOptions {
+            uncertain_edges: true,
+            ..Default::default()
+        };
+        let result = g.find_paths(ids["a"], ids["c"], &uncertain);
+        let found: Vec<String> = result.paths.iter().map(|p| names(&g, p)).collect();
+        assert_eq!(found, ["abc"]);
+    }
+}
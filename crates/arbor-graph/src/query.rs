@@ -5,6 +5,7 @@
 
 use arbor_core::CodeNode;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Result of an impact analysis query.
 #[derive(Debug, Serialize, Deserialize)]
@@ -47,6 +48,9 @@ pub struct NodeInfo {
     pub line_end: u32,
     pub signature: Option<String>,
     pub centrality: f64,
+    /// Code metrics such as cyclomatic complexity; empty for non-callables.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, u32>,
 }
 
 impl From<&CodeNode> for NodeInfo {
@@ -61,6 +65,7 @@ impl From<&CodeNode> for NodeInfo {
             line_end: node.line_end,
             signature: node.signature.clone(),
             centrality: 0.0, // Will be filled in by the graph
+            metrics: node.metrics.clone(),
         }
     }
 }
@@ -35,7 +35,7 @@ impl CentralityScores {
 /// Returns true if this file path is a test/spec/fixture file.
 /// Callers from test files get de-weighted 10x so test utilities don't
 /// false-inflate their centrality scores vs. production callers.
-fn is_test_file(file: &str) -> bool {
+pub(crate) fn is_test_file(file: &str) -> bool {
     let lower = file.to_lowercase();
     lower.contains("/test")
         || lower.contains("\\test")
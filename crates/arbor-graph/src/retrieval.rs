@@ -0,0 +1,359 @@
+//! Natural-language task → context retrieval.
+//!
+//! Turns a task description like "fix the retry logic in payment webhooks"
+//! into a token-bounded set of relevant nodes:
+//!
+//! 1. Extract keywords and identifiers from the task.
+//! 2. Rank seed nodes with the lexical search index (names, docstrings, paths).
+//! 3. Expand the top seeds through the graph with `slice_context`.
+//! 4. Pack the highest-scoring nodes into the budget, counting real tokens.
+
+use crate::graph::{ArborGraph, NodeId};
+use crate::query::NodeInfo;
+use crate::slice::count_tokens;
+use crate::source::{NodeSource, SourceReader};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// Words that describe the task rather than the code.
+const STOPWORDS: &str = "\
+    a an the and or but if then else when where which who what why how to of in on at by for \
+    from with without into onto about as is are was were be been being it its this that these \
+    those there here we our us i me my you your they them their should would could can will must \
+    may might do does did done not no so too very all any some more most other such only also \
+    just than up out over under again once fix fixes fixed fixing add adds adding update updates \
+    change changes make makes implement refactor remove support handle handling bug bugs issue \
+    issues problem code logic function functions method methods class file files need needs want \
+    please properly correctly broken wrong new use using";
+
+/// Retrieval settings.
+#[derive(Debug, Clone)]
+pub struct TaskContextOptions {
+    /// Token budget for the packed result (0 = unlimited).
+    pub max_tokens: usize,
+    /// How many top search hits seed the graph expansion.
+    pub max_seeds: usize,
+    /// Hops to expand from each seed.
+    pub depth: usize,
+    /// Include each node's source text (read via its byte range; omitted
+    /// if the file changed since indexing).
+    pub include_source: bool,
+}
+
+impl Default for TaskContextOptions {
+    fn default() -> Self {
+        Self {
+            max_tokens: 8000,
+            max_seeds: 5,
+            depth: 1,
+            include_source: false,
+        }
+    }
+}
+
+/// A node selected for a task.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskContextNode {
+    #[serde(flatten)]
+    pub node_info: NodeInfo,
+    /// Relevance: the seed's search score, decayed per hop.
+    pub score: f64,
+    /// Hops from the nearest seed (0 = seed).
+    pub depth: usize,
+    /// Tokens this node costs in the packed context.
+    pub tokens: usize,
+    /// Source text, when requested and readable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// Result of task retrieval.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskContext {
+    /// Keywords extracted from the task.
+    pub keywords: Vec<String>,
+    /// Selected nodes, most relevant first.
+    pub nodes: Vec<TaskContextNode>,
+    /// Tokens used by the selected nodes.
+    pub total_tokens: usize,
+    /// The budget.
+    pub max_tokens: usize,
+    /// True if relevant nodes were left out to fit the budget.
+    pub truncated: bool,
+    /// Time taken in milliseconds.
+    pub query_time_ms: u64,
+}
+
+/// Score multiplier per hop away from a seed.
+const HOP_DECAY: f64 = 0.5;
+
+/// Extracts search keywords from a task description.
+///
+/// Identifiers (`retryPolicy`, `charge_card`, `Billing::refund`, anything in
+/// backticks or followed by `()`) are kept whole and come first; plain words
+/// are lowercased, stopwords dropped and simple plurals trimmed.
+pub fn extract_keywords(task: &str) -> Vec<String> {
+    let mut identifiers = Vec::new();
+    let mut words = Vec::new();
+
+    // `quoted` spans are identifiers verbatim.
+    let mut rest = task;
+    let mut prose = String::new();
+    while let Some(open) = rest.find('`') {
+        prose.push_str(&rest[..open]);
+        match rest[open + 1..].find('`') {
+            Some(close) => {
+                let quoted = rest[open + 1..open + 1 + close].trim();
+                let quoted = quoted.trim_end_matches("()");
+                if !quoted.is_empty() {
+                    identifiers.push(quoted.to_string());
+                }
+                rest = &rest[open + close + 2..];
+            }
+            None => {
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+    prose.push_str(rest);
+
+    for raw in prose.split_whitespace() {
+        let is_call = raw.contains("()");
+        let word = raw.trim_matches(|c: char| !(c.is_alphanumeric() || c == '_'));
+        if word.is_empty() {
+            continue;
+        }
+        if is_call || is_identifier(word) {
+            identifiers.push(word.to_string());
+            continue;
+        }
+        for part in word.split(|c: char| !c.is_alphanumeric()) {
+            let lower = part.to_lowercase();
+            if lower.len() < 2 || STOPWORDS.split_whitespace().any(|w| w == lower) {
+                continue;
+            }
+            words.push(singular(&lower));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    identifiers
+        .into_iter()
+        .chain(words)
+        .filter(|k| seen.insert(k.to_lowercase()))
+        .collect()
+}
+
+/// True for words that look like code rather than prose.
+fn is_identifier(word: &str) -> bool {
+    let has_inner_upper = word
+        .char_indices()
+        .any(|(i, c)| i > 0 && c.is_uppercase() && word[..i].chars().any(char::is_lowercase));
+    word.contains('_') || word.contains("::") || word.contains('.') || has_inner_upper
+}
+
+/// Trims simple English plurals: `webhooks` → `webhook`, `retries` → `retry`.
+fn singular(word: &str) -> String {
+    if word.len() > 4 && word.ends_with("ies") {
+        format!("{}y", &word[..word.len() - 3])
+    } else if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// The text a node contributes to the packed context.
+fn render(info: &NodeInfo, source: Option<&str>) -> String {
+    let header = format!(
+        "{} {} ({}:{})",
+        info.kind, info.qualified_name, info.file, info.line_start
+    );
+    match (source, &info.signature) {
+        (Some(source), _) => format!("{}\n{}", header, source),
+        (None, Some(signature)) => format!("{}\n{}", header, signature),
+        (None, None) => header,
+    }
+}
+
+impl ArborGraph {
+    /// Finds and packs the nodes most relevant to a natural-language task.
+    pub fn retrieve_for_task(&self, task: &str, options: &TaskContextOptions) -> TaskContext {
+        let start = Instant::now();
+        let keywords = extract_keywords(task);
+        let budget = if options.max_tokens == 0 {
+            usize::MAX
+        } else {
+            options.max_tokens
+        };
+
+        // Rank seeds; the search ORs terms and rewards nodes matching more of them.
+        let seeds: Vec<(NodeId, f64)> = if keywords.is_empty() {
+            Vec::new()
+        } else {
+            self.search_scored(&keywords.join(" "))
+                .into_iter()
+                .filter_map(|(node, score)| Some((self.get_index(&node.id)?, score)))
+                .take(options.max_seeds)
+                .collect()
+        };
+
+        // Expand each seed; a node reached from several seeds keeps its best score.
+        let mut candidates: HashMap<NodeId, (f64, usize)> = HashMap::new();
+        for &(seed, seed_score) in &seeds {
+            let slice = self.slice_context(seed, 0, options.depth.max(1), &[]);
+            for node in slice.nodes {
+                if node.depth > options.depth {
+                    continue;
+                }
+                let Some(index) = self.get_index(&node.node_info.id) else {
+                    continue;
+                };
+                let score = seed_score * HOP_DECAY.powi(node.depth as i32);
+                let entry = candidates.entry(index).or_insert((score, node.depth));
+                if score > entry.0 {
+                    *entry = (score, node.depth);
+                }
+            }
+        }
+
+        let mut ranked: Vec<(NodeId, f64, usize)> = candidates
+            .into_iter()
+            .map(|(index, (score, depth))| (index, score, depth))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        // Greedy packing: skip anything that doesn't fit and keep trying smaller nodes.
+        let mut reader = SourceReader::new();
+        let mut nodes = Vec::new();
+        let mut total_tokens = 0usize;
+        let mut truncated = false;
+        for (index, score, depth) in ranked {
+            let Some(node) = self.get(index) else {
+                continue;
+            };
+            let mut info = NodeInfo::from(node);
+            info.centrality = self.centrality(index);
+
+            let source = match options.include_source.then(|| reader.read(node)) {
+                Some(NodeSource::Fresh(text)) => Some(text),
+                _ => None,
+            };
+            let tokens = count_tokens(&render(&info, source.as_deref()));
+            if total_tokens.saturating_add(tokens) > budget {
+                truncated = true;
+                continue;
+            }
+            total_tokens += tokens;
+            nodes.push(TaskContextNode {
+                node_info: info,
+                score,
+                depth,
+                tokens,
+                source,
+            });
+        }
+
+        TaskContext {
+            keywords,
+            nodes,
+            total_tokens,
+            max_tokens: options.max_tokens,
+            truncated,
+            query_time_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{Edge, EdgeKind};
+    use arbor_core::{CodeNode, NodeKind};
+
+    #[test]
+    fn test_extract_keywords() {
+        assert_eq!(
+            extract_keywords("Fix the retry logic in payment webhooks"),
+            vec!["retry", "payment", "webhook"]
+        );
+        assert_eq!(
+            extract_keywords("why does `charge_card` call retryPolicy.next() twice?"),
+            vec!["charge_card", "retryPolicy.next", "call", "twice"]
+        );
+    }
+
+    #[test]
+    fn test_retrieval_finds_seeds_and_neighbors() {
+        let mut graph = ArborGraph::new();
+        let handler = graph.add_node(CodeNode::new(
+            "handle_webhook",
+            "payments.handle_webhook",
+            NodeKind::Function,
+            "src/payments/webhooks.py",
+        ));
+        let retry = graph.add_node(CodeNode::new(
+            "with_retry",
+            "payments.with_retry",
+            NodeKind::Function,
+            "src/payments/retry.py",
+        ));
+        let helper = graph.add_node(CodeNode::new(
+            "backoff",
+            "util.backoff",
+            NodeKind::Function,
+            "src/util.py",
+        ));
+        graph.add_node(CodeNode::new(
+            "render_page",
+            "ui.render_page",
+            NodeKind::Function,
+            "src/ui.py",
+        ));
+        graph.add_edge(handler, retry, Edge::new(EdgeKind::Calls));
+        graph.add_edge(retry, helper, Edge::new(EdgeKind::Calls));
+
+        let context = graph.retrieve_for_task(
+            "fix the retry logic in payment webhooks",
+            &TaskContextOptions::default(),
+        );
+        let names: Vec<&str> = context
+            .nodes
+            .iter()
+            .map(|n| n.node_info.name.as_str())
+            .collect();
+        assert!(names.contains(&"handle_webhook"));
+        assert!(names.contains(&"with_retry"));
+        assert!(names.contains(&"backoff"), "one hop from a seed");
+        assert!(!names.contains(&"render_page"));
+        assert_eq!(context.nodes[0].depth, 0);
+    }
+
+    #[test]
+    fn test_retrieval_respects_budget() {
+        let mut graph = ArborGraph::new();
+        for i in 0..20 {
+            graph.add_node(CodeNode::new(
+                format!("payment_step_{i}"),
+                format!("payments.payment_step_{i}"),
+                NodeKind::Function,
+                "payments.rs",
+            ));
+        }
+        let options = TaskContextOptions {
+            max_tokens: 40,
+            max_seeds: 20,
+            ..TaskContextOptions::default()
+        };
+        let context = graph.retrieve_for_task("payment step", &options);
+        assert!(context.total_tokens <= 40);
+        assert!(context.truncated);
+        assert!(!context.nodes.is_empty());
+    }
+}
@@ -1,10 +1,32 @@
-//! Search index for fast substring matching.
+//! Ranked symbol search.
 //!
-//! This module provides an inverted index that enables O(k) substring
-//! search where k is the number of matches, instead of O(n) linear scan.
+//! Every node is indexed as a small document with five fields — name,
+//! qualified name, signature, docstring and file path — each split into
+//! camelCase/snake_case subwords. Queries are scored with BM25 over those
+//! fields, so `user validate` finds `validateUser` and `UserValidator`
+//! and ranks the one whose *name* matches above one that merely mentions
+//! the words in its docstring.
+//!
+//! Query terms match index terms exactly, by prefix (search-as-you-type),
+//! within a small edit distance (typos), or as a substring of the name.
+//! Weaker matches contribute less. Scores are then boosted by node kind,
+//! an exact name match and the caller-supplied centrality.
+//!
+//! Queries can also carry filters:
+//!
+//! ```text
+//! invoice kind:method,function file:src/billing/** async:true exported:true
+//! ```
+//!
+//! `via:global_suffix,heuristic` keeps nodes with an outgoing edge resolved
+//! that way; the index has no edges, so [`ArborGraph::search`] applies it.
+//!
+//! [`ArborGraph::search`]: crate::ArborGraph::search
 
+use crate::edge::Provenance;
 use crate::graph::NodeId;
-use std::collections::{HashMap, HashSet};
+use arbor_core::{CodeNode, NodeKind};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Minimum n-gram length for indexing.
 const MIN_NGRAM_LEN: usize = 2;
@@ -12,17 +34,174 @@ const MIN_NGRAM_LEN: usize = 2;
 /// Maximum n-gram length for indexing.
 const MAX_NGRAM_LEN: usize = 4;
 
-/// An inverted index for fast substring search.
-///
-/// Uses n-gram indexing to support substring matching. When a name is added,
-/// we break it into overlapping n-grams and index each one. During search,
-/// we look up the query's n-grams and intersect the results.
+/// Indexed fields, in posting order.
+const FIELD_COUNT: usize = 5;
+const NAME: usize = 0;
+const QUALIFIED_NAME: usize = 1;
+const SIGNATURE: usize = 2;
+const DOCSTRING: usize = 3;
+const FILE: usize = 4;
+
+/// How much a hit in each field is worth relative to the others.
+const FIELD_WEIGHTS: [f64; FIELD_COUNT] = [3.0, 1.5, 1.0, 0.6, 0.4];
+
+/// BM25 term-frequency saturation and length normalisation.
+const K1: f64 = 1.2;
+const B: f64 = 0.75;
+
+/// Docstrings are long; only the opening words are worth indexing.
+const MAX_DOCSTRING_TERMS: usize = 64;
+
+/// Path components (from the end) indexed for the file field.
+const FILE_COMPONENTS: usize = 3;
+
+/// Cap on index terms a single short prefix may expand to.
+const MAX_PREFIX_EXPANSION: usize = 256;
+
+/// Match quality multipliers for the ways a query term can hit.
+const EXACT_MATCH: f64 = 1.0;
+const FUZZY_MATCH_1: f64 = 0.45;
+const FUZZY_MATCH_2: f64 = 0.3;
+const INFIX_MATCH: f64 = 0.35;
+
+/// A parsed search query: free-text terms plus optional filters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    /// Lowercased subword terms from the free text.
+    pub terms: Vec<String>,
+    /// The free text with everything but letters and digits removed,
+    /// for exact-name matching (`validateUser` == `validate_user`).
+    pub compact: String,
+    /// `kind:` — node kinds to keep (any of).
+    pub kinds: Vec<NodeKind>,
+    /// `file:` — path globs to keep (any of). A pattern without `*`/`?`
+    /// matches as a substring.
+    pub files: Vec<String>,
+    /// `async:true|false`.
+    pub is_async: Option<bool>,
+    /// `exported:true|false`.
+    pub is_exported: Option<bool>,
+    /// `static:true|false`.
+    pub is_static: Option<bool>,
+    /// `via:` — provenances of outgoing edges to keep (any of).
+    pub via: Vec<Provenance>,
+}
+
+impl SearchQuery {
+    /// Parses a query string. Words of the form `key:value` with a known
+    /// key become filters; everything else is free text.
+    pub fn parse(query: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut text = Vec::new();
+
+        for word in query.split_whitespace() {
+            if let Some((key, value)) = word.split_once(':') {
+                if parsed.apply_filter(&key.to_lowercase(), value) {
+                    continue;
+                }
+            }
+            text.push(word);
+        }
+
+        for word in &text {
+            parsed.terms.extend(tokenize(word));
+            parsed.compact.push_str(&compact(word));
+        }
+        parsed
+    }
+
+    /// True if the query has neither free text nor filters.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && !self.has_filters()
+    }
+
+    /// True if any filter is set.
+    pub fn has_filters(&self) -> bool {
+        !self.kinds.is_empty()
+            || !self.files.is_empty()
+            || self.is_async.is_some()
+            || self.is_exported.is_some()
+            || self.is_static.is_some()
+            || !self.via.is_empty()
+    }
+
+    fn apply_filter(&mut self, key: &str, value: &str) -> bool {
+        match key {
+            "kind" => {
+                let kinds: Vec<NodeKind> = value.split(',').filter_map(parse_kind).collect();
+                if kinds.is_empty() {
+                    return false;
+                }
+                self.kinds.extend(kinds);
+            }
+            "file" | "path" if !value.is_empty() => {
+                self.files.push(value.replace('\\', "/"));
+            }
+            "async" => match parse_bool(value) {
+                Some(b) => self.is_async = Some(b),
+                None => return false,
+            },
+            "exported" | "pub" => match parse_bool(value) {
+                Some(b) => self.is_exported = Some(b),
+                None => return false,
+            },
+            "static" => match parse_bool(value) {
+                Some(b) => self.is_static = Some(b),
+                None => return false,
+            },
+            "via" => {
+                let via: Vec<Provenance> =
+                    value.split(',').filter_map(|v| v.parse().ok()).collect();
+                if via.is_empty() {
+                    return false;
+                }
+                self.via.extend(via);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// A ranked search result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchHit {
+    pub id: NodeId,
+    pub score: f64,
+}
+
+/// What the index remembers about each node.
+#[derive(Debug, Clone)]
+struct Document {
+    /// Lowercased name, for substring matches.
+    name: String,
+    /// Name with only letters and digits, lowercased.
+    compact_name: String,
+    kind: NodeKind,
+    /// File path with forward slashes.
+    file: String,
+    is_async: bool,
+    is_exported: bool,
+    is_static: bool,
+    /// Term count per field.
+    lengths: [u16; FIELD_COUNT],
+    /// Distinct terms, for removal.
+    terms: Vec<String>,
+}
+
+/// Ranked, fuzzy, multi-field search over code nodes.
 #[derive(Debug, Default, Clone)]
 pub struct SearchIndex {
-    /// Maps lowercased full names to NodeIds for exact match lookup.
-    exact_index: HashMap<String, Vec<NodeId>>,
-    /// Maps lowercased n-grams to NodeIds for substring search.
+    /// Indexed nodes.
+    docs: HashMap<NodeId, Document>,
+    /// Term -> node -> occurrences per field. Ordered for prefix scans.
+    postings: BTreeMap<String, HashMap<NodeId, [u16; FIELD_COUNT]>>,
+    /// Sum of field lengths across all docs, for average-length normalisation.
+    field_length_sums: [u64; FIELD_COUNT],
+    /// Maps lowercased name n-grams to NodeIds for substring search.
     ngram_index: HashMap<String, HashSet<NodeId>>,
+    /// Number of nodes per distinct lowercased name.
+    name_counts: HashMap<String, usize>,
 }
 
 impl SearchIndex {
@@ -31,33 +210,87 @@ impl SearchIndex {
         Self::default()
     }
 
-    /// Inserts a name into the index.
-    pub fn insert(&mut self, name: &str, id: NodeId) {
-        let lower = name.to_lowercase();
+    /// Indexes a node. Re-inserting an id replaces its previous entry.
+    pub fn insert(&mut self, node: &CodeNode, id: NodeId) {
+        if self.docs.contains_key(&id) {
+            self.remove(id);
+        }
 
-        // Add to exact index
-        self.exact_index.entry(lower.clone()).or_default().push(id);
+        let mut fields: [Vec<String>; FIELD_COUNT] = Default::default();
+        fields[NAME] = tokenize(&node.name);
+        let compact_name = compact(&node.name);
+        if !compact_name.is_empty() && fields[NAME] != [compact_name.clone()] {
+            fields[NAME].push(compact_name.clone());
+        }
+        fields[QUALIFIED_NAME] = tokenize(&node.qualified_name);
+        if let Some(signature) = &node.signature {
+            fields[SIGNATURE] = tokenize(signature);
+        }
+        if let Some(docstring) = &node.docstring {
+            fields[DOCSTRING] = tokenize(docstring);
+            fields[DOCSTRING].truncate(MAX_DOCSTRING_TERMS);
+        }
+        let file = node.file.replace('\\', "/");
+        let tail: Vec<&str> = file.rsplit('/').take(FILE_COMPONENTS).collect();
+        fields[FILE] = tail.iter().rev().flat_map(|c| tokenize(c)).collect();
+
+        let mut lengths = [0u16; FIELD_COUNT];
+        let mut terms = HashSet::new();
+        for (field, tokens) in fields.iter().enumerate() {
+            lengths[field] = tokens.len().min(u16::MAX as usize) as u16;
+            self.field_length_sums[field] += lengths[field] as u64;
+            for token in tokens {
+                let counts = self
+                    .postings
+                    .entry(token.clone())
+                    .or_default()
+                    .entry(id)
+                    .or_default();
+                counts[field] = counts[field].saturating_add(1);
+                terms.insert(token.clone());
+            }
+        }
 
-        // Add to n-gram index
-        for ngram in self.generate_ngrams(&lower) {
+        let name = node.name.to_lowercase();
+        for ngram in generate_ngrams(&name) {
             self.ngram_index.entry(ngram).or_default().insert(id);
         }
+        *self.name_counts.entry(name.clone()).or_default() += 1;
+
+        self.docs.insert(
+            id,
+            Document {
+                name,
+                compact_name,
+                kind: node.kind,
+                file,
+                is_async: node.is_async,
+                is_exported: node.is_exported,
+                is_static: node.is_static,
+                lengths,
+                terms: terms.into_iter().collect(),
+            },
+        );
     }
 
-    /// Removes a name from the index.
-    pub fn remove(&mut self, name: &str, id: NodeId) {
-        let lower = name.to_lowercase();
+    /// Removes a node from the index.
+    pub fn remove(&mut self, id: NodeId) {
+        let Some(doc) = self.docs.remove(&id) else {
+            return;
+        };
 
-        // Remove from exact index
-        if let Some(ids) = self.exact_index.get_mut(&lower) {
-            ids.retain(|&x| x != id);
-            if ids.is_empty() {
-                self.exact_index.remove(&lower);
+        for (field, length) in doc.lengths.iter().enumerate() {
+            self.field_length_sums[field] -= *length as u64;
+        }
+        for term in &doc.terms {
+            if let Some(ids) = self.postings.get_mut(term) {
+                ids.remove(&id);
+                if ids.is_empty() {
+                    self.postings.remove(term);
+                }
             }
         }
-
-        // Remove from n-gram index
-        for ngram in self.generate_ngrams(&lower) {
+        for ngram in generate_ngrams(&doc.name) {
             if let Some(ids) = self.ngram_index.get_mut(&ngram) {
                 ids.remove(&id);
                 if ids.is_empty() {
@@ -65,95 +298,409 @@ impl SearchIndex {
                 }
             }
         }
+        if let Some(count) = self.name_counts.get_mut(&doc.name) {
+            *count -= 1;
+            if *count == 0 {
+                self.name_counts.remove(&doc.name);
+            }
+        }
     }
 
-    /// Searches for nodes whose names contain the query substring.
+    /// Searches for nodes matching the query, best first.
     ///
-    /// Returns matching NodeIds sorted for deterministic output.
+    /// Convenience wrapper around [`SearchIndex::rank`] without centrality.
     pub fn search(&self, query: &str) -> Vec<NodeId> {
+        self.rank(&SearchQuery::parse(query), |_| 0.0)
+            .into_iter()
+            .map(|hit| hit.id)
+            .collect()
+    }
+
+    /// Scores every node matching `query`, best first.
+    ///
+    /// `centrality` should return a node's normalised (0-1) centrality;
+    /// central nodes get up to a 50% boost. Ties break on `NodeId` so the
+    /// order is deterministic.
+    pub fn rank(&self, query: &SearchQuery, centrality: impl Fn(NodeId) -> f64) -> Vec<SearchHit> {
         if query.is_empty() {
             return Vec::new();
         }
 
-        let query_lower = query.to_lowercase();
-
-        // For very short queries, fall back to prefix matching on exact index
-        if query_lower.len() < MIN_NGRAM_LEN {
-            let mut results: Vec<NodeId> = self
-                .exact_index
-                .iter()
-                .filter(|(name, _)| name.starts_with(&query_lower))
-                .flat_map(|(_, ids)| ids.iter().copied())
-                .collect();
-            results.sort();
-            results.dedup();
-            return results;
+        let mut scores: HashMap<NodeId, f64> = HashMap::new();
+        if query.terms.is_empty() {
+            // Filter-only query: every node is a candidate.
+            for &id in self.docs.keys() {
+                scores.insert(id, 1.0);
+            }
+        } else {
+            let mut matched_terms: HashMap<NodeId, usize> = HashMap::new();
+            for term in &query.terms {
+                for (id, score) in self.score_term(term) {
+                    *scores.entry(id).or_default() += score;
+                    *matched_terms.entry(id).or_default() += 1;
+                }
+            }
+            // Coordination: nodes matching more of the query rank higher.
+            let total = query.terms.len() as f64;
+            for (id, score) in scores.iter_mut() {
+                let matched = matched_terms[id] as f64;
+                *score *= (matched / total).powi(2);
+            }
         }
 
-        // Generate n-grams for the query
-        let query_ngrams: Vec<String> = self.generate_ngrams(&query_lower);
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let doc = self.docs.get(&id)?;
+                if !matches_filters(doc, query) {
+                    return None;
+                }
+                let mut score = score * kind_boost(doc.kind);
+                if !query.compact.is_empty() {
+                    if doc.compact_name == query.compact {
+                        score *= 3.0;
+                    } else if doc.compact_name.starts_with(&query.compact) {
+                        score *= 1.5;
+                    }
+                }
+                score *= 1.0 + 0.5 * centrality(id).clamp(0.0, 1.0);
+                Some(SearchHit { id, score })
+            })
+            .collect();
 
-        if query_ngrams.is_empty() {
-            return Vec::new();
-        }
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        hits
+    }
 
-        // Find candidate nodes by intersecting n-gram matches
-        let mut candidates: Option<HashSet<NodeId>> = None;
+    /// Best BM25 contribution of one query term to each node it reaches.
+    fn score_term(&self, term: &str) -> HashMap<NodeId, f64> {
+        let mut best: HashMap<NodeId, f64> = HashMap::new();
+        let mut record = |id: NodeId, score: f64| {
+            let entry = best.entry(id).or_insert(0.0);
+            if score > *entry {
+                *entry = score;
+            }
+        };
+
+        for (index_term, quality) in self.expand_term(term) {
+            let Some(ids) = self.postings.get(&index_term) else {
+                continue;
+            };
+            let idf = self.idf(ids.len());
+            for (&id, counts) in ids {
+                if let Some(doc) = self.docs.get(&id) {
+                    record(id, quality * idf * self.bm25(doc, counts));
+                }
+            }
+        }
 
-        for ngram in &query_ngrams {
-            if let Some(ids) = self.ngram_index.get(ngram) {
-                match &mut candidates {
-                    None => candidates = Some(ids.clone()),
-                    Some(c) => {
-                        c.retain(|id| ids.contains(id));
-                    }
+        // Substring of the name, e.g. `base` in `database`.
+        if term.chars().count() >= 3 {
+            let infix = self.name_substring_matches(term);
+            let idf = self.idf(infix.len());
+            for id in infix {
+                if let Some(doc) = self.docs.get(&id) {
+                    let mut counts = [0u16; FIELD_COUNT];
+                    counts[NAME] = 1;
+                    record(id, INFIX_MATCH * idf * self.bm25(doc, &counts));
                 }
+            }
+        }
+
+        best
+    }
+
+    /// Index terms a query term can stand for, with a match quality.
+    fn expand_term(&self, term: &str) -> Vec<(String, f64)> {
+        let mut expanded = Vec::new();
+        let term_len = term.chars().count();
+
+        // Exact and prefix matches come from one ordered range scan.
+        for index_term in self
+            .postings
+            .range(term.to_string()..)
+            .map(|(t, _)| t)
+            .take_while(|t| t.starts_with(term))
+            .take(MAX_PREFIX_EXPANSION)
+        {
+            let quality = if index_term == term {
+                EXACT_MATCH
             } else {
-                // If any n-gram has no matches, the query has no results
+                // Closer to a full word is a better prefix match.
+                0.5 + 0.4 * term_len as f64 / index_term.chars().count() as f64
+            };
+            expanded.push((index_term.clone(), quality));
+        }
+
+        let max_distance = match term_len {
+            0..=3 => 0,
+            4..=7 => 1,
+            _ => 2,
+        };
+        if max_distance > 0 {
+            for index_term in self.postings.keys() {
+                let len = index_term.chars().count();
+                if len.abs_diff(term_len) > max_distance || index_term.starts_with(term) {
+                    continue;
+                }
+                match edit_distance(term, index_term, max_distance) {
+                    Some(1) => expanded.push((index_term.clone(), FUZZY_MATCH_1)),
+                    Some(2) => expanded.push((index_term.clone(), FUZZY_MATCH_2)),
+                    _ => {}
+                }
+            }
+        }
+
+        expanded
+    }
+
+    /// Nodes whose lowercased name contains `term`.
+    fn name_substring_matches(&self, term: &str) -> Vec<NodeId> {
+        let mut candidates: Option<HashSet<NodeId>> = None;
+        for ngram in generate_ngrams(term) {
+            let Some(ids) = self.ngram_index.get(&ngram) else {
                 return Vec::new();
+            };
+            match &mut candidates {
+                None => candidates = Some(ids.clone()),
+                Some(c) => c.retain(|id| ids.contains(id)),
             }
         }
 
-        // Filter candidates to ensure full substring match
-        // (n-gram intersection can have false positives)
-        let mut results: Vec<NodeId> = candidates
+        // N-gram intersection can have false positives.
+        candidates
             .unwrap_or_default()
             .into_iter()
-            .filter(|id| {
-                self.exact_index
-                    .iter()
-                    .any(|(name, ids)| ids.contains(id) && name.contains(&query_lower))
-            })
-            .collect();
-
-        results.sort();
-        results
+            .filter(|id| self.docs.get(id).is_some_and(|d| d.name.contains(term)))
+            .collect()
     }
 
-    /// Generates n-grams for a lowercased string.
-    fn generate_ngrams(&self, s: &str) -> Vec<String> {
-        let chars: Vec<char> = s.chars().collect();
-        let mut ngrams = Vec::new();
+    fn idf(&self, doc_freq: usize) -> f64 {
+        let n = self.docs.len() as f64;
+        let df = doc_freq as f64;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
 
-        for n in MIN_NGRAM_LEN..=MAX_NGRAM_LEN {
-            if chars.len() >= n {
-                for i in 0..=(chars.len() - n) {
-                    ngrams.push(chars[i..i + n].iter().collect());
-                }
+    /// Field-weighted BM25 term-frequency component for one document.
+    fn bm25(&self, doc: &Document, counts: &[u16; FIELD_COUNT]) -> f64 {
+        let n = self.docs.len().max(1) as f64;
+        let mut score = 0.0;
+        for field in 0..FIELD_COUNT {
+            let tf = counts[field] as f64;
+            if tf == 0.0 {
+                continue;
             }
+            let avg_len = (self.field_length_sums[field] as f64 / n).max(1.0);
+            let norm = 1.0 - B + B * doc.lengths[field] as f64 / avg_len;
+            score += FIELD_WEIGHTS[field] * tf * (K1 + 1.0) / (tf + K1 * norm);
         }
-
-        ngrams
+        score
     }
 
     /// Returns the number of unique names indexed.
     pub fn len(&self) -> usize {
-        self.exact_index.len()
+        self.name_counts.len()
     }
 
     /// Returns true if the index is empty.
     pub fn is_empty(&self) -> bool {
-        self.exact_index.is_empty()
+        self.docs.is_empty()
+    }
+}
+
+/// Splits text into lowercased camelCase/snake_case subwords.
+///
+/// `parseHTTPRequest_v2` -> `parse`, `http`, `request`, `v2`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let chars: Vec<char> = word.chars().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (prev, cur) = (chars[i - 1], chars[i]);
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            let boundary = (cur.is_uppercase() && (prev.is_lowercase() || prev.is_numeric()))
+                || (cur.is_uppercase() && prev.is_uppercase() && next_is_lower);
+            if boundary {
+                tokens.push(chars[start..i].iter().collect::<String>().to_lowercase());
+                start = i;
+            }
+        }
+        if start < chars.len() {
+            tokens.push(chars[start..].iter().collect::<String>().to_lowercase());
+        }
+    }
+    tokens
+}
+
+/// Lowercased letters and digits only.
+fn compact(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Generates n-grams for a lowercased string.
+fn generate_ngrams(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut ngrams = Vec::new();
+
+    for n in MIN_NGRAM_LEN..=MAX_NGRAM_LEN {
+        if chars.len() >= n {
+            for i in 0..=(chars.len() - n) {
+                ngrams.push(chars[i..i + n].iter().collect());
+            }
+        }
+    }
+
+    ngrams
+}
+
+/// Optimal-string-alignment distance (edits plus adjacent swaps), or
+/// `None` once it is certain to exceed `max`.
+fn edit_distance(a: &str, b: &str, max: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev_prev: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        cur[0] = i;
+        let mut row_min = cur[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev_prev[j - 2] + 1);
+            }
+            row_min = row_min.min(cur[j]);
+        }
+        if row_min > max {
+            return None;
+        }
+        std::mem::swap(&mut prev_prev, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    let distance = prev[b.len()];
+    (distance <= max).then_some(distance)
+}
+
+fn matches_filters(doc: &Document, query: &SearchQuery) -> bool {
+    (query.kinds.is_empty() || query.kinds.contains(&doc.kind))
+        && (query.files.is_empty() || query.files.iter().any(|p| path_matches(p, &doc.file)))
+        && query.is_async.is_none_or(|b| doc.is_async == b)
+        && query.is_exported.is_none_or(|b| doc.is_exported == b)
+        && query.is_static.is_none_or(|b| doc.is_static == b)
+}
+
+/// Definitions people search for outrank the statements that mention them.
+fn kind_boost(kind: NodeKind) -> f64 {
+    match kind {
+        NodeKind::Function
+        | NodeKind::Method
+        | NodeKind::Constructor
+        | NodeKind::Class
+        | NodeKind::Interface
+        | NodeKind::Struct
+        | NodeKind::Enum
+        | NodeKind::TypeAlias
+        | NodeKind::Route => 1.0,
+        NodeKind::Topic => 0.9,
+        NodeKind::Module | NodeKind::Constant | NodeKind::Section => 0.8,
+        NodeKind::Variable | NodeKind::Field => 0.7,
+        NodeKind::Export => 0.5,
+        NodeKind::Import => 0.3,
+    }
+}
+
+fn parse_kind(value: &str) -> Option<NodeKind> {
+    let kind = match value.to_lowercase().as_str() {
+        "function" | "fn" | "func" => NodeKind::Function,
+        "method" => NodeKind::Method,
+        "class" => NodeKind::Class,
+        "interface" | "trait" | "protocol" => NodeKind::Interface,
+        "struct" => NodeKind::Struct,
+        "enum" => NodeKind::Enum,
+        "variable" | "var" => NodeKind::Variable,
+        "constant" | "const" => NodeKind::Constant,
+        "type_alias" | "type" => NodeKind::TypeAlias,
+        "module" | "mod" => NodeKind::Module,
+        "import" => NodeKind::Import,
+        "export" => NodeKind::Export,
+        "constructor" => NodeKind::Constructor,
+        "field" | "property" => NodeKind::Field,
+        "section" => NodeKind::Section,
+        "route" | "endpoint" => NodeKind::Route,
+        "topic" | "event" => NodeKind::Topic,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Matches a `file:` filter against a path.
+///
+/// Patterns without wildcards match as substrings. Relative glob patterns
+/// may match starting at any directory, so `src/billing/**` matches
+/// `/home/me/app/src/billing/invoice.rs`.
+pub(crate) fn path_matches(pattern: &str, path: &str) -> bool {
+    if !pattern.contains(['*', '?']) {
+        return path.contains(pattern);
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    if pattern.first() == Some(&'/') {
+        return glob_match(&pattern, &path);
+    }
+    (0..path.len())
+        .filter(|&i| i == 0 || path[i - 1] == '/')
+        .any(|i| glob_match(&pattern, &path[i..]))
+}
+
+/// `*` and `?` stay within a path component; `**` crosses them.
+pub(crate) fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // `**/` also matches zero directories.
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], path) {
+                return true;
+            }
+            (0..=path.len()).any(|i| glob_match(rest, &path[i..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=path.len() {
+                if glob_match(rest, &path[i..]) {
+                    return true;
+                }
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            path.first().is_some_and(|&c| c != '/') && glob_match(&pattern[1..], &path[1..])
+        }
+        Some(&c) => path.first() == Some(&c) && glob_match(&pattern[1..], &path[1..]),
     }
 }
 
@@ -166,23 +713,37 @@ mod tests {
         NodeIndex::new(n as usize)
     }
 
+    fn node(name: &str) -> CodeNode {
+        CodeNode::new(name, name, NodeKind::Function, "test.rs")
+    }
+
+    fn index_of(nodes: &[CodeNode]) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        for (i, node) in nodes.iter().enumerate() {
+            index.insert(node, node_id(i as u32));
+        }
+        index
+    }
+
     #[test]
     fn test_insert_and_search_exact() {
-        let mut index = SearchIndex::new();
-        index.insert("validate_user", node_id(0));
-        index.insert("validate_email", node_id(1));
-        index.insert("send_email", node_id(2));
+        let index = index_of(&[
+            node("validate_user"),
+            node("validate_email"),
+            node("send_email"),
+        ]);
 
         let results = index.search("validate_user");
-        assert_eq!(results, vec![node_id(0)]);
+        assert_eq!(results[0], node_id(0));
     }
 
     #[test]
     fn test_search_substring() {
-        let mut index = SearchIndex::new();
-        index.insert("validate_user", node_id(0));
-        index.insert("validate_email", node_id(1));
-        index.insert("send_email", node_id(2));
+        let index = index_of(&[
+            node("validate_user"),
+            node("validate_email"),
+            node("send_email"),
+        ]);
 
         let results = index.search("validate");
         assert!(results.contains(&node_id(0)));
@@ -192,8 +753,7 @@ mod tests {
 
     #[test]
     fn test_search_case_insensitive() {
-        let mut index = SearchIndex::new();
-        index.insert("ValidateUser", node_id(0));
+        let index = index_of(&[node("ValidateUser")]);
 
         let results = index.search("validateuser");
         assert_eq!(results, vec![node_id(0)]);
@@ -204,8 +764,7 @@ mod tests {
 
     #[test]
     fn test_search_middle_substring() {
-        let mut index = SearchIndex::new();
-        index.insert("get_user_profile", node_id(0));
+        let index = index_of(&[node("get_user_profile")]);
 
         let results = index.search("user");
         assert_eq!(results, vec![node_id(0)]);
@@ -216,11 +775,9 @@ mod tests {
 
     #[test]
     fn test_remove_from_index() {
-        let mut index = SearchIndex::new();
-        index.insert("foo", node_id(0));
-        index.insert("foobar", node_id(1));
+        let mut index = index_of(&[node("foo"), node("foobar")]);
 
-        index.remove("foo", node_id(0));
+        index.remove(node_id(0));
 
         let results = index.search("foo");
         assert!(!results.contains(&node_id(0)));
@@ -229,8 +786,7 @@ mod tests {
 
     #[test]
     fn test_search_no_match() {
-        let mut index = SearchIndex::new();
-        index.insert("hello", node_id(0));
+        let index = index_of(&[node("hello")]);
 
         let results = index.search("world");
         assert!(results.is_empty());
@@ -238,12 +794,9 @@ mod tests {
 
     #[test]
     fn test_short_query() {
-        let mut index = SearchIndex::new();
-        index.insert("ab", node_id(0));
-        index.insert("abc", node_id(1));
-        index.insert("xyz", node_id(2));
+        let index = index_of(&[node("ab"), node("abc"), node("xyz")]);
 
-        // Query shorter than MIN_NGRAM_LEN uses prefix matching
+        // Short queries match by prefix
         let results = index.search("a");
         assert!(results.contains(&node_id(0)));
         assert!(results.contains(&node_id(1)));
@@ -256,20 +809,18 @@ mod tests {
         assert!(index.is_empty());
         assert_eq!(index.len(), 0);
 
-        index.insert("foo", node_id(0));
+        index.insert(&node("foo"), node_id(0));
         assert!(!index.is_empty());
         assert_eq!(index.len(), 1);
 
-        index.insert("bar", node_id(1));
+        index.insert(&node("bar"), node_id(1));
         assert_eq!(index.len(), 2);
     }
 
     #[test]
     fn test_duplicate_name_different_ids() {
-        let mut index = SearchIndex::new();
         // Two different nodes with the same name (overloads, different files)
-        index.insert("process", node_id(0));
-        index.insert("process", node_id(1));
+        let index = index_of(&[node("process"), node("process")]);
 
         let results = index.search("process");
         assert!(results.contains(&node_id(0)));
@@ -282,17 +833,119 @@ mod tests {
     fn test_remove_nonexistent_does_not_panic() {
         let mut index = SearchIndex::new();
         // Removing from empty index should not panic
-        index.remove("nonexistent", node_id(99));
+        index.remove(node_id(99));
         assert!(index.is_empty());
     }
 
     #[test]
     fn test_search_empty_query() {
-        let mut index = SearchIndex::new();
-        index.insert("hello", node_id(0));
+        let index = index_of(&[node("hello")]);
 
         // Empty query should return empty (no prefix matches)
         let results = index.search("");
         assert!(results.is_empty());
     }
+
+    #[test]
+    fn test_tokenize_subwords() {
+        assert_eq!(
+            tokenize("parseHTTPRequest_v2"),
+            vec!["parse", "http", "request", "v2"]
+        );
+        assert_eq!(
+            tokenize("UserService::find_by_id"),
+            vec!["user", "service", "find", "by", "id"]
+        );
+    }
+
+    #[test]
+    fn test_subwords_match_across_naming_styles() {
+        let index = index_of(&[node("validateUser"), node("UserValidator"), node("send")]);
+
+        let results = index.search("user validate");
+        assert_eq!(results[0], node_id(0));
+        assert!(!results.contains(&node_id(2)));
+        // Compact form matches snake/camel alike.
+        assert_eq!(index.search("validate_user")[0], node_id(0));
+    }
+
+    #[test]
+    fn test_typo_tolerance() {
+        let index = index_of(&[node("authenticate"), node("authorize")]);
+        assert_eq!(index.search("authenitcate")[0], node_id(0));
+        assert_eq!(index.search("athorize")[0], node_id(1));
+    }
+
+    #[test]
+    fn test_name_hits_outrank_docstring_hits() {
+        let mut mentions = node("refresh");
+        mentions.docstring = Some("Refreshes the invoice cache".to_string());
+        let index = index_of(&[mentions, node("invoice")]);
+
+        assert_eq!(index.search("invoice"), vec![node_id(1), node_id(0)]);
+    }
+
+    #[test]
+    fn test_kind_and_centrality_boosts() {
+        let mut import = node("Config");
+        import.kind = NodeKind::Import;
+        let class = CodeNode::new("Config", "Config", NodeKind::Class, "config.rs");
+        let index = index_of(&[import, class]);
+        assert_eq!(index.search("config"), vec![node_id(1), node_id(0)]);
+
+        let index = index_of(&[node("load"), node("load")]);
+        let query = SearchQuery::parse("load");
+        let hits = index.rank(&query, |id| if id == node_id(1) { 1.0 } else { 0.0 });
+        assert_eq!(hits[0].id, node_id(1));
+        assert!(hits[0].score > hits[1].score);
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let mut charge = CodeNode::new(
+            "charge",
+            "Billing.charge",
+            NodeKind::Method,
+            "/repo/src/billing/stripe.ts",
+        );
+        charge.is_async = true;
+        let refund = CodeNode::new(
+            "refund",
+            "Billing.refund",
+            NodeKind::Method,
+            "/repo/src/billing/stripe.ts",
+        );
+        let charge_fn = CodeNode::new("charge", "charge", NodeKind::Function, "/repo/src/cli.ts");
+        let index = index_of(&[charge, refund, charge_fn]);
+
+        assert_eq!(index.search("charge kind:method"), vec![node_id(0)]);
+        assert_eq!(
+            index.search("kind:method file:src/billing/** async:true"),
+            vec![node_id(0)]
+        );
+        assert_eq!(index.search("file:cli.ts"), vec![node_id(2)]);
+        assert_eq!(index.search("kind:method").len(), 2);
+        // Unknown keys stay in the free text.
+        assert!(SearchQuery::parse("std::fs")
+            .terms
+            .contains(&"fs".to_string()));
+    }
+
+    #[test]
+    fn test_path_globs() {
+        assert!(path_matches("src/billing/**", "/repo/src/billing/a/b.rs"));
+        assert!(path_matches("**/*.rs", "/repo/src/lib.rs"));
+        assert!(path_matches("src/*.rs", "/repo/src/lib.rs"));
+        assert!(!path_matches("src/*.rs", "/repo/src/nested/lib.rs"));
+        assert!(!path_matches("/src/**", "/repo/src/lib.rs"));
+    }
+
+    #[test]
+    fn test_reinsert_replaces_document() {
+        let mut index = index_of(&[node("old_name")]);
+        index.insert(&node("new_name"), node_id(0));
+        assert!(index.search("old").is_empty());
+        assert_eq!(index.search("new"), vec![node_id(0)]);
+        assert_eq!(index.len(), 1);
+    }
 }
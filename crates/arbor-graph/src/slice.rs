@@ -6,6 +6,7 @@
 
 use crate::graph::{ArborGraph, NodeId};
 use crate::query::NodeInfo;
+use crate::source::{elide, signature_text, NodeSource, SourceDetail, SourcePolicy, SourceReader};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use serde::{Deserialize, Serialize};
@@ -45,6 +46,15 @@ pub struct ContextNode {
     pub depth: usize,
     /// Whether this node was pinned (always included).
     pub pinned: bool,
+    /// Source text at `detail` level, when requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// How much of the node's source `source` holds.
+    #[serde(default)]
+    pub detail: SourceDetail,
+    /// True if the file changed since indexing, so its source was withheld.
+    #[serde(default)]
+    pub stale: bool,
 }
 
 /// Result of a context slicing operation.
@@ -123,11 +133,32 @@ fn estimate_tokens(node: &NodeInfo) -> usize {
         " ".repeat(lines * 40) // Approximate code content
     );
 
-    // Use tiktoken for accurate count, fall back to heuristic if unavailable.
-    if let Some(tokenizer) = TOKENIZER.as_ref() {
-        tokenizer.encode_with_special_tokens(&text).len()
-    } else {
-        estimated_chars.div_ceil(4)
+    count_tokens(&text)
+}
+
+/// The text a node contributes to a slice with source.
+fn render_with_source(info: &NodeInfo, source: Option<&str>) -> String {
+    let header = format!(
+        "{} {} ({}:{}-{})",
+        info.kind, info.qualified_name, info.file, info.line_start, info.line_end
+    );
+    match source {
+        Some(source) => format!("{}\n{}", header, source),
+        None => header,
+    }
+}
+
+/// Counts cl100k_base tokens in `text`.
+///
+/// Uses the 4 chars/token heuristic for text over 800 KB or if the
+/// tokenizer failed to load.
+pub fn count_tokens(text: &str) -> usize {
+    if text.len() > LARGE_FILE_THRESHOLD {
+        return text.len().div_ceil(4);
+    }
+    match TOKENIZER.as_ref() {
+        Some(tokenizer) => tokenizer.encode_with_special_tokens(text).len(),
+        None => text.len().div_ceil(4),
     }
 }
 
@@ -174,6 +205,7 @@ impl ArborGraph {
                         line_end: 0,
                         signature: None,
                         centrality: 0.0,
+                        metrics: Default::default(),
                     },
                     nodes: Vec::new(),
                     total_tokens: 0,
@@ -238,6 +270,9 @@ impl ArborGraph {
                         token_estimate: token_est,
                         depth,
                         pinned: is_pinned,
+                        source: None,
+                        detail: SourceDetail::None,
+                        stale: false,
                     });
                 } else {
                     truncation_reason = TruncationReason::TokenBudget;
@@ -291,6 +326,120 @@ impl ArborGraph {
     }
 }
 
+impl ArborGraph {
+    /// Like [`slice_context`](Self::slice_context), but includes each node's
+    /// source text at the detail `policy` asks for.
+    ///
+    /// Token counts are exact for the rendered header plus source. Pinned
+    /// nodes always get their requested detail. Other nodes degrade to fit
+    /// the remaining budget: a full body is elided, then cut to its
+    /// signature, then dropped. A node whose file changed since indexing
+    /// is marked `stale` and gets no source.
+    pub fn slice_context_with_source(
+        &self,
+        target: NodeId,
+        max_tokens: usize,
+        max_depth: usize,
+        pinned: &[NodeId],
+        policy: &SourcePolicy,
+        reader: &mut SourceReader,
+    ) -> ContextSlice {
+        let start = Instant::now();
+        let mut slice = self.slice_context(target, 0, max_depth, pinned);
+        let budget = if max_tokens == 0 {
+            usize::MAX
+        } else {
+            max_tokens
+        };
+
+        let mut total_tokens = 0usize;
+        let mut nodes = Vec::with_capacity(slice.nodes.len());
+        for mut context_node in std::mem::take(&mut slice.nodes) {
+            let Some(node) = self
+                .get_index(&context_node.node_info.id)
+                .and_then(|index| self.get(index))
+            else {
+                continue;
+            };
+            let is_target = context_node.depth == 0;
+            let wanted = policy.for_node(is_target, context_node.pinned);
+
+            let mut body = None;
+            if wanted == SourceDetail::Full {
+                match reader.read(node) {
+                    NodeSource::Fresh(text) => body = Some(text),
+                    NodeSource::Stale => context_node.stale = true,
+                    NodeSource::Unavailable => {}
+                }
+            }
+            let signature = if context_node.stale {
+                None
+            } else {
+                signature_text(node)
+            };
+
+            // Candidate renderings, most detailed first.
+            let mut candidates: Vec<(SourceDetail, Option<String>)> = Vec::new();
+            if let Some(body) = body {
+                candidates.push((SourceDetail::Full, Some(body)));
+            }
+            if wanted != SourceDetail::None {
+                if let Some(signature) = signature {
+                    candidates.push((SourceDetail::Signature, Some(signature)));
+                }
+            }
+            candidates.push((SourceDetail::None, None));
+
+            let remaining = budget.saturating_sub(total_tokens);
+            let mut chosen = None;
+            for (detail, source) in candidates {
+                let tokens = count_tokens(&render_with_source(
+                    &context_node.node_info,
+                    source.as_deref(),
+                ));
+                if context_node.pinned || tokens <= remaining {
+                    chosen = Some((detail, source, tokens));
+                    break;
+                }
+                // Try an elided body before giving up on the full one.
+                if detail == SourceDetail::Full {
+                    let header = count_tokens(&render_with_source(&context_node.node_info, None));
+                    let elided = source
+                        .as_deref()
+                        .and_then(|text| elide(text, remaining.saturating_sub(header + 1)));
+                    if let Some(elided) = elided {
+                        let tokens = count_tokens(&render_with_source(
+                            &context_node.node_info,
+                            Some(&elided),
+                        ));
+                        if tokens <= remaining {
+                            chosen = Some((SourceDetail::Elided, Some(elided), tokens));
+                            break;
+                        }
+                    }
+                }
+            }
+
+            match chosen {
+                Some((detail, source, tokens)) => {
+                    total_tokens += tokens;
+                    context_node.detail = detail;
+                    context_node.source = source;
+                    context_node.token_estimate = tokens;
+                    nodes.push(context_node);
+                }
+                None => slice.truncation_reason = TruncationReason::TokenBudget,
+            }
+        }
+
+        slice.nodes = nodes;
+        slice.total_tokens = total_tokens;
+        slice.max_tokens = max_tokens;
+        slice.query_time_ms = start.elapsed().as_millis() as u64;
+        slice
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -401,4 +550,132 @@ mod tests {
         assert_eq!(result.truncation_reason, TruncationReason::Complete);
         assert_eq!(result.nodes.len(), 2);
     }
+
+    /// Writes `source` to `dir/file` and returns its functions as nodes with
+    /// byte ranges and hashes, one per `fn` block.
+    fn source_nodes(dir: &std::path::Path, file: &str, source: &str) -> Vec<CodeNode> {
+        std::fs::write(dir.join(file), source).unwrap();
+        let mut nodes = Vec::new();
+        let mut offset = 0;
+        for block in source.split_inclusive("}\n") {
+            let name = block
+                .trim_start()
+                .strip_prefix("fn ")
+                .and_then(|rest| rest.split('(').next())
+                .unwrap()
+                .to_string();
+            let end = offset + block.trim_end().len();
+            nodes.push(
+                CodeNode::new(&name, &name, NodeKind::Function, file)
+                    .with_bytes(offset as u32, end as u32)
+                    .with_signature(format!("fn {}()", name)),
+            );
+            offset += block.len();
+        }
+        arbor_core::stamp_content_hashes(&mut nodes, source);
+        nodes
+    }
+
+    #[test]
+    fn test_slice_with_source_applies_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "fn target() {\n    helper();\n}\nfn helper() {\n    let x = 1;\n}\n";
+        let nodes = source_nodes(dir.path(), "lib.rs", source);
+
+        let mut graph = ArborGraph::new();
+        let target = graph.add_node(nodes[0].clone());
+        let helper = graph.add_node(nodes[1].clone());
+        graph.add_edge(target, helper, Edge::new(EdgeKind::Calls));
+
+        let mut reader = SourceReader::with_root(dir.path());
+        let slice = graph.slice_context_with_source(
+            target,
+            0,
+            1,
+            &[],
+            &SourcePolicy::default(),
+            &mut reader,
+        );
+
+        let target_node = slice.nodes.iter().find(|n| n.depth == 0).unwrap();
+        assert_eq!(target_node.detail, SourceDetail::Full);
+        assert_eq!(
+            target_node.source.as_deref(),
+            Some("fn target() {\n    helper();\n}")
+        );
+
+        let neighbor = slice.nodes.iter().find(|n| n.depth == 1).unwrap();
+        assert_eq!(neighbor.detail, SourceDetail::Signature);
+        assert_eq!(neighbor.source.as_deref(), Some("fn helper()"));
+        assert_eq!(
+            slice.total_tokens,
+            slice.nodes.iter().map(|n| n.token_estimate).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_slice_with_source_elides_to_fit_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let body: Vec<String> = (0..80)
+            .map(|i| format!("    let step_{} = process_record({});", i, i))
+            .collect();
+        let source = format!("fn big() {{\n{}\n}}\n", body.join("\n"));
+        let nodes = source_nodes(dir.path(), "big.rs", &source);
+
+        let mut graph = ArborGraph::new();
+        let big = graph.add_node(nodes[0].clone());
+
+        let mut reader = SourceReader::with_root(dir.path());
+        let slice = graph.slice_context_with_source(
+            big,
+            150,
+            1,
+            &[],
+            &SourcePolicy::default(),
+            &mut reader,
+        );
+        assert_eq!(slice.nodes.len(), 1);
+        assert_eq!(slice.nodes[0].detail, SourceDetail::Elided);
+        assert!(slice.nodes[0]
+            .source
+            .as_deref()
+            .unwrap()
+            .contains("lines elided"));
+        assert!(slice.total_tokens <= 150);
+
+        // Pinned nodes keep their full body whatever the budget.
+        let pinned = graph.slice_context_with_source(
+            big,
+            150,
+            1,
+            &[big],
+            &SourcePolicy::default(),
+            &mut reader,
+        );
+        assert_eq!(pinned.nodes[0].detail, SourceDetail::Full);
+    }
+
+    #[test]
+    fn test_slice_with_source_marks_stale_nodes() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "fn target() {\n    old();\n}\n";
+        let nodes = source_nodes(dir.path(), "lib.rs", source);
+        std::fs::write(dir.path().join("lib.rs"), source.replace("old", "new")).unwrap();
+
+        let mut graph = ArborGraph::new();
+        let target = graph.add_node(nodes[0].clone());
+
+        let mut reader = SourceReader::with_root(dir.path());
+        let slice = graph.slice_context_with_source(
+            target,
+            0,
+            1,
+            &[],
+            &SourcePolicy::default(),
+            &mut reader,
+        );
+        assert!(slice.nodes[0].stale);
+        assert_eq!(slice.nodes[0].detail, SourceDetail::None);
+        assert!(slice.nodes[0].source.is_none());
+    }
 }
@@ -0,0 +1,284 @@
+//! Reading node source text for context slices.
+//!
+//! Nodes record the byte range they were extracted from and a hash of
+//! those bytes. [`SourceReader`] reads the range back and checks the hash,
+//! so a file edited since indexing yields "stale" instead of the wrong code.
+//! [`SourcePolicy`] decides how much text each node gets in a slice.
+
+use crate::slice::count_tokens;
+use arbor_core::CodeNode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How much of a node's source is included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceDetail {
+    /// Metadata only.
+    #[default]
+    None,
+    /// Docstring and signature.
+    Signature,
+    /// The complete body.
+    Full,
+    /// The body with its middle replaced by an elision marker to fit the budget.
+    Elided,
+}
+
+impl std::fmt::Display for SourceDetail {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceDetail::None => write!(f, "none"),
+            SourceDetail::Signature => write!(f, "signature"),
+            SourceDetail::Full => write!(f, "full"),
+            SourceDetail::Elided => write!(f, "elided"),
+        }
+    }
+}
+
+/// Requested detail per node role.
+///
+/// `Full` is best-effort for non-pinned nodes: a body that doesn't fit the
+/// budget is elided, then reduced to its signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePolicy {
+    pub target: SourceDetail,
+    pub pinned: SourceDetail,
+    pub neighbors: SourceDetail,
+}
+
+impl Default for SourcePolicy {
+    fn default() -> Self {
+        Self {
+            target: SourceDetail::Full,
+            pinned: SourceDetail::Full,
+            neighbors: SourceDetail::Signature,
+        }
+    }
+}
+
+impl SourcePolicy {
+    /// Detail requested for a node.
+    pub fn for_node(&self, is_target: bool, pinned: bool) -> SourceDetail {
+        if pinned {
+            self.pinned
+        } else if is_target {
+            self.target
+        } else {
+            self.neighbors
+        }
+    }
+}
+
+/// Outcome of reading a node's source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeSource {
+    /// The bytes match what was indexed.
+    Fresh(String),
+    /// The file changed since indexing; its bytes no longer match the hash.
+    Stale,
+    /// The file couldn't be read or the node has no location.
+    Unavailable,
+}
+
+/// Reads node source from disk, caching file contents per file.
+#[derive(Debug, Default)]
+pub struct SourceReader {
+    root: Option<PathBuf>,
+    files: HashMap<String, Option<Vec<u8>>>,
+}
+
+impl SourceReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolves relative node paths against `root`.
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: Some(root.into()),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Reads a node's source and validates it against its content hash.
+    pub fn read(&mut self, node: &CodeNode) -> NodeSource {
+        let Some(bytes) = self.file(&node.file) else {
+            return NodeSource::Unavailable;
+        };
+
+        let (start, end) = (node.byte_start as usize, node.byte_end as usize);
+        if end > start {
+            return match bytes.get(start..end) {
+                Some(range) if node.matches_source(range) => {
+                    NodeSource::Fresh(String::from_utf8_lossy(range).into_owned())
+                }
+                _ => NodeSource::Stale,
+            };
+        }
+        match read_node_source(node, bytes) {
+            Some(text) => NodeSource::Fresh(text),
+            None => NodeSource::Unavailable,
+        }
+    }
+
+    /// The current contents of `file`, read once and cached.
+    pub(crate) fn file(&mut self, file: &str) -> Option<&[u8]> {
+        let root = self.root.as_deref();
+        self.files
+            .entry(file.to_string())
+            .or_insert_with(|| std::fs::read(resolve(root, file)).ok())
+            .as_deref()
+    }
+}
+
+fn resolve(root: Option<&Path>, file: &str) -> PathBuf {
+    match root {
+        Some(root) if Path::new(file).is_relative() => root.join(file),
+        _ => PathBuf::from(file),
+    }
+}
+
+/// Reads a node's source text via its byte range, falling back to its
+/// line range for nodes without byte offsets.
+pub fn read_node_source(node: &CodeNode, file_bytes: &[u8]) -> Option<String> {
+    let (start, end) = (node.byte_start as usize, node.byte_end as usize);
+    if end > start && end <= file_bytes.len() {
+        return Some(String::from_utf8_lossy(&file_bytes[start..end]).into_owned());
+    }
+
+    let text = String::from_utf8_lossy(file_bytes);
+    let first = node.line_start.max(1) as usize - 1;
+    let count = node
+        .line_end
+        .max(node.line_start)
+        .saturating_sub(node.line_start) as usize
+        + 1;
+    let lines: Vec<&str> = text.lines().skip(first).take(count).collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// A node's docstring and signature, or `None` if it has neither.
+pub fn signature_text(node: &CodeNode) -> Option<String> {
+    match (&node.docstring, &node.signature) {
+        (Some(doc), Some(sig)) => Some(format!("{}\n{}", doc.trim_end(), sig)),
+        (Some(doc), None) => Some(doc.trim_end().to_string()),
+        (None, Some(sig)) => Some(sig.clone()),
+        (None, None) => None,
+    }
+}
+
+/// Shortens `source` to at most `max_tokens` by keeping its first and last
+/// lines around a marker saying how many lines were cut.
+///
+/// Returns the source unchanged if it already fits, and `None` if not even
+/// one head and one tail line fit.
+pub fn elide(source: &str, max_tokens: usize) -> Option<String> {
+    if count_tokens(source) <= max_tokens {
+        return Some(source.to_string());
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    if lines.len() < 3 {
+        return None;
+    }
+
+    // Keep two head lines for every tail line: signatures and setup carry
+    // more meaning than the closing lines.
+    let build = |kept: usize| {
+        let tail = (kept / 3).max(1);
+        let head = kept - tail;
+        let cut = lines.len() - kept;
+        let indent: String = lines[head]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        format!(
+            "{}\n{}… {} lines elided …\n{}",
+            lines[..head].join("\n"),
+            indent,
+            cut,
+            lines[lines.len() - tail..].join("\n")
+        )
+    };
+
+    // Binary search for the most lines that still fit.
+    let (mut low, mut high) = (2, lines.len() - 1);
+    let mut best = None;
+    while low <= high {
+        let kept = (low + high) / 2;
+        let text = build(kept);
+        if count_tokens(&text) <= max_tokens {
+            best = Some(text);
+            low = kept + 1;
+        } else {
+            high = kept - 1;
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arbor_core::{stamp_content_hashes, NodeKind};
+
+    #[test]
+    fn test_read_node_source_uses_byte_range() {
+        let source = b"fn a() {}\nfn b() {\n    a();\n}\n";
+        let node = CodeNode::new("b", "b", NodeKind::Function, "x.rs").with_bytes(10, 29);
+        assert_eq!(
+            read_node_source(&node, source).unwrap(),
+            "fn b() {\n    a();\n}"
+        );
+
+        let no_bytes = CodeNode::new("a", "a", NodeKind::Function, "x.rs").with_lines(1, 1);
+        assert_eq!(read_node_source(&no_bytes, source).unwrap(), "fn a() {}");
+    }
+
+    #[test]
+    fn test_reader_detects_stale_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "fn a() {}\nfn b() {\n    a();\n}\n";
+        std::fs::write(dir.path().join("x.rs"), source).unwrap();
+
+        let mut nodes =
+            vec![CodeNode::new("b", "b", NodeKind::Function, "x.rs").with_bytes(10, 29)];
+        stamp_content_hashes(&mut nodes, source);
+
+        let mut reader = SourceReader::with_root(dir.path());
+        assert_eq!(
+            reader.read(&nodes[0]),
+            NodeSource::Fresh("fn b() {\n    a();\n}".to_string())
+        );
+
+        // Same length, different bytes.
+        std::fs::write(dir.path().join("x.rs"), source.replace("a();", "c();")).unwrap();
+        assert_eq!(
+            SourceReader::with_root(dir.path()).read(&nodes[0]),
+            NodeSource::Stale
+        );
+
+        let missing = CodeNode::new("m", "m", NodeKind::Function, "missing.rs");
+        assert_eq!(reader.read(&missing), NodeSource::Unavailable);
+    }
+
+    #[test]
+    fn test_elide_keeps_head_and_tail() {
+        let body: Vec<String> = (0..60)
+            .map(|i| format!("    let value_{} = compute_something({});", i, i))
+            .collect();
+        let source = format!("fn big() {{\n{}\n}}", body.join("\n"));
+
+        assert_eq!(elide(&source, 100_000).as_deref(), Some(source.as_str()));
+
+        let elided = elide(&source, 120).unwrap();
+        assert!(count_tokens(&elided) <= 120);
+        assert!(elided.starts_with("fn big() {"));
+        assert!(elided.ends_with('}'));
+        assert!(elided.contains("lines elided"));
+
+        assert!(elide(&source, 3).is_none());
+    }
+}
@@ -1,12 +1,26 @@
 use crate::builder::GraphBuilder;
+use crate::declared::Declarations;
 use crate::graph::ArborGraph;
-use arbor_core::CodeNode;
+use arbor_core::{CodeNode, ParseDiagnostics};
 use sled::{Batch, Db};
 use std::path::Path;
 use thiserror::Error;
 
-/// Current cache format version. Increment when schema changes.
-const CACHE_VERSION: &str = concat!("arbor-", env!("CARGO_PKG_VERSION"));
+/// Layout of the cached nodes and graph. Bump it whenever a serialized
+/// type (`CodeNode`, `Edge`, `ArborGraph`, ...) gains, loses or reorders a
+/// field: bincode stores no field names, so `#[serde(default)]` cannot
+/// fill in a field an old cache lacks.
+const SCHEMA_VERSION: u32 = 3;
+
+/// Version stamped on the sled cache and `graph.bin`; a cache with any
+/// other stamp is rebuilt.
+fn cache_version() -> String {
+    format!(
+        "arbor-{}-schema-{}",
+        env!("CARGO_PKG_VERSION"),
+        SCHEMA_VERSION
+    )
+}
 
 #[derive(Error, Debug)]
 pub enum StoreError {
@@ -20,6 +34,28 @@ pub enum StoreError {
     VersionMismatch { expected: String, found: String },
 }
 
+/// Serializes a graph for `graph.bin`, led by the cache version.
+pub fn encode_graph(graph: &ArborGraph) -> Result<Vec<u8>, StoreError> {
+    let mut bytes = bincode::serialize(&cache_version())?;
+    bincode::serialize_into(&mut bytes, graph)?;
+    Ok(bytes)
+}
+
+/// Reads a graph written by [`encode_graph`]; a graph written by another
+/// version is a [`StoreError::VersionMismatch`], not misread data.
+pub fn decode_graph(bytes: &[u8]) -> Result<ArborGraph, StoreError> {
+    let mut reader = bytes;
+    let version: String = bincode::deserialize_from(&mut reader)
+        .map_err(|_| StoreError::Corrupted("missing cache version".to_string()))?;
+    if version != cache_version() {
+        return Err(StoreError::VersionMismatch {
+            expected: cache_version(),
+            found: version,
+        });
+    }
+    Ok(bincode::deserialize_from(reader)?)
+}
+
 pub struct GraphStore {
     db: Db,
 }
@@ -34,15 +70,15 @@ impl GraphStore {
         // Check cache version
         if let Some(version_bytes) = store.db.get("meta:version")? {
             let version: String = bincode::deserialize(&version_bytes)?;
-            if version != CACHE_VERSION {
+            if version != cache_version() {
                 return Err(StoreError::VersionMismatch {
-                    expected: CACHE_VERSION.to_string(),
+                    expected: cache_version(),
                     found: version,
                 });
             }
         } else {
             // New cache, set version
-            let version_bytes = bincode::serialize(&CACHE_VERSION.to_string())?;
+            let version_bytes = bincode::serialize(&cache_version())?;
             store.db.insert("meta:version", version_bytes)?;
         }
 
@@ -57,7 +93,7 @@ impl GraphStore {
                 // Clear and reopen
                 let db = sled::open(path.as_ref())?;
                 db.clear()?;
-                let version_bytes = bincode::serialize(&CACHE_VERSION.to_string())?;
+                let version_bytes = bincode::serialize(&cache_version())?;
                 db.insert("meta:version", version_bytes)?;
                 db.flush()?;
                 Ok(Self { db })
@@ -112,7 +148,8 @@ impl GraphStore {
         let mtime_key = format!("m:{}", file_path);
         let mut batch = Batch::default();
 
-        // 1. Get old nodes for this file
+        // 1. Get old nodes for this file (their embeddings stay: node IDs
+        //    are stable and each vector carries the hash it was computed from)
         if let Some(old_bytes) = self.db.get(&file_key)? {
             let old_ids: Vec<String> = bincode::deserialize(&old_bytes)?;
             for id in old_ids {
@@ -143,22 +180,95 @@ impl GraphStore {
         Ok(())
     }
 
+    /// Stores the parse diagnostics for a file.
+    pub fn set_file_diagnostics(
+        &self,
+        file_path: &str,
+        diagnostics: &ParseDiagnostics,
+    ) -> Result<(), StoreError> {
+        let key = format!("d:{}", file_path);
+        self.db.insert(key, bincode::serialize(diagnostics)?)?;
+        Ok(())
+    }
+
+    /// Gets the stored parse diagnostics for a file.
+    pub fn get_file_diagnostics(
+        &self,
+        file_path: &str,
+    ) -> Result<Option<ParseDiagnostics>, StoreError> {
+        let key = format!("d:{}", file_path);
+        match self.db.get(&key)? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Gets a node's stored embedding and the content hash it was computed from.
+    pub fn get_embedding(&self, node_id: &str) -> Result<Option<(u64, Vec<f32>)>, StoreError> {
+        let key = format!("e:{}", node_id);
+        match self.db.get(&key)? {
+            Some(bytes) => Ok(Some(bincode::deserialize(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Stores embeddings as `(node_id, content_hash, vector)`.
+    pub fn set_embeddings(&self, embeddings: &[(String, u64, Vec<f32>)]) -> Result<(), StoreError> {
+        let mut batch = Batch::default();
+        for (node_id, hash, vector) in embeddings {
+            let key = format!("e:{}", node_id);
+            batch.insert(key.as_bytes(), bincode::serialize(&(hash, vector))?);
+        }
+        self.db.apply_batch(batch)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Records which embedding model produced the stored vectors.
+    ///
+    /// If it differs from the recorded one, all stored embeddings are
+    /// dropped, since vectors from different models aren't comparable.
+    pub fn set_embedding_model(&self, fingerprint: &str) -> Result<(), StoreError> {
+        let current = match self.db.get("meta:embedder")? {
+            Some(bytes) => Some(bincode::deserialize::<String>(&bytes)?),
+            None => None,
+        };
+        if current.as_deref() == Some(fingerprint) {
+            return Ok(());
+        }
+
+        let mut batch = Batch::default();
+        for item in self.db.scan_prefix(b"e:") {
+            let (key, _) = item?;
+            batch.remove(key);
+        }
+        batch.insert(
+            "meta:embedder",
+            bincode::serialize(&fingerprint.to_string())?,
+        );
+        self.db.apply_batch(batch)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
     /// Removes a file from the cache (for deleted files).
     pub fn remove_file(&self, file_path: &str) -> Result<(), StoreError> {
         let file_key = format!("f:{}", file_path);
         let mtime_key = format!("m:{}", file_path);
         let mut batch = Batch::default();
 
-        // Remove nodes
+        // Remove nodes and their embeddings
         if let Some(old_bytes) = self.db.get(&file_key)? {
             let old_ids: Vec<String> = bincode::deserialize(&old_bytes)?;
             for id in old_ids {
                 batch.remove(format!("n:{}", id).as_bytes());
+                batch.remove(format!("e:{}", id).as_bytes());
             }
         }
 
         batch.remove(file_key.as_bytes());
         batch.remove(mtime_key.as_bytes());
+        batch.remove(format!("d:{}", file_path).as_bytes());
 
         self.db.apply_batch(batch)?;
         self.db.flush()?;
@@ -184,7 +294,14 @@ impl GraphStore {
     /// This iterates over all stored nodes and reconstructs the ArborGraph
     /// using the GraphBuilder (which re-links edges).
     pub fn load_graph(&self) -> Result<ArborGraph, StoreError> {
+        self.load_graph_with(Declarations::default())
+    }
+
+    /// Like [`GraphStore::load_graph`], adding the project's declared
+    /// edges and aliases.
+    pub fn load_graph_with(&self, declarations: Declarations) -> Result<ArborGraph, StoreError> {
         let mut builder = GraphBuilder::new();
+        builder.add_declarations(declarations);
         let mut nodes = Vec::new();
 
         // Iterate over all keys starting with "n:"
@@ -203,7 +320,16 @@ impl GraphStore {
         // Reconstruct graph; centrality pre-computed on-demand via ranking.rs for large (500k+) repos (sled keeps RAM low per roadmap)
         builder.add_nodes(nodes);
         // resolve_edges() is called by build()
-        let graph = builder.build();
+        let mut graph = builder.build();
+
+        for item in self.db.scan_prefix(b"d:") {
+            let (key, value) = item?;
+            let key_str = String::from_utf8_lossy(&key);
+            if let Some(file_path) = key_str.strip_prefix("d:") {
+                let diagnostics: ParseDiagnostics = bincode::deserialize(&value)?;
+                graph.set_file_diagnostics(file_path, diagnostics);
+            }
+        }
 
         Ok(graph)
     }
@@ -212,7 +338,7 @@ impl GraphStore {
     pub fn clear(&self) -> Result<(), StoreError> {
         self.db.clear()?;
         // Re-set version after clear
-        let version_bytes = bincode::serialize(&CACHE_VERSION.to_string())?;
+        let version_bytes = bincode::serialize(&cache_version())?;
         self.db.insert("meta:version", version_bytes)?;
         self.db.flush()?;
         Ok(())
@@ -269,6 +395,41 @@ mod tests {
         // Second open should succeed with same version
         let store2 = GraphStore::open(dir.path()).unwrap();
         drop(store2);
+
+        // A cache from another schema is rejected, then reset
+        let db = sled::open(dir.path()).unwrap();
+        db.insert(
+            "meta:version",
+            bincode::serialize(&"arbor-0.0.0-schema-1".to_string()).unwrap(),
+        )
+        .unwrap();
+        db.insert("m:old.rs", bincode::serialize(&1u64).unwrap())
+            .unwrap();
+        drop(db);
+        assert!(matches!(
+            GraphStore::open(dir.path()),
+            Err(StoreError::VersionMismatch { .. })
+        ));
+        let store3 = GraphStore::open_or_reset(dir.path()).unwrap();
+        assert_eq!(store3.get_mtime("old.rs").unwrap(), None);
+    }
+
+    #[test]
+    fn test_graph_bytes_carry_cache_version() {
+        let mut graph = ArborGraph::new();
+        graph.add_node(CodeNode::new("foo", "foo", NodeKind::Function, "a.rs"));
+        let bytes = encode_graph(&graph).unwrap();
+        assert_eq!(decode_graph(&bytes).unwrap().node_count(), 1);
+
+        // A graph.bin written before the version header, or by another
+        // schema, is refused rather than misread.
+        assert!(decode_graph(&bincode::serialize(&graph).unwrap()).is_err());
+        let mut stale = bincode::serialize(&"arbor-0.0.0-schema-1".to_string()).unwrap();
+        bincode::serialize_into(&mut stale, &graph).unwrap();
+        assert!(matches!(
+            decode_graph(&stale),
+            Err(StoreError::VersionMismatch { .. })
+        ));
     }
 
     #[test]
@@ -291,6 +452,60 @@ mod tests {
         assert!(store.get_file_nodes("test.rs").unwrap().is_none());
     }
 
+    #[test]
+    fn test_file_diagnostics_roundtrip() {
+        let dir = tempdir().unwrap();
+        let store = GraphStore::open(dir.path()).unwrap();
+
+        let node = CodeNode::new("foo", "foo", NodeKind::Function, "broken.rs");
+        store.update_file("broken.rs", &[node], 1000).unwrap();
+        let diagnostics = ParseDiagnostics {
+            error_count: 2,
+            coverage: 0.4,
+            ..ParseDiagnostics::default()
+        };
+        store
+            .set_file_diagnostics("broken.rs", &diagnostics)
+            .unwrap();
+
+        let graph = store.load_graph().unwrap();
+        assert_eq!(graph.file_diagnostics("broken.rs"), Some(&diagnostics));
+
+        store.remove_file("broken.rs").unwrap();
+        assert!(store.get_file_diagnostics("broken.rs").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_embeddings_roundtrip_and_model_change() {
+        let dir = tempdir().unwrap();
+        let store = GraphStore::open(dir.path()).unwrap();
+        let node = CodeNode::new("a", "a", NodeKind::Function, "a.rs");
+        store
+            .update_file("a.rs", std::slice::from_ref(&node), 1)
+            .unwrap();
+
+        store.set_embedding_model("model-a").unwrap();
+        store
+            .set_embeddings(&[(node.id.clone(), 7, vec![0.6, 0.8])])
+            .unwrap();
+        assert_eq!(
+            store.get_embedding(&node.id).unwrap(),
+            Some((7, vec![0.6, 0.8]))
+        );
+
+        // Same model keeps vectors; a different one drops them.
+        store.set_embedding_model("model-a").unwrap();
+        assert!(store.get_embedding(&node.id).unwrap().is_some());
+        store.set_embedding_model("model-b").unwrap();
+        assert!(store.get_embedding(&node.id).unwrap().is_none());
+
+        store
+            .set_embeddings(&[(node.id.clone(), 7, vec![1.0, 0.0])])
+            .unwrap();
+        store.remove_file("a.rs").unwrap();
+        assert!(store.get_embedding(&node.id).unwrap().is_none());
+    }
+
     #[test]
     fn test_list_cached_files() {
         let dir = tempdir().unwrap();
@@ -1,6 +1,6 @@
 use crate::graph::NodeId;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// A global symbol table for resolving cross-file references.
 ///
@@ -8,8 +8,10 @@ use std::path::PathBuf;
 /// Example FQN: "arbor::graph::SymbolTable" -> NodeId(42)
 #[derive(Debug, Default, Clone)]
 pub struct SymbolTable {
-    /// Map of FQN to NodeId
-    by_fqn: HashMap<String, NodeId>,
+    /// Map of FQN to the nodes defining it, with their files, in
+    /// insertion order. Generated code and the contract it comes from,
+    /// or two languages in one repo, can define the same FQN.
+    by_fqn: HashMap<String, Vec<(NodeId, PathBuf)>>,
 
     /// Map of File Path to list of exported symbols (FQNs)
     /// Used to resolve wildcard imports or find all symbols in a file.
@@ -28,13 +30,57 @@ impl SymbolTable {
     /// * `id` - The Node ID in the graph
     /// * `file` - The file path defining this symbol
     pub fn insert(&mut self, fqn: String, id: NodeId, file: PathBuf) {
-        self.by_fqn.insert(fqn.clone(), id);
+        self.by_fqn
+            .entry(fqn.clone())
+            .or_default()
+            .push((id, file.clone()));
         self.exports_by_file.entry(file).or_default().push(fqn);
     }
 
     /// Resolves a Fully Qualified Name to a Node ID.
+    ///
+    /// If several nodes define it, the last one registered wins.
     pub fn resolve(&self, fqn: &str) -> Option<NodeId> {
-        self.by_fqn.get(fqn).copied()
+        self.by_fqn.get(fqn)?.last().map(|(id, _)| *id)
+    }
+
+    /// Resolves a Fully Qualified Name defined in `file` itself.
+    pub fn resolve_in_file(&self, fqn: &str, file: &Path) -> Option<NodeId> {
+        self.by_fqn
+            .get(fqn)?
+            .iter()
+            .find(|(_, defined_in)| defined_in == file)
+            .map(|(id, _)| *id)
+    }
+
+    /// Resolves a Fully Qualified Name as seen from `file`: a definition
+    /// in the file itself, else the only one in its directory, else the
+    /// last one registered.
+    pub fn resolve_from(&self, fqn: &str, file: &Path) -> Option<NodeId> {
+        let definitions = self.by_fqn.get(fqn)?;
+        if let Some(id) = self.resolve_in_file(fqn, file) {
+            return Some(id);
+        }
+        let mut same_dir = definitions
+            .iter()
+            .filter(|(_, defined_in)| defined_in.parent() == file.parent());
+        match (same_dir.next(), same_dir.next()) {
+            (Some((id, _)), None) => Some(*id),
+            _ => definitions.last().map(|(id, _)| *id),
+        }
+    }
+
+    /// Every definition whose FQN is `name` or ends with it after a `.`
+    /// or `:`, i.e. everything a reference to `name` could mean.
+    pub fn matches<'a>(&'a self, name: &'a str) -> impl Iterator<Item = (NodeId, &'a Path)> {
+        self.by_fqn
+            .iter()
+            .filter(move |(fqn, _)| {
+                fqn.strip_suffix(name)
+                    .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with(['.', ':']))
+            })
+            .flat_map(|(_, definitions)| definitions.iter())
+            .map(|(id, file)| (*id, file.as_path()))
     }
 
     /// Returns all symbols exported by a file.
@@ -58,51 +104,28 @@ impl SymbolTable {
     /// Returns None if:
     /// - No match found
     /// - Multiple matches exist and none are in the same directory (ambiguous)
-    pub fn resolve_with_context(
-        &self,
-        name: &str,
-        context_file: &std::path::Path,
-    ) -> Option<NodeId> {
+    pub fn resolve_with_context(&self, name: &str, context_file: &Path) -> Option<NodeId> {
         // 1. Try exact match first
-        if let Some(id) = self.by_fqn.get(name) {
-            return Some(*id);
+        if let Some(id) = self.resolve(name) {
+            return Some(id);
         }
 
         // 2. Suffix match
         let context_dir = context_file.parent();
-        let mut candidates: Vec<(&String, NodeId, bool)> = Vec::new();
-
-        for (fqn, &id) in &self.by_fqn {
-            // Check if FQN ends with the name (with separator)
-            if fqn.ends_with(name) {
-                // Ensure it's a proper suffix (preceded by separator or start)
-                let prefix_len = fqn.len() - name.len();
-                if prefix_len == 0
-                    || fqn.chars().nth(prefix_len - 1) == Some('.')
-                    || fqn.chars().nth(prefix_len - 1) == Some(':')
-                {
-                    // Check if in same directory
-                    let same_dir = self
-                        .exports_by_file
-                        .iter()
-                        .find(|(_, exports)| exports.contains(fqn))
-                        .map(|(file, _)| file.parent() == context_dir)
-                        .unwrap_or(false);
-
-                    candidates.push((fqn, id, same_dir));
-                }
-            }
-        }
+        let candidates: Vec<(NodeId, bool)> = self
+            .matches(name)
+            .map(|(id, file)| (id, file.parent() == context_dir))
+            .collect();
 
         match candidates.len() {
             0 => None,
-            1 => Some(candidates[0].1),
+            1 => Some(candidates[0].0),
             _ => {
                 // Multiple candidates: only resolve if exactly one is in same directory
                 let same_dir_candidates: Vec<_> =
-                    candidates.iter().filter(|(_, _, same)| *same).collect();
+                    candidates.iter().filter(|(_, same)| *same).collect();
                 if same_dir_candidates.len() == 1 {
-                    Some(same_dir_candidates[0].1)
+                    Some(same_dir_candidates[0].0)
                 } else {
                     // Ambiguous: don't auto-link
                     None
@@ -208,4 +231,65 @@ mod tests {
         let result = table.resolve_with_context("helper", &PathBuf::from("src/b/caller.rs"));
         assert_eq!(result, Some(id2));
     }
+
+    #[test]
+    fn test_resolve_in_file_prefers_the_file_itself() {
+        let mut table = SymbolTable::new();
+        let proto = NodeId::new(1);
+        let generated = NodeId::new(2);
+        table.insert("User".to_string(), proto, PathBuf::from("api/users.proto"));
+        table.insert(
+            "User".to_string(),
+            generated,
+            PathBuf::from("api/users.pb.go"),
+        );
+
+        assert_eq!(table.resolve("User"), Some(generated));
+        assert_eq!(
+            table.resolve_in_file("User", &PathBuf::from("api/users.proto")),
+            Some(proto)
+        );
+        assert_eq!(
+            table.resolve_in_file("User", &PathBuf::from("api/other.proto")),
+            None
+        );
+        assert_eq!(
+            table.resolve_from("User", &PathBuf::from("api/other.proto")),
+            Some(generated)
+        );
+        assert_eq!(
+            table.resolve_from("User", &PathBuf::from("web/users.ts")),
+            Some(generated)
+        );
+    }
+
+    #[test]
+    fn test_matches_counts_every_definition() {
+        let mut table = SymbolTable::new();
+        table.insert(
+            "helper".to_string(),
+            NodeId::new(1),
+            PathBuf::from("a/x.go"),
+        );
+        table.insert(
+            "helper".to_string(),
+            NodeId::new(2),
+            PathBuf::from("b/y.php"),
+        );
+        table.insert(
+            "util::helper".to_string(),
+            NodeId::new(3),
+            PathBuf::from("c/z.rs"),
+        );
+        table.insert(
+            "run_helper".to_string(),
+            NodeId::new(4),
+            PathBuf::from("d/w.py"),
+        );
+
+        let mut ids: Vec<NodeId> = table.matches("helper").map(|(id, _)| id).collect();
+        ids.sort();
+        assert_eq!(ids, [NodeId::new(1), NodeId::new(2), NodeId::new(3)]);
+        assert_eq!(table.matches("util::helper").count(), 1);
+    }
 }
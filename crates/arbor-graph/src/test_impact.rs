@@ -0,0 +1,405 @@
+//! Test impact analysis.
+//!
+//! Walks the call graph upstream from changed symbols until it hits test
+//! functions, so CI can run only the tests that exercise a change. Results
+//! can be rendered as filters for cargo, pytest, `go test` and jest.
+//!
+//! Only calls, references and type uses lead to a test. Containment and
+//! imports would select every test of a file whose module imports the
+//! changed symbol's file, whether or not it runs the changed code.
+
+use crate::edge::EdgeKind;
+use crate::graph::{ArborGraph, NodeId};
+use crate::query::NodeInfo;
+use arbor_core::NodeKind;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// A test runner whose test selection syntax we can emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestRunner {
+    /// `cargo test -- <name>...`
+    Cargo,
+    /// `pytest <node id>...`
+    Pytest,
+    /// `go test ./... -run <regex>`
+    Go,
+    /// `jest --testPathPattern <regex>`
+    Jest,
+}
+
+impl TestRunner {
+    /// The runner for a source file, by extension.
+    pub fn for_file(file: &str) -> Option<Self> {
+        let ext = Path::new(file).extension()?.to_str()?;
+        match ext {
+            "rs" => Some(Self::Cargo),
+            "py" => Some(Self::Pytest),
+            "go" => Some(Self::Go),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Some(Self::Jest),
+            _ => None,
+        }
+    }
+
+    /// Parses a runner name as used on the command line.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cargo" | "rust" => Some(Self::Cargo),
+            "pytest" | "python" => Some(Self::Pytest),
+            "go" => Some(Self::Go),
+            "jest" | "js" | "ts" => Some(Self::Jest),
+            _ => None,
+        }
+    }
+
+    /// The command that runs only the selected tests.
+    pub fn command(&self, filter: &str) -> String {
+        match self {
+            Self::Cargo => format!("cargo test -- {}", filter),
+            Self::Pytest => format!("pytest {}", filter),
+            Self::Go => format!("go test ./... -run '{}'", filter),
+            Self::Jest => format!("jest --testPathPattern '{}'", filter),
+        }
+    }
+}
+
+impl std::fmt::Display for TestRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Cargo => "cargo",
+            Self::Pytest => "pytest",
+            Self::Go => "go",
+            Self::Jest => "jest",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Edges a change travels along to reach the tests that exercise it.
+const TEST_IMPACT_EDGES: &[EdgeKind] = &[EdgeKind::Calls, EdgeKind::References, EdgeKind::UsesType];
+
+/// A test that exercises changed code.
+#[derive(Debug, Clone, Serialize)]
+pub struct AffectedTest {
+    #[serde(flatten)]
+    pub node_info: NodeInfo,
+    pub runner: Option<TestRunner>,
+    /// Call hops to the nearest changed symbol (0 if the test itself changed).
+    pub hops: usize,
+    /// Qualified names of the changed symbols it reaches.
+    pub covers: Vec<String>,
+}
+
+/// Result of [`ArborGraph::affected_tests`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TestImpact {
+    pub changed_symbols: usize,
+    /// Affected tests, by runner, file and line.
+    pub tests: Vec<AffectedTest>,
+    /// Changed functions and methods no test reaches.
+    pub uncovered: Vec<NodeInfo>,
+}
+
+impl TestImpact {
+    /// Runners with at least one affected test.
+    pub fn runners(&self) -> Vec<TestRunner> {
+        let runners: BTreeSet<TestRunner> = self.tests.iter().filter_map(|t| t.runner).collect();
+        runners.into_iter().collect()
+    }
+
+    /// The affected tests as a filter for `runner`, ready to pass to
+    /// [`TestRunner::command`]. Paths are made relative to `root`.
+    ///
+    /// Returns `None` when no affected test belongs to that runner.
+    pub fn filter(&self, runner: TestRunner, root: &Path) -> Option<String> {
+        let tests: Vec<&AffectedTest> = self
+            .tests
+            .iter()
+            .filter(|t| t.runner == Some(runner))
+            .collect();
+        if tests.is_empty() {
+            return None;
+        }
+
+        let selectors: BTreeSet<String> = tests
+            .iter()
+            .map(|t| {
+                let info = &t.node_info;
+                match runner {
+                    TestRunner::Cargo | TestRunner::Go => info.name.clone(),
+                    TestRunner::Pytest => format!(
+                        "{}::{}",
+                        relative(&info.file, root),
+                        info.qualified_name.replace('.', "::")
+                    ),
+                    TestRunner::Jest => regex_escape(&relative(&info.file, root)),
+                }
+            })
+            .collect();
+        let selectors: Vec<String> = selectors.into_iter().collect();
+
+        Some(match runner {
+            TestRunner::Cargo | TestRunner::Pytest => selectors.join(" "),
+            TestRunner::Go => format!("^({})$", selectors.join("|")),
+            TestRunner::Jest => selectors.join("|"),
+        })
+    }
+}
+
+impl ArborGraph {
+    /// Finds the tests that reach any of the `changed` nodes through
+    /// callers, references and type uses, at most `max_depth` hops away
+    /// (0 = unlimited).
+    pub fn affected_tests(&self, changed: &[NodeId], max_depth: usize) -> TestImpact {
+        let test_code = self.test_code();
+        let max_depth = if max_depth == 0 {
+            usize::MAX
+        } else {
+            max_depth
+        };
+
+        let mut reached: HashMap<NodeId, (usize, BTreeSet<String>)> = HashMap::new();
+        let mut uncovered = Vec::new();
+        for &start in changed {
+            let Some(changed_node) = self.get(start) else {
+                continue;
+            };
+            let mut found = false;
+            let mut visited = HashSet::from([start]);
+            let mut queue = VecDeque::from([(start, 0usize)]);
+            while let Some((current, hops)) = queue.pop_front() {
+                if test_code.contains(&current) && self.is_test_entry(current, &test_code) {
+                    found = true;
+                    let entry = reached
+                        .entry(current)
+                        .or_insert_with(|| (hops, BTreeSet::new()));
+                    entry.0 = entry.0.min(hops);
+                    entry.1.insert(changed_node.qualified_name.clone());
+                    continue;
+                }
+                if hops >= max_depth {
+                    continue;
+                }
+                let callers = self
+                    .graph
+                    .edges_directed(current, Direction::Incoming)
+                    .filter(|edge| TEST_IMPACT_EDGES.contains(&edge.weight().kind))
+                    .map(|edge| edge.source());
+                for caller in callers {
+                    if visited.insert(caller) {
+                        queue.push_back((caller, hops + 1));
+                    }
+                }
+            }
+
+            if !found
+                && !test_code.contains(&start)
+                && matches!(
+                    changed_node.kind,
+                    NodeKind::Function | NodeKind::Method | NodeKind::Constructor
+                )
+            {
+                uncovered.push(NodeInfo::from(changed_node));
+            }
+        }
+
+        let mut tests: Vec<AffectedTest> = reached
+            .into_iter()
+            .map(|(index, (hops, covers))| {
+                let node = &self.graph[index];
+                AffectedTest {
+                    node_info: NodeInfo::from(node),
+                    runner: TestRunner::for_file(&node.file),
+                    hops,
+                    covers: covers.into_iter().collect(),
+                }
+            })
+            .collect();
+        tests.sort_by(|a, b| {
+            a.runner
+                .cmp(&b.runner)
+                .then_with(|| a.node_info.file.cmp(&b.node_info.file))
+                .then_with(|| a.node_info.line_start.cmp(&b.node_info.line_start))
+                .then_with(|| a.node_info.name.cmp(&b.node_info.name))
+        });
+        uncovered.sort_by(|a, b| (&a.file, a.line_start).cmp(&(&b.file, b.line_start)));
+
+        TestImpact {
+            changed_symbols: changed.len(),
+            tests,
+            uncovered,
+        }
+    }
+
+    /// Whether a test-code node is a test the runner executes, rather than a
+    /// helper or fixture.
+    fn is_test_entry(&self, index: NodeId, test_code: &HashSet<NodeId>) -> bool {
+        let node = &self.graph[index];
+        if !matches!(node.kind, NodeKind::Function | NodeKind::Method) {
+            return false;
+        }
+        match TestRunner::for_file(&node.file) {
+            Some(TestRunner::Go) => node.name.starts_with("Test"),
+            Some(TestRunner::Pytest) => node.name.starts_with("test"),
+            // jest selects whole files, and its tests are anonymous callbacks.
+            Some(TestRunner::Jest) => true,
+            // `#[test]` attributes aren't extracted, so a test is a test
+            // function nothing else in the tests calls.
+            Some(TestRunner::Cargo) | None => {
+                node.name.starts_with("test_") || !self.called_from_tests(index, test_code)
+            }
+        }
+    }
+
+    fn called_from_tests(&self, index: NodeId, test_code: &HashSet<NodeId>) -> bool {
+        self.graph
+            .neighbors_directed(index, Direction::Incoming)
+            .any(|caller| test_code.contains(&caller))
+    }
+}
+
+fn relative(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn regex_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edge::{Edge, EdgeKind};
+    use arbor_core::CodeNode;
+
+    fn function(name: &str, qualified: &str, file: &str) -> CodeNode {
+        CodeNode::new(name, qualified, NodeKind::Function, file).with_lines(1, 3)
+    }
+
+    fn calls(graph: &mut ArborGraph, from: NodeId, to: NodeId) {
+        graph.add_edge(from, to, Edge::new(EdgeKind::Calls));
+    }
+
+    #[test]
+    fn test_walks_through_helpers_to_tests() {
+        let mut graph = ArborGraph::new();
+        let parse = graph.add_node(function("parse", "parse", "/repo/src/parser.rs"));
+        let load = graph.add_node(function("load", "load", "/repo/src/loader.rs"));
+        let untested = graph.add_node(function("flush", "flush", "/repo/src/parser.rs"));
+        let helper = graph.add_node(function("fixture", "fixture", "/repo/tests/common.rs"));
+        let test = graph.add_node(function(
+            "parses_empty",
+            "parses_empty",
+            "/repo/tests/parse.rs",
+        ));
+        let other = graph.add_node(function("test_load", "test_load", "/repo/src/loader.rs"));
+        calls(&mut graph, load, parse);
+        calls(&mut graph, helper, load);
+        calls(&mut graph, test, helper);
+        calls(&mut graph, other, load);
+
+        let impact = graph.affected_tests(&[parse, untested], 0);
+        let names: Vec<&str> = impact
+            .tests
+            .iter()
+            .map(|t| t.node_info.name.as_str())
+            .collect();
+        assert_eq!(names, ["test_load", "parses_empty"]);
+        assert_eq!(impact.tests[0].hops, 2);
+        assert_eq!(impact.tests[1].hops, 3);
+        assert_eq!(impact.tests[1].covers, ["parse"]);
+        assert_eq!(impact.uncovered.len(), 1);
+        assert_eq!(impact.uncovered[0].name, "flush");
+
+        let filter = impact
+            .filter(TestRunner::Cargo, Path::new("/repo"))
+            .unwrap();
+        assert_eq!(filter, "parses_empty test_load");
+        assert!(impact.filter(TestRunner::Go, Path::new("/repo")).is_none());
+
+        // Too far away once depth is bounded.
+        assert!(graph.affected_tests(&[parse], 2).tests.len() == 1);
+    }
+
+    #[test]
+    fn test_containment_and_imports_do_not_select_tests() {
+        let mut graph = ArborGraph::new();
+        let module = graph.add_node(
+            CodeNode::new(
+                "billing",
+                "billing",
+                NodeKind::Module,
+                "/repo/src/billing.rs",
+            )
+            .with_lines(1, 40),
+        );
+        let charge = graph.add_node(function("charge", "charge", "/repo/src/billing.rs"));
+        let test = graph.add_node(function(
+            "test_refund",
+            "test_refund",
+            "/repo/tests/billing.rs",
+        ));
+        graph.add_edge(module, charge, Edge::new(EdgeKind::Contains));
+        graph.add_edge(test, module, Edge::new(EdgeKind::Imports));
+
+        let impact = graph.affected_tests(&[charge], 0);
+        assert!(impact.tests.is_empty());
+        assert_eq!(impact.uncovered.len(), 1);
+    }
+
+    #[test]
+    fn test_runner_filters() {
+        let mut graph = ArborGraph::new();
+        let target = graph.add_node(function("total", "total", "/repo/pkg/cart.go"));
+        let mut callers = vec![
+            function("TestTotal", "TestTotal", "/repo/pkg/cart_test.go"),
+            function("helper", "helper", "/repo/pkg/cart_test.go"),
+            function(
+                "test_total",
+                "TestCart.test_total",
+                "/repo/tests/test_cart.py",
+            ),
+            function("renders", "renders", "/repo/src/cart.test.tsx"),
+        ];
+        callers[2].kind = NodeKind::Method;
+        for caller in callers {
+            let index = graph.add_node(caller);
+            calls(&mut graph, index, target);
+        }
+
+        let impact = graph.affected_tests(&[target], 0);
+        let root = Path::new("/repo");
+        assert_eq!(
+            impact.runners(),
+            [TestRunner::Pytest, TestRunner::Go, TestRunner::Jest]
+        );
+        assert_eq!(
+            impact.filter(TestRunner::Go, root).unwrap(),
+            "^(TestTotal)$"
+        );
+        assert_eq!(
+            impact.filter(TestRunner::Pytest, root).unwrap(),
+            "tests/test_cart.py::TestCart::test_total"
+        );
+        assert_eq!(
+            impact.filter(TestRunner::Jest, root).unwrap(),
+            "src/cart\\.test\\.tsx"
+        );
+        assert!(impact.uncovered.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+//! Message topics between publishers and subscribers.
+//!
+//! The parsers record the topics a node publishes to or subscribes to
+//! ([`CodeNode::wiring`]). [`topic_links`] gathers them by name, and the
+//! builder adds one [`NodeKind::Topic`] node per name with a
+//! `publisher → topic` [`EdgeKind::Publishes`](crate::EdgeKind::Publishes)
+//! edge and a `topic → subscriber`
+//! [`EdgeKind::Subscribes`](crate::EdgeKind::Subscribes) edge, so impact
+//! analysis follows a message across the broker as it follows a call.
+
+use crate::graph::{ArborGraph, NodeId};
+use arbor_core::{CodeNode, NodeKind, Visibility, Wiring};
+use std::collections::BTreeMap;
+
+/// Nodes on one side of a topic, with the call or annotation each uses.
+type Sites = Vec<(NodeId, String)>;
+
+/// A topic and the nodes on either side of it.
+pub(crate) struct TopicLinks {
+    pub node: CodeNode,
+    pub publishers: Sites,
+    pub subscribers: Sites,
+}
+
+/// One [`TopicLinks`] per topic name the graph's nodes use, by name.
+pub(crate) fn topic_links(graph: &ArborGraph) -> Vec<TopicLinks> {
+    let mut topics: BTreeMap<&str, (Sites, Sites)> = BTreeMap::new();
+    for index in graph.node_indexes() {
+        let node = &graph.graph[index];
+        // Overloads share an id; link the indexed copy.
+        let site = graph.get_index(&node.id).unwrap_or(index);
+        for wiring in &node.wiring {
+            let (topic, call, subscribes) = match wiring {
+                Wiring::Publishes { topic, call } => (topic, call, false),
+                Wiring::Subscribes { topic, call } => (topic, call, true),
+                _ => continue,
+            };
+            let (publishers, subscribers) = topics.entry(topic.as_str()).or_default();
+            let sites = if subscribes { subscribers } else { publishers };
+            if !sites.iter().any(|(s, _)| *s == site) {
+                sites.push((site, call.clone()));
+            }
+        }
+    }
+
+    topics
+        .into_iter()
+        .map(|(name, (publishers, subscribers))| {
+            let first = publishers
+                .iter()
+                .chain(&subscribers)
+                .map(|&(site, _)| &graph.graph[site])
+                .min_by(|a, b| (&a.file, a.line_start).cmp(&(&b.file, b.line_start)))
+                .expect("a topic has a publisher or subscriber");
+            TopicLinks {
+                node: topic_node(name, &first.file, first.line_start),
+                publishers,
+                subscribers,
+            }
+        })
+        .collect()
+}
+
+/// A topic node, located at its first use. Its ID depends only on the
+/// name, so the topic keeps it as the code around it changes.
+fn topic_node(name: &str, file: &str, line: u32) -> CodeNode {
+    let mut node = CodeNode::new(name, name, NodeKind::Topic, file)
+        .with_lines(line, line)
+        .with_visibility(Visibility::Public);
+    node.id = CodeNode::compute_id("", name, NodeKind::Topic);
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EdgeKind, GraphBuilder};
+    use arbor_core::{CodeNode, NodeKind, Wiring};
+
+    fn function(name: &str, file: &str, line: u32, wiring: Vec<Wiring>) -> CodeNode {
+        let mut node =
+            CodeNode::new(name, name, NodeKind::Function, file).with_lines(line, line + 5);
+        node.wiring = wiring;
+        node
+    }
+
+    fn publishes(topic: &str) -> Wiring {
+        Wiring::Publishes {
+            topic: topic.to_string(),
+            call: "producer.send".to_string(),
+        }
+    }
+
+    fn subscribes(topic: &str) -> Wiring {
+        Wiring::Subscribes {
+            topic: topic.to_string(),
+            call: "@KafkaListener".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_impact_crosses_topics() {
+        let mut checkout = function("checkout", "orders/api.ts", 3, Vec::new());
+        checkout.references = vec!["place_order".into()];
+        let place = function(
+            "place_order",
+            "orders/service.ts",
+            10,
+            vec![publishes("orders.created")],
+        );
+        let on_order = function(
+            "on_order",
+            "billing/Listener.java",
+            20,
+            vec![subscribes("orders.created"), subscribes("orders.cancelled")],
+        );
+        let audit = function(
+            "audit",
+            "audit/log.py",
+            1,
+            vec![subscribes("orders.created")],
+        );
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![checkout, place, on_order, audit]);
+        let graph = builder.build();
+
+        let topics: Vec<(&str, &str)> = graph
+            .nodes()
+            .filter(|n| n.kind == NodeKind::Topic)
+            .map(|n| (n.name.as_str(), n.file.as_str()))
+            .collect();
+        assert_eq!(
+            topics,
+            [
+                ("orders.cancelled", "billing/Listener.java"),
+                ("orders.created", "audit/log.py"),
+            ]
+        );
+
+        let on_order = graph
+            .get_index(&graph.find_by_name("on_order")[0].id)
+            .unwrap();
+        let impact = graph.analyze_impact(on_order, 5);
+        let upstream: Vec<(&str, EdgeKind)> = impact
+            .upstream
+            .iter()
+            .map(|n| (n.node_info.name.as_str(), n.entry_edge))
+            .collect();
+        assert!(upstream.contains(&("orders.created", EdgeKind::Subscribes)));
+        assert!(upstream.contains(&("place_order", EdgeKind::Subscribes)));
+        assert!(upstream.contains(&("checkout", EdgeKind::Subscribes)));
+
+        let place = graph
+            .get_index(&graph.find_by_name("place_order")[0].id)
+            .unwrap();
+        let downstream: Vec<String> = graph
+            .analyze_impact(place, 5)
+            .downstream
+            .iter()
+            .map(|n| n.node_info.name.clone())
+            .collect();
+        assert!(downstream.contains(&"audit".to_string()));
+        assert!(downstream.contains(&"on_order".to_string()));
+        assert!(!downstream.contains(&"orders.cancelled".to_string()));
+
+        // Rebuilding from the graph's own nodes does not duplicate topics.
+        let mut rebuilt = GraphBuilder::new();
+        rebuilt.add_nodes(graph.nodes().cloned().collect());
+        let rebuilt = rebuilt.build();
+        assert_eq!(rebuilt.find_by_name("orders.created").len(), 1);
+    }
+}
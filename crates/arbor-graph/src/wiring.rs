@@ -0,0 +1,353 @@
+//! Callback and dependency-injection edges.
+//!
+//! The parsers record functions passed by name to other calls and the
+//! types a DI container injects or provides ([`CodeNode::wiring`]).
+//! [`wiring_edges`] resolves both to uncertain edges between real nodes:
+//!
+//! - a callback links the function that registers it to the function
+//!   passed, since the registry may call it at any time;
+//! - an injection links the consumer to each provider of the injected
+//!   type: an explicit binding (`{ provide: Store, useClass: PgStore }`,
+//!   `registerSingleton<Store>(PgStore())`), or else any subtype of the
+//!   type that the container manages (`@Repository class PgStore
+//!   implements Store`).
+
+use crate::edge::EdgeKind;
+use crate::graph::{ArborGraph, NodeId};
+use crate::heuristics::{UncertainEdge, UncertainEdgeKind};
+use crate::hierarchy::{resolve_type, subtypes};
+use arbor_core::{NodeKind, Wiring};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Registration calls whose callbacks fire on events.
+const EVENT_REGISTRATIONS: &[&str] = &[
+    "addEventListener",
+    "addListener",
+    "on",
+    "once",
+    "subscribe",
+    "listen",
+    "connect",
+];
+
+/// A function registered for an event.
+const EVENT_HANDLER: f32 = 0.8;
+/// A function passed to any other call.
+const CALLBACK: f32 = 0.7;
+/// The injected type has a single provider.
+const SINGLE_PROVIDER: f32 = 0.9;
+/// The container picks one of several providers.
+const SEVERAL_PROVIDERS: f32 = 0.6;
+
+/// Uncertain edges for the callbacks and injections recorded on nodes.
+pub(crate) fn wiring_edges(graph: &ArborGraph) -> Vec<UncertainEdge> {
+    let providers = providers(graph);
+
+    // (from, to) → strongest candidate edge
+    let mut edges: BTreeMap<(NodeId, NodeId), (UncertainEdgeKind, f32, String)> = BTreeMap::new();
+    let mut add =
+        |from: NodeId, to: NodeId, kind: UncertainEdgeKind, confidence: f32, reason: String| {
+            if from == to {
+                return;
+            }
+            let entry =
+                edges
+                    .entry((from, to))
+                    .or_insert((kind.clone(), confidence, reason.clone()));
+            if confidence > entry.1 {
+                *entry = (kind, confidence, reason);
+            }
+        };
+
+    for site in graph.node_indexes() {
+        let node = &graph.graph[site];
+        for wiring in &node.wiring {
+            match wiring {
+                Wiring::Callback { call, function } => {
+                    let Some(target) = resolve_function(graph, site, function) else {
+                        continue;
+                    };
+                    let (kind, confidence) = if EVENT_REGISTRATIONS.contains(&call.as_str()) {
+                        (UncertainEdgeKind::EventHandler, EVENT_HANDLER)
+                    } else {
+                        (UncertainEdgeKind::Callback, CALLBACK)
+                    };
+                    add(
+                        site,
+                        target,
+                        kind,
+                        confidence,
+                        format!("'{}' passes '{}' to '{}'", node.name, function, call),
+                    );
+                }
+                Wiring::Injects {
+                    type_name,
+                    container,
+                } => {
+                    let candidates = injected(graph, &providers, type_name, &node.file);
+                    let confidence = if candidates.len() == 1 {
+                        SINGLE_PROVIDER
+                    } else {
+                        SEVERAL_PROVIDERS
+                    };
+                    for provider in candidates {
+                        add(
+                            site,
+                            provider,
+                            UncertainEdgeKind::DependencyInjection,
+                            confidence,
+                            format!(
+                                "'{}' gets a '{}' from {}, which '{}' provides",
+                                node.name, type_name, container, graph.graph[provider].name
+                            ),
+                        );
+                    }
+                }
+                Wiring::Provides { .. } | Wiring::Publishes { .. } | Wiring::Subscribes { .. } => {}
+            }
+        }
+    }
+
+    edges
+        .into_iter()
+        .map(|((from, to), (kind, confidence, reason))| UncertainEdge {
+            from: graph.graph[from].id.clone(),
+            to: graph.graph[to].id.clone(),
+            kind,
+            confidence,
+            reason,
+        })
+        .collect()
+}
+
+/// The function a callback names, unless the site already calls it:
+/// `Owner.name` by qualified name, a bare name preferring the site's own
+/// type, then its file, then the only match in its directory or overall.
+fn resolve_function(graph: &ArborGraph, site: NodeId, function: &str) -> Option<NodeId> {
+    let node = &graph.graph[site];
+    let name = function.rsplit('.').next().unwrap_or(function);
+    let candidates: Vec<NodeId> = graph
+        .find_by_name(name)
+        .into_iter()
+        .filter(|n| matches!(n.kind, NodeKind::Function | NodeKind::Method))
+        .filter(|n| {
+            !function.contains('.')
+                || n.qualified_name == function
+                || n.qualified_name.ends_with(&format!(".{}", function))
+        })
+        .filter_map(|n| graph.get_index(&n.id))
+        .filter(|&n| n != site)
+        .collect();
+
+    let owner = |qualified: &str| {
+        qualified
+            .rsplit_once('.')
+            .map(|(owner, _)| owner.to_string())
+    };
+    let dir = Path::new(&node.file).parent();
+    let same_dir: Vec<NodeId> = candidates
+        .iter()
+        .copied()
+        .filter(|&c| Path::new(&graph.graph[c].file).parent() == dir)
+        .collect();
+    let target = candidates
+        .iter()
+        .copied()
+        .find(|&c| {
+            let candidate = &graph.graph[c];
+            candidate.file == node.file
+                && owner(&node.qualified_name).is_some()
+                && owner(&candidate.qualified_name) == owner(&node.qualified_name)
+        })
+        .or_else(|| {
+            candidates
+                .iter()
+                .copied()
+                .find(|&c| graph.graph[c].file == node.file)
+        })
+        .or_else(|| (same_dir.len() == 1).then(|| same_dir[0]))
+        .or_else(|| (candidates.len() == 1).then(|| candidates[0]))?;
+
+    let called = graph
+        .graph
+        .edges_directed(site, Direction::Outgoing)
+        .any(|e| e.weight().kind == EdgeKind::Calls && e.target() == target);
+    (!called).then_some(target)
+}
+
+/// Nodes that provide each type name: the bound implementation when it
+/// resolves to a type, else the registering node itself.
+fn providers(graph: &ArborGraph) -> HashMap<&str, Vec<NodeId>> {
+    let mut providers: HashMap<&str, Vec<NodeId>> = HashMap::new();
+    for index in graph.node_indexes() {
+        let node = &graph.graph[index];
+        for wiring in &node.wiring {
+            let Wiring::Provides {
+                type_name,
+                implementation,
+                ..
+            } = wiring
+            else {
+                continue;
+            };
+            let provider = implementation
+                .as_deref()
+                .and_then(|i| resolve_type(graph, i, &node.file))
+                .unwrap_or(index);
+            let entry = providers.entry(type_name.as_str()).or_default();
+            if !entry.contains(&provider) {
+                entry.push(provider);
+            }
+        }
+    }
+    providers
+}
+
+/// Providers a container may inject for `type_name`: its explicit
+/// bindings, or else the managed subtypes of the type.
+fn injected(
+    graph: &ArborGraph,
+    providers: &HashMap<&str, Vec<NodeId>>,
+    type_name: &str,
+    file: &str,
+) -> Vec<NodeId> {
+    let bound = providers.get(type_name).cloned().unwrap_or_default();
+    if !bound.is_empty() {
+        return bound;
+    }
+    let Some(declared) = resolve_type(graph, type_name, file) else {
+        return Vec::new();
+    };
+    subtypes(graph, declared)
+        .into_iter()
+        .filter(|&s| {
+            providers
+                .get(graph.graph[s].name.as_str())
+                .is_some_and(|p| p.contains(&s))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GraphBuilder, UncertainEdgeKind};
+    use arbor_core::{CodeNode, NodeKind, Wiring};
+    use petgraph::Direction;
+
+    fn node(name: &str, qualified: &str, kind: NodeKind, file: &str) -> CodeNode {
+        CodeNode::new(name, qualified, kind, file)
+    }
+
+    fn injects(type_name: &str) -> Wiring {
+        Wiring::Injects {
+            type_name: type_name.to_string(),
+            container: "spring".to_string(),
+        }
+    }
+
+    fn provides(type_name: &str, implementation: Option<&str>) -> Wiring {
+        Wiring::Provides {
+            type_name: type_name.to_string(),
+            implementation: implementation.map(str::to_string),
+            container: "spring".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_callbacks_link_the_registering_site() {
+        let mut setup = node("setup", "setup", NodeKind::Function, "src/app.js");
+        setup.wiring = vec![
+            Wiring::Callback {
+                call: "addEventListener".into(),
+                function: "onClick".into(),
+            },
+            Wiring::Callback {
+                call: "then".into(),
+                function: "render".into(),
+            },
+            // Not a function: no edge.
+            Wiring::Callback {
+                call: "save".into(),
+                function: "user".into(),
+            },
+        ];
+        let on_click = node("onClick", "onClick", NodeKind::Function, "src/app.js");
+        let render = node("render", "render", NodeKind::Function, "src/view.js");
+        let other = node("render", "render", NodeKind::Function, "lib/other.js");
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![setup, on_click, render, other]);
+        let graph = builder.build();
+
+        let render_id = graph
+            .find_by_name("render")
+            .into_iter()
+            .find(|n| n.file == "src/view.js")
+            .and_then(|n| graph.get_index(&n.id))
+            .unwrap();
+        let callers = graph.uncertain_edges_of(render_id, Direction::Incoming);
+        assert_eq!(callers.len(), 1);
+        assert_eq!(graph.get(callers[0].0).unwrap().name, "setup");
+        assert_eq!(callers[0].1.kind, UncertainEdgeKind::Callback);
+        assert_eq!(callers[0].1.reason, "'setup' passes 'render' to 'then'");
+
+        let kinds: Vec<_> = graph
+            .uncertain_edges()
+            .iter()
+            .map(|e| e.kind.clone())
+            .collect();
+        assert_eq!(
+            kinds,
+            [UncertainEdgeKind::EventHandler, UncertainEdgeKind::Callback]
+        );
+    }
+
+    #[test]
+    fn test_injections_link_consumers_to_providers() {
+        let store = node("Store", "Store", NodeKind::Interface, "Store.java");
+        let mut pg = node("PgStore", "PgStore", NodeKind::Class, "PgStore.java");
+        pg.supertypes = vec!["Store".into()];
+        pg.wiring = vec![provides("PgStore", None)];
+        let mut mem = node("MemStore", "MemStore", NodeKind::Class, "MemStore.java");
+        mem.supertypes = vec!["Store".into()];
+        let clock = node("Clock", "Clock", NodeKind::Interface, "Clock.java");
+        let system = node(
+            "SystemClock",
+            "SystemClock",
+            NodeKind::Class,
+            "SystemClock.java",
+        );
+        let mut beans = node("clock", "Beans.clock", NodeKind::Method, "Beans.java");
+        beans.wiring = vec![provides("Clock", Some("SystemClock"))];
+        let mut checkout = node("Checkout", "Checkout", NodeKind::Class, "Checkout.java");
+        checkout.wiring = vec![injects("Store"), injects("Clock")];
+
+        let mut builder = GraphBuilder::new();
+        builder.add_nodes(vec![store, pg, mem, clock, system, beans, checkout]);
+        let graph = builder.build();
+
+        let checkout = graph
+            .get_index(&graph.find_by_name("Checkout")[0].id)
+            .unwrap();
+        let mut providers: Vec<(String, f32)> = graph
+            .uncertain_edges_of(checkout, Direction::Outgoing)
+            .into_iter()
+            .map(|(to, edge)| {
+                assert_eq!(edge.kind, UncertainEdgeKind::DependencyInjection);
+                (graph.get(to).unwrap().name.clone(), edge.confidence)
+            })
+            .collect();
+        providers.sort_by(|a, b| a.0.cmp(&b.0));
+        // MemStore implements Store but is not managed by the container.
+        assert_eq!(
+            providers,
+            [
+                ("PgStore".to_string(), 0.9),
+                ("SystemClock".to_string(), 0.9)
+            ]
+        );
+    }
+}
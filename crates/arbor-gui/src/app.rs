@@ -103,13 +103,21 @@ impl ArborApp {
         if let Some(graph) = &self.graph {
             let target = self.symbol_input.trim();
 
-            // Find the node
-            let node_idx = graph.get_index(target).or_else(|| {
-                graph
-                    .find_by_name(target)
-                    .first()
-                    .and_then(|n| graph.get_index(&n.id))
-            });
+            // Find the node: exact id, exact name, then best search hit
+            let node_idx = graph
+                .get_index(target)
+                .or_else(|| {
+                    graph
+                        .find_by_name(target)
+                        .first()
+                        .and_then(|n| graph.get_index(&n.id))
+                })
+                .or_else(|| {
+                    graph
+                        .search(target)
+                        .first()
+                        .and_then(|n| graph.get_index(&n.id))
+                });
 
             match node_idx {
                 Some(idx) => {
@@ -1,10 +1,17 @@
 use anyhow::Result;
+use petgraph::Direction;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arbor_graph::{changed_node_ids, compute_blast_radius, compute_centrality, HeuristicsMatcher};
+use arbor_graph::{
+    changed_node_ids, compute_blast_radius, compute_centrality, count_tokens, ArborGraph,
+    ChangeHistory, ContextSlice, DuplicateOptions, EmbeddingConfig, EmbeddingIndex,
+    EmbeddingProvider, GraphStore, HeuristicsMatcher, HistoryOptions, HistoryReport, NodeId,
+    NodeSource, Ownership, SourcePolicy, SourceReader, TruncationReason, EMBEDDINGS_CONFIG,
+    HISTORY_COMMITS,
+};
 use arbor_server::{SharedGraph, SyncServerHandle};
 
 mod apps;
@@ -52,6 +59,22 @@ pub struct McpServer {
     project_root: PathBuf,
     tasks: Arc<TaskManager>,
     negotiated_protocol: Arc<tokio::sync::RwLock<Option<String>>>,
+    embeddings: Arc<tokio::sync::Mutex<Option<EmbeddingCache>>>,
+    history: Arc<tokio::sync::Mutex<Option<HistoryCache>>>,
+}
+
+/// Embedding index built for a graph of a given size.
+struct EmbeddingCache {
+    graph_size: (usize, usize),
+    index: EmbeddingIndex,
+    provider: Box<dyn EmbeddingProvider>,
+}
+
+/// History report computed for a graph of a given size at a given `HEAD`.
+struct HistoryCache {
+    graph_size: (usize, usize),
+    head: Option<String>,
+    report: Option<Arc<HistoryReport>>,
 }
 
 impl McpServer {
@@ -62,6 +85,8 @@ impl McpServer {
             project_root: PathBuf::from("."),
             tasks: Arc::new(TaskManager::new()),
             negotiated_protocol: Arc::new(tokio::sync::RwLock::new(None)),
+            embeddings: Arc::new(tokio::sync::Mutex::new(None)),
+            history: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
@@ -73,6 +98,8 @@ impl McpServer {
             project_root,
             tasks: Arc::new(TaskManager::new()),
             negotiated_protocol: Arc::new(tokio::sync::RwLock::new(None)),
+            embeddings: Arc::new(tokio::sync::Mutex::new(None)),
+            history: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
@@ -84,6 +111,8 @@ impl McpServer {
             project_root: PathBuf::from("."),
             tasks: Arc::new(TaskManager::new()),
             negotiated_protocol: Arc::new(tokio::sync::RwLock::new(None)),
+            embeddings: Arc::new(tokio::sync::Mutex::new(None)),
+            history: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
@@ -99,6 +128,8 @@ impl McpServer {
             project_root,
             tasks: Arc::new(TaskManager::new()),
             negotiated_protocol: Arc::new(tokio::sync::RwLock::new(None)),
+            embeddings: Arc::new(tokio::sync::Mutex::new(None)),
+            history: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
@@ -398,6 +429,25 @@ impl McpServer {
         })
     }
 
+    /// A node's edges in one direction, with how each was resolved.
+    fn edge_details(graph: &ArborGraph, index: NodeId, direction: Direction) -> Vec<Value> {
+        graph
+            .edges_of(index, direction)
+            .into_iter()
+            .filter_map(|(other, edge)| {
+                let other = graph.get(other)?;
+                Some(json!({
+                    "id": other.id,
+                    "name": other.name,
+                    "kind": edge.kind,
+                    "provenance": edge.provenance,
+                    "reference": edge.reference,
+                    "confidence": edge.confidence
+                }))
+            })
+            .collect()
+    }
+
     fn err_envelope(tool: &str, message: &str) -> Value {
         json!({
             "content": [{
@@ -417,11 +467,13 @@ impl McpServer {
             "tools": [
                 {
                     "name": "get_logic_path",
-                    "description": "Traces the call graph to find dependencies and usage of a function or class.",
+                    "description": "Traces the call graph to find dependencies and usage of a function or class. With `end_node`, also lists the shortest call paths to that symbol and the chokepoints every path goes through.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
-                            "start_node": { "type": "string", "description": "Name of the function or class to trace" }
+                            "start_node": { "type": "string", "description": "Name of the function or class to trace" },
+                            "end_node": { "type": "string", "description": "Optional symbol to trace paths to" },
+                            "k": { "type": "integer", "description": "Paths to list when `end_node` is set (default: 3)", "default": 3 }
                         },
                         "required": ["start_node"]
                     },
@@ -429,7 +481,7 @@ impl McpServer {
                 },
                 {
                     "name": "analyze_impact",
-                    "description": "Analyzes the impact (blast radius) of changing a node. Returns structured data with upstream/downstream affected nodes.",
+                    "description": "Analyzes the impact (blast radius) of changing a node. Returns structured data with upstream/downstream affected nodes, plus `possibly_affected`: nodes reached only through calls that may dispatch to the target via an interface or base class, functions that register it as a callback, or consumers a DI container injects it into, and `uncertain_edges`: those candidates and code that git history shows changing together with the target but that has no static edge to it. Every affected node carries a `probability` that the change reaches it and a `trace` of the factors that lowered it: name-suffix matches, parse errors, low parse coverage, the fallback parser and uncertain edges.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
@@ -443,12 +495,14 @@ impl McpServer {
                 },
                 {
                     "name": "find_path",
-                    "description": "Finds the shortest path between two nodes.",
+                    "description": "Finds the k shortest paths between two nodes, with an estimate of how many paths exist, the chokepoints every path goes through, and which budget (result_limit, time_budget, max_depth) truncated the answer.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
                             "start_node": { "type": "string", "description": "Name or ID of the start node" },
-                            "end_node": { "type": "string", "description": "Name or ID of the end node" }
+                            "end_node": { "type": "string", "description": "Name or ID of the end node" },
+                            "k": { "type": "integer", "description": "Number of shortest paths to return (default: 3)", "default": 3 },
+                            "max_depth": { "type": "integer", "description": "Longest path to follow, in hops (default: 12)", "default": 12 }
                         },
                         "required": ["start_node", "end_node"]
                     },
@@ -468,8 +522,14 @@ impl McpServer {
                 },
                 {
                     "name": "list_entry_points",
-                    "description": "Lists all detected production entry points: HTTP handlers, main functions, webhooks, background jobs, and CLI commands. Use this first to understand the execution surface of a codebase.",
-                    "inputSchema": { "type": "object", "properties": {}, "required": [] },
+                    "description": "Lists all detected production entry points: HTTP routes declared through web frameworks (with method, path and handlers), main functions, webhooks, background jobs, and CLI commands. With `reaching`, lists only the entry points whose call tree reaches that symbol. Use this first to understand the execution surface of a codebase.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "reaching": { "type": "string", "description": "Optional symbol (name or ID): only entry points that reach it" }
+                        },
+                        "required": []
+                    },
                     "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false }
                 },
                 {
@@ -498,18 +558,45 @@ impl McpServer {
                 },
                 {
                     "name": "search_symbols",
-                    "description": "Fuzzy-searches symbol names across the graph. Use INSTEAD of grep/rg/find to locate functions, classes, or files. Supports multi-term OR queries with '|' separator.",
+                    "description": "Ranked, typo-tolerant search over symbol names, qualified names, signatures, docstrings and paths. Use INSTEAD of grep/rg/find to locate functions, classes, or files. camelCase/snake_case subwords match independently ('user validate' finds validateUser). Filters: kind:method,function file:src/billing/** async:true exported:true static:false via:global_suffix,heuristic (symbols with outgoing edges resolved that way).",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
-                            "query": { "type": "string", "description": "Partial or full symbol name to search for" },
+                            "query": { "type": "string", "description": "Words and/or filters, e.g. 'invoice total kind:method file:src/billing/**'" },
                             "limit": { "type": "integer", "description": "Maximum results to return (default: 20)", "default": 20 },
-                            "offset": { "type": "integer", "description": "Pagination offset (default: 0)", "default": 0 }
+                            "offset": { "type": "integer", "description": "Pagination offset (default: 0)", "default": 0 },
+                            "semantic": { "type": "boolean", "description": "Rank by meaning using local embeddings instead of keywords, e.g. 'retry failed uploads' (requires .arbor/embeddings.toml; filters are not applied)", "default": false }
                         },
                         "required": ["query"]
                     },
                     "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false }
                 },
+                {
+                    "name": "find_similar",
+                    "description": "Finds symbols whose code is most similar to a given symbol, using local embeddings of signatures, docstrings and bodies. Use to spot duplicated logic or examples of the same pattern. Requires .arbor/embeddings.toml.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "symbol": { "type": "string", "description": "Name or ID of the symbol" },
+                            "limit": { "type": "integer", "description": "Maximum results to return (default: 10)", "default": 10 }
+                        },
+                        "required": ["symbol"]
+                    },
+                    "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false }
+                },
+                {
+                    "name": "find_duplicates",
+                    "description": "Finds duplicated and near-duplicated functions across files and languages by fingerprinting normalized token streams (renames, literals and comments are ignored). Returns clone groups with locations, similarity and combined centrality, most central first. Use to find logic worth consolidating or copies that must change together.",
+                    "inputSchema": {
+                        "type": "object",
+                        "properties": {
+                            "min_tokens": { "type": "integer", "description": "Ignore functions shorter than this many normalized tokens (default: 50)", "default": 50 },
+                            "threshold": { "type": "number", "description": "Minimum similarity 0.0-1.0 (default: 0.85; cross-language clones usually score 0.6-0.8)", "default": 0.85 },
+                            "limit": { "type": "integer", "description": "Maximum clone groups to return (default: 10)", "default": 10 }
+                        }
+                    },
+                    "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false }
+                },
                 {
                     "name": "get_file_graph",
                     "description": "Returns all symbols and internal call edges within a single file. Use INSTEAD of reading/catting a file to understand its structure — shows what's defined and how it connects.",
@@ -524,11 +611,12 @@ impl McpServer {
                 },
                 {
                     "name": "get_node_detail",
-                    "description": "Returns full detail for a single symbol: file, line range, kind, role, centrality rank. Use after search_symbols or list_entry_points to inspect a specific node.",
+                    "description": "Returns full detail for a single symbol: file, line range, kind, role, centrality rank, code metrics (cyclomatic complexity, nesting depth, parameter count, LOC) and its edges, each with how it was resolved (provenance: exact_fqn, import_map, same_dir_suffix, global_suffix, heuristic, user_declared), the reference text and a confidence. Use after search_symbols or list_entry_points to inspect a specific node.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
-                            "symbol": { "type": "string", "description": "Name or ID of the symbol" }
+                            "symbol": { "type": "string", "description": "Name or ID of the symbol" },
+                            "include_source": { "type": "boolean", "description": "Include the symbol's source code, read from disk and checked against the indexed content (default: false)", "default": false }
                         },
                         "required": ["symbol"]
                     },
@@ -552,12 +640,13 @@ impl McpServer {
                 },
                 {
                     "name": "get_blast_radius",
-                    "description": "Analyzes the blast radius of current uncommitted git changes. Returns affected files, risk level, and architectural impact. Use this to understand how pending changes ripple through the codebase.",
+                    "description": "Analyzes the blast radius of current uncommitted git changes. Returns affected files, risk level, architectural impact, and the CODEOWNERS teams whose code is affected. Use this to understand how pending changes ripple through the codebase and whom to loop in.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
                             "base_ref": { "type": "string", "description": "Git ref for diff base (default: HEAD)", "default": "HEAD" },
-                            "format": { "type": "string", "description": "Output format: json or markdown", "enum": ["json", "markdown"], "default": "json" }
+                            "format": { "type": "string", "description": "Output format: json or markdown", "enum": ["json", "markdown"], "default": "json" },
+                            "include_authors": { "type": "boolean", "description": "Also group impacted code by primary git blame author (slower; default: false)", "default": false }
                         }
                     },
                     "annotations": { "readOnlyHint": true, "destructiveHint": false, "idempotentHint": true, "openWorldHint": false }
@@ -569,7 +658,8 @@ impl McpServer {
                         "type": "object",
                         "properties": {
                             "symbol": { "type": "string", "description": "Name or ID of the symbol to explain" },
-                            "max_tokens": { "type": "integer", "description": "Maximum tokens for the explanation (default: 2000)", "default": 2000 }
+                            "max_tokens": { "type": "integer", "description": "Maximum tokens for the explanation (default: 2000)", "default": 2000 },
+                            "include_source": { "type": "boolean", "description": "Append source code: the symbol's full body and its neighbors' signatures and docstrings, elided to fit max_tokens (default: false)", "default": false }
                         },
                         "required": ["symbol"]
                     },
@@ -590,7 +680,7 @@ impl McpServer {
                 },
                 {
                     "name": "get_architecture_overview",
-                    "description": "Returns a high-level architectural overview: top central nodes (hotspots), module boundaries, entry points, languages detected, and graph statistics. Use first to orient in an unfamiliar codebase.",
+                    "description": "Returns a high-level architectural overview: top central nodes (hotspots), module boundaries, entry points, languages detected, and graph statistics. In a git repository it adds change history: symbols ranked by risk (churn weighted by centrality), the most changed files, and files that change together with no static edge. Use first to orient in an unfamiliar codebase.",
                     "inputSchema": {
                         "type": "object",
                         "properties": {
@@ -647,7 +737,25 @@ impl McpServer {
                 // Trigger Spotlight so the Visualizer shows what the AI is looking at
                 self.trigger_spotlight(start_node).await;
 
-                let context = self.generate_context(start_node).await;
+                let mut context = self.generate_context(start_node).await;
+                if let Some(end_node) = arguments.get("end_node").and_then(|v| v.as_str()) {
+                    let options = arbor_graph::PathOptions {
+                        max_paths: arguments.get("k").and_then(|v| v.as_u64()).unwrap_or(3)
+                            as usize,
+                        ..Default::default()
+                    };
+                    let graph = self.graph.read().await;
+                    let section = match (
+                        resolve_node(&graph, start_node),
+                        resolve_node(&graph, end_node),
+                    ) {
+                        (Some(u), Some(v)) => {
+                            path_markdown(&graph, &graph.find_paths(u, v, &options))
+                        }
+                        _ => format!("Could not resolve '{}' or '{}'.", start_node, end_node),
+                    };
+                    context.push_str(&format!("\n\n## Paths to `{}`\n\n{}", end_node, section));
+                }
                 Ok(json!({
                     "content": [
                         {
@@ -683,11 +791,16 @@ impl McpServer {
 
                 match node_index {
                     Some(idx) => {
-                        let analysis = graph.analyze_impact(idx, max_depth);
+                        let mut analysis = graph.analyze_impact(idx, max_depth);
+                        Ownership::load(&self.project_root, false).annotate(&mut analysis);
+                        if let Some(history) = self.history_report(&graph).await {
+                            history.annotate(&self.project_root, &mut analysis);
+                        }
 
                         // Compute confidence and role
-                        let confidence =
-                            arbor_graph::ConfidenceExplanation::from_analysis(&analysis);
+                        let confidence = arbor_graph::ConfidenceExplanation::from_graph_analysis(
+                            &analysis, &graph,
+                        );
                         let role = arbor_graph::NodeRole::from_analysis(&analysis);
 
                         // Build structured response
@@ -702,7 +815,10 @@ impl McpServer {
                                     "file": n.node_info.file,
                                     "severity": n.severity.as_str(),
                                     "hop_distance": n.hop_distance,
-                                    "entry_edge": n.entry_edge.to_string()
+                                    "entry_edge": n.entry_edge.to_string(),
+                                    "owners": n.owners,
+                                    "probability": n.probability,
+                                    "trace": n.trace
                                 })
                             })
                             .collect();
@@ -718,7 +834,10 @@ impl McpServer {
                                     "file": n.node_info.file,
                                     "severity": n.severity.as_str(),
                                     "hop_distance": n.hop_distance,
-                                    "entry_edge": n.entry_edge.to_string()
+                                    "entry_edge": n.entry_edge.to_string(),
+                                    "owners": n.owners,
+                                    "probability": n.probability,
+                                    "trace": n.trace
                                 })
                             })
                             .collect();
@@ -735,11 +854,27 @@ impl McpServer {
                                     },
                                     "confidence": {
                                         "level": confidence.level.to_string(),
+                                        "probability": confidence.probability,
                                         "reasons": confidence.reasons
                                     },
                                     "role": role.to_string(),
                                     "upstream": upstream,
                                     "downstream": downstream,
+                                    "possibly_affected": analysis
+                                        .possibly_affected
+                                        .iter()
+                                        .map(|n| json!({
+                                            "id": n.node_info.id,
+                                            "name": n.node_info.name,
+                                            "kind": n.node_info.kind,
+                                            "file": n.node_info.file,
+                                            "direction": n.direction.to_string(),
+                                            "hop_distance": n.hop_distance,
+                                            "probability": n.probability,
+                                            "trace": n.trace
+                                        }))
+                                        .collect::<Vec<_>>(),
+                                    "uncertain_edges": analysis.uncertain_edges,
                                     "total_affected": analysis.total_affected,
                                     "max_depth": analysis.max_depth,
                                     "query_time_ms": analysis.query_time_ms,
@@ -771,37 +906,26 @@ impl McpServer {
                     .and_then(|v| v.as_str())
                     .unwrap_or("");
 
-                let graph = self.graph.read().await;
+                let options = arbor_graph::PathOptions {
+                    max_paths: arguments.get("k").and_then(|v| v.as_u64()).unwrap_or(3) as usize,
+                    max_depth: arguments
+                        .get("max_depth")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(12) as usize,
+                    ..Default::default()
+                };
 
-                let start_idx = graph.get_index(start_node).or_else(|| {
-                    graph
-                        .find_by_name(start_node)
-                        .first()
-                        .and_then(|n| graph.get_index(&n.id))
-                });
-                let end_idx = graph.get_index(end_node).or_else(|| {
-                    graph
-                        .find_by_name(end_node)
-                        .first()
-                        .and_then(|n| graph.get_index(&n.id))
-                });
+                let graph = self.graph.read().await;
 
-                match (start_idx, end_idx) {
+                match (
+                    resolve_node(&graph, start_node),
+                    resolve_node(&graph, end_node),
+                ) {
                     (Some(u), Some(v)) => {
-                        if let Some(path) = graph.find_path(u, v) {
-                            let path_str = path
-                                .iter()
-                                .map(|n| format!("`{}` ({})", n.name, n.kind))
-                                .collect::<Vec<_>>()
-                                .join(" -> ");
-                            Ok(json!({
-                                "content": [{ "type": "text", "text": format!("Found path:\n\n{}", path_str) }]
-                            }))
-                        } else {
-                            Ok(json!({
-                                "content": [{ "type": "text", "text": "No path found between these nodes." }]
-                            }))
-                        }
+                        let result = graph.find_paths(u, v, &options);
+                        Ok(json!({
+                            "content": [{ "type": "text", "text": path_markdown(&graph, &result) }]
+                        }))
                     }
                     _ => Err(JsonRpcError {
                         code: -32602,
@@ -832,16 +956,37 @@ impl McpServer {
             }
             "list_entry_points" => {
                 let graph = self.graph.read().await;
-                let eps = graph.list_entry_points();
+                let eps = match arguments.get("reaching").and_then(|v| v.as_str()) {
+                    Some(symbol) => match resolve_node(&graph, symbol) {
+                        Some(idx) => graph.entry_points_reaching(idx),
+                        None => {
+                            return Ok(Self::err_envelope(
+                                "list_entry_points",
+                                &format!("Symbol '{}' not found", symbol),
+                            ))
+                        }
+                    },
+                    None => graph.list_entry_points(),
+                };
                 let entries: Vec<Value> = eps
                     .iter()
                     .map(|n| {
+                        let handlers: Vec<String> = graph
+                            .get_index(&n.id)
+                            .filter(|_| n.route.is_some())
+                            .map(|idx| graph.get_callees(idx))
+                            .unwrap_or_default()
+                            .iter()
+                            .map(|h| h.qualified_name.clone())
+                            .collect();
                         json!({
                             "id": n.id,
                             "name": n.name,
                             "kind": n.kind.to_string(),
                             "file": n.file,
-                            "line": n.line_start
+                            "line": n.line_start,
+                            "route": n.route,
+                            "handlers": handlers
                         })
                     })
                     .collect();
@@ -988,19 +1133,45 @@ impl McpServer {
                     .get("offset")
                     .and_then(|v| v.as_u64())
                     .unwrap_or(0) as usize;
+                let semantic = arguments
+                    .get("semantic")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
                 let graph = self.graph.read().await;
-                let all_results = graph.search(query);
+                let all_results: Vec<(&arbor_core::CodeNode, f64)> = if semantic {
+                    // One extra hit tells us whether there's another page.
+                    let hits = self
+                        .with_embeddings(&graph, |index, provider| {
+                            index.search(provider, query, offset + limit + 1)
+                        })
+                        .await;
+                    match hits {
+                        Ok(Ok(hits)) => hits
+                            .into_iter()
+                            .filter_map(|hit| {
+                                Some((graph.get_by_id(&hit.node_id)?, hit.similarity as f64))
+                            })
+                            .collect(),
+                        Ok(Err(e)) => {
+                            return Ok(Self::err_envelope("search_symbols", &e.to_string()))
+                        }
+                        Err(e) => return Ok(Self::err_envelope("search_symbols", &e)),
+                    }
+                } else {
+                    graph.search_scored(query)
+                };
                 let total = all_results.len();
                 let page_results: Vec<_> = all_results.iter().skip(offset).take(limit).collect();
                 let items: Vec<Value> = page_results
                     .iter()
-                    .map(|n| {
+                    .map(|(n, score)| {
                         json!({
                             "id": n.id,
                             "name": n.name,
                             "kind": n.kind.to_string(),
                             "file": n.file,
-                            "line": n.line_start
+                            "line": n.line_start,
+                            "score": (score * 1000.0).round() / 1000.0
                         })
                     })
                     .collect();
@@ -1029,6 +1200,94 @@ impl McpServer {
                     json!({ "symbol": first }),
                 ))
             }
+            "find_similar" => {
+                let symbol = arguments
+                    .get("symbol")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("");
+                let limit = arguments
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(10) as usize;
+                let graph = self.graph.read().await;
+                let Some(node) = graph
+                    .get_by_id(symbol)
+                    .or_else(|| graph.find_by_name(symbol).into_iter().next())
+                else {
+                    return Ok(Self::err_envelope(
+                        "find_similar",
+                        &format!("Symbol '{}' not found", symbol),
+                    ));
+                };
+                let hits = match self
+                    .with_embeddings(&graph, |index, _| index.similar_to(&node.id, limit))
+                    .await
+                {
+                    Ok(hits) => hits,
+                    Err(e) => return Ok(Self::err_envelope("find_similar", &e)),
+                };
+                let items: Vec<Value> = hits
+                    .iter()
+                    .filter_map(|hit| {
+                        let similar = graph.get_by_id(&hit.node_id)?;
+                        Some(json!({
+                            "id": similar.id,
+                            "name": similar.name,
+                            "kind": similar.kind.to_string(),
+                            "file": similar.file,
+                            "line": similar.line_start,
+                            "similarity": (hit.similarity * 1000.0).round() / 1000.0
+                        }))
+                    })
+                    .collect();
+                let count = items.len();
+                let first = items
+                    .first()
+                    .and_then(|e| e["name"].as_str())
+                    .unwrap_or("")
+                    .to_string();
+                Ok(Self::ok_envelope(
+                    "find_similar",
+                    json!({ "symbol": node.name, "results": items }),
+                    count,
+                    "get_node_detail",
+                    json!({ "symbol": first }),
+                ))
+            }
+            "find_duplicates" => {
+                let defaults = DuplicateOptions::default();
+                let options = DuplicateOptions {
+                    min_tokens: arguments
+                        .get("min_tokens")
+                        .and_then(|v| v.as_u64())
+                        .map_or(defaults.min_tokens, |v| v as usize),
+                    threshold: arguments
+                        .get("threshold")
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(defaults.threshold)
+                        .clamp(0.0, 1.0),
+                };
+                let limit = arguments
+                    .get("limit")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(10) as usize;
+                let graph = self.graph.read().await;
+                let mut groups = graph
+                    .find_duplicates(&options, &mut SourceReader::with_root(&self.project_root));
+                let total = groups.len();
+                groups.truncate(limit);
+                let first = groups
+                    .first()
+                    .map(|g| g.instances[0].node_info.name.clone())
+                    .unwrap_or_default();
+                Ok(Self::ok_envelope(
+                    "find_duplicates",
+                    json!({ "total": total, "groups": groups }),
+                    groups.len(),
+                    "analyze_impact",
+                    json!({ "node_id": first }),
+                ))
+            }
             "get_file_graph" => {
                 let file_path = arguments
                     .get("file_path")
@@ -1076,6 +1335,10 @@ impl McpServer {
                     .get("symbol")
                     .and_then(|v| v.as_str())
                     .unwrap_or("");
+                let include_source = arguments
+                    .get("include_source")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
                 let graph = self.graph.read().await;
                 let idx = graph.get_index(symbol).or_else(|| {
                     graph
@@ -1116,22 +1379,36 @@ impl McpServer {
                         } else {
                             "get_callers"
                         };
+                        let mut detail = json!({
+                            "id": node.id,
+                            "name": node.name,
+                            "kind": node.kind.to_string(),
+                            "file": node.file,
+                            "line_start": node.line_start,
+                            "line_end": node.line_end,
+                            "signature": node.signature,
+                            "centrality": centrality,
+                            "role": role,
+                            "caller_count": callers.len(),
+                            "callee_count": callees.len(),
+                            "is_entry_point": is_entry,
+                            "metrics": node.metrics,
+                            "edges_out": Self::edge_details(&graph, idx, Direction::Outgoing),
+                            "edges_in": Self::edge_details(&graph, idx, Direction::Incoming)
+                        });
+                        if include_source {
+                            let mut reader = SourceReader::with_root(&self.project_root);
+                            let (source, stale) = match reader.read(node) {
+                                NodeSource::Fresh(text) => (Some(text), false),
+                                NodeSource::Stale => (None, true),
+                                NodeSource::Unavailable => (None, false),
+                            };
+                            detail["source"] = json!(source);
+                            detail["stale"] = json!(stale);
+                        }
                         Ok(Self::ok_envelope(
                             "get_node_detail",
-                            json!({
-                                "id": node.id,
-                                "name": node.name,
-                                "kind": node.kind.to_string(),
-                                "file": node.file,
-                                "line_start": node.line_start,
-                                "line_end": node.line_end,
-                                "signature": node.signature,
-                                "centrality": centrality,
-                                "role": role,
-                                "caller_count": callers.len(),
-                                "callee_count": callees.len(),
-                                "is_entry_point": is_entry
-                            }),
+                            detail,
                             1,
                             next,
                             json!({ "symbol": symbol }),
@@ -1212,6 +1489,10 @@ impl McpServer {
                     .get("format")
                     .and_then(|v| v.as_str())
                     .unwrap_or("json");
+                let include_authors = arguments
+                    .get("include_authors")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
 
                 let graph = self.graph.read().await;
                 let node_count = graph.node_count();
@@ -1240,12 +1521,14 @@ impl McpServer {
 
                         let changed_ids =
                             changed_node_ids(&graph, &changed_files, &self.project_root);
+                        let mut ownership = Ownership::load(&self.project_root, include_authors);
                         let summary = compute_blast_radius(
                             &graph,
                             changed_files,
                             changed_ids,
                             depth,
                             &self.project_root,
+                            Some(&mut ownership),
                         );
 
                         if format == "markdown" {
@@ -1276,6 +1559,16 @@ impl McpServer {
                                 summary.entrypoints_affected,
                                 summary.files_likely_updates
                             ));
+                            push_owner_markdown(
+                                &mut markdown,
+                                "Teams Affected",
+                                &summary.teams_affected,
+                            );
+                            push_owner_markdown(
+                                &mut markdown,
+                                "Authors Affected",
+                                &summary.authors_affected,
+                            );
                             Ok(json!({
                                 "content": [{ "type": "text", "text": markdown }]
                             }))
@@ -1291,7 +1584,9 @@ impl McpServer {
                                     "files_likely_updates": summary.files_likely_updates,
                                     "blast_radius_nodes": summary.blast_radius_nodes,
                                     "risk_level": summary.risk_level,
-                                    "mermaid_diagram": summary.mermaid_diagram
+                                    "mermaid_diagram": summary.mermaid_diagram,
+                                    "teams_affected": summary.teams_affected,
+                                    "authors_affected": summary.authors_affected
                                 }),
                                 node_count,
                                 "analyze_impact",
@@ -1310,6 +1605,14 @@ impl McpServer {
                         message: "Missing 'symbol' parameter".to_string(),
                         data: None,
                     })?;
+                let max_tokens = arguments
+                    .get("max_tokens")
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(2000) as usize;
+                let include_source = arguments
+                    .get("include_source")
+                    .and_then(|v| v.as_bool())
+                    .unwrap_or(false);
 
                 self.trigger_spotlight(symbol).await;
                 let graph = self.graph.read().await;
@@ -1343,7 +1646,7 @@ impl McpServer {
                             "internal"
                         };
 
-                        let markdown = format!(
+                        let mut markdown = format!(
                             "### 🌳 Symbol Explanation: `{}`\n\n\
                             - **Kind**: {}\n\
                             - **Location**: `{}` (Lines {}-{})\n\
@@ -1376,6 +1679,20 @@ impl McpServer {
                                 .join("\n")
                         );
 
+                        if include_source {
+                            let budget = max_tokens.saturating_sub(count_tokens(&markdown));
+                            let mut reader = SourceReader::with_root(&self.project_root);
+                            let slice = graph.slice_context_with_source(
+                                idx,
+                                budget.max(1),
+                                1,
+                                &[],
+                                &SourcePolicy::default(),
+                                &mut reader,
+                            );
+                            markdown.push_str(&Self::render_slice_source(&slice));
+                        }
+
                         Ok(json!({
                             "content": [{
                                 "type": "text",
@@ -1538,13 +1855,21 @@ impl McpServer {
 
                 let modules_list: Vec<String> = modules.into_iter().collect();
 
-                let data = json!({
+                let mut data = json!({
                     "node_count": node_count,
                     "edge_count": edge_count,
                     "modules": modules_list,
                     "top_hotspots": hotspots,
                     "entry_points": entry_list
                 });
+                if let Some(history) = self.history_report(&graph).await {
+                    data["change_history"] = json!({
+                        "commits_analyzed": history.commits_analyzed,
+                        "risk_hotspots": history.hotspots(top_n),
+                        "most_changed_files": &history.files[..history.files.len().min(top_n)],
+                        "hidden_coupling": history.hidden_file_coupling().take(top_n).collect::<Vec<_>>()
+                    });
+                }
 
                 let mut next_args = json!({});
                 if let Some(first_hotspot) = hotspots.first() {
@@ -1917,6 +2242,113 @@ impl McpServer {
         trimmed.split_whitespace().last()
     }
 
+    /// Churn and co-change coupling for the project, recomputed when the
+    /// graph changes size or `HEAD` moves. `None` outside a git repository.
+    async fn history_report(&self, graph: &ArborGraph) -> Option<Arc<HistoryReport>> {
+        if graph.node_count() == 0 {
+            return None;
+        }
+        let graph_size = (graph.node_count(), graph.edge_count());
+        let head = ChangeHistory::head(&self.project_root);
+        let mut cache = self.history.lock().await;
+        if cache
+            .as_ref()
+            .is_none_or(|c| c.graph_size != graph_size || c.head != head)
+        {
+            let report = ChangeHistory::load(&self.project_root, HISTORY_COMMITS).map(|history| {
+                Arc::new(graph.analyze_history(&history, &HistoryOptions::default()))
+            });
+            *cache = Some(HistoryCache {
+                graph_size,
+                head,
+                report,
+            });
+        }
+        cache.as_ref().and_then(|c| c.report.clone())
+    }
+
+    /// Runs `f` against the project's embedding index, building it on first
+    /// use and again whenever the graph changes size.
+    ///
+    /// The build runs the provider, so it happens on a blocking thread and
+    /// without holding the cache lock.
+    async fn with_embeddings<T>(
+        &self,
+        graph: &ArborGraph,
+        f: impl FnOnce(&EmbeddingIndex, &dyn EmbeddingProvider) -> T,
+    ) -> Result<T, String> {
+        let graph_size = (graph.node_count(), graph.edge_count());
+        let stale = self
+            .embeddings
+            .lock()
+            .await
+            .as_ref()
+            .is_none_or(|c| c.graph_size != graph_size);
+        if stale {
+            let root = self.project_root.clone();
+            let config = EmbeddingConfig::load(&root)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| {
+                    format!(
+                        "Embeddings are not configured; create {} with a provider",
+                        EMBEDDINGS_CONFIG
+                    )
+                })?;
+            let provider = config.provider(&root).map_err(|e| e.to_string())?;
+            let nodes: Vec<arbor_core::CodeNode> = graph.nodes().cloned().collect();
+            let built = tokio::task::spawn_blocking(move || {
+                // The watcher may hold the cache open; embed without it then.
+                let store = GraphStore::open_or_reset(root.join(".arbor").join("cache")).ok();
+                EmbeddingIndex::build_from(
+                    &nodes,
+                    provider.as_ref(),
+                    store.as_ref(),
+                    &mut SourceReader::with_root(&root),
+                    &config,
+                )
+                .map(|(index, _)| EmbeddingCache {
+                    graph_size,
+                    index,
+                    provider,
+                })
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+            *self.embeddings.lock().await = Some(built);
+        }
+        let cache = self.embeddings.lock().await;
+        let cache = cache.as_ref().expect("built above");
+        Ok(f(&cache.index, cache.provider.as_ref()))
+    }
+
+    /// Markdown code blocks for the nodes of a slice that carry source.
+    fn render_slice_source(slice: &ContextSlice) -> String {
+        let mut out = String::from("\n\n#### Source\n");
+        for node in &slice.nodes {
+            let info = &node.node_info;
+            let location = format!(
+                "`{}` (`{}`:{}-{})",
+                info.qualified_name, info.file, info.line_start, info.line_end
+            );
+            match &node.source {
+                Some(source) => out.push_str(&format!(
+                    "\n{} — {}\n```\n{}\n```\n",
+                    location, node.detail, source
+                )),
+                None if node.stale => out.push_str(&format!(
+                    "\n{} — changed since indexing, re-index to see its source\n",
+                    location
+                )),
+                None => {}
+            }
+        }
+        if slice.truncation_reason == TruncationReason::TokenBudget {
+            out.push_str("\n_Some neighbors were omitted to fit the token budget._\n");
+        }
+        out
+    }
+
     async fn generate_context(&self, node_start: &str) -> String {
         let graph = self.graph.read().await;
 
@@ -2016,7 +2448,7 @@ impl McpServer {
             json!({
                 "uri": "arbor://graph/hotspots",
                 "name": "Code Hotspots",
-                "description": "Top 20 most central nodes in the codebase",
+                "description": "Top 20 most central nodes in the codebase, and the top 20 by change risk (git churn weighted by centrality)",
                 "mimeType": "application/json"
             }),
         ];
@@ -2083,7 +2515,11 @@ impl McpServer {
                         })
                     })
                     .collect();
-                json!({ "hotspots": hotspots })
+                let risk_hotspots = match self.history_report(&graph).await {
+                    Some(history) => json!(history.hotspots(20)),
+                    None => json!([]),
+                };
+                json!({ "hotspots": hotspots, "risk_hotspots": risk_hotspots })
             }
             uri if uri.starts_with("ui://") => {
                 if let Some(html) = apps::read_app_template(uri) {
@@ -2122,6 +2558,82 @@ impl McpServer {
     }
 }
 
+/// Resolves a tool argument to a node, by ID first and then by name.
+fn resolve_node(graph: &ArborGraph, symbol: &str) -> Option<arbor_graph::NodeId> {
+    graph.get_index(symbol).or_else(|| {
+        graph
+            .find_by_name(symbol)
+            .first()
+            .and_then(|n| graph.get_index(&n.id))
+    })
+}
+
+/// Renders a path query as markdown: the paths, the path count, the
+/// chokepoints and any truncation.
+fn path_markdown(graph: &ArborGraph, result: &arbor_graph::PathResult) -> String {
+    let name = |id: &arbor_graph::NodeId| {
+        graph
+            .get(*id)
+            .map(|n| format!("`{}` ({})", n.name, n.kind))
+            .unwrap_or_default()
+    };
+    if result.paths.is_empty() {
+        let mut text = "No path found between these nodes.".to_string();
+        if result
+            .truncated
+            .contains(&arbor_graph::PathTruncation::MaxDepth)
+        {
+            text.push_str(" Longer routes exist beyond `max_depth`.");
+        }
+        return text;
+    }
+
+    let mut text = String::new();
+    for (i, path) in result.paths.iter().enumerate() {
+        let hops: Vec<String> = path.iter().map(name).collect();
+        text.push_str(&format!(
+            "Found path {} ({} hops):\n\n{}\n\n",
+            i + 1,
+            path.len().saturating_sub(1),
+            hops.join(" -> ")
+        ));
+    }
+    text.push_str(&format!(
+        "Paths within depth: {}{}\n",
+        if result.count_exact { "" } else { "~" },
+        result.estimated_paths
+    ));
+    if !result.chokepoints.is_empty() {
+        let names: Vec<String> = result.chokepoints.iter().map(name).collect();
+        text.push_str(&format!("Every path goes through: {}\n", names.join(", ")));
+    }
+    if !result.truncated.is_empty() {
+        let reasons: Vec<String> = result.truncated.iter().map(|r| r.to_string()).collect();
+        text.push_str(&format!("Truncated: {}\n", reasons.join(", ")));
+    }
+    text
+}
+
+/// Appends an owner table to a markdown report; nothing if `owners` is empty.
+fn push_owner_markdown(markdown: &mut String, title: &str, owners: &[arbor_graph::OwnerImpact]) {
+    if owners.is_empty() {
+        return;
+    }
+    markdown.push_str(&format!(
+        "\n### {}\n\n| Owner | Nodes | Files |\n|-------|-------|-------|\n",
+        title
+    ));
+    for owner in owners {
+        let files: Vec<String> = owner.files.iter().map(|f| format!("`{}`", f)).collect();
+        markdown.push_str(&format!(
+            "| {} | {} | {} |\n",
+            owner.owner,
+            owner.nodes,
+            files.join(", ")
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tool_tests {
     use super::*;
@@ -2242,6 +2754,174 @@ mod tool_tests {
         assert_eq!(envelope["ok"], false);
     }
 
+    #[tokio::test]
+    async fn test_get_node_detail_reports_edge_provenance() {
+        use arbor_core::{CodeNode, NodeKind};
+
+        let mut builder = arbor_graph::GraphBuilder::new();
+        builder.add_nodes(vec![
+            CodeNode::new("main", "main", NodeKind::Function, "src/main.rs")
+                .with_references(vec!["helper".to_string()]),
+            CodeNode::new("helper", "util.helper", NodeKind::Function, "src/util.rs"),
+        ]);
+        let server = McpServer::new(Arc::new(RwLock::new(builder.build())));
+
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "get_node_detail", "arguments": { "symbol": "main" }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(text).unwrap();
+        let edge = &envelope["data"]["edges_out"][0];
+        assert_eq!(edge["name"], "helper");
+        assert_eq!(edge["provenance"], "same_dir_suffix");
+        assert_eq!(edge["reference"], "helper");
+        assert!((edge["confidence"].as_f64().unwrap() - 0.85).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_with_command_provider() {
+        use arbor_core::{CodeNode, NodeKind};
+
+        let root = std::env::temp_dir().join(format!("arbor-mcp-embed-{}", std::process::id()));
+        std::fs::create_dir_all(root.join(".arbor")).unwrap();
+        arbor_graph::allow_embeddings();
+        // Vector = [text length, 1]: names of similar length end up close.
+        std::fs::write(
+            root.join(".arbor/embeddings.toml"),
+            "provider = \"command\"\ncommand = [\"sh\", \"-c\", \"while read -r l; do echo \\\"[${#l}, 40]\\\"; done\"]\n",
+        )
+        .unwrap();
+
+        let mut graph = ArborGraph::new();
+        for name in ["a", "bb", "long_function_name_here_and_more"] {
+            graph.add_node(CodeNode::new(name, name, NodeKind::Function, "x.rs"));
+        }
+        let server = McpServer::with_project(Arc::new(RwLock::new(graph)), root.clone());
+
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "find_similar", "arguments": { "symbol": "a", "limit": 1 }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(envelope["ok"], true, "{}", text);
+        assert_eq!(envelope["data"]["results"][0]["name"], "bb");
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_find_similar_requires_config() {
+        let server = empty_server();
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "find_similar", "arguments": { "symbol": "_dummy" }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(envelope["ok"], false);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_groups_copies() {
+        let root = std::env::temp_dir().join(format!("arbor-mcp-dupes-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let body = "(items: &[i64]) -> i64 {\n    let mut best = 0;\n    for item in items {\n        if *item > best && item % 3 != 0 {\n            best = *item * 2 - 1;\n        }\n    }\n    best\n}\n";
+        let source = format!("fn largest{}\nfn biggest{}\nfn other() {{}}\n", body, body);
+        let file = root.join("lib.rs");
+        std::fs::write(&file, &source).unwrap();
+
+        let mut graph = ArborGraph::new();
+        for node in arbor_core::parse_file(&file).unwrap() {
+            graph.add_node(node);
+        }
+        let server = McpServer::with_project(Arc::new(RwLock::new(graph)), root.clone());
+
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "find_duplicates", "arguments": { "min_tokens": 20 }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let envelope: serde_json::Value = serde_json::from_str(text).unwrap();
+        assert_eq!(envelope["ok"], true, "{}", text);
+        assert_eq!(envelope["data"]["total"], 1);
+        let group = &envelope["data"]["groups"][0];
+        assert_eq!(group["instances"].as_array().unwrap().len(), 2);
+        assert_eq!(group["similarity"], 1.0);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[tokio::test]
+    async fn test_analyze_impact_reports_hidden_co_change() {
+        use arbor_core::{CodeNode, NodeKind};
+
+        let root = std::env::temp_dir().join(format!("arbor-mcp-history-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(["-c", "user.name=Alice", "-c", "user.email=a@example.com"])
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        git(&["init", "-q"]);
+        for i in 0..3 {
+            std::fs::write(
+                root.join("schema.py"),
+                format!("def schema():\n    return {}\n", i),
+            )
+            .unwrap();
+            std::fs::write(
+                root.join("client.ts"),
+                format!("function client() {{ return {}; }}\n", i),
+            )
+            .unwrap();
+            git(&["add", "-A"]);
+            git(&["commit", "-qm", "change"]);
+        }
+
+        let mut graph = ArborGraph::new();
+        graph.add_node(
+            CodeNode::new("schema", "schema", NodeKind::Function, "schema.py").with_lines(1, 2),
+        );
+        graph.add_node(
+            CodeNode::new("client", "client", NodeKind::Function, "client.ts").with_lines(1, 1),
+        );
+        let server = McpServer::with_project(Arc::new(RwLock::new(graph)), root.clone());
+
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "analyze_impact", "arguments": { "node_id": "schema" }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        let data: serde_json::Value = serde_json::from_str(text).unwrap();
+        let edges = data["uncertain_edges"].as_array().unwrap();
+        assert_eq!(edges.len(), 1, "{}", text);
+        assert_eq!(edges[0]["kind"], "co_change");
+        assert!(data["confidence"]["reasons"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r.as_str().unwrap().contains("co-change")));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
     #[tokio::test]
     async fn test_unknown_tool_returns_error() {
         let server = empty_server();
@@ -2315,6 +2995,52 @@ mod tool_tests {
         assert!(!entries.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_explain_symbol_includes_source() {
+        use arbor_core::{stamp_content_hashes, CodeNode, NodeKind};
+
+        let root = std::env::temp_dir().join(format!("arbor-mcp-source-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let source = "fn main() {\n    helper(1);\n}\nfn helper(x: i32) -> i32 {\n    x + 1\n}\n";
+        std::fs::write(root.join("main.rs"), source).unwrap();
+
+        let mut nodes = vec![
+            CodeNode::new("main", "main", NodeKind::Function, "main.rs")
+                .with_lines(1, 3)
+                .with_bytes(0, 28),
+            CodeNode::new("helper", "helper", NodeKind::Function, "main.rs")
+                .with_lines(4, 6)
+                .with_bytes(29, 67)
+                .with_signature("fn helper(x: i32) -> i32"),
+        ];
+        stamp_content_hashes(&mut nodes, source);
+
+        let mut graph = ArborGraph::new();
+        let main = graph.add_node(nodes[0].clone());
+        let helper = graph.add_node(nodes[1].clone());
+        graph.add_edge(
+            main,
+            helper,
+            arbor_graph::Edge::new(arbor_graph::EdgeKind::Calls),
+        );
+        let server = McpServer::with_project(Arc::new(RwLock::new(graph)), root.clone());
+
+        let result = server
+            .call_tool(serde_json::json!({
+                "name": "explain_symbol",
+                "arguments": { "symbol": "main", "include_source": true }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("fn main() {\n    helper(1);\n}"));
+        // The neighbor gets only its signature.
+        assert!(text.contains("fn helper(x: i32) -> i32\n```"));
+        assert!(!text.contains("x + 1"));
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
     #[tokio::test]
     async fn test_explain_symbol_not_found() {
         let server = empty_server();
@@ -2411,4 +3137,35 @@ mod tool_tests {
         let text = result["contents"][0]["text"].as_str().unwrap();
         assert!(text.contains("canvas"));
     }
+
+    #[tokio::test]
+    async fn test_find_path_lists_k_paths_and_chokepoints() {
+        use arbor_graph::{Edge, EdgeKind};
+        let mut graph = ArborGraph::new();
+        let mut ids = Vec::new();
+        for name in ["handle", "route", "save", "insert", "execute"] {
+            let node =
+                arbor_core::CodeNode::new(name, name, arbor_core::NodeKind::Function, "app.rs");
+            ids.push(graph.add_node(node));
+        }
+        // handle -> route -> {save, insert} -> execute
+        for (from, to) in [(0, 1), (1, 2), (1, 3), (2, 4), (3, 4)] {
+            graph.add_edge(ids[from], ids[to], Edge::new(EdgeKind::Calls));
+        }
+        let server = McpServer::with_project(Arc::new(RwLock::new(graph)), PathBuf::from("."));
+
+        let result = server
+            .call_tool(json!({
+                "name": "find_path",
+                "arguments": { "start_node": "handle", "end_node": "execute", "k": 1 }
+            }))
+            .await
+            .unwrap();
+        let text = result["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Found path 1 (3 hops)"));
+        assert!(!text.contains("Found path 2"));
+        assert!(text.contains("Paths within depth: 2"));
+        assert!(text.contains("Every path goes through: `route`"));
+        assert!(text.contains("Truncated: result_limit"));
+    }
 }
@@ -3,9 +3,11 @@
 //! Each handler implements one method from the Arbor Protocol.
 
 use crate::protocol::{
-    ContextParams, DiscoverParams, ImpactParams, NodeGetParams, Response, SearchParams,
+    ContextParams, DiscoverParams, ImpactParams, NodeGetParams, Response, SearchParams, SliceParams,
+};
+use arbor_graph::{
+    ArborGraph, NodeInfo, NodeSource, SourceDetail, SourcePolicy, SourceReader, TaskContextOptions,
 };
-use arbor_graph::{ArborGraph, NodeInfo};
 use serde::Serialize;
 use serde_json::Value;
 use std::sync::Arc;
@@ -28,6 +30,8 @@ pub async fn handle_info(graph: SharedGraph, id: Option<Value>) -> Response {
         edge_count: usize,
         languages: Vec<String>,
         version: &'static str,
+        #[serde(rename = "parseHealth")]
+        parse_health: arbor_core::ParseHealth,
     }
 
     Response::success(
@@ -40,6 +44,7 @@ pub async fn handle_info(graph: SharedGraph, id: Option<Value>) -> Response {
                 .map(|lang| (*lang).to_string())
                 .collect(),
             version: env!("CARGO_PKG_VERSION"),
+            parse_health: arbor_core::ParseHealth::summarize(g.all_file_diagnostics()),
         },
     )
 }
@@ -152,45 +157,59 @@ pub async fn handle_context(
 
     debug!("Context request for task: {}", params.task);
 
-    // Search for relevant nodes
-    let mut matches: Vec<_> = g
-        .search(&params.task)
-        .into_iter()
-        .map(|node| {
-            let centrality = g.centrality(g.get_index(&node.id).unwrap_or_default());
-            let mut info = NodeInfo::from(node);
-            info.centrality = centrality;
-            info
-        })
-        .collect();
+    let options = TaskContextOptions {
+        max_tokens: params.max_tokens,
+        include_source: params.include_source,
+        ..TaskContextOptions::default()
+    };
+    let context = g.retrieve_for_task(&params.task, &options);
 
-    // Sort by centrality
-    matches.sort_by(|a, b| {
-        b.centrality
-            .partial_cmp(&a.centrality)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    Response::success(
+        id,
+        serde_json::json!({
+            "keywords": context.keywords,
+            "nodes": context.nodes,
+            "totalTokens": context.total_tokens,
+            "truncated": context.truncated,
+            "queryTime": start.elapsed().as_millis()
+        }),
+    )
+}
 
-    // Estimate tokens and truncate
-    // (Rough estimate: 4 characters per token)
-    let mut total_tokens = 0usize;
-    let mut selected = Vec::new();
+/// Handles the slice method.
+pub async fn handle_slice(graph: SharedGraph, id: Option<Value>, params: SliceParams) -> Response {
+    let g = graph.read().await;
 
-    for node in matches {
-        let estimated_tokens = (node.line_end - node.line_start) as usize * 40 / 4;
-        if total_tokens + estimated_tokens > params.max_tokens {
-            break;
-        }
-        total_tokens += estimated_tokens;
-        selected.push(node);
-    }
+    let Some(target) = g.get_index(&params.node) else {
+        return Response::error(id, -32001, format!("Node not found: {}", params.node));
+    };
+    let pinned: Vec<_> = params
+        .pinned
+        .iter()
+        .filter_map(|node| g.get_index(node))
+        .collect();
+
+    let slice = if params.include_source {
+        g.slice_context_with_source(
+            target,
+            params.max_tokens,
+            params.max_depth,
+            &pinned,
+            &SourcePolicy::default(),
+            &mut SourceReader::new(),
+        )
+    } else {
+        g.slice_context(target, params.max_tokens, params.max_depth, &pinned)
+    };
 
     Response::success(
         id,
         serde_json::json!({
-            "nodes": selected,
-            "totalTokens": total_tokens,
-            "queryTime": start.elapsed().as_millis()
+            "target": slice.target,
+            "nodes": slice.nodes,
+            "totalTokens": slice.total_tokens,
+            "truncationReason": slice.truncation_reason.to_string(),
+            "queryTime": slice.query_time_ms
         }),
     )
 }
@@ -206,27 +225,32 @@ pub async fn handle_search(
 
     debug!("Search: {}", params.query);
 
+    // The kind param is shorthand for a `kind:` filter in the query
+    let query = match params.kind {
+        Some(ref kind) => format!("{} kind:{}", params.query, kind),
+        None => params.query.clone(),
+    };
+
     let mut matches: Vec<_> = g
-        .search(&params.query)
+        .search_scored(&query)
         .into_iter()
-        .filter(|node| {
-            // Filter by kind if specified
-            if let Some(ref kind) = params.kind {
-                node.kind.to_string() == *kind
-            } else {
-                true
-            }
+        .map(|(node, score)| {
+            let mut info = NodeInfo::from(node);
+            info.centrality = g.centrality(g.get_index(&node.id).unwrap_or_default());
+            (info, score)
         })
-        .map(NodeInfo::from)
         .collect();
 
     let total = matches.len();
     matches.truncate(params.limit);
+    let scores: Vec<f64> = matches.iter().map(|(_, score)| *score).collect();
+    let nodes: Vec<NodeInfo> = matches.into_iter().map(|(info, _)| info).collect();
 
     Response::success(
         id,
         serde_json::json!({
-            "nodes": matches,
+            "nodes": nodes,
+            "scores": scores,
             "total": total,
             "queryTime": start.elapsed().as_millis()
         }),
@@ -256,9 +280,7 @@ pub async fn handle_node_get(
             let callers: Vec<_> = g.get_callers(idx).iter().map(|n| &n.id).collect();
             let callees: Vec<_> = g.get_callees(idx).iter().map(|n| &n.id).collect();
 
-            Response::success(
-                id,
-                serde_json::json!({
+            let mut result = serde_json::json!({
                     "id": node.id,
                     "name": node.name,
                     "qualifiedName": node.qualified_name,
@@ -267,12 +289,23 @@ pub async fn handle_node_get(
                     "lineStart": node.line_start,
                     "lineEnd": node.line_end,
                     "signature": node.signature,
-                    "edges": {
-                        "calledBy": callers,
-                        "calls": callees
-                    }
-                }),
-            )
+                "edges": {
+                    "calledBy": callers,
+                    "calls": callees
+                }
+            });
+            if params.include_source {
+                let (source, detail, stale) = match SourceReader::new().read(node) {
+                    NodeSource::Fresh(text) => (Some(text), SourceDetail::Full, false),
+                    NodeSource::Stale => (None, SourceDetail::None, true),
+                    NodeSource::Unavailable => (None, SourceDetail::None, false),
+                };
+                result["source"] = serde_json::json!(source);
+                result["detail"] = serde_json::json!(detail);
+                result["stale"] = serde_json::json!(stale);
+            }
+
+            Response::success(id, result)
         }
         None => Response::error(id, -32001, format!("Node not found: {}", params.id)),
     }
@@ -130,7 +130,7 @@ pub struct ContextParams {
     #[serde(default = "default_max_tokens", rename = "maxTokens")]
     pub max_tokens: usize,
     #[serde(default, rename = "includeSource")]
-    pub _include_source: bool,
+    pub include_source: bool,
 }
 
 /// Params for the search method.
@@ -142,10 +142,26 @@ pub struct SearchParams {
     pub limit: usize,
 }
 
+/// Params for the slice method.
+#[derive(Debug, Deserialize)]
+pub struct SliceParams {
+    pub node: String,
+    #[serde(default = "default_max_tokens", rename = "maxTokens")]
+    pub max_tokens: usize,
+    #[serde(default = "default_slice_depth", rename = "maxDepth")]
+    pub max_depth: usize,
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    #[serde(default, rename = "includeSource")]
+    pub include_source: bool,
+}
+
 /// Params for node.get method.
 #[derive(Debug, Deserialize)]
 pub struct NodeGetParams {
     pub id: String,
+    #[serde(default, rename = "includeSource")]
+    pub include_source: bool,
 }
 
 fn default_limit() -> usize {
@@ -160,6 +176,10 @@ fn default_max_tokens() -> usize {
     8000
 }
 
+fn default_slice_depth() -> usize {
+    2
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +264,29 @@ mod tests {
         assert_eq!(params.depth, 5);
     }
 
+    #[test]
+    fn test_context_params_deserialization() {
+        let json = serde_json::json!({"task": "fix webhook retries", "includeSource": true});
+        let params: ContextParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.max_tokens, 8000); // default
+        assert!(params.include_source);
+    }
+
+    #[test]
+    fn test_slice_params_deserialization() {
+        let params: SliceParams =
+            serde_json::from_value(serde_json::json!({"node": "abc", "pinned": ["def"]})).unwrap();
+        assert_eq!(params.max_tokens, 8000);
+        assert_eq!(params.max_depth, 2);
+        assert_eq!(params.pinned, vec!["def"]);
+        assert!(!params.include_source);
+
+        let params: NodeGetParams =
+            serde_json::from_value(serde_json::json!({"id": "abc", "includeSource": true}))
+                .unwrap();
+        assert!(params.include_source);
+    }
+
     #[test]
     fn test_search_params_with_kind_filter() {
         let json = serde_json::json!({"query": "user", "kind": "function", "limit": 20});
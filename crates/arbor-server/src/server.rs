@@ -4,10 +4,11 @@
 
 use crate::handlers::{
     handle_context, handle_discover, handle_impact, handle_info, handle_node_get, handle_search,
-    SharedGraph,
+    handle_slice, SharedGraph,
 };
 use crate::protocol::{
     ContextParams, DiscoverParams, ImpactParams, NodeGetParams, Request, Response, SearchParams,
+    SliceParams,
 };
 use arbor_graph::ArborGraph;
 use futures_util::{SinkExt, StreamExt};
@@ -160,6 +161,11 @@ async fn process_message(text: &str, graph: SharedGraph) -> Response {
             Err(e) => Response::invalid_params(id, e.to_string()),
         },
 
+        "slice" => match serde_json::from_value::<SliceParams>(request.params) {
+            Ok(params) => handle_slice(graph, id, params).await,
+            Err(e) => Response::invalid_params(id, e.to_string()),
+        },
+
         "node.get" => match serde_json::from_value::<NodeGetParams>(request.params) {
             Ok(params) => handle_node_get(graph, id, params).await,
             Err(e) => Response::invalid_params(id, e.to_string()),
@@ -77,6 +77,20 @@ impl Default for SyncServerConfig {
                 "sh".into(),
                 "bash".into(),
                 "zsh".into(),
+                "scala".into(),
+                "sc".into(),
+                "lua".into(),
+                "ex".into(),
+                "exs".into(),
+                "zig".into(),
+                "proto".into(),
+                "thrift".into(),
+                "sql".into(),
+                "vue".into(),
+                "svelte".into(),
+                "astro".into(),
+                "html".into(),
+                "htm".into(),
             ],
         }
     }
@@ -667,6 +681,7 @@ async fn run_background_indexer(
 
                         // Remove old nodes from this file
                         g.remove_file(&result.file_path);
+                        g.set_file_diagnostics(&result.file_path, result.diagnostics.clone());
 
                         // Add new nodes
                         let mut node_ids = HashMap::new();
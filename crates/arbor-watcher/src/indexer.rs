@@ -3,8 +3,8 @@
 //! Walks directories to find and parse source files, building
 //! the initial code graph.
 
-use arbor_core::{parse_file, CodeNode};
-use arbor_graph::{ArborGraph, GraphBuilder, GraphStore};
+use arbor_core::{parse_file, parse_file_with_diagnostics, CodeNode, ParseDiagnostics};
+use arbor_graph::{ArborGraph, Declarations, GraphBuilder, GraphStore, EDGES_CONFIG};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
 use std::collections::HashSet;
@@ -71,6 +71,18 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
 
     info!("Starting index of {}", root.display());
 
+    // Register grammars from .arbor/languages/ before filtering by extension
+    arbor_core::languages::load_plugins(root);
+    // Publish/subscribe patterns from .arbor/topics.toml
+    arbor_core::topics::load_topic_patterns(root);
+
+    // Edges and aliases declared in .arbor/edges.toml
+    match Declarations::load(root) {
+        Ok(Some(declarations)) => builder.add_declarations(declarations),
+        Ok(None) => {}
+        Err(e) => warn!("Ignoring declared edges: {}", e),
+    }
+
     // Open cache if configured
     let store =
         options
@@ -100,10 +112,7 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
             if path.is_dir() {
                 return false;
             }
-            match path.extension().and_then(|e| e.to_str()) {
-                Some(ext) => arbor_core::languages::is_supported(ext),
-                None => false,
-            }
+            arbor_core::languages::is_supported_file(path)
         })
         .map(|entry| entry.into_path())
         .collect();
@@ -116,8 +125,8 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
     // check and the parse can fan out. Results collect in walk order, keeping
     // graph construction deterministic.
     enum Outcome {
-        CacheHit(Vec<CodeNode>),
-        Parsed(Vec<CodeNode>),
+        CacheHit(Vec<CodeNode>, Option<ParseDiagnostics>),
+        Parsed(Vec<CodeNode>, ParseDiagnostics),
         Failed(String),
     }
 
@@ -143,21 +152,25 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
                         // File unchanged, load from cache
                         if let Ok(Some(cached_nodes)) = store.get_file_nodes(&path_str) {
                             debug!("Cache hit: {}", path.display());
-                            return (path_str, Outcome::CacheHit(cached_nodes));
+                            let diagnostics = store.get_file_diagnostics(&path_str).ok().flatten();
+                            return (path_str, Outcome::CacheHit(cached_nodes, diagnostics));
                         }
                     }
                 }
             }
 
             debug!("Parsing: {}", path.display());
-            match parse_file(path) {
-                Ok(nodes) => {
+            match parse_file_with_diagnostics(path) {
+                Ok((nodes, diagnostics)) => {
                     if let Some(store) = store_ref {
-                        if let Err(e) = store.update_file(&path_str, &nodes, current_mtime) {
+                        if let Err(e) = store
+                            .update_file(&path_str, &nodes, current_mtime)
+                            .and_then(|_| store.set_file_diagnostics(&path_str, &diagnostics))
+                        {
                             warn!("Failed to update cache for {}: {}", path_str, e);
                         }
                     }
-                    (path_str, Outcome::Parsed(nodes))
+                    (path_str, Outcome::Parsed(nodes, diagnostics))
                 }
                 Err(e) => {
                     warn!("Failed to parse {}: {}", path.display(), e);
@@ -167,17 +180,22 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
         })
         .collect();
 
+    let mut file_diagnostics = Vec::new();
     for (path_str, outcome) in outcomes {
         match outcome {
-            Outcome::CacheHit(nodes) => {
+            Outcome::CacheHit(nodes, diagnostics) => {
                 nodes_extracted += nodes.len();
                 cache_hits += 1;
                 builder.add_nodes(nodes);
+                if let Some(diagnostics) = diagnostics {
+                    file_diagnostics.push((path_str, diagnostics));
+                }
             }
-            Outcome::Parsed(nodes) => {
+            Outcome::Parsed(nodes, diagnostics) => {
                 nodes_extracted += nodes.len();
                 files_indexed += 1;
                 builder.add_nodes(nodes);
+                file_diagnostics.push((path_str, diagnostics));
             }
             Outcome::Failed(error) => {
                 errors.push((path_str, error));
@@ -199,7 +217,10 @@ pub fn index_directory(root: &Path, options: IndexOptions) -> Result<IndexResult
         }
     }
 
-    let graph = builder.build();
+    let mut graph = builder.build();
+    for (path_str, diagnostics) in file_diagnostics {
+        graph.set_file_diagnostics(&path_str, diagnostics);
+    }
     let duration = start.elapsed();
 
     info!(
@@ -230,8 +251,9 @@ pub fn parse_single_file(path: &Path) -> Result<Vec<CodeNode>, arbor_core::Parse
 /// stats files — no parsing — and early-exits on the first newer file.
 ///
 /// `cache_mtime` is the modified time of the cache file, in seconds since the
-/// UNIX epoch. Catches edits and additions; a lone deletion leaves no newer
-/// file, so it is picked up on the next edit instead.
+/// UNIX epoch. Catches edits and additions, including to
+/// `.arbor/edges.toml`; a lone deletion leaves no newer file, so it is
+/// picked up on the next edit instead.
 pub fn sources_newer_than(root: &Path, cache_mtime: u64, follow_symlinks: bool) -> bool {
     let walker = WalkBuilder::new(root)
         .hidden(true)
@@ -241,14 +263,22 @@ pub fn sources_newer_than(root: &Path, cache_mtime: u64, follow_symlinks: bool)
         .follow_links(follow_symlinks)
         .build();
 
+    // Declared edges change the graph without touching a source file.
+    let declared = std::fs::metadata(root.join(EDGES_CONFIG))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok());
+    if declared.is_some_and(|d| d.as_secs() > cache_mtime) {
+        return true;
+    }
+
     for entry in walker.filter_map(Result::ok) {
         let path = entry.path();
         if path.is_dir() {
             continue;
         }
-        match path.extension().and_then(|e| e.to_str()) {
-            Some(ext) if arbor_core::languages::is_supported(ext) => {}
-            _ => continue,
+        if !arbor_core::languages::is_supported_file(path) {
+            continue;
         }
         let mtime = match std::fs::metadata(path).and_then(|m| m.modified()) {
             Ok(t) => t
@@ -299,6 +329,54 @@ mod tests {
         assert!(!sources_newer_than(dir.path(), 0, false));
     }
 
+    #[test]
+    fn test_index_adds_declared_edges() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".arbor")).unwrap();
+        fs::write(
+            dir.path().join("orders.py"),
+            "# arbor: calls billing.charge\ndef place_order():\n    bus.send('charge')\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("billing.py"),
+            "def charge():\n    pass\n\ndef refund():\n    pass\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join(EDGES_CONFIG),
+            "[[edge]]\nfrom = \"billing.refund\"\nto = \"orders.place_order\"\n",
+        )
+        .unwrap();
+
+        let graph = index_directory(dir.path(), IndexOptions::default())
+            .unwrap()
+            .graph;
+        let mut edges: Vec<(String, String)> = graph
+            .export_edges()
+            .into_iter()
+            .filter(|e| e.provenance == arbor_graph::Provenance::UserDeclared)
+            .map(|e| {
+                let name = |id: &str| graph.get_by_id(id).unwrap().name.clone();
+                (name(&e.source), name(&e.target))
+            })
+            .collect();
+        edges.sort();
+        assert_eq!(
+            edges,
+            [
+                ("place_order".to_string(), "charge".to_string()),
+                ("refund".to_string(), "place_order".to_string()),
+            ]
+        );
+
+        // Editing the declarations makes a cache stale.
+        let only_config = tempdir().unwrap();
+        fs::create_dir_all(only_config.path().join(".arbor")).unwrap();
+        fs::write(only_config.path().join(EDGES_CONFIG), "").unwrap();
+        assert!(sources_newer_than(only_config.path(), 0, false));
+    }
+
     #[test]
     fn test_index_with_rust_file() {
         let dir = tempdir().unwrap();
@@ -319,6 +397,89 @@ mod tests {
         assert!(result.nodes_extracted > 0);
     }
 
+    #[test]
+    fn test_index_links_embedded_code() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("api")).unwrap();
+        fs::create_dir_all(dir.path().join("components")).unwrap();
+        fs::create_dir_all(dir.path().join("db")).unwrap();
+
+        fs::write(
+            dir.path().join("api/users.ts"),
+            "export function fetchUser(id: string) {\n  return get(id);\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("components/UserCard.vue"),
+            "<template><p>{{ user }}</p></template>\n<script setup lang=\"ts\">\nimport { fetchUser } from '../api/users'\nconst user = fetchUser('1')\n</script>\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("db/schema.sql"),
+            "CREATE TABLE users (id TEXT PRIMARY KEY);\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("api/repo.py"),
+            "def load_user(db, id):\n    return db.execute(\"SELECT * FROM users WHERE id = ?\", id)\n",
+        )
+        .unwrap();
+
+        let graph = index_directory(dir.path(), IndexOptions::default())
+            .unwrap()
+            .graph;
+        let callees = |name: &str| -> Vec<String> {
+            let node = graph.find_by_name(name)[0];
+            let idx = graph.get_index(&node.id).unwrap();
+            graph
+                .get_callees(idx)
+                .iter()
+                .map(|n| n.qualified_name.clone())
+                .collect()
+        };
+
+        assert_eq!(callees("UserCard"), vec!["fetchUser".to_string()]);
+        assert_eq!(callees("load_user"), vec!["sql:users".to_string()]);
+    }
+
+    #[test]
+    fn test_index_records_parse_diagnostics() {
+        let dir = tempdir().unwrap();
+        let cache = dir.path().join(".arbor/cache");
+        fs::write(dir.path().join("ok.rs"), "pub fn ok() {}\n").unwrap();
+        fs::write(
+            dir.path().join("broken.rs"),
+            "pub fn fine() {}\n\npub fn broken( {\n    let x = ;\n}\n",
+        )
+        .unwrap();
+
+        let options = IndexOptions {
+            cache_path: Some(cache),
+            ..IndexOptions::default()
+        };
+        let check = |graph: &ArborGraph| {
+            let diagnostics = graph.all_file_diagnostics();
+            let (_, broken) = diagnostics
+                .iter()
+                .find(|(path, _)| path.ends_with("broken.rs"))
+                .unwrap();
+            assert!(broken.error_count > 0);
+            let (_, ok) = diagnostics
+                .iter()
+                .find(|(path, _)| path.ends_with("ok.rs"))
+                .unwrap();
+            assert!(!ok.is_degraded());
+        };
+
+        let first = index_directory(dir.path(), options.clone()).unwrap();
+        check(&first.graph);
+
+        // Diagnostics come back from the cache on the second run.
+        let second = index_directory(dir.path(), options).unwrap();
+        assert_eq!(second.cache_hits, 2);
+        check(&second.graph);
+    }
+
     /// Helper to create a directory symlink cross-platform.
     /// Returns None if symlink creation fails (e.g., no privileges on Windows).
     fn create_dir_symlink(original: &std::path::Path, link: &std::path::Path) -> Option<()> {
@@ -37,9 +37,7 @@ impl FileWatcher {
                 Ok(event) => {
                     for path in event.paths {
                         // Only care about supported source files
-                        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-                        if !arbor_core::languages::is_supported(ext) {
+                        if !arbor_core::languages::is_supported_file(&path) {
                             continue;
                         }
 